@@ -0,0 +1,172 @@
+//! Build script for admin crate.
+//!
+//! Generates the Shopify `CountryCode` enum and its ISO 3166-1 alpha-2 lookup
+//! table from `data/shopify_country_codes.json`, and the `SHOPIFY_API_VERSION`
+//! constant the GraphQL queries are written against.
+//!
+//! With `FETCH_SHOPIFY_SCHEMA=1`, also checks `graphql/admin/schema.json`
+//! against Shopify when it is more than 30 days old. Only
+//! `cargo xtask update-schema` changes the checked-in schema.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+// Shared with `cargo xtask update-schema`; only the Admin API source is used here
+#[allow(dead_code)]
+#[path = "../xtask/src/schema.rs"]
+mod schema;
+
+/// Admin API version used when `SHOPIFY_API_VERSION` is not set at build time.
+///
+/// Keep in step with `graphql/admin/schema.json`.
+const DEFAULT_API_VERSION: &str = "2026-01";
+
+fn main() {
+    generate_country_codes();
+    generate_api_version();
+    fetch_schema();
+}
+
+/// Check the Admin API schema against Shopify if `FETCH_SHOPIFY_SCHEMA=1` and it is stale.
+///
+/// CI leaves the variable unset and builds against the checked-in schema.
+/// The current schema is downloaded to `OUT_DIR` and the build fails if it
+/// differs from the checked-in one, which the `graphql_client` derives read;
+/// the build never rewrites it. Fails the build if `SHOPIFY_STORE` or
+/// `SHOPIFY_ADMIN_OAUTH_TOKEN` is not set.
+fn fetch_schema() {
+    println!("cargo:rerun-if-env-changed={}", schema::FETCH_ENV);
+    if env::var(schema::FETCH_ENV).as_deref() != Ok("1") {
+        return;
+    }
+    println!("cargo:rerun-if-env-changed=SHOPIFY_STORE");
+    println!("cargo:rerun-if-env-changed={}", schema::ADMIN.token_env);
+
+    let manifest_dir =
+        env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR must be set by Cargo");
+    let path = Path::new(&manifest_dir).join("graphql/admin/schema.json");
+    println!("cargo:rerun-if-changed={}", path.display());
+    if schema::is_fresh(&path, schema::MAX_AGE) {
+        return;
+    }
+
+    let version = env::var("SHOPIFY_API_VERSION")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| DEFAULT_API_VERSION.to_string());
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR must be set by Cargo");
+    let fetched = Path::new(&out_dir).join("schema.json");
+    if let Err(e) = schema::ADMIN.update(&version, &fetched) {
+        panic!(
+            "{}=1 but the schema could not be checked: {e}",
+            schema::FETCH_ENV
+        );
+    }
+
+    let current = fs::read(&fetched).expect("Failed to read the downloaded schema");
+    assert!(
+        fs::read(&path).is_ok_and(|checked_in| checked_in == current),
+        "{} is out of date with Admin API {version}. Run `cargo xtask update-schema admin` \
+         to update it, or unset {} to build against it as is.",
+        path.display(),
+        schema::FETCH_ENV
+    );
+}
+
+/// Generate `api_version.rs` in `OUT_DIR`.
+///
+/// Reads `SHOPIFY_API_VERSION` from the build environment so the version the
+/// queries are compiled against is fixed in the binary. Fails the build if
+/// the version is not in `YYYY-MM` format.
+fn generate_api_version() {
+    println!("cargo:rerun-if-env-changed=SHOPIFY_API_VERSION");
+
+    let version = env::var("SHOPIFY_API_VERSION")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| DEFAULT_API_VERSION.to_string());
+
+    let valid = version.split_once('-').is_some_and(|(year, month)| {
+        year.len() == 4
+            && year.bytes().all(|b| b.is_ascii_digit())
+            && month.len() == 2
+            && month
+                .parse::<u8>()
+                .is_ok_and(|month| (1..=12).contains(&month))
+    });
+    assert!(
+        valid,
+        "SHOPIFY_API_VERSION must be in YYYY-MM format, got {version:?}"
+    );
+
+    let code = format!(
+        "/// Admin API version the GraphQL queries are compiled against.\n\
+         ///\n\
+         /// Set from `SHOPIFY_API_VERSION` at build time.\n\
+         pub const SHOPIFY_API_VERSION: &str = \"{version}\";\n"
+    );
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR must be set by Cargo");
+    fs::write(Path::new(&out_dir).join("api_version.rs"), code)
+        .expect("Failed to write api_version.rs");
+}
+
+/// Generate `country_code.rs` in `OUT_DIR`.
+///
+/// The JSON file lists the values of Shopify's `CountryCode` GraphQL enum.
+/// Refresh it from the Admin API schema when Shopify adds countries.
+fn generate_country_codes() {
+    let manifest_dir =
+        env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR must be set by Cargo");
+    let json_path = Path::new(&manifest_dir).join("data/shopify_country_codes.json");
+
+    println!("cargo:rerun-if-changed={}", json_path.display());
+
+    let content =
+        fs::read_to_string(&json_path).expect("Failed to read shopify_country_codes.json");
+    let codes: Vec<String> =
+        serde_json::from_str(&content).expect("shopify_country_codes.json must be a string array");
+
+    let mut code = String::new();
+    code.push_str("/// Shopify `CountryCode` enum (ISO 3166-1 alpha-2).\n");
+    code.push_str("///\n");
+    code.push_str("/// Generated from `data/shopify_country_codes.json`. `Other` holds values\n");
+    code.push_str("/// Shopify returns that are not in the checked-in list.\n");
+    code.push_str("#[allow(clippy::upper_case_acronyms)]\n");
+    code.push_str("#[derive(Debug, Clone, PartialEq, Eq, Hash)]\n");
+    code.push_str("pub enum CountryCode {\n");
+    for c in &codes {
+        let _ = writeln!(code, "    {c},");
+    }
+    code.push_str("    Other(String),\n}\n\n");
+
+    code.push_str("impl CountryCode {\n");
+    code.push_str("    /// The ISO 3166-1 alpha-2 code.\n");
+    code.push_str("    #[allow(clippy::too_many_lines)]\n");
+    code.push_str("    #[must_use]\n");
+    code.push_str("    pub fn as_str(&self) -> &str {\n");
+    code.push_str("        match self {\n");
+    for c in &codes {
+        let _ = writeln!(code, "            Self::{c} => \"{c}\",");
+    }
+    code.push_str("            Self::Other(other) => other,\n");
+    code.push_str("        }\n    }\n}\n\n");
+
+    let mut map = phf_codegen::Map::new();
+    for c in &codes {
+        map.entry(c.as_str(), format!("CountryCode::{c}"));
+    }
+    let _ = writeln!(
+        code,
+        "/// Lookup table from ISO 3166-1 alpha-2 code to [`CountryCode`].\n\
+         #[allow(clippy::unreadable_literal)]\n\
+         static ISO_2: phf::Map<&'static str, CountryCode> = {};",
+        map.build()
+    );
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR must be set by Cargo");
+    fs::write(Path::new(&out_dir).join("country_code.rs"), code)
+        .expect("Failed to write country_code.rs");
+}
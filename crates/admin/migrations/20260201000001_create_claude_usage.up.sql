@@ -0,0 +1,20 @@
+-- Claude API token usage per admin user, for cost tracking and budgets
+-- One row per Claude API response
+
+SET search_path TO admin, public;
+
+CREATE TABLE admin.claude_usage (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    admin_user_email CITEXT NOT NULL,
+    model TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL,
+    output_tokens INTEGER NOT NULL,
+    -- Estimated from the configured per-model token prices at request time
+    estimated_cost_usd DECIMAL(10,6) NOT NULL,
+    -- Chat session the request belonged to (kept when the session is deleted)
+    conversation_id INTEGER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc')
+);
+
+CREATE INDEX idx_claude_usage_email_created_at ON admin.claude_usage(admin_user_email, created_at DESC);
+CREATE INDEX idx_claude_usage_created_at ON admin.claude_usage(created_at DESC);
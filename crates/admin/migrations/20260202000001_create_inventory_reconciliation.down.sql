@@ -0,0 +1,4 @@
+SET search_path TO admin, public;
+
+DROP TABLE IF EXISTS admin.reconciliation_counts;
+DROP TABLE IF EXISTS admin.reconciliation_session;
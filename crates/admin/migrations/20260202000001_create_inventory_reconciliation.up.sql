@@ -0,0 +1,32 @@
+-- Physical inventory counts, reconciled against Shopify quantities
+-- A session snapshots the on-hand quantity of every SKU at one location;
+-- staff record counted quantities and apply the discrepancies to Shopify
+
+SET search_path TO admin, public;
+
+CREATE TABLE admin.reconciliation_session (
+    session_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    location_id VARCHAR(255) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'in_progress'
+        CHECK (status IN ('in_progress', 'completed')),
+    started_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc'),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_reconciliation_session_status ON admin.reconciliation_session(status, started_at DESC);
+
+CREATE TABLE admin.reconciliation_counts (
+    session_id UUID NOT NULL REFERENCES admin.reconciliation_session(session_id) ON DELETE CASCADE,
+    sku VARCHAR(255) NOT NULL,
+    inventory_item_id VARCHAR(255) NOT NULL,
+    -- On-hand quantity in Shopify when the session started
+    system_quantity INTEGER NOT NULL,
+    -- NULL until the SKU has been counted
+    counted_quantity INTEGER CHECK (counted_quantity >= 0),
+    -- counted_quantity - system_quantity
+    delta INTEGER,
+    counted_at TIMESTAMPTZ,
+    -- Set once the counted quantity has been written to Shopify
+    applied_at TIMESTAMPTZ,
+    PRIMARY KEY (session_id, sku)
+);
@@ -0,0 +1,3 @@
+SET search_path TO admin, public;
+
+DROP TABLE IF EXISTS admin.shiphero_order_mappings;
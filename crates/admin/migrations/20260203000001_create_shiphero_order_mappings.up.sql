@@ -0,0 +1,13 @@
+-- Maps ShipHero order IDs to Shopify order IDs for fulfillment webhooks
+-- Rows are added the first time a ShipHero order is looked up
+
+SET search_path TO admin, public;
+
+CREATE TABLE admin.shiphero_order_mappings (
+    shiphero_order_id VARCHAR(255) PRIMARY KEY,
+    -- Shopify order GID (gid://shopify/Order/...)
+    shopify_order_id VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc')
+);
+
+CREATE INDEX idx_shiphero_order_mappings_shopify_order ON admin.shiphero_order_mappings(shopify_order_id);
@@ -0,0 +1,6 @@
+-- Revert read-only role creation
+
+ALTER DEFAULT PRIVILEGES IN SCHEMA admin REVOKE SELECT ON TABLES FROM np_admin_reader;
+REVOKE SELECT ON ALL TABLES IN SCHEMA admin FROM np_admin_reader;
+REVOKE USAGE ON SCHEMA admin FROM np_admin_reader;
+DROP ROLE IF EXISTS np_admin_reader;
@@ -0,0 +1,20 @@
+-- Per-admin, per-page preferences such as the default order export columns
+-- Keyed by email so a preference follows the admin, not a session
+
+SET search_path TO admin, public;
+
+CREATE TABLE admin.user_preferences (
+    admin_email CITEXT NOT NULL REFERENCES admin.admin_user(email) ON DELETE CASCADE ON UPDATE CASCADE,
+    -- Page the preference applies to (e.g., 'orders')
+    page VARCHAR(100) NOT NULL,
+    -- Column keys selected for CSV export, in display order
+    export_fields TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc'),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc'),
+    PRIMARY KEY (admin_email, page)
+);
+
+CREATE TRIGGER user_preferences_updated_at
+    BEFORE UPDATE ON admin.user_preferences
+    FOR EACH ROW
+    EXECUTE FUNCTION admin.update_updated_at_column();
@@ -0,0 +1,14 @@
+-- Gift card expiry reminders already sent, so the daily job never sends twice
+-- Keyed by expiry date as well, so a card whose expiry is extended gets a new reminder
+
+SET search_path TO admin, public;
+
+CREATE TABLE admin.gift_card_reminders_sent (
+    -- Shopify gift card GID
+    gift_card_id VARCHAR(255) NOT NULL,
+    -- Expiry date the reminder was for
+    expires_on DATE NOT NULL,
+    customer_email CITEXT NOT NULL,
+    sent_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc'),
+    PRIMARY KEY (gift_card_id, expires_on)
+);
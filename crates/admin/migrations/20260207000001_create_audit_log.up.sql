@@ -0,0 +1,30 @@
+-- Audit trail of admin actions, for compliance and security investigations
+-- One row per action; rows are never updated or deleted by the application
+
+SET search_path TO admin, public;
+
+CREATE TABLE admin.audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    -- Acting admin (kept when the admin user is deleted)
+    admin_user_id INTEGER REFERENCES admin.admin_user(id) ON DELETE SET NULL,
+    admin_user_email CITEXT NOT NULL,
+    -- e.g. ORDER_CANCEL, DISCOUNT_CREATE
+    action VARCHAR(100) NOT NULL,
+    -- e.g. Order, Discount
+    resource_type VARCHAR(100) NOT NULL,
+    -- Shopify GID or local ID of the affected resource
+    resource_id VARCHAR(255),
+    ip_address INET,
+    user_agent TEXT,
+    -- Action-specific context (previous values, reason, ...)
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc')
+);
+
+-- Every `np-cli admin audit-log` filter is combined with a date range and
+-- ordered newest first
+CREATE INDEX idx_audit_log_created_at ON admin.audit_log(created_at DESC);
+CREATE INDEX idx_audit_log_email_created_at ON admin.audit_log(admin_user_email, created_at DESC);
+CREATE INDEX idx_audit_log_action_created_at ON admin.audit_log(action, created_at DESC);
+CREATE INDEX idx_audit_log_resource_created_at ON admin.audit_log(resource_type, resource_id, created_at DESC);
+CREATE INDEX idx_audit_log_ip_created_at ON admin.audit_log(ip_address, created_at DESC) WHERE ip_address IS NOT NULL;
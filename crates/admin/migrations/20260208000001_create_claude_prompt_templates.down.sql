@@ -0,0 +1,6 @@
+SET search_path TO admin, public;
+
+ALTER TABLE admin.claude_usage DROP COLUMN IF EXISTS prompt_version;
+ALTER TABLE admin.claude_usage DROP COLUMN IF EXISTS prompt_name;
+
+DROP TABLE IF EXISTS admin.claude_prompt_template;
@@ -0,0 +1,18 @@
+-- Claude system prompt templates seeded with `np-cli seed prompts`
+-- The highest version of each name overrides the bundled data/prompts.toml at startup
+
+SET search_path TO admin, public;
+
+CREATE TABLE admin.claude_prompt_template (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    system_prompt TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc'),
+    UNIQUE (name, version)
+);
+
+-- Prompt template each Claude request was sent with (NULL for requests before templates)
+ALTER TABLE admin.claude_usage ADD COLUMN prompt_name TEXT;
+ALTER TABLE admin.claude_usage ADD COLUMN prompt_version INTEGER;
@@ -7,7 +7,12 @@
 //!
 //! - `ClaudeClient` - HTTP client for the Claude Messages API
 //! - `all_shopify_tools()` - All 111 Shopify tool definitions
-//! - `ToolExecutor` - Executes tools by calling the Shopify Admin API
+//! - `ToolExecutor` - Executes tools by calling the Shopify Admin API, running
+//!   the tool calls of one assistant message concurrently
+//! - `ToolConfirmations` - One-time tokens for tools that confirm risky input
+//!   with the admin themselves
+//! - `ClaudeUsageTracker` - Records token usage and estimated cost per admin user
+//! - `PromptRegistry` - Versioned system prompt templates, chosen by name
 //!
 //! # Example
 //!
@@ -41,13 +46,18 @@
 
 mod client;
 mod error;
+pub mod prompts;
 pub mod tools;
 pub mod types;
+mod usage;
 
 pub use client::ClaudeClient;
 pub use error::ClaudeError;
+pub use prompts::{PromptError, PromptRegistry, PromptTemplate, parse_prompts};
 pub use tools::{
-    ToolExecutor, ToolResult, all_shopify_tools, filter_tools_by_names, get_tool_by_name,
-    get_tool_domain, get_tools_by_domain, requires_confirmation,
+    MAX_CONCURRENT_TOOLS, ToolConfirmations, ToolExecutor, ToolResult, all_shopify_tools,
+    filter_tools_by_names, get_tool_by_name, get_tool_domain, get_tools_by_domain,
+    requires_confirmation,
 };
 pub use types::*;
+pub use usage::{BUDGET_ALERT_PERCENT, ClaudeUsageTracker, start_of_month};
@@ -0,0 +1,248 @@
+//! Versioned system prompt templates for the Claude chat assistant.
+//!
+//! Templates are bundled from `data/prompts.toml` and can be overridden
+//! without a deploy by seeding a higher version into the database with
+//! `np-cli seed prompts --file prompts.toml`. The registry is loaded once
+//! at startup; the active template is chosen with `CLAUDE_PROMPT_TEMPLATE`.
+//!
+//! ## TOML Format
+//!
+//! ```toml
+//! [[prompt]]
+//! name = "default"
+//! version = 2
+//! description = "Shorter answers"
+//! system_prompt = """
+//! You are a helpful assistant. Today is {{ today_date }}.
+//! """
+//! ```
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use serde::Deserialize;
+use sqlx::PgPool;
+use thiserror::Error;
+
+use crate::db::{PromptTemplateRepository, RepositoryError};
+pub use crate::models::PromptTemplate;
+
+/// Prompt templates bundled with the binary.
+const BUNDLED_PROMPTS: &str = include_str!("../../data/prompts.toml");
+
+/// Errors that can occur while loading prompt templates.
+#[derive(Debug, Error)]
+pub enum PromptError {
+    /// The TOML could not be parsed.
+    #[error("invalid prompts TOML: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    /// A template is missing required content.
+    #[error("invalid prompt template: {0}")]
+    Invalid(String),
+
+    /// No template has the configured name.
+    #[error("prompt template not found: {0}")]
+    NotFound(String),
+
+    /// Seeded templates could not be loaded.
+    #[error("failed to load prompt templates: {0}")]
+    Repository(#[from] RepositoryError),
+}
+
+/// Contents of a prompts TOML file.
+#[derive(Debug, Deserialize)]
+struct PromptsFile {
+    #[serde(default, rename = "prompt")]
+    prompts: Vec<PromptTemplate>,
+}
+
+/// Parse and validate prompt templates from TOML.
+///
+/// # Errors
+///
+/// Returns an error if the TOML is malformed, a template has an empty name
+/// or prompt or a version below 1, or a name and version appear twice.
+pub fn parse_prompts(content: &str) -> Result<Vec<PromptTemplate>, PromptError> {
+    let file: PromptsFile = toml::from_str(content)?;
+
+    for (i, template) in file.prompts.iter().enumerate() {
+        if template.name.trim().is_empty() {
+            return Err(PromptError::Invalid(format!(
+                "prompt #{} has no name",
+                i + 1
+            )));
+        }
+        if template.version < 1 {
+            return Err(PromptError::Invalid(format!(
+                "{} has version {}; versions start at 1",
+                template.name, template.version
+            )));
+        }
+        if template.system_prompt.trim().is_empty() {
+            return Err(PromptError::Invalid(format!(
+                "{} v{} has an empty system_prompt",
+                template.name, template.version
+            )));
+        }
+        if file
+            .prompts
+            .iter()
+            .take(i)
+            .any(|t| t.name == template.name && t.version == template.version)
+        {
+            return Err(PromptError::Invalid(format!(
+                "{} v{} is defined twice",
+                template.name, template.version
+            )));
+        }
+    }
+
+    Ok(file.prompts)
+}
+
+impl PromptTemplate {
+    /// The system prompt with each `{{ key }}` replaced by its value.
+    ///
+    /// Placeholders without a value are left as they are.
+    #[must_use]
+    pub fn render(&self, variables: &[(&str, &str)]) -> String {
+        variables
+            .iter()
+            .fold(self.system_prompt.clone(), |prompt, (key, value)| {
+                prompt.replace(&format!("{{{{ {key} }}}}"), value)
+            })
+    }
+}
+
+/// The latest version of every known prompt template, by name.
+#[derive(Debug, Clone, Default)]
+pub struct PromptRegistry {
+    templates: HashMap<String, PromptTemplate>,
+}
+
+impl PromptRegistry {
+    /// Registry of the templates bundled in `data/prompts.toml`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundled TOML is invalid.
+    pub fn bundled() -> Result<Self, PromptError> {
+        Ok(Self::from_templates(parse_prompts(BUNDLED_PROMPTS)?))
+    }
+
+    /// Registry of the bundled templates, overridden by any seeded template
+    /// with a higher version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundled TOML is invalid or the database
+    /// can't be queried.
+    pub async fn load(pool: &PgPool) -> Result<Self, PromptError> {
+        let mut registry = Self::bundled()?;
+        registry.extend(PromptTemplateRepository::new(pool).latest().await?);
+        Ok(registry)
+    }
+
+    /// Registry of `templates`, keeping the highest version of each name.
+    #[must_use]
+    pub fn from_templates(templates: impl IntoIterator<Item = PromptTemplate>) -> Self {
+        let mut registry = Self::default();
+        registry.extend(templates);
+        registry
+    }
+
+    /// Add `templates`, replacing a known template only with a higher version.
+    fn extend(&mut self, templates: impl IntoIterator<Item = PromptTemplate>) {
+        for template in templates {
+            match self.templates.entry(template.name.clone()) {
+                Entry::Occupied(mut existing) => {
+                    if template.version > existing.get().version {
+                        existing.insert(template);
+                    }
+                }
+                Entry::Vacant(slot) => {
+                    slot.insert(template);
+                }
+            }
+        }
+    }
+
+    /// The template named `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&PromptTemplate> {
+        self.templates.get(name)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn template(name: &str, version: i32, system_prompt: &str) -> PromptTemplate {
+        PromptTemplate {
+            name: name.to_string(),
+            version,
+            system_prompt: system_prompt.to_string(),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_bundled_prompts_include_default() {
+        let registry = PromptRegistry::bundled().expect("bundled prompts are valid");
+        let default = registry.get("default").expect("default template");
+        assert!(default.system_prompt.contains("{{ today_date }}"));
+    }
+
+    #[test]
+    fn test_registry_keeps_highest_version() {
+        let registry = PromptRegistry::from_templates([
+            template("default", 2, "v2"),
+            template("default", 3, "v3"),
+            template("default", 1, "v1"),
+            template("terse", 1, "terse"),
+        ]);
+
+        assert_eq!(registry.get("default").unwrap().version, 3);
+        assert_eq!(registry.get("terse").unwrap().system_prompt, "terse");
+        assert!(registry.get("missing").is_none());
+    }
+
+    #[test]
+    fn test_render_replaces_placeholders() {
+        let prompt = template("default", 1, "Today is {{ today_date }}. {{ unknown }}");
+        assert_eq!(
+            prompt.render(&[("today_date", "2026-02-08")]),
+            "Today is 2026-02-08. {{ unknown }}"
+        );
+    }
+
+    #[test]
+    fn test_parse_prompts_rejects_duplicates() {
+        let toml = r#"
+            [[prompt]]
+            name = "default"
+            version = 1
+            system_prompt = "a"
+
+            [[prompt]]
+            name = "default"
+            version = 1
+            system_prompt = "b"
+        "#;
+        assert!(matches!(parse_prompts(toml), Err(PromptError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_parse_prompts_rejects_zero_version() {
+        let toml = r#"
+            [[prompt]]
+            name = "default"
+            version = 0
+            system_prompt = "a"
+        "#;
+        assert!(matches!(parse_prompts(toml), Err(PromptError::Invalid(_))));
+    }
+}
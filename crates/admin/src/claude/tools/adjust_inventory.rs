@@ -0,0 +1,430 @@
+//! Inventory adjustment tool for Claude.
+//!
+//! Lets warehouse operators correct stock from the chat ("we're 3 short on
+//! NP-CLN-100 at the warehouse") without looking up inventory item IDs: the
+//! SKU or variant is resolved to its inventory item, the `available`
+//! quantity is adjusted, and the new quantity is read back. Adjustments over
+//! [`AdjustInventoryTool::MAX_UNCONFIRMED_DELTA`] units are only made with a
+//! confirmation token from an earlier turn (see [`super::ToolConfirmations`]).
+
+use naked_pineapple_core::{InventoryItemId, LocationId, VariantId};
+use serde_json::json;
+
+use crate::claude::error::ClaudeError;
+use crate::claude::types::Tool;
+use crate::shopify::AdminClient;
+
+use super::{TurnConfirmations, required_string};
+
+/// Adjust a variant's available inventory at a location by a delta.
+pub struct AdjustInventoryTool;
+
+/// Validated input for [`AdjustInventoryTool`].
+#[derive(Debug, Clone)]
+pub struct AdjustInventoryInput {
+    /// SKU, or variant ID (`gid://shopify/ProductVariant/...`).
+    pub sku_or_variant_id: String,
+    /// Location ID (`gid://shopify/Location/...`).
+    pub location_id: LocationId,
+    /// Change to the available quantity.
+    pub delta: i64,
+    /// Shopify inventory adjustment reason.
+    pub reason: String,
+    /// Token from an earlier call, confirming a large delta.
+    pub confirmation_token: Option<String>,
+}
+
+impl AdjustInventoryInput {
+    /// Whether the admin must confirm the adjustment before it is made,
+    /// because it is larger than [`AdjustInventoryTool::MAX_UNCONFIRMED_DELTA`].
+    #[must_use]
+    pub fn needs_confirmation(&self) -> bool {
+        let max = AdjustInventoryTool::MAX_UNCONFIRMED_DELTA;
+        !(-max..=max).contains(&self.delta)
+    }
+
+    /// What a confirmation token for this adjustment confirms.
+    fn confirmation_subject(&self) -> String {
+        format!(
+            "{} at {} by {}",
+            self.sku_or_variant_id, self.location_id, self.delta
+        )
+    }
+}
+
+impl AdjustInventoryTool {
+    /// Tool name exposed to Claude.
+    pub const NAME: &'static str = "adjust_inventory";
+
+    /// Largest adjustment, in either direction, made without confirmation.
+    pub const MAX_UNCONFIRMED_DELTA: i64 = 10_000;
+
+    /// Adjustment reasons accepted by Shopify's `inventoryAdjustQuantities`.
+    pub const REASONS: [&'static str; 10] = [
+        "correction",
+        "cycle_count_available",
+        "damaged",
+        "other",
+        "promotion",
+        "quality_control",
+        "received",
+        "restock",
+        "safety_stock",
+        "shrinkage",
+    ];
+
+    /// Tool definition for Claude.
+    #[must_use]
+    pub fn definition() -> Tool {
+        Tool {
+            name: Self::NAME.to_string(),
+            description: "Adjust a variant's available inventory at one location by a delta \
+                and return the new available quantity. The variant can be given by SKU. \
+                USE THIS to correct stock discrepancies, like 'we have 3 fewer NP-CLN-100 \
+                at the warehouse' or 'add 24 units of SKU NP-OIL-30, we received a delivery'. \
+                Adjustments larger than 10000 units are not made; the response has a \
+                confirmation_token. Ask the admin to confirm, and once they reply, call this \
+                tool again with the token."
+                .to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "sku_or_variant_id": {
+                        "type": "string",
+                        "minLength": 1,
+                        "description": "Variant SKU (e.g. 'NP-CLN-100') or variant ID \
+                            (gid://shopify/ProductVariant/...)"
+                    },
+                    "location_id": {
+                        "type": "string",
+                        "minLength": 1,
+                        "description": "Location ID (gid://shopify/Location/...), from \
+                            get_locations_low_level_shopify"
+                    },
+                    "delta": {
+                        "type": "integer",
+                        "description": "Change to the available quantity (positive to add, \
+                            negative to remove, never 0)"
+                    },
+                    "reason": {
+                        "type": "string",
+                        "enum": Self::REASONS,
+                        "description": "Why the quantity changed (default: correction)"
+                    },
+                    "confirmation_token": {
+                        "type": "string",
+                        "description": "confirmation_token from an earlier response, sent \
+                            only after the admin has replied confirming an adjustment larger \
+                            than 10000 units"
+                    }
+                },
+                "required": ["sku_or_variant_id", "location_id", "delta"]
+            }),
+            domain: Some("inventory".to_string()),
+            requires_confirmation: false,
+        }
+    }
+
+    /// Adjust the inventory and report the new available quantity, or ask
+    /// for confirmation of a large delta.
+    ///
+    /// A delta over [`Self::MAX_UNCONFIRMED_DELTA`] is only applied when
+    /// `input` carries a token issued by an earlier call in an earlier turn
+    /// of the same chat session. Otherwise a new token is issued and returned
+    /// with a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is invalid, the delta needs confirmation
+    /// outside a chat session, the SKU or variant can't be resolved to
+    /// exactly one inventory item, or a Shopify request fails.
+    pub async fn execute(
+        shopify: &AdminClient,
+        confirmations: Option<&TurnConfirmations>,
+        input: &serde_json::Value,
+    ) -> Result<String, ClaudeError> {
+        let input = Self::validate_input(input)?;
+
+        if input.needs_confirmation() {
+            let confirmations = confirmations.ok_or_else(|| {
+                ClaudeError::ToolExecution(format!(
+                    "A delta larger than {} units can only be confirmed in the admin chat",
+                    Self::MAX_UNCONFIRMED_DELTA
+                ))
+            })?;
+            let subject = input.confirmation_subject();
+            let confirmed = match &input.confirmation_token {
+                Some(token) => confirmations.redeem(Self::NAME, &subject, token).await,
+                None => false,
+            };
+            if !confirmed {
+                let token = confirmations.issue(Self::NAME, subject).await;
+                return Ok(json!({
+                    "success": false,
+                    "requires_confirmation": true,
+                    "confirmation_token": token,
+                    "warning": format!(
+                        "A delta of {} is larger than {} units. Ask the admin to confirm this \
+                         adjustment. Once they reply, call {} again with the same input and \
+                         this confirmation_token. The token is not accepted before the admin \
+                         replies.",
+                        input.delta,
+                        Self::MAX_UNCONFIRMED_DELTA,
+                        Self::NAME,
+                    ),
+                })
+                .to_string());
+            }
+        }
+
+        let (inventory_item_id, sku) =
+            resolve_inventory_item(shopify, &input.sku_or_variant_id).await?;
+
+        shopify
+            .adjust_inventory(
+                &inventory_item_id,
+                &input.location_id,
+                input.delta,
+                Some(&input.reason),
+            )
+            .await
+            .map_err(|e| ClaudeError::ToolExecution(format!("Failed to adjust inventory: {e}")))?;
+
+        let item = shopify
+            .get_inventory_item(&inventory_item_id)
+            .await
+            .map_err(|e| {
+                ClaudeError::ToolExecution(format!(
+                    "Inventory was adjusted, but reading it back failed: {e}"
+                ))
+            })?;
+        let level = item
+            .inventory_levels
+            .iter()
+            .find(|level| level.location_id == input.location_id);
+
+        Ok(json!({
+            "success": true,
+            "sku": sku,
+            "inventory_item_id": inventory_item_id,
+            "location_id": input.location_id,
+            "location_name": level.and_then(|level| level.location_name.as_deref()),
+            "delta": input.delta,
+            "reason": input.reason,
+            "available": level.map(|level| level.available),
+        })
+        .to_string())
+    }
+
+    /// Validate the tool input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required field is missing or blank, the delta
+    /// is zero, or the reason is unknown. A delta over
+    /// [`Self::MAX_UNCONFIRMED_DELTA`] is accepted here; [`Self::execute`]
+    /// checks its confirmation.
+    pub fn validate_input(input: &serde_json::Value) -> Result<AdjustInventoryInput, ClaudeError> {
+        let sku_or_variant_id = required_string(input, "sku_or_variant_id")?;
+        let location_id = LocationId::parse_admin_id(&required_string(input, "location_id")?)
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
+
+        let delta = match input.get("delta") {
+            Some(delta) => delta.as_i64().ok_or_else(|| {
+                ClaudeError::ToolExecution("delta must be an integer".to_string())
+            })?,
+            None => {
+                return Err(ClaudeError::ToolExecution(
+                    "Missing required field: delta".to_string(),
+                ));
+            }
+        };
+        if delta == 0 {
+            return Err(ClaudeError::ToolExecution(
+                "delta must not be 0".to_string(),
+            ));
+        }
+
+        let reason = match input.get("reason") {
+            None | Some(serde_json::Value::Null) => "correction",
+            Some(serde_json::Value::String(reason)) => Self::REASONS
+                .into_iter()
+                .find(|known| *known == reason.as_str())
+                .ok_or_else(|| {
+                    ClaudeError::ToolExecution(format!(
+                        "Invalid reason: {reason} (expected one of: {})",
+                        Self::REASONS.join(", ")
+                    ))
+                })?,
+            Some(_) => {
+                return Err(ClaudeError::ToolExecution(
+                    "reason must be a string".to_string(),
+                ));
+            }
+        };
+
+        Ok(AdjustInventoryInput {
+            sku_or_variant_id,
+            location_id,
+            delta,
+            reason: reason.to_string(),
+            confirmation_token: input
+                .get("confirmation_token")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string),
+        })
+    }
+}
+
+/// Resolve a SKU or variant ID to its inventory item ID and SKU.
+async fn resolve_inventory_item(
+    shopify: &AdminClient,
+    sku_or_variant_id: &str,
+) -> Result<(InventoryItemId, Option<String>), ClaudeError> {
+    if let Ok(variant_id) = sku_or_variant_id.parse::<VariantId>() {
+        let variant = shopify
+            .get_variant(&variant_id)
+            .await
+            .map_err(|e| ClaudeError::ToolExecution(format!("Failed to look up variant: {e}")))?
+            .ok_or_else(|| {
+                ClaudeError::ToolExecution(format!("Variant {sku_or_variant_id} not found"))
+            })?;
+        return Ok((variant.inventory_item_id, variant.sku));
+    }
+
+    // The search can match more than the exact SKU, so filter the results
+    let query = format!("sku:\"{}\"", sku_or_variant_id.replace('"', "\\\""));
+    let items = shopify
+        .get_inventory_items(10, None, Some(query))
+        .await
+        .map_err(|e| ClaudeError::ToolExecution(format!("Failed to look up SKU: {e}")))?
+        .items;
+    let mut matches = items.into_iter().filter(|item| {
+        item.sku
+            .as_deref()
+            .is_some_and(|sku| sku.eq_ignore_ascii_case(sku_or_variant_id))
+    });
+
+    match (matches.next(), matches.next()) {
+        (Some(item), None) => Ok((item.id, item.sku)),
+        (None, _) => Err(ClaudeError::ToolExecution(format!(
+            "No inventory item has SKU {sku_or_variant_id}"
+        ))),
+        (Some(_), Some(_)) => Err(ClaudeError::ToolExecution(format!(
+            "More than one inventory item has SKU {sku_or_variant_id}; use a variant ID instead"
+        ))),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use secrecy::SecretString;
+    use serde_json::Value;
+
+    use naked_pineapple_core::ChatSessionId;
+
+    use super::*;
+    use crate::claude::ToolConfirmations;
+    use crate::config::ShopifyAdminConfig;
+
+    /// A client that is never sent a request.
+    fn client() -> AdminClient {
+        AdminClient::new(&ShopifyAdminConfig {
+            store: "test.myshopify.com".to_string(),
+            api_version: "2026-01".to_string(),
+            client_id: "test_client_id".to_string(),
+            client_secret: SecretString::from("test_client_secret"),
+        })
+        .unwrap()
+    }
+
+    fn input(delta: i64) -> Value {
+        json!({"sku_or_variant_id": "NP-CLN-100", "location_id": "1", "delta": delta})
+    }
+
+    fn validation_error(input: &Value) -> String {
+        match AdjustInventoryTool::validate_input(input) {
+            Err(ClaudeError::ToolExecution(message)) => message,
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_largest_unconfirmed_delta() {
+        for delta in [10_000, -10_000] {
+            let input = AdjustInventoryTool::validate_input(&input(delta)).unwrap();
+            assert_eq!(input.delta, delta);
+            assert_eq!(input.location_id.as_str(), "gid://shopify/Location/1");
+            assert_eq!(input.reason, "correction");
+            assert!(!input.needs_confirmation());
+        }
+    }
+
+    #[test]
+    fn test_validate_large_delta_needs_confirmation() {
+        for delta in [10_001, -10_001] {
+            let unconfirmed = AdjustInventoryTool::validate_input(&input(delta)).unwrap();
+            assert!(unconfirmed.needs_confirmation());
+            assert_eq!(unconfirmed.confirmation_token, None);
+
+            let mut with_token = input(delta);
+            with_token
+                .as_object_mut()
+                .unwrap()
+                .insert("confirmation_token".to_string(), json!("token"));
+            let confirmed = AdjustInventoryTool::validate_input(&with_token).unwrap();
+            assert!(confirmed.needs_confirmation());
+            assert_eq!(confirmed.confirmation_token.as_deref(), Some("token"));
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_delta() {
+        assert_eq!(validation_error(&input(0)), "delta must not be 0");
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_reason() {
+        let mut input = input(5);
+        input
+            .as_object_mut()
+            .unwrap()
+            .insert("reason".to_string(), json!("stolen"));
+        assert!(validation_error(&input).starts_with("Invalid reason: stolen"));
+    }
+
+    #[tokio::test]
+    async fn test_large_delta_returns_confirmation_token() {
+        let shopify = client();
+        let confirmations = ToolConfirmations::new();
+        let turn = confirmations.turn(ChatSessionId::new(1));
+        let mut input = input(-20_000);
+
+        let response = AdjustInventoryTool::execute(&shopify, Some(&turn), &input)
+            .await
+            .unwrap();
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response.get("success"), Some(&json!(false)));
+        assert_eq!(response.get("requires_confirmation"), Some(&json!(true)));
+        let token = response.get("confirmation_token").cloned().unwrap();
+
+        // Echoing the token back before the admin replies confirms nothing
+        input
+            .as_object_mut()
+            .unwrap()
+            .insert("confirmation_token".to_string(), token.clone());
+        let response = AdjustInventoryTool::execute(&shopify, Some(&turn), &input)
+            .await
+            .unwrap();
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response.get("requires_confirmation"), Some(&json!(true)));
+        assert_ne!(response.get("confirmation_token"), Some(&token));
+    }
+
+    #[tokio::test]
+    async fn test_large_delta_is_refused_outside_a_chat_session() {
+        let result = AdjustInventoryTool::execute(&client(), None, &input(20_000)).await;
+        assert!(matches!(result, Err(ClaudeError::ToolExecution(_))));
+    }
+}
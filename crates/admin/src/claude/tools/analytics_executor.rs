@@ -5,6 +5,7 @@
 
 use std::collections::HashMap;
 
+use naked_pineapple_core::CustomerId;
 use serde_json::json;
 
 use crate::claude::error::ClaudeError;
@@ -586,7 +587,8 @@ impl ToolExecutor<'_> {
         let query = format!("created_at:>={start_date} created_at:<={end_date}");
 
         // Track unique customers and count new vs returning
-        let mut customer_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
+        let mut customer_ids: std::collections::HashSet<CustomerId> =
+            std::collections::HashSet::new();
         let mut new_customers = 0u64;
         let mut cursor: Option<String> = None;
 
@@ -0,0 +1,191 @@
+//! One-time confirmation tokens for tools that confirm risky input themselves.
+//!
+//! [`super::CreateDiscountCodeTool`] and [`super::AdjustInventoryTool`] run
+//! without Slack approval, but some inputs (a code without a usage limit, a
+//! very large adjustment) need the admin's go-ahead first. The tool answers
+//! those with a warning and a token, and only goes ahead when it is called
+//! again with that token in a later turn of the same chat session, after the
+//! admin has replied. Tokens are kept server-side, so Claude can't confirm
+//! on the admin's behalf by making one up or by echoing it back in the turn
+//! it was issued.
+
+use std::time::{Duration, Instant};
+
+use moka::future::Cache;
+use uuid::Uuid;
+
+use naked_pineapple_core::ChatSessionId;
+
+/// How long an unused token stays valid.
+const TOKEN_TTL: Duration = Duration::from_mins(30);
+
+/// Number of outstanding tokens kept across all sessions.
+const TOKEN_CAPACITY: u64 = 10_000;
+
+/// What a token confirms, and when it was issued.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingConfirmation {
+    session_id: ChatSessionId,
+    turn: Uuid,
+    tool: &'static str,
+    subject: String,
+    issued_at: Instant,
+}
+
+/// Outstanding confirmation tokens for every chat session.
+#[derive(Clone)]
+pub struct ToolConfirmations {
+    pending: Cache<String, PendingConfirmation>,
+}
+
+impl Default for ToolConfirmations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToolConfirmations {
+    /// Create an empty token store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            pending: Cache::builder()
+                .max_capacity(TOKEN_CAPACITY)
+                .time_to_live(TOKEN_TTL)
+                .build(),
+        }
+    }
+
+    /// Tokens for one turn of a chat session: the tool calls made while
+    /// answering one admin message.
+    #[must_use]
+    pub fn turn(&self, session_id: ChatSessionId) -> TurnConfirmations {
+        TurnConfirmations {
+            confirmations: self.clone(),
+            session_id,
+            turn: Uuid::new_v4(),
+        }
+    }
+}
+
+/// Issues and redeems confirmation tokens within one turn of a chat session.
+#[derive(Clone)]
+pub struct TurnConfirmations {
+    confirmations: ToolConfirmations,
+    session_id: ChatSessionId,
+    turn: Uuid,
+}
+
+impl TurnConfirmations {
+    /// Issue a token confirming `subject` (the risky part of the input) for
+    /// `tool`.
+    pub async fn issue(&self, tool: &'static str, subject: String) -> String {
+        let token = Uuid::new_v4().to_string();
+        self.confirmations
+            .pending
+            .insert(
+                token.clone(),
+                PendingConfirmation {
+                    session_id: self.session_id,
+                    turn: self.turn,
+                    tool,
+                    subject,
+                    issued_at: Instant::now(),
+                },
+            )
+            .await;
+        token
+    }
+
+    /// Redeem `token` for `subject`.
+    ///
+    /// Succeeds only once, and only for a token issued for the same tool and
+    /// subject in an earlier turn of this session. The token is taken out of
+    /// the store before it is checked, so concurrent tool calls can't both
+    /// redeem it; a token that doesn't match is put back.
+    pub async fn redeem(&self, tool: &'static str, subject: &str, token: &str) -> bool {
+        let pending = &self.confirmations.pending;
+        let Some(confirmation) = pending.remove(token).await else {
+            return false;
+        };
+        if confirmation.issued_at.elapsed() >= TOKEN_TTL {
+            return false;
+        }
+
+        let confirmed = confirmation.session_id == self.session_id
+            && confirmation.turn != self.turn
+            && confirmation.tool == tool
+            && confirmation.subject == subject;
+        if !confirmed {
+            // Re-inserting restarts the cache TTL; `issued_at` still bounds
+            // how long the token can be redeemed.
+            pending.insert(token.to_string(), confirmation).await;
+        }
+        confirmed
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_token_is_redeemed_once_in_a_later_turn() {
+        let confirmations = ToolConfirmations::new();
+        let session_id = ChatSessionId::new(1);
+        let first = confirmations.turn(session_id);
+        let token = first.issue("tool", "subject".to_string()).await;
+
+        assert!(!first.redeem("tool", "subject", &token).await);
+
+        let second = confirmations.turn(session_id);
+        assert!(second.redeem("tool", "subject", &token).await);
+        assert!(!second.redeem("tool", "subject", &token).await);
+    }
+
+    #[tokio::test]
+    async fn test_token_only_confirms_what_it_was_issued_for() {
+        let confirmations = ToolConfirmations::new();
+        let token = confirmations
+            .turn(ChatSessionId::new(1))
+            .issue("tool", "subject".to_string())
+            .await;
+
+        let other_session = confirmations.turn(ChatSessionId::new(2));
+        assert!(!other_session.redeem("tool", "subject", &token).await);
+
+        let next_turn = confirmations.turn(ChatSessionId::new(1));
+        assert!(!next_turn.redeem("other_tool", "subject", &token).await);
+        assert!(!next_turn.redeem("tool", "other subject", &token).await);
+        assert!(!next_turn.redeem("tool", "subject", "made-up-token").await);
+        assert!(next_turn.redeem("tool", "subject", &token).await);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_concurrent_redeems_succeed_once() {
+        let confirmations = ToolConfirmations::new();
+        let session_id = ChatSessionId::new(1);
+        let token = confirmations
+            .turn(session_id)
+            .issue("tool", "subject".to_string())
+            .await;
+
+        let turn = confirmations.turn(session_id);
+        let tasks: Vec<_> = (0..2)
+            .map(|_| {
+                let turn = turn.clone();
+                let token = token.clone();
+                tokio::spawn(async move { turn.redeem("tool", "subject", &token).await })
+            })
+            .collect();
+
+        let mut redeemed = 0;
+        for task in tasks {
+            if task.await.unwrap() {
+                redeemed += 1;
+            }
+        }
+        assert_eq!(redeemed, 1);
+    }
+}
@@ -0,0 +1,556 @@
+//! Discount code creation tool for Claude.
+//!
+//! Handles requests like "create a 20% off code for the holiday sale" in one
+//! step: the basic discount code is created right away and the response
+//! links to it in the Shopify admin. A code without a usage limit is only
+//! created once the admin has confirmed it may be used an unlimited number of
+//! times: the first call returns a confirmation token, which is accepted in a
+//! later turn of the chat (see [`super::ToolConfirmations`]).
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+
+use crate::claude::error::ClaudeError;
+use crate::claude::types::Tool;
+use crate::shopify::types::DiscountMinimumRequirement;
+use crate::shopify::{AdminClient, CustomerEligibility, DiscountCreateInput, DiscountItems};
+
+use super::{TurnConfirmations, required_string};
+
+/// Shopify admin page for a discount, by numeric ID.
+const SHOPIFY_ADMIN_DISCOUNT_URL: &str =
+    "https://admin.shopify.com/store/naked-pineapple/discounts";
+
+/// Create a percentage or fixed-amount discount code.
+pub struct CreateDiscountCodeTool;
+
+/// How much a new discount code takes off.
+#[derive(Debug, Clone)]
+pub enum DiscountCodeValue {
+    /// Percent off (20.0 for 20% off).
+    Percentage(f64),
+    /// Fixed amount in the store currency, with two decimal places.
+    FixedAmount(Decimal),
+}
+
+/// Validated input for [`CreateDiscountCodeTool`].
+#[derive(Debug, Clone)]
+pub struct CreateDiscountCodeInput {
+    /// Internal discount title.
+    pub title: String,
+    /// Customer-facing code.
+    pub code: String,
+    /// Discount value.
+    pub value: DiscountCodeValue,
+    /// Start time (RFC 3339).
+    pub starts_at: String,
+    /// End time (RFC 3339), if the code expires.
+    pub ends_at: Option<String>,
+    /// Maximum total uses, if limited.
+    pub usage_limit: Option<i64>,
+    /// Token from an earlier call, confirming unlimited usage.
+    pub confirmation_token: Option<String>,
+    /// What a confirmation token is issued for: the title, code, value and
+    /// dates as given, before defaults are filled in.
+    pub confirmation_subject: String,
+}
+
+impl CreateDiscountCodeInput {
+    /// Whether the admin must confirm the code before it is created, because
+    /// it could be used an unlimited number of times.
+    #[must_use]
+    pub const fn needs_confirmation(&self) -> bool {
+        self.usage_limit.is_none()
+    }
+}
+
+impl CreateDiscountCodeTool {
+    /// Tool name exposed to Claude.
+    pub const NAME: &'static str = "create_discount_code";
+
+    /// Currency of fixed-amount discounts.
+    pub const CURRENCY_CODE: &'static str = "USD";
+
+    /// Tool definition for Claude.
+    #[must_use]
+    pub fn definition() -> Tool {
+        Tool {
+            name: Self::NAME.to_string(),
+            description: "Create a discount code that takes a percentage or a fixed amount off \
+                the whole order, and return the code with a link to it in the Shopify admin. \
+                USE THIS for requests like 'create a 20% off code for the holiday sale' or \
+                'make a $10 off code that expires Sunday'. If no usage_limit is given, the \
+                code is not created; the response has a confirmation_token. Ask the admin to \
+                confirm unlimited usage, and once they reply, call this tool again with the \
+                token."
+                .to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "title": {
+                        "type": "string",
+                        "minLength": 1,
+                        "description": "Internal discount title, e.g. 'Holiday Sale 2026'"
+                    },
+                    "code": {
+                        "type": "string",
+                        "minLength": 1,
+                        "description": "Code customers enter at checkout, e.g. 'HOLIDAY20'"
+                    },
+                    "percentage": {
+                        "type": "number",
+                        "exclusiveMinimum": 0,
+                        "maximum": 100,
+                        "description": "Percent off, e.g. 20 for 20% off. Give either this \
+                            or fixed_amount."
+                    },
+                    "fixed_amount": {
+                        "type": "string",
+                        "description": "Amount off in USD, e.g. '10.00'. Give either this \
+                            or percentage."
+                    },
+                    "starts_at": {
+                        "type": "string",
+                        "description": "When the code becomes usable (ISO 8601 date or \
+                            datetime). Defaults to now."
+                    },
+                    "ends_at": {
+                        "type": "string",
+                        "description": "When the code expires (ISO 8601 date or datetime). \
+                            Omit for no expiry."
+                    },
+                    "usage_limit": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Maximum total number of uses. Omit for unlimited."
+                    },
+                    "confirmation_token": {
+                        "type": "string",
+                        "description": "confirmation_token from an earlier response, sent \
+                            only after the admin has replied confirming a code without \
+                            usage_limit may be used an unlimited number of times"
+                    }
+                },
+                "required": ["title", "code"]
+            }),
+            domain: Some("discounts".to_string()),
+            requires_confirmation: false,
+        }
+    }
+
+    /// Create the discount code, or ask for confirmation of unlimited usage.
+    ///
+    /// A code without a usage limit is only created when `input` carries a
+    /// token issued by an earlier call in an earlier turn of the same chat
+    /// session. Otherwise a new token is issued and returned with a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is invalid, if the code needs
+    /// confirmation outside a chat session, or if the Shopify request fails.
+    pub async fn execute(
+        shopify: &AdminClient,
+        confirmations: Option<&TurnConfirmations>,
+        input: &serde_json::Value,
+    ) -> Result<String, ClaudeError> {
+        let input = Self::validate_input(input)?;
+
+        if input.needs_confirmation() {
+            let confirmations = confirmations.ok_or_else(|| {
+                ClaudeError::ToolExecution(
+                    "A code without usage_limit can only be confirmed in the admin chat; give \
+                     a usage_limit"
+                        .to_string(),
+                )
+            })?;
+            let confirmed = match &input.confirmation_token {
+                Some(token) => {
+                    confirmations
+                        .redeem(Self::NAME, &input.confirmation_subject, token)
+                        .await
+                }
+                None => false,
+            };
+            if !confirmed {
+                let token = confirmations
+                    .issue(Self::NAME, input.confirmation_subject.clone())
+                    .await;
+                return Ok(json!({
+                    "success": false,
+                    "requires_confirmation": true,
+                    "confirmation_token": token,
+                    "warning": format!(
+                        "No usage_limit was given, so {} could be used an unlimited number of \
+                         times. Ask the admin to confirm unlimited usage. Once they reply, call \
+                         {} again with this confirmation_token (or with a usage_limit). The \
+                         token is not accepted before the admin replies.",
+                        input.code,
+                        Self::NAME,
+                    ),
+                })
+                .to_string());
+            }
+        }
+
+        let (percentage, fixed_amount) = match &input.value {
+            DiscountCodeValue::Percentage(percent) => (Some(percent / 100.0), None),
+            DiscountCodeValue::FixedAmount(amount) => (None, Some(amount.to_string())),
+        };
+        let amount = fixed_amount
+            .as_deref()
+            .map(|amount| (amount, Self::CURRENCY_CODE));
+
+        let discount_id = shopify
+            .create_discount(DiscountCreateInput {
+                title: &input.title,
+                code: &input.code,
+                percentage,
+                amount,
+                starts_at: &input.starts_at,
+                ends_at: input.ends_at.as_deref(),
+                usage_limit: input.usage_limit,
+                minimum_requirement: DiscountMinimumRequirement::None,
+                items: DiscountItems::All,
+                once_per_customer: false,
+                customer_eligibility: CustomerEligibility::All,
+            })
+            .await
+            .map_err(|e| ClaudeError::ToolExecution(format!("Failed to create discount: {e}")))?;
+
+        let short_id = discount_id.rsplit('/').next().unwrap_or(&discount_id);
+        let value = match &input.value {
+            DiscountCodeValue::Percentage(percent) => format!("{percent}% off"),
+            DiscountCodeValue::FixedAmount(amount) => {
+                format!("${amount} {} off", Self::CURRENCY_CODE)
+            }
+        };
+
+        Ok(json!({
+            "success": true,
+            "id": &discount_id,
+            "code": input.code,
+            "title": input.title,
+            "value": value,
+            "starts_at": input.starts_at,
+            "ends_at": input.ends_at,
+            "usage_limit": input.usage_limit,
+            "admin_url": format!("{SHOPIFY_ADMIN_DISCOUNT_URL}/{short_id}"),
+        })
+        .to_string())
+    }
+
+    /// Validate the tool input.
+    ///
+    /// Dates may be full RFC 3339 datetimes or plain dates (midnight UTC).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required field is missing or blank, if not
+    /// exactly one of `percentage` and `fixed_amount` is given, or if a value
+    /// is out of range.
+    pub fn validate_input(
+        input: &serde_json::Value,
+    ) -> Result<CreateDiscountCodeInput, ClaudeError> {
+        let title = required_string(input, "title")?;
+        let code = required_string(input, "code")?;
+
+        let value = match (input.get("percentage"), input.get("fixed_amount")) {
+            (Some(percentage), None) => {
+                let percentage = percentage
+                    .as_f64()
+                    .filter(|p| *p > 0.0 && *p <= 100.0)
+                    .ok_or_else(|| {
+                        ClaudeError::ToolExecution(
+                            "percentage must be a number greater than 0 and at most 100"
+                                .to_string(),
+                        )
+                    })?;
+                DiscountCodeValue::Percentage(percentage)
+            }
+            (None, Some(amount)) => {
+                let amount = match amount {
+                    serde_json::Value::String(amount) => amount.trim().parse::<Decimal>().ok(),
+                    serde_json::Value::Number(amount) => amount.to_string().parse().ok(),
+                    _ => None,
+                };
+                let mut amount = amount
+                    .filter(|a| *a > Decimal::ZERO && a.normalize().scale() <= 2)
+                    .ok_or_else(|| {
+                        ClaudeError::ToolExecution(
+                            "fixed_amount must be a positive amount with at most 2 decimal \
+                             places"
+                                .to_string(),
+                        )
+                    })?;
+                amount.rescale(2);
+                DiscountCodeValue::FixedAmount(amount)
+            }
+            _ => {
+                return Err(ClaudeError::ToolExecution(
+                    "Give exactly one of percentage or fixed_amount".to_string(),
+                ));
+            }
+        };
+
+        let starts_at = optional_datetime(input, "starts_at")?.unwrap_or_else(Utc::now);
+        let ends_at = optional_datetime(input, "ends_at")?;
+        if ends_at.is_some_and(|ends_at| ends_at <= starts_at) {
+            return Err(ClaudeError::ToolExecution(
+                "ends_at must be after starts_at".to_string(),
+            ));
+        }
+
+        let usage_limit = match input.get("usage_limit") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(limit) => Some(limit.as_i64().filter(|l| *l >= 1).ok_or_else(|| {
+                ClaudeError::ToolExecution("usage_limit must be a positive integer".to_string())
+            })?),
+        };
+
+        Ok(CreateDiscountCodeInput {
+            title,
+            code,
+            value,
+            starts_at: starts_at.to_rfc3339(),
+            ends_at: ends_at.map(|ends_at| ends_at.to_rfc3339()),
+            usage_limit,
+            confirmation_token: input
+                .get("confirmation_token")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string),
+            confirmation_subject: confirmation_subject(input),
+        })
+    }
+}
+
+/// The fields a confirmation token confirms, as given in the raw input.
+///
+/// Dates are taken before defaulting, so a repeated call without `starts_at`
+/// still matches.
+fn confirmation_subject(input: &serde_json::Value) -> String {
+    serde_json::Value::Array(
+        [
+            "title",
+            "code",
+            "percentage",
+            "fixed_amount",
+            "starts_at",
+            "ends_at",
+        ]
+        .iter()
+        .map(|field| input.get(field).cloned().unwrap_or_default())
+        .collect(),
+    )
+    .to_string()
+}
+
+/// Parse an optional ISO 8601 date or datetime field.
+fn optional_datetime(
+    input: &serde_json::Value,
+    field: &str,
+) -> Result<Option<DateTime<Utc>>, ClaudeError> {
+    let value = match input.get(field) {
+        None | Some(serde_json::Value::Null) => return Ok(None),
+        Some(serde_json::Value::String(value)) => value.trim(),
+        Some(_) => {
+            return Err(ClaudeError::ToolExecution(format!(
+                "{field} must be a string"
+            )));
+        }
+    };
+
+    DateTime::parse_from_rfc3339(value)
+        .map(|datetime| datetime.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc())
+        })
+        .map(Some)
+        .map_err(|_| {
+            ClaudeError::ToolExecution(format!(
+                "{field} must be an ISO 8601 date or datetime, e.g. 2026-11-27 or \
+                 2026-11-27T09:00:00-05:00"
+            ))
+        })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use secrecy::SecretString;
+    use serde_json::Value;
+
+    use naked_pineapple_core::ChatSessionId;
+
+    use super::*;
+    use crate::claude::ToolConfirmations;
+    use crate::config::ShopifyAdminConfig;
+
+    /// A client that is never sent a request.
+    fn client() -> AdminClient {
+        AdminClient::new(&ShopifyAdminConfig {
+            store: "test.myshopify.com".to_string(),
+            api_version: "2026-01".to_string(),
+            client_id: "test_client_id".to_string(),
+            client_secret: SecretString::from("test_client_secret"),
+        })
+        .unwrap()
+    }
+
+    fn validation_error(input: &Value) -> String {
+        match CreateDiscountCodeTool::validate_input(input) {
+            Err(ClaudeError::ToolExecution(message)) => message,
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_percentage() {
+        let input = CreateDiscountCodeTool::validate_input(
+            &json!({"title": "Holiday", "code": "HOLIDAY20", "percentage": 20}),
+        )
+        .unwrap();
+        assert!(
+            matches!(input.value, DiscountCodeValue::Percentage(p) if (p - 20.0).abs() < f64::EPSILON)
+        );
+
+        for percentage in [json!(0), json!(-5), json!(100.5), json!("20")] {
+            assert_eq!(
+                validation_error(&json!({"title": "T", "code": "C", "percentage": percentage})),
+                "percentage must be a number greater than 0 and at most 100"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_fixed_amount() {
+        for (amount, expected) in [
+            (json!("10.00"), "10.00"),
+            (json!(" 5 "), "5.00"),
+            (json!(7.5), "7.50"),
+            (json!("2.500"), "2.50"),
+        ] {
+            let input = CreateDiscountCodeTool::validate_input(
+                &json!({"title": "T", "code": "C", "fixed_amount": amount}),
+            )
+            .unwrap();
+            assert!(
+                matches!(input.value, DiscountCodeValue::FixedAmount(a) if a.to_string() == expected)
+            );
+        }
+
+        for amount in [
+            json!("0"),
+            json!("-1"),
+            json!("ten"),
+            json!(true),
+            json!("inf"),
+            json!("1e3"),
+            json!("0.0000001"),
+        ] {
+            assert_eq!(
+                validation_error(&json!({"title": "T", "code": "C", "fixed_amount": amount})),
+                "fixed_amount must be a positive amount with at most 2 decimal places"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_requires_exactly_one_value() {
+        for input in [
+            json!({"title": "T", "code": "C"}),
+            json!({"title": "T", "code": "C", "percentage": 20, "fixed_amount": "10"}),
+        ] {
+            assert_eq!(
+                validation_error(&input),
+                "Give exactly one of percentage or fixed_amount"
+            );
+        }
+    }
+
+    #[test]
+    fn test_needs_confirmation_without_usage_limit() {
+        let unlimited = CreateDiscountCodeTool::validate_input(
+            &json!({"title": "T", "code": "C", "percentage": 10}),
+        )
+        .unwrap();
+        assert!(unlimited.needs_confirmation());
+
+        let limited = CreateDiscountCodeTool::validate_input(
+            &json!({"title": "T", "code": "C", "percentage": 10, "usage_limit": 100}),
+        )
+        .unwrap();
+        assert!(!limited.needs_confirmation());
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_code_returns_confirmation_token() {
+        let shopify = client();
+        let turn = ToolConfirmations::new().turn(ChatSessionId::new(1));
+        let mut input = json!({"title": "T", "code": "FOREVER", "percentage": 10});
+
+        let response = CreateDiscountCodeTool::execute(&shopify, Some(&turn), &input)
+            .await
+            .unwrap();
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response.get("success"), Some(&json!(false)));
+        assert_eq!(response.get("requires_confirmation"), Some(&json!(true)));
+        let warning = response.get("warning").and_then(Value::as_str).unwrap();
+        assert!(warning.contains("FOREVER"));
+        let token = response.get("confirmation_token").cloned().unwrap();
+        assert!(token.is_string());
+
+        // Echoing the token back before the admin replies confirms nothing
+        input
+            .as_object_mut()
+            .unwrap()
+            .insert("confirmation_token".to_string(), token.clone());
+        let response = CreateDiscountCodeTool::execute(&shopify, Some(&turn), &input)
+            .await
+            .unwrap();
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response.get("requires_confirmation"), Some(&json!(true)));
+        assert_ne!(response.get("confirmation_token"), Some(&token));
+    }
+
+    #[tokio::test]
+    async fn test_token_only_confirms_the_input_it_was_issued_for() {
+        let shopify = client();
+        let confirmations = ToolConfirmations::new();
+        let session_id = ChatSessionId::new(1);
+
+        let response = CreateDiscountCodeTool::execute(
+            &shopify,
+            Some(&confirmations.turn(session_id)),
+            &json!({"title": "Holiday", "code": "HOLIDAY20", "percentage": 20}),
+        )
+        .await
+        .unwrap();
+        let response: Value = serde_json::from_str(&response).unwrap();
+        let token = response.get("confirmation_token").cloned().unwrap();
+
+        let response = CreateDiscountCodeTool::execute(
+            &shopify,
+            Some(&confirmations.turn(session_id)),
+            &json!({
+                "title": "Holiday",
+                "code": "HOLIDAY20",
+                "percentage": 100,
+                "confirmation_token": token,
+            }),
+        )
+        .await
+        .unwrap();
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response.get("requires_confirmation"), Some(&json!(true)));
+        assert_ne!(response.get("confirmation_token"), Some(&token));
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_code_is_refused_outside_a_chat_session() {
+        let input = json!({"title": "T", "code": "C", "percentage": 10, "confirmation_token": "t"});
+        let result = CreateDiscountCodeTool::execute(&client(), None, &input).await;
+        assert!(matches!(result, Err(ClaudeError::ToolExecution(_))));
+    }
+}
@@ -94,6 +94,14 @@ fn customer_read_tools() -> Vec<Tool> {
                     "limit": {
                         "type": "integer",
                         "description": "Number of segments to fetch (default 10)"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Search query (e.g., 'name:VIP')"
+                    },
+                    "after": {
+                        "type": "string",
+                        "description": "Cursor for pagination"
                     }
                 }
             }),
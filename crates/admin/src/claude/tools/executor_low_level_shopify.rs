@@ -3,6 +3,9 @@
 //! This module contains the actual implementations for executing each tool
 //! by calling the corresponding Shopify Admin API methods.
 
+use naked_pineapple_core::{
+    CollectionId, CustomerId, InventoryItemId, LocationId, OrderId, ProductId,
+};
 use serde_json::json;
 
 use crate::claude::error::ClaudeError;
@@ -22,11 +25,13 @@ impl ToolExecutor<'_> {
     pub(super) async fn get_order(&self, input: &serde_json::Value) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         let result = self
             .shopify
-            .get_order(id)
+            .get_order(&id)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to get order: {e}")))?;
 
@@ -93,10 +98,12 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         let result =
-            self.shopify.get_order_detail(id).await.map_err(|e| {
+            self.shopify.get_order_detail(&id).await.map_err(|e| {
                 ClaudeError::ToolExecution(format!("Failed to get order detail: {e}"))
             })?;
 
@@ -152,11 +159,13 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<CustomerId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         let result = self
             .shopify
-            .get_customer(id)
+            .get_customer(&id)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to get customer: {e}")))?;
 
@@ -226,11 +235,13 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<CustomerId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         let url = self
             .shopify
-            .generate_customer_activation_url(id)
+            .generate_customer_activation_url(&id)
             .await
             .map_err(|e| {
                 ClaudeError::ToolExecution(format!("Failed to generate activation URL: {e}"))
@@ -244,10 +255,12 @@ impl ToolExecutor<'_> {
         input: &serde_json::Value,
     ) -> Result<String, ClaudeError> {
         let limit = input["limit"].as_i64().unwrap_or(10);
+        let query = input["query"].as_str().map(String::from);
+        let after = input["after"].as_str().map(String::from);
 
         let result = self
             .shopify
-            .get_customer_segments(limit)
+            .get_customer_segments(limit, after, query)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to get segments: {e}")))?;
 
@@ -265,11 +278,13 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<ProductId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         let result = self
             .shopify
-            .get_product(id)
+            .get_product(&id)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to get product: {e}")))?;
 
@@ -349,12 +364,14 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let location_id = input["location_id"]
             .as_str()
-            .unwrap_or("gid://shopify/Location/1");
+            .unwrap_or("gid://shopify/Location/1")
+            .parse::<LocationId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
         let limit = input["limit"].as_i64().unwrap_or(20).clamp(1, 50);
 
         let result = self
             .shopify
-            .get_inventory_levels(location_id, limit, None)
+            .get_inventory_levels(&location_id, limit, None)
             .await
             .map_err(|e| {
                 ClaudeError::ToolExecution(format!("Failed to get inventory levels: {e}"))
@@ -369,7 +386,9 @@ impl ToolExecutor<'_> {
                     "location": l.location_name,
                     "available": l.available,
                     "on_hand": l.on_hand,
+                    "committed": l.committed,
                     "incoming": l.incoming,
+                    "reserved": l.reserved,
                 })
             })
             .collect();
@@ -406,9 +425,11 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<InventoryItemId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
-        let result = self.shopify.get_inventory_item(id).await.map_err(|e| {
+        let result = self.shopify.get_inventory_item(&id).await.map_err(|e| {
             ClaudeError::ToolExecution(format!("Failed to get inventory item: {e}"))
         })?;
 
@@ -426,10 +447,12 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<CollectionId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         let result =
-            self.shopify.get_collection(id).await.map_err(|e| {
+            self.shopify.get_collection(&id).await.map_err(|e| {
                 ClaudeError::ToolExecution(format!("Failed to get collection: {e}"))
             })?;
 
@@ -465,12 +488,14 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<CollectionId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
         let product_limit = input["product_limit"].as_i64().unwrap_or(20);
 
         let result = self
             .shopify
-            .get_collection_with_products(id, product_limit, None)
+            .get_collection_with_products(&id, product_limit, None)
             .await
             .map_err(|e| {
                 ClaudeError::ToolExecution(format!("Failed to get collection with products: {e}"))
@@ -625,13 +650,17 @@ impl ToolExecutor<'_> {
         &self,
         input: &serde_json::Value,
     ) -> Result<String, ClaudeError> {
-        let order_id = input["order_id"].as_str().ok_or_else(|| {
-            ClaudeError::ToolExecution("Missing required field: order_id".to_string())
-        })?;
+        let order_id = input["order_id"]
+            .as_str()
+            .ok_or_else(|| {
+                ClaudeError::ToolExecution("Missing required field: order_id".to_string())
+            })?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         let result = self
             .shopify
-            .get_fulfillment_orders(order_id)
+            .get_fulfillment_orders(&order_id)
             .await
             .map_err(|e| {
                 ClaudeError::ToolExecution(format!("Failed to get fulfillment orders: {e}"))
@@ -645,13 +674,17 @@ impl ToolExecutor<'_> {
         &self,
         input: &serde_json::Value,
     ) -> Result<String, ClaudeError> {
-        let order_id = input["order_id"].as_str().ok_or_else(|| {
-            ClaudeError::ToolExecution("Missing required field: order_id".to_string())
-        })?;
+        let order_id = input["order_id"]
+            .as_str()
+            .ok_or_else(|| {
+                ClaudeError::ToolExecution("Missing required field: order_id".to_string())
+            })?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         let result = self
             .shopify
-            .get_suggested_refund(order_id)
+            .get_suggested_refund(&order_id)
             .await
             .map_err(|e| {
                 ClaudeError::ToolExecution(format!("Failed to get suggested refund: {e}"))
@@ -818,14 +851,19 @@ impl ToolExecutor<'_> {
         &self,
         input: &serde_json::Value,
     ) -> Result<String, ClaudeError> {
-        let order_id = input["order_id"].as_str().ok_or_else(|| {
-            ClaudeError::ToolExecution("Missing required field: order_id".to_string())
-        })?;
+        let order_id = input["order_id"]
+            .as_str()
+            .ok_or_else(|| {
+                ClaudeError::ToolExecution("Missing required field: order_id".to_string())
+            })?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
-        let result =
-            self.shopify.order_edit_begin(order_id).await.map_err(|e| {
-                ClaudeError::ToolExecution(format!("Failed to begin order edit: {e}"))
-            })?;
+        let result = self
+            .shopify
+            .order_edit_begin(&order_id)
+            .await
+            .map_err(|e| ClaudeError::ToolExecution(format!("Failed to begin order edit: {e}")))?;
 
         serde_json::to_string_pretty(&result)
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to serialize: {e}")))
@@ -855,11 +893,13 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
         let note = input["note"].as_str();
 
         self.shopify
-            .update_order_note(id, note)
+            .update_order_note(&id, note)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to update order note: {e}")))?;
 
@@ -872,7 +912,9 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
         let tags: Vec<String> = input["tags"]
             .as_array()
             .map(|arr| {
@@ -883,7 +925,7 @@ impl ToolExecutor<'_> {
             .unwrap_or_default();
 
         self.shopify
-            .update_order_tags(id, tags)
+            .update_order_tags(&id, tags)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to update order tags: {e}")))?;
 
@@ -896,9 +938,11 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
-        self.shopify.mark_order_as_paid(id).await.map_err(|e| {
+        self.shopify.mark_order_as_paid(&id).await.map_err(|e| {
             ClaudeError::ToolExecution(format!("Failed to mark order as paid: {e}"))
         })?;
 
@@ -911,14 +955,16 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
         let reason = input["reason"].as_str();
         let refund = input["refund"].as_bool().unwrap_or(false);
         let restock = input["restock"].as_bool().unwrap_or(false);
         let notify = input["notify_customer"].as_bool().unwrap_or(false);
 
         self.shopify
-            .cancel_order(id, reason, refund, restock, notify)
+            .cancel_order(&id, reason, refund, restock, notify)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to cancel order: {e}")))?;
 
@@ -931,10 +977,12 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         self.shopify
-            .archive_order(id)
+            .archive_order(&id)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to archive order: {e}")))?;
 
@@ -947,10 +995,12 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         self.shopify
-            .unarchive_order(id)
+            .unarchive_order(&id)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to unarchive order: {e}")))?;
 
@@ -963,7 +1013,9 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
         let parent_transaction_id = input["parent_transaction_id"].as_str().ok_or_else(|| {
             ClaudeError::ToolExecution("Missing required field: parent_transaction_id".to_string())
         })?;
@@ -972,7 +1024,7 @@ impl ToolExecutor<'_> {
         })?;
 
         self.shopify
-            .capture_order_payment(id, parent_transaction_id, amount)
+            .capture_order_payment(&id, parent_transaction_id, amount)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to capture payment: {e}")))?;
 
@@ -985,7 +1037,9 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
         let tags: Vec<String> = input["tags"]
             .as_array()
             .map(|arr| {
@@ -996,7 +1050,7 @@ impl ToolExecutor<'_> {
             .unwrap_or_default();
 
         self.shopify
-            .add_tags_to_order(id, &tags)
+            .add_tags_to_order(&id, &tags)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to add tags: {e}")))?;
 
@@ -1009,7 +1063,9 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<OrderId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
         let tags: Vec<String> = input["tags"]
             .as_array()
             .map(|arr| {
@@ -1020,7 +1076,7 @@ impl ToolExecutor<'_> {
             .unwrap_or_default();
 
         self.shopify
-            .remove_tags_from_order(id, &tags)
+            .remove_tags_from_order(&id, &tags)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to remove tags: {e}")))?;
 
@@ -1060,10 +1116,12 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<CustomerId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         self.shopify
-            .delete_customer(id)
+            .delete_customer(&id)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to delete customer: {e}")))?;
 
@@ -1076,7 +1134,9 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<CustomerId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
         let tags: Vec<String> = input["tags"]
             .as_array()
             .map(|arr| {
@@ -1087,7 +1147,7 @@ impl ToolExecutor<'_> {
             .unwrap_or_default();
 
         self.shopify
-            .add_customer_tags(id, tags)
+            .add_customer_tags(&id, tags)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to add customer tags: {e}")))?;
 
@@ -1100,7 +1160,9 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<CustomerId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
         let tags: Vec<String> = input["tags"]
             .as_array()
             .map(|arr| {
@@ -1111,7 +1173,7 @@ impl ToolExecutor<'_> {
             .unwrap_or_default();
 
         self.shopify
-            .remove_customer_tags(id, tags)
+            .remove_customer_tags(&id, tags)
             .await
             .map_err(|e| {
                 ClaudeError::ToolExecution(format!("Failed to remove customer tags: {e}"))
@@ -1126,9 +1188,11 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<CustomerId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
-        self.shopify.send_customer_invite(id).await.map_err(|e| {
+        self.shopify.send_customer_invite(&id).await.map_err(|e| {
             ClaudeError::ToolExecution(format!("Failed to send customer invite: {e}"))
         })?;
 
@@ -1223,10 +1287,12 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<ProductId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         self.shopify
-            .delete_product(id)
+            .delete_product(&id)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to delete product: {e}")))?;
 
@@ -1329,10 +1395,12 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<CollectionId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         self.shopify
-            .delete_collection(id)
+            .delete_collection(&id)
             .await
             .map_err(|e| ClaudeError::ToolExecution(format!("Failed to delete collection: {e}")))?;
 
@@ -1355,10 +1423,12 @@ impl ToolExecutor<'_> {
     ) -> Result<String, ClaudeError> {
         let id = input["id"]
             .as_str()
-            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?;
+            .ok_or_else(|| ClaudeError::ToolExecution("Missing required field: id".to_string()))?
+            .parse::<CollectionId>()
+            .map_err(|e| ClaudeError::ToolExecution(e.to_string()))?;
 
         self.shopify
-            .delete_collection_image(id)
+            .delete_collection_image(&id)
             .await
             .map_err(|e| {
                 ClaudeError::ToolExecution(format!("Failed to delete collection image: {e}"))
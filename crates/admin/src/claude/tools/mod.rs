@@ -6,14 +6,32 @@
 //! Summarized, aggregate data for answering business questions.
 //! These return compact responses ideal for questions like "what's our revenue?"
 //!
+//! **Order search tool ([`QueryOrdersTool`]):**
+//! Lists orders matching a Shopify search as a markdown table.
+//!
+//! **Product search tool ([`SearchProductsTool`]):**
+//! Lists variants of products matching a Shopify search as JSON.
+//!
+//! **Discount code tool ([`CreateDiscountCodeTool`]):**
+//! Creates a basic discount code directly. A code with no usage limit is only
+//! created with a confirmation token from an earlier turn (see
+//! [`ToolConfirmations`]).
+//!
+//! **Inventory adjustment tool ([`AdjustInventoryTool`]):**
+//! Adjusts a variant's available stock by SKU and reports the new quantity.
+//! Very large adjustments also need a confirmation token from an earlier turn.
+//!
 //! **Low-level Shopify API tools (111 total):**
 //! - 38 read operations (execute immediately)
 //! - 73 write operations (require confirmation via Slack)
 //!
 //! These return detailed data for specific lookups and modifications.
 
+mod adjust_inventory;
 mod analytics;
 mod collections_low_level_shopify;
+mod confirmation;
+mod create_discount;
 mod customers_low_level_shopify;
 mod discounts_low_level_shopify;
 mod finance_low_level_shopify;
@@ -21,11 +39,16 @@ mod fulfillment_low_level_shopify;
 mod gift_cards_low_level_shopify;
 mod inventory_low_level_shopify;
 mod order_editing_low_level_shopify;
+mod orders;
 mod orders_low_level_shopify;
 mod products_low_level_shopify;
+mod search_products;
 
+pub use adjust_inventory::AdjustInventoryTool;
 pub use analytics::analytics_tools;
 pub use collections_low_level_shopify::collection_tools;
+pub use confirmation::{ToolConfirmations, TurnConfirmations};
+pub use create_discount::CreateDiscountCodeTool;
 pub use customers_low_level_shopify::customer_tools;
 pub use discounts_low_level_shopify::discount_tools;
 pub use finance_low_level_shopify::finance_tools;
@@ -33,26 +56,40 @@ pub use fulfillment_low_level_shopify::fulfillment_tools;
 pub use gift_cards_low_level_shopify::gift_card_tools;
 pub use inventory_low_level_shopify::inventory_tools;
 pub use order_editing_low_level_shopify::order_editing_tools;
+pub use orders::QueryOrdersTool;
 pub use orders_low_level_shopify::order_tools;
 pub use products_low_level_shopify::product_tools;
+pub use search_products::SearchProductsTool;
+
+use std::sync::Arc;
 
 use serde_json::json;
-use tracing::instrument;
+use tokio::sync::Semaphore;
+use tracing::{Instrument, instrument};
 
 use crate::shopify::AdminClient;
 
 use super::error::ClaudeError;
 use super::types::Tool;
 
-/// Get all tools (126 total: 15 high-level analytics + 111 low-level Shopify).
+/// Get all tools (130 total: 15 high-level analytics + order and product search + discount code
+/// creation + inventory adjustment + 111 low-level Shopify).
 ///
 /// High-level analytics tools are listed first as they should be preferred
 /// for answering common business questions.
 #[must_use]
 pub fn all_shopify_tools() -> Vec<Tool> {
-    let mut tools = Vec::with_capacity(126);
+    let mut tools = Vec::with_capacity(130);
     // High-level analytics tools (preferred for business questions)
     tools.extend(analytics_tools());
+    // Order search (compact table of matching orders)
+    tools.push(QueryOrdersTool::definition());
+    // Product search (variants with SKU and price)
+    tools.push(SearchProductsTool::definition());
+    // Discount code creation (confirms unlimited codes in the response)
+    tools.push(CreateDiscountCodeTool::definition());
+    // Inventory adjustment by SKU (confirms large deltas in the response)
+    tools.push(AdjustInventoryTool::definition());
     // Low-level Shopify API tools (for specific lookups and modifications)
     tools.extend(order_tools());
     tools.extend(customer_tools());
@@ -117,6 +154,24 @@ pub fn filter_tools_by_names(names: &[String]) -> Vec<Tool> {
     tools
 }
 
+/// Extract a required, non-blank string field from tool input.
+fn required_string(input: &serde_json::Value, field: &str) -> Result<String, ClaudeError> {
+    match input.get(field) {
+        Some(serde_json::Value::String(value)) if !value.trim().is_empty() => {
+            Ok(value.trim().to_string())
+        }
+        Some(serde_json::Value::String(_)) => Err(ClaudeError::ToolExecution(format!(
+            "{field} must not be empty"
+        ))),
+        Some(_) => Err(ClaudeError::ToolExecution(format!(
+            "{field} must be a string"
+        ))),
+        None => Err(ClaudeError::ToolExecution(format!(
+            "Missing required field: {field}"
+        ))),
+    }
+}
+
 /// Sort tools so that high-level analytics tools come first and
 /// low-level Shopify API tools (ending in `_low_level_shopify`) come last.
 fn sort_tools_high_level_first(tools: &mut [Tool]) {
@@ -127,19 +182,44 @@ fn sort_tools_high_level_first(tools: &mut [Tool]) {
     });
 }
 
+/// Maximum number of tool calls from one assistant message executed at once.
+pub const MAX_CONCURRENT_TOOLS: usize = 3;
+
+/// Write tools that ask the admin to confirm risky input in their own response
+/// (see [`ToolConfirmations`]) instead of waiting for Slack approval.
+const SELF_CONFIRMED_WRITE_TOOLS: [&str; 2] =
+    [CreateDiscountCodeTool::NAME, AdjustInventoryTool::NAME];
+
 /// Executor for Shopify tools.
 ///
 /// Handles tool execution by mapping tool names to Shopify Admin API calls.
-/// Write operations return a pending status for confirmation flow.
+/// Read operations run immediately. Most write operations return a pending
+/// status for the Slack approval flow; `create_discount_code` and
+/// `adjust_inventory` run immediately and confirm risky input themselves.
 pub struct ToolExecutor<'a> {
     shopify: &'a AdminClient,
+    /// Confirmation tokens for the current chat turn, if any.
+    confirmations: Option<TurnConfirmations>,
 }
 
 impl<'a> ToolExecutor<'a> {
     /// Create a new tool executor.
+    ///
+    /// Tools that confirm risky input themselves refuse it until
+    /// [`Self::with_confirmations`] is used.
     #[must_use]
     pub const fn new(shopify: &'a AdminClient) -> Self {
-        Self { shopify }
+        Self {
+            shopify,
+            confirmations: None,
+        }
+    }
+
+    /// Issue and redeem confirmation tokens for one turn of a chat session.
+    #[must_use]
+    pub fn with_confirmations(mut self, confirmations: TurnConfirmations) -> Self {
+        self.confirmations = Some(confirmations);
+        self
     }
 
     /// Execute a tool and return the result as a string.
@@ -151,8 +231,8 @@ impl<'a> ToolExecutor<'a> {
     ///
     /// # Returns
     ///
-    /// For read operations: the tool result as JSON.
-    /// For write operations that need confirmation: a pending status message.
+    /// For read operations and self-confirmed writes: the tool result as JSON.
+    /// For write operations that need Slack approval: a pending status message.
     ///
     /// # Errors
     ///
@@ -172,11 +252,60 @@ impl<'a> ToolExecutor<'a> {
             });
         }
 
-        // Execute read operation immediately
-        let result = self.execute_read(name, input).await?;
+        let result = if SELF_CONFIRMED_WRITE_TOOLS.contains(&name) {
+            self.execute_self_confirmed_write(name, input).await?
+        } else {
+            self.execute_read(name, input).await?
+        };
         Ok(ToolResult::Success(result))
     }
 
+    /// Execute every tool call from one assistant message.
+    ///
+    /// Calls run as concurrent tasks, at most [`MAX_CONCURRENT_TOOLS`] at a
+    /// time. Results are returned in the same order as `calls`, ready to be
+    /// sent back as a single batch of `tool_result` blocks.
+    pub async fn execute_all(
+        &self,
+        calls: &[(String, serde_json::Value)],
+    ) -> Vec<Result<ToolResult, ClaudeError>> {
+        if let [(name, input)] = calls {
+            return vec![self.execute(name, input).await];
+        }
+
+        let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_TOOLS));
+        let handles: Vec<_> = calls
+            .iter()
+            .cloned()
+            .map(|(name, input)| {
+                let shopify = self.shopify.clone();
+                let confirmations = self.confirmations.clone();
+                let semaphore = Arc::clone(&semaphore);
+                tokio::spawn(
+                    async move {
+                        let _permit = semaphore.acquire_owned().await.map_err(|e| {
+                            ClaudeError::ToolExecution(format!("Tool scheduling failed: {e}"))
+                        })?;
+                        let executor = ToolExecutor {
+                            shopify: &shopify,
+                            confirmations,
+                        };
+                        executor.execute(&name, &input).await
+                    }
+                    .in_current_span(),
+                )
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(handles.len());
+        for handle in handles {
+            results.push(handle.await.unwrap_or_else(|e| {
+                Err(ClaudeError::ToolExecution(format!("Tool task failed: {e}")))
+            }));
+        }
+        results
+    }
+
     /// Execute a write operation after confirmation.
     ///
     /// This should only be called after Slack approval.
@@ -217,6 +346,12 @@ impl<'a> ToolExecutor<'a> {
             "get_gift_card_summary" => self.get_gift_card_summary(input).await,
             "get_fulfillment_summary" => self.get_fulfillment_summary(input).await,
 
+            // Order search
+            QueryOrdersTool::NAME => QueryOrdersTool::execute(self.shopify, input).await,
+
+            // Product search
+            SearchProductsTool::NAME => SearchProductsTool::execute(self.shopify, input).await,
+
             // Low-level Shopify API tools
             // Orders (read)
             "get_order_low_level_shopify" => self.get_order(input).await,
@@ -286,6 +421,31 @@ impl<'a> ToolExecutor<'a> {
         }
     }
 
+    /// Execute a write operation that confirms risky input itself.
+    ///
+    /// These run without Slack approval; instead they answer risky input
+    /// with a confirmation token for the admin to approve in a later turn.
+    async fn execute_self_confirmed_write(
+        &self,
+        name: &str,
+        input: &serde_json::Value,
+    ) -> Result<String, ClaudeError> {
+        match name {
+            // Discount code creation (confirms unlimited usage)
+            CreateDiscountCodeTool::NAME => {
+                CreateDiscountCodeTool::execute(self.shopify, self.confirmations.as_ref(), input)
+                    .await
+            }
+
+            // Inventory adjustment (confirms large deltas)
+            AdjustInventoryTool::NAME => {
+                AdjustInventoryTool::execute(self.shopify, self.confirmations.as_ref(), input).await
+            }
+
+            _ => Err(ClaudeError::ToolExecution(format!("Unknown tool: {name}"))),
+        }
+    }
+
     /// Execute a write operation.
     async fn execute_write(
         &self,
@@ -0,0 +1,194 @@
+//! Order search tool for Claude.
+//!
+//! Answers questions like "show me orders placed this week over $200" by
+//! running a Shopify order search and returning a compact markdown table,
+//! rather than the full JSON the low-level order tools return.
+
+use std::fmt::Write as _;
+
+use serde_json::json;
+
+use crate::claude::error::ClaudeError;
+use crate::claude::types::Tool;
+use crate::shopify::AdminClient;
+use crate::shopify::types::{FinancialStatus, FulfillmentStatus, OrderListItem, OrderSortKey};
+
+/// Search orders with Shopify query syntax and summarize them as a table.
+pub struct QueryOrdersTool;
+
+impl QueryOrdersTool {
+    /// Tool name exposed to Claude.
+    pub const NAME: &'static str = "query_orders";
+
+    /// Maximum number of orders listed in the table.
+    pub const MAX_ROWS: usize = 20;
+
+    /// Tool definition for Claude.
+    #[must_use]
+    pub fn definition() -> Tool {
+        Tool {
+            name: Self::NAME.to_string(),
+            description: "Search orders using Shopify order search syntax and return the \
+                newest matches as a markdown table (order, customer, total, status, date), \
+                with the total number of matches. \
+                USE THIS for questions that list specific orders matching conditions, like \
+                'orders this week over $200' or 'unpaid orders from jane@example.com'."
+                .to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "minLength": 1,
+                        "description": "Shopify order search query. Combine terms with spaces \
+                            (AND) or OR, e.g. 'created_at:>=2024-06-03 total_price:>200', \
+                            'financial_status:pending', 'fulfillment_status:unfulfilled', \
+                            'email:jane@example.com', 'tag:wholesale'"
+                    }
+                },
+                "required": ["query"]
+            }),
+            domain: Some("orders".to_string()),
+            requires_confirmation: false,
+        }
+    }
+
+    /// Run the search and format the matching orders.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query is missing or empty, or the Shopify
+    /// request fails.
+    pub async fn execute(
+        shopify: &AdminClient,
+        input: &serde_json::Value,
+    ) -> Result<String, ClaudeError> {
+        let query = Self::validate_input(input)?;
+        let first = i64::try_from(Self::MAX_ROWS).unwrap_or(i64::MAX);
+
+        let (orders, count) = tokio::join!(
+            shopify.get_orders_list(
+                first,
+                None,
+                Some(query.clone()),
+                Some(OrderSortKey::CreatedAt),
+                true,
+            ),
+            shopify.get_orders_count(Some(query)),
+        );
+
+        let orders = orders
+            .map_err(|e| ClaudeError::ToolExecution(format!("Failed to query orders: {e}")))?
+            .orders;
+        // The count only feeds the footer; fall back to what was returned
+        let total = count.unwrap_or_else(|e| {
+            tracing::warn!("Failed to count orders: {e}");
+            i64::try_from(orders.len()).unwrap_or(i64::MAX)
+        });
+
+        Ok(format_orders_table(&orders, total))
+    }
+
+    /// Extract the `query` parameter, which must be a non-empty string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `query` is missing, not a string, or blank.
+    pub fn validate_input(input: &serde_json::Value) -> Result<String, ClaudeError> {
+        match input.get("query") {
+            Some(serde_json::Value::String(query)) if !query.trim().is_empty() => {
+                Ok(query.trim().to_string())
+            }
+            Some(serde_json::Value::String(_)) => Err(ClaudeError::ToolExecution(
+                "query must not be empty".to_string(),
+            )),
+            Some(_) => Err(ClaudeError::ToolExecution(
+                "query must be a string".to_string(),
+            )),
+            None => Err(ClaudeError::ToolExecution(
+                "Missing required field: query".to_string(),
+            )),
+        }
+    }
+}
+
+/// Render orders as a markdown table, capped at [`QueryOrdersTool::MAX_ROWS`].
+///
+/// `total` is the number of orders matching the search; when it exceeds the
+/// rows shown, a "showing X of N results" footer is added.
+#[must_use]
+pub fn format_orders_table(orders: &[OrderListItem], total: i64) -> String {
+    if orders.is_empty() {
+        return "No orders match this query.".to_string();
+    }
+
+    let mut table =
+        String::from("| Order | Customer | Total | Status | Date |\n|---|---|---|---|---|\n");
+    let shown = orders.iter().take(QueryOrdersTool::MAX_ROWS);
+    for order in shown.clone() {
+        let customer = order
+            .customer_name
+            .as_deref()
+            .or(order.email.as_deref())
+            .unwrap_or("Guest");
+        let date = order.created_at.get(..10).unwrap_or(&order.created_at);
+        let _ = writeln!(
+            table,
+            "| {} | {} | {} {} | {} | {} |",
+            markdown_cell(&order.name),
+            markdown_cell(customer),
+            order.total_price.amount,
+            order.total_price.currency_code,
+            order_status(order),
+            date,
+        );
+    }
+
+    let shown = i64::try_from(shown.count()).unwrap_or(i64::MAX);
+    if total > shown {
+        let _ = write!(table, "\nShowing {shown} of {total} results");
+    }
+
+    table
+}
+
+/// One-line status: cancelled, or payment and fulfillment state.
+fn order_status(order: &OrderListItem) -> String {
+    if order.cancelled {
+        return "Cancelled".to_string();
+    }
+
+    let payment = order
+        .financial_status
+        .map_or("Unknown", |status| match status {
+            FinancialStatus::Pending => "Pending",
+            FinancialStatus::Authorized => "Authorized",
+            FinancialStatus::Paid => "Paid",
+            FinancialStatus::PartiallyPaid => "Partially paid",
+            FinancialStatus::Refunded => "Refunded",
+            FinancialStatus::PartiallyRefunded => "Partially refunded",
+            FinancialStatus::Voided => "Voided",
+            FinancialStatus::Expired => "Expired",
+        });
+    let fulfillment = order
+        .fulfillment_status
+        .map_or("Unfulfilled", |status| match status {
+            FulfillmentStatus::Unfulfilled => "Unfulfilled",
+            FulfillmentStatus::PartiallyFulfilled => "Partially fulfilled",
+            FulfillmentStatus::Fulfilled => "Fulfilled",
+            FulfillmentStatus::OnHold => "On hold",
+            FulfillmentStatus::InProgress => "In progress",
+            FulfillmentStatus::Restocked => "Restocked",
+            FulfillmentStatus::Scheduled => "Scheduled",
+            FulfillmentStatus::PendingFulfillment => "Pending fulfillment",
+            FulfillmentStatus::Open => "Open",
+            FulfillmentStatus::RequestDeclined => "Request declined",
+        });
+
+    format!("{payment} / {fulfillment}")
+}
+
+/// Escape characters that would break a markdown table cell.
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
@@ -0,0 +1,194 @@
+//! Product search tool for Claude.
+//!
+//! Answers questions like "look up SKU NP-CLN-100" or "show me draft
+//! products" by running a Shopify product search and returning one compact
+//! JSON row per matching variant, rather than the product summaries the
+//! low-level product tools return.
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::claude::error::ClaudeError;
+use crate::claude::types::Tool;
+use crate::shopify::AdminClient;
+use crate::shopify::types::{AdminProduct, Money};
+
+/// Search products by title, SKU, or status and list their variants.
+pub struct SearchProductsTool;
+
+/// Validated input for [`SearchProductsTool`].
+#[derive(Debug)]
+pub struct SearchProductsInput {
+    /// Shopify product search query, including the status filter.
+    pub query: String,
+    /// Number of products to fetch.
+    pub first: i64,
+}
+
+/// One matching variant in the tool result.
+#[derive(Debug, Serialize)]
+struct ProductMatch<'a> {
+    id: &'a str,
+    product_id: &'a str,
+    title: String,
+    sku: Option<&'a str>,
+    price: &'a Money,
+}
+
+impl SearchProductsTool {
+    /// Tool name exposed to Claude.
+    pub const NAME: &'static str = "search_products";
+
+    /// Number of products fetched when `first` isn't given.
+    pub const DEFAULT_FIRST: i64 = 10;
+
+    /// Maximum number of products fetched.
+    pub const MAX_FIRST: i64 = 50;
+
+    /// Tool definition for Claude.
+    #[must_use]
+    pub fn definition() -> Tool {
+        Tool {
+            name: Self::NAME.to_string(),
+            description: "Search products by title, SKU, or other product fields and return \
+                a JSON array with one entry per matching variant (variant ID, product ID, \
+                title, SKU, and current price). \
+                USE THIS to find specific products, like 'find products matching cleanser', \
+                'look up SKU NP-CLN-100', or 'show me draft products'."
+                .to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Shopify product search query. Plain words match \
+                            titles; fields narrow the search, e.g. 'cleanser', \
+                            'sku:NP-CLN-100', 'vendor:Naked Pineapple', 'tag:bundle'. \
+                            May be empty when status is given."
+                    },
+                    "status": {
+                        "type": ["string", "null"],
+                        "enum": ["ACTIVE", "DRAFT", "ARCHIVED", null],
+                        "description": "Only return products with this status"
+                    },
+                    "first": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": Self::MAX_FIRST,
+                        "description": "Number of products to search (1-50, default 10)"
+                    }
+                },
+                "required": ["query"]
+            }),
+            domain: Some("products".to_string()),
+            requires_confirmation: false,
+        }
+    }
+
+    /// Run the search and list the matching variants.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is invalid or the Shopify request fails.
+    pub async fn execute(
+        shopify: &AdminClient,
+        input: &serde_json::Value,
+    ) -> Result<String, ClaudeError> {
+        let input = Self::validate_input(input)?;
+
+        let products = shopify
+            .get_products(input.first, None, Some(input.query))
+            .await
+            .map_err(|e| ClaudeError::ToolExecution(format!("Failed to search products: {e}")))?
+            .products;
+
+        let matches: Vec<ProductMatch<'_>> = products.iter().flat_map(product_matches).collect();
+        serde_json::to_string_pretty(&matches)
+            .map_err(|e| ClaudeError::ToolExecution(format!("Failed to serialize products: {e}")))
+    }
+
+    /// Validate the input and build the Shopify search query.
+    ///
+    /// The status, if given, is added to the query as a `status:` filter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `query` is missing or not a string, if it is blank
+    /// and no status is given, or if `status` or `first` is invalid.
+    pub fn validate_input(input: &serde_json::Value) -> Result<SearchProductsInput, ClaudeError> {
+        let query = match input.get("query") {
+            Some(serde_json::Value::String(query)) => query.trim(),
+            Some(_) => {
+                return Err(ClaudeError::ToolExecution(
+                    "query must be a string".to_string(),
+                ));
+            }
+            None => {
+                return Err(ClaudeError::ToolExecution(
+                    "Missing required field: query".to_string(),
+                ));
+            }
+        };
+
+        let status = match input.get("status") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(serde_json::Value::String(status)) => match status.as_str() {
+                "ACTIVE" => Some("active"),
+                "DRAFT" => Some("draft"),
+                "ARCHIVED" => Some("archived"),
+                _ => {
+                    return Err(ClaudeError::ToolExecution(format!(
+                        "Invalid status: {status} (expected ACTIVE, DRAFT, or ARCHIVED)"
+                    )));
+                }
+            },
+            Some(_) => {
+                return Err(ClaudeError::ToolExecution(
+                    "status must be a string or null".to_string(),
+                ));
+            }
+        };
+
+        let first = match input.get("first") {
+            None | Some(serde_json::Value::Null) => Self::DEFAULT_FIRST,
+            Some(first) => first
+                .as_i64()
+                .filter(|first| (1..=Self::MAX_FIRST).contains(first))
+                .ok_or_else(|| {
+                    ClaudeError::ToolExecution(format!(
+                        "first must be an integer from 1 to {}",
+                        Self::MAX_FIRST
+                    ))
+                })?,
+        };
+
+        let query = match (query.is_empty(), status) {
+            (true, None) => {
+                return Err(ClaudeError::ToolExecution(
+                    "query must not be empty unless status is given".to_string(),
+                ));
+            }
+            (true, Some(status)) => format!("status:{status}"),
+            (false, Some(status)) => format!("({query}) status:{status}"),
+            (false, None) => query.to_string(),
+        };
+
+        Ok(SearchProductsInput { query, first })
+    }
+}
+
+/// Result rows for a product, one per variant.
+fn product_matches(product: &AdminProduct) -> impl Iterator<Item = ProductMatch<'_>> {
+    product.variants.iter().map(|variant| ProductMatch {
+        id: variant.id.as_str(),
+        product_id: product.id.as_str(),
+        // Single-variant products are named "Default Title" by Shopify
+        title: if product.variants.len() > 1 {
+            format!("{} - {}", product.title, variant.title)
+        } else {
+            product.title.clone()
+        },
+        sku: variant.sku.as_deref().filter(|sku| !sku.is_empty()),
+        price: &variant.price,
+    })
+}
@@ -0,0 +1,160 @@
+//! Claude API usage tracking.
+//!
+//! Records the tokens and estimated cost of every Claude response against the
+//! admin user who sent the request, and alerts Slack when a user passes 80%
+//! of their monthly token budget.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use naked_pineapple_core::{ChatSessionId, Email};
+
+use super::types::Usage;
+use crate::config::{ClaudeConfig, ModelPrice};
+use crate::db::ClaudeUsageRepository;
+use crate::models::{NewClaudeUsage, PromptTemplate};
+use crate::slack::SlackClient;
+
+/// Share of the monthly budget (in percent) at which Slack is alerted.
+pub const BUDGET_ALERT_PERCENT: u64 = 80;
+
+/// Records Claude token usage for one admin user.
+#[derive(Clone)]
+pub struct ClaudeUsageTracker {
+    pool: PgPool,
+    model_prices: HashMap<String, ModelPrice>,
+    monthly_token_budget: Option<u64>,
+    slack: Option<SlackClient>,
+    admin_email: Email,
+}
+
+impl ClaudeUsageTracker {
+    /// Create a tracker for requests sent by `admin_email`.
+    #[must_use]
+    pub fn new(
+        pool: PgPool,
+        config: &ClaudeConfig,
+        slack: Option<SlackClient>,
+        admin_email: Email,
+    ) -> Self {
+        Self {
+            pool,
+            model_prices: config.model_prices.clone(),
+            monthly_token_budget: config.monthly_token_budget,
+            slack,
+            admin_email,
+        }
+    }
+
+    /// Estimated cost in USD of a response, from the configured model prices.
+    ///
+    /// Returns zero for models without a configured price.
+    #[must_use]
+    pub fn estimate_cost(&self, model: &str, usage: &Usage) -> Decimal {
+        let Some(price) = self.model_prices.get(model) else {
+            warn!(model, "No token price configured for Claude model");
+            return Decimal::ZERO;
+        };
+
+        let per_token = Decimal::from(1_000_000);
+        let cost = Decimal::from(usage.input_tokens) * price.input_per_mtok / per_token
+            + Decimal::from(usage.output_tokens) * price.output_per_mtok / per_token;
+        cost.round_dp(6)
+    }
+
+    /// Record the usage of one Claude response, with the prompt template
+    /// version the request was sent with.
+    ///
+    /// Failures are logged rather than returned so that usage tracking never
+    /// interrupts a chat.
+    pub async fn record(
+        &self,
+        conversation_id: Option<ChatSessionId>,
+        prompt: Option<&PromptTemplate>,
+        model: &str,
+        usage: &Usage,
+    ) {
+        let record = NewClaudeUsage {
+            admin_user_email: self.admin_email.clone(),
+            model: model.to_string(),
+            input_tokens: i32::try_from(usage.input_tokens).unwrap_or(i32::MAX),
+            output_tokens: i32::try_from(usage.output_tokens).unwrap_or(i32::MAX),
+            estimated_cost_usd: self.estimate_cost(model, usage),
+            conversation_id,
+            prompt_name: prompt.map(|p| p.name.clone()),
+            prompt_version: prompt.map(|p| p.version),
+        };
+
+        let repo = ClaudeUsageRepository::new(&self.pool);
+        if let Err(e) = repo.insert(&record).await {
+            warn!(error = %e, "Failed to record Claude usage");
+            return;
+        }
+
+        let tokens = u64::from(usage.input_tokens) + u64::from(usage.output_tokens);
+        self.check_budget(tokens).await;
+    }
+
+    /// Alert Slack if the last `tokens` pushed this user past the alert threshold.
+    async fn check_budget(&self, tokens: u64) {
+        let Some(budget) = self.monthly_token_budget else {
+            return;
+        };
+
+        let repo = ClaudeUsageRepository::new(&self.pool);
+        let used = match repo
+            .tokens_since(&self.admin_email, start_of_month(Utc::now()))
+            .await
+        {
+            Ok(used) => u64::try_from(used).unwrap_or_default(),
+            Err(e) => {
+                warn!(error = %e, "Failed to load monthly Claude usage");
+                return;
+            }
+        };
+
+        if !crosses_alert_threshold(used.saturating_sub(tokens), used, budget) {
+            return;
+        }
+
+        info!(
+            email = %self.admin_email,
+            used,
+            budget,
+            "Admin user passed Claude token budget alert threshold"
+        );
+
+        let Some(slack) = &self.slack else {
+            return;
+        };
+        let text = format!(
+            ":warning: *Claude usage alert*: {} has used {used} of their {budget} token \
+             monthly budget ({}%).",
+            self.admin_email,
+            used.saturating_mul(100) / budget.max(1),
+        );
+        if let Err(e) = slack.post_text(slack.default_channel(), &text).await {
+            warn!(error = %e, "Failed to send Claude budget alert to Slack");
+        }
+    }
+}
+
+/// Midnight UTC on the first day of the month containing `now`.
+#[must_use]
+pub fn start_of_month(now: DateTime<Utc>) -> DateTime<Utc> {
+    now.date_naive()
+        .with_day(1)
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .map_or(now, |start| start.and_utc())
+}
+
+/// Whether usage going from `before` to `after` tokens crossed the alert
+/// threshold of `budget`, so each user is alerted once per month.
+const fn crosses_alert_threshold(before: u64, after: u64, budget: u64) -> bool {
+    let threshold = budget.saturating_mul(BUDGET_ALERT_PERCENT);
+    before.saturating_mul(100) < threshold && after.saturating_mul(100) >= threshold
+}
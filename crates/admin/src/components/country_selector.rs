@@ -0,0 +1,85 @@
+//! Country and province selector component.
+//!
+//! Renders a `country` select and a `province` select. Changing the country
+//! replaces the province select with the provinces of the new country from
+//! `GET /api/provinces?country=XX`.
+//!
+//! Include `components/country_selector.html` in a form whose template has a
+//! `country_selector` field.
+
+use crate::shopify::{Country, Province};
+
+/// Country option in the selector.
+#[derive(Debug, Clone)]
+pub struct CountryOption {
+    /// Country code (ISO 3166-1 alpha-2).
+    pub code: String,
+    /// Country name.
+    pub name: String,
+}
+
+/// State of a country and province selector.
+#[derive(Debug, Clone, Default)]
+pub struct CountrySelector {
+    /// Every country that can be selected.
+    pub countries: Vec<CountryOption>,
+    /// Selected country code.
+    pub selected_country: Option<String>,
+    /// Provinces of the selected country.
+    pub provinces: Vec<Province>,
+    /// Selected province code.
+    pub selected_province: Option<String>,
+}
+
+impl CountrySelector {
+    /// Create a selector over `countries` with the given selection.
+    #[must_use]
+    pub fn new(
+        countries: &[Country],
+        selected_country: Option<&str>,
+        selected_province: Option<&str>,
+    ) -> Self {
+        Self {
+            countries: countries
+                .iter()
+                .map(|c| CountryOption {
+                    code: c.code.clone(),
+                    name: c.name.clone(),
+                })
+                .collect(),
+            provinces: Self::provinces_of(countries, selected_country),
+            selected_country: selected_country.map(ToString::to_string),
+            selected_province: selected_province.map(ToString::to_string),
+        }
+    }
+
+    /// Create a selector holding only the provinces of `country`, for
+    /// re-rendering the province select.
+    #[must_use]
+    pub fn provinces_only(countries: &[Country], country: Option<&str>) -> Self {
+        Self {
+            provinces: Self::provinces_of(countries, country),
+            selected_country: country.map(ToString::to_string),
+            ..Self::default()
+        }
+    }
+
+    /// Provinces of the country with code `code` (case-insensitive).
+    fn provinces_of(countries: &[Country], code: Option<&str>) -> Vec<Province> {
+        code.and_then(|code| countries.iter().find(|c| c.code.eq_ignore_ascii_case(code)))
+            .map(|c| c.provinces.clone())
+            .unwrap_or_default()
+    }
+
+    /// Whether `code` is the selected country.
+    #[must_use]
+    pub fn is_country_selected(&self, code: &str) -> bool {
+        self.selected_country.as_deref() == Some(code)
+    }
+
+    /// Whether `code` is the selected province.
+    #[must_use]
+    pub fn is_province_selected(&self, code: &str) -> bool {
+        self.selected_province.as_deref() == Some(code)
+    }
+}
@@ -1,5 +1,7 @@
 //! Reusable UI components for admin templates.
 
+pub mod country_selector;
 pub mod data_table;
 
+pub use country_selector::*;
 pub use data_table::*;
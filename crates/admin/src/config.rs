@@ -16,21 +16,35 @@
 //! - `SMTP_FROM` - Email sender address
 //!
 //! ## Optional
+//! - `ADMIN_DATABASE_READ_URL` - `PostgreSQL` read replica connection string (default: primary)
 //! - `ADMIN_HOST` - Bind address (default: 127.0.0.1)
 //! - `ADMIN_PORT` - Listen port (default: 3001)
 //! - `SHOPIFY_API_VERSION` - API version (default: 2026-01)
 //! - `CLAUDE_MODEL` - Claude model ID (default: claude-sonnet-4-20250514)
+//! - `CLAUDE_MODEL_PRICES` - Per-model token prices in USD per million tokens,
+//!   as `model=input/output` pairs separated by commas (overrides built-in prices)
+//! - `CLAUDE_MONTHLY_TOKEN_BUDGET` - Monthly token budget per admin user (Slack alert at 80%)
+//! - `CLAUDE_PROMPT_TEMPLATE` - Name of the chat system prompt template (default: default)
 //! - `OPENAI_API_KEY` - `OpenAI` API key (for embeddings, required for tool selection)
 //! - `SMTP_PORT` - SMTP port (default: 587)
 //! - `SENTRY_DSN` - Sentry error tracking DSN
 //! - `KLAVIYO_API_KEY` - Klaviyo private API key (for newsletter campaigns)
 //! - `KLAVIYO_LIST_ID` - Klaviyo newsletter list ID
+//! - `INVENTORY_LEAD_TIME_DAYS` - Supplier lead time used to size reorder suggestions (default: 14)
 //!
 //! ## Optional (Slack - enables write operation confirmations)
 //! - `SLACK_BOT_TOKEN` - Slack bot token (xoxb-...)
 //! - `SLACK_SIGNING_SECRET` - Slack app signing secret
 //! - `SLACK_CHANNEL_ID` - Default channel for confirmation messages
 //!
+//! ## Optional (`ShipHero` webhooks)
+//! - `SHIPHERO_WEBHOOK_SECRET` - Shared secret for verifying `ShipHero` webhook signatures
+//!
+//! ## Optional (Shopify Flow - triggers without a handle are not sent)
+//! - `SHOPIFY_FLOW_ORDER_FLAGGED_HANDLE` - Trigger handle for orders held as high risk of fraud
+//! - `SHOPIFY_FLOW_LOW_INVENTORY_HANDLE` - Trigger handle for low inventory alerts
+//! - `SHOPIFY_FLOW_HIGH_VALUE_CUSTOMER_HANDLE` - Trigger handle for customers tagged VIP
+//!
 //! ## Optional (TLS)
 //! - `ADMIN_TLS_CERT` - PEM-encoded certificate chain
 //! - `ADMIN_TLS_KEY` - PEM-encoded private key
@@ -38,12 +52,22 @@
 use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
 
+use rust_decimal::Decimal;
 use secrecy::{ExposeSecret, SecretString};
 use thiserror::Error;
 
 const MIN_SESSION_SECRET_LENGTH: usize = 32;
 const MIN_ENTROPY_BITS_PER_CHAR: f64 = 3.3;
 const DEFAULT_CLAUDE_MODEL: &str = "claude-sonnet-4-20250514";
+const DEFAULT_CLAUDE_PROMPT_TEMPLATE: &str = "default";
+const DEFAULT_INVENTORY_LEAD_TIME_DAYS: &str = "14";
+
+/// Built-in Claude token prices: (model, USD per million input tokens, USD per million output tokens).
+const DEFAULT_CLAUDE_MODEL_PRICES: &[(&str, i64, i64)] = &[
+    ("claude-sonnet-4-20250514", 3, 15),
+    ("claude-opus-4-20250514", 15, 75),
+    ("claude-haiku-4-5-20251001", 1, 5),
+];
 
 /// Blocklist of common placeholder patterns (case-insensitive)
 const PLACEHOLDER_PATTERNS: &[&str] = &[
@@ -79,6 +103,8 @@ pub enum ConfigError {
 pub struct AdminConfig {
     /// `PostgreSQL` database connection URL (contains password)
     pub database_url: SecretString,
+    /// `PostgreSQL` read replica connection URL (contains password)
+    pub database_read_url: Option<SecretString>,
     /// IP address to bind the server to
     pub host: IpAddr,
     /// Port to listen on
@@ -95,10 +121,16 @@ pub struct AdminConfig {
     pub openai: Option<OpenAIConfig>,
     /// Slack configuration for write operation confirmations (optional)
     pub slack: Option<SlackConfig>,
+    /// `ShipHero` webhook shared secret (optional, enables fulfillment webhooks)
+    pub shiphero_webhook_secret: Option<SecretString>,
+    /// Shopify Flow trigger handles
+    pub flow: FlowConfig,
     /// Email configuration
     pub email: EmailConfig,
     /// Klaviyo configuration (optional - for newsletter campaigns)
     pub klaviyo: Option<KlaviyoConfig>,
+    /// Days between placing and receiving a stock reorder
+    pub inventory_lead_time_days: u32,
     /// Sentry DSN for error tracking
     pub sentry_dsn: Option<String>,
     /// Sentry environment (e.g., "development", "staging", "production")
@@ -120,6 +152,9 @@ pub struct ShopifyAdminConfig {
     /// Shopify store domain (e.g., your-store.myshopify.com)
     pub store: String,
     /// Shopify API version (e.g., 2026-01)
+    ///
+    /// The admin server refuses to start unless this matches
+    /// [`SHOPIFY_API_VERSION`](crate::shopify::SHOPIFY_API_VERSION).
     pub api_version: String,
     /// OAuth client ID (HIGH PRIVILEGE - full store access)
     pub client_id: String,
@@ -147,6 +182,12 @@ pub struct ClaudeConfig {
     pub api_key: SecretString,
     /// Model ID (e.g., claude-sonnet-4-20250514)
     pub model: String,
+    /// Token prices by model ID, used to estimate API costs
+    pub model_prices: HashMap<String, ModelPrice>,
+    /// Monthly input + output token budget per admin user
+    pub monthly_token_budget: Option<u64>,
+    /// Name of the system prompt template used by the chat assistant
+    pub prompt_template: String,
 }
 
 impl std::fmt::Debug for ClaudeConfig {
@@ -154,10 +195,22 @@ impl std::fmt::Debug for ClaudeConfig {
         f.debug_struct("ClaudeConfig")
             .field("api_key", &"[REDACTED]")
             .field("model", &self.model)
+            .field("model_prices", &self.model_prices)
+            .field("monthly_token_budget", &self.monthly_token_budget)
+            .field("prompt_template", &self.prompt_template)
             .finish()
     }
 }
 
+/// Claude token prices for one model, in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelPrice {
+    /// Price per million input tokens
+    pub input_per_mtok: Decimal,
+    /// Price per million output tokens
+    pub output_per_mtok: Decimal,
+}
+
 /// `OpenAI` API configuration for embeddings.
 ///
 /// Used for tool selection via semantic similarity search.
@@ -229,6 +282,33 @@ impl std::fmt::Debug for EmailConfig {
     }
 }
 
+/// Shopify Flow trigger configuration.
+///
+/// Each handle names the Flow trigger extension a [`FlowTrigger`] is sent
+/// to; triggers without a handle are skipped. Triggers are sent with the
+/// Admin API OAuth token, so no separate credentials are needed.
+///
+/// [`FlowTrigger`]: crate::shopify::FlowTrigger
+#[derive(Debug, Clone, Default)]
+pub struct FlowConfig {
+    /// Trigger handle for orders flagged for review
+    pub order_flagged: Option<String>,
+    /// Trigger handle for low inventory alerts
+    pub low_inventory: Option<String>,
+    /// Trigger handle for high value customers
+    pub high_value_customer: Option<String>,
+}
+
+impl FlowConfig {
+    fn from_env() -> Self {
+        Self {
+            order_flagged: get_optional_env("SHOPIFY_FLOW_ORDER_FLAGGED_HANDLE"),
+            low_inventory: get_optional_env("SHOPIFY_FLOW_LOW_INVENTORY_HANDLE"),
+            high_value_customer: get_optional_env("SHOPIFY_FLOW_HIGH_VALUE_CUSTOMER_HANDLE"),
+        }
+    }
+}
+
 /// Klaviyo API configuration for newsletter campaigns.
 ///
 /// Implements `Debug` manually to redact the API key.
@@ -323,6 +403,7 @@ impl AdminConfig {
         let _ = dotenvy::dotenv();
 
         let database_url = get_database_url("ADMIN_DATABASE_URL")?;
+        let database_read_url = get_optional_env("ADMIN_DATABASE_READ_URL").map(SecretString::from);
         let host = get_env_or_default("ADMIN_HOST", "127.0.0.1")
             .parse::<IpAddr>()
             .map_err(|e| ConfigError::InvalidEnvVar("ADMIN_HOST".to_string(), e.to_string()))?;
@@ -337,8 +418,24 @@ impl AdminConfig {
         let claude = ClaudeConfig::from_env()?;
         let openai = OpenAIConfig::from_env();
         let slack = SlackConfig::from_env();
+        let shiphero_webhook_secret = get_optional_env("SHIPHERO_WEBHOOK_SECRET").map(|secret| {
+            if let Err(e) = validate_secret_strength(&secret, "SHIPHERO_WEBHOOK_SECRET") {
+                tracing::warn!("SHIPHERO_WEBHOOK_SECRET validation warning: {e}");
+            }
+            SecretString::from(secret)
+        });
+        let flow = FlowConfig::from_env();
         let email = EmailConfig::from_env()?;
         let klaviyo = KlaviyoConfig::from_env()?;
+        let inventory_lead_time_days =
+            get_env_or_default("INVENTORY_LEAD_TIME_DAYS", DEFAULT_INVENTORY_LEAD_TIME_DAYS)
+                .parse::<u32>()
+                .map_err(|e| {
+                    ConfigError::InvalidEnvVar(
+                        "INVENTORY_LEAD_TIME_DAYS".to_string(),
+                        e.to_string(),
+                    )
+                })?;
         let sentry_dsn = get_optional_env("SENTRY_DSN");
         let sentry_environment = get_optional_env("SENTRY_ENVIRONMENT");
         let sentry_sample_rate = get_optional_env("SENTRY_SAMPLE_RATE")
@@ -351,6 +448,7 @@ impl AdminConfig {
 
         Ok(Self {
             database_url,
+            database_read_url,
             host,
             port,
             base_url,
@@ -359,8 +457,11 @@ impl AdminConfig {
             claude,
             openai,
             slack,
+            shiphero_webhook_secret,
+            flow,
             email,
             klaviyo,
+            inventory_lead_time_days,
             sentry_dsn,
             sentry_environment,
             sentry_sample_rate,
@@ -404,13 +505,33 @@ impl AdminConfig {
     pub const fn slack(&self) -> Option<&SlackConfig> {
         self.slack.as_ref()
     }
+
+    /// Returns the `ShipHero` webhook secret, if available.
+    ///
+    /// Returns `None` if `SHIPHERO_WEBHOOK_SECRET` was not set, which
+    /// disables the `ShipHero` fulfillment webhook.
+    #[must_use]
+    pub const fn shiphero_webhook_secret(&self) -> Option<&SecretString> {
+        self.shiphero_webhook_secret.as_ref()
+    }
 }
 
 impl ShopifyAdminConfig {
-    fn from_env() -> Result<Self, ConfigError> {
+    /// Load Shopify Admin API configuration from environment variables.
+    ///
+    /// Used on its own by tools (such as the CLI) that only talk to Shopify.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError` if a required variable is missing or the client
+    /// secret fails validation.
+    pub fn from_env() -> Result<Self, ConfigError> {
         Ok(Self {
             store: get_required_env("SHOPIFY_STORE")?,
-            api_version: get_env_or_default("SHOPIFY_API_VERSION", "2026-01"),
+            api_version: get_env_or_default(
+                "SHOPIFY_API_VERSION",
+                crate::shopify::SHOPIFY_API_VERSION,
+            ),
             client_id: get_required_env("SHOPIFY_ADMIN_CLIENT_ID")?,
             client_secret: get_validated_secret("SHOPIFY_ADMIN_CLIENT_SECRET")?,
         })
@@ -422,10 +543,76 @@ impl ClaudeConfig {
         Ok(Self {
             api_key: get_validated_secret("CLAUDE_API_KEY")?,
             model: get_env_or_default("CLAUDE_MODEL", DEFAULT_CLAUDE_MODEL),
+            model_prices: parse_model_prices(get_optional_env("CLAUDE_MODEL_PRICES").as_deref())?,
+            monthly_token_budget: get_optional_env("CLAUDE_MONTHLY_TOKEN_BUDGET")
+                .map(|s| {
+                    s.parse::<u64>().map_err(|e| {
+                        ConfigError::InvalidEnvVar(
+                            "CLAUDE_MONTHLY_TOKEN_BUDGET".to_string(),
+                            e.to_string(),
+                        )
+                    })
+                })
+                .transpose()?,
+            prompt_template: get_env_or_default(
+                "CLAUDE_PROMPT_TEMPLATE",
+                DEFAULT_CLAUDE_PROMPT_TEMPLATE,
+            ),
         })
     }
 }
 
+/// Build the model price table from the built-in prices and `CLAUDE_MODEL_PRICES`.
+///
+/// Overrides are `model=input/output` pairs separated by commas, e.g.
+/// `claude-sonnet-4-20250514=3/15,claude-opus-4-20250514=15/75`.
+fn parse_model_prices(overrides: Option<&str>) -> Result<HashMap<String, ModelPrice>, ConfigError> {
+    let invalid = |msg: String| ConfigError::InvalidEnvVar("CLAUDE_MODEL_PRICES".to_string(), msg);
+
+    let mut prices: HashMap<String, ModelPrice> = DEFAULT_CLAUDE_MODEL_PRICES
+        .iter()
+        .map(|&(model, input, output)| {
+            (
+                model.to_string(),
+                ModelPrice {
+                    input_per_mtok: Decimal::from(input),
+                    output_per_mtok: Decimal::from(output),
+                },
+            )
+        })
+        .collect();
+
+    for entry in overrides
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+    {
+        let (model, price) = entry
+            .split_once('=')
+            .ok_or_else(|| invalid(format!("expected model=input/output, got '{entry}'")))?;
+        let (input, output) = price
+            .split_once('/')
+            .ok_or_else(|| invalid(format!("expected input/output prices, got '{price}'")))?;
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<Decimal>()
+                .map_err(|e| invalid(format!("invalid price '{value}': {e}")))
+        };
+
+        prices.insert(
+            model.trim().to_string(),
+            ModelPrice {
+                input_per_mtok: parse(input)?,
+                output_per_mtok: parse(output)?,
+            },
+        );
+    }
+
+    Ok(prices)
+}
+
 impl OpenAIConfig {
     /// Load `OpenAI` configuration from environment.
     ///
@@ -673,6 +860,7 @@ mod tests {
     fn test_socket_addr() {
         let config = AdminConfig {
             database_url: SecretString::from("postgres://localhost/test"),
+            database_read_url: None,
             host: "127.0.0.1".parse().unwrap(),
             port: 3001,
             base_url: "http://localhost:3001".to_string(),
@@ -686,9 +874,14 @@ mod tests {
             claude: ClaudeConfig {
                 api_key: SecretString::from("sk-ant-test"),
                 model: DEFAULT_CLAUDE_MODEL.to_string(),
+                model_prices: HashMap::new(),
+                monthly_token_budget: None,
+                prompt_template: DEFAULT_CLAUDE_PROMPT_TEMPLATE.to_string(),
             },
             openai: None,
             slack: None,
+            shiphero_webhook_secret: None,
+            flow: FlowConfig::default(),
             email: EmailConfig {
                 smtp_host: "smtp.example.com".to_string(),
                 smtp_port: 587,
@@ -697,6 +890,7 @@ mod tests {
                 from_address: "admin@example.com".to_string(),
             },
             klaviyo: None,
+            inventory_lead_time_days: 14,
             sentry_dsn: None,
             sentry_environment: None,
             sentry_sample_rate: 1.0,
@@ -714,6 +908,37 @@ mod tests {
         assert_eq!(DEFAULT_CLAUDE_MODEL, "claude-sonnet-4-20250514");
     }
 
+    #[test]
+    fn test_parse_model_prices_defaults() {
+        let prices = parse_model_prices(None).unwrap();
+        let sonnet = prices.get(DEFAULT_CLAUDE_MODEL).unwrap();
+        assert_eq!(sonnet.input_per_mtok, Decimal::from(3));
+        assert_eq!(sonnet.output_per_mtok, Decimal::from(15));
+    }
+
+    #[test]
+    fn test_parse_model_prices_overrides() {
+        let prices = parse_model_prices(Some(
+            "claude-sonnet-4-20250514=2.5/12, custom-model=0.25/1.25",
+        ))
+        .unwrap();
+
+        let sonnet = prices.get("claude-sonnet-4-20250514").unwrap();
+        assert_eq!(sonnet.input_per_mtok, "2.5".parse::<Decimal>().unwrap());
+        assert_eq!(sonnet.output_per_mtok, Decimal::from(12));
+
+        let custom = prices.get("custom-model").unwrap();
+        assert_eq!(custom.input_per_mtok, "0.25".parse::<Decimal>().unwrap());
+        assert_eq!(custom.output_per_mtok, "1.25".parse::<Decimal>().unwrap());
+    }
+
+    #[test]
+    fn test_parse_model_prices_invalid() {
+        assert!(parse_model_prices(Some("claude-sonnet-4-20250514")).is_err());
+        assert!(parse_model_prices(Some("claude-sonnet-4-20250514=3")).is_err());
+        assert!(parse_model_prices(Some("claude-sonnet-4-20250514=three/15")).is_err());
+    }
+
     #[test]
     fn test_shopify_admin_config_debug_redacts_secrets() {
         let config = ShopifyAdminConfig {
@@ -740,6 +965,9 @@ mod tests {
         let config = ClaudeConfig {
             api_key: SecretString::from("sk-ant-super-secret-key"),
             model: "claude-sonnet-4-20250514".to_string(),
+            model_prices: HashMap::new(),
+            monthly_token_budget: None,
+            prompt_template: DEFAULT_CLAUDE_PROMPT_TEMPLATE.to_string(),
         };
 
         let debug_output = format!("{config:?}");
@@ -0,0 +1,156 @@
+//! Database operations for Claude API usage tracking.
+//!
+//! All queries use sqlx macros for compile-time verification. sqlx expects
+//! `time` types for `timestamptz` parameters in this workspace, so chrono
+//! cutoffs are bound with `as _` (chrono encodes to the same type).
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+use naked_pineapple_core::Email;
+
+use super::RepositoryError;
+use crate::models::claude_usage::{DailyClaudeUsage, MonthlyClaudeUsage, NewClaudeUsage};
+
+/// Repository for Claude usage records.
+pub struct ClaudeUsageRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> ClaudeUsageRepository<'a> {
+    /// Create a new Claude usage repository.
+    #[must_use]
+    pub const fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record token usage for one Claude API response.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn insert(&self, usage: &NewClaudeUsage) -> Result<(), RepositoryError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO admin.claude_usage
+                (admin_user_email, model, input_tokens, output_tokens,
+                 estimated_cost_usd, conversation_id, prompt_name, prompt_version)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            usage.admin_user_email.as_str(),
+            usage.model,
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.estimated_cost_usd,
+            usage.conversation_id.map(|id| id.as_i32()),
+            usage.prompt_name,
+            usage.prompt_version,
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Total input + output tokens used by an admin user since `since`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn tokens_since(
+        &self,
+        email: &Email,
+        since: DateTime<Utc>,
+    ) -> Result<i64, RepositoryError> {
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(SUM(input_tokens::BIGINT + output_tokens::BIGINT), 0)::BIGINT AS "total!"
+            FROM admin.claude_usage
+            WHERE admin_user_email = $1 AND created_at >= $2
+            "#,
+            email.as_str(),
+            since as _
+        )
+        .fetch_one(self.pool)
+        .await?;
+
+        Ok(total)
+    }
+
+    /// Usage per admin user per day (UTC) since `since`, newest day first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn daily_usage_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<DailyClaudeUsage>, RepositoryError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                (created_at AT TIME ZONE 'utc')::DATE AS "day!: NaiveDate",
+                admin_user_email::TEXT AS "admin_user_email!",
+                COUNT(*) AS "requests!",
+                SUM(input_tokens)::BIGINT AS "input_tokens!",
+                SUM(output_tokens)::BIGINT AS "output_tokens!",
+                SUM(estimated_cost_usd) AS "estimated_cost_usd!: Decimal"
+            FROM admin.claude_usage
+            WHERE created_at >= $1
+            GROUP BY 1, 2
+            ORDER BY 1 DESC, 2
+            "#,
+            since as _
+        )
+        .fetch_all(self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| DailyClaudeUsage {
+                day: row.day,
+                admin_user_email: row.admin_user_email,
+                requests: row.requests,
+                input_tokens: row.input_tokens,
+                output_tokens: row.output_tokens,
+                estimated_cost_usd: row.estimated_cost_usd,
+            })
+            .collect())
+    }
+
+    /// Usage per admin user since `since`, heaviest users first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn usage_by_user_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<MonthlyClaudeUsage>, RepositoryError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                admin_user_email::TEXT AS "admin_user_email!",
+                SUM(input_tokens::BIGINT + output_tokens::BIGINT)::BIGINT AS "total_tokens!",
+                SUM(estimated_cost_usd) AS "estimated_cost_usd!: Decimal"
+            FROM admin.claude_usage
+            WHERE created_at >= $1
+            GROUP BY 1
+            ORDER BY 2 DESC
+            "#,
+            since as _
+        )
+        .fetch_all(self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MonthlyClaudeUsage {
+                admin_user_email: row.admin_user_email,
+                total_tokens: row.total_tokens,
+                estimated_cost_usd: row.estimated_cost_usd,
+            })
+            .collect())
+    }
+}
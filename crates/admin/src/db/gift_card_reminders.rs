@@ -0,0 +1,81 @@
+//! Gift card expiry reminder repository for database operations.
+//!
+//! Records which gift cards have been sent an expiry reminder, per expiry
+//! date, so the daily reminder job sends each one once.
+//!
+//! sqlx expects `time` types for `date` parameters in this workspace, so
+//! expiry dates are bound with `as _` (chrono encodes to the same type).
+
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+use super::RepositoryError;
+
+/// Repository for sent gift card expiry reminders.
+pub struct GiftCardReminderRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> GiftCardReminderRepository<'a> {
+    /// Create a new gift card reminder repository.
+    #[must_use]
+    pub const fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Check whether a reminder was already sent for a gift card's expiry date.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn was_sent(
+        &self,
+        gift_card_id: &str,
+        expires_on: NaiveDate,
+    ) -> Result<bool, RepositoryError> {
+        let sent = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM admin.gift_card_reminders_sent
+                WHERE gift_card_id = $1 AND expires_on = $2
+            ) AS "sent!"
+            "#,
+            gift_card_id,
+            expires_on as _
+        )
+        .fetch_one(self.pool)
+        .await?;
+
+        Ok(sent)
+    }
+
+    /// Record that a reminder was sent for a gift card's expiry date.
+    ///
+    /// Recording the same reminder twice is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn record_sent(
+        &self,
+        gift_card_id: &str,
+        expires_on: NaiveDate,
+        customer_email: &str,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO admin.gift_card_reminders_sent (gift_card_id, expires_on, customer_email)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (gift_card_id, expires_on) DO NOTHING
+            "#,
+            gift_card_id,
+            expires_on as _,
+            customer_email
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
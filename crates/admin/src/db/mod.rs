@@ -10,8 +10,15 @@
 //! - `admin_invite` - Email allowlist for registration
 //! - `chat_session` - Claude AI chat sessions
 //! - `chat_message` - Chat message history (JSONB content)
+//! - `claude_prompt_template` - Seeded Claude system prompt versions
+//! - `claude_usage` - Claude API token usage and estimated cost per request
+//! - `gift_card_reminders_sent` - Gift card expiry reminders already sent
+//! - `reconciliation_session` - Physical inventory counts at a location
+//! - `reconciliation_counts` - Counted vs. system quantity per SKU
+//! - `shiphero_order_mappings` - `ShipHero` order ID to Shopify order ID
 //! - `shopify_token` - Encrypted OAuth tokens (if needed)
 //! - `settings` - Application settings (JSONB)
+//! - `user_preferences` - Per-admin, per-page preferences (e.g., export columns)
 //!
 //! # Migrations
 //!
@@ -23,28 +30,46 @@
 pub mod admin_invites;
 pub mod admin_users;
 pub mod chat;
+pub mod claude_usage;
+pub mod gift_card_reminders;
 pub mod inventory_lot;
 pub mod manufacturing;
 pub mod pending_actions;
+pub mod prompt_templates;
+pub mod reconciliation;
+pub mod sessions;
 pub mod settings;
 pub mod shiphero;
+pub mod shiphero_orders;
 pub mod shopify;
 pub mod tool_examples;
+pub mod user_preferences;
 
 use std::time::Duration;
 
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
 use secrecy::ExposeSecret;
 use sqlx::PgPool;
-use sqlx::postgres::PgPoolOptions;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use thiserror::Error;
 
+use crate::state::AppState;
+
 pub use admin_invites::{AdminInvite, AdminInviteRepository};
 pub use admin_users::AdminUserRepository;
 pub use chat::ChatRepository;
+pub use claude_usage::ClaudeUsageRepository;
+pub use gift_card_reminders::GiftCardReminderRepository;
 pub use inventory_lot::InventoryLotRepository;
 pub use manufacturing::ManufacturingRepository;
+pub use prompt_templates::PromptTemplateRepository;
+pub use reconciliation::ReconciliationRepository;
+pub use sessions::SessionRepository;
 pub use shiphero::{SaveCredentialsParams, ShipHeroCredentials, ShipHeroCredentialsRepository};
+pub use shiphero_orders::ShipHeroOrderMappingRepository;
 pub use shopify::ShopifyTokenRepository;
+pub use user_preferences::UserPreferencesRepository;
 
 /// Errors that can occur during repository operations.
 #[derive(Debug, Error)]
@@ -87,3 +112,51 @@ pub async fn create_pool(database_url: &secrecy::SecretString) -> Result<PgPool,
         .connect(database_url.expose_secret())
         .await
 }
+
+/// Create a connection pool for a read replica.
+///
+/// Every connection defaults to read-only transactions, so a write sent to
+/// the replica pool fails instead of silently going to the wrong server.
+///
+/// # Arguments
+///
+/// * `database_url` - Read replica connection string (wrapped in `SecretString`)
+///
+/// # Errors
+///
+/// Returns `sqlx::Error` if the URL is invalid or the connection cannot be
+/// established.
+pub async fn create_read_pool(database_url: &secrecy::SecretString) -> Result<PgPool, sqlx::Error> {
+    let options = database_url
+        .expose_secret()
+        .parse::<PgConnectOptions>()?
+        .options([("default_transaction_read_only", "on")]);
+
+    PgPoolOptions::new()
+        .max_connections(20)
+        .min_connections(2)
+        .acquire_timeout(Duration::from_secs(10))
+        .connect_with(options)
+        .await
+}
+
+/// Extractor for the read-only connection pool.
+///
+/// Yields the read replica pool when `ADMIN_DATABASE_READ_URL` is set and
+/// the primary pool otherwise. Use it for handlers that only `SELECT`; writes
+/// go through [`AppState::pool`].
+///
+/// Replicas can lag behind the primary, so a read that must see a write made
+/// earlier in the same request should also use [`AppState::pool`].
+pub struct ReadPool(pub PgPool);
+
+impl FromRequestParts<AppState> for ReadPool {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(
+        _parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self(state.read_pool().clone()))
+    }
+}
@@ -273,28 +273,20 @@ pub async fn mark_failed(
 ///
 /// Returns error if the database query fails.
 pub async fn get_expiring_actions(pool: &PgPool) -> Result<Vec<PendingAction>, RepositoryError> {
-    let actions = sqlx::query_as::<_, PendingAction>(
-        r"
+    let actions = sqlx::query_as!(
+        PendingAction,
+        r#"
         SELECT
-            id,
-            chat_session_id,
-            chat_message_id,
-            admin_user_id,
-            tool_name,
-            tool_input,
-            status,
-            slack_message_ts,
-            slack_channel_id,
-            result,
-            error_message,
-            approved_by,
-            rejected_by,
-            created_at,
-            resolved_at,
-            expires_at
+            id, chat_session_id, chat_message_id, admin_user_id,
+            tool_name, tool_input, status as "status: ActionStatus",
+            slack_message_ts, slack_channel_id, result, error_message,
+            approved_by, rejected_by,
+            created_at as "created_at: DateTime<Utc>",
+            resolved_at as "resolved_at: DateTime<Utc>",
+            expires_at as "expires_at: DateTime<Utc>"
         FROM admin.pending_actions
         WHERE status = 'pending' AND expires_at < NOW()
-        ",
+        "#,
     )
     .fetch_all(pool)
     .await?;
@@ -332,20 +324,23 @@ pub async fn get_pending_actions_for_session(
     pool: &PgPool,
     chat_session_id: i32,
 ) -> Result<Vec<PendingAction>, RepositoryError> {
-    let actions = sqlx::query_as::<_, PendingAction>(
-        r"
+    let actions = sqlx::query_as!(
+        PendingAction,
+        r#"
         SELECT
             id, chat_session_id, chat_message_id, admin_user_id,
-            tool_name, tool_input, status,
+            tool_name, tool_input, status as "status: ActionStatus",
             slack_message_ts, slack_channel_id, result, error_message,
             approved_by, rejected_by,
-            created_at, resolved_at, expires_at
+            created_at as "created_at: DateTime<Utc>",
+            resolved_at as "resolved_at: DateTime<Utc>",
+            expires_at as "expires_at: DateTime<Utc>"
         FROM admin.pending_actions
         WHERE chat_session_id = $1
         ORDER BY created_at DESC
-        ",
+        "#,
+        chat_session_id,
     )
-    .bind(chat_session_id)
     .fetch_all(pool)
     .await?;
 
@@ -361,20 +356,23 @@ pub async fn get_pending_actions_for_admin(
     pool: &PgPool,
     admin_user_id: i32,
 ) -> Result<Vec<PendingAction>, RepositoryError> {
-    let actions = sqlx::query_as::<_, PendingAction>(
-        r"
+    let actions = sqlx::query_as!(
+        PendingAction,
+        r#"
         SELECT
             id, chat_session_id, chat_message_id, admin_user_id,
-            tool_name, tool_input, status,
+            tool_name, tool_input, status as "status: ActionStatus",
             slack_message_ts, slack_channel_id, result, error_message,
             approved_by, rejected_by,
-            created_at, resolved_at, expires_at
+            created_at as "created_at: DateTime<Utc>",
+            resolved_at as "resolved_at: DateTime<Utc>",
+            expires_at as "expires_at: DateTime<Utc>"
         FROM admin.pending_actions
         WHERE admin_user_id = $1 AND status = 'pending'
         ORDER BY created_at DESC
-        ",
+        "#,
+        admin_user_id,
     )
-    .bind(admin_user_id)
     .fetch_all(pool)
     .await?;
 
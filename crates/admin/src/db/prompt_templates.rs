@@ -0,0 +1,69 @@
+//! Claude prompt template repository for database operations.
+//!
+//! Stores prompt templates seeded with `np-cli seed prompts`. Versions are
+//! never overwritten, so `claude_usage` rows keep pointing at the prompt
+//! text they were sent with.
+
+use sqlx::PgPool;
+
+use super::RepositoryError;
+use crate::models::PromptTemplate;
+
+/// Repository for seeded Claude prompt templates.
+pub struct PromptTemplateRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> PromptTemplateRepository<'a> {
+    /// Create a new prompt template repository.
+    #[must_use]
+    pub const fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// The highest version of every seeded template.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn latest(&self) -> Result<Vec<PromptTemplate>, RepositoryError> {
+        let templates = sqlx::query_as!(
+            PromptTemplate,
+            r#"
+            SELECT DISTINCT ON (name) name, version, system_prompt, description
+            FROM admin.claude_prompt_template
+            ORDER BY name, version DESC
+            "#
+        )
+        .fetch_all(self.pool)
+        .await?;
+
+        Ok(templates)
+    }
+
+    /// Insert a template version.
+    ///
+    /// Returns `false` if that name and version already exist; the stored
+    /// version is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn insert(&self, template: &PromptTemplate) -> Result<bool, RepositoryError> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO admin.claude_prompt_template (name, version, system_prompt, description)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (name, version) DO NOTHING
+            "#,
+            template.name,
+            template.version,
+            template.system_prompt,
+            template.description
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
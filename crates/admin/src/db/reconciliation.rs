@@ -0,0 +1,293 @@
+//! Database operations for inventory count reconciliation.
+//!
+//! All queries use sqlx macros for compile-time verification.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::RepositoryError;
+use crate::models::reconciliation::{
+    NewReconciliationCount, ReconciliationCount, ReconciliationProgress, ReconciliationSession,
+    ReconciliationStatus,
+};
+
+// =============================================================================
+// Internal Row Types
+// =============================================================================
+
+/// Internal row type for reconciliation session queries.
+#[derive(Debug)]
+struct ReconciliationSessionRow {
+    session_id: Uuid,
+    location_id: String,
+    status: String,
+    started_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<ReconciliationSessionRow> for ReconciliationSession {
+    type Error = RepositoryError;
+
+    fn try_from(row: ReconciliationSessionRow) -> Result<Self, Self::Error> {
+        let status = ReconciliationStatus::from_db(&row.status).ok_or_else(|| {
+            RepositoryError::DataCorruption(format!(
+                "invalid reconciliation status: {}",
+                row.status
+            ))
+        })?;
+
+        Ok(Self {
+            session_id: row.session_id,
+            location_id: row.location_id,
+            started_at: row.started_at,
+            completed_at: row.completed_at,
+            status,
+        })
+    }
+}
+
+/// Repository for reconciliation sessions and counts.
+pub struct ReconciliationRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> ReconciliationRepository<'a> {
+    /// Create a new reconciliation repository.
+    #[must_use]
+    pub const fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Start a session with a snapshot of the location's SKUs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn create_session(
+        &self,
+        location_id: &str,
+        counts: &[NewReconciliationCount],
+    ) -> Result<ReconciliationSession, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query_as!(
+            ReconciliationSessionRow,
+            r#"
+            INSERT INTO admin.reconciliation_session (location_id)
+            VALUES ($1)
+            RETURNING session_id, location_id, status,
+                   started_at AS "started_at: DateTime<Utc>",
+                   completed_at AS "completed_at: DateTime<Utc>"
+            "#,
+            location_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let skus: Vec<String> = counts.iter().map(|c| c.sku.clone()).collect();
+        let item_ids: Vec<String> = counts.iter().map(|c| c.inventory_item_id.clone()).collect();
+        let quantities: Vec<i32> = counts.iter().map(|c| c.system_quantity).collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO admin.reconciliation_counts
+                (session_id, sku, inventory_item_id, system_quantity)
+            SELECT $1, sku, inventory_item_id, system_quantity
+            FROM UNNEST($2::VARCHAR[], $3::VARCHAR[], $4::INTEGER[])
+                AS t(sku, inventory_item_id, system_quantity)
+            "#,
+            row.session_id,
+            &skus,
+            &item_ids,
+            &quantities
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        row.try_into()
+    }
+
+    /// Get a session by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn get_session(
+        &self,
+        session_id: Uuid,
+    ) -> Result<Option<ReconciliationSession>, RepositoryError> {
+        let row = sqlx::query_as!(
+            ReconciliationSessionRow,
+            r#"
+            SELECT session_id, location_id, status,
+                   started_at AS "started_at: DateTime<Utc>",
+                   completed_at AS "completed_at: DateTime<Utc>"
+            FROM admin.reconciliation_session
+            WHERE session_id = $1
+            "#,
+            session_id
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    /// Sessions still being counted, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn list_in_progress(&self) -> Result<Vec<ReconciliationSession>, RepositoryError> {
+        let rows = sqlx::query_as!(
+            ReconciliationSessionRow,
+            r#"
+            SELECT session_id, location_id, status,
+                   started_at AS "started_at: DateTime<Utc>",
+                   completed_at AS "completed_at: DateTime<Utc>"
+            FROM admin.reconciliation_session
+            WHERE status = 'in_progress'
+            ORDER BY started_at DESC
+            "#
+        )
+        .fetch_all(self.pool)
+        .await?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Record the counted quantity for a SKU, replacing any earlier count.
+    ///
+    /// Returns `None` if the SKU isn't part of the session.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn record_count(
+        &self,
+        session_id: Uuid,
+        sku: &str,
+        counted_quantity: i32,
+    ) -> Result<Option<ReconciliationCount>, RepositoryError> {
+        let count = sqlx::query_as!(
+            ReconciliationCount,
+            r#"
+            UPDATE admin.reconciliation_counts
+            SET counted_quantity = $3,
+                delta = $3 - system_quantity,
+                counted_at = NOW(),
+                applied_at = NULL
+            WHERE session_id = $1 AND sku = $2
+            RETURNING sku, inventory_item_id, system_quantity, counted_quantity, delta,
+                   applied_at AS "applied_at: DateTime<Utc>"
+            "#,
+            session_id,
+            sku,
+            counted_quantity
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Counted SKUs, discrepancies first, then by SKU.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn list_counted(
+        &self,
+        session_id: Uuid,
+    ) -> Result<Vec<ReconciliationCount>, RepositoryError> {
+        let counts = sqlx::query_as!(
+            ReconciliationCount,
+            r#"
+            SELECT sku, inventory_item_id, system_quantity, counted_quantity, delta,
+                   applied_at AS "applied_at: DateTime<Utc>"
+            FROM admin.reconciliation_counts
+            WHERE session_id = $1 AND counted_quantity IS NOT NULL
+            ORDER BY delta = 0, ABS(delta) DESC, sku
+            "#,
+            session_id
+        )
+        .fetch_all(self.pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// Counting progress for a session.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn progress(
+        &self,
+        session_id: Uuid,
+    ) -> Result<ReconciliationProgress, RepositoryError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "total!",
+                COUNT(counted_quantity) AS "counted!",
+                COUNT(*) FILTER (WHERE delta <> 0) AS "discrepancies!"
+            FROM admin.reconciliation_counts
+            WHERE session_id = $1
+            "#,
+            session_id
+        )
+        .fetch_one(self.pool)
+        .await?;
+
+        Ok(ReconciliationProgress {
+            total: row.total,
+            counted: row.counted,
+            discrepancies: row.discrepancies,
+        })
+    }
+
+    /// Mark a count as written to Shopify.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn mark_applied(&self, session_id: Uuid, sku: &str) -> Result<(), RepositoryError> {
+        sqlx::query!(
+            r#"
+            UPDATE admin.reconciliation_counts
+            SET applied_at = NOW()
+            WHERE session_id = $1 AND sku = $2
+            "#,
+            session_id,
+            sku
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark a session as completed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn complete_session(&self, session_id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query!(
+            r#"
+            UPDATE admin.reconciliation_session
+            SET status = 'completed', completed_at = NOW()
+            WHERE session_id = $1
+            "#,
+            session_id
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
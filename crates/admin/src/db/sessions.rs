@@ -0,0 +1,45 @@
+//! Session table maintenance.
+//!
+//! tower-sessions only ignores expired rows in `admin.session`; this repository
+//! removes them so the table doesn't grow without bound.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use super::RepositoryError;
+
+/// Repository for session table maintenance.
+pub struct SessionRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> SessionRepository<'a> {
+    /// Create a new session repository.
+    #[must_use]
+    pub const fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Delete sessions that expired before `before`.
+    ///
+    /// Returns the number of rows deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn delete_expired(&self, before: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM admin.session
+            WHERE expiry_date < $1
+            "#,
+            // sqlx expects `time` types for `timestamptz` parameters in this
+            // workspace; chrono encodes to the same type
+            before as _
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -139,7 +139,7 @@ pub async fn get_user_settings_by_prefix(
     user_id: i32,
     prefix: &str,
 ) -> Result<Vec<(String, JsonValue)>, SettingsError> {
-    let pattern = format!("{prefix}%");
+    let pattern = format!("{}%", escape_like(prefix));
     let results = sqlx::query!(
         r#"
         SELECT key, value FROM admin.settings
@@ -153,3 +153,13 @@ pub async fn get_user_settings_by_prefix(
 
     Ok(results.into_iter().map(|r| (r.key, r.value)).collect())
 }
+
+/// Escape `LIKE` wildcards so `value` only matches literally.
+///
+/// Uses `\`, the default `LIKE` escape character in `PostgreSQL`.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
@@ -0,0 +1,73 @@
+//! `ShipHero` order mapping repository for database operations.
+//!
+//! `ShipHero` webhooks identify orders by their `ShipHero` order ID. This
+//! module maps those IDs to Shopify order IDs so fulfillment updates can be
+//! applied to the right Shopify order.
+
+use sqlx::PgPool;
+
+use super::RepositoryError;
+
+/// Repository for `ShipHero` to Shopify order mappings.
+pub struct ShipHeroOrderMappingRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> ShipHeroOrderMappingRepository<'a> {
+    /// Create a new `ShipHero` order mapping repository.
+    #[must_use]
+    pub const fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get the Shopify order ID mapped to a `ShipHero` order ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn find_shopify_order_id(
+        &self,
+        shiphero_order_id: &str,
+    ) -> Result<Option<String>, RepositoryError> {
+        let shopify_order_id = sqlx::query_scalar!(
+            r#"
+            SELECT shopify_order_id
+            FROM admin.shiphero_order_mappings
+            WHERE shiphero_order_id = $1
+            "#,
+            shiphero_order_id
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(shopify_order_id)
+    }
+
+    /// Save the Shopify order ID for a `ShipHero` order ID.
+    ///
+    /// Replaces any existing mapping for the `ShipHero` order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn save_mapping(
+        &self,
+        shiphero_order_id: &str,
+        shopify_order_id: &str,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO admin.shiphero_order_mappings (shiphero_order_id, shopify_order_id)
+            VALUES ($1, $2)
+            ON CONFLICT (shiphero_order_id)
+            DO UPDATE SET shopify_order_id = EXCLUDED.shopify_order_id
+            "#,
+            shiphero_order_id,
+            shopify_order_id
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -169,6 +169,34 @@ impl<'a> ShopifyTokenRepository<'a> {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Delete a shop's token if it is still `access_token`.
+    ///
+    /// Used once Shopify has revoked the token, so a token saved by
+    /// re-authorizing in the meantime is kept. Returns whether a row was
+    /// deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn invalidate(
+        &self,
+        shop: &str,
+        access_token: &str,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM admin.shopify_token
+            WHERE shop = $1 AND access_token = $2
+            "#,
+            shop,
+            access_token
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Check if a shop has a token stored.
     ///
     /// # Errors
@@ -47,6 +47,7 @@ pub struct CreateToolExample {
 ///
 /// Returns error if the database insert fails.
 #[instrument(skip(pool, params), fields(tool = %params.tool_name, domain = %params.domain))]
+#[allow(clippy::disallowed_methods)] // pgvector column; the embedding is still a bound parameter
 pub async fn insert_tool_example(
     pool: &PgPool,
     params: CreateToolExample,
@@ -108,20 +109,20 @@ pub async fn example_exists(
     tool_name: &str,
     example_query: &str,
 ) -> Result<bool, RepositoryError> {
-    let row: (bool,) = sqlx::query_as(
-        r"
+    let exists = sqlx::query_scalar!(
+        r#"
         SELECT EXISTS(
             SELECT 1 FROM admin.tool_example_queries
             WHERE tool_name = $1 AND example_query = $2
-        )
-        ",
+        ) as "exists!"
+        "#,
+        tool_name,
+        example_query
     )
-    .bind(tool_name)
-    .bind(example_query)
     .fetch_one(pool)
     .await?;
 
-    Ok(row.0)
+    Ok(exists)
 }
 
 /// Increment usage count for an existing example.
@@ -130,14 +131,14 @@ pub async fn example_exists(
 ///
 /// Returns error if the database update fails.
 pub async fn increment_usage_count(pool: &PgPool, id: i32) -> Result<(), RepositoryError> {
-    sqlx::query::<sqlx::Postgres>(
+    sqlx::query!(
         r"
         UPDATE admin.tool_example_queries
         SET usage_count = usage_count + 1
         WHERE id = $1
         ",
+        id
     )
-    .bind(id)
     .execute(pool)
     .await?;
 
@@ -154,19 +155,19 @@ pub async fn find_example_id(
     tool_name: &str,
     example_query: &str,
 ) -> Result<Option<i32>, RepositoryError> {
-    let result: Option<(i32,)> = sqlx::query_as(
+    let id = sqlx::query_scalar!(
         r"
         SELECT id FROM admin.tool_example_queries
         WHERE tool_name = $1 AND example_query = $2
         LIMIT 1
         ",
+        tool_name,
+        example_query
     )
-    .bind(tool_name)
-    .bind(example_query)
     .fetch_optional(pool)
     .await?;
 
-    Ok(result.map(|r| r.0))
+    Ok(id)
 }
 
 /// Search for similar tools using embedding similarity.
@@ -186,6 +187,7 @@ pub async fn find_example_id(
 ///
 /// Returns error if the database query fails.
 #[instrument(skip(pool, embedding), fields(domains = ?domains, limit))]
+#[allow(clippy::disallowed_methods)] // pgvector operators; every value is still a bound parameter
 pub async fn search_similar_tools(
     pool: &PgPool,
     embedding: &[f32],
@@ -251,8 +253,7 @@ pub async fn get_tools_by_domain(
 ) -> Result<Vec<String>, RepositoryError> {
     let limit_i64 = i64::try_from(limit).unwrap_or(i64::MAX);
 
-    // Using runtime query to avoid SQLx offline mode cache requirements
-    let rows = sqlx::query_scalar::<_, String>(
+    let rows = sqlx::query_scalar!(
         r"
         SELECT tool_name
         FROM (
@@ -264,9 +265,9 @@ pub async fn get_tools_by_domain(
             LIMIT $2
         ) sub
         ",
+        domains,
+        limit_i64
     )
-    .bind(domains)
-    .bind(limit_i64)
     .fetch_all(pool)
     .await?;
 
@@ -282,14 +283,14 @@ pub async fn get_tools_by_domain(
 ///
 /// Returns error if the database query fails.
 pub async fn get_domain_counts(pool: &PgPool) -> Result<Vec<DomainCount>, RepositoryError> {
-    // Using runtime query to avoid SQLx offline mode cache requirements
-    let rows: Vec<DomainCountRow> = sqlx::query_as(
+    let rows = sqlx::query_as!(
+        DomainCountRow,
         r"
         SELECT domain, COUNT(*) as count
         FROM admin.tool_example_queries
         GROUP BY domain
         ORDER BY domain
-        ",
+        "
     )
     .fetch_all(pool)
     .await?;
@@ -309,12 +310,12 @@ pub async fn get_domain_counts(pool: &PgPool) -> Result<Vec<DomainCount>, Reposi
 ///
 /// Returns error if the database query fails.
 pub async fn get_total_count(pool: &PgPool) -> Result<i64, RepositoryError> {
-    // Using runtime query to avoid SQLx offline mode cache requirements
-    let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM admin.tool_example_queries")
-        .fetch_one(pool)
-        .await?;
+    let count =
+        sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!" FROM admin.tool_example_queries"#)
+            .fetch_one(pool)
+            .await?;
 
-    Ok(count.0)
+    Ok(count)
 }
 
 /// Delete all tool examples (for re-seeding).
@@ -323,8 +324,7 @@ pub async fn get_total_count(pool: &PgPool) -> Result<i64, RepositoryError> {
 ///
 /// Returns error if the database delete fails.
 pub async fn delete_all(pool: &PgPool) -> Result<u64, RepositoryError> {
-    // Using runtime query to avoid SQLx offline mode cache requirements
-    let result = sqlx::query::<sqlx::Postgres>("DELETE FROM admin.tool_example_queries")
+    let result = sqlx::query!("DELETE FROM admin.tool_example_queries")
         .execute(pool)
         .await?;
 
@@ -337,12 +337,9 @@ pub async fn delete_all(pool: &PgPool) -> Result<u64, RepositoryError> {
 ///
 /// Returns error if the database delete fails.
 pub async fn delete_preseeded(pool: &PgPool) -> Result<u64, RepositoryError> {
-    // Using runtime query to avoid SQLx offline mode cache requirements
-    let result = sqlx::query::<sqlx::Postgres>(
-        "DELETE FROM admin.tool_example_queries WHERE is_learned = FALSE",
-    )
-    .execute(pool)
-    .await?;
+    let result = sqlx::query!("DELETE FROM admin.tool_example_queries WHERE is_learned = FALSE")
+        .execute(pool)
+        .await?;
 
     Ok(result.rows_affected())
 }
@@ -364,7 +361,6 @@ struct SimilarToolRow {
 }
 
 /// Internal row type for domain count query.
-#[derive(sqlx::FromRow)]
 struct DomainCountRow {
     domain: String,
     count: Option<i64>,
@@ -0,0 +1,76 @@
+//! Per-admin page preference repository for database operations.
+//!
+//! Preferences are keyed by admin email and page, e.g. the columns an admin
+//! exports from the orders list by default.
+
+use sqlx::PgPool;
+
+use naked_pineapple_core::Email;
+
+use super::RepositoryError;
+
+/// Repository for per-admin page preferences.
+pub struct UserPreferencesRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> UserPreferencesRepository<'a> {
+    /// Create a new user preferences repository.
+    #[must_use]
+    pub const fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get an admin's saved export fields for a page.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn get_export_fields(
+        &self,
+        email: &Email,
+        page: &str,
+    ) -> Result<Option<Vec<String>>, RepositoryError> {
+        let fields = sqlx::query_scalar!(
+            r#"
+            SELECT export_fields
+            FROM admin.user_preferences
+            WHERE admin_email = $1 AND page = $2
+            "#,
+            email.as_str(),
+            page
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(fields)
+    }
+
+    /// Save an admin's export fields for a page, replacing any saved before.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn save_export_fields(
+        &self,
+        email: &Email,
+        page: &str,
+        fields: &[String],
+    ) -> Result<(), RepositoryError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO admin.user_preferences (admin_email, page, export_fields)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (admin_email, page)
+            DO UPDATE SET export_fields = EXCLUDED.export_fields
+            "#,
+            email.as_str(),
+            page,
+            fields
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
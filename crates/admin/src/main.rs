@@ -31,10 +31,12 @@
 
 use axum::extract::State;
 use axum::http::StatusCode;
-use axum::{Router, routing::get};
+use axum::{Json, Router, routing::get};
 use axum_server::Handle;
 use axum_server::tls_rustls::RustlsConfig;
+use naked_pineapple_core::{HealthCheck, HealthReport};
 use secrecy::ExposeSecret;
+use tokio::task::JoinHandle;
 use tower_http::services::ServeDir;
 use tower_http::trace::{DefaultOnResponse, OnResponse, TraceLayer};
 use tracing::Span;
@@ -94,6 +96,16 @@ fn sentry_event_filter(metadata: &tracing::Metadata<'_>) -> sentry_tracing::Even
     }
 }
 
+/// Connect to the read replica, if one is configured.
+async fn create_read_pool(config: &AdminConfig) -> Option<sqlx::PgPool> {
+    let url = config.database_read_url.as_ref()?;
+    let read_pool = db::create_read_pool(url)
+        .await
+        .expect("Failed to create read replica pool");
+    tracing::info!("Read replica pool created");
+    Some(read_pool)
+}
+
 #[tokio::main]
 async fn main() {
     // Install rustls crypto provider (must be done before any TLS operations)
@@ -130,23 +142,42 @@ async fn main() {
         .expect("Failed to create database pool");
     tracing::info!("Database pool created");
 
+    // Initialize read replica pool (reads fall back to the primary if unset)
+    let read_pool = create_read_pool(&config).await;
+
     // NOTE: Migrations are NOT run automatically on startup.
     // Run them explicitly via: cargo run -p naked-pineapple-cli -- migrate admin
 
-    // Create session layer (PostgreSQL-backed with SameSite=Strict)
+    // Create session layer (PostgreSQL-backed with SameSite=Lax)
     let session_layer = create_session_layer(&pool, &config);
 
     // Build application state (includes WebAuthn)
-    let state = AppState::new(config.clone(), pool.clone())
+    let state = AppState::new(config.clone(), pool.clone(), read_pool)
         .await
         .expect("Failed to create application state");
 
+    // Delete expired sessions now and every few hours
+    let mut background_tasks = vec![
+        services::SessionCleanupTask::new(pool.clone(), state.session_cleanup().clone()).spawn(),
+    ];
+
+    // Remind customers of gift cards expiring in 30 days, once a day
+    background_tasks
+        .push(services::GiftCardExpiryReminderService::new(pool, state.shopify().clone()).spawn());
+
     // Build router
     let app = Router::new()
         .route("/health", get(health))
         .route("/health/ready", get(readiness))
-        .merge(routes::routes())
+        .merge(routes::routes().layer(axum::middleware::from_fn(
+            middleware::session_expiry_middleware,
+        )))
+        // Verified with the client secret, so built from the config
+        .merge(routes::webhooks::shopify_router(
+            config.shopify.client_secret.clone(),
+        ))
         .nest_service("/static", ServeDir::new("crates/admin/static"))
+        .layer(middleware::SentryContextLayer::new(&config.shopify.store))
         .layer(session_layer)
         .layer(
             TraceLayer::new_for_http()
@@ -217,6 +248,8 @@ async fn main() {
             .await
             .expect("Server error");
     }
+
+    stop_background_tasks(background_tasks).await;
 }
 
 /// Liveness health check endpoint.
@@ -228,12 +261,52 @@ async fn health() -> &'static str {
 
 /// Readiness health check endpoint.
 ///
-/// Verifies database connectivity before returning OK.
-/// Returns 503 Service Unavailable if the database is not reachable.
-async fn readiness(State(state): State<AppState>) -> StatusCode {
-    match sqlx::query("SELECT 1").fetch_one(state.pool()).await {
-        Ok(_) => StatusCode::OK,
-        Err(_) => StatusCode::SERVICE_UNAVAILABLE,
+/// Checks the database (critical) and the Shopify Admin API (non-critical)
+/// concurrently and returns a JSON [`HealthReport`]. Returns 503 Service
+/// Unavailable if the database is not reachable; a failing Shopify check only
+/// marks the service as degraded. The number of expired sessions deleted by
+/// the last cleanup run is included as a diagnostic. Error details are
+/// logged, and only included in the response in debug builds.
+async fn readiness(State(state): State<AppState>) -> (StatusCode, Json<HealthReport>) {
+    let (database, shopify) = tokio::join!(
+        HealthCheck::measure(sqlx::query!("SELECT 1 AS one").fetch_one(state.pool())),
+        HealthCheck::measure(state.shopify().ping()),
+    );
+
+    let mut report = HealthReport::new(vec![("database", database)], vec![("shopify", shopify)]);
+    if let Some(deleted) = state.session_cleanup().last_deleted() {
+        report = report.with_diagnostic("expired_sessions_deleted", deleted);
+    }
+    for (name, check) in &report.checks {
+        if let Some(error) = &check.error {
+            tracing::warn!(check = *name, "Readiness check failed: {error}");
+        }
+    }
+
+    let status = if report.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let report = if cfg!(debug_assertions) {
+        report
+    } else {
+        report.without_errors()
+    };
+
+    (status, Json(report))
+}
+
+/// Stop background tasks after the server has shut down, logging any that
+/// panicked while it was running.
+async fn stop_background_tasks(tasks: Vec<JoinHandle<()>>) {
+    for task in tasks {
+        task.abort();
+        if let Err(e) = task.await
+            && e.is_panic()
+        {
+            tracing::error!("Background task panicked: {e}");
+        }
     }
 }
 
@@ -0,0 +1,324 @@
+//! Idempotency key deduplication for mutation routes.
+//!
+//! A retried form submission could otherwise cancel an order, create a
+//! fulfillment or issue a refund twice. Requests that carry an
+//! `X-Idempotency-Key` header are answered once; a repeat of the same key in
+//! the same session within 24 hours gets the first response back without
+//! running the handler again. A repeat that arrives while the first request
+//! is still running waits for its response.
+//!
+//! The admin layout gives every HTMX form its own key and keeps it until the
+//! server answers, so only retries reuse a key.
+
+use std::convert::Infallible;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use axum::{
+    body::{Body, Bytes},
+    extract::Request,
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use futures::future::BoxFuture;
+use moka::future::Cache;
+use sha2::{Digest, Sha256};
+use tower::{Layer, Service};
+use tower_sessions::Session;
+
+/// Header carrying the client's idempotency key.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "x-idempotency-key";
+
+/// How long a response is replayed for its key.
+const CACHE_TTL: Duration = Duration::from_hours(24);
+
+/// Maximum number of cached responses.
+const CACHE_CAPACITY: u64 = 10_000;
+
+/// A response stored for replay.
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl IntoResponse for CachedResponse {
+    fn into_response(self) -> Response {
+        let mut response = (self.status, self.body).into_response();
+        *response.headers_mut() = self.headers;
+        response
+    }
+}
+
+/// Layer that replays responses for repeated idempotency keys.
+///
+/// Clones share one cache, so a single layer can be applied to several
+/// routers.
+#[derive(Clone)]
+pub struct DeduplicationLayer {
+    cache: Cache<String, CachedResponse>,
+}
+
+impl Default for DeduplicationLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeduplicationLayer {
+    /// Create a layer with an empty cache.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cache: Cache::builder()
+                .max_capacity(CACHE_CAPACITY)
+                .time_to_live(CACHE_TTL)
+                .build(),
+        }
+    }
+}
+
+impl<S> Layer<S> for DeduplicationLayer {
+    type Service = DeduplicationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DeduplicationService {
+            inner,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+/// Service created by [`DeduplicationLayer`].
+#[derive(Clone)]
+pub struct DeduplicationService<S> {
+    inner: S,
+    cache: Cache<String, CachedResponse>,
+}
+
+impl<S> Service<Request> for DeduplicationService<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Use the service that was polled ready, leaving a fresh clone behind
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let cache = self.cache.clone();
+
+        Box::pin(async move {
+            let Some(key) = cache_key(&request) else {
+                return inner.call(request).await;
+            };
+            let path = request.uri().path().to_string();
+
+            // Concurrent requests with the same key are coalesced: only the
+            // first runs the handler, the others wait for its response
+            let mut own_response = None;
+            let result = cache
+                .entry(key)
+                .or_try_insert_with(run_and_buffer(&mut inner, request, &mut own_response))
+                .await;
+
+            if let Some(response) = own_response {
+                return Ok(response);
+            }
+
+            tracing::info!(%path, "Replaying response for idempotency key");
+            Ok(match result {
+                Ok(entry) => entry.into_value().into_response(),
+                Err(error) => CachedResponse::clone(&error).into_response(),
+            })
+        })
+    }
+}
+
+/// Run a request and buffer its response for replay.
+///
+/// The response itself, with its cookies, is left in `own_response` for the
+/// caller. Only completed actions (success or redirect) are returned as `Ok`
+/// and cached; errors are replayed to concurrent duplicates but not cached,
+/// so they can be retried.
+async fn run_and_buffer<S>(
+    inner: &mut S,
+    request: Request,
+    own_response: &mut Option<Response>,
+) -> Result<CachedResponse, CachedResponse>
+where
+    S: Service<Request, Response = Response, Error = Infallible>,
+{
+    let Ok(response) = inner.call(request).await;
+    let status = response.status();
+
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to buffer response for idempotency cache");
+            *own_response = Some(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            return Err(CachedResponse {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                headers: HeaderMap::new(),
+                body: Bytes::new(),
+            });
+        }
+    };
+
+    let mut headers = parts.headers.clone();
+    headers.remove(header::SET_COOKIE);
+    let cached = CachedResponse {
+        status,
+        headers,
+        body: body.clone(),
+    };
+    *own_response = Some(Response::from_parts(parts, Body::from(body)));
+
+    if status.is_success() || status.is_redirection() {
+        Ok(cached)
+    } else {
+        Err(cached)
+    }
+}
+
+/// Cache key for a request: the idempotency key hashed with the session ID.
+///
+/// Returns `None` for safe methods, requests without a key, and requests
+/// without a saved session, which are passed through untouched.
+fn cache_key(request: &Request) -> Option<String> {
+    if request.method().is_safe() {
+        return None;
+    }
+
+    let key = request.headers().get(IDEMPOTENCY_KEY_HEADER)?.as_bytes();
+    if key.is_empty() {
+        return None;
+    }
+    let session_id = request.extensions().get::<Session>()?.id()?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(session_id.to_string().as_bytes());
+    hasher.update(b":");
+    hasher.update(key);
+    Some(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::{Router, routing::post};
+    use tower::ServiceExt;
+    use tower_sessions::cookie::time::{Duration as TimeDuration, OffsetDateTime};
+    use tower_sessions::session::{Id, Record};
+    use tower_sessions::{MemoryStore, SessionManagerLayer, SessionStore};
+
+    use super::*;
+
+    /// Store a non-empty session and return its ID.
+    async fn create_session(store: &MemoryStore) -> Id {
+        let mut record = Record {
+            id: Id::default(),
+            data: HashMap::from([("user".to_string(), serde_json::json!(1))]),
+            expiry_date: OffsetDateTime::now_utc() + TimeDuration::hours(1),
+        };
+        store.create(&mut record).await.unwrap();
+        record.id
+    }
+
+    /// Router whose handler returns how many times it has run.
+    fn app(store: &MemoryStore, calls: &Arc<AtomicUsize>) -> Router {
+        let calls = Arc::clone(calls);
+        Router::new()
+            .route(
+                "/orders/1/cancel",
+                post(move || {
+                    let calls = Arc::clone(&calls);
+                    async move {
+                        let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                        // Long enough for a concurrent duplicate to arrive
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        call.to_string()
+                    }
+                }),
+            )
+            .layer(DeduplicationLayer::new())
+            .layer(SessionManagerLayer::new(store.clone()))
+    }
+
+    /// POST with an optional idempotency key and return the body.
+    async fn send(app: Router, session: Id, key: Option<&str>) -> String {
+        let mut request = axum::http::Request::builder()
+            .method("POST")
+            .uri("/orders/1/cancel")
+            .header(header::COOKIE, format!("id={session}"));
+        if let Some(key) = key {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_repeated_key_replays_response() {
+        let store = MemoryStore::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let layer_app = app(&store, &calls);
+        let session = create_session(&store).await;
+
+        assert_eq!(send(layer_app.clone(), session, Some("form-1")).await, "1");
+        assert_eq!(send(layer_app.clone(), session, Some("form-1")).await, "1");
+        assert_eq!(send(layer_app.clone(), session, Some("form-2")).await, "2");
+        assert_eq!(send(layer_app, session, None).await, "3");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_duplicates_run_handler_once() {
+        let store = MemoryStore::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let layer_app = app(&store, &calls);
+        let session = create_session(&store).await;
+
+        let (first, second) = tokio::join!(
+            send(layer_app.clone(), session, Some("form-1")),
+            send(layer_app.clone(), session, Some("form-1")),
+        );
+
+        assert_eq!(first, "1");
+        assert_eq!(second, "1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(send(layer_app, session, Some("form-1")).await, "1");
+    }
+
+    #[tokio::test]
+    async fn test_key_is_scoped_to_session() {
+        let store = MemoryStore::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let layer_app = app(&store, &calls);
+        let first = create_session(&store).await;
+        let second = create_session(&store).await;
+
+        assert_eq!(send(layer_app.clone(), first, Some("form-1")).await, "1");
+        assert_eq!(send(layer_app, second, Some("form-1")).await, "2");
+    }
+}
@@ -6,15 +6,25 @@
 //! 2. `TraceLayer` (request tracing)
 //! 3. Request ID (add unique ID to each request)
 //! 4. Session layer (tower-sessions with `PostgreSQL` store)
-//! 5. Admin context (add admin user info to tracing span)
-//! 6. Security headers (stricter CSP for admin)
-//! 7. Auth guard (require authentication for most routes)
+//! 5. Sentry context (tag errors with the admin, role, store and request ID)
+//! 6. Session expiry (slide the expiry for active admins; app routes only)
+//! 7. Admin context (add admin user info to tracing span)
+//! 8. Security headers (stricter CSP for admin)
+//! 9. Auth guard (require authentication for most routes)
+//! 10. Deduplication (replay responses for repeated idempotency keys; mutation routes only)
+//! 11. Webhook signature (verify Shopify's HMAC; Shopify webhook routes only)
 
 pub mod auth;
+pub mod deduplication;
+pub mod sentry_context;
 pub mod session;
+pub mod webhook;
 
 pub use auth::{
     OptionalAdminAuth, RequireAdminAuth, RequireSuperAdmin, clear_current_admin,
     require_super_admin, set_current_admin,
 };
-pub use session::create_session_layer;
+pub use deduplication::DeduplicationLayer;
+pub use sentry_context::SentryContextLayer;
+pub use session::{create_session_layer, session_expiry_middleware};
+pub use webhook::verify_shopify_webhook;
@@ -0,0 +1,118 @@
+//! Sentry request context for admin requests.
+//!
+//! Tags each request's Sentry scope with the signed-in admin, their role, the
+//! Shopify store and a request ID, so errors can be filtered by admin and an
+//! error spike traced back to the operations behind it.
+//!
+//! `NewSentryLayer` gives every request its own scope, so the context never
+//! leaks between requests. The layer must run inside the session layer to
+//! see the signed-in admin.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::{extract::Request, http::HeaderValue, response::Response};
+use futures::future::BoxFuture;
+use tower::{Layer, Service};
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use crate::models::{CurrentAdmin, session_keys};
+
+/// Header carrying the request ID, from an upstream proxy or generated here.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Layer that adds admin and request context to the Sentry scope.
+#[derive(Clone)]
+pub struct SentryContextLayer {
+    shopify_store: Arc<str>,
+}
+
+impl SentryContextLayer {
+    /// Create a layer that tags events with `shopify_store`.
+    #[must_use]
+    pub fn new(shopify_store: &str) -> Self {
+        Self {
+            shopify_store: Arc::from(shopify_store),
+        }
+    }
+}
+
+impl<S> Layer<S> for SentryContextLayer {
+    type Service = SentryContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SentryContextService {
+            inner,
+            shopify_store: Arc::clone(&self.shopify_store),
+        }
+    }
+}
+
+/// Service created by [`SentryContextLayer`].
+#[derive(Clone)]
+pub struct SentryContextService<S> {
+    inner: S,
+    shopify_store: Arc<str>,
+}
+
+impl<S> Service<Request> for SentryContextService<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Use the service that was polled ready, leaving a fresh clone behind
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let shopify_store = Arc::clone(&self.shopify_store);
+
+        Box::pin(async move {
+            let request_id = request
+                .headers()
+                .get(REQUEST_ID_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .map_or_else(|| Uuid::new_v4().to_string(), String::from);
+
+            let admin = match request.extensions().get::<Session>() {
+                Some(session) => session
+                    .get::<CurrentAdmin>(session_keys::CURRENT_ADMIN)
+                    .await
+                    .ok()
+                    .flatten(),
+                None => None,
+            };
+
+            sentry::configure_scope(|scope| {
+                scope.set_tag("shopify_store", &*shopify_store);
+                scope.set_tag("request_id", &request_id);
+                if let Some(admin) = &admin {
+                    scope.set_user(Some(sentry::User {
+                        id: Some(admin.id.to_string()),
+                        email: Some(admin.email.to_string()),
+                        ..Default::default()
+                    }));
+                    scope.set_tag("admin_role", admin.role);
+                }
+            });
+
+            let mut response = inner.call(request).await?;
+
+            // Let admins quote the ID when reporting a problem
+            if let Ok(value) = HeaderValue::from_str(&request_id) {
+                response.headers_mut().insert(REQUEST_ID_HEADER, value);
+            }
+
+            Ok(response)
+        })
+    }
+}
@@ -1,20 +1,28 @@
 //! Session middleware configuration for admin.
 //!
 //! Sets up `PostgreSQL`-backed sessions using tower-sessions with
-//! secure settings (SameSite=Lax, 24hr expiry). We use Lax instead of
+//! secure settings (SameSite=Lax, 8hr sliding expiry). We use Lax instead of
 //! Strict to support OAuth flows where external providers redirect back.
+//!
+//! tower-sessions only writes a session back when it changes, so an
+//! unchanged session keeps the expiry it was saved with.
+//! [`session_expiry_middleware`] slides the expiry forward while an admin is
+//! active.
 
+use axum::{extract::Request, middleware::Next, response::Response};
+use chrono::{DateTime, Duration, Utc};
 use sqlx::PgPool;
-use tower_sessions::{Expiry, SessionManagerLayer};
+use tower_sessions::{Expiry, Session, SessionManagerLayer, SessionStore};
 use tower_sessions_sqlx_store::PostgresStore;
 
 use crate::config::AdminConfig;
+use crate::models::session_keys;
 
 /// Session cookie name for admin.
 pub const SESSION_COOKIE_NAME: &str = "np_admin_session";
 
-/// Session expiry time in seconds (24 hours - stricter than storefront).
-const SESSION_EXPIRY_SECONDS: i64 = 24 * 60 * 60;
+/// Session expiry time in seconds (8 hours of inactivity - stricter than storefront).
+const SESSION_EXPIRY_SECONDS: i64 = 8 * 60 * 60;
 
 /// Create the session layer with `PostgreSQL` store.
 ///
@@ -43,6 +51,11 @@ pub fn create_session_layer(
     // Determine if we're in production (HTTPS)
     let is_secure = config.base_url.starts_with("https://");
 
+    session_layer(store, is_secure)
+}
+
+/// Session layer settings, independent of the backing store.
+fn session_layer<S: SessionStore>(store: S, is_secure: bool) -> SessionManagerLayer<S> {
     SessionManagerLayer::new(store)
         .with_name(SESSION_COOKIE_NAME)
         .with_expiry(Expiry::OnInactivity(
@@ -55,3 +68,153 @@ pub fn create_session_layer(
         .with_http_only(true)
         .with_path("/")
 }
+
+/// Extend a logged-in admin's session expiry while they are active.
+///
+/// Once less than half of the session lifetime remains, records
+/// `session_last_active`, which saves the session with a fresh expiry and
+/// cookie. Requests in the first half of the window don't write to the
+/// database.
+pub async fn session_expiry_middleware(session: Session, request: Request, next: Next) -> Response {
+    let is_authenticated = session
+        .get_value(session_keys::CURRENT_ADMIN)
+        .await
+        .ok()
+        .flatten()
+        .is_some();
+
+    if is_authenticated {
+        let now = Utc::now();
+        let last_active: Option<DateTime<Utc>> =
+            session.get(session_keys::LAST_ACTIVE).await.ok().flatten();
+
+        if needs_extension(last_active, now)
+            && let Err(e) = session.insert(session_keys::LAST_ACTIVE, now).await
+        {
+            tracing::warn!(error = %e, "Failed to extend admin session");
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Whether less than half of the session lifetime remains, given when the
+/// expiry was last extended.
+fn needs_extension(last_active: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    last_active.is_none_or(|last_active| {
+        now - last_active >= Duration::seconds(SESSION_EXPIRY_SECONDS / 2)
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::{
+        Router,
+        body::Body,
+        http::{StatusCode, header},
+        routing::get,
+    };
+    use tower::ServiceExt;
+    use tower_sessions::MemoryStore;
+    use tower_sessions::cookie::time::{Duration as TimeDuration, OffsetDateTime};
+    use tower_sessions::session::{Id, Record};
+
+    use super::*;
+    use crate::middleware::RequireAdminAuth;
+
+    /// Store a logged-in admin session and return its ID.
+    async fn create_session(
+        store: &MemoryStore,
+        last_active: DateTime<Utc>,
+        expiry_date: OffsetDateTime,
+    ) -> Id {
+        let mut record = Record {
+            id: Id::default(),
+            data: HashMap::from([
+                (
+                    session_keys::CURRENT_ADMIN.to_string(),
+                    serde_json::json!({
+                        "id": 1,
+                        "email": "admin@example.com",
+                        "name": "Admin",
+                        "role": "admin"
+                    }),
+                ),
+                (
+                    session_keys::LAST_ACTIVE.to_string(),
+                    serde_json::to_value(last_active).unwrap(),
+                ),
+            ]),
+            expiry_date,
+        };
+        store.create(&mut record).await.unwrap();
+        record.id
+    }
+
+    /// Request `/` with the session cookie.
+    async fn request(store: &MemoryStore, id: Id) -> axum::response::Response {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|RequireAdminAuth(_): RequireAdminAuth| async { "ok" }),
+            )
+            .layer(axum::middleware::from_fn(session_expiry_middleware))
+            .layer(session_layer(store.clone(), false));
+
+        let request = axum::http::Request::builder()
+            .uri("/")
+            .header(header::COOKIE, format!("{SESSION_COOKIE_NAME}={id}"))
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(request).await.unwrap()
+    }
+
+    #[test]
+    fn test_needs_extension() {
+        let now = Utc::now();
+        assert!(needs_extension(None, now));
+        assert!(!needs_extension(Some(now - Duration::hours(1)), now));
+        assert!(needs_extension(Some(now - Duration::hours(4)), now));
+    }
+
+    #[tokio::test]
+    async fn test_session_near_expiry_is_extended() {
+        let store = MemoryStore::default();
+        let expiry_date = OffsetDateTime::now_utc() + TimeDuration::minutes(30);
+        let id = create_session(&store, Utc::now() - Duration::hours(7), expiry_date).await;
+
+        let response = request(&store, id).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let record = store.load(&id).await.unwrap().unwrap();
+        assert!(record.expiry_date > OffsetDateTime::now_utc() + TimeDuration::hours(7));
+    }
+
+    #[tokio::test]
+    async fn test_recently_extended_session_is_not_saved() {
+        let store = MemoryStore::default();
+        let expiry_date = OffsetDateTime::now_utc() + TimeDuration::hours(7);
+        let id = create_session(&store, Utc::now() - Duration::hours(1), expiry_date).await;
+
+        let response = request(&store, id).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let record = store.load(&id).await.unwrap().unwrap();
+        assert_eq!(record.expiry_date, expiry_date);
+    }
+
+    #[tokio::test]
+    async fn test_expired_session_redirects_to_login() {
+        let store = MemoryStore::default();
+        let expiry_date = OffsetDateTime::now_utc() - TimeDuration::minutes(1);
+        let id = create_session(&store, Utc::now() - Duration::hours(9), expiry_date).await;
+
+        let response = request(&store, id).await;
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(response.headers()[header::LOCATION], "/auth/login");
+    }
+}
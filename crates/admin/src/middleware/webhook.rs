@@ -0,0 +1,134 @@
+//! Shopify webhook signature verification.
+//!
+//! Shopify signs every webhook with an HMAC-SHA256 of the raw body, keyed
+//! with the app's client secret, and sends it base64-encoded in the
+//! `X-Shopify-Hmac-Sha256` header. The middleware buffers the body, checks
+//! the signature and hands the request on with the same bytes, so handlers
+//! behind it can extract the body as usual and only ever see verified
+//! payloads.
+//!
+//! ```rust,ignore
+//! Router::new()
+//!     .route("/webhooks/shopify", post(handler))
+//!     .route_layer(axum::middleware::from_fn_with_state(
+//!         config.shopify.client_secret.clone(),
+//!         verify_shopify_webhook,
+//!     ))
+//! ```
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use secrecy::{ExposeSecret, SecretString};
+
+use crate::error::AppError;
+use crate::shopify::verify_webhook_signature;
+
+/// Header carrying the base64 HMAC-SHA256 signature of a Shopify webhook.
+pub const SHOPIFY_HMAC_HEADER: &str = "X-Shopify-Hmac-Sha256";
+
+/// Largest webhook body accepted (Shopify payloads are well under this).
+const MAX_BODY_SIZE: usize = 5 * 1024 * 1024;
+
+/// Reject requests whose `X-Shopify-Hmac-Sha256` header doesn't match the
+/// body signed with `client_secret`.
+///
+/// # Errors
+///
+/// Returns `AppError::Unauthorized` (401) if the header is missing or the
+/// signature doesn't match, and `AppError::BadRequest` if the body can't be
+/// read.
+pub async fn verify_shopify_webhook(
+    State(client_secret): State<SecretString>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let (parts, body) = request.into_parts();
+
+    let signature = parts
+        .headers
+        .get(SHOPIFY_HMAC_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| AppError::Unauthorized("Missing webhook signature".into()))?
+        .to_string();
+
+    let bytes = to_bytes(body, MAX_BODY_SIZE)
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Failed to read webhook body: {e}")))?;
+
+    if !verify_webhook_signature(client_secret.expose_secret(), &bytes, &signature) {
+        tracing::warn!(uri = %parts.uri, "Shopify webhook signature mismatch");
+        return Err(AppError::Unauthorized("Invalid webhook signature".into()));
+    }
+
+    Ok(next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use axum::{Router, http::StatusCode, routing::post};
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    const SECRET: &str = "test_client_secret";
+    const BODY: &str = r#"{"id":1,"topic":"orders/create"}"#;
+
+    fn sign(body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(body.as_bytes());
+        BASE64.encode(mac.finalize().into_bytes())
+    }
+
+    /// Send `BODY` with `signature` to a handler that echoes the body back.
+    async fn send(signature: Option<&str>) -> Response {
+        let app = Router::new()
+            .route(
+                "/webhooks/shopify",
+                post(|body: String| async move { body }),
+            )
+            .route_layer(axum::middleware::from_fn_with_state(
+                SecretString::from(SECRET),
+                verify_shopify_webhook,
+            ));
+
+        let mut request = Request::builder().method("POST").uri("/webhooks/shopify");
+        if let Some(signature) = signature {
+            request = request.header(SHOPIFY_HMAC_HEADER, signature);
+        }
+        app.oneshot(request.body(Body::from(BODY)).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_valid_signature_reaches_handler_with_body() {
+        let response = send(Some(&sign(BODY))).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, BODY.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_signature_is_rejected() {
+        let response = send(Some(&sign("tampered"))).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_missing_signature_is_rejected() {
+        let response = send(None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
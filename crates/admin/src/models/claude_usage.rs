@@ -0,0 +1,55 @@
+//! Claude API usage models for cost tracking.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use naked_pineapple_core::{ChatSessionId, Email};
+
+/// Token usage from one Claude API response, ready to be recorded.
+#[derive(Debug, Clone)]
+pub struct NewClaudeUsage {
+    /// Admin user who sent the request.
+    pub admin_user_email: Email,
+    /// Model that served the request.
+    pub model: String,
+    /// Input tokens consumed.
+    pub input_tokens: i32,
+    /// Output tokens generated.
+    pub output_tokens: i32,
+    /// Cost estimated from the configured model prices.
+    pub estimated_cost_usd: Decimal,
+    /// Chat session the request belonged to.
+    pub conversation_id: Option<ChatSessionId>,
+    /// Name of the prompt template the request was sent with.
+    pub prompt_name: Option<String>,
+    /// Version of the prompt template the request was sent with.
+    pub prompt_version: Option<i32>,
+}
+
+/// Claude usage for one admin user on one day.
+#[derive(Debug, Clone)]
+pub struct DailyClaudeUsage {
+    /// Day (UTC).
+    pub day: NaiveDate,
+    /// Admin user email.
+    pub admin_user_email: String,
+    /// Number of Claude API requests.
+    pub requests: i64,
+    /// Total input tokens.
+    pub input_tokens: i64,
+    /// Total output tokens.
+    pub output_tokens: i64,
+    /// Total estimated cost in USD.
+    pub estimated_cost_usd: Decimal,
+}
+
+/// Claude usage for one admin user in the current month.
+#[derive(Debug, Clone)]
+pub struct MonthlyClaudeUsage {
+    /// Admin user email.
+    pub admin_user_email: String,
+    /// Total input + output tokens.
+    pub total_tokens: i64,
+    /// Total estimated cost in USD.
+    pub estimated_cost_usd: Decimal,
+}
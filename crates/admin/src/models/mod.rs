@@ -2,12 +2,16 @@
 
 pub mod admin_user;
 pub mod chat;
+pub mod claude_usage;
 pub mod inventory_lot;
 pub mod manufacturing;
+pub mod prompt_template;
+pub mod reconciliation;
 pub mod session;
 
 pub use admin_user::{AdminCredential, AdminRole, AdminUser};
 pub use chat::{ChatMessage, ChatSession};
+pub use claude_usage::{DailyClaudeUsage, MonthlyClaudeUsage, NewClaudeUsage};
 pub use inventory_lot::{
     AllocateLotInput, CreateLotInput, InventoryLot, InventoryLotWithBatch,
     InventoryLotWithRemaining, LotAllocation, LotAllocationWithContext, LotFilter, UpdateLotInput,
@@ -16,4 +20,9 @@ pub use manufacturing::{
     BatchFilter, BatchMetadata, CreateBatchInput, ManufacturingBatch,
     ManufacturingBatchWithDetails, UpdateBatchInput,
 };
+pub use prompt_template::PromptTemplate;
+pub use reconciliation::{
+    NewReconciliationCount, ReconciliationCount, ReconciliationProgress, ReconciliationSession,
+    ReconciliationStatus,
+};
 pub use session::{CurrentAdmin, keys as session_keys};
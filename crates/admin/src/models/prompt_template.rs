@@ -0,0 +1,20 @@
+//! Claude system prompt template model.
+
+use serde::{Deserialize, Serialize};
+
+/// A versioned system prompt for the Claude chat assistant.
+///
+/// Bundled templates come from `data/prompts.toml`; custom ones are seeded
+/// into the database with `np-cli seed prompts`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    /// Template name, selected with `CLAUDE_PROMPT_TEMPLATE`.
+    pub name: String,
+    /// Version, recorded with each Claude request in `claude_usage`.
+    pub version: i32,
+    /// System prompt text, with `{{ placeholder }}` variables.
+    pub system_prompt: String,
+    /// What the template is for or what changed in this version.
+    #[serde(default)]
+    pub description: String,
+}
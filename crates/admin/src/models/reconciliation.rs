@@ -0,0 +1,177 @@
+//! Inventory count reconciliation models.
+//!
+//! A reconciliation session snapshots Shopify's on-hand quantity for every
+//! SKU at one location. Staff record what they physically count, review the
+//! discrepancies, and apply the counted quantities back to Shopify.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Status of a reconciliation session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconciliationStatus {
+    /// Counts are being recorded.
+    InProgress,
+    /// Counted quantities have been applied to Shopify.
+    Completed,
+}
+
+impl ReconciliationStatus {
+    /// Database representation.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::InProgress => "in_progress",
+            Self::Completed => "completed",
+        }
+    }
+
+    /// Parse the database representation.
+    #[must_use]
+    pub fn from_db(value: &str) -> Option<Self> {
+        match value {
+            "in_progress" => Some(Self::InProgress),
+            "completed" => Some(Self::Completed),
+            _ => None,
+        }
+    }
+}
+
+/// A physical inventory count at one location.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationSession {
+    /// Unique session ID.
+    pub session_id: Uuid,
+    /// Shopify location GID being counted.
+    pub location_id: String,
+    /// When the count started.
+    pub started_at: DateTime<Utc>,
+    /// When the counts were applied.
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Current status.
+    pub status: ReconciliationStatus,
+}
+
+/// One SKU in a reconciliation session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationCount {
+    /// SKU code.
+    pub sku: String,
+    /// Shopify inventory item GID.
+    pub inventory_item_id: String,
+    /// On-hand quantity in Shopify when the session started.
+    pub system_quantity: i32,
+    /// Physically counted quantity (if counted).
+    pub counted_quantity: Option<i32>,
+    /// Counted minus system quantity (if counted).
+    pub delta: Option<i32>,
+    /// When the counted quantity was written to Shopify.
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+impl ReconciliationCount {
+    /// Whether the count differs from the system quantity.
+    #[must_use]
+    pub fn is_discrepant(&self) -> bool {
+        self.delta.is_some_and(|delta| delta != 0)
+    }
+
+    /// Counted quantity still to be written to Shopify.
+    ///
+    /// `None` if the SKU wasn't counted, matches the system quantity, or
+    /// has already been applied.
+    #[must_use]
+    pub fn pending_quantity(&self) -> Option<i32> {
+        if !self.is_discrepant() || self.applied_at.is_some() {
+            return None;
+        }
+        self.counted_quantity
+    }
+}
+
+/// A SKU snapshot taken when a session starts.
+#[derive(Debug, Clone)]
+pub struct NewReconciliationCount {
+    /// SKU code.
+    pub sku: String,
+    /// Shopify inventory item GID.
+    pub inventory_item_id: String,
+    /// Current on-hand quantity in Shopify.
+    pub system_quantity: i32,
+}
+
+/// Counting progress for a session.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ReconciliationProgress {
+    /// SKUs in the session.
+    pub total: i64,
+    /// SKUs counted so far.
+    pub counted: i64,
+    /// Counted SKUs whose count differs from the system quantity.
+    pub discrepancies: i64,
+}
+
+impl ReconciliationProgress {
+    /// Share of SKUs counted, in percent.
+    #[must_use]
+    pub const fn percent_counted(&self) -> i64 {
+        if self.total == 0 {
+            100
+        } else {
+            self.counted * 100 / self.total
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(counted_quantity: Option<i32>) -> ReconciliationCount {
+        ReconciliationCount {
+            sku: "NP-CLN-100".to_string(),
+            inventory_item_id: "gid://shopify/InventoryItem/1".to_string(),
+            system_quantity: 10,
+            counted_quantity,
+            delta: counted_quantity.map(|counted| counted - 10),
+            applied_at: None,
+        }
+    }
+
+    #[test]
+    fn test_is_discrepant() {
+        assert!(!count(None).is_discrepant());
+        assert!(!count(Some(10)).is_discrepant());
+        assert!(count(Some(7)).is_discrepant());
+        assert!(count(Some(12)).is_discrepant());
+        assert!(count(Some(0)).is_discrepant());
+    }
+
+    #[test]
+    fn test_pending_quantity() {
+        assert_eq!(count(None).pending_quantity(), None);
+        assert_eq!(count(Some(10)).pending_quantity(), None);
+        assert_eq!(count(Some(7)).pending_quantity(), Some(7));
+
+        let applied = ReconciliationCount {
+            applied_at: Some(Utc::now()),
+            ..count(Some(7))
+        };
+        assert_eq!(applied.pending_quantity(), None);
+    }
+
+    #[test]
+    fn test_percent_counted() {
+        let progress = |total, counted| ReconciliationProgress {
+            total,
+            counted,
+            discrepancies: 0,
+        };
+        assert_eq!(progress(0, 0).percent_counted(), 100);
+        assert_eq!(progress(3, 0).percent_counted(), 0);
+        assert_eq!(progress(3, 2).percent_counted(), 66);
+        assert_eq!(progress(3, 3).percent_counted(), 100);
+    }
+}
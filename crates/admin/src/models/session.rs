@@ -33,4 +33,7 @@ pub mod keys {
 
     /// Key for `WebAuthn` authentication challenge state.
     pub const WEBAUTHN_AUTH: &str = "webauthn_auth";
+
+    /// Key for when the session expiry was last extended.
+    pub const LAST_ACTIVE: &str = "session_last_active";
 }
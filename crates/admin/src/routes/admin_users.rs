@@ -14,7 +14,7 @@ use tracing::instrument;
 use naked_pineapple_core::{AdminRole, AdminUserId};
 
 use crate::{
-    db::{AdminInvite, AdminInviteRepository, AdminUserRepository, RepositoryError},
+    db::{AdminInvite, AdminInviteRepository, AdminUserRepository, ReadPool, RepositoryError},
     filters,
     middleware::auth::RequireSuperAdmin,
     state::AppState,
@@ -132,13 +132,13 @@ pub struct ErrorTemplate {
 // =============================================================================
 
 /// Admin users list page handler (`super_admin` only).
-#[instrument(skip(admin, state))]
+#[instrument(skip(admin, pool))]
 pub async fn index(
     RequireSuperAdmin(admin): RequireSuperAdmin,
-    State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
 ) -> Html<String> {
-    let user_repo = AdminUserRepository::new(state.pool());
-    let invite_repo = AdminInviteRepository::new(state.pool());
+    let user_repo = AdminUserRepository::new(&pool);
+    let invite_repo = AdminInviteRepository::new(&pool);
     let current_user_id = admin.id.as_i32();
 
     // Fetch all users
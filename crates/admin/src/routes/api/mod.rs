@@ -3,6 +3,7 @@
 //! JSON API endpoints for various admin operations.
 
 pub mod preferences;
+pub mod provinces;
 pub mod webauthn;
 
 use axum::Router;
@@ -14,4 +15,5 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .merge(webauthn::router())
         .merge(preferences::router())
+        .merge(provinces::router())
 }
@@ -0,0 +1,59 @@
+//! Province API handlers for address forms.
+
+use askama::Template;
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+    routing::get,
+};
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::{components::CountrySelector, middleware::auth::RequireAdminAuth, state::AppState};
+
+/// Build the provinces router.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/api/provinces", get(provinces))
+}
+
+/// Query parameters for the province select.
+#[derive(Debug, Deserialize)]
+pub struct ProvincesQuery {
+    pub country: Option<String>,
+}
+
+/// Province select partial.
+#[derive(Template)]
+#[template(path = "components/province_select.html")]
+pub struct ProvinceSelectTemplate {
+    pub country_selector: CountrySelector,
+}
+
+/// GET /api/provinces - Province select for a country (HTMX partial).
+#[instrument(skip(state))]
+pub async fn provinces(
+    RequireAdminAuth(_): RequireAdminAuth,
+    State(state): State<AppState>,
+    Query(query): Query<ProvincesQuery>,
+) -> Response {
+    let countries = match state.countries().await {
+        Ok(countries) => countries,
+        Err(e) => {
+            tracing::error!("Failed to fetch countries: {e}");
+            return (StatusCode::BAD_GATEWAY, "Failed to load provinces").into_response();
+        }
+    };
+
+    let country = query.country.as_deref().filter(|c| !c.is_empty());
+    let template = ProvinceSelectTemplate {
+        country_selector: CountrySelector::provinces_only(countries, country),
+    };
+
+    Html(template.render().unwrap_or_else(|e| {
+        tracing::error!("Template render error: {}", e);
+        "Internal Server Error".to_string()
+    }))
+    .into_response()
+}
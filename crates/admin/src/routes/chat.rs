@@ -8,7 +8,7 @@ use axum::response::sse::{Event, KeepAlive};
 use axum::{
     Json, Router,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{Html, IntoResponse, Redirect, Response, Sse},
     routing::{delete, get, post},
 };
@@ -18,12 +18,14 @@ use std::convert::Infallible;
 
 use naked_pineapple_core::{AdminUserId, ChatSessionId};
 
-use crate::claude::ClaudeClient;
-use crate::db::ChatRepository;
+use crate::claude::{ClaudeClient, ClaudeUsageTracker};
+use crate::db::{ChatRepository, ReadPool};
 use crate::filters;
 use crate::middleware::RequireAdminAuth;
+use crate::models::CurrentAdmin;
 use crate::models::chat::{ChatMessage, ChatSession};
 use crate::routes::dashboard::AdminUserView;
+use crate::services::chat_export::{conversation_to_markdown, export_filename};
 use crate::services::{ChatError, ChatService, ChatStreamEvent, stream_chat_message};
 use crate::state::AppState;
 
@@ -45,6 +47,9 @@ pub fn router() -> Router<AppState> {
             get(get_session).delete(delete_session),
         )
         .route("/chat/sessions/{id}/messages", post(send_message))
+        // Export routes
+        .route("/chat/sessions/{id}/export", get(export_markdown))
+        .route("/chat/sessions/{id}/export.json", get(export_json))
         .route(
             "/chat/sessions/{id}/messages/stream",
             post(send_message_stream),
@@ -179,7 +184,12 @@ async fn list_sessions(
     RequireAdminAuth(admin): RequireAdminAuth,
 ) -> Result<Json<Vec<SessionResponse>>, ChatError> {
     let claude = ClaudeClient::new(state.config().claude());
-    let service = ChatService::new(state.pool(), &claude, state.shopify());
+    let service = ChatService::new(
+        state.pool(),
+        &claude,
+        state.shopify(),
+        state.tool_confirmations(),
+    );
 
     let sessions = service.list_sessions(admin.id).await?;
 
@@ -194,7 +204,12 @@ async fn create_session(
     RequireAdminAuth(admin): RequireAdminAuth,
 ) -> Result<(StatusCode, Json<SessionResponse>), ChatError> {
     let claude = ClaudeClient::new(state.config().claude());
-    let service = ChatService::new(state.pool(), &claude, state.shopify());
+    let service = ChatService::new(
+        state.pool(),
+        &claude,
+        state.shopify(),
+        state.tool_confirmations(),
+    );
 
     let session = service.create_session(admin.id).await?;
 
@@ -212,7 +227,12 @@ async fn get_session(
     let session_id = ChatSessionId::new(id);
 
     let claude = ClaudeClient::new(state.config().claude());
-    let service = ChatService::new(state.pool(), &claude, state.shopify());
+    let service = ChatService::new(
+        state.pool(),
+        &claude,
+        state.shopify(),
+        state.tool_confirmations(),
+    );
 
     let session = service
         .get_session(session_id)
@@ -234,16 +254,29 @@ async fn get_session(
 /// Returns all new messages (user message + assistant response + any tool use).
 async fn send_message(
     State(state): State<AppState>,
-    RequireAdminAuth(_admin): RequireAdminAuth,
+    RequireAdminAuth(admin): RequireAdminAuth,
     Path(id): Path<i32>,
     Json(request): Json<SendMessageRequest>,
 ) -> Result<Json<SendMessageResponse>, ChatError> {
     let session_id = ChatSessionId::new(id);
 
     let claude = ClaudeClient::new(state.config().claude());
-    let service = ChatService::new(state.pool(), &claude, state.shopify());
-
-    let messages = service.send_message(session_id, &request.message).await?;
+    let service = ChatService::new(
+        state.pool(),
+        &claude,
+        state.shopify(),
+        state.tool_confirmations(),
+    );
+    let usage = usage_tracker(&state, &admin);
+
+    let messages = service
+        .send_message(
+            session_id,
+            &request.message,
+            state.prompt_template(),
+            &usage,
+        )
+        .await?;
 
     Ok(Json(SendMessageResponse {
         messages: messages.into_iter().map(Into::into).collect(),
@@ -259,7 +292,7 @@ async fn send_message(
 /// tool results are sent after execution.
 async fn send_message_stream(
     State(state): State<AppState>,
-    RequireAdminAuth(_admin): RequireAdminAuth,
+    RequireAdminAuth(admin): RequireAdminAuth,
     Path(id): Path<i32>,
     Json(request): Json<SendMessageRequest>,
 ) -> Sse<impl futures::Stream<Item = Result<Event, Infallible>>> {
@@ -269,9 +302,21 @@ async fn send_message_stream(
     let pool = state.pool().clone();
     let claude = ClaudeClient::new(state.config().claude());
     let shopify = state.shopify().clone();
+    let confirmations = state.tool_confirmations().clone();
+    let prompt = state.prompt_template().clone();
+    let usage = usage_tracker(&state, &admin);
 
     // Use true streaming - events are yielded as Claude generates them
-    let event_stream = stream_chat_message(pool, claude, shopify, session_id, request.message);
+    let event_stream = stream_chat_message(
+        pool,
+        claude,
+        shopify,
+        confirmations,
+        prompt,
+        usage,
+        session_id,
+        request.message,
+    );
 
     // Map ChatStreamEvent to SSE Event
     let sse_stream = event_stream.map(|event| {
@@ -284,6 +329,16 @@ async fn send_message_stream(
     Sse::new(sse_stream).keep_alive(KeepAlive::default())
 }
 
+/// Usage tracker that records Claude token usage against `admin`.
+fn usage_tracker(state: &AppState, admin: &CurrentAdmin) -> ClaudeUsageTracker {
+    ClaudeUsageTracker::new(
+        state.pool().clone(),
+        state.config().claude(),
+        state.slack().cloned(),
+        admin.email.clone(),
+    )
+}
+
 // =============================================================================
 // History Routes (Server-Rendered)
 // =============================================================================
@@ -333,7 +388,7 @@ struct HistoryPageTemplate {
 ///
 /// GET /chat/history
 async fn history_page(
-    State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
     RequireAdminAuth(admin): RequireAdminAuth,
     Query(params): Query<HistoryQueryParams>,
 ) -> impl IntoResponse {
@@ -346,7 +401,7 @@ async fn history_page(
         Some(admin.id.as_i32())
     };
 
-    let repo = ChatRepository::new(state.pool());
+    let repo = ChatRepository::new(&pool);
 
     // Get total count and paginated sessions
     let admin_filter = filter_admin_id.map(AdminUserId::new);
@@ -416,14 +471,14 @@ struct HistoryShowTemplate {
 ///
 /// GET /chat/history/:id
 async fn history_show(
-    State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
     RequireAdminAuth(admin): RequireAdminAuth,
     Path(id): Path<i32>,
 ) -> Result<impl IntoResponse, Response> {
     let session_id = ChatSessionId::new(id);
     let is_super_admin = admin.role == crate::models::AdminRole::SuperAdmin;
 
-    let repo = ChatRepository::new(state.pool());
+    let repo = ChatRepository::new(&pool);
 
     // Get the session
     let session = repo
@@ -472,13 +527,13 @@ async fn history_show(
 ///
 /// POST /chat/history/:id/continue
 async fn history_continue(
-    State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
     RequireAdminAuth(admin): RequireAdminAuth,
     Path(id): Path<i32>,
 ) -> Result<impl IntoResponse, Response> {
     let session_id = ChatSessionId::new(id);
 
-    let repo = ChatRepository::new(state.pool());
+    let repo = ChatRepository::new(&pool);
 
     // Verify session exists and belongs to user
     let is_owner = repo
@@ -532,6 +587,92 @@ async fn delete_session(
     Ok(StatusCode::NO_CONTENT)
 }
 
+// =============================================================================
+// Export Routes
+// =============================================================================
+
+/// Load a conversation for export, checking the admin may read it.
+///
+/// Session owners and super admins can export a conversation.
+async fn load_conversation(
+    state: &AppState,
+    admin: &CurrentAdmin,
+    id: i32,
+) -> Result<(ChatSession, Vec<ChatMessage>), Response> {
+    let session_id = ChatSessionId::new(id);
+    let is_super_admin = admin.role == crate::models::AdminRole::SuperAdmin;
+
+    let repo = ChatRepository::new(state.pool());
+
+    let session = repo
+        .get_session(session_id)
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response())?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Session not found").into_response())?;
+
+    // Check permission: super admin or session owner
+    if !is_super_admin && session.admin_user_id != admin.id {
+        return Err((StatusCode::FORBIDDEN, "Access denied").into_response());
+    }
+
+    let messages = repo
+        .get_messages(session_id)
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response())?;
+
+    Ok((session, messages))
+}
+
+/// `Content-Disposition` value that downloads as `filename`.
+fn attachment(filename: &str) -> String {
+    format!("attachment; filename=\"{filename}\"")
+}
+
+/// Download a conversation as Markdown.
+///
+/// GET /chat/sessions/:id/export
+async fn export_markdown(
+    State(state): State<AppState>,
+    RequireAdminAuth(admin): RequireAdminAuth,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, Response> {
+    let (session, messages) = load_conversation(&state, &admin, id).await?;
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                "text/markdown; charset=utf-8".to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                attachment(&export_filename(&session, "md")),
+            ),
+        ],
+        conversation_to_markdown(&session, &messages),
+    ))
+}
+
+/// Download a conversation as JSON, in the same shape as `GET /chat/sessions/:id`.
+///
+/// GET /chat/sessions/:id/export.json
+async fn export_json(
+    State(state): State<AppState>,
+    RequireAdminAuth(admin): RequireAdminAuth,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, Response> {
+    let (session, messages) = load_conversation(&state, &admin, id).await?;
+    let disposition = attachment(&export_filename(&session, "json"));
+
+    Ok((
+        [(header::CONTENT_DISPOSITION, disposition)],
+        Json(SessionWithMessagesResponse {
+            session: session.into(),
+            messages: messages.into_iter().map(Into::into).collect(),
+        }),
+    ))
+}
+
 // =============================================================================
 // Debug Panel (Super Admin Only)
 // =============================================================================
@@ -744,6 +885,7 @@ fn process_tool_result(
 /// GET /chat/sessions/:id/debug
 async fn get_session_debug(
     State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
     RequireAdminAuth(admin): RequireAdminAuth,
     Path(id): Path<i32>,
 ) -> Result<Json<SessionDebugResponse>, Response> {
@@ -753,7 +895,7 @@ async fn get_session_debug(
     }
 
     let session_id = ChatSessionId::new(id);
-    let repo = ChatRepository::new(state.pool());
+    let repo = ChatRepository::new(&pool);
 
     // Verify session exists
     let _session = repo
@@ -1,18 +1,24 @@
 //! Collections management route handlers.
 
 use std::collections::HashSet;
+use std::convert::Infallible;
 
 use askama::Template;
+use async_stream::stream;
 use axum::{
     Form, Json,
     extract::{Path, Query, State},
     http::StatusCode,
-    response::{Html, IntoResponse, Redirect},
+    response::{
+        Html, IntoResponse, Redirect, Response, Sse,
+        sse::{Event, KeepAlive},
+    },
 };
-use serde::Deserialize;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
-use naked_pineapple_core::AdminRole;
+use naked_pineapple_core::{AdminRole, CollectionId, GidError, ProductId};
 
 use crate::{
     filters,
@@ -82,7 +88,7 @@ pub struct CollectionView {
 impl From<&Collection> for CollectionView {
     fn from(collection: &Collection) -> Self {
         Self {
-            id: collection.id.clone(),
+            id: collection.id.to_string(),
             title: collection.title.clone(),
             handle: collection.handle.clone(),
             description: collection.description.clone(),
@@ -120,7 +126,7 @@ pub struct CollectionDetailView {
 impl From<&Collection> for CollectionDetailView {
     fn from(collection: &Collection) -> Self {
         Self {
-            id: collection.id.clone(),
+            id: collection.id.to_string(),
             title: collection.title.clone(),
             handle: collection.handle.clone(),
             description: collection.description.clone(),
@@ -165,7 +171,7 @@ impl From<&CollectionProduct> for CollectionProductView {
         };
 
         Self {
-            id: product.id.clone(),
+            id: product.id.to_string(),
             title: product.title.clone(),
             handle: product.handle.clone(),
             status: product.status.clone(),
@@ -280,10 +286,8 @@ pub async fn show(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let collection_id = if id.starts_with("gid://") {
-        id
-    } else {
-        format!("gid://shopify/Collection/{id}")
+    let Ok(collection_id) = CollectionId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Collection not found").into_response();
     };
 
     match state
@@ -385,10 +389,8 @@ pub async fn edit(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let collection_id = if id.starts_with("gid://") {
-        id
-    } else {
-        format!("gid://shopify/Collection/{id}")
+    let Ok(collection_id) = CollectionId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Collection not found").into_response();
     };
 
     // Fetch collection and all available publications in parallel
@@ -449,7 +451,7 @@ pub async fn edit(
 /// Handle collection publication changes across multiple sales channels.
 async fn handle_publication_changes(
     state: &AppState,
-    collection_id: &str,
+    collection_id: &CollectionId,
     current_publications: &[crate::shopify::types::ResourcePublication],
     desired_publication_ids: &[String],
 ) {
@@ -597,10 +599,8 @@ pub async fn update(
     Path(id): Path<String>,
     Form(input): Form<CollectionFormInput>,
 ) -> impl IntoResponse {
-    let collection_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Collection/{id}")
+    let Ok(collection_id) = CollectionId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Collection not found").into_response();
     };
 
     // Get current collection to check publication changes
@@ -685,11 +685,7 @@ pub async fn update(
             .await;
 
             tracing::info!(collection_id = %collection_id, "Collection updated");
-            let numeric_id = collection_id
-                .split('/')
-                .next_back()
-                .unwrap_or(&collection_id);
-            Redirect::to(&format!("/collections/{numeric_id}")).into_response()
+            Redirect::to(&format!("/collections/{}", collection_id.numeric_id())).into_response()
         }
         Err(e) => {
             tracing::error!(collection_id = %collection_id, error = %e, "Failed to update collection");
@@ -705,10 +701,8 @@ pub async fn delete(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let collection_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Collection/{id}")
+    let Ok(collection_id) = CollectionId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Collection not found").into_response();
     };
 
     match state.shopify().delete_collection(&collection_id).await {
@@ -731,24 +725,18 @@ pub async fn add_products(
     Path(id): Path<String>,
     Json(input): Json<ProductsFormInput>,
 ) -> impl IntoResponse {
-    let collection_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Collection/{id}")
+    let Ok(collection_id) = CollectionId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Collection not found").into_response();
     };
 
-    // Ensure product IDs are in GID format
-    let product_ids: Vec<String> = input
+    let Ok(product_ids) = input
         .product_ids
-        .into_iter()
-        .map(|pid| {
-            if pid.starts_with("gid://") {
-                pid
-            } else {
-                format!("gid://shopify/Product/{pid}")
-            }
-        })
-        .collect();
+        .iter()
+        .map(|pid| ProductId::parse_admin_id(pid))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return (StatusCode::BAD_REQUEST, "Invalid product ID").into_response();
+    };
 
     match state
         .shopify()
@@ -807,24 +795,18 @@ pub async fn remove_products(
     Path(id): Path<String>,
     Json(input): Json<ProductsFormInput>,
 ) -> impl IntoResponse {
-    let collection_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Collection/{id}")
+    let Ok(collection_id) = CollectionId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Collection not found").into_response();
     };
 
-    // Ensure product IDs are in GID format
-    let product_ids: Vec<String> = input
+    let Ok(product_ids) = input
         .product_ids
-        .into_iter()
-        .map(|pid| {
-            if pid.starts_with("gid://") {
-                pid
-            } else {
-                format!("gid://shopify/Product/{pid}")
-            }
-        })
-        .collect();
+        .iter()
+        .map(|pid| ProductId::parse_admin_id(pid))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return (StatusCode::BAD_REQUEST, "Invalid product ID").into_response();
+    };
 
     match state
         .shopify()
@@ -893,10 +875,8 @@ pub async fn update_sort_order(
     Path(id): Path<String>,
     Json(input): Json<SortOrderInput>,
 ) -> impl IntoResponse {
-    let collection_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Collection/{id}")
+    let Ok(collection_id) = CollectionId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Collection not found").into_response();
     };
 
     match state
@@ -945,24 +925,18 @@ pub async fn reorder_products(
     Path(id): Path<String>,
     Json(moves): Json<Vec<ProductMoveInput>>,
 ) -> impl IntoResponse {
-    let collection_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Collection/{id}")
+    let Ok(collection_id) = CollectionId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Collection not found").into_response();
     };
 
     // Convert moves to the format expected by the API
-    let move_tuples: Vec<(String, i64)> = moves
-        .into_iter()
-        .map(|m| {
-            let product_id = if m.id.starts_with("gid://") {
-                m.id
-            } else {
-                format!("gid://shopify/Product/{}", m.id)
-            };
-            (product_id, m.new_position)
-        })
-        .collect();
+    let Ok(move_tuples) = moves
+        .iter()
+        .map(|m| ProductId::parse_admin_id(&m.id).map(|product_id| (product_id, m.new_position)))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return (StatusCode::BAD_REQUEST, "Invalid product ID").into_response();
+    };
 
     match state
         .shopify()
@@ -1064,21 +1038,46 @@ async fn upload_to_staged_target(
     }
 }
 
+/// Largest collection image accepted, matching Shopify's 20 MB image limit.
+pub const MAX_IMAGE_UPLOAD_BYTES: usize = 20 * 1024 * 1024;
+
+/// Progress event streamed while a collection image is uploaded.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ImageUploadEvent {
+    /// A step started; `percent` is overall progress.
+    Progress { percent: u8, message: &'static str },
+    /// The image is attached to the collection.
+    Complete { filename: String },
+    /// The upload failed.
+    Error { message: String },
+}
+
+impl ImageUploadEvent {
+    fn into_sse(self) -> Event {
+        let json = serde_json::to_string(&self).unwrap_or_else(|_| {
+            r#"{"type":"error","message":"Failed to serialize event"}"#.to_string()
+        });
+        Event::default().data(json)
+    }
+}
+
 /// Upload collection image handler.
-#[instrument(skip(_admin, state))]
+///
+/// Reads the uploaded file, then streams progress via SSE while it is
+/// staged with Shopify, uploaded to the staging URL, and attached to the
+/// collection.
+#[instrument(skip(_admin, state, multipart))]
 pub async fn upload_image(
     RequireAdminAuth(_admin): RequireAdminAuth,
     State(state): State<AppState>,
     Path(id): Path<String>,
     multipart: axum::extract::Multipart,
-) -> impl IntoResponse {
-    let collection_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Collection/{id}")
+) -> Response {
+    let Ok(collection_id) = CollectionId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Collection not found").into_response();
     };
 
-    // Step 1: Extract file from multipart
     let file = match extract_file_from_multipart(multipart).await {
         Ok(f) => f,
         Err(msg) => {
@@ -1090,64 +1089,65 @@ pub async fn upload_image(
         }
     };
 
-    let file_size = i64::try_from(file.bytes.len()).unwrap_or(i64::MAX);
-
-    // Step 2: Create staged upload target
-    let staged_target = match state
-        .shopify()
-        .create_staged_upload(&file.filename, &file.content_type, file_size, "IMAGE")
-        .await
-    {
-        Ok(target) => target,
-        Err(e) => {
-            tracing::error!(error = %e, "Failed to create staged upload");
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"error": format!("Failed to create upload: {e}")})),
-            )
-                .into_response();
-        }
-    };
-
-    // Step 3: Upload file to staged target
-    if let Err(msg) = upload_to_staged_target(
-        &staged_target,
-        &file.filename,
-        &file.content_type,
-        file.bytes,
-    )
-    .await
-    {
+    if file.bytes.len() > MAX_IMAGE_UPLOAD_BYTES {
         return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": msg})),
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(serde_json::json!({"error": "Image must be 20 MB or smaller"})),
         )
             .into_response();
     }
 
-    // Step 4: Update collection with new image
-    match state
-        .shopify()
-        .update_collection_image(&collection_id, &staged_target.resource_url, None)
+    let shopify = state.shopify().clone();
+    let events = stream! {
+        yield ImageUploadEvent::Progress { percent: 40, message: "Preparing upload..." };
+
+        let file_size = i64::try_from(file.bytes.len()).unwrap_or(i64::MAX);
+        let staged_target = match shopify
+            .create_staged_upload(&file.filename, &file.content_type, file_size, "IMAGE")
+            .await
+        {
+            Ok(target) => target,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to create staged upload");
+                yield ImageUploadEvent::Error { message: format!("Failed to create upload: {e}") };
+                return;
+            }
+        };
+
+        yield ImageUploadEvent::Progress { percent: 60, message: "Uploading to Shopify..." };
+
+        if let Err(message) = upload_to_staged_target(
+            &staged_target,
+            &file.filename,
+            &file.content_type,
+            file.bytes,
+        )
         .await
-    {
-        Ok(()) => {
-            tracing::info!(collection_id = %collection_id, filename = %file.filename, "Collection image uploaded");
-            (
-                StatusCode::OK,
-                Json(serde_json::json!({"success": true, "filename": file.filename})),
-            )
-                .into_response()
+        {
+            yield ImageUploadEvent::Error { message };
+            return;
         }
-        Err(e) => {
-            tracing::error!(error = %e, "Failed to update collection image");
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"error": format!("Failed to update image: {e}")})),
-            )
-                .into_response()
+
+        yield ImageUploadEvent::Progress { percent: 85, message: "Updating collection..." };
+
+        match shopify
+            .update_collection_image(&collection_id, &staged_target.resource_url, None)
+            .await
+        {
+            Ok(()) => {
+                tracing::info!(collection_id = %collection_id, filename = %file.filename, "Collection image uploaded");
+                yield ImageUploadEvent::Complete { filename: file.filename };
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to update collection image");
+                yield ImageUploadEvent::Error { message: format!("Failed to update image: {e}") };
+            }
         }
-    }
+    };
+
+    Sse::new(events.map(|event| Ok::<_, Infallible>(event.into_sse())))
+        .keep_alive(KeepAlive::default())
+        .into_response()
 }
 
 /// Delete collection image handler.
@@ -1157,10 +1157,8 @@ pub async fn delete_image(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let collection_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Collection/{id}")
+    let Ok(collection_id) = CollectionId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Collection not found").into_response();
     };
 
     match state
@@ -11,17 +11,24 @@ use serde::Deserialize;
 use tracing::instrument;
 
 use crate::{
+    components::CountrySelector,
     filters,
     middleware::auth::{RequireAdminAuth, RequireSuperAdmin},
     models::CurrentAdmin,
-    shopify::types::{Address, Customer, CustomerOrder, CustomerState, Money},
+    services::FlowService,
+    shopify::types::{
+        Address, Customer, CustomerOrder, CustomerState, CustomerUpdateParams, FlowTrigger, Money,
+    },
     state::AppState,
 };
 
-use naked_pineapple_core::AdminRole;
+use naked_pineapple_core::{AdminRole, CustomerId};
 
 use super::dashboard::AdminUserView;
 
+/// Customer tag that marks a high value customer for Shopify Flow.
+const VIP_TAG: &str = "VIP";
+
 // =============================================================================
 // Query Parameters
 // =============================================================================
@@ -423,9 +430,10 @@ pub struct CustomerDetailView {
 }
 
 /// Address view for templates.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct AddressView {
     pub id: Option<String>,
+    pub short_id: String,
     pub formatted: String,
     pub address1: Option<String>,
     pub address2: Option<String>,
@@ -520,6 +528,15 @@ fn fulfillment_status_class(status: Option<&str>) -> &'static str {
     }
 }
 
+/// Numeric ID of a mailing address GID, as used in address routes.
+///
+/// Customer address GIDs carry a `?model_name=CustomerAddress` suffix,
+/// which is dropped.
+fn address_short_id(gid: &str) -> String {
+    let id = gid.split('/').next_back().unwrap_or(gid);
+    id.split('?').next().unwrap_or(id).to_string()
+}
+
 /// Format an address as a single line.
 fn format_address(addr: &Address) -> String {
     let parts: Vec<&str> = [
@@ -561,8 +578,8 @@ impl From<&Customer> for CustomerView {
         let location = customer.default_address.as_ref().and_then(get_location);
 
         Self {
-            id: customer.id.clone(),
-            short_id: extract_short_id(&customer.id),
+            id: customer.id.to_string(),
+            short_id: extract_short_id(customer.id.as_str()),
             name: customer.display_name.clone(),
             email: customer.email.clone(),
             phone: customer.phone.clone(),
@@ -583,6 +600,7 @@ impl From<&Customer> for CustomerDetailView {
     fn from(customer: &Customer) -> Self {
         let default_address = customer.default_address.as_ref().map(|a| AddressView {
             id: a.id.clone(),
+            short_id: a.id.as_deref().map(address_short_id).unwrap_or_default(),
             formatted: format_address(a),
             address1: a.address1.clone(),
             address2: a.address2.clone(),
@@ -604,6 +622,7 @@ impl From<&Customer> for CustomerDetailView {
             .iter()
             .map(|a| AddressView {
                 id: a.id.clone(),
+                short_id: a.id.as_deref().map(address_short_id).unwrap_or_default(),
                 formatted: format_address(a),
                 address1: a.address1.clone(),
                 address2: a.address2.clone(),
@@ -623,8 +642,8 @@ impl From<&Customer> for CustomerDetailView {
             .recent_orders
             .iter()
             .map(|o| OrderView {
-                id: o.id.clone(),
-                short_id: extract_short_id(&o.id),
+                id: o.id.to_string(),
+                short_id: extract_short_id(o.id.as_str()),
                 name: o.name.clone(),
                 created_at: o.created_at.clone(),
                 financial_status: o.financial_status.clone(),
@@ -638,8 +657,8 @@ impl From<&Customer> for CustomerDetailView {
             .collect();
 
         Self {
-            id: customer.id.clone(),
-            short_id: extract_short_id(&customer.id),
+            id: customer.id.to_string(),
+            short_id: extract_short_id(customer.id.as_str()),
             display_name: customer.display_name.clone(),
             first_name: customer.first_name.clone(),
             last_name: customer.last_name.clone(),
@@ -865,7 +884,9 @@ pub async fn show(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let gid = format!("gid://shopify/Customer/{id}");
+    let Ok(gid) = CustomerId::from_numeric(&id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
 
     match state.shopify().get_customer(&gid).await {
         Ok(Some(customer)) => {
@@ -959,7 +980,9 @@ pub async fn edit(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let gid = format!("gid://shopify/Customer/{id}");
+    let Ok(gid) = CustomerId::from_numeric(&id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
 
     match state.shopify().get_customer(&gid).await {
         Ok(Some(customer)) => {
@@ -992,7 +1015,9 @@ pub async fn update(
     Path(id): Path<String>,
     Form(input): Form<CustomerFormInput>,
 ) -> impl IntoResponse {
-    let gid = format!("gid://shopify/Customer/{id}");
+    let Ok(gid) = CustomerId::from_numeric(&id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
 
     let tags: Option<Vec<String>> = input.tags.as_ref().map(|t| {
         t.split(',')
@@ -1001,14 +1026,14 @@ pub async fn update(
             .collect()
     });
 
-    let params = crate::shopify::types::CustomerUpdateParams {
-        email: Some(input.email.clone()),
-        first_name: input.first_name.clone(),
-        last_name: input.last_name.clone(),
-        phone: input.phone.clone(),
-        note: input.note.clone(),
-        tags,
-    };
+    let params = CustomerUpdateParams::builder()
+        .email(input.email.clone())
+        .first_name(input.first_name.clone())
+        .last_name(input.last_name.clone())
+        .phone(input.phone.clone())
+        .note(input.note.clone())
+        .tags(tags)
+        .build();
 
     match state.shopify().update_customer(&gid, params).await {
         Ok(_) => Redirect::to(&format!("/customers/{id}")).into_response(),
@@ -1046,7 +1071,9 @@ pub async fn delete(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let gid = format!("gid://shopify/Customer/{id}");
+    let Ok(gid) = CustomerId::from_numeric(&id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
 
     match state.shopify().delete_customer(&gid).await {
         Ok(_) => Redirect::to("/customers").into_response(),
@@ -1069,7 +1096,9 @@ pub async fn update_tags(
     Path(id): Path<String>,
     Form(input): Form<TagsFormInput>,
 ) -> impl IntoResponse {
-    let gid = format!("gid://shopify/Customer/{id}");
+    let Ok(gid) = CustomerId::from_numeric(&id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
 
     let tags: Vec<String> = input
         .tags
@@ -1078,7 +1107,9 @@ pub async fn update_tags(
         .filter(|s| !s.is_empty())
         .collect();
 
-    let result = if input.action == "add" {
+    let adding = input.action == "add";
+    let marks_vip = adding && tags.iter().any(|tag| tag.eq_ignore_ascii_case(VIP_TAG));
+    let result = if adding {
         state.shopify().add_customer_tags(&gid, tags).await
     } else {
         state.shopify().remove_customer_tags(&gid, tags).await
@@ -1086,6 +1117,11 @@ pub async fn update_tags(
 
     match result {
         Ok(_) => {
+            if marks_vip {
+                FlowService::new(state.shopify(), &state.config().flow)
+                    .send(&FlowTrigger::HighValueCustomer(gid.to_string()))
+                    .await;
+            }
             // Return updated tags partial
             if let Ok(Some(customer)) = state.shopify().get_customer(&gid).await {
                 let tags_html = customer
@@ -1127,13 +1163,14 @@ pub async fn update_note(
     Path(id): Path<String>,
     Form(input): Form<NoteFormInput>,
 ) -> impl IntoResponse {
-    let gid = format!("gid://shopify/Customer/{id}");
-
-    let params = crate::shopify::types::CustomerUpdateParams {
-        note: Some(input.note.clone()),
-        ..Default::default()
+    let Ok(gid) = CustomerId::from_numeric(&id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
     };
 
+    let params = CustomerUpdateParams::builder()
+        .note(input.note.clone())
+        .build();
+
     match state.shopify().update_customer(&gid, params).await {
         Ok(_) => Html("Note saved").into_response(),
         Err(e) => {
@@ -1150,7 +1187,9 @@ pub async fn send_invite(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let gid = format!("gid://shopify/Customer/{id}");
+    let Ok(gid) = CustomerId::from_numeric(&id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
 
     match state.shopify().send_customer_invite(&gid).await {
         Ok(()) => Html(
@@ -1173,7 +1212,9 @@ pub async fn activation_url(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let gid = format!("gid://shopify/Customer/{id}");
+    let Ok(gid) = CustomerId::from_numeric(&id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
 
     match state.shopify().generate_customer_activation_url(&gid).await {
         Ok(url) => Html(format!(
@@ -1374,7 +1415,9 @@ pub async fn update_marketing(
     Path(id): Path<String>,
     Form(form): Form<UpdateMarketingForm>,
 ) -> impl IntoResponse {
-    let gid = format!("gid://shopify/Customer/{id}");
+    let Ok(gid) = CustomerId::from_numeric(&id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
     let marketing_state = if form.subscribed == "true" {
         "SUBSCRIBED"
     } else {
@@ -1451,6 +1494,101 @@ pub struct AddressForm {
     pub is_default: Option<String>,
 }
 
+/// Address form modal template.
+#[derive(Template)]
+#[template(path = "customers/_address_form.html")]
+pub struct AddressFormTemplate {
+    pub customer_id: String,
+    /// The address being edited, empty when adding one.
+    pub address: AddressView,
+    pub is_edit: bool,
+    pub country_selector: CountrySelector,
+    pub error: Option<String>,
+}
+
+/// Render the address form modal.
+///
+/// The country list is left empty if it can't be fetched, with the reason
+/// shown in the modal.
+async fn render_address_form(
+    state: &AppState,
+    customer_id: String,
+    address: Option<AddressView>,
+) -> Html<String> {
+    let (country_selector, error) = match state.countries().await {
+        Ok(countries) => (
+            CountrySelector::new(
+                countries,
+                address.as_ref().and_then(|a| a.country_code.as_deref()),
+                address.as_ref().and_then(|a| a.province_code.as_deref()),
+            ),
+            None,
+        ),
+        Err(e) => {
+            tracing::error!("Failed to fetch countries: {e}");
+            (
+                CountrySelector::default(),
+                Some(format!("Failed to load countries: {e}")),
+            )
+        }
+    };
+
+    let template = AddressFormTemplate {
+        customer_id,
+        is_edit: address.is_some(),
+        address: address.unwrap_or_default(),
+        country_selector,
+        error,
+    };
+
+    Html(template.render().unwrap_or_else(|e| {
+        tracing::error!("Template render error: {}", e);
+        "Internal Server Error".to_string()
+    }))
+}
+
+/// GET `/customers/{id}/addresses/new` - Add address modal (HTMX partial).
+#[instrument(skip(state))]
+pub async fn address_new(
+    RequireAdminAuth(_): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Html<String> {
+    render_address_form(&state, id, None).await
+}
+
+/// GET `/customers/{id}/addresses/{address_id}/edit` - Edit address modal (HTMX partial).
+#[instrument(skip(state))]
+pub async fn address_edit(
+    RequireAdminAuth(_): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path((customer_id, address_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    let Ok(gid) = CustomerId::from_numeric(&customer_id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
+
+    match state.shopify().get_customer(&gid).await {
+        Ok(Some(customer)) => {
+            let address = CustomerDetailView::from(&customer)
+                .addresses
+                .into_iter()
+                .find(|a| a.short_id == address_id);
+            match address {
+                Some(address) => render_address_form(&state, customer_id, Some(address))
+                    .await
+                    .into_response(),
+                None => (StatusCode::NOT_FOUND, "Address not found").into_response(),
+            }
+        }
+        Ok(None) => (StatusCode::NOT_FOUND, "Customer not found").into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch customer: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to load customer").into_response()
+        }
+    }
+}
+
 /// Create a new address for a customer.
 #[instrument(skip(state, form))]
 pub async fn address_create(
@@ -1459,7 +1597,9 @@ pub async fn address_create(
     Path(id): Path<String>,
     Form(form): Form<AddressForm>,
 ) -> impl IntoResponse {
-    let gid = format!("gid://shopify/Customer/{id}");
+    let Ok(gid) = CustomerId::from_numeric(&id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
 
     let address_input = crate::shopify::types::AddressInput {
         first_name: form.first_name,
@@ -1498,7 +1638,9 @@ pub async fn address_update(
     Path((customer_id, address_id)): Path<(String, String)>,
     Form(form): Form<AddressForm>,
 ) -> impl IntoResponse {
-    let shopify_customer_gid = format!("gid://shopify/Customer/{customer_id}");
+    let Ok(shopify_customer_gid) = CustomerId::from_numeric(&customer_id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
     let mailing_address_gid = format!("gid://shopify/MailingAddress/{address_id}");
 
     let address_input = crate::shopify::types::AddressInput {
@@ -1534,7 +1676,9 @@ pub async fn address_delete(
     State(state): State<AppState>,
     Path((customer_id, address_id)): Path<(String, String)>,
 ) -> impl IntoResponse {
-    let shopify_customer_gid = format!("gid://shopify/Customer/{customer_id}");
+    let Ok(shopify_customer_gid) = CustomerId::from_numeric(&customer_id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
     let mailing_address_gid = format!("gid://shopify/MailingAddress/{address_id}");
 
     match state
@@ -1557,7 +1701,9 @@ pub async fn set_default_address(
     State(state): State<AppState>,
     Path((customer_id, address_id)): Path<(String, String)>,
 ) -> impl IntoResponse {
-    let shopify_customer_gid = format!("gid://shopify/Customer/{customer_id}");
+    let Ok(shopify_customer_gid) = CustomerId::from_numeric(&customer_id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
     let mailing_address_gid = format!("gid://shopify/MailingAddress/{address_id}");
 
     match state
@@ -1596,10 +1742,14 @@ pub async fn merge(
     Path(id): Path<String>,
     Form(form): Form<MergeForm>,
 ) -> impl IntoResponse {
-    let customer_one_gid = format!("gid://shopify/Customer/{id}");
+    let Ok(customer_one_gid) = CustomerId::from_numeric(&id) else {
+        return (StatusCode::NOT_FOUND, "Customer not found").into_response();
+    };
 
     // TODO: If merge_customer_id contains '@', look up customer by email first
-    let customer_two_gid = format!("gid://shopify/Customer/{}", form.merge_customer_id);
+    let Ok(customer_two_gid) = CustomerId::from_numeric(&form.merge_customer_id) else {
+        return (StatusCode::BAD_REQUEST, "Invalid customer ID to merge").into_response();
+    };
 
     let overrides = crate::shopify::types::CustomerMergeOverrides {
         first_name: form.override_first_name.as_deref() == Some("true"),
@@ -1660,7 +1810,10 @@ pub async fn bulk_tags(
     let mut errors = Vec::new();
 
     for id in ids {
-        let gid = format!("gid://shopify/Customer/{id}");
+        let Ok(gid) = CustomerId::from_numeric(id) else {
+            errors.push(format!("{id}: invalid customer ID"));
+            continue;
+        };
         let result = match form.action.as_str() {
             "add" => state.shopify().add_customer_tags(&gid, tags.clone()).await,
             "remove" => {
@@ -1718,7 +1871,10 @@ pub async fn bulk_marketing(
     let mut errors = Vec::new();
 
     for id in ids {
-        let gid = format!("gid://shopify/Customer/{id}");
+        let Ok(gid) = CustomerId::from_numeric(id) else {
+            errors.push(format!("{id}: invalid customer ID"));
+            continue;
+        };
         if let Err(e) = state
             .shopify()
             .update_customer_email_marketing(&gid, marketing_state)
@@ -1738,3 +1894,42 @@ pub async fn bulk_marketing(
             .into_response()
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn address_form(address: Option<AddressView>) -> String {
+        AddressFormTemplate {
+            customer_id: "42".to_string(),
+            is_edit: address.is_some(),
+            address: address.unwrap_or_default(),
+            country_selector: CountrySelector::default(),
+            error: None,
+        }
+        .render()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_address_form_for_new_address() {
+        let html = address_form(None);
+        assert!(html.contains(r#"action="/customers/42/addresses""#));
+        assert!(html.contains("Set as default address"));
+    }
+
+    #[test]
+    fn test_address_form_prefills_edited_address() {
+        let html = address_form(Some(AddressView {
+            short_id: "7".to_string(),
+            first_name: Some("Ada".to_string()),
+            city: Some("Honolulu".to_string()),
+            ..AddressView::default()
+        }));
+        assert!(html.contains(r#"action="/customers/42/addresses/7""#));
+        assert!(html.contains(r#"value="Ada""#));
+        assert!(html.contains(r#"value="Honolulu""#));
+        assert!(!html.contains("Set as default address"));
+    }
+}
@@ -8,15 +8,14 @@ use crate::{
     filters,
     middleware::auth::RequireAdminAuth,
     models::CurrentAdmin,
-    shopify::types::{AdminProduct, Money, Order, ProductStatus},
+    services::DashboardMetrics,
+    services::dashboard::LOW_STOCK_THRESHOLD,
+    shopify::types::{AdminProduct, DiscountSummary, Money, Order, ProductStatus},
     state::AppState,
 };
 
 use naked_pineapple_core::AdminRole;
 
-/// Low stock threshold for alerts.
-const LOW_STOCK_THRESHOLD: i64 = 10;
-
 /// Admin user view for templates.
 #[derive(Debug, Clone)]
 pub struct AdminUserView {
@@ -37,22 +36,75 @@ impl From<&CurrentAdmin> for AdminUserView {
     }
 }
 
-/// Dashboard metrics.
+/// Top product row for the dashboard metrics.
 #[derive(Debug, Clone)]
-pub struct DashboardMetrics {
-    pub orders: String,
+pub struct TopProductView {
+    pub title: String,
     pub revenue: String,
-    pub customers: String,
-    pub products: String,
+    pub units_sold: i64,
+}
+
+/// Discount summary for the dashboard metrics.
+#[derive(Debug, Clone)]
+pub struct DiscountSummaryView {
+    pub active_codes: i64,
+    pub redemptions: i64,
+    pub discount_value: String,
+    /// Most-used codes this month as `(code, redemptions)`.
+    pub top_codes: Vec<(String, i64)>,
+}
+
+impl From<&DiscountSummary> for DiscountSummaryView {
+    fn from(summary: &DiscountSummary) -> Self {
+        Self {
+            active_codes: summary.total_active_codes,
+            redemptions: summary.total_redemptions_in_period,
+            discount_value: format_price(&summary.total_discount_value_in_period),
+            top_codes: summary
+                .top_codes
+                .iter()
+                .map(|c| (c.code.clone(), c.usage_count))
+                .collect(),
+        }
+    }
+}
+
+/// Dashboard metric cards view.
+#[derive(Debug, Clone)]
+pub struct DashboardMetricsView {
+    pub orders_today: i64,
+    pub orders_this_month: i64,
+    pub revenue_today: String,
+    pub revenue_this_month: String,
+    pub pending_fulfillments: i64,
+    pub low_stock_items: i64,
+    pub top_products: Vec<TopProductView>,
+    /// `None` if the discount summary couldn't be loaded.
+    pub discounts: Option<DiscountSummaryView>,
+    /// Time the metrics were assembled (e.g., "14:05 UTC").
+    pub updated_at: String,
 }
 
-impl Default for DashboardMetrics {
-    fn default() -> Self {
+impl From<&DashboardMetrics> for DashboardMetricsView {
+    fn from(metrics: &DashboardMetrics) -> Self {
         Self {
-            orders: "0".to_string(),
-            revenue: "$0.00".to_string(),
-            customers: "0".to_string(),
-            products: "0".to_string(),
+            orders_today: metrics.orders_today,
+            orders_this_month: metrics.orders_this_month,
+            revenue_today: format!("${:.2}", metrics.revenue_today),
+            revenue_this_month: format!("${:.2}", metrics.revenue_this_month),
+            pending_fulfillments: metrics.pending_fulfillments,
+            low_stock_items: metrics.low_stock_items,
+            top_products: metrics
+                .top_products
+                .iter()
+                .map(|p| TopProductView {
+                    title: p.product_title.clone(),
+                    revenue: format!("${:.2}", p.total_sales),
+                    units_sold: p.units_sold,
+                })
+                .collect(),
+            discounts: metrics.discounts.as_deref().map(DiscountSummaryView::from),
+            updated_at: metrics.generated_at.format("%H:%M UTC").to_string(),
         }
     }
 }
@@ -113,12 +165,21 @@ impl LowStockItemView {
 pub struct DashboardTemplate {
     pub admin_user: AdminUserView,
     pub current_path: String,
-    pub metrics: DashboardMetrics,
+    /// `None` if the metrics couldn't be loaded.
+    pub metrics: Option<DashboardMetricsView>,
     pub recent_orders: Vec<RecentOrderView>,
     pub recent_activity: Vec<ActivityView>,
     pub low_stock_items: Vec<LowStockItemView>,
 }
 
+/// Dashboard metric cards fragment (for HTMX polling).
+#[derive(Template)]
+#[template(path = "dashboard/_metrics.html")]
+pub struct DashboardMetricsTemplate {
+    /// `None` if the metrics couldn't be loaded.
+    pub metrics: Option<DashboardMetricsView>,
+}
+
 // =============================================================================
 // Type Conversions
 // =============================================================================
@@ -188,91 +249,48 @@ pub async fn dashboard(
     State(state): State<AppState>,
 ) -> Html<String> {
     // Fetch data from Shopify Admin API in parallel
-    let orders_future = state.shopify().get_orders(50, None, None);
+    let orders_future = state.shopify().get_orders(5, None, None);
     let products_future = state.shopify().get_products(50, None, None);
-    let customers_future =
-        state
-            .shopify()
-            .get_customers(crate::shopify::types::CustomerListParams {
-                first: Some(1),
-                ..Default::default()
-            });
-
-    let (orders_result, products_result, customers_result) =
-        tokio::join!(orders_future, products_future, customers_future);
-
-    // Process orders for metrics and recent orders
-    let (order_count, total_revenue, recent_orders) = match orders_result {
-        Ok(order_conn) => {
-            let count = order_conn.orders.len();
-            let revenue: f64 = order_conn
-                .orders
-                .iter()
-                .filter_map(|o| o.total_price.amount.parse::<f64>().ok())
-                .sum();
-            let recent: Vec<RecentOrderView> = order_conn
-                .orders
-                .iter()
-                .take(5)
-                .map(RecentOrderView::from)
-                .collect();
-            (count, revenue, recent)
-        }
+    let metrics_future = state.dashboard().metrics(state.shopify());
+
+    let (orders_result, products_result, metrics_result) =
+        tokio::join!(orders_future, products_future, metrics_future);
+
+    let recent_orders: Vec<RecentOrderView> = match orders_result {
+        Ok(order_conn) => order_conn
+            .orders
+            .iter()
+            .take(5)
+            .map(RecentOrderView::from)
+            .collect(),
         Err(e) => {
             tracing::error!("Failed to fetch orders: {e}");
-            (0, 0.0, vec![])
+            vec![]
         }
     };
 
-    // Get product count and low stock items
-    let (product_count, low_stock_items) = match products_result {
-        Ok(product_conn) => {
-            // Shopify doesn't return total count easily, so we approximate
-            // In production, you'd cache this or use a separate count query
-            let count = if product_conn.page_info.has_next_page {
-                "50+".to_string() // Indicates there are more
-            } else {
-                product_conn.products.len().to_string()
-            };
-
-            // Extract low stock items from all products
-            let low_stock: Vec<LowStockItemView> = product_conn
-                .products
-                .iter()
-                .flat_map(LowStockItemView::from_product)
-                .take(5) // Limit to 5 items on dashboard
-                .collect();
-
-            (count, low_stock)
-        }
+    // Extract low stock items from active products
+    let low_stock_items: Vec<LowStockItemView> = match products_result {
+        Ok(product_conn) => product_conn
+            .products
+            .iter()
+            .flat_map(LowStockItemView::from_product)
+            .take(5) // Limit to 5 items on dashboard
+            .collect(),
         Err(e) => {
             tracing::error!("Failed to fetch products: {e}");
-            ("0".to_string(), vec![])
+            vec![]
         }
     };
 
-    // Get customer count
-    let customer_count = match customers_result {
-        Ok(customer_conn) => {
-            if customer_conn.page_info.has_next_page {
-                "50+".to_string()
-            } else {
-                customer_conn.customers.len().to_string()
-            }
-        }
+    let metrics = match metrics_result {
+        Ok(metrics) => Some(DashboardMetricsView::from(metrics.as_ref())),
         Err(e) => {
-            tracing::error!("Failed to fetch customers: {e}");
-            "0".to_string()
+            tracing::error!("Failed to load dashboard metrics: {e}");
+            None
         }
     };
 
-    let metrics = DashboardMetrics {
-        orders: order_count.to_string(),
-        revenue: format!("${total_revenue:.2}"),
-        customers: customer_count,
-        products: product_count,
-    };
-
     // Build activity feed from recent orders
     let recent_activity: Vec<ActivityView> = recent_orders
         .iter()
@@ -299,3 +317,27 @@ pub async fn dashboard(
         "Internal Server Error".to_string()
     }))
 }
+
+/// Refresh the dashboard metric cards (HTMX).
+///
+/// Bypasses the metrics cache. Polled by the dashboard every five minutes.
+#[instrument(skip(_admin, state))]
+pub async fn refresh_metrics(
+    RequireAdminAuth(_admin): RequireAdminAuth,
+    State(state): State<AppState>,
+) -> Html<String> {
+    let metrics = match state.dashboard().refresh(state.shopify()).await {
+        Ok(metrics) => Some(DashboardMetricsView::from(metrics.as_ref())),
+        Err(e) => {
+            tracing::error!("Failed to refresh dashboard metrics: {e}");
+            None
+        }
+    };
+
+    let template = DashboardMetricsTemplate { metrics };
+
+    Html(template.render().unwrap_or_else(|e| {
+        tracing::error!("Template render error: {}", e);
+        "Internal Server Error".to_string()
+    }))
+}
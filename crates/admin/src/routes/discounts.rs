@@ -9,6 +9,7 @@ use axum::{
     http::StatusCode,
     response::{Html, IntoResponse, Redirect},
 };
+use naked_pineapple_core::CustomerId;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
@@ -19,7 +20,7 @@ use crate::{
     filters,
     middleware::auth::RequireAdminAuth,
     shopify::{
-        DiscountCreateInput,
+        CustomerEligibility, DiscountCreateInput, DiscountItems,
         types::{
             CustomerSegment, DiscountCode, DiscountCombinesWith, DiscountListItem, DiscountMethod,
             DiscountMinimumRequirement, DiscountSortKey, DiscountStatus, DiscountType,
@@ -62,11 +63,81 @@ pub struct BasicDiscountFormInput {
     pub once_per_customer: Option<bool>,
     pub minimum_type: Option<String>,
     pub minimum_value: Option<String>,
+    /// "all", "segments" or "customers".
+    pub customer_eligibility: Option<String>,
+    /// Comma-separated customer segment IDs.
+    pub segment_ids: Option<String>,
+    /// Comma-separated customer IDs.
+    pub customer_ids: Option<String>,
     pub combines_order: Option<bool>,
     pub combines_product: Option<bool>,
     pub combines_shipping: Option<bool>,
 }
 
+impl BasicDiscountFormInput {
+    /// Parse the minimum purchase requirement.
+    fn minimum_requirement(&self) -> Result<DiscountMinimumRequirement, String> {
+        let value = self.minimum_value.as_deref().map_or("", str::trim);
+        match self.minimum_type.as_deref() {
+            Some("amount") => match value.parse::<f64>() {
+                Ok(n) if n.is_finite() && n > 0.0 => Ok(DiscountMinimumRequirement::Subtotal {
+                    amount: value.to_string(),
+                    currency: "USD".to_string(),
+                }),
+                _ => Err("Minimum purchase amount must be greater than 0".to_string()),
+            },
+            Some("quantity") => match value.parse::<u64>() {
+                Ok(n) if n > 0 => Ok(DiscountMinimumRequirement::Quantity {
+                    quantity: n.to_string(),
+                }),
+                _ => Err("Minimum quantity must be a whole number greater than 0".to_string()),
+            },
+            _ => Ok(DiscountMinimumRequirement::None),
+        }
+    }
+
+    /// Parse which customers may use the discount.
+    fn customer_eligibility(&self) -> Result<CustomerEligibility, String> {
+        match self.customer_eligibility.as_deref() {
+            Some("segments") => {
+                let ids = split_ids(self.segment_ids.as_deref(), "Segment");
+                if ids.is_empty() {
+                    return Err("Select at least one customer segment".to_string());
+                }
+                Ok(CustomerEligibility::CustomerSegments(ids))
+            }
+            Some("customers") => {
+                let ids = split_ids(self.customer_ids.as_deref(), "Customer")
+                    .into_iter()
+                    .map(CustomerId::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| "Customer IDs must be numbers or customer GIDs".to_string())?;
+                if ids.is_empty() {
+                    return Err("Enter at least one customer ID".to_string());
+                }
+                Ok(CustomerEligibility::SpecificCustomers(ids))
+            }
+            _ => Ok(CustomerEligibility::All),
+        }
+    }
+}
+
+/// Split a comma-separated list of IDs, expanding numeric IDs to Shopify GIDs.
+fn split_ids(ids: Option<&str>, resource: &str) -> Vec<String> {
+    ids.unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            if id.starts_with("gid://") {
+                id.to_string()
+            } else {
+                format!("gid://shopify/{resource}/{id}")
+            }
+        })
+        .collect()
+}
+
 /// Form input for BXGY discounts.
 #[derive(Debug, Deserialize)]
 pub struct BxgyDiscountFormInput {
@@ -117,6 +188,14 @@ pub struct AddCodesInput {
     pub codes: String,
 }
 
+/// Form input for duplicating a discount under a new code.
+#[derive(Debug, Deserialize)]
+pub struct DuplicateDiscountInput {
+    pub code: String,
+    pub starts_at: String,
+    pub ends_at: Option<String>,
+}
+
 // =============================================================================
 // Column Visibility
 // =============================================================================
@@ -445,6 +524,8 @@ pub struct DiscountNewStep3Template {
     pub method: String,
     pub discount_type: String,
     pub error: Option<String>,
+    /// Segments offered in the customer eligibility picker.
+    pub segments: Vec<CustomerSegmentView>,
 }
 
 /// Discount edit form template.
@@ -457,6 +538,16 @@ pub struct DiscountEditTemplate {
     pub error: Option<String>,
 }
 
+/// Inline duplicate form row (HTMX fragment).
+#[derive(Template)]
+#[template(path = "discounts/_duplicate_row.html")]
+pub struct DiscountDuplicateRowTemplate {
+    pub discount_id: String,
+    pub title: String,
+    pub code: String,
+    pub error: Option<String>,
+}
+
 // Legacy templates for backward compatibility
 /// Discount create form template (legacy).
 #[derive(Template)]
@@ -871,18 +962,45 @@ pub async fn new_step2(
     }))
 }
 
+/// Fetch customer segments for the eligibility picker.
+///
+/// Returns an empty list if they can't be fetched, which only hides the
+/// segment options.
+async fn eligibility_segments(state: &AppState) -> Vec<CustomerSegmentView> {
+    state
+        .shopify()
+        .get_customer_segments(50, None, None)
+        .await
+        .inspect_err(|e| tracing::warn!(error = %e, "Failed to fetch customer segments"))
+        .map(|conn| {
+            conn.segments
+                .iter()
+                .map(CustomerSegmentView::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Step 3: Full discount form.
-#[instrument(skip(admin))]
+#[instrument(skip(admin, state))]
 pub async fn new_step3(
     RequireAdminAuth(admin): RequireAdminAuth,
+    State(state): State<AppState>,
     Path((method, discount_type)): Path<(String, String)>,
 ) -> Html<String> {
+    let segments = if discount_type == "basic" {
+        eligibility_segments(&state).await
+    } else {
+        Vec::new()
+    };
+
     let template = DiscountNewStep3Template {
         admin_user: AdminUserView::from(&admin),
         current_path: "/discounts".to_string(),
         method,
         discount_type,
         error: None,
+        segments,
     };
 
     Html(template.render().unwrap_or_else(|e| {
@@ -931,31 +1049,43 @@ pub async fn create_basic(
 
     let code = input.code.as_deref().unwrap_or("");
 
-    match state
-        .shopify()
-        .create_discount(DiscountCreateInput {
-            title: &input.title,
-            code,
-            percentage,
-            amount,
-            starts_at,
-            ends_at: input.ends_at.as_deref(),
-            usage_limit: input.usage_limit,
-        })
-        .await
-    {
+    let result = match (input.minimum_requirement(), input.customer_eligibility()) {
+        (Ok(minimum_requirement), Ok(customer_eligibility)) => state
+            .shopify()
+            .create_discount(DiscountCreateInput {
+                title: &input.title,
+                code,
+                percentage,
+                amount,
+                starts_at,
+                ends_at: input.ends_at.as_deref(),
+                usage_limit: input.usage_limit,
+                minimum_requirement,
+                items: DiscountItems::All,
+                once_per_customer: input.once_per_customer.unwrap_or(false),
+                customer_eligibility,
+            })
+            .await
+            .map_err(|e| {
+                tracing::error!(code = %code, error = %e, "Failed to create discount");
+                e.to_string()
+            }),
+        (Err(e), _) | (_, Err(e)) => Err(e),
+    };
+
+    match result {
         Ok(discount_id) => {
             tracing::info!(discount_id = %discount_id, code = %code, "Discount created");
             Redirect::to("/discounts").into_response()
         }
-        Err(e) => {
-            tracing::error!(code = %code, error = %e, "Failed to create discount");
+        Err(error) => {
             let template = DiscountNewStep3Template {
                 admin_user: AdminUserView::from(&admin),
                 current_path: "/discounts".to_string(),
+                segments: eligibility_segments(&state).await,
                 method: input.method,
                 discount_type: input.discount_type,
-                error: Some(e.to_string()),
+                error: Some(error),
             };
 
             Html(template.render().unwrap_or_else(|e| {
@@ -1029,11 +1159,11 @@ pub async fn update(
 
     let discount_id = normalize_discount_id(&id);
 
-    let update_input = DiscountUpdateInput {
-        title: Some(&input.title),
-        starts_at: input.starts_at.as_deref(),
-        ends_at: input.ends_at.as_deref(),
-    };
+    let update_input = DiscountUpdateInput::builder()
+        .title(input.title.as_str())
+        .starts_at(input.starts_at.as_deref())
+        .ends_at(input.ends_at.as_deref())
+        .build();
 
     match state
         .shopify()
@@ -1170,17 +1300,86 @@ pub async fn delete(
     }
 }
 
-/// Duplicate discount handler.
-#[instrument(skip(_admin, _state))]
+/// Duplicate discount form handler (HTMX).
+///
+/// Returns an inline form row asking only for the new code and validity dates.
+#[instrument(skip(_admin, state))]
+pub async fn duplicate_form(
+    RequireAdminAuth(_admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let discount_id = normalize_discount_id(&id);
+
+    let Some(discount) = find_discount_by_id(&state, &discount_id).await else {
+        return (StatusCode::NOT_FOUND, "Discount not found").into_response();
+    };
+
+    let template = DiscountDuplicateRowTemplate {
+        discount_id: extract_numeric_id(&discount_id).to_string(),
+        title: discount.title,
+        code: String::new(),
+        error: None,
+    };
+
+    Html(template.render().unwrap_or_else(|e| {
+        tracing::error!("Template render error: {}", e);
+        "Internal Server Error".to_string()
+    }))
+    .into_response()
+}
+
+/// Duplicate discount handler (HTMX).
+///
+/// Clones the source discount's rules under a new code and validity window.
+#[instrument(skip(_admin, state))]
 pub async fn duplicate(
     RequireAdminAuth(_admin): RequireAdminAuth,
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Path(id): Path<String>,
+    Form(input): Form<DuplicateDiscountInput>,
 ) -> impl IntoResponse {
-    // TODO: Implement duplicate functionality
-    // This requires fetching the discount details and creating a copy
-    tracing::warn!(discount_id = %id, "Duplicate discount not yet implemented");
-    Redirect::to("/discounts").into_response()
+    let discount_id = normalize_discount_id(&id);
+    let code = input.code.trim();
+    let ends_at = input.ends_at.as_deref().filter(|s| !s.is_empty());
+
+    match state
+        .shopify()
+        .clone_discount(&discount_id, code, &input.starts_at, ends_at)
+        .await
+    {
+        Ok(new_id) => {
+            tracing::info!(source_id = %discount_id, discount_id = %new_id, code = %code, "Discount duplicated");
+            (
+                StatusCode::OK,
+                [
+                    ("HX-Trigger", "discount-duplicated"),
+                    ("HX-Redirect", "/discounts"),
+                ],
+                Html("Duplicated"),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!(source_id = %discount_id, error = %e, "Failed to duplicate discount");
+            let title = find_discount_by_id(&state, &discount_id)
+                .await
+                .map(|d| d.title)
+                .unwrap_or_default();
+            let template = DiscountDuplicateRowTemplate {
+                discount_id: extract_numeric_id(&discount_id).to_string(),
+                title,
+                code: code.to_string(),
+                error: Some(e.to_string()),
+            };
+
+            Html(template.render().unwrap_or_else(|e| {
+                tracing::error!("Template render error: {}", e);
+                "Internal Server Error".to_string()
+            }))
+            .into_response()
+        }
+    }
 }
 
 // =============================================================================
@@ -1323,10 +1522,13 @@ pub async fn api_customer_segments(
     RequireAdminAuth(_admin): RequireAdminAuth,
     State(state): State<AppState>,
 ) -> Json<Vec<CustomerSegmentView>> {
-    match state.shopify().get_customer_segments(50).await {
-        Ok(segments) => {
-            let views: Vec<CustomerSegmentView> =
-                segments.iter().map(CustomerSegmentView::from).collect();
+    match state.shopify().get_customer_segments(50, None, None).await {
+        Ok(conn) => {
+            let views: Vec<CustomerSegmentView> = conn
+                .segments
+                .iter()
+                .map(CustomerSegmentView::from)
+                .collect();
             Json(views)
         }
         Err(e) => {
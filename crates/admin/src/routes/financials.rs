@@ -10,12 +10,13 @@ use axum::{
     routing::{get, post},
 };
 use chrono::NaiveDate;
+use naked_pineapple_core::ProductId;
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use tracing::instrument;
 
 use crate::{
-    db::{InventoryLotRepository, ManufacturingRepository, RepositoryError},
+    db::{InventoryLotRepository, ManufacturingRepository, ReadPool, RepositoryError},
     filters,
     middleware::auth::RequireAdminAuth,
     models::inventory_lot::{CreateLotInput, UpdateLotInput},
@@ -182,6 +183,16 @@ fn extract_short_id(gid: &str) -> String {
     gid.split('/').next_back().unwrap_or(gid).to_string()
 }
 
+/// Fetch a batch's product from Shopify, treating a malformed stored ID like
+/// a failed fetch.
+async fn fetch_product(
+    shopify: &crate::shopify::AdminClient,
+    product_id: &str,
+) -> Option<crate::shopify::types::AdminProduct> {
+    let id: ProductId = product_id.parse().ok()?;
+    shopify.get_product(&id).await.ok().flatten()
+}
+
 /// Fetch product info from Shopify and create `BatchView` with product details.
 async fn enrich_batch_view(
     shopify: &crate::shopify::AdminClient,
@@ -192,7 +203,7 @@ async fn enrich_batch_view(
     let mut variant_title = None;
 
     // Try to fetch product info from Shopify
-    if let Ok(Some(product)) = shopify.get_product(&batch.batch.shopify_product_id).await {
+    if let Some(product) = fetch_product(shopify, &batch.batch.shopify_product_id).await {
         product_title = Some(product.title.clone());
         product_image = product.images.first().map(|img| img.url.clone());
 
@@ -201,7 +212,7 @@ async fn enrich_batch_view(
             variant_title = product
                 .variants
                 .iter()
-                .find(|v| &v.id == variant_id)
+                .find(|v| v.id.as_str() == variant_id)
                 .map(|v| v.title.clone());
         }
     }
@@ -262,7 +273,7 @@ async fn fetch_locations(shopify: &crate::shopify::AdminClient) -> Vec<LocationV
             .into_iter()
             .filter(|l| l.is_active)
             .map(|l| LocationView {
-                id: l.id,
+                id: l.id.to_string(),
                 name: l.name,
                 is_active: l.is_active,
             })
@@ -289,16 +300,16 @@ fn products_to_search_results(
     products
         .into_iter()
         .map(|p| ProductSearchResult {
-            short_id: extract_short_id(&p.id),
-            id: p.id,
+            short_id: extract_short_id(p.id.as_str()),
+            id: p.id.to_string(),
             title: p.title,
             image_url: p.images.first().map(|img| img.url.clone()),
             variants: p
                 .variants
                 .into_iter()
                 .map(|v| VariantSearchResult {
-                    short_id: extract_short_id(&v.id),
-                    id: v.id,
+                    short_id: extract_short_id(v.id.as_str()),
+                    id: v.id.to_string(),
                     title: v.title,
                     sku: v.sku,
                     price: format!("${}", v.price.amount),
@@ -399,10 +410,11 @@ pub async fn index() -> Redirect {
 #[instrument(skip_all)]
 pub async fn manufacturing_index(
     State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
     RequireAdminAuth(user): RequireAdminAuth,
     Query(query): Query<BatchesQuery>,
 ) -> impl IntoResponse {
-    let repo = ManufacturingRepository::new(state.pool());
+    let repo = ManufacturingRepository::new(&pool);
 
     let page = query.page.unwrap_or(1).max(1);
     let limit = 25_i64;
@@ -439,8 +451,8 @@ pub async fn manufacturing_index(
         let lots_received = repo.get_lots_received(batch.id).await.unwrap_or(0);
 
         // Try to fetch product info from Shopify
-        let (product_title, product_image, variant_title) = if let Ok(Some(product)) =
-            state.shopify().get_product(&batch.shopify_product_id).await
+        let (product_title, product_image, variant_title) = if let Some(product) =
+            fetch_product(state.shopify(), &batch.shopify_product_id).await
         {
             let title = Some(product.title.clone());
             let image = product.images.first().map(|img| img.url.clone());
@@ -448,7 +460,7 @@ pub async fn manufacturing_index(
                 product
                     .variants
                     .iter()
-                    .find(|v| &v.id == vid)
+                    .find(|v| v.id.as_str() == vid)
                     .map(|v| v.title.clone())
             });
             (title, image, var_title)
@@ -564,11 +576,12 @@ pub async fn manufacturing_create(
 #[instrument(skip_all)]
 pub async fn manufacturing_show(
     State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
     RequireAdminAuth(user): RequireAdminAuth,
     Path(id): Path<i32>,
 ) -> impl IntoResponse {
-    let mfg_repo = ManufacturingRepository::new(state.pool());
-    let lot_repo = InventoryLotRepository::new(state.pool());
+    let mfg_repo = ManufacturingRepository::new(&pool);
+    let lot_repo = InventoryLotRepository::new(&pool);
 
     let batch_id = naked_pineapple_core::ManufacturingBatchId::new(id);
 
@@ -592,14 +605,14 @@ pub async fn manufacturing_show(
 
     // Fetch product info from Shopify
     let (product_title, product_image, variant_title) =
-        if let Ok(Some(product)) = state.shopify().get_product(&batch.shopify_product_id).await {
+        if let Some(product) = fetch_product(state.shopify(), &batch.shopify_product_id).await {
             let title = Some(product.title.clone());
             let image = product.images.first().map(|img| img.url.clone());
             let var_title = batch.shopify_variant_id.as_ref().and_then(|vid| {
                 product
                     .variants
                     .iter()
-                    .find(|v| &v.id == vid)
+                    .find(|v| v.id.as_str() == vid)
                     .map(|v| v.title.clone())
             });
             (title, image, var_title)
@@ -677,10 +690,11 @@ pub async fn manufacturing_show(
 #[instrument(skip_all)]
 pub async fn manufacturing_edit(
     State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
     RequireAdminAuth(user): RequireAdminAuth,
     Path(id): Path<i32>,
 ) -> impl IntoResponse {
-    let repo = ManufacturingRepository::new(state.pool());
+    let repo = ManufacturingRepository::new(&pool);
     let batch_id = naked_pineapple_core::ManufacturingBatchId::new(id);
 
     let batch = match repo.get_batch(batch_id).await {
@@ -696,14 +710,14 @@ pub async fn manufacturing_edit(
 
     // Fetch product info from Shopify
     let (product_title, product_image, variant_title) =
-        if let Ok(Some(product)) = state.shopify().get_product(&batch.shopify_product_id).await {
+        if let Some(product) = fetch_product(state.shopify(), &batch.shopify_product_id).await {
             let title = Some(product.title.clone());
             let image = product.images.first().map(|img| img.url.clone());
             let var_title = batch.shopify_variant_id.as_ref().and_then(|vid| {
                 product
                     .variants
                     .iter()
-                    .find(|v| &v.id == vid)
+                    .find(|v| v.id.as_str() == vid)
                     .map(|v| v.title.clone())
             });
             (title, image, var_title)
@@ -805,10 +819,11 @@ pub async fn manufacturing_delete(
 #[instrument(skip_all)]
 pub async fn lot_new(
     State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
     RequireAdminAuth(user): RequireAdminAuth,
     Path(batch_id): Path<i32>,
 ) -> impl IntoResponse {
-    let repo = ManufacturingRepository::new(state.pool());
+    let repo = ManufacturingRepository::new(&pool);
     let id = naked_pineapple_core::ManufacturingBatchId::new(batch_id);
 
     let batch = match repo.get_batch(id).await {
@@ -824,14 +839,14 @@ pub async fn lot_new(
 
     // Fetch product info from Shopify
     let (product_title, product_image, variant_title) =
-        if let Ok(Some(product)) = state.shopify().get_product(&batch.shopify_product_id).await {
+        if let Some(product) = fetch_product(state.shopify(), &batch.shopify_product_id).await {
             let title = Some(product.title.clone());
             let image = product.images.first().map(|img| img.url.clone());
             let var_title = batch.shopify_variant_id.as_ref().and_then(|vid| {
                 product
                     .variants
                     .iter()
-                    .find(|v| &v.id == vid)
+                    .find(|v| v.id.as_str() == vid)
                     .map(|v| v.title.clone())
             });
             (title, image, var_title)
@@ -911,11 +926,12 @@ pub async fn lot_create(
 #[instrument(skip_all)]
 pub async fn lot_edit(
     State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
     RequireAdminAuth(user): RequireAdminAuth,
     Path((batch_id, lot_id)): Path<(i32, i32)>,
 ) -> impl IntoResponse {
-    let mfg_repo = ManufacturingRepository::new(state.pool());
-    let lot_repo = InventoryLotRepository::new(state.pool());
+    let mfg_repo = ManufacturingRepository::new(&pool);
+    let lot_repo = InventoryLotRepository::new(&pool);
 
     let b_id = naked_pineapple_core::ManufacturingBatchId::new(batch_id);
     let l_id = naked_pineapple_core::InventoryLotId::new(lot_id);
@@ -944,14 +960,14 @@ pub async fn lot_edit(
 
     // Fetch product info from Shopify
     let (product_title, product_image, variant_title) =
-        if let Ok(Some(product)) = state.shopify().get_product(&batch.shopify_product_id).await {
+        if let Some(product) = fetch_product(state.shopify(), &batch.shopify_product_id).await {
             let title = Some(product.title.clone());
             let image = product.images.first().map(|img| img.url.clone());
             let var_title = batch.shopify_variant_id.as_ref().and_then(|vid| {
                 product
                     .variants
                     .iter()
-                    .find(|v| &v.id == vid)
+                    .find(|v| v.id.as_str() == vid)
                     .map(|v| v.title.clone())
             });
             (title, image, var_title)
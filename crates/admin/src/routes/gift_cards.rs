@@ -7,6 +7,7 @@ use axum::{
     http::StatusCode,
     response::{Html, IntoResponse},
 };
+use naked_pineapple_core::{CustomerId, GidError};
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
@@ -253,10 +254,10 @@ impl From<&GiftCard> for GiftCardView {
             balance_empty: balance_amount == 0.0,
             balance_partial: balance_amount > 0.0 && balance_amount < initial_amount,
             created_at: gc.created_at.clone(),
-            customer_id: gc.customer_id.clone(),
+            customer_id: gc.customer_id.as_ref().map(ToString::to_string),
             customer_name: gc.customer_name.clone(),
             customer_email: gc.customer_email.clone(),
-            order_id: gc.order_id.clone(),
+            order_id: gc.order_id.as_ref().map(ToString::to_string),
             order_name: gc.order_name.clone(),
             note: gc.note.clone(),
         }
@@ -392,12 +393,9 @@ impl From<&GiftCardDetail> for GiftCardDetailView {
         let customer_short_id = gc
             .customer_id
             .as_ref()
-            .and_then(|id| id.split('/').next_back().map(String::from));
+            .map(|id| id.numeric_id().to_string());
 
-        let order_short_id = gc
-            .order_id
-            .as_ref()
-            .and_then(|id| id.split('/').next_back().map(String::from));
+        let order_short_id = gc.order_id.as_ref().map(|id| id.numeric_id().to_string());
 
         // Check expiration status
         let is_expired = gc.expires_on.as_ref().is_some_and(|exp| {
@@ -467,18 +465,18 @@ impl From<&GiftCardDetail> for GiftCardDetailView {
             updated_at: gc.updated_at.clone(),
             note: gc.note.clone(),
             template_suffix: gc.template_suffix.clone(),
-            customer_id: gc.customer_id.clone(),
+            customer_id: gc.customer_id.as_ref().map(ToString::to_string),
             customer_short_id,
             customer_name: gc.customer_name.clone(),
             customer_email: gc.customer_email.clone(),
             customer_phone: gc.customer_phone.clone(),
-            recipient_id,
+            recipient_id: recipient_id.map(String::from),
             recipient_name,
             recipient_email,
             recipient_preferred_name,
             recipient_message,
             recipient_send_notification_at,
-            order_id: gc.order_id.clone(),
+            order_id: gc.order_id.as_ref().map(ToString::to_string),
             order_short_id,
             order_name: gc.order_name.clone(),
             order_created_at: gc.order_created_at.clone(),
@@ -530,6 +528,15 @@ pub struct GiftCardUpdateInput {
     pub customer_id: Option<String>,
 }
 
+/// Parse an optional customer ID form field (full GID or numeric ID).
+/// A blank field means no customer.
+fn parse_customer_field(id: Option<&str>) -> Result<Option<CustomerId>, GidError> {
+    id.map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(CustomerId::parse_admin_id)
+        .transpose()
+}
+
 /// Gift cards list page handler.
 #[instrument(skip(admin, state))]
 pub async fn index(
@@ -621,18 +628,24 @@ pub async fn create(
     State(state): State<AppState>,
     Form(input): Form<GiftCardFormInput>,
 ) -> impl IntoResponse {
-    match state
-        .shopify()
-        .create_gift_card(
-            &input.initial_value,
-            input.customer_id.as_deref(),
-            input.expires_on.as_deref(),
-            input.note.as_deref(),
-            input.recipient_id.as_deref(),
-            input.recipient_message.as_deref(),
-        )
-        .await
-    {
+    let result = async {
+        let customer_id = parse_customer_field(input.customer_id.as_deref())?;
+        let recipient_id = parse_customer_field(input.recipient_id.as_deref())?;
+        state
+            .shopify()
+            .create_gift_card(
+                &input.initial_value,
+                customer_id.as_ref(),
+                input.expires_on.as_deref(),
+                input.note.as_deref(),
+                recipient_id.as_ref(),
+                input.recipient_message.as_deref(),
+            )
+            .await
+    }
+    .await;
+
+    match result {
         Ok((gift_card_id, code)) => {
             tracing::info!(gift_card_id = %gift_card_id, initial_value = %input.initial_value, "Gift card created");
             let template = GiftCardNewTemplate {
@@ -1009,15 +1022,19 @@ pub async fn update(
     };
 
     // Update the gift card
-    let result = state
-        .shopify()
-        .update_gift_card(
-            &gift_card_id,
-            input.note.as_deref(),
-            input.expires_on.as_deref(),
-            input.customer_id.as_deref(),
-        )
-        .await;
+    let result = async {
+        let customer_id = parse_customer_field(input.customer_id.as_deref())?;
+        state
+            .shopify()
+            .update_gift_card(
+                &gift_card_id,
+                input.note.as_deref(),
+                input.expires_on.as_deref(),
+                customer_id.as_ref(),
+            )
+            .await
+    }
+    .await;
 
     // Fetch the updated gift card
     let gift_card = match state.shopify().get_gift_card_detail(&gift_card_id).await {
@@ -13,17 +13,21 @@ use tracing::instrument;
 
 use crate::{
     components::data_table::{DataTableConfig, FilterType, inventory_table_config},
+    db::{ReadPool, ReconciliationRepository},
     filters,
     middleware::auth::RequireAdminAuth,
+    services::FlowService,
     shopify::types::{
-        InventoryItem, InventoryItemConnection, InventoryItemUpdateInput, Location, ProductStatus,
+        FlowTrigger, InventoryItem, InventoryItemConnection, InventoryItemUpdateInput, Location,
+        ProductStatus,
     },
     state::AppState,
 };
 
-use naked_pineapple_core::AdminRole;
+use naked_pineapple_core::{AdminRole, GidError, InventoryItemId, LocationId};
 
 use super::dashboard::AdminUserView;
+use super::reconciliation::ReconciliationSessionView;
 
 /// Low stock threshold (items below this are highlighted).
 const LOW_STOCK_THRESHOLD: i64 = 10;
@@ -52,8 +56,8 @@ pub struct InventoryQuery {
 /// Form input for inventory adjustment.
 #[derive(Debug, Deserialize)]
 pub struct InventoryAdjustForm {
-    pub inventory_item_id: String,
-    pub location_id: String,
+    pub inventory_item_id: InventoryItemId,
+    pub location_id: LocationId,
     pub delta: i64,
     pub reason: Option<String>,
 }
@@ -61,8 +65,8 @@ pub struct InventoryAdjustForm {
 /// Form input for inventory set.
 #[derive(Debug, Deserialize)]
 pub struct InventorySetForm {
-    pub inventory_item_id: String,
-    pub location_id: String,
+    pub inventory_item_id: InventoryItemId,
+    pub location_id: LocationId,
     pub quantity: i64,
     pub reason: Option<String>,
 }
@@ -70,9 +74,9 @@ pub struct InventorySetForm {
 /// Form input for inventory move between locations.
 #[derive(Debug, Deserialize)]
 pub struct InventoryMoveForm {
-    pub inventory_item_id: String,
-    pub from_location_id: String,
-    pub to_location_id: String,
+    pub inventory_item_id: InventoryItemId,
+    pub from_location_id: LocationId,
+    pub to_location_id: LocationId,
     pub quantity: i64,
     pub reason: Option<String>,
 }
@@ -80,8 +84,8 @@ pub struct InventoryMoveForm {
 /// Form input for activating inventory at a location.
 #[derive(Debug, Deserialize)]
 pub struct InventoryActivateForm {
-    pub inventory_item_id: String,
-    pub location_id: String,
+    pub inventory_item_id: InventoryItemId,
+    pub location_id: LocationId,
 }
 
 /// Form input for deactivating inventory at a location.
@@ -106,7 +110,7 @@ pub struct LocationView {
 impl From<&Location> for LocationView {
     fn from(loc: &Location) -> Self {
         Self {
-            id: loc.id.clone(),
+            id: loc.id.to_string(),
             name: loc.name.clone(),
             is_active: loc.is_active,
         }
@@ -194,7 +198,7 @@ fn extract_product_info(
     };
 
     (
-        Some(p.id.clone()),
+        Some(p.id.to_string()),
         p.title.clone(),
         Some(p.handle.clone()),
         p.featured_image.as_ref().map(|img| img.url.clone()),
@@ -203,23 +207,13 @@ fn extract_product_info(
     )
 }
 
-/// Ensure ID has the proper Shopify GID format for inventory items.
-fn normalize_inventory_item_id(id: &str) -> String {
-    if id.starts_with("gid://") {
-        id.to_string()
-    } else {
-        format!("gid://shopify/InventoryItem/{id}")
-    }
-}
-
 impl From<&InventoryItem> for InventoryItemView {
     fn from(item: &InventoryItem) -> Self {
         // Sum quantities across all locations
         let on_hand: i64 = item.inventory_levels.iter().map(|l| l.on_hand).sum();
         let available: i64 = item.inventory_levels.iter().map(|l| l.available).sum();
+        let committed: i64 = item.inventory_levels.iter().map(|l| l.committed).sum();
         let incoming: i64 = item.inventory_levels.iter().map(|l| l.incoming).sum();
-        // Committed is on_hand - available
-        let committed = on_hand.saturating_sub(available);
 
         // Extract product/variant info
         let (product_id, product_title, product_handle, product_image_url, status, status_class) =
@@ -232,13 +226,13 @@ impl From<&InventoryItem> for InventoryItemView {
             .map_or_else(|| "Default Title".to_string(), |v| v.title.clone());
 
         Self {
-            id: item.id.clone(),
-            inventory_item_id: item.id.clone(),
+            id: item.id.to_string(),
+            inventory_item_id: item.id.to_string(),
             product_id,
             product_title,
             product_handle,
             product_image_url,
-            variant_id,
+            variant_id: variant_id.map(String::from),
             variant_title,
             sku: item.sku.clone(),
             tracked: item.tracked,
@@ -343,6 +337,8 @@ pub struct InventoryIndexTemplate {
     pub low_stock_only: bool,
     // Preserve URL params for links
     pub preserve_params: String,
+    // Inventory counts still in progress
+    pub reconciliations: Vec<ReconciliationSessionView>,
 }
 
 /// Inventory row partial template (for HTMX updates).
@@ -420,23 +416,16 @@ impl From<&InventoryItem> for InventoryItemDetailView {
         let inventory_levels: Vec<InventoryLevelView> = item
             .inventory_levels
             .iter()
-            .map(|level| {
-                let available = level.available;
-                let on_hand = level.on_hand;
-                let incoming = level.incoming;
-                let committed = on_hand.saturating_sub(available);
-
-                InventoryLevelView {
-                    location_id: level.location_id.clone(),
-                    location_name: level.location_name.clone().unwrap_or_default(),
-                    available,
-                    on_hand,
-                    committed,
-                    incoming,
-                    reserved: 0,
-                    damaged: 0,
-                    updated_at: level.updated_at.clone(),
-                }
+            .map(|level| InventoryLevelView {
+                location_id: level.location_id.to_string(),
+                location_name: level.location_name.clone().unwrap_or_default(),
+                available: level.available,
+                on_hand: level.on_hand,
+                committed: level.committed,
+                incoming: level.incoming,
+                reserved: level.reserved,
+                damaged: 0,
+                updated_at: level.updated_at.clone(),
             })
             .collect();
 
@@ -444,12 +433,12 @@ impl From<&InventoryItem> for InventoryItemDetailView {
         let total_available: i64 = inventory_levels.iter().map(|l| l.available).sum();
 
         Self {
-            id: item.id.clone(),
+            id: item.id.to_string(),
             product_id,
             product_title,
             product_handle,
             product_image_url,
-            variant_id,
+            variant_id: variant_id.map(String::from),
             variant_title,
             sku: item.sku.clone(),
             tracked: item.tracked,
@@ -612,10 +601,11 @@ fn build_preserve_params(query: &InventoryQuery) -> String {
 // =============================================================================
 
 /// GET /inventory - Inventory list page.
-#[instrument(skip(admin, state))]
+#[instrument(skip(admin, state, pool))]
 pub async fn index(
     RequireAdminAuth(admin): RequireAdminAuth,
     State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
     Query(query): Query<InventoryQuery>,
 ) -> Html<String> {
     // Fetch locations and inventory items in parallel
@@ -625,7 +615,12 @@ pub async fn index(
         .shopify()
         .get_inventory_items(50, query.cursor.clone(), shopify_query);
 
-    let (locations_result, items_result) = tokio::join!(locations_future, items_future);
+    let reconciliation_repo = ReconciliationRepository::new(&pool);
+    let (locations_result, items_result, reconciliations_result) = tokio::join!(
+        locations_future,
+        items_future,
+        reconciliation_repo.list_in_progress()
+    );
 
     // Process locations
     let locations: Vec<LocationView> = match locations_result {
@@ -641,6 +636,21 @@ pub async fn index(
         }
     };
 
+    let location_names: Vec<(String, String)> = locations
+        .iter()
+        .map(|l| (l.id.clone(), l.name.clone()))
+        .collect();
+    let reconciliations = match reconciliations_result {
+        Ok(sessions) => sessions
+            .iter()
+            .map(|s| ReconciliationSessionView::new(s, &location_names))
+            .collect(),
+        Err(e) => {
+            tracing::error!("Failed to fetch inventory counts: {e}");
+            vec![]
+        }
+    };
+
     // Use first location as default if none selected
     let selected_location_id = query
         .location_id
@@ -705,6 +715,7 @@ pub async fn index(
         out_of_stock_count,
         low_stock_only,
         preserve_params,
+        reconciliations,
     };
 
     Html(template.render().unwrap_or_else(|e| {
@@ -798,6 +809,9 @@ pub async fn set(
                 quantity = %form.quantity,
                 "Inventory set"
             );
+            if form.quantity <= LOW_STOCK_THRESHOLD {
+                send_low_inventory_alert(&state, &form.inventory_item_id, form.quantity).await;
+            }
             (
                 StatusCode::OK,
                 [("HX-Trigger", "inventory-updated")],
@@ -826,6 +840,34 @@ pub async fn set(
     }
 }
 
+/// Send a low inventory Flow trigger for an inventory item with a SKU.
+///
+/// Skips loading the SKU when no low inventory trigger handle is configured.
+async fn send_low_inventory_alert(
+    state: &AppState,
+    inventory_item_id: &InventoryItemId,
+    quantity: i64,
+) {
+    if state.config().flow.low_inventory.is_none() {
+        return;
+    }
+
+    let sku = match state.shopify().get_inventory_item(inventory_item_id).await {
+        Ok(item) => item.sku,
+        Err(e) => {
+            tracing::warn!(inventory_item_id = %inventory_item_id, error = %e, "Failed to load SKU for Flow trigger");
+            return;
+        }
+    };
+    let Some(sku) = sku.filter(|sku| !sku.is_empty()) else {
+        return;
+    };
+
+    FlowService::new(state.shopify(), &state.config().flow)
+        .send(&FlowTrigger::LowInventoryAlert { sku, quantity })
+        .await;
+}
+
 /// GET /inventory/:id - Inventory item detail page.
 #[instrument(skip(admin, state))]
 pub async fn show(
@@ -833,7 +875,13 @@ pub async fn show(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let inventory_item_id = normalize_inventory_item_id(&id);
+    let Ok(inventory_item_id) = InventoryItemId::parse_admin_id(&id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Html("Inventory item not found".to_string()),
+        )
+            .into_response();
+    };
 
     // Fetch inventory item and locations in parallel
     let item_future = state.shopify().get_inventory_item(&inventory_item_id);
@@ -887,7 +935,13 @@ pub async fn edit(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let inventory_item_id = normalize_inventory_item_id(&id);
+    let Ok(inventory_item_id) = InventoryItemId::parse_admin_id(&id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Html("Inventory item not found".to_string()),
+        )
+            .into_response();
+    };
 
     let item = match state.shopify().get_inventory_item(&inventory_item_id).await {
         Ok(item) => InventoryItemDetailView::from(&item),
@@ -922,7 +976,13 @@ pub async fn update(
     Path(id): Path<String>,
     Form(form): Form<InventoryUpdateForm>,
 ) -> impl IntoResponse {
-    let inventory_item_id = normalize_inventory_item_id(&id);
+    let Ok(inventory_item_id) = InventoryItemId::parse_admin_id(&id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Html("Inventory item not found".to_string()),
+        )
+            .into_response();
+    };
 
     let input = InventoryItemUpdateInput {
         sku: None, // SKU updates require variant mutation
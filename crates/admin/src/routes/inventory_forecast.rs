@@ -0,0 +1,164 @@
+//! Inventory stockout forecast route handlers.
+//!
+//! # Routes
+//!
+//! ```text
+//! GET /inventory/forecast - SKUs predicted to sell out within 30 days
+//! ```
+
+use askama::Template;
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+    routing::get,
+};
+use naked_pineapple_core::LocationId;
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::{
+    filters,
+    middleware::auth::RequireAdminAuth,
+    services::{InventoryForecastService, StockoutForecast, inventory_forecast},
+    state::AppState,
+};
+
+use super::dashboard::AdminUserView;
+use super::inventory::LocationView;
+
+/// Days ahead the forecast page looks for stockouts.
+const FORECAST_DAYS: u32 = 30;
+
+/// Query parameters for the forecast page.
+#[derive(Debug, Deserialize)]
+pub struct ForecastQuery {
+    pub location_id: Option<LocationId>,
+}
+
+/// Forecast row for the template.
+#[derive(Debug, Clone)]
+pub struct StockoutForecastView {
+    pub sku: String,
+    pub product_title: String,
+    pub current_available: i64,
+    pub daily_velocity: String,
+    pub days_until_stockout: String,
+    pub suggested_reorder_quantity: i64,
+    pub urgency_class: String,
+}
+
+impl From<&StockoutForecast> for StockoutForecastView {
+    fn from(forecast: &StockoutForecast) -> Self {
+        let days = forecast.days_until_stockout.unwrap_or(f64::INFINITY);
+        let urgency_class = if days < 7.0 {
+            "bg-red-500/10 text-red-600 dark:text-red-400 ring-1 ring-inset ring-red-500/20"
+        } else if days < 14.0 {
+            "bg-yellow-500/10 text-yellow-600 dark:text-yellow-400 ring-1 ring-inset ring-yellow-500/20"
+        } else {
+            "bg-zinc-500/10 text-zinc-600 dark:text-zinc-400 ring-1 ring-inset ring-zinc-500/20"
+        };
+
+        Self {
+            sku: forecast.sku.clone(),
+            product_title: forecast.product_title.clone(),
+            current_available: forecast.current_available,
+            daily_velocity: format!("{:.1}", forecast.daily_velocity),
+            days_until_stockout: if days < 1.0 {
+                "Today".to_string()
+            } else {
+                format!("{days:.0} days")
+            },
+            suggested_reorder_quantity: forecast.suggested_reorder_quantity,
+            urgency_class: urgency_class.to_string(),
+        }
+    }
+}
+
+/// Stockout forecast page template.
+#[derive(Template)]
+#[template(path = "inventory/forecast.html")]
+pub struct ForecastTemplate {
+    pub admin_user: AdminUserView,
+    pub current_path: String,
+    pub locations: Vec<LocationView>,
+    pub selected_location_id: Option<String>,
+    pub forecasts: Vec<StockoutForecastView>,
+    pub forecast_days: u32,
+    pub velocity_window_days: u32,
+    pub lead_time_days: u32,
+    pub error: Option<String>,
+}
+
+/// GET /inventory/forecast - SKUs predicted to sell out within 30 days.
+#[instrument(skip(admin, state))]
+pub async fn forecast(
+    RequireAdminAuth(admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Query(query): Query<ForecastQuery>,
+) -> Response {
+    let locations: Vec<LocationView> = match state.shopify().get_locations().await {
+        Ok(conn) => conn
+            .locations
+            .iter()
+            .filter(|l| l.is_active)
+            .map(LocationView::from)
+            .collect(),
+        Err(e) => {
+            tracing::error!("Failed to fetch locations: {e}");
+            vec![]
+        }
+    };
+
+    // Use first location as default if none selected
+    let selected_location_id = query
+        .location_id
+        .or_else(|| locations.first().and_then(|l| l.id.parse().ok()));
+
+    let lead_time_days = state.config().inventory_lead_time_days;
+    let (forecasts, error, status) = match &selected_location_id {
+        Some(location_id) => match InventoryForecastService::new(state.shopify(), lead_time_days)
+            .forecast_stockouts(location_id, FORECAST_DAYS)
+            .await
+        {
+            Ok(forecasts) => (
+                forecasts.iter().map(StockoutForecastView::from).collect(),
+                None,
+                StatusCode::OK,
+            ),
+            Err(e) => {
+                tracing::error!(location_id = %location_id, error = %e, "Failed to forecast stockouts");
+                (
+                    Vec::new(),
+                    Some(format!("Failed to forecast stockouts: {e}")),
+                    StatusCode::BAD_GATEWAY,
+                )
+            }
+        },
+        None => (Vec::new(), None, StatusCode::OK),
+    };
+
+    let template = ForecastTemplate {
+        admin_user: AdminUserView::from(&admin),
+        current_path: "/inventory".to_string(),
+        locations,
+        selected_location_id: selected_location_id.map(String::from),
+        forecasts,
+        forecast_days: FORECAST_DAYS,
+        velocity_window_days: inventory_forecast::VELOCITY_WINDOW_DAYS,
+        lead_time_days,
+        error,
+    };
+
+    let page = Html(template.render().unwrap_or_else(|e| {
+        tracing::error!("Template render error: {}", e);
+        "Internal Server Error".to_string()
+    }));
+    (status, page).into_response()
+}
+
+/// Build the inventory forecast router.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/inventory/forecast", get(forecast))
+}
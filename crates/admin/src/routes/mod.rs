@@ -4,6 +4,7 @@
 //!
 //! ```text
 //! GET  /                        - Dashboard (auth required)
+//! GET  /dashboard/refresh       - Dashboard metric cards, uncached (HTMX fragment)
 //! GET  /health                 - Health check
 //!
 //! # Authentication
@@ -31,18 +32,39 @@
 //!
 //! # Products (auth required)
 //! GET  /products               - Products list
+//! POST /products/:id/collections        - Add product to a manual collection
+//! POST /products/:id/collections/remove - Remove product from a manual collection
+//! POST /products/:id/publications       - Publish/unpublish product on a sales channel
+//! PUT  /products/:id/variants/reorder    - Set variant display order (JSON)
+//! GET  /products/:id/variants/:variant_id/image        - Variant image picker (HTMX)
+//! POST /products/:id/variants/:variant_id/image        - Assign an image to a variant
+//! POST /products/:id/variants/:variant_id/image/detach - Detach a variant's image
 //!
 //! # Orders (auth required)
 //! GET  /orders                 - Orders list
+//! GET  /orders/summary         - Totals for the filtered orders list (HTMX)
+//! GET  /orders/export          - Orders CSV export (`fields[]` selects columns)
 //!
 //! # Customers (auth required)
 //! GET  /customers              - Customers list
+//! GET  /customers/:id/addresses/new            - Add address modal (HTMX)
+//! GET  /customers/:id/addresses/:address_id/edit - Edit address modal (HTMX)
+//! GET  /segments/:id/members   - Customers in a segment (paginated)
+//!
+//! # Address forms (auth required)
+//! GET  /api/provinces?country=XX - Province select for a country (HTMX)
 //!
 //! # Chat (Claude AI) - auth required
 //! GET  /chat/sessions          - List chat sessions
 //! POST /chat/sessions          - Create new chat session
 //! GET  /chat/sessions/:id      - Get chat session with messages
 //! POST /chat/sessions/:id/messages - Send message (returns response)
+//! GET  /chat/sessions/:id/export      - Download conversation as Markdown
+//! GET  /chat/sessions/:id/export.json - Download conversation as JSON
+//!
+//! # Webhooks (signature verified)
+//! POST /webhooks/shiphero/fulfillment - ShipHero shipment update
+//! POST /webhooks/shopify              - Shopify webhook (merged in `main`)
 //! ```
 
 pub mod admin_users;
@@ -57,22 +79,28 @@ pub mod discounts;
 pub mod financials;
 pub mod gift_cards;
 pub mod inventory;
+pub mod inventory_forecast;
 pub mod newsletter;
 pub mod orders;
 pub mod payouts;
 pub mod products;
+pub mod reconciliation;
+pub mod segments;
 pub mod settings;
 pub mod setup;
 pub mod shiphero_settings;
 pub mod shopify;
 pub mod slack;
 pub mod warehouse;
+pub mod webhooks;
 
 use axum::{
     Router,
+    extract::DefaultBodyLimit,
     routing::{get, post},
 };
 
+use crate::middleware::DeduplicationLayer;
 use crate::state::AppState;
 
 /// Build product routes.
@@ -84,10 +112,38 @@ fn product_routes() -> Router<AppState> {
         .route("/products/{id}/edit", get(products::edit))
         .route("/products/{id}/archive", post(products::archive))
         .route("/products/{id}/delete", post(products::delete))
+        .route(
+            "/products/{id}/variants/reorder",
+            axum::routing::put(products::reorder_variants),
+        )
         .route(
             "/products/{id}/variants/{variant_id}",
             post(products::update_variant),
         )
+        .route(
+            "/products/{id}/variants/{variant_id}/cost",
+            post(products::update_variant_cost),
+        )
+        .route(
+            "/products/{id}/variants/{variant_id}/image",
+            get(products::variant_image_picker).post(products::assign_variant_image),
+        )
+        .route(
+            "/products/{id}/variants/{variant_id}/image/detach",
+            post(products::detach_variant_image),
+        )
+        .route(
+            "/products/{id}/collections",
+            post(products::add_to_collection),
+        )
+        .route(
+            "/products/{id}/collections/remove",
+            post(products::remove_from_collection),
+        )
+        .route(
+            "/products/{id}/publications",
+            post(products::update_publication),
+        )
         .route("/products/{id}/images", post(products::upload_image))
         .route(
             "/products/{id}/images/{media_id}",
@@ -126,6 +182,11 @@ fn customer_routes() -> Router<AppState> {
             post(customers::activation_url),
         )
         .route("/customers/{id}/addresses", post(customers::address_create))
+        .route("/customers/{id}/addresses/new", get(customers::address_new))
+        .route(
+            "/customers/{id}/addresses/{address_id}/edit",
+            get(customers::address_edit),
+        )
         .route(
             "/customers/{id}/addresses/{address_id}",
             post(customers::address_update).delete(customers::address_delete),
@@ -169,7 +230,13 @@ fn collection_routes() -> Router<AppState> {
             "/collections/{id}/sort-order",
             post(collections::update_sort_order),
         )
-        .route("/collections/{id}/image", post(collections::upload_image))
+        .route(
+            "/collections/{id}/image",
+            // Room for the multipart framing around a maximum-size image
+            post(collections::upload_image).layer(DefaultBodyLimit::max(
+                collections::MAX_IMAGE_UPLOAD_BYTES + 1024 * 1024,
+            )),
+        )
         .route(
             "/collections/{id}/image/delete",
             post(collections::delete_image),
@@ -180,6 +247,12 @@ fn collection_routes() -> Router<AppState> {
 fn order_routes() -> Router<AppState> {
     Router::new()
         .route("/orders", get(orders::index))
+        .route("/orders/summary", get(orders::summary))
+        .route("/orders/export", get(orders::export))
+        .route(
+            "/orders/export/preferences",
+            post(orders::save_export_preferences),
+        )
         .route("/orders/{id}", get(orders::show))
         .route("/orders/{id}/note", post(orders::update_note))
         .route("/orders/{id}/mark-paid", post(orders::mark_paid))
@@ -200,6 +273,11 @@ fn order_routes() -> Router<AppState> {
         )
         .route("/orders/{id}/refund", post(orders::refund))
         .route("/orders/{id}/return", post(orders::create_return))
+        .route("/orders/{id}/exchange", post(orders::create_exchange))
+        .route(
+            "/orders/{id}/exchange/search-variants",
+            get(orders::exchange_search_variants),
+        )
         .route("/orders/{id}/capture", post(orders::capture))
         .route("/orders/{id}/archive", post(orders::archive))
         .route("/orders/{id}/print", get(orders::print))
@@ -279,7 +357,10 @@ fn discount_routes() -> Router<AppState> {
         .route("/discounts/{id}/activate", post(discounts::activate))
         .route("/discounts/{id}/deactivate", post(discounts::deactivate))
         .route("/discounts/{id}/delete", post(discounts::delete))
-        .route("/discounts/{id}/duplicate", post(discounts::duplicate))
+        .route(
+            "/discounts/{id}/duplicate",
+            get(discounts::duplicate_form).post(discounts::duplicate),
+        )
         .route("/discounts/bulk/activate", post(discounts::bulk_activate))
         .route(
             "/discounts/bulk/deactivate",
@@ -389,18 +470,27 @@ fn admin_user_routes() -> Router<AppState> {
 }
 
 /// Build the complete router for the admin application.
+///
+/// Commerce routes that change Shopify data share a [`DeduplicationLayer`],
+/// so a retried submission with the same idempotency key runs only once.
 pub fn routes() -> Router<AppState> {
+    let deduplication = DeduplicationLayer::new();
+
     Router::new()
         .route("/", get(dashboard::dashboard))
-        .merge(product_routes())
-        .merge(order_routes())
-        .merge(customer_routes())
-        .merge(collection_routes())
-        .merge(discount_routes())
-        .merge(inventory_routes())
-        .merge(gift_card_routes())
+        .route("/dashboard/refresh", get(dashboard::refresh_metrics))
+        .merge(product_routes().layer(deduplication.clone()))
+        .merge(order_routes().layer(deduplication.clone()))
+        .merge(customer_routes().layer(deduplication.clone()))
+        .merge(collection_routes().layer(deduplication.clone()))
+        .merge(discount_routes().layer(deduplication.clone()))
+        .merge(inventory_routes().layer(deduplication.clone()))
+        .merge(reconciliation::router())
+        .merge(inventory_forecast::router())
+        .merge(segments::router())
+        .merge(gift_card_routes().layer(deduplication.clone()))
         .merge(analytics_routes())
-        .merge(payout_routes())
+        .merge(payout_routes().layer(deduplication))
         .merge(admin_user_routes())
         // Financials (manufacturing, lots)
         .merge(financials::router())
@@ -415,6 +505,8 @@ pub fn routes() -> Router<AppState> {
         .merge(shopify::router())
         // Slack webhooks
         .merge(slack::router())
+        // ShipHero webhooks
+        .merge(webhooks::router())
         // Settings
         .merge(settings::router())
         // ShipHero settings (super_admin only)
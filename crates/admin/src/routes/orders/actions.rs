@@ -6,18 +6,24 @@ use axum::{
     http::StatusCode,
     response::{Html, IntoResponse, Redirect},
 };
+use naked_pineapple_core::{OrderId, VariantId};
+use rust_decimal::Decimal;
 use serde::Deserialize;
 use tracing::instrument;
 
 use crate::{
     middleware::auth::RequireAdminAuth,
+    services::FlowService,
     shopify::types::{
-        FulfillmentHoldInput, FulfillmentHoldReason, RefundCreateInput, RefundLineItemInput,
-        RefundRestockType, ReturnCreateInput, ReturnLineItemCreateInput,
+        ExchangeLineItemInput, ExchangeOrderInput, FlowTrigger, FulfillmentHoldInput,
+        FulfillmentHoldReason, RefundCreateInput, RefundLineItemInput, RefundRestockType,
+        ReturnCreateInput, ReturnLineItemCreateInput,
     },
     state::AppState,
 };
 
+use super::edit::ProductSearchQuery;
+
 // =============================================================================
 // Input Types
 // =============================================================================
@@ -77,6 +83,23 @@ pub struct ReturnInput {
     pub reason_note: Option<String>,
 }
 
+/// Input for creating an exchange.
+#[derive(Debug, Deserialize)]
+pub struct ExchangeInput {
+    /// Comma-separated fulfillment line item IDs and quantities (format: "id:qty,id:qty").
+    pub line_items: String,
+    /// Selected replacement variant (format: `variant_id|list_price`).
+    pub variant: String,
+    /// Quantity of the replacement variant to send.
+    pub quantity: Option<i64>,
+    /// Unit price to charge for the replacement (defaults to the list price).
+    pub price: Option<String>,
+    /// Currency of the order's prices.
+    pub currency_code: String,
+    /// Exchange note.
+    pub note: Option<String>,
+}
+
 /// Input for capturing payment.
 #[derive(Debug, Deserialize)]
 pub struct CaptureInput {
@@ -105,10 +128,8 @@ pub async fn update_tags(
     Path(id): Path<String>,
     Form(input): Form<TagInput>,
 ) -> impl IntoResponse {
-    let order_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Order/{id}")
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
     };
 
     let result = if input.action == "remove" {
@@ -229,6 +250,16 @@ pub async fn hold_fulfillment(
     {
         Ok(()) => {
             tracing::info!(order_id = %order_id, fulfillment_order_id = %fulfillment_order_id, "Fulfillment order held");
+            if matches!(reason, FulfillmentHoldReason::HighRiskOfFraud) {
+                let flagged_order = if order_id.starts_with("gid://") {
+                    order_id.clone()
+                } else {
+                    format!("gid://shopify/Order/{order_id}")
+                };
+                FlowService::new(state.shopify(), &state.config().flow)
+                    .send(&FlowTrigger::OrderFlaggedForReview(flagged_order))
+                    .await;
+            }
             let numeric_id = order_id.split('/').next_back().unwrap_or(&order_id);
             Redirect::to(&format!("/orders/{numeric_id}")).into_response()
         }
@@ -290,10 +321,8 @@ pub async fn calculate_refund(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let order_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Order/{id}")
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
     };
 
     match state.shopify().get_suggested_refund(&order_id).await {
@@ -360,10 +389,8 @@ pub async fn refund(
     Path(id): Path<String>,
     Form(input): Form<RefundInput>,
 ) -> impl IntoResponse {
-    let order_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Order/{id}")
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
     };
 
     // Parse line items from "id:qty,id:qty" format
@@ -432,22 +459,48 @@ pub async fn create_return(
     Path(id): Path<String>,
     Form(input): Form<ReturnInput>,
 ) -> impl IntoResponse {
-    let order_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Order/{id}")
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
     };
 
-    // Parse line items from "id:qty,id:qty" format
-    let line_items: Vec<ReturnLineItemCreateInput> = input
-        .line_items
+    let line_items = parse_return_line_items(&input.line_items, input.reason_note.as_ref());
+
+    if line_items.is_empty() {
+        return (StatusCode::BAD_REQUEST, "No valid line items specified").into_response();
+    }
+
+    let return_input = ReturnCreateInput {
+        line_items,
+        requested_at: None,
+    };
+
+    match state.shopify().create_return(&order_id, return_input).await {
+        Ok(return_id) => {
+            tracing::info!(order_id = %order_id, return_id = %return_id, "Return created");
+            let numeric_id = id.split('/').next_back().unwrap_or(&id);
+            Redirect::to(&format!("/orders/{numeric_id}")).into_response()
+        }
+        Err(e) => {
+            tracing::error!(order_id = %order_id, error = %e, "Failed to create return");
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Failed to create return: {e}"),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Parse fulfillment line items from "id:qty,id:qty" format.
+fn parse_return_line_items(
+    line_items: &str,
+    reason_note: Option<&String>,
+) -> Vec<ReturnLineItemCreateInput> {
+    line_items
         .split(',')
         .filter_map(|item| {
-            let mut parts = item.trim().split(':');
-            let id_part = parts.next()?;
-            let qty_part = parts.next()?;
-            // Ensure no extra parts
-            if parts.next().is_some() {
+            let (id_part, qty_part) = item.trim().split_once(':')?;
+            if qty_part.contains(':') {
                 return None;
             }
             let fulfillment_line_item_id = if id_part.starts_with("gid://") {
@@ -455,41 +508,151 @@ pub async fn create_return(
             } else {
                 format!("gid://shopify/FulfillmentLineItem/{id_part}")
             };
-            let quantity = qty_part.parse().ok()?;
             Some(ReturnLineItemCreateInput {
                 fulfillment_line_item_id,
-                quantity,
-                return_reason_note: input.reason_note.clone(),
+                quantity: qty_part.parse().ok()?,
+                return_reason_note: reason_note.cloned(),
             })
         })
-        .collect();
+        .collect()
+}
 
-    if line_items.is_empty() {
+/// Create an exchange for an order.
+///
+/// The returned items and the replacement variant are submitted together;
+/// the new exchange shows up on the order timeline.
+#[instrument(skip(_admin, state, input))]
+pub async fn create_exchange(
+    RequireAdminAuth(_admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Form(input): Form<ExchangeInput>,
+) -> impl IntoResponse {
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
+    };
+
+    let return_line_items = parse_return_line_items(&input.line_items, None);
+    if return_line_items.is_empty() {
         return (StatusCode::BAD_REQUEST, "No valid line items specified").into_response();
     }
 
-    let return_input = ReturnCreateInput {
-        line_items,
-        requested_at: None,
+    let Some((variant_id, list_price)) = input.variant.split_once('|').and_then(|(id, price)| {
+        Some((
+            id.parse::<VariantId>().ok()?,
+            price.parse::<Decimal>().ok()?,
+        ))
+    }) else {
+        return (StatusCode::BAD_REQUEST, "Select a replacement variant").into_response();
     };
 
-    match state.shopify().create_return(&order_id, return_input).await {
-        Ok(return_id) => {
-            tracing::info!(order_id = %order_id, return_id = %return_id, "Return created");
+    let quantity = input.quantity.unwrap_or(1);
+    if quantity < 1 {
+        return (StatusCode::BAD_REQUEST, "Quantity must be at least 1").into_response();
+    }
+
+    let price = match input.price.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(p) => match p.parse::<Decimal>() {
+            Ok(price) if price >= Decimal::ZERO => Some(price),
+            _ => return (StatusCode::BAD_REQUEST, "Invalid price").into_response(),
+        },
+    };
+
+    let exchange_input = ExchangeOrderInput {
+        order_id: order_id.clone(),
+        return_line_items,
+        line_items_to_add: vec![ExchangeLineItemInput {
+            variant_id,
+            quantity,
+            list_price,
+            price,
+            currency_code: input.currency_code,
+        }],
+        note: input.note.filter(|n| !n.trim().is_empty()),
+    };
+
+    match state.shopify().create_exchange_order(exchange_input).await {
+        Ok(exchange_id) => {
+            tracing::info!(order_id = %order_id, exchange_id = %exchange_id, "Exchange created");
             let numeric_id = id.split('/').next_back().unwrap_or(&id);
             Redirect::to(&format!("/orders/{numeric_id}")).into_response()
         }
         Err(e) => {
-            tracing::error!(order_id = %order_id, error = %e, "Failed to create return");
+            tracing::error!(order_id = %order_id, error = %e, "Failed to create exchange");
             (
                 StatusCode::BAD_REQUEST,
-                format!("Failed to create return: {e}"),
+                format!("Failed to create exchange: {e}"),
             )
                 .into_response()
         }
     }
 }
 
+/// Search variants to offer as an exchange replacement (HTMX).
+///
+/// Each result is a radio input for the exchange form.
+#[instrument(skip(_admin, state))]
+pub async fn exchange_search_variants(
+    RequireAdminAuth(_admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Query(query): Query<ProductSearchQuery>,
+) -> impl IntoResponse {
+    use std::fmt::Write;
+
+    let search_query = query.q.filter(|q| !q.is_empty());
+
+    match state.shopify().get_products(20, None, search_query).await {
+        Ok(products) => {
+            let mut html = String::new();
+            for product in products.products {
+                let _ = write!(
+                    html,
+                    r#"<div class="p-3 border-b border-border">
+                        <div class="font-medium text-sm">{}</div>
+                        <div class="mt-2 space-y-1">"#,
+                    product.title
+                );
+                for variant in &product.variants {
+                    let price: Decimal = variant.price.amount.parse().unwrap_or_default();
+                    let variant_title = &variant.title;
+                    let sku_display = variant
+                        .sku
+                        .as_ref()
+                        .filter(|s| !s.is_empty())
+                        .map_or(String::new(), |s| format!("({s})"));
+                    let _ = write!(
+                        html,
+                        r#"<label class="flex justify-between items-center gap-2 px-2 py-1 rounded hover:bg-primary/10 text-sm cursor-pointer">
+                            <span class="flex items-center gap-2">
+                                <input type="radio" name="variant" value="{}|{price:.2}" required>
+                                {variant_title} {sku_display}
+                            </span>
+                            <span class="text-muted-foreground">${price:.2}</span>
+                        </label>"#,
+                        variant.id,
+                    );
+                }
+                html.push_str("</div></div>");
+            }
+            if html.is_empty() {
+                html =
+                    r#"<div class="p-4 text-center text-muted-foreground">No products found</div>"#
+                        .to_string();
+            }
+            Html(html).into_response()
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to search products");
+            Html(
+                r#"<div class="p-4 text-center text-destructive">Failed to search products</div>"#
+                    .to_string(),
+            )
+            .into_response()
+        }
+    }
+}
+
 // =============================================================================
 // Payment Handlers
 // =============================================================================
@@ -502,10 +665,8 @@ pub async fn capture(
     Path(id): Path<String>,
     Form(input): Form<CaptureInput>,
 ) -> impl IntoResponse {
-    let order_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Order/{id}")
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
     };
 
     let transaction_id = if input.transaction_id.starts_with("gid://") {
@@ -547,10 +708,8 @@ pub async fn archive(
     Path(id): Path<String>,
     Query(params): Query<ArchiveParams>,
 ) -> impl IntoResponse {
-    let order_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Order/{id}")
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
     };
 
     let result = if params.unarchive.unwrap_or(false) {
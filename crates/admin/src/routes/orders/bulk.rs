@@ -6,6 +6,7 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Redirect},
 };
+use naked_pineapple_core::OrderId;
 use serde::Deserialize;
 use tracing::instrument;
 
@@ -50,10 +51,9 @@ pub async fn bulk_add_tags(
     let mut error_messages = Vec::new();
 
     for id in &order_ids {
-        let order_id = if id.starts_with("gid://") {
-            (*id).to_string()
-        } else {
-            format!("gid://shopify/Order/{id}")
+        let Ok(order_id) = OrderId::parse_admin_id(id) else {
+            error_messages.push(format!("{id}: invalid order ID"));
+            continue;
         };
 
         match state.shopify().add_tags_to_order(&order_id, &tags).await {
@@ -107,10 +107,9 @@ pub async fn bulk_remove_tags(
     let mut error_messages = Vec::new();
 
     for id in &order_ids {
-        let order_id = if id.starts_with("gid://") {
-            (*id).to_string()
-        } else {
-            format!("gid://shopify/Order/{id}")
+        let Ok(order_id) = OrderId::parse_admin_id(id) else {
+            error_messages.push(format!("{id}: invalid order ID"));
+            continue;
         };
 
         match state
@@ -162,10 +161,9 @@ pub async fn bulk_archive(
     let mut error_messages = Vec::new();
 
     for id in &order_ids {
-        let order_id = if id.starts_with("gid://") {
-            (*id).to_string()
-        } else {
-            format!("gid://shopify/Order/{id}")
+        let Ok(order_id) = OrderId::parse_admin_id(id) else {
+            error_messages.push(format!("{id}: invalid order ID"));
+            continue;
         };
 
         match state.shopify().archive_order(&order_id).await {
@@ -213,10 +211,9 @@ pub async fn bulk_cancel(
     let mut error_messages = Vec::new();
 
     for id in &order_ids {
-        let order_id = if id.starts_with("gid://") {
-            (*id).to_string()
-        } else {
-            format!("gid://shopify/Order/{id}")
+        let Ok(order_id) = OrderId::parse_admin_id(id) else {
+            error_messages.push(format!("{id}: invalid order ID"));
+            continue;
         };
 
         // Cancel with default settings: no notification, no refund, restock items
@@ -9,12 +9,12 @@ use axum::{
     http::StatusCode,
     response::{Html, IntoResponse, Redirect},
 };
-use naked_pineapple_core::{AdminUserId, InventoryLotId, LotAllocationId};
+use naked_pineapple_core::{AdminUserId, InventoryLotId, LotAllocationId, OrderId};
 use serde::Deserialize;
 use tracing::instrument;
 
 use crate::{
-    db::inventory_lot::InventoryLotRepository,
+    db::{ReadPool, inventory_lot::InventoryLotRepository},
     filters,
     middleware::auth::RequireAdminAuth,
     models::inventory_lot::{AllocateLotInput, LotAllocation},
@@ -77,16 +77,15 @@ pub struct CancelFormInput {
 }
 
 /// Order detail page handler.
-#[instrument(skip(admin, state))]
+#[instrument(skip(admin, state, pool))]
 pub async fn show(
     RequireAdminAuth(admin): RequireAdminAuth,
     State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let order_id = if id.starts_with("gid://") {
-        id
-    } else {
-        format!("gid://shopify/Order/{id}")
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
     };
 
     match state.shopify().get_order(&order_id).await {
@@ -94,8 +93,8 @@ pub async fn show(
             let mut order_view = OrderDetailView::from(&order);
 
             // Fetch lot allocations for this order
-            let lot_repo = InventoryLotRepository::new(state.pool());
-            if let Ok(allocations) = lot_repo.get_allocations_for_order(&order_id).await {
+            let lot_repo = InventoryLotRepository::new(&pool);
+            if let Ok(allocations) = lot_repo.get_allocations_for_order(order_id.as_str()).await {
                 // Group allocations by line item ID
                 let mut alloc_by_line_item: HashMap<String, Vec<_>> = HashMap::new();
                 for alloc in allocations {
@@ -197,10 +196,8 @@ pub async fn update_note(
     Path(id): Path<String>,
     Form(input): Form<NoteFormInput>,
 ) -> impl IntoResponse {
-    let order_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Order/{id}")
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
     };
 
     match state
@@ -245,10 +242,8 @@ pub async fn mark_paid(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let order_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Order/{id}")
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
     };
 
     match state.shopify().mark_order_as_paid(&order_id).await {
@@ -276,10 +271,8 @@ pub async fn cancel(
     Path(id): Path<String>,
     Form(input): Form<CancelFormInput>,
 ) -> impl IntoResponse {
-    let order_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Order/{id}")
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
     };
 
     let notify = input.notify_customer.as_deref() == Some("on");
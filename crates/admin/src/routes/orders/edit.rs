@@ -7,6 +7,7 @@ use axum::{
     http::StatusCode,
     response::{Html, IntoResponse, Redirect},
 };
+use naked_pineapple_core::{OrderId, VariantId};
 use serde::Deserialize;
 use tracing::instrument;
 
@@ -207,10 +208,8 @@ pub async fn edit(
         id.clone()
     };
 
-    let order_id = if id.starts_with("gid://") {
-        id
-    } else {
-        format!("gid://shopify/Order/{id}")
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
     };
 
     match state.shopify().order_edit_begin(&order_id).await {
@@ -246,10 +245,8 @@ pub async fn edit_add_variant(
     Path(id): Path<String>,
     Form(input): Form<AddVariantInput>,
 ) -> impl IntoResponse {
-    let variant_id = if input.variant_id.starts_with("gid://") {
-        input.variant_id
-    } else {
-        format!("gid://shopify/ProductVariant/{}", input.variant_id)
+    let Ok(variant_id) = VariantId::parse_admin_id(&input.variant_id) else {
+        return (StatusCode::BAD_REQUEST, "Invalid variant ID").into_response();
     };
 
     match state
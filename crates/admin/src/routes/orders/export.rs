@@ -0,0 +1,386 @@
+//! Order CSV export with per-admin column selection.
+//!
+//! The export streams one row per order, fetching a page from Shopify at a
+//! time, so large exports never hold every order in memory.
+
+use axum::{
+    body::Body,
+    extract::{Query, RawQuery, State},
+    http::{StatusCode, header},
+    response::{Html, IntoResponse, Response},
+};
+use futures::{StreamExt, TryStreamExt, stream};
+use tracing::instrument;
+
+use naked_pineapple_core::Email;
+
+use crate::{
+    db::UserPreferencesRepository,
+    middleware::auth::RequireAdminAuth,
+    shopify::{
+        AdminShopifyError, CursorPaginator,
+        types::{OrderListItem, OrderSortKey},
+    },
+    state::AppState,
+};
+
+use super::types::{
+    OrdersQuery, build_shopify_query, format_financial_status, format_fulfillment_status,
+};
+
+/// Page key for order preferences in `admin.user_preferences`.
+pub const PREFERENCES_PAGE: &str = "orders";
+
+/// Maximum rows in one export.
+const MAX_EXPORT_ROWS: usize = 50_000;
+
+/// Orders fetched from Shopify per page while exporting.
+const EXPORT_PAGE_SIZE: i64 = 250;
+
+/// Query parameter (and form field) holding the selected field keys.
+const FIELDS_PARAM: &str = "fields[]";
+
+/// A column available in the order CSV export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportField {
+    /// Key used in `fields[]` and saved preferences.
+    pub key: &'static str,
+    /// CSV header and checkbox label.
+    pub label: &'static str,
+}
+
+impl ExportField {
+    const fn new(key: &'static str, label: &'static str) -> Self {
+        Self { key, label }
+    }
+}
+
+/// Every exportable column, in the order they appear in the CSV.
+pub const EXPORT_FIELDS: &[ExportField] = &[
+    ExportField::new("order_name", "Order"),
+    ExportField::new("created_at", "Created At"),
+    ExportField::new("customer_name", "Customer"),
+    ExportField::new("customer_email", "Email"),
+    ExportField::new("customer_phone", "Phone"),
+    ExportField::new("financial_status", "Payment Status"),
+    ExportField::new("fulfillment_status", "Fulfillment Status"),
+    ExportField::new("return_status", "Return Status"),
+    ExportField::new("items", "Items"),
+    ExportField::new("subtotal", "Subtotal"),
+    ExportField::new("shipping", "Shipping"),
+    ExportField::new("tax", "Tax"),
+    ExportField::new("discounts", "Discounts"),
+    ExportField::new("total", "Total"),
+    ExportField::new("currency", "Currency"),
+    ExportField::new("discount_codes", "Discount Codes"),
+    ExportField::new("tags", "Tags"),
+    ExportField::new("channel", "Channel"),
+    ExportField::new("shipping_method", "Shipping Method"),
+    ExportField::new("shipping_city", "Shipping City"),
+    ExportField::new("shipping_province", "Shipping Province"),
+    ExportField::new("shipping_country", "Shipping Country"),
+    ExportField::new("shipping_zip", "Shipping Zip"),
+    ExportField::new("note", "Note"),
+    ExportField::new("cancelled_at", "Cancelled At"),
+];
+
+/// Fields exported when an admin hasn't saved a selection.
+pub const DEFAULT_EXPORT_FIELDS: &[&str] = &[
+    "order_name",
+    "created_at",
+    "customer_email",
+    "financial_status",
+    "fulfillment_status",
+    "total",
+];
+
+/// Export field checkbox for the configure export panel.
+#[derive(Debug, Clone)]
+pub struct ExportFieldView {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub selected: bool,
+}
+
+/// Build checkboxes for every field, ticking `selected`.
+#[must_use]
+pub fn export_field_views(selected: &[String]) -> Vec<ExportFieldView> {
+    EXPORT_FIELDS
+        .iter()
+        .map(|field| ExportFieldView {
+            key: field.key,
+            label: field.label,
+            selected: selected.iter().any(|key| key == field.key),
+        })
+        .collect()
+}
+
+/// An admin's saved export fields, or the defaults.
+pub async fn saved_export_fields(state: &AppState, admin_email: &Email) -> Vec<String> {
+    match UserPreferencesRepository::new(state.pool())
+        .get_export_fields(admin_email, PREFERENCES_PAGE)
+        .await
+    {
+        Ok(Some(fields)) if !fields.is_empty() => fields,
+        Ok(_) => DEFAULT_EXPORT_FIELDS
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        Err(e) => {
+            tracing::warn!("Failed to load export preferences: {e}");
+            DEFAULT_EXPORT_FIELDS
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        }
+    }
+}
+
+/// Pick the known export fields out of url-encoded `fields[]` pairs.
+///
+/// Unknown keys are dropped and the result follows [`EXPORT_FIELDS`] order,
+/// so the CSV layout doesn't depend on checkbox order.
+fn parse_fields(encoded: &str) -> Vec<&'static ExportField> {
+    let requested: Vec<String> = url::form_urlencoded::parse(encoded.as_bytes())
+        .filter(|(name, _)| name == FIELDS_PARAM || name == "fields")
+        .map(|(_, value)| value.into_owned())
+        .collect();
+
+    EXPORT_FIELDS
+        .iter()
+        .filter(|field| requested.iter().any(|key| key == field.key))
+        .collect()
+}
+
+/// Quote a CSV value if needed.
+///
+/// Non-numeric values starting with a formula character are prefixed with
+/// `'` so spreadsheets don't evaluate them.
+fn csv_escape(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) && value.parse::<f64>().is_err() {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Join values into one CSV line.
+fn csv_line<I, S>(values: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut line = values
+        .into_iter()
+        .map(|v| csv_escape(v.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// Value of one export field for an order.
+fn field_value(order: &OrderListItem, key: &str) -> String {
+    let shipping = order.shipping_address.as_ref();
+
+    match key {
+        "order_name" => order.name.clone(),
+        "created_at" => order.created_at.clone(),
+        "customer_name" => order.customer_name.clone().unwrap_or_default(),
+        "customer_email" => order.email.clone().unwrap_or_default(),
+        "customer_phone" => order.phone.clone().unwrap_or_default(),
+        "financial_status" => format_financial_status(order.financial_status.as_ref()).0,
+        "fulfillment_status" => format_fulfillment_status(order.fulfillment_status.as_ref()).0,
+        "return_status" => order
+            .return_status
+            .as_ref()
+            .map_or_else(String::new, ToString::to_string),
+        "items" => order.total_items_quantity.to_string(),
+        "subtotal" => order.subtotal_price.amount.clone(),
+        "shipping" => order.total_shipping_price.amount.clone(),
+        "tax" => order.total_tax.amount.clone(),
+        "discounts" => order.total_discounts.amount.clone(),
+        "total" => order.total_price.amount.clone(),
+        "currency" => order.currency_code.clone(),
+        "discount_codes" => order.discount_codes.join(", "),
+        "tags" => order.tags.join(", "),
+        "channel" => order
+            .channel_info
+            .as_ref()
+            .and_then(|c| c.channel_name.clone())
+            .unwrap_or_default(),
+        "shipping_method" => order
+            .shipping_line
+            .as_ref()
+            .map(|s| s.title.clone())
+            .unwrap_or_default(),
+        "shipping_city" => shipping.and_then(|a| a.city.clone()).unwrap_or_default(),
+        "shipping_province" => shipping
+            .and_then(|a| a.province_code.clone())
+            .unwrap_or_default(),
+        "shipping_country" => shipping
+            .and_then(|a| a.country_code.clone())
+            .unwrap_or_default(),
+        "shipping_zip" => shipping.and_then(|a| a.zip.clone()).unwrap_or_default(),
+        "note" => order.note.clone().unwrap_or_default(),
+        "cancelled_at" => order.cancelled_at.clone().unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Export orders as CSV.
+///
+/// Takes the same filters and sort as the orders list, plus repeated
+/// `fields[]` parameters naming the columns. Without `fields[]`, the admin's
+/// saved selection (or the defaults) is used. At most 50,000 orders are
+/// exported.
+#[instrument(skip(admin, state, raw_query))]
+pub async fn export(
+    RequireAdminAuth(admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Query(query): Query<OrdersQuery>,
+    RawQuery(raw_query): RawQuery,
+) -> Response {
+    let mut fields = parse_fields(raw_query.as_deref().unwrap_or_default());
+    if fields.is_empty() {
+        let saved = saved_export_fields(&state, &admin.email).await;
+        fields = EXPORT_FIELDS
+            .iter()
+            .filter(|field| saved.iter().any(|key| key == field.key))
+            .collect();
+    }
+
+    let shopify_query = build_shopify_query(&query);
+    let sort_key = query
+        .sort
+        .as_ref()
+        .and_then(|s| OrderSortKey::from_str_param(s));
+    let reverse = query.dir.as_deref() == Some("desc");
+
+    tracing::info!(
+        admin = %admin.email,
+        fields = fields.len(),
+        query = ?shopify_query,
+        "Exporting orders"
+    );
+
+    let orders = CursorPaginator::<OrderListItem>::new(move |cursor| {
+        let state = state.clone();
+        let shopify_query = shopify_query.clone();
+        Box::pin(async move {
+            state
+                .shopify()
+                .get_orders_list(EXPORT_PAGE_SIZE, cursor, shopify_query, sort_key, reverse)
+                .await
+        })
+    });
+
+    let header_line = csv_line(fields.iter().map(|field| field.label));
+    let rows = orders
+        .take(MAX_EXPORT_ROWS)
+        .map_ok(move |order| csv_line(fields.iter().map(|field| field_value(&order, field.key))))
+        .inspect_err(|e| tracing::error!("Order export failed: {e}"));
+    let body = stream::once(async move { Ok::<_, AdminShopifyError>(header_line) }).chain(rows);
+
+    let filename = format!(
+        "orders-{}.csv",
+        chrono::Utc::now().format("%Y-%m-%d-%H%M%S")
+    );
+
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        Body::from_stream(body),
+    )
+        .into_response()
+}
+
+/// Save the posted `fields[]` as the admin's default export columns (HTMX).
+#[instrument(skip(admin, state, body))]
+pub async fn save_export_preferences(
+    RequireAdminAuth(admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    body: String,
+) -> impl IntoResponse {
+    let fields: Vec<String> = parse_fields(&body)
+        .iter()
+        .map(|field| field.key.to_string())
+        .collect();
+
+    if fields.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(r#"<span class="text-destructive">Select at least one field</span>"#),
+        );
+    }
+
+    match UserPreferencesRepository::new(state.pool())
+        .save_export_fields(&admin.email, PREFERENCES_PAGE, &fields)
+        .await
+    {
+        Ok(()) => (
+            StatusCode::OK,
+            Html(r#"<span class="text-success">Saved as default</span>"#),
+        ),
+        Err(e) => {
+            tracing::error!("Failed to save export preferences: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html(r#"<span class="text-destructive">Failed to save</span>"#),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fields_keeps_known_fields_in_export_order() {
+        let fields =
+            parse_fields("fields%5B%5D=total&fields%5B%5D=bogus&fields[]=order_name&query=x");
+        let keys: Vec<_> = fields.iter().map(|f| f.key).collect();
+        assert_eq!(keys, vec!["order_name", "total"]);
+    }
+
+    #[test]
+    fn test_parse_fields_without_fields() {
+        assert!(parse_fields("status=open").is_empty());
+        assert!(parse_fields("").is_empty());
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("#1001"), "#1001");
+        assert_eq!(csv_escape("a, b"), "\"a, b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_escape("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(csv_escape("=SUM(A1)"), "'=SUM(A1)");
+        assert_eq!(csv_escape("-5.00"), "-5.00");
+    }
+
+    #[test]
+    fn test_csv_line() {
+        assert_eq!(csv_line(["Order", "Total"]), "Order,Total\r\n");
+    }
+
+    #[test]
+    fn test_default_fields_exist() {
+        for key in DEFAULT_EXPORT_FIELDS {
+            assert!(EXPORT_FIELDS.iter().any(|f| f.key == *key), "{key}");
+        }
+    }
+}
@@ -13,13 +13,16 @@ use crate::{
     },
     filters,
     middleware::auth::RequireAdminAuth,
+    services::OrderSummary,
     shopify::types::OrderSortKey,
     state::AppState,
 };
 
 use super::super::dashboard::AdminUserView;
+use super::export::{ExportFieldView, export_field_views, saved_export_fields};
 use super::types::{
     OrderColumnVisibility, OrderTableView, OrdersQuery, build_preserve_params, build_shopify_query,
+    format_financial_status,
 };
 
 /// Orders list page template with data table support.
@@ -56,6 +59,10 @@ pub struct OrdersIndexTemplate {
     pub preserve_params: String,
     /// Active filter values for highlighting.
     pub filter_values: std::collections::HashMap<String, String>,
+    /// Export field checkboxes, ticked with the admin's saved selection.
+    pub export_fields: Vec<ExportFieldView>,
+    /// Current filters and sort, carried into the export as hidden inputs.
+    pub export_params: Vec<(String, String)>,
 }
 
 /// Orders list page handler.
@@ -124,6 +131,18 @@ pub async fn index(
 
     let preserve_params = build_preserve_params(&query);
 
+    let export_fields = export_field_views(&saved_export_fields(&state, &admin.email).await);
+    let mut export_params: Vec<(String, String)> =
+        url::form_urlencoded::parse(preserve_params.as_bytes())
+            .into_owned()
+            .collect();
+    if let Some(sort) = &query.sort {
+        export_params.push(("sort".to_string(), sort.clone()));
+    }
+    if let Some(dir) = &query.dir {
+        export_params.push(("dir".to_string(), dir.clone()));
+    }
+
     let template = OrdersIndexTemplate {
         admin_user: AdminUserView::from(&admin),
         current_path: "/orders".to_string(),
@@ -141,6 +160,97 @@ pub async fn index(
         sort_direction: query.dir.unwrap_or_else(|| "desc".to_string()),
         preserve_params,
         filter_values,
+        export_fields,
+        export_params,
+    };
+
+    Html(template.render().unwrap_or_else(|e| {
+        tracing::error!("Template render error: {}", e);
+        "Internal Server Error".to_string()
+    }))
+}
+
+/// Order count and revenue for one financial status, for display.
+#[derive(Debug, Clone)]
+pub struct FinancialStatusTotalView {
+    pub label: String,
+    pub class: String,
+    pub order_count: i64,
+    pub total: String,
+}
+
+/// Orders summary card partial template.
+#[derive(Template)]
+#[template(path = "orders/_summary_card.html")]
+pub struct OrdersSummaryTemplate {
+    pub order_count: i64,
+    pub gross_revenue: String,
+    pub refunded: String,
+    pub net_revenue: String,
+    pub by_status: Vec<FinancialStatusTotalView>,
+    pub unreadable_orders: Vec<String>,
+    pub truncated: bool,
+    pub error: Option<String>,
+}
+
+impl From<&OrderSummary> for OrdersSummaryTemplate {
+    fn from(summary: &OrderSummary) -> Self {
+        Self {
+            order_count: summary.order_count,
+            gross_revenue: format!("${:.2}", summary.gross_revenue),
+            refunded: format!("${:.2}", summary.refunded),
+            net_revenue: format!("${:.2}", summary.net_revenue),
+            by_status: summary
+                .by_status
+                .iter()
+                .map(|s| {
+                    let (label, class) = format_financial_status(s.status.as_ref());
+                    FinancialStatusTotalView {
+                        label,
+                        class,
+                        order_count: s.order_count,
+                        total: format!("${:.2}", s.total),
+                    }
+                })
+                .collect(),
+            unreadable_orders: summary.unreadable_orders.clone(),
+            truncated: summary.truncated,
+            error: None,
+        }
+    }
+}
+
+/// Orders summary card handler (HTMX).
+///
+/// Totals the orders matching the list's current filters. Sort and cursor
+/// parameters are ignored.
+#[instrument(skip(_admin, state))]
+pub async fn summary(
+    RequireAdminAuth(_admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Query(query): Query<OrdersQuery>,
+) -> Html<String> {
+    let shopify_query = build_shopify_query(&query);
+
+    let template = match state
+        .order_summary()
+        .summary(state.shopify(), shopify_query)
+        .await
+    {
+        Ok(summary) => OrdersSummaryTemplate::from(summary.as_ref()),
+        Err(e) => {
+            tracing::error!("Failed to summarize orders: {e}");
+            OrdersSummaryTemplate {
+                order_count: 0,
+                gross_revenue: String::new(),
+                refunded: String::new(),
+                net_revenue: String::new(),
+                by_status: Vec::new(),
+                unreadable_orders: Vec::new(),
+                truncated: false,
+                error: Some("Could not load order totals.".to_string()),
+            }
+        }
     };
 
     Html(template.render().unwrap_or_else(|e| {
@@ -1,12 +1,14 @@
 //! Orders management route handlers.
 //!
-//! This module contains handlers for order listing, detail views, bulk actions,
-//! single order actions, printing, and order editing.
+//! This module contains handlers for order listing and totals, CSV export,
+//! detail views, bulk actions, single order actions, printing, and order
+//! editing.
 
 mod actions;
 mod bulk;
 mod detail;
 mod edit;
+mod export;
 mod list;
 mod print;
 pub mod types;
@@ -14,8 +16,13 @@ pub mod types;
 // Re-export types needed by templates and router
 pub use types::{OrderColumnVisibility, OrderDetailView, OrderTableView, OrderView, OrdersQuery};
 
+// Re-export export handlers
+pub use export::{ExportFieldView, export, save_export_preferences};
+
 // Re-export list handlers
-pub use list::{OrdersIndexTemplate, index};
+pub use list::{
+    FinancialStatusTotalView, OrdersIndexTemplate, OrdersSummaryTemplate, index, summary,
+};
 
 // Re-export detail handlers
 pub use detail::{
@@ -30,9 +37,9 @@ pub use bulk::{
 
 // Re-export single action handlers
 pub use actions::{
-    ArchiveParams, CaptureInput, FulfillInput, HoldInput, RefundInput, ReturnInput, TagInput,
-    archive, calculate_refund, capture, create_return, fulfill, hold_fulfillment, refund,
-    release_hold, update_tags,
+    ArchiveParams, CaptureInput, ExchangeInput, FulfillInput, HoldInput, RefundInput, ReturnInput,
+    TagInput, archive, calculate_refund, capture, create_exchange, create_return,
+    exchange_search_variants, fulfill, hold_fulfillment, refund, release_hold, update_tags,
 };
 
 // Re-export print handlers
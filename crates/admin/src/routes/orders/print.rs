@@ -6,6 +6,7 @@ use axum::{
     http::StatusCode,
     response::{Html, IntoResponse},
 };
+use naked_pineapple_core::OrderId;
 use serde::Deserialize;
 use tracing::instrument;
 
@@ -99,10 +100,8 @@ pub async fn print(
     Path(id): Path<String>,
     Query(query): Query<PrintQuery>,
 ) -> impl IntoResponse {
-    let order_id = if id.starts_with("gid://") {
-        id
-    } else {
-        format!("gid://shopify/Order/{id}")
+    let Ok(order_id) = OrderId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Order not found").into_response();
     };
 
     match state.shopify().get_order(&order_id).await {
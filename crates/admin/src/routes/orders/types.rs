@@ -1,11 +1,13 @@
 //! Type definitions and conversions for order views.
 
+use naked_pineapple_core::{ProductId, VariantId};
 use serde::Deserialize;
 
 use crate::shopify::types::{
     Address, CalculatedLineItem, CalculatedOrder, CalculatedShippingLine,
     CalculatedShippingLineStagedStatus, FinancialStatus, Fulfillment, FulfillmentStatus, Money,
-    Order, OrderLineItem, OrderListItem, OrderReturnStatus, OrderRiskLevel,
+    Order, OrderExchange, OrderLineItem, OrderListItem, OrderReturnStatus, OrderRiskLevel,
+    OrderTimelineEvent,
 };
 
 use super::super::dashboard::AdminUserView;
@@ -198,7 +200,8 @@ pub fn format_price(money: &Money) -> String {
 }
 
 /// Format financial status with semantic badge class.
-fn format_financial_status(status: Option<&FinancialStatus>) -> (String, String) {
+#[must_use]
+pub fn format_financial_status(status: Option<&FinancialStatus>) -> (String, String) {
     match status {
         Some(FinancialStatus::Paid) => ("Paid".to_string(), "badge badge-success".to_string()),
         Some(FinancialStatus::Authorized) => {
@@ -503,7 +506,7 @@ pub fn build_preserve_params(query: &OrdersQuery) -> String {
 
 impl From<&OrderListItem> for OrderTableView {
     fn from(order: &OrderListItem) -> Self {
-        let short_id = extract_numeric_id(&order.id);
+        let short_id = extract_numeric_id(order.id.as_str());
         let (payment_status, payment_status_class) =
             format_financial_status(order.financial_status.as_ref());
         let (fulfillment_status, fulfillment_status_class) =
@@ -520,7 +523,7 @@ impl From<&OrderListItem> for OrderTableView {
 
         Self {
             short_id,
-            id: order.id.clone(),
+            id: order.id.to_string(),
             name: order.name.clone(),
             created_at: order.created_at.clone(),
             customer_name: order
@@ -592,7 +595,7 @@ impl From<&Order> for OrderView {
         let item_count: i64 = order.line_items.iter().map(|li| li.quantity).sum();
 
         Self {
-            id: order.id.clone(),
+            id: order.id.to_string(),
             name: order.name.clone(),
             customer_name: get_customer_name(order),
             customer_email: order.email.clone(),
@@ -689,8 +692,8 @@ impl From<&OrderLineItem> for LineItemView {
             quantity: item.quantity,
             unit_price,
             total_price: format!("${total:.2}"),
-            product_id: item.product_id.clone(),
-            variant_id: item.variant_id.clone(),
+            product_id: item.product_id.as_ref().map(ToString::to_string),
+            variant_id: item.variant_id.as_ref().map(ToString::to_string),
             allocations: vec![],
             allocated_quantity: 0,
             needed_quantity: item.quantity,
@@ -758,7 +761,9 @@ impl From<&Address> for AddressView {
 /// Fulfilled line item view for templates.
 #[derive(Debug, Clone)]
 pub struct FulfilledLineItemView {
+    pub id: String,
     pub title: String,
+    pub variant_title: Option<String>,
     pub quantity: i64,
 }
 
@@ -786,7 +791,16 @@ impl From<&Fulfillment> for FulfillmentView {
             carrier: tracking.and_then(|t| t.company.clone()),
             location_name: None,
             created_at: f.created_at.clone(),
-            line_items: vec![],
+            line_items: f
+                .line_items
+                .iter()
+                .map(|li| FulfilledLineItemView {
+                    id: li.id.clone(),
+                    title: li.title.clone(),
+                    variant_title: li.variant_title.clone(),
+                    quantity: li.quantity,
+                })
+                .collect(),
         }
     }
 }
@@ -852,6 +866,83 @@ pub struct TimelineEventView {
     pub message: String,
     pub created_at: String,
     pub staff_name: Option<String>,
+    /// Link to the record the event refers to.
+    pub link: Option<String>,
+}
+
+impl TimelineEventView {
+    /// Timeline event for an exchange created on the order.
+    ///
+    /// Exchanges live on the original order in Shopify, so the link opens the
+    /// order in the Shopify admin where the exchange can be processed.
+    fn from_exchange(exchange: &OrderExchange, order_short_id: &str) -> Self {
+        let items = if exchange.exchange_quantity == 1 {
+            "item"
+        } else {
+            "items"
+        };
+        Self {
+            event_type: "EXCHANGE".to_string(),
+            message: format!(
+                "Exchange {} created with {} replacement {items}",
+                exchange.name, exchange.exchange_quantity
+            ),
+            created_at: exchange.created_at.clone(),
+            staff_name: None,
+            link: Some(format!(
+                "https://admin.shopify.com/store/naked-pineapple/orders/{order_short_id}"
+            )),
+        }
+    }
+
+    /// Timeline event for a Shopify order event.
+    ///
+    /// Status changes are typed from Shopify's event action so the timeline
+    /// can color them by what happened.
+    fn from_timeline_event(event: &OrderTimelineEvent) -> Self {
+        match event {
+            OrderTimelineEvent::StatusChange {
+                action,
+                message,
+                created_at,
+            } => Self {
+                event_type: status_event_type(action).to_string(),
+                message: message.clone(),
+                created_at: created_at.clone(),
+                staff_name: None,
+                link: None,
+            },
+            OrderTimelineEvent::Comment {
+                body,
+                author,
+                created_at,
+            } => Self {
+                event_type: "COMMENT".to_string(),
+                message: body.clone(),
+                created_at: created_at.clone(),
+                staff_name: Some(author.clone()),
+                link: None,
+            },
+        }
+    }
+}
+
+/// Timeline event type for a Shopify event action.
+fn status_event_type(action: &str) -> &'static str {
+    if action == "placed" || action == "create" {
+        "CREATED"
+    } else if action.contains("refund") {
+        "REFUND"
+    } else if action.contains("fulfillment") {
+        "FULFILLMENT"
+    } else if ["paid", "capture", "authorization", "sale"]
+        .iter()
+        .any(|keyword| action.contains(keyword))
+    {
+        "PAYMENT"
+    } else {
+        "STATUS_CHANGE"
+    }
 }
 
 /// Order detail view for templates with full enhanced data.
@@ -891,6 +982,7 @@ pub struct OrderDetailView {
     pub total_refunded: String,
     pub net_payment: String,
     pub total_outstanding: String,
+    pub currency_code: String,
 
     // Customer
     pub customer_id: Option<String>,
@@ -971,15 +1063,21 @@ pub fn financial_status_display(order: &Order) -> (String, String, bool) {
 /// Convert a fulfillment order to view with product ID lookup.
 fn convert_fulfillment_order_to_view(
     fo: &crate::shopify::types::FulfillmentOrderDetail,
-    line_item_map: &std::collections::HashMap<&str, (&Option<String>, &Option<String>)>,
+    line_item_map: &std::collections::HashMap<&str, (&Option<ProductId>, &Option<VariantId>)>,
 ) -> FulfillmentOrderView {
     let line_items = fo
         .line_items
         .iter()
         .map(|li| {
-            let (product_id, variant_id) = line_item_map
-                .get(li.line_item_id.as_str())
-                .map_or((None, None), |(pid, vid)| ((*pid).clone(), (*vid).clone()));
+            let (product_id, variant_id) =
+                line_item_map
+                    .get(li.line_item_id.as_str())
+                    .map_or((None, None), |(pid, vid)| {
+                        (
+                            pid.as_ref().map(ToString::to_string),
+                            vid.as_ref().map(ToString::to_string),
+                        )
+                    });
             FulfillmentOrderLineItemView {
                 // Use line_item_id as id since allocations are stored against it
                 id: li.line_item_id.clone(),
@@ -1010,11 +1108,12 @@ fn convert_fulfillment_order_to_view(
 /// Convert fulfillment orders to views with product ID lookup from line items.
 fn convert_fulfillment_orders(order: &Order) -> Vec<FulfillmentOrderView> {
     // Build a mapping from line_item_id -> (product_id, variant_id)
-    let line_item_map: std::collections::HashMap<&str, (&Option<String>, &Option<String>)> = order
-        .line_items
-        .iter()
-        .map(|li| (li.id.as_str(), (&li.product_id, &li.variant_id)))
-        .collect();
+    let line_item_map: std::collections::HashMap<&str, (&Option<ProductId>, &Option<VariantId>)> =
+        order
+            .line_items
+            .iter()
+            .map(|li| (li.id.as_str(), (&li.product_id, &li.variant_id)))
+            .collect();
 
     order
         .fulfillment_orders
@@ -1025,14 +1124,26 @@ fn convert_fulfillment_orders(order: &Order) -> Vec<FulfillmentOrderView> {
 
 impl From<&Order> for OrderDetailView {
     fn from(order: &Order) -> Self {
-        let short_id = extract_numeric_id(&order.id);
+        let short_id = extract_numeric_id(order.id.as_str());
         let (fulfillment_status, fulfillment_status_class) = fulfillment_status_display(order);
         let (financial_status, financial_status_class, is_paid) = financial_status_display(order);
         let total_str = format_price(&order.total_price);
         let fulfillment_orders = convert_fulfillment_orders(order);
+        let mut events: Vec<TimelineEventView> = order
+            .timeline_events
+            .iter()
+            .map(TimelineEventView::from_timeline_event)
+            .chain(
+                order
+                    .exchanges
+                    .iter()
+                    .map(|exchange| TimelineEventView::from_exchange(exchange, &short_id)),
+            )
+            .collect();
+        events.sort_by(|a, b| a.created_at.cmp(&b.created_at));
 
         Self {
-            id: order.id.clone(),
+            id: order.id.to_string(),
             short_id,
             name: order.name.clone(),
             created_at: order.created_at.clone(),
@@ -1068,8 +1179,12 @@ impl From<&Order> for OrderDetailView {
             } else {
                 format_price(&order.total_price)
             },
+            currency_code: order.currency_code.clone(),
 
-            customer_id: order.customer_id.as_ref().map(|id| extract_numeric_id(id)),
+            customer_id: order
+                .customer_id
+                .as_ref()
+                .map(|id| extract_numeric_id(id.as_str())),
             customer_name: get_customer_name(order),
             customer_email: order.email.clone(),
             customer_phone: order.phone.clone(),
@@ -1091,7 +1206,7 @@ impl From<&Order> for OrderDetailView {
 
             transactions: vec![],
             risks: vec![],
-            events: vec![],
+            events,
 
             shipping_address: order.shipping_address.as_ref().map(AddressView::from),
             billing_address: order.billing_address.as_ref().map(AddressView::from),
@@ -1232,7 +1347,7 @@ impl From<&CalculatedOrder> for OrderEditView {
 
         Self {
             calculated_order_id: order.id.clone(),
-            order_id: order.original_order_id.clone(),
+            order_id: order.original_order_id.to_string(),
             order_name: order.original_order_name.clone(),
             line_items: order
                 .line_items
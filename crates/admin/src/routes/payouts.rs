@@ -208,7 +208,7 @@ impl From<&BalanceTransaction> for TransactionView {
             transaction_type: tx_type.to_string(),
             type_class: type_class.to_string(),
             source: source.to_string(),
-            order_id: t.order_id.clone(),
+            order_id: t.order_id.as_ref().map(ToString::to_string),
             order_name: t.order_name.clone(),
             amount: format!("${}", t.amount.amount),
             fee: format!("-${}", t.fee.amount),
@@ -259,7 +259,7 @@ impl From<&Dispute> for DisputeView {
             initiated_at: d.initiated_at.clone(),
             evidence_due_by: d.evidence_due_by.clone(),
             days_until_due: None, // Would need date calculation
-            order_id: d.order_id.clone(),
+            order_id: d.order_id.as_ref().map(ToString::to_string),
             order_name: d.order_name.clone(),
             reason,
             is_urgent,
@@ -15,14 +15,21 @@ use crate::{
     middleware::auth::{RequireAdminAuth, RequireSuperAdmin},
     models::CurrentAdmin,
     shopify::{
-        ProductUpdateInput,
-        types::{AdminProduct, Money, ProductStatus},
+        AdminShopifyError, ProductUpdateInput, VariantUpdateInput,
+        types::{
+            AdminProduct, AdminProductVariant, CogsSummary, CollectionRef, Image, Money,
+            ProductInventorySummary, ProductStatus, Publication, ResourcePublication,
+            VariantDimensions,
+        },
     },
     state::AppState,
 };
 
-use naked_pineapple_core::AdminRole;
+use naked_pineapple_core::{
+    AdminRole, Barcode, CollectionId, GidError, InventoryItemId, ProductId, VariantId,
+};
 
+use super::collections::PublicationView;
 use super::dashboard::AdminUserView;
 
 /// Pagination query parameters.
@@ -85,7 +92,7 @@ impl From<&AdminProduct> for ProductView {
             .map_or_else(|| "$0.00".to_string(), |v| format_price(&v.price));
 
         Self {
-            id: product.id.clone(),
+            id: product.id.to_string(),
             title: product.title.clone(),
             status: status.to_string(),
             status_class: status_class.to_string(),
@@ -127,6 +134,14 @@ pub struct ProductShowTemplate {
     pub admin_user: AdminUserView,
     pub current_path: String,
     pub product: ProductDetailView,
+    /// Per-location inventory, if it could be fetched.
+    pub inventory_summary: Option<ProductInventorySummary>,
+    /// Costs and margins, derived from the inventory summary.
+    pub economics: Option<EconomicsView>,
+    /// Manual collections the product can be added to.
+    pub available_collections: Vec<ProductCollectionView>,
+    /// Sales channel publishing toggles.
+    pub publications: ProductPublicationsCard,
 }
 
 /// Product create form template.
@@ -148,6 +163,29 @@ pub struct ProductEditTemplate {
     pub error: Option<String>,
 }
 
+/// Variant image slot partial, swapped in after an image is assigned or
+/// detached.
+#[derive(Template)]
+#[template(path = "products/_variant_image.html")]
+pub struct VariantImageTemplate {
+    pub slot: VariantImageSlot,
+}
+
+/// Media picker listing the product's images for a variant.
+#[derive(Template)]
+#[template(path = "products/_variant_image_picker.html")]
+pub struct VariantImagePickerTemplate {
+    pub slot: VariantImageSlot,
+    pub images: Vec<ImageView>,
+}
+
+/// Sales channels card partial, swapped in after a channel is toggled.
+#[derive(Template)]
+#[template(path = "products/_publications.html")]
+pub struct ProductPublicationsTemplate {
+    pub card: ProductPublicationsCard,
+}
+
 /// Detailed product view for detail/edit pages.
 #[derive(Debug, Clone)]
 pub struct ProductDetailView {
@@ -165,10 +203,30 @@ pub struct ProductDetailView {
     pub images: Vec<ImageView>,
     pub handle: String,
     pub variants: Vec<VariantView>,
+    pub collections: Vec<ProductCollectionView>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
 }
 
+/// Collection membership view for the product detail page.
+#[derive(Debug, Clone)]
+pub struct ProductCollectionView {
+    pub id: String,
+    pub title: String,
+    /// Smart collections are rule-based, so membership can't be edited.
+    pub is_smart: bool,
+}
+
+impl From<&CollectionRef> for ProductCollectionView {
+    fn from(collection: &CollectionRef) -> Self {
+        Self {
+            id: collection.id.to_string(),
+            title: collection.title.clone(),
+            is_smart: collection.is_smart,
+        }
+    }
+}
+
 /// Image view for templates.
 #[derive(Debug, Clone)]
 pub struct ImageView {
@@ -177,6 +235,16 @@ pub struct ImageView {
     pub alt: Option<String>,
 }
 
+impl From<&Image> for ImageView {
+    fn from(image: &Image) -> Self {
+        Self {
+            id: image.id.clone().unwrap_or_default(),
+            url: image.url.clone(),
+            alt: image.alt_text.clone(),
+        }
+    }
+}
+
 /// Variant view for templates.
 #[derive(Debug, Clone)]
 pub struct VariantView {
@@ -187,6 +255,156 @@ pub struct VariantView {
     pub price: String,
     pub compare_at_price: Option<String>,
     pub inventory_quantity: i64,
+    pub weight: Option<f64>,
+    pub weight_unit: String,
+    pub length: Option<f64>,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    pub dimension_unit: String,
+    pub image_slot: VariantImageSlot,
+}
+
+/// A variant's assigned image, with the IDs its assign/detach actions need.
+#[derive(Debug, Clone)]
+pub struct VariantImageSlot {
+    /// Numeric product ID.
+    pub product_id: String,
+    /// Numeric variant ID.
+    pub variant_id: String,
+    pub variant_title: String,
+    pub image: Option<ImageView>,
+    pub error: Option<String>,
+}
+
+impl VariantImageSlot {
+    fn new(product: &AdminProduct, variant: &AdminProductVariant) -> Self {
+        Self {
+            product_id: product.id.numeric_id().to_string(),
+            variant_id: variant.id.numeric_id().to_string(),
+            variant_title: variant.title.clone(),
+            image: variant.image.as_ref().map(ImageView::from),
+            error: None,
+        }
+    }
+
+    /// Whether `media_id` is the variant's current image.
+    #[must_use]
+    pub fn is_current(&self, media_id: &str) -> bool {
+        self.image
+            .as_ref()
+            .is_some_and(|image| image.id == media_id)
+    }
+}
+
+/// A product's publication status on every sales channel.
+#[derive(Debug, Clone)]
+pub struct ProductPublicationsCard {
+    /// Numeric product ID.
+    pub product_id: String,
+    pub publications: Vec<PublicationView>,
+    /// Only super admins can unpublish, as with collections.
+    pub can_unpublish: bool,
+    pub error: Option<String>,
+}
+
+impl ProductPublicationsCard {
+    fn new(product: &AdminProduct, all_publications: &[Publication], admin: &CurrentAdmin) -> Self {
+        Self {
+            product_id: product.id.numeric_id().to_string(),
+            publications: publication_views(all_publications, &product.publications),
+            can_unpublish: admin.role == AdminRole::SuperAdmin,
+            error: None,
+        }
+    }
+}
+
+/// Every sales channel, marked published if the product is published on it.
+fn publication_views(
+    all_publications: &[Publication],
+    current: &[ResourcePublication],
+) -> Vec<PublicationView> {
+    all_publications
+        .iter()
+        .map(|p| PublicationView {
+            id: p.id.clone(),
+            name: p.name.clone(),
+            is_published: current
+                .iter()
+                .any(|c| c.is_published && c.publication.id == p.id),
+        })
+        .collect()
+}
+
+/// Product economics (COGS and margins) view for templates.
+#[derive(Debug, Clone)]
+pub struct EconomicsView {
+    pub total_value_on_hand: String,
+    pub average_margin: String,
+    pub lowest_margin_variant: Option<String>,
+    pub variants: Vec<VariantCogsView>,
+}
+
+/// Cost and margin of one variant for templates.
+#[derive(Debug, Clone)]
+pub struct VariantCogsView {
+    pub variant_id: String,
+    pub inventory_item_id: String,
+    /// Raw cost amount, for the edit form.
+    pub cost_amount: Option<String>,
+    pub cost: String,
+    pub currency_code: String,
+    pub margin: String,
+    pub margin_class: &'static str,
+}
+
+impl EconomicsView {
+    /// Cost and margin of a variant, by variant ID.
+    #[must_use]
+    pub fn variant(&self, variant_id: &str) -> Option<&VariantCogsView> {
+        self.variants.iter().find(|v| v.variant_id == variant_id)
+    }
+}
+
+impl From<&CogsSummary> for EconomicsView {
+    fn from(cogs: &CogsSummary) -> Self {
+        let has_costs = cogs.variants.iter().any(|v| v.margin_percent.is_some());
+
+        Self {
+            total_value_on_hand: format_price(&cogs.total_value_on_hand),
+            average_margin: if has_costs {
+                format!("{:.1}%", cogs.average_margin_percent)
+            } else {
+                "-".to_string()
+            },
+            lowest_margin_variant: cogs.lowest_margin_variant.clone(),
+            variants: cogs
+                .variants
+                .iter()
+                .map(|v| VariantCogsView {
+                    variant_id: v.variant_id.to_string(),
+                    inventory_item_id: v.inventory_item_id.to_string(),
+                    cost_amount: v.unit_cost.as_ref().map(|c| c.amount.clone()),
+                    cost: v
+                        .unit_cost
+                        .as_ref()
+                        .map_or_else(|| "-".to_string(), format_price),
+                    currency_code: v.unit_cost.as_ref().map_or_else(
+                        || cogs.total_value_on_hand.currency_code.clone(),
+                        |c| c.currency_code.clone(),
+                    ),
+                    margin: v
+                        .margin_percent
+                        .map_or_else(|| "-".to_string(), |m| format!("{m:.1}%")),
+                    margin_class: match v.margin_percent {
+                        Some(m) if m < 0.0 => "text-destructive",
+                        Some(m) if m < 30.0 => "text-warning",
+                        Some(_) => "text-foreground",
+                        None => "text-muted-foreground",
+                    },
+                })
+                .collect(),
+        }
+    }
 }
 
 impl From<&AdminProduct> for ProductDetailView {
@@ -219,28 +437,33 @@ impl From<&AdminProduct> for ProductDetailView {
             .variants
             .iter()
             .map(|v| VariantView {
-                id: v.id.clone(),
+                id: v.id.to_string(),
                 title: v.title.clone(),
                 sku: v.sku.clone(),
                 barcode: v.barcode.clone(),
                 price: v.price.amount.clone(),
                 compare_at_price: v.compare_at_price.as_ref().map(|p| p.amount.clone()),
                 inventory_quantity: v.inventory_quantity,
+                weight: v.weight,
+                weight_unit: v
+                    .weight_unit
+                    .clone()
+                    .unwrap_or_else(|| "POUNDS".to_string()),
+                length: v.dimensions.as_ref().map(|d| d.length),
+                width: v.dimensions.as_ref().map(|d| d.width),
+                height: v.dimensions.as_ref().map(|d| d.height),
+                dimension_unit: v
+                    .dimensions
+                    .as_ref()
+                    .map_or_else(|| "in".to_string(), |d| d.unit.clone()),
+                image_slot: VariantImageSlot::new(product, v),
             })
             .collect();
 
-        let images: Vec<ImageView> = product
-            .images
-            .iter()
-            .map(|img| ImageView {
-                id: img.id.clone().unwrap_or_default(),
-                url: img.url.clone(),
-                alt: img.alt_text.clone(),
-            })
-            .collect();
+        let images: Vec<ImageView> = product.images.iter().map(ImageView::from).collect();
 
         Self {
-            id: product.id.clone(),
+            id: product.id.to_string(),
             title: product.title.clone(),
             description_html: product.description_html.clone(),
             status: status.to_string(),
@@ -254,6 +477,11 @@ impl From<&AdminProduct> for ProductDetailView {
             images,
             handle: product.handle.clone(),
             variants,
+            collections: product
+                .collections
+                .iter()
+                .map(ProductCollectionView::from)
+                .collect(),
             created_at: product.created_at.clone(),
             updated_at: product.updated_at.clone(),
         }
@@ -309,19 +537,54 @@ pub async fn show(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    // Ensure ID has the proper Shopify format
-    let product_id = if id.starts_with("gid://") {
-        id
-    } else {
-        format!("gid://shopify/Product/{id}")
+    let Ok(product_id) = ProductId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Product not found").into_response();
     };
 
-    match state.shopify().get_product(&product_id).await {
+    let (product_result, inventory_result, collections_result, publications_result) = tokio::join!(
+        state.shopify().get_product(&product_id),
+        state.shopify().get_product_inventory_summary(&product_id),
+        state
+            .shopify()
+            .get_collections(250, None, Some("collection_type:custom".to_string())),
+        state.shopify().get_publications(),
+    );
+
+    match product_result {
         Ok(Some(product)) => {
+            let inventory_summary = inventory_result
+                .inspect_err(|e| tracing::warn!("Failed to fetch inventory summary: {e}"))
+                .ok();
+            let economics = inventory_summary
+                .as_ref()
+                .map(|summary| EconomicsView::from(&CogsSummary::from(summary)));
+            let available_collections = collections_result
+                .inspect_err(|e| tracing::warn!("Failed to fetch collections: {e}"))
+                .map(|connection| {
+                    connection
+                        .collections
+                        .into_iter()
+                        .filter(|c| !product.collections.iter().any(|m| m.id == c.id))
+                        .map(|c| ProductCollectionView {
+                            id: c.id.to_string(),
+                            title: c.title,
+                            is_smart: false,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let all_publications = publications_result
+                .inspect_err(|e| tracing::warn!("Failed to fetch publications: {e}"))
+                .unwrap_or_default();
+
             let template = ProductShowTemplate {
                 admin_user: AdminUserView::from(&admin),
                 current_path: "/products".to_string(),
+                publications: ProductPublicationsCard::new(&product, &all_publications, &admin),
                 product: ProductDetailView::from(&product),
+                inventory_summary,
+                economics,
+                available_collections,
             };
 
             Html(template.render().unwrap_or_else(|e| {
@@ -412,10 +675,8 @@ pub async fn edit(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let product_id = if id.starts_with("gid://") {
-        id
-    } else {
-        format!("gid://shopify/Product/{id}")
+    let Ok(product_id) = ProductId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Product not found").into_response();
     };
 
     match state.shopify().get_product(&product_id).await {
@@ -449,10 +710,8 @@ pub async fn update(
     Path(id): Path<String>,
     Form(input): Form<ProductFormInput>,
 ) -> impl IntoResponse {
-    let product_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Product/{id}")
+    let Ok(product_id) = ProductId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Product not found").into_response();
     };
 
     // Fetch current product to merge values (workaround for graphql_client skip_none bug)
@@ -498,14 +757,14 @@ pub async fn update(
         .shopify()
         .update_product(
             &product_id,
-            ProductUpdateInput {
-                title: Some(&title),
-                description_html: Some(&description_html),
-                vendor: Some(&vendor),
-                product_type: Some(&product_type),
-                tags: Some(tags),
-                status: Some(&status),
-            },
+            ProductUpdateInput::builder()
+                .title(title.as_str())
+                .description_html(description_html.as_str())
+                .vendor(vendor.as_str())
+                .product_type(product_type.as_str())
+                .tags(tags)
+                .status(status.as_str())
+                .build(),
         )
         .await
     {
@@ -540,10 +799,8 @@ pub async fn archive(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let product_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Product/{id}")
+    let Ok(product_id) = ProductId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, Html("Product not found".to_string())).into_response();
     };
 
     // Fetch current product to merge values (workaround for graphql_client skip_none bug)
@@ -565,14 +822,14 @@ pub async fn archive(
         .shopify()
         .update_product(
             &product_id,
-            ProductUpdateInput {
-                title: Some(&current_product.title),
-                description_html: Some(&current_product.description_html),
-                vendor: Some(&current_product.vendor),
-                product_type: Some(&current_product.kind),
-                tags: Some(current_product.tags.clone()),
-                status: Some("ARCHIVED"),
-            },
+            ProductUpdateInput::builder()
+                .title(current_product.title.as_str())
+                .description_html(current_product.description_html.as_str())
+                .vendor(current_product.vendor.as_str())
+                .product_type(current_product.kind.as_str())
+                .tags(current_product.tags.clone())
+                .status("ARCHIVED")
+                .build(),
         )
         .await
     {
@@ -609,10 +866,8 @@ pub async fn delete(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let product_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Product/{id}")
+    let Ok(product_id) = ProductId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Product not found").into_response();
     };
 
     match state.shopify().delete_product(&product_id).await {
@@ -638,6 +893,79 @@ pub struct VariantFormInput {
     pub compare_at_price: Option<String>,
     pub sku: Option<String>,
     pub barcode: Option<String>,
+    pub weight: Option<String>,
+    pub weight_unit: Option<String>,
+    pub length: Option<String>,
+    pub width: Option<String>,
+    pub height: Option<String>,
+    pub dimension_unit: Option<String>,
+}
+
+impl VariantFormInput {
+    /// Parse the weight field, if filled in.
+    fn weight(&self) -> Result<Option<f64>, String> {
+        parse_measurement("Weight", self.weight.as_deref())
+    }
+
+    /// Parse the barcode field, if filled in.
+    ///
+    /// An empty barcode is kept so it clears the variant's barcode.
+    fn barcode(&self) -> Result<Option<Barcode>, String> {
+        self.barcode
+            .as_deref()
+            .map(Barcode::parse)
+            .transpose()
+            .map_err(|e| AdminShopifyError::from(e).to_string())
+    }
+
+    /// Parse the dimension fields.
+    ///
+    /// All three dimensions must be filled in together, or none of them.
+    fn dimensions(&self) -> Result<Option<VariantDimensions>, String> {
+        let length = parse_measurement("Length", self.length.as_deref())?;
+        let width = parse_measurement("Width", self.width.as_deref())?;
+        let height = parse_measurement("Height", self.height.as_deref())?;
+
+        match (length, width, height) {
+            (None, None, None) => Ok(None),
+            (Some(length), Some(width), Some(height)) => {
+                let unit = self.dimension_unit.as_deref().unwrap_or("in");
+                if !VariantDimensions::UNITS.contains(&unit) {
+                    return Err(format!("Unknown dimension unit: {unit}"));
+                }
+                Ok(Some(VariantDimensions {
+                    length,
+                    width,
+                    height,
+                    unit: unit.to_string(),
+                }))
+            }
+            _ => Err("Length, width and height must all be set".to_string()),
+        }
+    }
+}
+
+/// Parse an optional non-negative measurement from a form field.
+fn parse_measurement(label: &str, value: Option<&str>) -> Result<Option<f64>, String> {
+    let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+    match value.parse::<f64>() {
+        Ok(n) if n.is_finite() && n >= 0.0 => Ok(Some(n)),
+        _ => Err(format!("{label} must be a non-negative number")),
+    }
+}
+
+/// Render an HTMX error message for the variant form.
+fn variant_error_html(message: &str) -> String {
+    format!(
+        r#"<div class="p-4 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg">
+                    <div class="flex items-center gap-2 text-red-600 dark:text-red-400 text-sm">
+                        <i class="ph ph-warning-circle"></i>
+                        <span>Error: {message}</span>
+                    </div>
+                </div>"#
+    )
 }
 
 /// Update variant handler (HTMX).
@@ -648,28 +976,41 @@ pub async fn update_variant(
     Path((product_id, variant_id)): Path<(String, String)>,
     Form(input): Form<VariantFormInput>,
 ) -> impl IntoResponse {
-    let full_product_id = if product_id.starts_with("gid://") {
-        product_id.clone()
-    } else {
-        format!("gid://shopify/Product/{product_id}")
+    let (full_product_id, full_variant_id) = match (
+        ProductId::parse_admin_id(&product_id),
+        VariantId::parse_admin_id(&variant_id),
+    ) {
+        (Ok(product_id), Ok(variant_id)) => (product_id, variant_id),
+        (Err(e), _) | (_, Err(e)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html(variant_error_html(&e.to_string())),
+            )
+                .into_response();
+        }
     };
 
-    let full_variant_id = if variant_id.starts_with("gid://") {
-        variant_id.clone()
-    } else {
-        format!("gid://shopify/ProductVariant/{variant_id}")
+    let (barcode, weight, dimensions) = match (input.barcode(), input.weight(), input.dimensions())
+    {
+        (Ok(barcode), Ok(weight), Ok(dimensions)) => (barcode, weight, dimensions),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            return (StatusCode::BAD_REQUEST, Html(variant_error_html(&e))).into_response();
+        }
+    };
+
+    let update = VariantUpdateInput {
+        price: input.price.as_deref(),
+        compare_at_price: input.compare_at_price.as_deref(),
+        sku: input.sku.as_deref(),
+        barcode,
+        weight,
+        weight_unit: weight.and(input.weight_unit.as_deref()),
+        dimensions,
     };
 
     match state
         .shopify()
-        .update_variant(
-            &full_product_id,
-            &full_variant_id,
-            input.price.as_deref(),
-            input.compare_at_price.as_deref(),
-            input.sku.as_deref(),
-            input.barcode.as_deref(),
-        )
+        .update_variant(&full_product_id, &full_variant_id, update)
         .await
     {
         Ok(variant) => {
@@ -686,6 +1027,8 @@ pub async fn update_variant(
                         {}
                         {}
                         {}
+                        {}
+                        {}
                     </div>
                 </div>"#,
                 variant.price.amount,
@@ -704,20 +1047,470 @@ pub async fn update_variant(
                     .barcode
                     .as_ref()
                     .map_or_else(String::new, |b| format!("<div>Barcode: {b}</div>")),
+                variant.weight.map_or_else(String::new, |w| format!(
+                    "<div>Weight: {w} {}</div>",
+                    variant.weight_unit.as_deref().unwrap_or_default()
+                )),
+                variant
+                    .dimensions
+                    .as_ref()
+                    .map_or_else(String::new, |d| format!(
+                        "<div>Dimensions: {} &times; {} &times; {} {}</div>",
+                        d.length, d.width, d.height, d.unit
+                    )),
             );
             (StatusCode::OK, Html(html)).into_response()
         }
         Err(e) => {
             tracing::error!(variant_id = %full_variant_id, error = %e, "Failed to update variant");
-            let html = format!(
-                r#"<div class="p-4 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg">
-                    <div class="flex items-center gap-2 text-red-600 dark:text-red-400 text-sm">
-                        <i class="ph ph-warning-circle"></i>
-                        <span>Error: {e}</span>
-                    </div>
-                </div>"#
-            );
-            (StatusCode::BAD_REQUEST, Html(html)).into_response()
+            (
+                StatusCode::BAD_REQUEST,
+                Html(variant_error_html(&e.to_string())),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Form input for setting a variant's unit cost.
+#[derive(Debug, Deserialize)]
+pub struct VariantCostInput {
+    pub inventory_item_id: InventoryItemId,
+    pub cost: String,
+    pub currency_code: String,
+}
+
+/// Update variant unit cost handler (HTMX).
+///
+/// Refreshes the page on success so margins and the economics card update.
+#[instrument(skip(state))]
+pub async fn update_variant_cost(
+    RequireAdminAuth(_): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path((_, variant_id)): Path<(String, String)>,
+    Form(input): Form<VariantCostInput>,
+) -> impl IntoResponse {
+    match state
+        .shopify()
+        .update_inventory_item_cost(&input.inventory_item_id, &input.cost, &input.currency_code)
+        .await
+    {
+        Ok(()) => {
+            tracing::info!(variant_id = %variant_id, cost = %input.cost, "Variant cost updated");
+            (
+                StatusCode::OK,
+                [("HX-Refresh", "true")],
+                Html(String::new()),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!(variant_id = %variant_id, error = %e, "Failed to update variant cost");
+            (
+                StatusCode::BAD_REQUEST,
+                Html(format!(
+                    r#"<span class="text-destructive">Error: {e}</span>"#
+                )),
+            )
+                .into_response()
+        }
+    }
+}
+
+// ============================================================================
+// Variant Order
+// ============================================================================
+
+/// Variant position input for reordering.
+#[derive(Debug, Deserialize)]
+pub struct VariantPositionInput {
+    /// The variant ID (full GID or numeric).
+    pub id: String,
+    /// The new position (1-indexed).
+    pub position: i64,
+}
+
+/// Reorder product variants handler.
+#[instrument(skip(_admin, state))]
+pub async fn reorder_variants(
+    RequireAdminAuth(_admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(positions): Json<Vec<VariantPositionInput>>,
+) -> impl IntoResponse {
+    let Ok(product_id) = ProductId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Product not found").into_response();
+    };
+
+    let Ok(variant_positions) = positions
+        .iter()
+        .map(|p| VariantId::parse_admin_id(&p.id).map(|variant_id| (variant_id, p.position)))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return (StatusCode::BAD_REQUEST, "Invalid variant ID").into_response();
+    };
+
+    match state
+        .shopify()
+        .reorder_variants(&product_id, variant_positions)
+        .await
+    {
+        Ok(()) => {
+            tracing::info!(product_id = %product_id, "Variants reordered");
+            (StatusCode::OK, Json(serde_json::json!({"success": true}))).into_response()
+        }
+        Err(e) => {
+            tracing::error!(product_id = %product_id, error = %e, "Failed to reorder variants");
+            (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response()
+        }
+    }
+}
+
+// ============================================================================
+// Variant Image
+// ============================================================================
+
+/// Form input for assigning an image to, or detaching it from, a variant.
+#[derive(Debug, Deserialize)]
+pub struct VariantImageInput {
+    pub media_id: String,
+}
+
+/// Render an HTMX partial, logging render errors.
+fn render_partial(template: &impl Template) -> Html<String> {
+    Html(template.render().unwrap_or_else(|e| {
+        tracing::error!("Template render error: {}", e);
+        "Internal Server Error".to_string()
+    }))
+}
+
+/// Fetch a product and one of its variants for the variant image handlers.
+async fn product_and_variant(
+    state: &AppState,
+    product_id: &str,
+    variant_id: &str,
+) -> Result<(AdminProduct, AdminProductVariant), String> {
+    let product_id = ProductId::parse_admin_id(product_id).map_err(|e| e.to_string())?;
+    let variant_id = VariantId::parse_admin_id(variant_id).map_err(|e| e.to_string())?;
+
+    let product = match state.shopify().get_product(&product_id).await {
+        Ok(Some(product)) => product,
+        Ok(None) => return Err("Product not found".to_string()),
+        Err(e) => {
+            tracing::error!(product_id = %product_id, error = %e, "Failed to fetch product");
+            return Err(format!("Failed to load product: {e}"));
+        }
+    };
+    let variant = product
+        .variants
+        .iter()
+        .find(|v| v.id == variant_id)
+        .cloned()
+        .ok_or_else(|| "Variant not found".to_string())?;
+
+    Ok((product, variant))
+}
+
+/// Variant image picker handler (HTMX).
+///
+/// Lists the product's images; choosing one assigns it to the variant.
+#[instrument(skip(_admin, state))]
+pub async fn variant_image_picker(
+    RequireAdminAuth(_admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path((product_id, variant_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    match product_and_variant(&state, &product_id, &variant_id).await {
+        Ok((product, variant)) => render_partial(&VariantImagePickerTemplate {
+            slot: VariantImageSlot::new(&product, &variant),
+            images: product.images.iter().map(ImageView::from).collect(),
+        })
+        .into_response(),
+        Err(message) => (StatusCode::OK, Html(variant_error_html(&message))).into_response(),
+    }
+}
+
+/// Assign variant image handler (HTMX).
+///
+/// Shopify allows one image per variant, so the current image is detached
+/// first. Returns the updated image slot.
+#[instrument(skip(_admin, state))]
+pub async fn assign_variant_image(
+    RequireAdminAuth(_admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path((product_id, variant_id)): Path<(String, String)>,
+    Form(input): Form<VariantImageInput>,
+) -> impl IntoResponse {
+    let (product, variant) = match product_and_variant(&state, &product_id, &variant_id).await {
+        Ok(found) => found,
+        Err(message) => {
+            return (StatusCode::OK, Html(variant_error_html(&message))).into_response();
+        }
+    };
+    let mut slot = VariantImageSlot::new(&product, &variant);
+
+    let Some(image) = product
+        .images
+        .iter()
+        .find(|img| img.id.as_deref() == Some(input.media_id.as_str()))
+    else {
+        slot.error = Some("That image isn't on this product".to_string());
+        return render_partial(&VariantImageTemplate { slot }).into_response();
+    };
+    if slot.is_current(&input.media_id) {
+        return render_partial(&VariantImageTemplate { slot }).into_response();
+    }
+
+    let shopify = state.shopify();
+    if let Some(current) = slot.image.take()
+        && let Err(e) = shopify
+            .detach_image_from_variant(&product.id, &variant.id, &current.id)
+            .await
+    {
+        tracing::error!(variant_id = %variant.id, error = %e, "Failed to detach variant image");
+        slot.image = Some(current);
+        slot.error = Some(format!("Failed to replace image: {e}"));
+        return render_partial(&VariantImageTemplate { slot }).into_response();
+    }
+
+    match shopify
+        .assign_image_to_variant(&product.id, &variant.id, &input.media_id)
+        .await
+    {
+        Ok(()) => {
+            tracing::info!(variant_id = %variant.id, media_id = %input.media_id, "Variant image assigned");
+            slot.image = Some(ImageView::from(image));
+        }
+        Err(e) => {
+            tracing::error!(variant_id = %variant.id, error = %e, "Failed to assign variant image");
+            slot.error = Some(format!("Failed to assign image: {e}"));
+        }
+    }
+
+    render_partial(&VariantImageTemplate { slot }).into_response()
+}
+
+/// Detach variant image handler (HTMX).
+///
+/// The image stays on the product. Returns the updated image slot.
+#[instrument(skip(_admin, state))]
+pub async fn detach_variant_image(
+    RequireAdminAuth(_admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path((product_id, variant_id)): Path<(String, String)>,
+    Form(input): Form<VariantImageInput>,
+) -> impl IntoResponse {
+    let (product, variant) = match product_and_variant(&state, &product_id, &variant_id).await {
+        Ok(found) => found,
+        Err(message) => {
+            return (StatusCode::OK, Html(variant_error_html(&message))).into_response();
+        }
+    };
+    let mut slot = VariantImageSlot::new(&product, &variant);
+
+    match state
+        .shopify()
+        .detach_image_from_variant(&product.id, &variant.id, &input.media_id)
+        .await
+    {
+        Ok(()) => {
+            tracing::info!(variant_id = %variant.id, media_id = %input.media_id, "Variant image detached");
+            slot.image = None;
+        }
+        Err(e) => {
+            tracing::error!(variant_id = %variant.id, error = %e, "Failed to detach variant image");
+            slot.error = Some(format!("Failed to remove image: {e}"));
+        }
+    }
+
+    render_partial(&VariantImageTemplate { slot }).into_response()
+}
+
+// ============================================================================
+// Collection Membership
+// ============================================================================
+
+/// Form input for adding a product to, or removing it from, a collection.
+#[derive(Debug, Deserialize)]
+pub struct ProductCollectionInput {
+    pub collection_id: CollectionId,
+}
+
+/// Add product to a manual collection handler (HTMX).
+#[instrument(skip(_admin, state))]
+pub async fn add_to_collection(
+    RequireAdminAuth(_admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Form(input): Form<ProductCollectionInput>,
+) -> impl IntoResponse {
+    let Ok(product_id) = ProductId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Product not found").into_response();
+    };
+
+    match state
+        .shopify()
+        .add_products_to_collection(&input.collection_id, vec![product_id.clone()])
+        .await
+    {
+        Ok(()) => {
+            tracing::info!(product_id = %product_id, collection_id = %input.collection_id, "Product added to collection");
+            (
+                StatusCode::OK,
+                [("HX-Refresh", "true")],
+                Html(String::new()),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!(product_id = %product_id, error = %e, "Failed to add product to collection");
+            (
+                StatusCode::BAD_REQUEST,
+                Html(variant_error_html(&e.to_string())),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Remove product from a manual collection handler (HTMX).
+#[instrument(skip(_admin, state))]
+pub async fn remove_from_collection(
+    RequireAdminAuth(_admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Form(input): Form<ProductCollectionInput>,
+) -> impl IntoResponse {
+    let Ok(product_id) = ProductId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Product not found").into_response();
+    };
+
+    match state
+        .shopify()
+        .remove_products_from_collection(&input.collection_id, vec![product_id.clone()])
+        .await
+    {
+        Ok(()) => {
+            tracing::info!(product_id = %product_id, collection_id = %input.collection_id, "Product removed from collection");
+            (
+                StatusCode::OK,
+                [("HX-Refresh", "true")],
+                Html(String::new()),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!(product_id = %product_id, error = %e, "Failed to remove product from collection");
+            (
+                StatusCode::BAD_REQUEST,
+                Html(variant_error_html(&e.to_string())),
+            )
+                .into_response()
+        }
+    }
+}
+
+// ============================================================================
+// Sales Channels
+// ============================================================================
+
+/// Form input for toggling a product on a sales channel.
+#[derive(Debug, Deserialize)]
+pub struct ProductPublicationInput {
+    pub publication_id: String,
+    /// `true` to publish, `false` to unpublish.
+    pub published: bool,
+}
+
+/// Publish or unpublish a product on a sales channel (HTMX).
+///
+/// Only super admins can unpublish. Returns the updated sales channels
+/// card, with any error shown in it.
+#[instrument(skip(admin, state))]
+pub async fn update_publication(
+    RequireAdminAuth(admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Form(input): Form<ProductPublicationInput>,
+) -> impl IntoResponse {
+    let Ok(product_id) = ProductId::parse_admin_id(&id) else {
+        return (
+            StatusCode::OK,
+            Html(variant_error_html("Product not found")),
+        )
+            .into_response();
+    };
+
+    let error = if !input.published && admin.role != AdminRole::SuperAdmin {
+        tracing::warn!(
+            product_id = %product_id,
+            admin_id = %admin.id,
+            "Non-super-admin attempted to unpublish product from a channel"
+        );
+        Some("Only super admins can unpublish products from sales channels".to_string())
+    } else {
+        let publication_ids = [input.publication_id.clone()];
+        let result = if input.published {
+            state
+                .shopify()
+                .publish_product(&product_id, &publication_ids)
+                .await
+        } else {
+            state
+                .shopify()
+                .unpublish_product(&product_id, &publication_ids)
+                .await
+        };
+        match result {
+            Ok(()) => {
+                tracing::info!(
+                    product_id = %product_id,
+                    publication_id = %input.publication_id,
+                    published = input.published,
+                    "Product publication updated"
+                );
+                None
+            }
+            Err(e) => {
+                tracing::error!(
+                    product_id = %product_id,
+                    publication_id = %input.publication_id,
+                    error = %e,
+                    "Failed to update product publication"
+                );
+                Some(format!("Failed to update sales channel: {e}"))
+            }
+        }
+    };
+
+    let (product_result, publications_result) = tokio::join!(
+        state.shopify().get_product(&product_id),
+        state.shopify().get_publications(),
+    );
+    match (product_result, publications_result) {
+        (Ok(Some(product)), Ok(all_publications)) => {
+            let mut card = ProductPublicationsCard::new(&product, &all_publications, &admin);
+            card.error = error;
+            render_partial(&ProductPublicationsTemplate { card }).into_response()
+        }
+        (Ok(None), _) => (
+            StatusCode::OK,
+            Html(variant_error_html("Product not found")),
+        )
+            .into_response(),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!(product_id = %product_id, error = %e, "Failed to reload sales channels");
+            (
+                StatusCode::OK,
+                Html(variant_error_html(&format!(
+                    "Failed to load sales channels: {e}"
+                ))),
+            )
+                .into_response()
         }
     }
 }
@@ -787,10 +1580,8 @@ pub async fn reorder_images(
     Path(id): Path<String>,
     Json(moves): Json<Vec<ImageMoveInput>>,
 ) -> impl IntoResponse {
-    let product_id = if id.starts_with("gid://") {
-        id
-    } else {
-        format!("gid://shopify/Product/{id}")
+    let Ok(product_id) = ProductId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Product not found").into_response();
     };
 
     // Convert moves to the format expected by the Shopify client
@@ -960,10 +1751,8 @@ pub async fn upload_image(
     Path(id): Path<String>,
     multipart: axum::extract::Multipart,
 ) -> impl IntoResponse {
-    let product_id = if id.starts_with("gid://") {
-        id.clone()
-    } else {
-        format!("gid://shopify/Product/{id}")
+    let Ok(product_id) = ProductId::parse_admin_id(&id) else {
+        return (StatusCode::NOT_FOUND, "Product not found").into_response();
     };
 
     // Step 1: Extract file from multipart
@@ -0,0 +1,373 @@
+//! Inventory count reconciliation route handlers.
+//!
+//! # Routes
+//!
+//! ```text
+//! POST /inventory/reconciliation/start               - Start a count at a location
+//! POST /inventory/reconciliation/{session_id}/count  - Record a counted quantity
+//! GET  /inventory/reconciliation/{session_id}/review - Progress and discrepancies
+//! POST /inventory/reconciliation/{session_id}/apply  - Apply counted quantities to Shopify
+//! ```
+
+use askama::Template;
+use axum::{
+    Form, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::{get, post},
+};
+use naked_pineapple_core::LocationId;
+use serde::Deserialize;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    db::ReconciliationRepository,
+    filters,
+    middleware::auth::RequireAdminAuth,
+    models::{
+        CurrentAdmin, ReconciliationCount, ReconciliationProgress, ReconciliationSession,
+        ReconciliationStatus,
+    },
+    services::{ReconciliationError, ReconciliationService},
+    state::AppState,
+};
+
+use super::dashboard::AdminUserView;
+
+// =============================================================================
+// Forms and Query Parameters
+// =============================================================================
+
+/// Form for starting a count.
+#[derive(Debug, Deserialize)]
+pub struct StartForm {
+    pub location_id: LocationId,
+}
+
+/// Form for recording a counted quantity.
+#[derive(Debug, Deserialize)]
+pub struct CountForm {
+    pub sku: String,
+    pub counted_quantity: i32,
+}
+
+/// Query parameters for the review page.
+#[derive(Debug, Deserialize)]
+pub struct ReviewQuery {
+    /// Show counted SKUs that match the system quantity too.
+    #[serde(default)]
+    pub all: bool,
+    /// SKU that was just counted.
+    pub counted: Option<String>,
+}
+
+// =============================================================================
+// View Types
+// =============================================================================
+
+/// Reconciliation session summary for the inventory page.
+#[derive(Debug, Clone)]
+pub struct ReconciliationSessionView {
+    pub id: String,
+    pub location_name: String,
+    pub started_at: String,
+}
+
+impl ReconciliationSessionView {
+    /// Build a summary, naming the location from `locations`.
+    #[must_use]
+    pub fn new(session: &ReconciliationSession, locations: &[(String, String)]) -> Self {
+        Self {
+            id: session.session_id.to_string(),
+            location_name: location_name(&session.location_id, locations),
+            started_at: session.started_at.format("%b %d, %Y %H:%M").to_string(),
+        }
+    }
+}
+
+/// Counted SKU row for the review page.
+#[derive(Debug, Clone)]
+pub struct CountView {
+    pub sku: String,
+    pub system_quantity: i32,
+    pub counted_quantity: i32,
+    pub delta: i32,
+    pub delta_display: String,
+    pub is_discrepant: bool,
+    pub is_applied: bool,
+}
+
+impl From<&ReconciliationCount> for CountView {
+    fn from(count: &ReconciliationCount) -> Self {
+        let delta = count.delta.unwrap_or_default();
+        Self {
+            sku: count.sku.clone(),
+            system_quantity: count.system_quantity,
+            counted_quantity: count.counted_quantity.unwrap_or_default(),
+            delta,
+            delta_display: if delta > 0 {
+                format!("+{delta}")
+            } else {
+                delta.to_string()
+            },
+            is_discrepant: count.is_discrepant(),
+            is_applied: count.applied_at.is_some(),
+        }
+    }
+}
+
+// =============================================================================
+// Templates
+// =============================================================================
+
+/// Reconciliation review page template.
+#[derive(Template)]
+#[template(path = "inventory/reconciliation.html")]
+pub struct ReviewTemplate {
+    pub admin_user: AdminUserView,
+    pub current_path: String,
+    pub session_id: String,
+    pub location_name: String,
+    pub started_at: String,
+    pub is_completed: bool,
+    pub progress: ReconciliationProgress,
+    pub percent_counted: i64,
+    pub counts: Vec<CountView>,
+    pub show_all: bool,
+    pub notice: Option<String>,
+    pub error: Option<String>,
+}
+
+// =============================================================================
+// Route Handlers
+// =============================================================================
+
+/// POST `/inventory/reconciliation/start` - Start a count at a location.
+#[instrument(skip(_admin, state))]
+pub async fn start(
+    RequireAdminAuth(_admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Form(form): Form<StartForm>,
+) -> Response {
+    match ReconciliationService::new(state.pool(), state.shopify())
+        .start(&form.location_id)
+        .await
+    {
+        Ok(session) => Redirect::to(&format!(
+            "/inventory/reconciliation/{}/review",
+            session.session_id
+        ))
+        .into_response(),
+        Err(e) => {
+            tracing::error!(location_id = %form.location_id, error = %e, "Failed to start count");
+            (
+                error_status(&e),
+                Html(format!("Failed to start inventory count: {e}")),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// POST `/inventory/reconciliation/:session_id/count` - Record a counted quantity.
+#[instrument(skip(admin, state))]
+pub async fn count(
+    RequireAdminAuth(admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path(session_id): Path<Uuid>,
+    Form(form): Form<CountForm>,
+) -> Response {
+    let sku = form.sku.trim();
+    match ReconciliationService::new(state.pool(), state.shopify())
+        .record_count(session_id, sku, form.counted_quantity)
+        .await
+    {
+        Ok(()) => Redirect::to(&format!(
+            "/inventory/reconciliation/{session_id}/review?counted={}",
+            urlencoding::encode(sku)
+        ))
+        .into_response(),
+        Err(e) => {
+            let status = error_status(&e);
+            let page =
+                render_review(&state, &admin, session_id, false, None, Some(e.to_string())).await;
+            (status, page).into_response()
+        }
+    }
+}
+
+/// GET `/inventory/reconciliation/:session_id/review` - Progress and discrepancies.
+#[instrument(skip(admin, state))]
+pub async fn review(
+    RequireAdminAuth(admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path(session_id): Path<Uuid>,
+    Query(query): Query<ReviewQuery>,
+) -> Response {
+    let notice = query.counted.map(|sku| format!("Recorded count for {sku}"));
+    render_review(&state, &admin, session_id, query.all, notice, None).await
+}
+
+/// POST `/inventory/reconciliation/:session_id/apply` - Apply counted quantities to Shopify.
+#[instrument(skip(admin, state))]
+pub async fn apply(
+    RequireAdminAuth(admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path(session_id): Path<Uuid>,
+) -> Response {
+    match ReconciliationService::new(state.pool(), state.shopify())
+        .apply(session_id)
+        .await
+    {
+        Ok(result) if result.failed.is_empty() => {
+            let notice = format!(
+                "Applied {} adjustment{} to Shopify",
+                result.applied,
+                if result.applied == 1 { "" } else { "s" }
+            );
+            render_review(&state, &admin, session_id, false, Some(notice), None).await
+        }
+        Ok(result) => {
+            let failed: Vec<String> = result
+                .failed
+                .iter()
+                .map(|(sku, error)| format!("{sku}: {error}"))
+                .collect();
+            let error = format!(
+                "Applied {}, {} failed. Apply again to retry. {}",
+                result.applied,
+                result.failed.len(),
+                failed.join("; ")
+            );
+            let page = render_review(&state, &admin, session_id, false, None, Some(error)).await;
+            (StatusCode::BAD_GATEWAY, page).into_response()
+        }
+        Err(e) => {
+            let status = error_status(&e);
+            let page =
+                render_review(&state, &admin, session_id, false, None, Some(e.to_string())).await;
+            (status, page).into_response()
+        }
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Render the review page for a session.
+async fn render_review(
+    state: &AppState,
+    admin: &CurrentAdmin,
+    session_id: Uuid,
+    show_all: bool,
+    notice: Option<String>,
+    error: Option<String>,
+) -> Response {
+    let repo = ReconciliationRepository::new(state.pool());
+    let session = match repo.get_session(session_id).await {
+        Ok(Some(session)) => session,
+        Ok(None) => return Redirect::to("/inventory").into_response(),
+        Err(e) => {
+            tracing::error!(%session_id, error = %e, "Failed to load reconciliation session");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html(format!("Error: {e}")),
+            )
+                .into_response();
+        }
+    };
+
+    let (progress, counts, locations) = tokio::join!(
+        repo.progress(session_id),
+        repo.list_counted(session_id),
+        fetch_location_names(state)
+    );
+    let progress = progress.unwrap_or_else(|e| {
+        tracing::error!(%session_id, error = %e, "Failed to load reconciliation progress");
+        ReconciliationProgress::default()
+    });
+    let counts: Vec<CountView> = counts
+        .unwrap_or_else(|e| {
+            tracing::error!(%session_id, error = %e, "Failed to load reconciliation counts");
+            Vec::new()
+        })
+        .iter()
+        .filter(|count| show_all || count.is_discrepant())
+        .map(CountView::from)
+        .collect();
+
+    let template = ReviewTemplate {
+        admin_user: AdminUserView::from(admin),
+        current_path: "/inventory".to_string(),
+        session_id: session_id.to_string(),
+        location_name: location_name(&session.location_id, &locations),
+        started_at: session.started_at.format("%b %d, %Y %H:%M").to_string(),
+        is_completed: session.status == ReconciliationStatus::Completed,
+        progress,
+        percent_counted: progress.percent_counted(),
+        counts,
+        show_all,
+        notice,
+        error,
+    };
+
+    Html(template.render().unwrap_or_else(|e| {
+        tracing::error!("Template render error: {}", e);
+        "Internal Server Error".to_string()
+    }))
+    .into_response()
+}
+
+/// `(id, name)` of every Shopify location.
+async fn fetch_location_names(state: &AppState) -> Vec<(String, String)> {
+    match state.shopify().get_locations().await {
+        Ok(conn) => conn
+            .locations
+            .into_iter()
+            .map(|location| (location.id.into(), location.name))
+            .collect(),
+        Err(e) => {
+            tracing::error!("Failed to fetch locations: {e}");
+            Vec::new()
+        }
+    }
+}
+
+/// Name of a location, or its ID if unknown.
+fn location_name(location_id: &str, locations: &[(String, String)]) -> String {
+    locations
+        .iter()
+        .find(|(id, _)| id == location_id)
+        .map_or_else(|| location_id.to_string(), |(_, name)| name.clone())
+}
+
+/// HTTP status for a reconciliation error.
+const fn error_status(error: &ReconciliationError) -> StatusCode {
+    match error {
+        ReconciliationError::NotFound => StatusCode::NOT_FOUND,
+        ReconciliationError::Completed | ReconciliationError::DuplicateSkus(_) => {
+            StatusCode::CONFLICT
+        }
+        ReconciliationError::UnknownSku(_) | ReconciliationError::NegativeQuantity => {
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+        ReconciliationError::Repository(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        ReconciliationError::Shopify(_) => StatusCode::BAD_GATEWAY,
+    }
+}
+
+// =============================================================================
+// Router
+// =============================================================================
+
+/// Build the reconciliation router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/inventory/reconciliation/start", post(start))
+        .route("/inventory/reconciliation/{session_id}/count", post(count))
+        .route("/inventory/reconciliation/{session_id}/review", get(review))
+        .route("/inventory/reconciliation/{session_id}/apply", post(apply))
+}
@@ -0,0 +1,155 @@
+//! Customer segment route handlers.
+//!
+//! # Routes
+//!
+//! ```text
+//! GET /segments/:id/members - Customers in a segment (paginated with `?cursor=`)
+//! ```
+
+use askama::Template;
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+    routing::get,
+};
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::{
+    filters,
+    middleware::auth::RequireAdminAuth,
+    shopify::types::{CustomerSegmentMember, Money},
+    state::AppState,
+};
+
+use super::dashboard::AdminUserView;
+
+/// Members fetched per page.
+const MEMBERS_PAGE_SIZE: i64 = 50;
+
+/// Query parameters for the members page.
+#[derive(Debug, Deserialize)]
+pub struct MembersQuery {
+    pub cursor: Option<String>,
+}
+
+/// Segment member row for the template.
+#[derive(Debug, Clone)]
+pub struct SegmentMemberView {
+    pub id: String,
+    pub name: String,
+    pub email: Option<String>,
+    pub orders_count: i64,
+    pub amount_spent: String,
+}
+
+impl From<&CustomerSegmentMember> for SegmentMemberView {
+    fn from(member: &CustomerSegmentMember) -> Self {
+        Self {
+            id: member.id.clone(),
+            name: member.display_name.clone(),
+            email: member.email.clone(),
+            orders_count: member.orders_count,
+            amount_spent: member
+                .amount_spent
+                .as_ref()
+                .map_or_else(|| "-".to_string(), format_price),
+        }
+    }
+}
+
+/// Segment members page template.
+#[derive(Template)]
+#[template(path = "segments/members.html")]
+pub struct SegmentMembersTemplate {
+    pub admin_user: AdminUserView,
+    pub current_path: String,
+    pub segment_id: String,
+    pub segment_name: String,
+    pub segment_query: String,
+    pub members: Vec<SegmentMemberView>,
+    pub total_count: i64,
+    pub has_next_page: bool,
+    pub next_cursor: Option<String>,
+    pub error: Option<String>,
+}
+
+/// GET /segments/:id/members - Customers in a segment.
+#[instrument(skip(admin, state))]
+pub async fn members(
+    RequireAdminAuth(admin): RequireAdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<MembersQuery>,
+) -> Response {
+    let gid = format!("gid://shopify/Segment/{id}");
+
+    let segment = match state.shopify().get_customer_segment(&gid).await {
+        Ok(Some(segment)) => segment,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Segment not found").into_response(),
+        Err(e) => {
+            tracing::error!(segment_id = %gid, error = %e, "Failed to fetch segment");
+            return (StatusCode::BAD_GATEWAY, "Failed to load segment").into_response();
+        }
+    };
+
+    let (members, total_count, has_next_page, next_cursor, error, status) = match state
+        .shopify()
+        .get_customer_segment_members(&gid, MEMBERS_PAGE_SIZE, query.cursor)
+        .await
+    {
+        Ok(conn) => (
+            conn.members.iter().map(SegmentMemberView::from).collect(),
+            conn.total_count,
+            conn.page_info.has_next_page,
+            conn.page_info.end_cursor,
+            None,
+            StatusCode::OK,
+        ),
+        Err(e) => {
+            tracing::error!(segment_id = %gid, error = %e, "Failed to fetch segment members");
+            (
+                Vec::new(),
+                0,
+                false,
+                None,
+                Some(format!("Failed to load segment members: {e}")),
+                StatusCode::BAD_GATEWAY,
+            )
+        }
+    };
+
+    let template = SegmentMembersTemplate {
+        admin_user: AdminUserView::from(&admin),
+        current_path: "/customers".to_string(),
+        segment_id: id,
+        segment_name: segment.name,
+        segment_query: segment.query,
+        members,
+        total_count,
+        has_next_page,
+        next_cursor,
+        error,
+    };
+
+    let page = Html(template.render().unwrap_or_else(|e| {
+        tracing::error!("Template render error: {}", e);
+        "Internal Server Error".to_string()
+    }));
+    (status, page).into_response()
+}
+
+/// Format a Shopify Money type as a price string.
+fn format_price(money: &Money) -> String {
+    money.amount.parse::<f64>().map_or_else(
+        |_| format!("${}", money.amount),
+        |amount| format!("${amount:.2}"),
+    )
+}
+
+/// Build the customer segments router.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/segments/{id}/members", get(members))
+}
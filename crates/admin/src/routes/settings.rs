@@ -1,6 +1,7 @@
 //! Admin settings routes.
 //!
-//! Provides profile management and passkey settings for admin users.
+//! Provides profile management and passkey settings for admin users, and
+//! Claude API usage reporting for super admins.
 
 use askama::Template;
 use axum::{
@@ -10,16 +11,17 @@ use axum::{
     response::{Html, IntoResponse, Redirect, Response},
     routing::{delete, get, post},
 };
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
 use tower_sessions::Session;
 use tracing::instrument;
 
 use naked_pineapple_core::{AdminCredentialId, Email};
 
-use crate::db::AdminUserRepository;
+use crate::claude::{BUDGET_ALERT_PERCENT, start_of_month};
+use crate::db::{AdminUserRepository, ClaudeUsageRepository, ReadPool};
 use crate::filters;
-use crate::middleware::{RequireAdminAuth, set_current_admin};
+use crate::middleware::{RequireAdminAuth, RequireSuperAdmin, set_current_admin};
 use crate::models::CurrentAdmin;
 use crate::services::{AdminAuthService, EmailService, generate_verification_code};
 use crate::state::AppState;
@@ -58,11 +60,50 @@ pub struct SettingsTemplate {
     pub error_message: Option<String>,
 }
 
+/// Daily Claude usage row for template rendering.
+#[derive(Debug, Clone)]
+pub struct DailyUsageView {
+    pub day: String,
+    pub email: String,
+    pub requests: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost: String,
+}
+
+/// Month-to-date Claude usage for one admin, for template rendering.
+#[derive(Debug, Clone)]
+pub struct MonthlyUsageView {
+    pub email: String,
+    pub total_tokens: i64,
+    pub cost: String,
+    /// Percent of the monthly token budget used, if a budget is configured.
+    pub budget_percent: Option<u64>,
+    /// Whether usage has reached the Slack alert threshold.
+    pub near_budget: bool,
+}
+
+/// Claude usage page template.
+#[derive(Template)]
+#[template(path = "settings/claude_usage.html")]
+pub struct ClaudeUsageTemplate {
+    pub admin_user: AdminUserView,
+    pub current_path: String,
+    pub monthly_token_budget: Option<u64>,
+    pub monthly: Vec<MonthlyUsageView>,
+    pub daily: Vec<DailyUsageView>,
+    pub error_message: Option<String>,
+}
+
+/// Days of history shown on the Claude usage page.
+const CLAUDE_USAGE_DAYS: i64 = 30;
+
 /// Build the settings router.
 pub fn router() -> Router<AppState> {
     Router::new()
         // Page
         .route("/settings", get(settings_page))
+        .route("/settings/claude-usage", get(claude_usage_page))
         // Profile API
         .route("/api/settings/profile", post(update_profile))
         // Email change API
@@ -239,6 +280,87 @@ async fn settings_page(
     .into_response()
 }
 
+// =============================================================================
+// Claude Usage Page
+// =============================================================================
+
+/// Render Claude API usage per admin user for the last 30 days.
+///
+/// GET /settings/claude-usage
+#[instrument(skip(state, pool))]
+async fn claude_usage_page(
+    State(state): State<AppState>,
+    ReadPool(pool): ReadPool,
+    RequireSuperAdmin(admin): RequireSuperAdmin,
+) -> Response {
+    let now = Utc::now();
+    let budget = state.config().claude().monthly_token_budget;
+    let repo = ClaudeUsageRepository::new(&pool);
+
+    let (daily, monthly) = tokio::join!(
+        repo.daily_usage_since(now - Duration::days(CLAUDE_USAGE_DAYS)),
+        repo.usage_by_user_since(start_of_month(now)),
+    );
+
+    let error_message = daily
+        .as_ref()
+        .err()
+        .or_else(|| monthly.as_ref().err())
+        .map(|e| {
+            tracing::error!(error = %e, "Failed to load Claude usage");
+            "Failed to load Claude usage.".to_owned()
+        });
+
+    let daily = daily
+        .unwrap_or_default()
+        .into_iter()
+        .map(|row| DailyUsageView {
+            day: row.day.format("%b %d, %Y").to_string(),
+            email: row.admin_user_email,
+            requests: row.requests,
+            input_tokens: row.input_tokens,
+            output_tokens: row.output_tokens,
+            cost: format!("${:.2}", row.estimated_cost_usd),
+        })
+        .collect();
+
+    let monthly = monthly
+        .unwrap_or_default()
+        .into_iter()
+        .map(|row| {
+            let budget_percent = budget.map(|budget| {
+                u64::try_from(row.total_tokens)
+                    .unwrap_or_default()
+                    .saturating_mul(100)
+                    / budget.max(1)
+            });
+            MonthlyUsageView {
+                email: row.admin_user_email,
+                total_tokens: row.total_tokens,
+                cost: format!("${:.2}", row.estimated_cost_usd),
+                near_budget: budget_percent.is_some_and(|percent| percent >= BUDGET_ALERT_PERCENT),
+                budget_percent,
+            }
+        })
+        .collect();
+
+    let template = ClaudeUsageTemplate {
+        admin_user: AdminUserView::from(&admin),
+        current_path: "/settings/claude-usage".to_owned(),
+        monthly_token_budget: budget,
+        monthly,
+        daily,
+        error_message,
+    };
+
+    Html(
+        template
+            .render()
+            .unwrap_or_else(|e| format!("Template error: {e}")),
+    )
+    .into_response()
+}
+
 // =============================================================================
 // Profile API
 // =============================================================================
@@ -20,6 +20,7 @@ use crate::db::ShopifyTokenRepository;
 use crate::filters;
 use crate::middleware::require_super_admin;
 use crate::models::CurrentAdmin;
+use crate::shopify::ShopLocale;
 use crate::state::AppState;
 
 use super::dashboard::AdminUserView;
@@ -38,6 +39,7 @@ const ADMIN_SCOPES: &[&str] = &[
     "read_fulfillments",
     "write_fulfillments",
     "read_publications",
+    "read_locales",
     "read_reports",
     "read_marketing_activities",
     "read_shopify_payments_payouts",
@@ -56,6 +58,8 @@ pub struct ShopifySettingsTemplate {
     pub connected: bool,
     pub shop: String,
     pub scopes: Vec<String>,
+    /// Languages enabled on the store (empty if not connected or unavailable)
+    pub locales: Vec<ShopLocale>,
     pub success_message: Option<String>,
     pub error_message: Option<String>,
 }
@@ -182,6 +186,18 @@ async fn settings_page(
     let connected = token.is_some();
     let scopes = token.map_or_else(Vec::new, |t| t.scopes);
 
+    let locales = if connected {
+        match state.shop_locales().await {
+            Ok(locales) => locales.to_vec(),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load shop locales");
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
     // Map query params to user-friendly messages
     let success_message = params.success.as_deref().map(|s| match s {
         "connected" => "Successfully connected to Shopify!".to_string(),
@@ -206,6 +222,7 @@ async fn settings_page(
         connected,
         shop,
         scopes,
+        locales,
         success_message,
         error_message,
     };
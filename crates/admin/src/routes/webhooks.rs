@@ -0,0 +1,217 @@
+//! Inbound webhook handlers for third-party services.
+//!
+//! # Shopify
+//!
+//! Shopify webhooks are routed through
+//! [`verify_shopify_webhook`](crate::middleware::verify_shopify_webhook), so
+//! handlers only see payloads signed with the app's client secret.
+//!
+//! # `ShipHero` fulfillment
+//!
+//! `ShipHero` posts a "Shipment Update" webhook when a warehouse order ships.
+//! The handler verifies the signature, responds immediately, and applies the
+//! tracking details to the Shopify order in the background:
+//!
+//! 1. Map the `ShipHero` order ID to a Shopify order ID, saving the mapping
+//!    from the payload's partner order ID the first time an order is seen
+//! 2. Update tracking on the order's most recent Shopify fulfillment
+//! 3. Post a shipment notification to Slack (if configured)
+
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::post,
+};
+use naked_pineapple_core::OrderId;
+use secrecy::{ExposeSecret, SecretString};
+use serde_json::json;
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::db::ShipHeroOrderMappingRepository;
+use crate::error::AppError;
+use crate::middleware::verify_shopify_webhook;
+use crate::shiphero::webhooks::{ShipmentFulfillment, ShipmentUpdateWebhook};
+use crate::shopify::verify_webhook_signature;
+use crate::state::AppState;
+
+/// Header carrying the base64 HMAC-SHA256 signature of a `ShipHero` webhook.
+const SHIPHERO_SIGNATURE_HEADER: &str = "X-Shiphero-Hmac-Sha256";
+
+/// Create inbound webhook routes.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/webhooks/shiphero/fulfillment", post(shiphero_fulfillment))
+}
+
+/// Create Shopify webhook routes, verified with the app's `client_secret`.
+pub fn shopify_router(client_secret: SecretString) -> Router<AppState> {
+    Router::new()
+        .route("/webhooks/shopify", post(shopify_webhook))
+        .route_layer(axum::middleware::from_fn_with_state(
+            client_secret,
+            verify_shopify_webhook,
+        ))
+}
+
+/// Acknowledge a verified Shopify webhook.
+///
+/// Shopify retries webhooks that aren't answered with a 2xx within five
+/// seconds, so this only records the delivery.
+#[instrument(skip_all)]
+async fn shopify_webhook(headers: HeaderMap, body: Bytes) -> StatusCode {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+    };
+
+    info!(
+        topic = header("X-Shopify-Topic"),
+        shop = header("X-Shopify-Shop-Domain"),
+        webhook_id = header("X-Shopify-Webhook-Id"),
+        bytes = body.len(),
+        "Received Shopify webhook"
+    );
+
+    StatusCode::OK
+}
+
+/// Handle a `ShipHero` "Shipment Update" webhook.
+///
+/// Returns the acknowledgement `ShipHero` expects as soon as the payload is
+/// verified and parsed; the Shopify update runs in a background task.
+#[instrument(skip(state, headers, body))]
+async fn shiphero_fulfillment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, AppError> {
+    let secret = state
+        .config()
+        .shiphero_webhook_secret()
+        .ok_or_else(|| AppError::Internal("ShipHero webhook secret not configured".into()))?;
+
+    let signature = headers
+        .get(SHIPHERO_SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| AppError::Unauthorized("Missing signature header".into()))?;
+
+    if !verify_webhook_signature(secret.expose_secret(), &body, signature) {
+        warn!("ShipHero webhook signature mismatch");
+        return Err(AppError::Unauthorized("Invalid signature".into()));
+    }
+
+    let webhook: ShipmentUpdateWebhook = serde_json::from_slice(&body)
+        .map_err(|e| AppError::BadRequest(format!("Failed to parse payload: {e}")))?;
+
+    if webhook.test {
+        debug!("Ignoring ShipHero test webhook");
+    } else {
+        tokio::spawn(async move {
+            let shiphero_order_id = webhook.fulfillment.order_id.clone();
+            if let Err(e) = process_shipment(&state, webhook.fulfillment).await {
+                error!(
+                    shiphero_order_id = %shiphero_order_id,
+                    error = %e,
+                    "Failed to process ShipHero shipment"
+                );
+            }
+        });
+    }
+
+    Ok(Json(json!({ "code": "200", "Status": "Success" })))
+}
+
+/// Apply a shipment's tracking details to the matching Shopify order.
+async fn process_shipment(state: &AppState, shipment: ShipmentFulfillment) -> Result<(), AppError> {
+    let Some(order_id) = shopify_order_id(state, &shipment).await? else {
+        warn!(
+            shiphero_order_id = %shipment.order_id,
+            "No Shopify order mapped to ShipHero order"
+        );
+        return Ok(());
+    };
+
+    let order = state
+        .shopify()
+        .get_order(&order_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Order {order_id}")))?;
+
+    let Some(fulfillment) = order
+        .fulfillments
+        .iter()
+        .max_by(|a, b| a.created_at.cmp(&b.created_at))
+    else {
+        warn!(order = %order.name, "Shopify order has no fulfillment to update");
+        return Ok(());
+    };
+
+    state
+        .shopify()
+        .update_fulfillment_tracking(
+            &fulfillment.id,
+            Some(&shipment.shipping_carrier),
+            Some(&shipment.tracking_number),
+            shipment.tracking_url.as_deref(),
+        )
+        .await?;
+
+    info!(
+        order = %order.name,
+        carrier = %shipment.shipping_carrier,
+        shipped_at = ?shipment.shipped_at,
+        "Updated fulfillment tracking from ShipHero"
+    );
+
+    if let Some(slack) = state.slack()
+        && let Err(e) = slack
+            .send_fulfillment_notification(
+                &order.name,
+                &shipment.shipping_carrier,
+                &shipment.tracking_number,
+                shipment.tracking_url.as_deref(),
+            )
+            .await
+    {
+        warn!(error = %e, "Failed to send fulfillment notification");
+    }
+
+    Ok(())
+}
+
+/// Look up the Shopify order ID for a shipment's `ShipHero` order.
+///
+/// Falls back to the payload's partner order ID (the numeric Shopify order
+/// ID) and saves it as the mapping for later shipments.
+async fn shopify_order_id(
+    state: &AppState,
+    shipment: &ShipmentFulfillment,
+) -> Result<Option<OrderId>, AppError> {
+    let repo = ShipHeroOrderMappingRepository::new(state.pool());
+
+    if let Some(order_id) = repo.find_shopify_order_id(&shipment.order_id).await? {
+        return order_id
+            .parse::<OrderId>()
+            .map(Some)
+            .map_err(|e| AppError::Internal(format!("Invalid saved order mapping: {e}")));
+    }
+
+    let Some(partner_order_id) = shipment.partner_order_id.as_deref() else {
+        return Ok(None);
+    };
+
+    let order_id = if partner_order_id.starts_with("gid://") {
+        partner_order_id.parse()
+    } else {
+        OrderId::from_numeric(partner_order_id)
+    }
+    .map_err(|e| AppError::BadRequest(format!("Invalid partner order ID: {e}")))?;
+    repo.save_mapping(&shipment.order_id, order_id.as_str())
+        .await?;
+
+    Ok(Some(order_id))
+}
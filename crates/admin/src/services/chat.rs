@@ -17,7 +17,6 @@
 
 use std::time::Instant;
 
-use askama::Template;
 use async_stream::stream;
 use chrono::{Datelike, Duration, Local, TimeZone, Utc};
 use futures::{Stream, StreamExt};
@@ -29,18 +28,16 @@ use uuid::Uuid;
 use naked_pineapple_core::{AdminUserId, ChatMessageId, ChatRole, ChatSessionId};
 
 use crate::claude::{
-    ClaudeClient, ClaudeError, ContentBlock, ContentBlockDelta, ContentBlockStart, Message,
-    MessageContent, StopReason, StreamEvent, Tool, ToolExecutor, ToolResult, Usage,
-    all_shopify_tools,
+    ClaudeClient, ClaudeError, ClaudeUsageTracker, ContentBlock, ContentBlockDelta,
+    ContentBlockStart, Message, MessageContent, PromptTemplate, StopReason, StreamEvent, Tool,
+    ToolConfirmations, ToolExecutor, ToolResult, Usage, all_shopify_tools,
 };
 use crate::db::{ChatRepository, RepositoryError};
 use crate::models::chat::{ApiInteraction, ChatMessage, ChatSession};
 use crate::shopify::AdminClient;
 
-/// System prompt template for the Claude chat assistant.
-#[derive(Template)]
-#[template(path = "claude/system_prompt.txt")]
-struct SystemPromptTemplate {
+/// Date context filled into the chat assistant's system prompt template.
+struct SystemPromptContext {
     /// Current timestamp with timezone.
     current_timestamp: String,
     /// Timezone offset (e.g., "-07:00").
@@ -67,8 +64,28 @@ struct SystemPromptTemplate {
     last_30_days_start_date: String,
 }
 
-/// Render the system prompt template with current date context.
-fn render_system_prompt() -> String {
+impl SystemPromptContext {
+    /// Placeholder names and values for [`PromptTemplate::render`].
+    fn variables(&self) -> [(&'static str, &str); 12] {
+        [
+            ("current_timestamp", &self.current_timestamp),
+            ("tz_offset", &self.tz_offset),
+            ("today_date", &self.today_date),
+            ("yesterday_date", &self.yesterday_date),
+            ("this_week_start_date", &self.this_week_start_date),
+            ("last_week_start_date", &self.last_week_start_date),
+            ("last_week_end_date", &self.last_week_end_date),
+            ("this_month_start_date", &self.this_month_start_date),
+            ("last_month_start_date", &self.last_month_start_date),
+            ("last_month_end_date", &self.last_month_end_date),
+            ("last_7_days_start_date", &self.last_7_days_start_date),
+            ("last_30_days_start_date", &self.last_30_days_start_date),
+        ]
+    }
+}
+
+/// Render a system prompt template with current date context.
+fn render_system_prompt(prompt: &PromptTemplate) -> String {
     let now = Local::now();
     let tz_offset = now.format("%:z").to_string();
     let current_timestamp = now.format("%Y-%m-%d %H:%M:%S %z").to_string();
@@ -122,7 +139,7 @@ fn render_system_prompt() -> String {
     let thirty_days_ago = now - Duration::days(30);
     let last_30_days_start_date = thirty_days_ago.format("%Y-%m-%d").to_string();
 
-    SystemPromptTemplate {
+    let context = SystemPromptContext {
         current_timestamp,
         tz_offset,
         today_date,
@@ -135,9 +152,8 @@ fn render_system_prompt() -> String {
         last_month_end_date,
         last_7_days_start_date,
         last_30_days_start_date,
-    }
-    .render()
-    .unwrap_or_else(|_| String::from("You are a helpful assistant."))
+    };
+    prompt.render(&context.variables())
 }
 
 /// Maximum number of tool use iterations to prevent infinite loops.
@@ -276,16 +292,23 @@ pub struct ChatService<'a> {
     pool: &'a PgPool,
     claude: &'a ClaudeClient,
     shopify: &'a AdminClient,
+    confirmations: &'a ToolConfirmations,
 }
 
 impl<'a> ChatService<'a> {
     /// Create a new chat service.
     #[must_use]
-    pub const fn new(pool: &'a PgPool, claude: &'a ClaudeClient, shopify: &'a AdminClient) -> Self {
+    pub const fn new(
+        pool: &'a PgPool,
+        claude: &'a ClaudeClient,
+        shopify: &'a AdminClient,
+        confirmations: &'a ToolConfirmations,
+    ) -> Self {
         Self {
             pool,
             claude,
             shopify,
+            confirmations,
         }
     }
 
@@ -351,14 +374,19 @@ impl<'a> ChatService<'a> {
     /// 5. Loop until Claude responds with text
     /// 6. Save and return all new messages
     ///
+    /// The system prompt is rendered from `prompt`, and token usage of every
+    /// Claude response is recorded with `usage` against that prompt version.
+    ///
     /// # Errors
     ///
     /// Returns an error if any step fails.
-    #[instrument(skip(self, user_message), fields(session_id = %session_id))]
+    #[instrument(skip(self, user_message, prompt, usage), fields(session_id = %session_id))]
     pub async fn send_message(
         &self,
         session_id: ChatSessionId,
         user_message: &str,
+        prompt: &PromptTemplate,
+        usage: &ClaudeUsageTracker,
     ) -> Result<Vec<ChatMessage>, ChatError> {
         let repo = ChatRepository::new(self.pool);
 
@@ -383,10 +411,11 @@ impl<'a> ChatService<'a> {
 
         // Get available tools and system prompt
         let tools = all_shopify_tools();
-        let system_prompt = render_system_prompt();
+        let system_prompt = render_system_prompt(prompt);
 
         // Tool use loop
-        let executor = ToolExecutor::new(self.shopify);
+        let executor =
+            ToolExecutor::new(self.shopify).with_confirmations(self.confirmations.turn(session_id));
         let mut iterations = 0;
 
         loop {
@@ -406,6 +435,15 @@ impl<'a> ChatService<'a> {
                 )
                 .await?;
 
+            usage
+                .record(
+                    Some(session_id),
+                    Some(prompt),
+                    &response.model,
+                    &response.usage,
+                )
+                .await;
+
             info!(
                 stop_reason = ?response.stop_reason,
                 content_blocks = response.content.len(),
@@ -413,8 +451,7 @@ impl<'a> ChatService<'a> {
             );
 
             // Process response content
-            let mut has_tool_use = false;
-            let mut tool_results: Vec<ContentBlock> = Vec::new();
+            let mut tool_uses: Vec<AccumulatedToolUse> = Vec::new();
 
             for block in &response.content {
                 match block {
@@ -427,12 +464,11 @@ impl<'a> ChatService<'a> {
                         new_messages.push(msg);
                     }
                     ContentBlock::ToolUse { id, name, input } => {
-                        has_tool_use = true;
-                        let (tool_use_msg, tool_result_msg, tool_result_block) =
-                            execute_tool_use(&repo, &executor, session_id, id, name, input).await?;
-                        new_messages.push(tool_use_msg);
-                        new_messages.push(tool_result_msg);
-                        tool_results.push(tool_result_block);
+                        tool_uses.push(AccumulatedToolUse {
+                            id: id.clone(),
+                            name: name.clone(),
+                            input: input.clone(),
+                        });
                     }
                     ContentBlock::ToolResult { .. } => {
                         // Should not appear in response
@@ -440,6 +476,12 @@ impl<'a> ChatService<'a> {
                 }
             }
 
+            // Run every tool call from this turn together
+            let has_tool_use = !tool_uses.is_empty();
+            let (tool_messages, tool_results) =
+                execute_tool_uses(&repo, &executor, session_id, &tool_uses).await?;
+            new_messages.extend(tool_messages);
+
             // If Claude wants to use tools, add results and continue
             if has_tool_use && response.stop_reason == Some(StopReason::ToolUse) {
                 // Add assistant message with tool use to conversation
@@ -481,20 +523,27 @@ impl<'a> ChatService<'a> {
     ///
     /// * `session_id` - The chat session ID
     /// * `user_message` - The user's message text
+    /// * `prompt` - System prompt template
+    /// * `usage` - Records token usage of every Claude response
     ///
     /// # Returns
     ///
     /// A stream of `ChatStreamEvent` items for real-time UI updates.
-    #[instrument(skip(self, user_message), fields(session_id = %session_id))]
+    #[instrument(skip(self, user_message, prompt, usage), fields(session_id = %session_id))]
     pub fn send_message_streaming(
         &self,
         session_id: ChatSessionId,
         user_message: String,
+        prompt: PromptTemplate,
+        usage: ClaudeUsageTracker,
     ) -> impl Stream<Item = ChatStreamEvent> + Send + 'static {
         stream_chat_message(
             self.pool.clone(),
             self.claude.clone(),
             self.shopify.clone(),
+            self.confirmations.clone(),
+            prompt,
+            usage,
             session_id,
             user_message,
         )
@@ -512,21 +561,45 @@ impl<'a> ChatService<'a> {
 /// * `pool` - Database connection pool (cheap to clone, uses Arc internally)
 /// * `claude` - Claude API client (cheap to clone, uses Arc internally)
 /// * `shopify` - Shopify Admin API client (cheap to clone, uses Arc internally)
+/// * `confirmations` - Confirmation tokens issued by tools (cheap to clone)
+/// * `prompt` - System prompt template
+/// * `usage` - Records token usage of every Claude response
 /// * `session_id` - The chat session ID
 /// * `user_message` - The user's message text
 ///
 /// # Returns
 ///
 /// A stream of `ChatStreamEvent` items for real-time UI updates.
-#[instrument(skip(pool, claude, shopify, user_message), fields(session_id = %session_id))]
+///
+/// # Allow: `too_many_arguments`
+///
+/// Each argument is an owned dependency captured by the returned stream;
+/// grouping them into a struct would only move the list to its constructor.
+#[allow(clippy::too_many_arguments)]
+#[instrument(
+    skip(pool, claude, shopify, confirmations, prompt, usage, user_message),
+    fields(session_id = %session_id)
+)]
 pub fn stream_chat_message(
     pool: PgPool,
     claude: ClaudeClient,
     shopify: AdminClient,
+    confirmations: ToolConfirmations,
+    prompt: PromptTemplate,
+    usage: ClaudeUsageTracker,
     session_id: ChatSessionId,
     user_message: String,
 ) -> impl Stream<Item = ChatStreamEvent> + Send + 'static {
-    streaming_chat_loop(pool, claude, shopify, session_id, user_message)
+    streaming_chat_loop(
+        pool,
+        claude,
+        shopify,
+        confirmations,
+        prompt,
+        usage,
+        session_id,
+        user_message,
+    )
 }
 
 /// State for accumulating a streaming content block.
@@ -606,11 +679,18 @@ impl Default for StreamingState {
 ///
 /// Refactoring to callbacks or separate streams would make the code harder to follow
 /// and maintain, as the sequential nature of the chat loop is essential to understand.
-#[allow(clippy::too_many_lines)]
+///
+/// # Allow: `too_many_arguments`
+///
+/// Takes the same owned dependencies as [`stream_chat_message`].
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 fn streaming_chat_loop(
     pool: PgPool,
     claude: ClaudeClient,
     shopify: AdminClient,
+    confirmations: ToolConfirmations,
+    prompt: PromptTemplate,
+    usage: ClaudeUsageTracker,
     session_id: ChatSessionId,
     user_message: String,
 ) -> impl Stream<Item = ChatStreamEvent> + Send {
@@ -681,10 +761,10 @@ fn streaming_chat_loop(
 
         // Get available tools and system prompt
         let tools = all_shopify_tools();
-        let system_prompt = render_system_prompt();
+        let system_prompt = render_system_prompt(&prompt);
 
         // Tool use loop
-        let executor = ToolExecutor::new(&shopify);
+        let executor = ToolExecutor::new(&shopify).with_confirmations(confirmations.turn(session_id));
         let mut iterations = 0;
 
         loop {
@@ -744,6 +824,10 @@ fn streaming_chat_loop(
             let input_tokens = i32::try_from(state.usage.input_tokens).unwrap_or(i32::MAX);
             let output_tokens = i32::try_from(state.usage.output_tokens).unwrap_or(i32::MAX);
 
+            usage
+                .record(Some(session_id), Some(&prompt), &state.model, &state.usage)
+                .await;
+
             // Emit API interaction metadata
             yield ChatStreamEvent::ApiInteractionEvent {
                 request_id: state.request_id.clone(),
@@ -846,19 +930,17 @@ fn streaming_chat_loop(
                             role: "tool_use".to_string(),
                         };
                     }
+                }
 
-                    // Execute the tool
-                    let result = executor.execute(&tool_use.name, &tool_use.input).await;
+                // Execute the tools concurrently; results keep the call order
+                let calls: Vec<(String, serde_json::Value)> = tool_uses
+                    .iter()
+                    .map(|tool_use| (tool_use.name.clone(), tool_use.input.clone()))
+                    .collect();
+                let results = executor.execute_all(&calls).await;
 
-                    let (result_content, is_error) = match result {
-                        Ok(ToolResult::Success(content)) => (content, false),
-                        Ok(ToolResult::RequiresConfirmation { tool_name, .. }) => {
-                            // Write operations require confirmation via Slack
-                            // For now, return a message indicating this
-                            (format!("Action '{tool_name}' requires confirmation. This feature is not yet implemented."), false)
-                        }
-                        Err(e) => (format!("Error: {e}"), true),
-                    };
+                for (tool_use, result) in tool_uses.iter().zip(results) {
+                    let (result_content, is_error) = convert_tool_result(result);
 
                     // Emit tool result event
                     yield ChatStreamEvent::ToolResult {
@@ -1092,49 +1174,65 @@ fn get_json_str(content: &serde_json::Value, key: &str) -> String {
         .to_string()
 }
 
-/// Execute a tool use block and save the messages.
+/// Execute the tool uses of one assistant message and save the messages.
 ///
-/// Returns (`tool_use_message`, `tool_result_message`, `tool_result_block`).
-async fn execute_tool_use(
+/// All tool use messages are saved first, then the tools run concurrently
+/// and their results are saved in call order, so the history replays as one
+/// assistant turn followed by one batch of tool results.
+///
+/// Returns the saved messages and the tool result blocks for Claude.
+async fn execute_tool_uses(
     repo: &ChatRepository<'_>,
     executor: &ToolExecutor<'_>,
     session_id: ChatSessionId,
-    id: &str,
-    name: &str,
-    input: &serde_json::Value,
-) -> Result<(ChatMessage, ChatMessage, ContentBlock), ChatError> {
-    // Save tool use message
-    let tool_use_content = serde_json::json!({
-        "id": id,
-        "name": name,
-        "input": input
-    });
-    let tool_use_msg = repo
-        .add_message(session_id, ChatRole::ToolUse, tool_use_content)
-        .await?;
-
-    // Execute the tool
-    let result = executor.execute(name, input).await;
-    let (result_content, is_error) = convert_tool_result(result);
-
-    // Save tool result message
-    let tool_result_content = serde_json::json!({
-        "tool_use_id": id,
-        "content": result_content,
-        "is_error": is_error
-    });
-    let tool_result_msg = repo
-        .add_message(session_id, ChatRole::ToolResult, tool_result_content)
-        .await?;
-
-    // Build tool result for next Claude request
-    let tool_result_block = ContentBlock::ToolResult {
-        tool_use_id: id.to_string(),
-        content: result_content,
-        is_error: Some(is_error),
-    };
+    tool_uses: &[AccumulatedToolUse],
+) -> Result<(Vec<ChatMessage>, Vec<ContentBlock>), ChatError> {
+    let mut messages = Vec::with_capacity(tool_uses.len() * 2);
+
+    // Save tool use messages
+    for tool_use in tool_uses {
+        let tool_use_content = serde_json::json!({
+            "id": &tool_use.id,
+            "name": &tool_use.name,
+            "input": &tool_use.input
+        });
+        messages.push(
+            repo.add_message(session_id, ChatRole::ToolUse, tool_use_content)
+                .await?,
+        );
+    }
+
+    // Execute the tools
+    let calls: Vec<(String, serde_json::Value)> = tool_uses
+        .iter()
+        .map(|tool_use| (tool_use.name.clone(), tool_use.input.clone()))
+        .collect();
+    let results = executor.execute_all(&calls).await;
+
+    let mut tool_result_blocks = Vec::with_capacity(tool_uses.len());
+    for (tool_use, result) in tool_uses.iter().zip(results) {
+        let (result_content, is_error) = convert_tool_result(result);
+
+        // Save tool result message
+        let tool_result_content = serde_json::json!({
+            "tool_use_id": &tool_use.id,
+            "content": result_content,
+            "is_error": is_error
+        });
+        messages.push(
+            repo.add_message(session_id, ChatRole::ToolResult, tool_result_content)
+                .await?,
+        );
 
-    Ok((tool_use_msg, tool_result_msg, tool_result_block))
+        // Build tool result for next Claude request
+        tool_result_blocks.push(ContentBlock::ToolResult {
+            tool_use_id: tool_use.id.clone(),
+            content: result_content,
+            is_error: Some(is_error),
+        });
+    }
+
+    Ok((messages, tool_result_blocks))
 }
 
 /// Convert a tool execution result to content string and error flag.
@@ -1206,4 +1304,59 @@ mod tests {
         let title = generate_title("  Hello world  ");
         assert_eq!(title, "Hello world");
     }
+
+    fn chat_message(role: ChatRole, content: serde_json::Value) -> ChatMessage {
+        ChatMessage {
+            id: ChatMessageId::new(1),
+            chat_session_id: ChatSessionId::new(1),
+            role,
+            content,
+            api_interaction: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_convert_batches_parallel_tool_results() {
+        let history = vec![
+            chat_message(
+                ChatRole::User,
+                serde_json::json!({ "text": "Orders and stock?" }),
+            ),
+            chat_message(
+                ChatRole::ToolUse,
+                serde_json::json!({ "id": "a", "name": "query_orders", "input": {} }),
+            ),
+            chat_message(
+                ChatRole::ToolUse,
+                serde_json::json!({ "id": "b", "name": "get_inventory", "input": {} }),
+            ),
+            chat_message(
+                ChatRole::ToolResult,
+                serde_json::json!({ "tool_use_id": "a", "content": "orders", "is_error": false }),
+            ),
+            chat_message(
+                ChatRole::ToolResult,
+                serde_json::json!({ "tool_use_id": "b", "content": "stock", "is_error": false }),
+            ),
+        ];
+
+        let messages = convert_to_claude_messages(&history);
+        assert_eq!(messages.len(), 3);
+
+        let roles: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["user", "assistant", "user"]);
+
+        let Some(MessageContent::Blocks(results)) = messages.last().map(|m| &m.content) else {
+            panic!("expected tool result blocks");
+        };
+        let ids: Vec<&str> = results
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::ToolResult { tool_use_id, .. } => Some(tool_use_id.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(ids, ["a", "b"]);
+    }
 }
@@ -0,0 +1,160 @@
+//! Chat conversation export.
+//!
+//! Renders a saved Claude conversation as Markdown: user and assistant turns
+//! become `## User` / `## Assistant` sections, and each tool call is folded
+//! into a `<details>` block holding its input and result.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use naked_pineapple_core::ChatRole;
+
+use crate::models::chat::{ChatMessage, ChatSession};
+
+/// Maximum length of the title part of an export filename.
+const MAX_SLUG_LENGTH: usize = 50;
+
+/// Render a conversation as Markdown.
+#[must_use]
+pub fn conversation_to_markdown(session: &ChatSession, messages: &[ChatMessage]) -> String {
+    let title = session.title.as_deref().unwrap_or("Untitled");
+    let mut markdown = format!(
+        "# {title}\n\n_Chat session #{} · started {}_\n",
+        session.id.as_i32(),
+        session.created_at.format("%Y-%m-%d %H:%M UTC"),
+    );
+
+    // Tool results are shown with the call that produced them
+    let results: HashMap<&str, &serde_json::Value> = messages
+        .iter()
+        .filter(|m| m.role == ChatRole::ToolResult)
+        .filter_map(|m| Some((m.content.get("tool_use_id")?.as_str()?, &m.content)))
+        .collect();
+
+    let mut last_heading = None;
+    for message in messages {
+        match message.role {
+            ChatRole::User => {
+                push_heading(&mut markdown, &mut last_heading, ChatRole::User);
+                let _ = writeln!(markdown, "\n{}", text_of(&message.content));
+            }
+            ChatRole::Assistant => {
+                push_heading(&mut markdown, &mut last_heading, ChatRole::Assistant);
+                let _ = writeln!(markdown, "\n{}", text_of(&message.content));
+            }
+            ChatRole::ToolUse => {
+                push_heading(&mut markdown, &mut last_heading, ChatRole::Assistant);
+                let id = json_str(&message.content, "id");
+                push_tool_call(&mut markdown, &message.content, results.get(id).copied());
+            }
+            ChatRole::ToolResult => {}
+        }
+    }
+
+    markdown
+}
+
+/// Download filename for an exported conversation, e.g. `chat-12-weekly-sales.md`.
+#[must_use]
+pub fn export_filename(session: &ChatSession, extension: &str) -> String {
+    let slug = session.title.as_deref().map(slugify).unwrap_or_default();
+    if slug.is_empty() {
+        format!("chat-{}.{extension}", session.id.as_i32())
+    } else {
+        format!("chat-{}-{slug}.{extension}", session.id.as_i32())
+    }
+}
+
+/// Start a new `## User` / `## Assistant` section unless one is already open.
+fn push_heading(markdown: &mut String, last: &mut Option<ChatRole>, role: ChatRole) {
+    if *last == Some(role) {
+        return;
+    }
+    let heading = if role == ChatRole::User {
+        "User"
+    } else {
+        "Assistant"
+    };
+    let _ = writeln!(markdown, "\n## {heading}");
+    *last = Some(role);
+}
+
+/// Append a collapsible block for one tool call and its result.
+fn push_tool_call(
+    markdown: &mut String,
+    tool_use: &serde_json::Value,
+    result: Option<&serde_json::Value>,
+) {
+    let name = json_str(tool_use, "name");
+    let input = tool_use
+        .get("input")
+        .map(|input| serde_json::to_string_pretty(input).unwrap_or_default())
+        .unwrap_or_default();
+
+    let _ = writeln!(
+        markdown,
+        "\n<details>\n<summary>Tool: <code>{name}</code></summary>\n\n**Input**\n\n{}",
+        fenced(&input, "json"),
+    );
+
+    if let Some(result) = result {
+        let label = if result
+            .get("is_error")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+        {
+            "Error"
+        } else {
+            "Result"
+        };
+        let _ = writeln!(
+            markdown,
+            "\n**{label}**\n\n{}",
+            fenced(json_str(result, "content"), ""),
+        );
+    }
+
+    markdown.push_str("\n</details>\n");
+}
+
+/// Wrap `content` in a code fence longer than any backtick run inside it.
+fn fenced(content: &str, language: &str) -> String {
+    let longest_run = content
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{fence}{language}\n{content}\n{fence}")
+}
+
+/// Text of a user or assistant message.
+fn text_of(content: &serde_json::Value) -> &str {
+    json_str(content, "text")
+}
+
+/// Extract a string field from message content, or `""`.
+fn json_str<'a>(content: &'a serde_json::Value, key: &str) -> &'a str {
+    content
+        .get(key)
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default()
+}
+
+/// Lowercase ASCII slug of a title, words joined by `-`.
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for word in title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        if slug.len() + word.len() + 1 > MAX_SLUG_LENGTH {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word.to_ascii_lowercase());
+    }
+    slug
+}
@@ -0,0 +1,168 @@
+//! Dashboard overview metrics.
+//!
+//! Assembles the dashboard's headline numbers from several Shopify Admin API
+//! calls made concurrently. Results are cached for five minutes so page
+//! loads don't each cost a round of API calls; the dashboard's refresh
+//! endpoint bypasses the cache. The discount summary pages through every
+//! order this month, so it's cached separately for thirty minutes, refresh
+//! included.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Utc};
+use moka::future::Cache;
+
+use crate::shopify::types::{DateRange, DiscountSummary, ProductRevenue};
+use crate::shopify::{AdminClient, AdminShopifyError};
+
+/// Variants at or below this many units count as low stock.
+pub const LOW_STOCK_THRESHOLD: i64 = 10;
+
+/// How long assembled metrics are reused.
+const CACHE_TTL: Duration = Duration::from_mins(5);
+
+/// How long the discount summary is reused.
+const DISCOUNT_CACHE_TTL: Duration = Duration::from_mins(30);
+
+/// Number of products in the top products list.
+const TOP_PRODUCTS_LIMIT: u32 = 5;
+
+/// Headline metrics for the admin dashboard.
+#[derive(Debug, Clone)]
+pub struct DashboardMetrics {
+    /// Orders created today (UTC).
+    pub orders_today: i64,
+    /// Orders created since the start of the month (UTC).
+    pub orders_this_month: i64,
+    /// Total sales today.
+    pub revenue_today: f64,
+    /// Total sales since the start of the month.
+    pub revenue_this_month: f64,
+    /// Open orders that haven't been fulfilled.
+    pub pending_fulfillments: i64,
+    /// Active product variants at or below [`LOW_STOCK_THRESHOLD`].
+    pub low_stock_items: i64,
+    /// Top products by revenue this month.
+    pub top_products: Vec<ProductRevenue>,
+    /// Discount code performance this month, `None` if it couldn't be loaded.
+    pub discounts: Option<Arc<DiscountSummary>>,
+    /// When these metrics were assembled.
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Assembles and caches [`DashboardMetrics`].
+#[derive(Clone)]
+pub struct DashboardService {
+    cache: Cache<(), Arc<DashboardMetrics>>,
+    discounts: Cache<(), Arc<DiscountSummary>>,
+}
+
+impl Default for DashboardService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DashboardService {
+    /// Create a new dashboard service with an empty cache.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cache: Cache::builder()
+                .max_capacity(1)
+                .time_to_live(CACHE_TTL)
+                .build(),
+            discounts: Cache::builder()
+                .max_capacity(1)
+                .time_to_live(DISCOUNT_CACHE_TTL)
+                .build(),
+        }
+    }
+
+    /// Get the dashboard metrics, using cached values when fresh.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the Shopify API calls fail.
+    pub async fn metrics(
+        &self,
+        client: &AdminClient,
+    ) -> Result<Arc<DashboardMetrics>, AdminShopifyError> {
+        if let Some(metrics) = self.cache.get(&()).await {
+            return Ok(metrics);
+        }
+
+        self.refresh(client).await
+    }
+
+    /// Assemble fresh dashboard metrics, bypassing and then updating the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the Shopify API calls fail.
+    pub async fn refresh(
+        &self,
+        client: &AdminClient,
+    ) -> Result<Arc<DashboardMetrics>, AdminShopifyError> {
+        let (metrics, discounts) = tokio::join!(fetch_metrics(client), self.discounts(client));
+        let metrics = Arc::new(DashboardMetrics {
+            discounts: discounts
+                .inspect_err(|e| tracing::error!("Failed to load discount summary: {e}"))
+                .ok(),
+            ..metrics?
+        });
+        self.cache.insert((), Arc::clone(&metrics)).await;
+        Ok(metrics)
+    }
+
+    /// Get this month's discount summary, using the cached value when fresh.
+    async fn discounts(
+        &self,
+        client: &AdminClient,
+    ) -> Result<Arc<DiscountSummary>, AdminShopifyError> {
+        if let Some(summary) = self.discounts.get(&()).await {
+            return Ok(summary);
+        }
+
+        let today = Utc::now().date_naive();
+        let month_start = today.with_day(1).unwrap_or(today);
+        let summary = Arc::new(
+            client
+                .get_discount_summary(&month_start.to_string(), &today.to_string())
+                .await?,
+        );
+        self.discounts.insert((), Arc::clone(&summary)).await;
+        Ok(summary)
+    }
+}
+
+/// Fetch every dashboard metric concurrently.
+async fn fetch_metrics(client: &AdminClient) -> Result<DashboardMetrics, AdminShopifyError> {
+    let now = Utc::now();
+    let today = now.date_naive();
+    let month_start = today.with_day(1).unwrap_or(today);
+
+    let today_range = DateRange::new(today.to_string(), "today");
+    let month_range = DateRange::new(month_start.to_string(), "today");
+
+    let (today_sales, month_sales, counts, top_products) = tokio::try_join!(
+        client.get_channel_analytics(&today_range),
+        client.get_channel_analytics(&month_range),
+        client.get_dashboard_counts(LOW_STOCK_THRESHOLD),
+        client.get_top_products_by_revenue(&month_range, TOP_PRODUCTS_LIMIT),
+    )?;
+    let (pending_fulfillments, low_stock_items) = counts;
+
+    Ok(DashboardMetrics {
+        orders_today: today_sales.total_orders,
+        orders_this_month: month_sales.total_orders,
+        revenue_today: today_sales.total_sales,
+        revenue_this_month: month_sales.total_sales,
+        pending_fulfillments,
+        low_stock_items,
+        top_products,
+        discounts: None,
+        generated_at: now,
+    })
+}
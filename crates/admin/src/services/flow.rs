@@ -0,0 +1,49 @@
+//! Shopify Flow triggers.
+//!
+//! Sends [`FlowTrigger`] events to the Flow trigger extensions named in
+//! [`FlowConfig`]. Triggers are best effort: a trigger without a configured
+//! handle is skipped, and failures are logged rather than returned so they
+//! never fail the admin action that raised them.
+
+use crate::config::FlowConfig;
+use crate::shopify::{AdminClient, FlowTrigger};
+
+/// Sends Flow triggers for admin events.
+pub struct FlowService<'a> {
+    shopify: &'a AdminClient,
+    config: &'a FlowConfig,
+}
+
+impl<'a> FlowService<'a> {
+    /// Create a Flow service.
+    #[must_use]
+    pub const fn new(shopify: &'a AdminClient, config: &'a FlowConfig) -> Self {
+        Self { shopify, config }
+    }
+
+    /// Send `trigger` to its configured Flow trigger extension.
+    pub async fn send(&self, trigger: &FlowTrigger) {
+        let Some(handle) = self.handle(trigger) else {
+            tracing::debug!(?trigger, "No Flow handle configured, skipping trigger");
+            return;
+        };
+
+        match self
+            .shopify
+            .trigger_flow(handle, trigger.resource_id(), trigger.properties())
+            .await
+        {
+            Ok(()) => tracing::info!(handle, ?trigger, "Sent Shopify Flow trigger"),
+            Err(e) => tracing::warn!(handle, ?trigger, error = %e, "Failed to send Flow trigger"),
+        }
+    }
+
+    /// Configured trigger extension handle for `trigger`.
+    fn handle(&self, trigger: &FlowTrigger) -> Option<&'a str> {
+        match trigger {
+            FlowTrigger::OrderFlaggedForReview(_) => self.config.order_flagged.as_deref(),
+            FlowTrigger::LowInventoryAlert { .. } => self.config.low_inventory.as_deref(),
+            FlowTrigger::HighValueCustomer(_) => self.config.high_value_customer.as_deref(),
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Gift card expiry reminders.
+//!
+//! Once a day, every enabled gift card with a balance that expires in about
+//! [`REMINDER_DAYS_BEFORE_EXPIRY`] days is sent to its customer again as a
+//! reminder. Sent reminders are recorded per expiry date, so overlapping
+//! windows on consecutive days never remind a customer twice.
+
+use std::time::Duration;
+
+use chrono::{Days, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+
+use crate::db::{GiftCardReminderRepository, RepositoryError};
+use crate::shopify::types::GiftCard;
+use crate::shopify::{AdminClient, AdminShopifyError, CursorPaginator, GiftCardSortKey};
+
+/// How often expiring gift cards are checked.
+pub const REMINDER_INTERVAL: Duration = Duration::from_hours(24);
+
+/// Days before expiry that a reminder is sent.
+pub const REMINDER_DAYS_BEFORE_EXPIRY: u64 = 30;
+
+/// Gift cards fetched per Shopify request.
+const PAGE_SIZE: i64 = 250;
+
+/// Errors that can occur while sending reminders.
+#[derive(Debug, Error)]
+pub enum GiftCardReminderError {
+    /// Database error.
+    #[error("Database error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    /// Shopify API error.
+    #[error("Shopify API error: {0}")]
+    Shopify(#[from] AdminShopifyError),
+}
+
+/// A gift card due an expiry reminder.
+#[derive(Debug, Clone)]
+pub struct DueReminder {
+    /// Gift card ID.
+    pub gift_card_id: String,
+    /// Last characters of the gift card code.
+    pub last_characters: String,
+    /// Remaining balance.
+    pub balance: String,
+    /// Currency of the balance.
+    pub currency_code: String,
+    /// Expiry date.
+    pub expires_on: NaiveDate,
+    /// Email of the gift card's customer.
+    pub customer_email: String,
+}
+
+/// Outcome of a reminder run.
+#[derive(Debug, Default)]
+pub struct ReminderResult {
+    /// Reminders sent.
+    pub sent: usize,
+    /// Gift cards that failed, with the error.
+    pub failed: Vec<(String, String)>,
+}
+
+/// Sends expiry reminders for gift cards.
+#[derive(Clone)]
+pub struct GiftCardExpiryReminderService {
+    pool: PgPool,
+    shopify: AdminClient,
+}
+
+impl GiftCardExpiryReminderService {
+    /// Create a reminder service.
+    #[must_use]
+    pub const fn new(pool: PgPool, shopify: AdminClient) -> Self {
+        Self { pool, shopify }
+    }
+
+    /// Send reminders in the background every [`REMINDER_INTERVAL`] until
+    /// the returned handle is aborted. The first run happens at startup.
+    #[must_use]
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(REMINDER_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once().await {
+                    tracing::error!("Failed to send gift card expiry reminders: {e}");
+                }
+            }
+        })
+    }
+
+    /// Send every due reminder.
+    ///
+    /// A failed send is logged and retried on the next run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if gift cards can't be fetched or the database
+    /// can't be reached.
+    pub async fn run_once(&self) -> Result<ReminderResult, GiftCardReminderError> {
+        let repo = GiftCardReminderRepository::new(&self.pool);
+        let mut result = ReminderResult::default();
+
+        for reminder in self.due_reminders().await? {
+            tracing::info!(
+                gift_card_id = %reminder.gift_card_id,
+                expires_on = %reminder.expires_on,
+                customer_email = %reminder.customer_email,
+                "Sending gift card expiry reminder"
+            );
+
+            match self
+                .shopify
+                .send_gift_card_notification_to_customer(&reminder.gift_card_id)
+                .await
+            {
+                Ok(()) => {
+                    repo.record_sent(
+                        &reminder.gift_card_id,
+                        reminder.expires_on,
+                        &reminder.customer_email,
+                    )
+                    .await?;
+                    result.sent += 1;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        gift_card_id = %reminder.gift_card_id,
+                        error = %e,
+                        "Failed to send gift card expiry reminder"
+                    );
+                    result.failed.push((reminder.gift_card_id, e.to_string()));
+                }
+            }
+        }
+
+        tracing::info!(
+            sent = result.sent,
+            failed = result.failed.len(),
+            "Sent gift card expiry reminders"
+        );
+        Ok(result)
+    }
+
+    /// Gift cards expiring in 29 to 31 days that are enabled, have a balance
+    /// and a customer email, and haven't been reminded for that expiry date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if gift cards can't be fetched or the database
+    /// can't be reached.
+    pub async fn due_reminders(&self) -> Result<Vec<DueReminder>, GiftCardReminderError> {
+        let today = Utc::now().date_naive();
+        let from = today + Days::new(REMINDER_DAYS_BEFORE_EXPIRY - 1);
+        let to = today + Days::new(REMINDER_DAYS_BEFORE_EXPIRY + 1);
+        let query = format!(
+            "expires_on:>={} expires_on:<={}",
+            from.format("%Y-%m-%d"),
+            to.format("%Y-%m-%d")
+        );
+
+        let gift_cards = CursorPaginator::new(|cursor| {
+            Box::pin(self.shopify.get_gift_cards(
+                PAGE_SIZE,
+                cursor,
+                Some(query.clone()),
+                Some(GiftCardSortKey::ExpiresOn),
+                false,
+            ))
+        })
+        .collect_all()
+        .await?;
+
+        let repo = GiftCardReminderRepository::new(&self.pool);
+        let mut due = Vec::new();
+        for reminder in gift_cards.iter().filter_map(due_reminder) {
+            if !repo
+                .was_sent(&reminder.gift_card_id, reminder.expires_on)
+                .await?
+            {
+                due.push(reminder);
+            }
+        }
+
+        Ok(due)
+    }
+}
+
+/// The reminder for a gift card, if it is enabled, has a positive balance,
+/// and has a customer email and expiry date.
+fn due_reminder(gift_card: &GiftCard) -> Option<DueReminder> {
+    if !gift_card.enabled {
+        return None;
+    }
+    let balance = gift_card.balance.amount.parse::<Decimal>().ok()?;
+    if balance <= Decimal::ZERO {
+        return None;
+    }
+    let expires_on =
+        NaiveDate::parse_from_str(gift_card.expires_on.as_deref()?, "%Y-%m-%d").ok()?;
+    let customer_email = gift_card
+        .customer_email
+        .clone()
+        .filter(|email| !email.is_empty())?;
+
+    Some(DueReminder {
+        gift_card_id: gift_card.id.clone(),
+        last_characters: gift_card.last_characters.clone(),
+        balance: gift_card.balance.amount.clone(),
+        currency_code: gift_card.balance.currency_code.clone(),
+        expires_on,
+        customer_email,
+    })
+}
@@ -0,0 +1,275 @@
+//! Inventory stockout forecasting.
+//!
+//! Sales velocity is the units of each SKU sold over the last
+//! [`VELOCITY_WINDOW_DAYS`] days, across every order regardless of the
+//! location that fulfilled it. Dividing a location's available quantity by
+//! that velocity gives the days until the SKU sells out there.
+
+use std::collections::HashMap;
+
+use chrono::{Duration, Utc};
+use naked_pineapple_core::LocationId;
+
+use crate::shopify::types::InventoryItem;
+use crate::shopify::{AdminClient, AdminShopifyError, CursorPaginator};
+
+/// Days of orders used to compute sales velocity.
+pub const VELOCITY_WINDOW_DAYS: u32 = 30;
+
+/// Inventory items fetched per Shopify request.
+const ITEMS_PAGE_SIZE: i64 = 100;
+
+/// Predicted stockout of one SKU at a location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StockoutForecast {
+    /// SKU code.
+    pub sku: String,
+    /// Title of the SKU's product.
+    pub product_title: String,
+    /// Quantity available to sell at the location.
+    pub current_available: i64,
+    /// Average units sold per day over the velocity window.
+    pub daily_velocity: f64,
+    /// Days until the available quantity sells out (`None` if it isn't selling).
+    pub days_until_stockout: Option<f64>,
+    /// Units needed to cover sales for the supplier lead time.
+    pub suggested_reorder_quantity: i64,
+}
+
+/// Forecasts when tracked SKUs will run out of stock.
+pub struct InventoryForecastService<'a> {
+    shopify: &'a AdminClient,
+    lead_time_days: u32,
+}
+
+impl<'a> InventoryForecastService<'a> {
+    /// Create a forecast service sizing reorders for `lead_time_days`.
+    #[must_use]
+    pub const fn new(shopify: &'a AdminClient, lead_time_days: u32) -> Self {
+        Self {
+            shopify,
+            lead_time_days,
+        }
+    }
+
+    /// SKUs at `location_id` predicted to sell out within `days_ahead` days,
+    /// most urgent first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if inventory or orders can't be fetched from Shopify.
+    pub async fn forecast_stockouts(
+        &self,
+        location_id: &LocationId,
+        days_ahead: u32,
+    ) -> Result<Vec<StockoutForecast>, AdminShopifyError> {
+        let since = Utc::now() - Duration::days(i64::from(VELOCITY_WINDOW_DAYS));
+        let orders_query = format!("created_at:>={}", since.format("%Y-%m-%d"));
+
+        let (items, units_sold) = tokio::join!(
+            CursorPaginator::new(|cursor| {
+                Box::pin(
+                    self.shopify
+                        .get_inventory_items(ITEMS_PAGE_SIZE, cursor, None),
+                )
+            })
+            .collect_all(),
+            self.shopify.get_units_sold_by_sku(&orders_query),
+        );
+
+        let forecasts = forecast(
+            &items?,
+            &units_sold?,
+            location_id,
+            days_ahead,
+            self.lead_time_days,
+        );
+
+        tracing::info!(
+            %location_id,
+            days_ahead,
+            stockouts = forecasts.len(),
+            "Forecast inventory stockouts"
+        );
+        Ok(forecasts)
+    }
+}
+
+/// Forecast each tracked SKU stocked at `location_id`, keeping those that
+/// sell out within `days_ahead` days, most urgent first.
+fn forecast(
+    items: &[InventoryItem],
+    units_sold: &HashMap<String, i64>,
+    location_id: &LocationId,
+    days_ahead: u32,
+    lead_time_days: u32,
+) -> Vec<StockoutForecast> {
+    let mut forecasts: Vec<StockoutForecast> = items
+        .iter()
+        .filter(|item| item.tracked)
+        .filter_map(|item| {
+            let sku = item.sku.as_deref().filter(|sku| !sku.is_empty())?;
+            let level = item
+                .inventory_levels
+                .iter()
+                .find(|level| level.location_id == *location_id)?;
+
+            // Allow: unit counts are far below 2^52
+            #[allow(clippy::cast_precision_loss)]
+            let (units, available) = (
+                units_sold.get(sku).copied().unwrap_or(0).max(0) as f64,
+                level.available.max(0) as f64,
+            );
+            let daily_velocity = units / f64::from(VELOCITY_WINDOW_DAYS);
+            let days_until_stockout = (daily_velocity > 0.0).then(|| available / daily_velocity);
+            // Allow: rounded up from a non-negative, bounded product
+            #[allow(clippy::cast_possible_truncation)]
+            let suggested_reorder_quantity =
+                (daily_velocity * f64::from(lead_time_days)).ceil() as i64;
+
+            Some(StockoutForecast {
+                sku: sku.to_string(),
+                product_title: item
+                    .variant
+                    .as_ref()
+                    .and_then(|variant| variant.product.as_ref())
+                    .map(|product| product.title.clone())
+                    .unwrap_or_default(),
+                current_available: level.available,
+                daily_velocity,
+                days_until_stockout,
+                suggested_reorder_quantity,
+            })
+        })
+        .filter(|forecast| {
+            forecast
+                .days_until_stockout
+                .is_some_and(|days| days <= f64::from(days_ahead))
+        })
+        .collect();
+
+    forecasts.sort_by(|a, b| {
+        a.days_until_stockout
+            .unwrap_or(f64::INFINITY)
+            .total_cmp(&b.days_until_stockout.unwrap_or(f64::INFINITY))
+    });
+    forecasts
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::shopify::types::{
+        InventoryItemProduct, InventoryItemVariant, InventoryLevel, ProductStatus,
+    };
+
+    const LOCATION: &str = "gid://shopify/Location/1";
+
+    fn location() -> LocationId {
+        LOCATION.parse().unwrap()
+    }
+
+    fn item(sku: &str, available: i64) -> InventoryItem {
+        InventoryItem {
+            id: format!("gid://shopify/InventoryItem/{sku}")
+                .parse()
+                .unwrap(),
+            sku: Some(sku.to_string()),
+            tracked: true,
+            requires_shipping: true,
+            unit_cost: None,
+            harmonized_system_code: None,
+            country_code_of_origin: None,
+            province_code_of_origin: None,
+            inventory_levels: vec![InventoryLevel {
+                inventory_item_id: format!("gid://shopify/InventoryItem/{sku}")
+                    .parse()
+                    .unwrap(),
+                location_id: location(),
+                location_name: None,
+                available,
+                on_hand: available,
+                committed: 0,
+                incoming: 0,
+                reserved: 0,
+                updated_at: None,
+            }],
+            variant: Some(InventoryItemVariant {
+                id: format!("gid://shopify/ProductVariant/{sku}")
+                    .parse()
+                    .unwrap(),
+                title: "Default Title".to_string(),
+                display_name: None,
+                price: None,
+                image: None,
+                product: Some(InventoryItemProduct {
+                    id: "gid://shopify/Product/1".parse().unwrap(),
+                    title: format!("Product {sku}"),
+                    handle: sku.to_lowercase(),
+                    status: ProductStatus::Active,
+                    featured_image: None,
+                }),
+            }),
+        }
+    }
+
+    fn sold(entries: &[(&str, i64)]) -> HashMap<String, i64> {
+        entries
+            .iter()
+            .map(|(sku, units)| ((*sku).to_string(), *units))
+            .collect()
+    }
+
+    #[test]
+    fn test_forecast_computes_velocity_and_reorder() {
+        let forecasts = forecast(&[item("A", 20)], &sold(&[("A", 60)]), &location(), 30, 14);
+
+        assert_eq!(forecasts.len(), 1);
+        let a = forecasts.first().unwrap();
+        assert_eq!(a.product_title, "Product A");
+        assert!((a.daily_velocity - 2.0).abs() < f64::EPSILON);
+        assert!((a.days_until_stockout.unwrap() - 10.0).abs() < f64::EPSILON);
+        assert_eq!(a.suggested_reorder_quantity, 28);
+    }
+
+    #[test]
+    fn test_forecast_sorts_by_urgency_and_filters_horizon() {
+        let items = [item("SLOW", 100), item("FAST", 10), item("SOON", 30)];
+        let units = sold(&[("SLOW", 30), ("FAST", 30), ("SOON", 60)]);
+
+        let skus: Vec<String> = forecast(&items, &units, &location(), 30, 14)
+            .into_iter()
+            .map(|f| f.sku)
+            .collect();
+
+        // SLOW runs out in 100 days, beyond the horizon
+        assert_eq!(skus, vec!["FAST", "SOON"]);
+    }
+
+    #[test]
+    fn test_forecast_skips_unsold_and_other_locations() {
+        let items = [item("IDLE", 5), item("SOLD", 5)];
+        let units = sold(&[("SOLD", 30)]);
+
+        assert_eq!(forecast(&items, &units, &location(), 30, 14).len(), 1);
+        assert!(
+            forecast(
+                &items,
+                &units,
+                &"gid://shopify/Location/2".parse().unwrap(),
+                30,
+                14
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_forecast_treats_oversold_as_out_of_stock() {
+        let forecasts = forecast(&[item("A", -3)], &sold(&[("A", 30)]), &location(), 30, 14);
+
+        let days = forecasts.first().unwrap().days_until_stockout.unwrap();
+        assert!(days.abs() < f64::EPSILON);
+    }
+}
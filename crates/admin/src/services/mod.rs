@@ -5,17 +5,40 @@
 //! - `action_queue` - Pending action queue for Slack confirmations
 //! - `auth` - `WebAuthn` passkey-only authentication
 //! - `chat` - Claude chat orchestration with tool execution
+//! - `chat_export` - Markdown export of saved chat conversations
+//! - `dashboard` - Cached dashboard overview metrics
 //! - `email` - Email delivery via SMTP
+//! - `flow` - Shopify Flow triggers for admin events
+//! - `gift_card_reminders` - Daily expiry reminders for gift cards with a balance
+//! - `inventory_forecast` - Stockout predictions from recent sales velocity
 //! - `klaviyo` - Klaviyo API client for newsletter campaigns
+//! - `order_summary` - Cached financial totals for the filtered orders list
+//! - `reconciliation` - Physical inventory counts reconciled with Shopify
+//! - `session_cleanup` - Periodic deletion of expired sessions
 
 pub mod action_queue;
 pub mod auth;
 pub mod chat;
+pub mod chat_export;
+pub mod dashboard;
 pub mod email;
+pub mod flow;
+pub mod gift_card_reminders;
+pub mod inventory_forecast;
 pub mod klaviyo;
+pub mod order_summary;
+pub mod reconciliation;
+pub mod session_cleanup;
 
 pub use action_queue::{ActionQueueService, EnqueueParams, EnqueueResult};
 pub use auth::{AdminAuthError, AdminAuthService};
 pub use chat::{ChatError, ChatService, ChatStreamEvent, stream_chat_message};
+pub use dashboard::{DashboardMetrics, DashboardService};
 pub use email::{EmailError, EmailService, generate_verification_code};
+pub use flow::FlowService;
+pub use gift_card_reminders::{GiftCardExpiryReminderService, GiftCardReminderError};
+pub use inventory_forecast::{InventoryForecastService, StockoutForecast};
 pub use klaviyo::{KlaviyoClient, KlaviyoError};
+pub use order_summary::{FinancialStatusTotal, OrderSummary, OrderSummaryService};
+pub use reconciliation::{ApplyResult, ReconciliationError, ReconciliationService};
+pub use session_cleanup::{SessionCleanupStats, SessionCleanupTask};
@@ -0,0 +1,191 @@
+//! Financial totals for the orders list.
+//!
+//! Sums the orders matching the orders page's current filter: gross revenue,
+//! refunds and net revenue, broken down by financial status. Summaries are
+//! cached for two minutes per Shopify search query, so paging through the
+//! list or toggling columns doesn't refetch them.
+
+use std::cmp::Reverse;
+use std::sync::Arc;
+use std::time::Duration;
+
+use moka::future::Cache;
+use rust_decimal::Decimal;
+
+use crate::shopify::types::{FinancialStatus, OrderListItem, OrderSortKey};
+use crate::shopify::{AdminClient, AdminShopifyError};
+
+/// How long a summary is reused.
+const CACHE_TTL: Duration = Duration::from_mins(2);
+
+/// Number of distinct filters kept in the cache.
+const CACHE_CAPACITY: u64 = 100;
+
+/// Orders fetched per request; larger pages exceed the query cost limit.
+const PAGE_SIZE: i64 = 50;
+
+/// Pages fetched before giving up, so broad filters stay responsive.
+///
+/// Orders are fetched newest first, so a truncated summary covers the most
+/// recent orders.
+const MAX_PAGES: usize = 20;
+
+/// Order count and revenue for one financial status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinancialStatusTotal {
+    /// The financial status, `None` for orders without one.
+    pub status: Option<FinancialStatus>,
+    /// Orders with this status.
+    pub order_count: i64,
+    /// Total price of those orders.
+    pub total: Decimal,
+}
+
+/// Totals for the orders matching a filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderSummary {
+    /// Orders counted.
+    pub order_count: i64,
+    /// Sum of order totals.
+    pub gross_revenue: Decimal,
+    /// Sum of amounts refunded.
+    pub refunded: Decimal,
+    /// Gross revenue less refunds.
+    pub net_revenue: Decimal,
+    /// Names of orders left out because their amounts aren't decimals.
+    pub unreadable_orders: Vec<String>,
+    /// Breakdown by financial status, largest order count first.
+    pub by_status: Vec<FinancialStatusTotal>,
+    /// Whether more orders matched than were counted.
+    pub truncated: bool,
+}
+
+impl OrderSummary {
+    /// Total `orders`.
+    ///
+    /// Orders whose total or refunded amount isn't a decimal are left out of
+    /// every total and listed in `unreadable_orders`.
+    #[must_use]
+    pub fn from_orders(orders: &[OrderListItem], truncated: bool) -> Self {
+        let mut by_status: Vec<FinancialStatusTotal> = Vec::new();
+        let mut order_count = 0;
+        let mut gross_revenue = Decimal::ZERO;
+        let mut refunded = Decimal::ZERO;
+        let mut unreadable_orders = Vec::new();
+
+        for order in orders {
+            let amounts = order
+                .total_price
+                .amount
+                .parse::<Decimal>()
+                .and_then(|total| Ok((total, order.total_refunded.amount.parse::<Decimal>()?)));
+            let (total, order_refunded) = match amounts {
+                Ok(amounts) => amounts,
+                Err(e) => {
+                    tracing::warn!(order = %order.name, "Unreadable order amount: {e}");
+                    unreadable_orders.push(order.name.clone());
+                    continue;
+                }
+            };
+            order_count += 1;
+            gross_revenue += total;
+            refunded += order_refunded;
+
+            match by_status
+                .iter_mut()
+                .find(|s| s.status == order.financial_status)
+            {
+                Some(entry) => {
+                    entry.order_count += 1;
+                    entry.total += total;
+                }
+                None => by_status.push(FinancialStatusTotal {
+                    status: order.financial_status,
+                    order_count: 1,
+                    total,
+                }),
+            }
+        }
+        by_status.sort_by_key(|s| Reverse(s.order_count));
+
+        Self {
+            order_count,
+            gross_revenue,
+            refunded,
+            net_revenue: gross_revenue - refunded,
+            unreadable_orders,
+            by_status,
+            truncated,
+        }
+    }
+}
+
+/// Computes and caches [`OrderSummary`] values by search query.
+#[derive(Clone)]
+pub struct OrderSummaryService {
+    cache: Cache<String, Arc<OrderSummary>>,
+}
+
+impl Default for OrderSummaryService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrderSummaryService {
+    /// Create a new order summary service with an empty cache.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cache: Cache::builder()
+                .max_capacity(CACHE_CAPACITY)
+                .time_to_live(CACHE_TTL)
+                .build(),
+        }
+    }
+
+    /// Get the summary of orders matching `query` (Shopify search syntax),
+    /// using the cached value when fresh.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the orders can't be fetched.
+    pub async fn summary(
+        &self,
+        client: &AdminClient,
+        query: Option<String>,
+    ) -> Result<Arc<OrderSummary>, AdminShopifyError> {
+        let key = query.clone().unwrap_or_default();
+        if let Some(summary) = self.cache.get(&key).await {
+            return Ok(summary);
+        }
+
+        let mut orders = Vec::new();
+        let mut cursor = None;
+        let mut truncated = false;
+        for page in 1..=MAX_PAGES {
+            let conn = client
+                .get_orders_list(
+                    PAGE_SIZE,
+                    cursor,
+                    query.clone(),
+                    Some(OrderSortKey::CreatedAt),
+                    true,
+                )
+                .await?;
+            orders.extend(conn.orders);
+
+            if !conn.page_info.has_next_page {
+                break;
+            }
+            if page == MAX_PAGES {
+                truncated = true;
+            }
+            cursor = conn.page_info.end_cursor;
+        }
+
+        let summary = Arc::new(OrderSummary::from_orders(&orders, truncated));
+        self.cache.insert(key, Arc::clone(&summary)).await;
+        Ok(summary)
+    }
+}
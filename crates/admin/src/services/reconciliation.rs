@@ -0,0 +1,327 @@
+//! Inventory count reconciliation.
+//!
+//! Starting a session snapshots the on-hand quantity of every tracked SKU
+//! at a location. Applying a session sets Shopify's on-hand quantity to the
+//! counted quantity for each counted SKU that differs; SKUs that were never
+//! counted are left alone, so a partial count can be applied.
+//!
+//! Counts are keyed by SKU, so a location where two inventory items share a
+//! SKU can't be counted until the duplicate SKUs are fixed in Shopify.
+
+use std::collections::HashSet;
+
+use naked_pineapple_core::{InventoryItemId, LocationId};
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::db::{ReconciliationRepository, RepositoryError};
+use crate::models::{NewReconciliationCount, ReconciliationSession, ReconciliationStatus};
+use crate::shopify::{AdminClient, AdminShopifyError, CursorPaginator};
+
+/// Inventory items fetched per Shopify request when snapshotting.
+const SNAPSHOT_PAGE_SIZE: i64 = 100;
+
+/// Reason recorded on Shopify inventory changes.
+const APPLY_REASON: &str = "Inventory count reconciliation";
+
+/// Errors that can occur during reconciliation.
+#[derive(Debug, Error)]
+pub enum ReconciliationError {
+    /// Database error.
+    #[error("Database error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    /// Shopify API error.
+    #[error("Shopify API error: {0}")]
+    Shopify(#[from] AdminShopifyError),
+
+    /// The session doesn't exist.
+    #[error("Reconciliation session not found")]
+    NotFound,
+
+    /// The session has already been applied.
+    #[error("Reconciliation session is already completed")]
+    Completed,
+
+    /// The SKU wasn't at the location when the session started.
+    #[error("SKU {0} is not part of this count")]
+    UnknownSku(String),
+
+    /// Counted quantities can't be negative.
+    #[error("Counted quantity can't be negative")]
+    NegativeQuantity,
+
+    /// More than one inventory item at the location has the same SKU.
+    #[error("More than one inventory item has SKU {}", .0.join(", "))]
+    DuplicateSkus(Vec<String>),
+}
+
+/// Outcome of applying a session.
+#[derive(Debug, Default)]
+pub struct ApplyResult {
+    /// SKUs whose Shopify quantity was updated.
+    pub applied: usize,
+    /// SKUs that failed, with the error.
+    pub failed: Vec<(String, String)>,
+}
+
+impl ApplyResult {
+    /// Whether every discrepancy was applied, so the session can complete.
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Runs inventory count reconciliation sessions.
+pub struct ReconciliationService<'a> {
+    pool: &'a PgPool,
+    shopify: &'a AdminClient,
+}
+
+impl<'a> ReconciliationService<'a> {
+    /// Create a reconciliation service.
+    #[must_use]
+    pub const fn new(pool: &'a PgPool, shopify: &'a AdminClient) -> Self {
+        Self { pool, shopify }
+    }
+
+    /// Start a session for `location_id`, snapshotting its tracked SKUs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Shopify or the database can't be reached, or if
+    /// more than one inventory item at the location has the same SKU.
+    pub async fn start(
+        &self,
+        location_id: &LocationId,
+    ) -> Result<ReconciliationSession, ReconciliationError> {
+        let counts = self.snapshot(location_id).await?;
+        let duplicates = duplicate_skus(&counts);
+        if !duplicates.is_empty() {
+            return Err(ReconciliationError::DuplicateSkus(duplicates));
+        }
+
+        let session = ReconciliationRepository::new(self.pool)
+            .create_session(location_id.as_str(), &counts)
+            .await?;
+
+        tracing::info!(
+            session_id = %session.session_id,
+            %location_id,
+            skus = counts.len(),
+            "Started inventory reconciliation"
+        );
+        Ok(session)
+    }
+
+    /// Record the counted quantity of `sku`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session is missing or completed, the SKU isn't
+    /// part of it, or the quantity is negative.
+    pub async fn record_count(
+        &self,
+        session_id: Uuid,
+        sku: &str,
+        counted_quantity: i32,
+    ) -> Result<(), ReconciliationError> {
+        if counted_quantity < 0 {
+            return Err(ReconciliationError::NegativeQuantity);
+        }
+        self.open_session(session_id).await?;
+
+        ReconciliationRepository::new(self.pool)
+            .record_count(session_id, sku, counted_quantity)
+            .await?
+            .ok_or_else(|| ReconciliationError::UnknownSku(sku.to_string()))?;
+        Ok(())
+    }
+
+    /// Set Shopify's on-hand quantity for every counted, discrepant SKU.
+    ///
+    /// The session is completed once every discrepancy has been applied;
+    /// if some fail it stays open so they can be retried.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session is missing or completed, or the
+    /// database can't be reached.
+    pub async fn apply(&self, session_id: Uuid) -> Result<ApplyResult, ReconciliationError> {
+        let session = self.open_session(session_id).await?;
+        let repo = ReconciliationRepository::new(self.pool);
+
+        let location_id = session
+            .location_id
+            .parse::<LocationId>()
+            .map_err(AdminShopifyError::from)?;
+
+        let mut result = ApplyResult::default();
+        for count in repo.list_counted(session_id).await? {
+            let Some(counted) = count.pending_quantity() else {
+                continue;
+            };
+
+            let applied = match count.inventory_item_id.parse::<InventoryItemId>() {
+                Ok(inventory_item_id) => {
+                    self.shopify
+                        .set_inventory(
+                            &inventory_item_id,
+                            &location_id,
+                            i64::from(counted),
+                            Some(APPLY_REASON),
+                        )
+                        .await
+                }
+                Err(e) => Err(e.into()),
+            };
+            match applied {
+                Ok(()) => {
+                    repo.mark_applied(session_id, &count.sku).await?;
+                    result.applied += 1;
+                }
+                Err(e) => {
+                    tracing::error!(sku = %count.sku, error = %e, "Failed to apply inventory count");
+                    result.failed.push((count.sku, e.to_string()));
+                }
+            }
+        }
+
+        if result.is_complete() {
+            repo.complete_session(session_id).await?;
+        }
+
+        tracing::info!(
+            %session_id,
+            applied = result.applied,
+            failed = result.failed.len(),
+            "Applied inventory reconciliation"
+        );
+        Ok(result)
+    }
+
+    /// Load a session that is still being counted.
+    async fn open_session(
+        &self,
+        session_id: Uuid,
+    ) -> Result<ReconciliationSession, ReconciliationError> {
+        let session = ReconciliationRepository::new(self.pool)
+            .get_session(session_id)
+            .await?
+            .ok_or(ReconciliationError::NotFound)?;
+
+        if session.status == ReconciliationStatus::Completed {
+            return Err(ReconciliationError::Completed);
+        }
+        Ok(session)
+    }
+
+    /// On-hand quantity of every tracked SKU stocked at `location_id`.
+    async fn snapshot(
+        &self,
+        location_id: &LocationId,
+    ) -> Result<Vec<NewReconciliationCount>, ReconciliationError> {
+        let items = CursorPaginator::new(|cursor| {
+            Box::pin(
+                self.shopify
+                    .get_inventory_items(SNAPSHOT_PAGE_SIZE, cursor, None),
+            )
+        })
+        .collect_all()
+        .await?;
+
+        let mut counts = Vec::new();
+        for item in items.into_iter().filter(|item| item.tracked) {
+            let Some(sku) = item.sku.filter(|sku| !sku.is_empty()) else {
+                continue;
+            };
+            let Some(level) = item
+                .inventory_levels
+                .iter()
+                .find(|level| level.location_id == *location_id)
+            else {
+                continue;
+            };
+
+            counts.push(NewReconciliationCount {
+                sku,
+                inventory_item_id: item.id.into(),
+                system_quantity: i32::try_from(level.on_hand).unwrap_or(i32::MAX),
+            });
+        }
+
+        Ok(counts)
+    }
+}
+
+/// SKUs shared by more than one snapshotted inventory item, in order.
+fn duplicate_skus(counts: &[NewReconciliationCount]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut duplicates = Vec::new();
+    for count in counts {
+        if !seen.insert(count.sku.as_str()) && !duplicates.contains(&count.sku) {
+            duplicates.push(count.sku.clone());
+        }
+    }
+    duplicates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(sku: &str, item: u32) -> NewReconciliationCount {
+        NewReconciliationCount {
+            sku: sku.to_string(),
+            inventory_item_id: format!("gid://shopify/InventoryItem/{item}"),
+            system_quantity: 5,
+        }
+    }
+
+    #[test]
+    fn test_duplicate_skus() {
+        assert!(duplicate_skus(&[]).is_empty());
+        assert!(duplicate_skus(&[count("A", 1), count("B", 2)]).is_empty());
+        assert_eq!(
+            duplicate_skus(&[
+                count("A", 1),
+                count("B", 2),
+                count("A", 3),
+                count("C", 4),
+                count("A", 5),
+                count("C", 6),
+            ]),
+            vec!["A".to_string(), "C".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_skus_error_lists_skus() {
+        let error = ReconciliationError::DuplicateSkus(vec!["A".to_string(), "C".to_string()]);
+        assert_eq!(
+            error.to_string(),
+            "More than one inventory item has SKU A, C"
+        );
+    }
+
+    #[test]
+    fn test_apply_completes_only_without_failures() {
+        assert!(ApplyResult::default().is_complete());
+        assert!(
+            ApplyResult {
+                applied: 3,
+                failed: Vec::new(),
+            }
+            .is_complete()
+        );
+        assert!(
+            !ApplyResult {
+                applied: 2,
+                failed: vec![("A".to_string(), "Shopify API error".to_string())],
+            }
+            .is_complete()
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Expired session cleanup.
+//!
+//! tower-sessions never deletes expired rows from `admin.session`, so a
+//! background task removes them every [`CLEANUP_INTERVAL`]. The first run
+//! happens at startup. The number of rows the last run deleted is reported
+//! by `/health/ready` through [`SessionCleanupStats`].
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+
+use chrono::Utc;
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+
+use crate::db::RepositoryError;
+use crate::db::sessions::SessionRepository;
+
+/// How often expired sessions are deleted.
+pub const CLEANUP_INTERVAL: Duration = Duration::from_hours(6);
+
+/// Outcome of the most recent cleanup run, shared with the readiness check.
+#[derive(Debug, Clone)]
+pub struct SessionCleanupStats {
+    /// Rows deleted by the last run, or -1 before a run has succeeded.
+    last_deleted: Arc<AtomicI64>,
+}
+
+impl Default for SessionCleanupStats {
+    fn default() -> Self {
+        Self {
+            last_deleted: Arc::new(AtomicI64::new(-1)),
+        }
+    }
+}
+
+impl SessionCleanupStats {
+    /// Rows deleted by the last successful run, if there has been one.
+    #[must_use]
+    pub fn last_deleted(&self) -> Option<i64> {
+        let deleted = self.last_deleted.load(Ordering::Relaxed);
+        (deleted >= 0).then_some(deleted)
+    }
+
+    fn record(&self, deleted: u64) {
+        self.last_deleted.store(
+            i64::try_from(deleted).unwrap_or(i64::MAX),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+/// Background task that periodically deletes expired sessions.
+pub struct SessionCleanupTask {
+    pool: PgPool,
+    stats: SessionCleanupStats,
+}
+
+impl SessionCleanupTask {
+    /// Create a cleanup task for the given pool, recording each run's
+    /// result in `stats`.
+    #[must_use]
+    pub const fn new(pool: PgPool, stats: SessionCleanupStats) -> Self {
+        Self { pool, stats }
+    }
+
+    /// Run the task in the background until the returned handle is aborted.
+    #[must_use]
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once().await {
+                    tracing::error!("Failed to delete expired sessions: {e}");
+                }
+            }
+        })
+    }
+
+    /// Delete every session that has already expired.
+    ///
+    /// Returns the number of rows deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the delete query fails.
+    pub async fn run_once(&self) -> Result<u64, RepositoryError> {
+        let deleted = SessionRepository::new(&self.pool)
+            .delete_expired(Utc::now())
+            .await?;
+        tracing::info!(deleted, "Deleted expired sessions");
+        self.stats.record(deleted);
+        Ok(deleted)
+    }
+}
@@ -21,6 +21,7 @@ pub mod client;
 pub mod inventory;
 pub mod orders;
 pub mod queries;
+pub mod webhooks;
 
 pub use client::ShipHeroClient;
 pub use inventory::*;
@@ -0,0 +1,121 @@
+//! `ShipHero` webhook payload types.
+//!
+//! `ShipHero` posts a "Shipment Update" webhook when a warehouse order ships.
+//! Order IDs may arrive as numbers or strings depending on the account's
+//! webhook version, so both are accepted.
+
+use serde::{Deserialize, Deserializer};
+
+/// A "Shipment Update" webhook from `ShipHero`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShipmentUpdateWebhook {
+    /// Webhook type (e.g., "Shipment Update").
+    pub webhook_type: Option<String>,
+    /// Whether this is a test webhook sent from the `ShipHero` dashboard.
+    #[serde(default)]
+    pub test: bool,
+    /// Shipment details.
+    pub fulfillment: ShipmentFulfillment,
+}
+
+/// Shipment details from a "Shipment Update" webhook.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShipmentFulfillment {
+    /// `ShipHero` order ID.
+    #[serde(deserialize_with = "string_or_number")]
+    pub order_id: String,
+    /// Partner order ID (the Shopify order ID for Shopify orders).
+    #[serde(default, deserialize_with = "optional_string_or_number")]
+    pub partner_order_id: Option<String>,
+    /// Order number (e.g., "#1001").
+    pub order_number: Option<String>,
+    /// Shipping carrier (e.g., "ups").
+    pub shipping_carrier: String,
+    /// Carrier tracking number.
+    pub tracking_number: String,
+    /// Carrier tracking URL.
+    pub tracking_url: Option<String>,
+    /// When the shipment was created.
+    #[serde(alias = "created_at")]
+    pub shipped_at: Option<String>,
+}
+
+/// An ID that may be sent as a JSON string or number.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    String(String),
+    Number(i64),
+}
+
+impl From<StringOrNumber> for String {
+    fn from(value: StringOrNumber) -> Self {
+        match value {
+            StringOrNumber::String(s) => s,
+            StringOrNumber::Number(n) => n.to_string(),
+        }
+    }
+}
+
+fn string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    StringOrNumber::deserialize(deserializer).map(String::from)
+}
+
+fn optional_string_or_number<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Option::<StringOrNumber>::deserialize(deserializer).map(|id| id.map(String::from))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shipment_update() {
+        let payload = r##"{
+            "webhook_type": "Shipment Update",
+            "fulfillment": {
+                "order_id": 123456,
+                "partner_order_id": "5551234567890",
+                "order_number": "#1001",
+                "shipping_carrier": "ups",
+                "tracking_number": "1Z999AA10123456784",
+                "tracking_url": "https://www.ups.com/track?tracknum=1Z999AA10123456784",
+                "created_at": "2026-02-03 14:30:00"
+            }
+        }"##;
+
+        let webhook: ShipmentUpdateWebhook = serde_json::from_str(payload).unwrap();
+
+        assert!(!webhook.test);
+        assert_eq!(webhook.fulfillment.order_id, "123456");
+        assert_eq!(
+            webhook.fulfillment.partner_order_id.as_deref(),
+            Some("5551234567890")
+        );
+        assert_eq!(webhook.fulfillment.shipping_carrier, "ups");
+        assert_eq!(
+            webhook.fulfillment.shipped_at.as_deref(),
+            Some("2026-02-03 14:30:00")
+        );
+    }
+
+    #[test]
+    fn test_parse_minimal_shipment_update() {
+        let payload = r#"{
+            "fulfillment": {
+                "order_id": "T3JkZXI6MTIz",
+                "shipping_carrier": "usps",
+                "tracking_number": "9400"
+            }
+        }"#;
+
+        let webhook: ShipmentUpdateWebhook = serde_json::from_str(payload).unwrap();
+
+        assert_eq!(webhook.fulfillment.order_id, "T3JkZXI6MTIz");
+        assert!(webhook.fulfillment.partner_order_id.is_none());
+        assert!(webhook.fulfillment.tracking_url.is_none());
+    }
+}
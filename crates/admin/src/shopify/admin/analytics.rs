@@ -7,11 +7,11 @@ use tracing::instrument;
 
 use super::{
     AdminClient, AdminShopifyError,
-    queries::{GetSalesChannels, GetSalesChannelsCount, ShopifyqlQuery},
+    queries::{GetDashboardCounts, GetSalesChannels, GetSalesChannelsCount, ShopifyqlQuery},
 };
 use crate::shopify::types::{
-    AnalyticsSummary, ChannelMetrics, DailyMetrics, DateRange, SalesChannel, SalesChannelApp,
-    ShopifyqlColumn, ShopifyqlResult,
+    AnalyticsSummary, ChannelMetrics, DailyMetrics, DateRange, ProductRevenue, SalesChannel,
+    SalesChannelApp, ShopifyqlColumn, ShopifyqlResult,
 };
 
 impl AdminClient {
@@ -295,4 +295,94 @@ impl AdminClient {
 
         Ok(metrics)
     }
+
+    /// Get the top-selling products by revenue for a date range.
+    ///
+    /// # Arguments
+    ///
+    /// * `date_range` - The date range to query
+    /// * `limit` - Maximum number of products to return
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    #[instrument(skip(self))]
+    pub async fn get_top_products_by_revenue(
+        &self,
+        date_range: &DateRange,
+        limit: u32,
+    ) -> Result<Vec<ProductRevenue>, AdminShopifyError> {
+        let query = format!(
+            "FROM sales SHOW total_sales, ordered_item_quantity \
+             GROUP BY product_title SINCE {} UNTIL {} \
+             ORDER BY total_sales DESC LIMIT {limit}",
+            date_range.start, date_range.end
+        );
+
+        let result = self.execute_shopifyql(&query).await?;
+
+        let title_idx = result.column_index("product_title");
+        let total_sales_idx = result.column_index("total_sales");
+        let units_idx = result.column_index("ordered_item_quantity");
+
+        let products = result
+            .rows
+            .iter()
+            .map(|row| {
+                let row_arr = row.as_array();
+
+                let product_title = title_idx
+                    .and_then(|i| row_arr.and_then(|r| r.get(i)))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("Unknown")
+                    .to_string();
+
+                let total_sales = total_sales_idx
+                    .and_then(|i| row_arr.and_then(|r| r.get(i)))
+                    .and_then(serde_json::Value::as_f64)
+                    .unwrap_or(0.0);
+
+                let units_sold = units_idx
+                    .and_then(|i| row_arr.and_then(|r| r.get(i)))
+                    .and_then(serde_json::Value::as_i64)
+                    .unwrap_or(0);
+
+                ProductRevenue {
+                    product_title,
+                    total_sales,
+                    units_sold,
+                }
+            })
+            .collect();
+
+        Ok(products)
+    }
+
+    /// Get the dashboard counts: orders awaiting fulfillment and variants
+    /// at or below `low_stock_threshold` units.
+    ///
+    /// Returns `(pending_fulfillments, low_stock_variants)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_dashboard_counts(
+        &self,
+        low_stock_threshold: i64,
+    ) -> Result<(i64, i64), AdminShopifyError> {
+        let variables = super::queries::get_dashboard_counts::Variables {
+            pending_fulfillment_query: "status:open fulfillment_status:unfulfilled".to_string(),
+            low_stock_query: format!(
+                "product_status:active inventory_quantity:<={low_stock_threshold}"
+            ),
+        };
+
+        let response = self.execute::<GetDashboardCounts>(variables).await?;
+
+        Ok((
+            response.pending_fulfillments.map_or(0, |c| c.count),
+            response.low_stock_variants.map_or(0, |c| c.count),
+        ))
+    }
 }
@@ -0,0 +1,278 @@
+//! Cost of goods sold (COGS) operations for the Admin API.
+//!
+//! Shopify stores a unit cost on each inventory item, in the shop's currency.
+//! Margins are computed against the variant's current sale price.
+
+use naked_pineapple_core::{InventoryItemId, ProductId};
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use tracing::instrument;
+
+use super::{AdminClient, AdminShopifyError, queries::UpdateInventoryItem};
+use crate::shopify::types::{CogsSummary, Money, ProductInventorySummary, VariantCogs};
+
+/// Currency reported when no variant has a cost.
+const DEFAULT_CURRENCY: &str = "USD";
+
+impl AdminClient {
+    /// Set the unit cost of an inventory item.
+    ///
+    /// Shopify records costs in the shop's currency, so `currency_code` must
+    /// match the currency of the item's existing cost, if it has one.
+    ///
+    /// # Arguments
+    ///
+    /// * `inventory_item_id` - Shopify inventory item ID
+    /// * `cost` - Unit cost as a decimal string (e.g., `"4.25"`)
+    /// * `currency_code` - ISO 4217 currency code of `cost`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cost isn't a non-negative decimal, the currency
+    /// doesn't match the item's, or the API request fails or returns user
+    /// errors.
+    #[instrument(skip(self), fields(inventory_item_id = %inventory_item_id))]
+    pub async fn update_inventory_item_cost(
+        &self,
+        inventory_item_id: &InventoryItemId,
+        cost: &str,
+        currency_code: &str,
+    ) -> Result<(), AdminShopifyError> {
+        use super::queries::update_inventory_item::{InventoryItemInput, Variables};
+
+        let cost = parse_cost(cost)?;
+
+        let item = self.get_inventory_item(inventory_item_id).await?;
+        if let Some(existing) = item.unit_cost
+            && !existing.currency_code.eq_ignore_ascii_case(currency_code)
+        {
+            return Err(AdminShopifyError::UserError(format!(
+                "Cost must be in {}, not {currency_code}",
+                existing.currency_code
+            )));
+        }
+
+        let variables = Variables {
+            id: inventory_item_id.to_string(),
+            input: InventoryItemInput {
+                tracked: None,
+                country_code_of_origin: None,
+                province_code_of_origin: None,
+                harmonized_system_code: None,
+                cost: Some(cost.to_string()),
+                country_harmonized_system_codes: None,
+                measurement: None,
+                requires_shipping: None,
+                sku: None,
+            },
+        };
+
+        let response = self.execute::<UpdateInventoryItem>(variables).await?;
+
+        if let Some(payload) = response.inventory_item_update
+            && !payload.user_errors.is_empty()
+        {
+            let error_messages: Vec<String> =
+                payload.user_errors.into_iter().map(|e| e.message).collect();
+            return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+        }
+
+        Ok(())
+    }
+
+    /// Get a product's cost of goods sold and margins.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_id` - Shopify product ID (e.g., `gid://shopify/Product/123`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or the product is not found.
+    #[instrument(skip(self), fields(product_id = %product_id))]
+    pub async fn get_product_cogs_summary(
+        &self,
+        product_id: &ProductId,
+    ) -> Result<CogsSummary, AdminShopifyError> {
+        let inventory = self.get_product_inventory_summary(product_id).await?;
+        Ok(CogsSummary::from(&inventory))
+    }
+}
+
+impl From<&ProductInventorySummary> for CogsSummary {
+    /// Compute costs and margins from an inventory snapshot.
+    ///
+    /// Variants without a cost are left out of the value on hand and margins.
+    /// Oversold locations (negative available) count as zero.
+    fn from(inventory: &ProductInventorySummary) -> Self {
+        let mut total_value_on_hand = Decimal::ZERO;
+        let mut variants = Vec::with_capacity(inventory.variants.len());
+
+        for variant in &inventory.variants {
+            let cost = variant
+                .unit_cost
+                .as_ref()
+                .and_then(|c| c.amount.parse::<Decimal>().ok());
+
+            if let Some(cost) = cost {
+                let available: i64 = variant.locations.iter().map(|l| l.available.max(0)).sum();
+                total_value_on_hand += cost * Decimal::from(available);
+            }
+
+            variants.push(VariantCogs {
+                variant_id: variant.variant_id.clone(),
+                inventory_item_id: variant.inventory_item_id.clone(),
+                title: variant.title.clone(),
+                price: variant.price.clone(),
+                unit_cost: variant.unit_cost.clone(),
+                margin_percent: cost.and_then(|cost| margin_percent(&variant.price, cost)),
+            });
+        }
+
+        let margins: Vec<f64> = variants.iter().filter_map(|v| v.margin_percent).collect();
+        // Variant counts are far below f64's exact integer range
+        #[allow(clippy::cast_precision_loss)]
+        let average_margin_percent = if margins.is_empty() {
+            0.0
+        } else {
+            margins.iter().sum::<f64>() / margins.len() as f64
+        };
+
+        let lowest_margin_variant = variants
+            .iter()
+            .filter_map(|v| v.margin_percent.map(|m| (m, &v.title)))
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, title)| title.clone());
+
+        let currency_code = variants
+            .iter()
+            .find_map(|v| v.unit_cost.as_ref())
+            .map_or(DEFAULT_CURRENCY, |c| c.currency_code.as_str())
+            .to_string();
+
+        Self {
+            product_id: inventory.product_id.clone(),
+            total_value_on_hand: Money {
+                amount: total_value_on_hand.round_dp(2).to_string(),
+                currency_code,
+            },
+            average_margin_percent,
+            lowest_margin_variant,
+            variants,
+        }
+    }
+}
+
+/// Parse a unit cost, rejecting negative amounts.
+fn parse_cost(cost: &str) -> Result<Decimal, AdminShopifyError> {
+    match cost.trim().parse::<Decimal>() {
+        Ok(cost) if cost >= Decimal::ZERO => Ok(cost.normalize()),
+        _ => Err(AdminShopifyError::UserError(format!(
+            "Invalid cost {cost:?}, expected a non-negative amount"
+        ))),
+    }
+}
+
+/// Gross margin of `price` over `cost`, as a percentage of `price`.
+///
+/// Returns `None` if the price isn't a positive decimal.
+fn margin_percent(price: &str, cost: Decimal) -> Option<f64> {
+    let price = price
+        .parse::<Decimal>()
+        .ok()
+        .filter(|p| *p > Decimal::ZERO)?;
+    ((price - cost) / price * Decimal::ONE_HUNDRED).to_f64()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::shopify::types::{LocationInventory, VariantInventory};
+
+    fn location(available: i64) -> LocationInventory {
+        LocationInventory {
+            location_id: "gid://shopify/Location/1".parse().unwrap(),
+            location_name: "Warehouse".to_string(),
+            available,
+            on_hand: available,
+            committed: 0,
+            incoming: 0,
+        }
+    }
+
+    fn variant(
+        title: &str,
+        price: &str,
+        cost: Option<&str>,
+        available: &[i64],
+    ) -> VariantInventory {
+        VariantInventory {
+            variant_id: format!("gid://shopify/ProductVariant/{title}")
+                .parse()
+                .unwrap(),
+            inventory_item_id: format!("gid://shopify/InventoryItem/{title}")
+                .parse()
+                .unwrap(),
+            sku: None,
+            title: title.to_string(),
+            price: price.to_string(),
+            unit_cost: cost.map(|amount| Money {
+                amount: amount.to_string(),
+                currency_code: "CAD".to_string(),
+            }),
+            total_available: available.iter().sum(),
+            locations: available.iter().copied().map(location).collect(),
+        }
+    }
+
+    fn summary(variants: Vec<VariantInventory>) -> CogsSummary {
+        CogsSummary::from(&ProductInventorySummary {
+            product_id: "gid://shopify/Product/1".parse().unwrap(),
+            variants,
+        })
+    }
+
+    #[test]
+    fn test_cogs_summary() {
+        let cogs = summary(vec![
+            variant("Small", "20.00", Some("5.00"), &[10, 4]),
+            variant("Large", "40.00", Some("20.00"), &[3, -2]),
+            variant("Sample", "10.00", None, &[100]),
+        ]);
+
+        // 14 x 5.00 + 3 x 20.00; the oversold location and uncosted variant are skipped
+        assert_eq!(cogs.total_value_on_hand.amount, "130.00");
+        assert_eq!(cogs.total_value_on_hand.currency_code, "CAD");
+        assert!((cogs.average_margin_percent - 62.5).abs() < f64::EPSILON);
+        assert_eq!(cogs.lowest_margin_variant.as_deref(), Some("Large"));
+        assert_eq!(cogs.variants.len(), 3);
+        assert!(cogs.variants.get(2).unwrap().margin_percent.is_none());
+    }
+
+    #[test]
+    fn test_cogs_summary_without_costs() {
+        let cogs = summary(vec![variant("Default", "12.00", None, &[5])]);
+
+        assert_eq!(cogs.total_value_on_hand.amount, "0");
+        assert_eq!(cogs.total_value_on_hand.currency_code, "USD");
+        assert!(cogs.average_margin_percent.abs() < f64::EPSILON);
+        assert!(cogs.lowest_margin_variant.is_none());
+    }
+
+    #[test]
+    fn test_margin_percent() {
+        let cost = Decimal::new(750, 2);
+        assert!((margin_percent("30.00", cost).unwrap() - 75.0).abs() < f64::EPSILON);
+        assert!(margin_percent("5.00", cost).unwrap() < 0.0);
+        assert!(margin_percent("0", cost).is_none());
+        assert!(margin_percent("free", cost).is_none());
+    }
+
+    #[test]
+    fn test_parse_cost() {
+        assert_eq!(parse_cost(" 4.50 ").unwrap().to_string(), "4.5");
+        assert_eq!(parse_cost("0").unwrap(), Decimal::ZERO);
+        assert!(parse_cost("-1").is_err());
+        assert!(parse_cost("abc").is_err());
+    }
+}
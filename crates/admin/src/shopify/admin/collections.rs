@@ -1,5 +1,6 @@
 //! Collection management operations for the Admin API.
 
+use naked_pineapple_core::{CollectionId, GidError, ProductId};
 use tracing::instrument;
 
 use super::{
@@ -7,12 +8,12 @@ use super::{
     queries::{
         CollectionAddProductsV2, CollectionCreate, CollectionDelete, CollectionUpdate,
         CollectionUpdateFields, CollectionUpdateSortOrder, GetCollection,
-        GetCollectionWithProducts, GetCollections, GetPublications, PublishablePublish,
-        PublishableUnpublish,
+        GetCollectionWithProducts, GetCollections, GetProductCollections,
     },
 };
 use crate::shopify::types::{
-    Collection, CollectionConnection, CollectionProduct, CollectionWithProducts, Image, PageInfo,
+    Collection, CollectionConnection, CollectionProduct, CollectionRef, CollectionWithProducts,
+    Image, PageInfo,
 };
 
 impl AdminClient {
@@ -22,18 +23,21 @@ impl AdminClient {
     ///
     /// Returns an error if the API request fails.
     #[instrument(skip(self), fields(collection_id = %id))]
-    pub async fn get_collection(&self, id: &str) -> Result<Option<Collection>, AdminShopifyError> {
+    pub async fn get_collection(
+        &self,
+        id: &CollectionId,
+    ) -> Result<Option<Collection>, AdminShopifyError> {
         let variables = super::queries::get_collection::Variables { id: id.to_string() };
 
         let response = self.execute::<GetCollection>(variables).await?;
 
-        Ok(response.collection.map(|c| {
+        let collection = response.collection.map(|c| {
             use crate::shopify::types::{
                 CollectionRule, CollectionRuleSet, CollectionSeo, Publication, ResourcePublication,
             };
 
-            Collection {
-                id: c.id,
+            Ok::<_, GidError>(Collection {
+                id: c.id.try_into()?,
                 title: c.title,
                 handle: c.handle,
                 description: c.description,
@@ -82,8 +86,10 @@ impl AdminClient {
                         is_published: e.node.is_published,
                     })
                     .collect(),
-            }
-        }))
+            })
+        });
+
+        Ok(collection.transpose()?)
     }
 
     /// Get a paginated list of collections.
@@ -106,14 +112,14 @@ impl AdminClient {
 
         let response = self.execute::<GetCollections>(variables).await?;
 
-        let collections: Vec<Collection> = response
+        let collections = response
             .collections
             .edges
             .into_iter()
             .map(|e| {
                 let c = e.node;
-                Collection {
-                    id: c.id,
+                Ok(Collection {
+                    id: c.id.try_into()?,
                     title: c.title,
                     handle: c.handle,
                     description: c.description,
@@ -131,9 +137,9 @@ impl AdminClient {
                     sort_order: None,
                     seo: None,
                     publications: vec![],
-                }
+                })
             })
-            .collect();
+            .collect::<Result<Vec<_>, GidError>>()?;
 
         Ok(CollectionConnection {
             collections,
@@ -211,7 +217,7 @@ impl AdminClient {
     #[instrument(skip(self))]
     pub async fn update_collection(
         &self,
-        id: &str,
+        id: &CollectionId,
         title: Option<&str>,
         description_html: Option<&str>,
         sort_order: Option<&str>,
@@ -278,7 +284,7 @@ impl AdminClient {
     #[instrument(skip(self))]
     pub async fn update_collection_sort_order(
         &self,
-        id: &str,
+        id: &CollectionId,
         sort_order: &str,
     ) -> Result<(), AdminShopifyError> {
         use super::queries::collection_update_sort_order::{CollectionSortOrder, Variables};
@@ -329,7 +335,7 @@ impl AdminClient {
     ///
     /// Returns an error if the API request fails or returns user errors.
     #[instrument(skip(self))]
-    pub async fn delete_collection(&self, id: &str) -> Result<String, AdminShopifyError> {
+    pub async fn delete_collection(&self, id: &CollectionId) -> Result<String, AdminShopifyError> {
         use super::queries::collection_delete::{CollectionDeleteInput, Variables};
 
         let variables = Variables {
@@ -371,7 +377,7 @@ impl AdminClient {
     #[instrument(skip(self))]
     pub async fn update_collection_image(
         &self,
-        id: &str,
+        id: &CollectionId,
         image_url: &str,
         alt_text: Option<&str>,
     ) -> Result<(), AdminShopifyError> {
@@ -435,7 +441,10 @@ impl AdminClient {
     ///
     /// Returns an error if the API request fails.
     #[instrument(skip(self))]
-    pub async fn delete_collection_image(&self, id: &str) -> Result<(), AdminShopifyError> {
+    pub async fn delete_collection_image(
+        &self,
+        id: &CollectionId,
+    ) -> Result<(), AdminShopifyError> {
         let query = r"
             mutation CollectionDeleteImage($input: CollectionInput!) {
                 collectionUpdate(input: $input) {
@@ -489,7 +498,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(collection_id = %id))]
     pub async fn get_collection_with_products(
         &self,
-        id: &str,
+        id: &CollectionId,
         first: i64,
         after: Option<String>,
     ) -> Result<Option<CollectionWithProducts>, AdminShopifyError> {
@@ -505,8 +514,8 @@ impl AdminClient {
 
         let response = self.execute::<GetCollectionWithProducts>(variables).await?;
 
-        Ok(response.collection.map(|c| {
-            let products: Vec<CollectionProduct> = c
+        let collection = response.collection.map(|c| {
+            let products = c
                 .products
                 .edges
                 .into_iter()
@@ -517,8 +526,8 @@ impl AdminClient {
                     let currency_code = format!("{:?}", min_price.currency_code);
 
                     #[allow(deprecated)]
-                    CollectionProduct {
-                        id: p.id,
+                    Ok(CollectionProduct {
+                        id: p.id.try_into()?,
                         title: p.title,
                         handle: p.handle,
                         status: format!("{:?}", p.status),
@@ -526,15 +535,16 @@ impl AdminClient {
                         total_inventory: p.total_inventory,
                         price,
                         currency_code,
-                    }
+                        created_at: Some(p.created_at),
+                    })
                 })
-                .collect();
+                .collect::<Result<Vec<_>, GidError>>()?;
 
             let has_next_page = c.products.page_info.has_next_page;
             let end_cursor = c.products.page_info.end_cursor;
 
             let collection = Collection {
-                id: c.id,
+                id: c.id.try_into()?,
                 title: c.title,
                 handle: c.handle,
                 description: c.description,
@@ -585,13 +595,51 @@ impl AdminClient {
                     .collect(),
             };
 
-            CollectionWithProducts {
+            Ok::<_, GidError>(CollectionWithProducts {
                 collection,
                 products,
                 has_next_page,
                 end_cursor,
-            }
-        }))
+            })
+        });
+
+        Ok(collection.transpose()?)
+    }
+
+    /// Get the collections a product belongs to (first 50).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AdminShopifyError::NotFound` if the product doesn't exist, or
+    /// an error if the API request fails.
+    #[instrument(skip(self), fields(product_id = %product_id))]
+    pub async fn get_product_collections(
+        &self,
+        product_id: &ProductId,
+    ) -> Result<Vec<CollectionRef>, AdminShopifyError> {
+        let variables = super::queries::get_product_collections::Variables {
+            id: product_id.to_string(),
+        };
+
+        let response = self.execute::<GetProductCollections>(variables).await?;
+        let product = response
+            .product
+            .ok_or_else(|| AdminShopifyError::NotFound(format!("Product {product_id}")))?;
+
+        Ok(product
+            .collections
+            .edges
+            .into_iter()
+            .map(|e| {
+                Ok(CollectionRef {
+                    id: e.node.id.try_into()?,
+                    title: e.node.title,
+                    handle: e.node.handle,
+                    image_url: e.node.image.map(|i| i.url),
+                    is_smart: e.node.rule_set.is_some(),
+                })
+            })
+            .collect::<Result<_, GidError>>()?)
     }
 
     /// Add products to a collection.
@@ -602,12 +650,12 @@ impl AdminClient {
     #[instrument(skip(self))]
     pub async fn add_products_to_collection(
         &self,
-        collection_id: &str,
-        product_ids: Vec<String>,
+        collection_id: &CollectionId,
+        product_ids: Vec<ProductId>,
     ) -> Result<(), AdminShopifyError> {
         let variables = super::queries::collection_add_products_v2::Variables {
             id: collection_id.to_string(),
-            product_ids,
+            product_ids: product_ids.into_iter().map(String::from).collect(),
         };
 
         let response = self.execute::<CollectionAddProductsV2>(variables).await?;
@@ -643,14 +691,14 @@ impl AdminClient {
     #[instrument(skip(self))]
     pub async fn remove_products_from_collection(
         &self,
-        collection_id: &str,
-        product_ids: Vec<String>,
+        collection_id: &CollectionId,
+        product_ids: Vec<ProductId>,
     ) -> Result<(), AdminShopifyError> {
         use super::queries::CollectionRemoveProducts;
 
         let variables = super::queries::collection_remove_products::Variables {
             id: collection_id.to_string(),
-            product_ids,
+            product_ids: product_ids.into_iter().map(String::from).collect(),
         };
 
         let response = self.execute::<CollectionRemoveProducts>(variables).await?;
@@ -691,8 +739,8 @@ impl AdminClient {
     #[instrument(skip(self))]
     pub async fn reorder_collection_products(
         &self,
-        collection_id: &str,
-        moves: Vec<(String, i64)>,
+        collection_id: &CollectionId,
+        moves: Vec<(ProductId, i64)>,
     ) -> Result<(), AdminShopifyError> {
         let query = r"
             mutation CollectionReorderProducts($id: ID!, $moves: [MoveInput!]!) {
@@ -747,123 +795,4 @@ impl AdminClient {
 
         Ok(())
     }
-
-    /// Get all publications (sales channels) for the shop.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the API request fails.
-    #[instrument(skip(self))]
-    pub async fn get_publications(
-        &self,
-    ) -> Result<Vec<crate::shopify::types::Publication>, AdminShopifyError> {
-        let variables = super::queries::get_publications::Variables {};
-        let response = self.execute::<GetPublications>(variables).await?;
-
-        Ok(response
-            .publications
-            .edges
-            .into_iter()
-            .map(|e| {
-                let id = e.node.id;
-                #[allow(deprecated)]
-                let name = e.node.catalog.map(|c| c.title).unwrap_or(e.node.name);
-                crate::shopify::types::Publication { id, name }
-            })
-            .collect())
-    }
-
-    /// Publish a collection to specified publications.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the API request fails or returns user errors.
-    #[instrument(skip(self))]
-    pub async fn publish_collection(
-        &self,
-        collection_id: &str,
-        publication_ids: &[String],
-    ) -> Result<(), AdminShopifyError> {
-        if publication_ids.is_empty() {
-            return Ok(());
-        }
-
-        let variables = super::queries::publishable_publish::Variables {
-            id: collection_id.to_string(),
-            input: publication_ids
-                .iter()
-                .map(
-                    |pub_id| super::queries::publishable_publish::PublicationInput {
-                        publication_id: Some(pub_id.clone()),
-                        publish_date: None,
-                    },
-                )
-                .collect(),
-        };
-
-        let response = self.execute::<PublishablePublish>(variables).await?;
-
-        if let Some(payload) = response.publishable_publish
-            && !payload.user_errors.is_empty()
-        {
-            let error_messages: Vec<String> = payload
-                .user_errors
-                .iter()
-                .map(|e| {
-                    let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
-                    format!("{field}: {}", e.message)
-                })
-                .collect();
-            return Err(AdminShopifyError::UserError(error_messages.join("; ")));
-        }
-
-        Ok(())
-    }
-
-    /// Unpublish a collection from specified publications.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the API request fails or returns user errors.
-    #[instrument(skip(self))]
-    pub async fn unpublish_collection(
-        &self,
-        collection_id: &str,
-        publication_ids: &[String],
-    ) -> Result<(), AdminShopifyError> {
-        if publication_ids.is_empty() {
-            return Ok(());
-        }
-
-        let variables = super::queries::publishable_unpublish::Variables {
-            id: collection_id.to_string(),
-            input: publication_ids
-                .iter()
-                .map(
-                    |pub_id| super::queries::publishable_unpublish::PublicationInput {
-                        publication_id: Some(pub_id.clone()),
-                        publish_date: None,
-                    },
-                )
-                .collect(),
-        };
-
-        let response = self.execute::<PublishableUnpublish>(variables).await?;
-
-        if let Some(payload) = response.publishable_unpublish
-            && !payload.user_errors.is_empty()
-        {
-            let error_messages: Vec<String> = payload
-                .user_errors
-                .iter()
-                .map(|e| {
-                    let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
-                    format!("{field}: {}", e.message)
-                })
-                .collect();
-            return Err(AdminShopifyError::UserError(error_messages.join("; ")));
-        }
-
-        Ok(())
-    }
 }
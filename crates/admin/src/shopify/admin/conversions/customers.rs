@@ -1,5 +1,7 @@
 //! Customer type conversion functions.
 
+use naked_pineapple_core::GidError;
+
 use crate::shopify::types::{
     Address, Customer, CustomerConnection, CustomerOrder, CustomerState, MarketingConsent,
     MarketingState, Money, PageInfo,
@@ -29,7 +31,7 @@ fn convert_marketing_state(state: &str) -> MarketingState {
 // GetCustomer conversions
 // =============================================================================
 
-pub fn convert_customer(customer: get_customer::GetCustomerCustomer) -> Customer {
+pub fn convert_customer(customer: get_customer::GetCustomerCustomer) -> Result<Customer, GidError> {
     let state = match customer.state {
         get_customer::CustomerState::ENABLED => CustomerState::Enabled,
         get_customer::CustomerState::INVITED => CustomerState::Invited,
@@ -83,14 +85,14 @@ pub fn convert_customer(customer: get_customer::GetCustomerCustomer) -> Customer
         .collect();
 
     // Convert recent orders
-    let recent_orders: Vec<CustomerOrder> = customer
+    let recent_orders = customer
         .orders
         .edges
         .into_iter()
         .map(|e| {
             let order = e.node;
-            CustomerOrder {
-                id: order.id,
+            Ok(CustomerOrder {
+                id: order.id.try_into()?,
                 name: order.name,
                 created_at: order.created_at,
                 financial_status: order.display_financial_status.map(|s| format!("{s:?}")),
@@ -101,12 +103,12 @@ pub fn convert_customer(customer: get_customer::GetCustomerCustomer) -> Customer
                         order.total_price_set.shop_money.currency_code,
                     ),
                 },
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<_>, GidError>>()?;
 
-    Customer {
-        id: customer.id,
+    Ok(Customer {
+        id: customer.id.try_into()?,
         email,
         first_name: customer.first_name,
         last_name: customer.last_name,
@@ -140,7 +142,7 @@ pub fn convert_customer(customer: get_customer::GetCustomerCustomer) -> Customer
         recent_orders,
         created_at: customer.created_at,
         updated_at: customer.updated_at,
-    }
+    })
 }
 
 fn convert_address_single(a: get_customer::GetCustomerCustomerDefaultAddress) -> Address {
@@ -181,25 +183,25 @@ fn convert_address_v2_node(a: get_customer::GetCustomerCustomerAddressesV2EdgesN
 
 pub fn convert_customer_connection(
     conn: get_customers::GetCustomersCustomers,
-) -> CustomerConnection {
-    CustomerConnection {
+) -> Result<CustomerConnection, GidError> {
+    Ok(CustomerConnection {
         customers: conn
             .edges
             .into_iter()
             .map(|e| convert_customers_list_customer(e.node))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         page_info: PageInfo {
             has_next_page: conn.page_info.has_next_page,
             has_previous_page: conn.page_info.has_previous_page,
             start_cursor: conn.page_info.start_cursor,
             end_cursor: conn.page_info.end_cursor,
         },
-    }
+    })
 }
 
 fn convert_customers_list_customer(
     customer: get_customers::GetCustomersCustomersEdgesNode,
-) -> Customer {
+) -> Result<Customer, GidError> {
     let state = match customer.state {
         get_customers::CustomerState::ENABLED => CustomerState::Enabled,
         get_customers::CustomerState::INVITED => CustomerState::Invited,
@@ -231,8 +233,8 @@ fn convert_customers_list_customer(
         .as_ref()
         .map(|p| p.phone_number.clone());
 
-    Customer {
-        id: customer.id,
+    Ok(Customer {
+        id: customer.id.try_into()?,
         email,
         first_name: customer.first_name,
         last_name: customer.last_name,
@@ -290,5 +292,5 @@ fn convert_customers_list_customer(
         recent_orders: vec![], // Not fetched in list view
         created_at: customer.created_at,
         updated_at: customer.updated_at,
-    }
+    })
 }
@@ -1,13 +1,16 @@
 //! Inventory type conversion functions.
 
+use naked_pineapple_core::{GidError, InventoryItemId, LocationId};
+
 use crate::shopify::types::{
     Image, InventoryItem, InventoryItemConnection, InventoryItemProduct, InventoryItemVariant,
-    InventoryLevel, InventoryLevelConnection, Location, LocationAddress, LocationConnection, Money,
-    PageInfo, ProductStatus,
+    InventoryLevel, InventoryLevelConnection, Location, LocationAddress, LocationConnection,
+    LocationInventory, Money, PageInfo, ProductInventorySummary, ProductStatus, VariantInventory,
 };
 
 use super::super::queries::{
     get_inventory_item, get_inventory_items, get_inventory_levels, get_locations,
+    get_product_inventory_summary,
 };
 
 // =============================================================================
@@ -16,54 +19,60 @@ use super::super::queries::{
 
 pub fn convert_inventory_level_connection(
     location: get_inventory_levels::GetInventoryLevelsLocation,
-) -> InventoryLevelConnection {
-    let location_id = location.id.clone();
-    let location_name = location.name.clone();
+) -> Result<InventoryLevelConnection, GidError> {
+    let location_id = LocationId::try_from(location.id)?;
+    let location_name = location.name;
 
-    InventoryLevelConnection {
+    Ok(InventoryLevelConnection {
         inventory_levels: location
             .inventory_levels
             .edges
             .into_iter()
             .map(|e| convert_inventory_level(e.node, &location_id, &location_name))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         page_info: PageInfo {
             has_next_page: location.inventory_levels.page_info.has_next_page,
             has_previous_page: location.inventory_levels.page_info.has_previous_page,
             start_cursor: location.inventory_levels.page_info.start_cursor,
             end_cursor: location.inventory_levels.page_info.end_cursor,
         },
-    }
+    })
 }
 
 fn convert_inventory_level(
     level: get_inventory_levels::GetInventoryLevelsLocationInventoryLevelsEdgesNode,
-    location_id: &str,
+    location_id: &LocationId,
     location_name: &str,
-) -> InventoryLevel {
+) -> Result<InventoryLevel, GidError> {
     // Extract quantities by name
     let mut available: i64 = 0;
     let mut on_hand: i64 = 0;
+    let mut committed: i64 = 0;
     let mut incoming: i64 = 0;
+    let mut reserved: i64 = 0;
 
     for qty in &level.quantities {
         match qty.name.as_str() {
             "available" => available = qty.quantity,
             "on_hand" => on_hand = qty.quantity,
+            "committed" => committed = qty.quantity,
             "incoming" => incoming = qty.quantity,
+            "reserved" => reserved = qty.quantity,
             _ => {}
         }
     }
 
-    InventoryLevel {
-        inventory_item_id: level.item.id,
-        location_id: location_id.to_string(),
+    Ok(InventoryLevel {
+        inventory_item_id: level.item.id.try_into()?,
+        location_id: location_id.clone(),
         location_name: Some(location_name.to_string()),
         available,
         on_hand,
+        committed,
         incoming,
+        reserved,
         updated_at: Some(level.updated_at),
-    }
+    })
 }
 
 // =============================================================================
@@ -72,26 +81,28 @@ fn convert_inventory_level(
 
 pub fn convert_location_connection(
     locations: get_locations::GetLocationsLocations,
-) -> LocationConnection {
-    LocationConnection {
+) -> Result<LocationConnection, GidError> {
+    Ok(LocationConnection {
         locations: locations
             .edges
             .into_iter()
             .map(|e| convert_location(e.node))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         page_info: PageInfo {
             has_next_page: locations.page_info.has_next_page,
             has_previous_page: false,
             start_cursor: None,
             end_cursor: locations.page_info.end_cursor,
         },
-    }
+    })
 }
 
-fn convert_location(location: get_locations::GetLocationsLocationsEdgesNode) -> Location {
+fn convert_location(
+    location: get_locations::GetLocationsLocationsEdgesNode,
+) -> Result<Location, GidError> {
     let address = location.address;
-    Location {
-        id: location.id,
+    Ok(Location {
+        id: location.id.try_into()?,
         name: location.name,
         is_active: location.is_active,
         fulfills_online_orders: location.fulfills_online_orders,
@@ -102,7 +113,7 @@ fn convert_location(location: get_locations::GetLocationsLocationsEdgesNode) ->
             country_code: address.country_code,
             zip: address.zip,
         }),
-    }
+    })
 }
 
 // =============================================================================
@@ -112,28 +123,30 @@ fn convert_location(location: get_locations::GetLocationsLocationsEdgesNode) ->
 /// Convert the `GetInventoryItems` response to our domain type.
 pub fn convert_inventory_item_connection(
     response: get_inventory_items::ResponseData,
-) -> InventoryItemConnection {
-    InventoryItemConnection {
+) -> Result<InventoryItemConnection, GidError> {
+    Ok(InventoryItemConnection {
         items: response
             .inventory_items
             .edges
             .into_iter()
             .map(|e| convert_inventory_item_from_list(e.node))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         page_info: PageInfo {
             has_next_page: response.inventory_items.page_info.has_next_page,
             has_previous_page: false,
             start_cursor: None,
             end_cursor: response.inventory_items.page_info.end_cursor,
         },
-    }
+    })
 }
 
 fn convert_inventory_item_from_list(
     item: get_inventory_items::GetInventoryItemsInventoryItemsEdgesNode,
-) -> InventoryItem {
+) -> Result<InventoryItem, GidError> {
+    let id: InventoryItemId = item.id.try_into()?;
+
     // Convert inventory levels
-    let inventory_levels: Vec<InventoryLevel> = item
+    let inventory_levels = item
         .inventory_levels
         .edges
         .into_iter()
@@ -141,38 +154,45 @@ fn convert_inventory_item_from_list(
             let level = e.node;
             let mut available: i64 = 0;
             let mut on_hand: i64 = 0;
+            let mut committed: i64 = 0;
             let mut incoming: i64 = 0;
+            let mut reserved: i64 = 0;
 
             for qty in &level.quantities {
                 match qty.name.as_str() {
                     "available" => available = qty.quantity,
                     "on_hand" => on_hand = qty.quantity,
+                    "committed" => committed = qty.quantity,
                     "incoming" => incoming = qty.quantity,
-                    // committed tracked but not stored in InventoryLevel
+                    "reserved" => reserved = qty.quantity,
                     _ => {}
                 }
             }
 
-            InventoryLevel {
-                inventory_item_id: item.id.clone(),
-                location_id: level.location.id,
+            Ok(InventoryLevel {
+                inventory_item_id: id.clone(),
+                location_id: level.location.id.try_into()?,
                 location_name: Some(level.location.name),
                 available,
                 on_hand,
+                committed,
                 incoming,
+                reserved,
                 updated_at: None,
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<_>, GidError>>()?;
 
     // Convert variant and product info from variants connection (first item)
-    let variant = item.variants.and_then(|variants| {
-        variants.edges.into_iter().next().map(|edge| {
+    let variant = item
+        .variants
+        .and_then(|variants| variants.edges.into_iter().next())
+        .map(|edge| {
             let v = edge.node;
             // product is a required field in this query, so convert directly
             let p = v.product;
             let product = Some(InventoryItemProduct {
-                id: p.id,
+                id: p.id.try_into()?,
                 title: p.title,
                 handle: p.handle,
                 status: convert_product_status(&p.status),
@@ -188,19 +208,19 @@ fn convert_inventory_item_from_list(
                         height: None,
                     }),
             });
-            InventoryItemVariant {
-                id: v.id,
+            Ok::<_, GidError>(InventoryItemVariant {
+                id: v.id.try_into()?,
                 title: v.title,
                 display_name: None,
                 price: None,
                 image: None,
                 product,
-            }
+            })
         })
-    });
+        .transpose()?;
 
-    InventoryItem {
-        id: item.id,
+    Ok(InventoryItem {
+        id,
         sku: item.sku,
         tracked: item.tracked,
         requires_shipping: item.requires_shipping,
@@ -213,7 +233,7 @@ fn convert_inventory_item_from_list(
         province_code_of_origin: None,
         inventory_levels,
         variant,
-    }
+    })
 }
 
 const fn convert_product_status(status: &get_inventory_items::ProductStatus) -> ProductStatus {
@@ -233,9 +253,11 @@ const fn convert_product_status(status: &get_inventory_items::ProductStatus) ->
 /// Convert the `GetInventoryItem` response to our domain type.
 pub fn convert_single_inventory_item(
     item: get_inventory_item::GetInventoryItemInventoryItem,
-) -> InventoryItem {
+) -> Result<InventoryItem, GidError> {
+    let id: InventoryItemId = item.id.try_into()?;
+
     // Convert inventory levels
-    let inventory_levels: Vec<InventoryLevel> = item
+    let inventory_levels = item
         .inventory_levels
         .edges
         .into_iter()
@@ -243,38 +265,46 @@ pub fn convert_single_inventory_item(
             let level = e.node;
             let mut available: i64 = 0;
             let mut on_hand: i64 = 0;
+            let mut committed: i64 = 0;
             let mut incoming: i64 = 0;
+            let mut reserved: i64 = 0;
 
             for qty in &level.quantities {
                 match qty.name.as_str() {
                     "available" => available = qty.quantity,
                     "on_hand" => on_hand = qty.quantity,
+                    "committed" => committed = qty.quantity,
                     "incoming" => incoming = qty.quantity,
-                    // committed, reserved, damaged tracked but not stored in InventoryLevel
+                    "reserved" => reserved = qty.quantity,
+                    // damaged tracked but not stored in InventoryLevel
                     _ => {}
                 }
             }
 
-            InventoryLevel {
-                inventory_item_id: item.id.clone(),
-                location_id: level.location.id,
+            Ok(InventoryLevel {
+                inventory_item_id: id.clone(),
+                location_id: level.location.id.try_into()?,
                 location_name: Some(level.location.name),
                 available,
                 on_hand,
+                committed,
                 incoming,
+                reserved,
                 updated_at: Some(level.updated_at),
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<_>, GidError>>()?;
 
     // Convert variant and product info from variants connection (first item)
-    let variant = item.variants.and_then(|variants| {
-        variants.edges.into_iter().next().map(|edge| {
+    let variant = item
+        .variants
+        .and_then(|variants| variants.edges.into_iter().next())
+        .map(|edge| {
             let v = edge.node;
             // product is a required field in this query, so convert directly
             let p = v.product;
             let product = Some(InventoryItemProduct {
-                id: p.id,
+                id: p.id.try_into()?,
                 title: p.title,
                 handle: p.handle,
                 status: convert_single_product_status(&p.status),
@@ -290,19 +320,19 @@ pub fn convert_single_inventory_item(
                         height: None,
                     }),
             });
-            InventoryItemVariant {
-                id: v.id,
+            Ok::<_, GidError>(InventoryItemVariant {
+                id: v.id.try_into()?,
                 title: v.title,
                 display_name: None,
                 price: None,
                 image: None,
                 product,
-            }
+            })
         })
-    });
+        .transpose()?;
 
-    InventoryItem {
-        id: item.id,
+    Ok(InventoryItem {
+        id,
         sku: item.sku,
         tracked: item.tracked,
         requires_shipping: item.requires_shipping,
@@ -315,7 +345,7 @@ pub fn convert_single_inventory_item(
         province_code_of_origin: item.province_code_of_origin.map(|p| format!("{p:?}")),
         inventory_levels,
         variant,
-    }
+    })
 }
 
 const fn convert_single_product_status(
@@ -330,3 +360,68 @@ const fn convert_single_product_status(
         }
     }
 }
+
+// =============================================================================
+// GetProductInventorySummary conversions
+// =============================================================================
+
+pub fn convert_product_inventory_summary(
+    product: get_product_inventory_summary::GetProductInventorySummaryProduct,
+) -> Result<ProductInventorySummary, GidError> {
+    let variants = product
+        .variants
+        .edges
+        .into_iter()
+        .map(|e| {
+            let variant = e.node;
+            let locations: Vec<LocationInventory> = variant
+                .inventory_item
+                .inventory_levels
+                .edges
+                .into_iter()
+                .map(|e| {
+                    let level = e.node;
+                    let mut inventory = LocationInventory {
+                        location_id: level.location.id.try_into()?,
+                        location_name: level.location.name,
+                        available: 0,
+                        on_hand: 0,
+                        committed: 0,
+                        incoming: 0,
+                    };
+
+                    for qty in &level.quantities {
+                        match qty.name.as_str() {
+                            "available" => inventory.available = qty.quantity,
+                            "on_hand" => inventory.on_hand = qty.quantity,
+                            "committed" => inventory.committed = qty.quantity,
+                            "incoming" => inventory.incoming = qty.quantity,
+                            _ => {}
+                        }
+                    }
+
+                    Ok(inventory)
+                })
+                .collect::<Result<Vec<_>, GidError>>()?;
+
+            Ok(VariantInventory {
+                variant_id: variant.id.try_into()?,
+                inventory_item_id: variant.inventory_item.id.try_into()?,
+                sku: variant.sku,
+                title: variant.title,
+                price: variant.price,
+                unit_cost: variant.inventory_item.unit_cost.map(|c| Money {
+                    amount: c.amount,
+                    currency_code: format!("{:?}", c.currency_code),
+                }),
+                total_available: locations.iter().map(|l| l.available).sum(),
+                locations,
+            })
+        })
+        .collect::<Result<_, GidError>>()?;
+
+    Ok(ProductInventorySummary {
+        product_id: product.id.try_into()?,
+        variants,
+    })
+}
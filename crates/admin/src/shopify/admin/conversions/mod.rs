@@ -11,13 +11,13 @@ mod products;
 pub use customers::{convert_customer, convert_customer_connection};
 pub use inventory::{
     convert_inventory_item_connection, convert_inventory_level_connection,
-    convert_location_connection, convert_single_inventory_item,
+    convert_location_connection, convert_product_inventory_summary, convert_single_inventory_item,
 };
 pub use orders::{
     convert_calculated_order, convert_fulfillment_orders, convert_order, convert_order_connection,
     convert_order_list_connection,
 };
-pub use products::{convert_product, convert_product_connection};
+pub use products::{convert_product, convert_product_connection, convert_variant};
 
 /// Convert a currency code enum to string.
 pub fn currency_code_to_string<T: std::fmt::Debug>(code: T) -> String {
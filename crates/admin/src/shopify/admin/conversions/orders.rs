@@ -1,10 +1,13 @@
 //! Order type conversion functions.
 
+use naked_pineapple_core::GidError;
+
 use crate::shopify::types::{
-    Address, DeliveryCategory, FinancialStatus, Fulfillment, FulfillmentOrderAction,
-    FulfillmentOrderDetail, FulfillmentOrderLineItemDetail, FulfillmentStatus, Image, Money, Order,
-    OrderChannelInfo, OrderConnection, OrderLineItem, OrderListConnection, OrderListItem,
-    OrderReturnStatus, OrderRisk, OrderRiskLevel, OrderShippingLine, PageInfo, TrackingInfo,
+    Address, DeliveryCategory, FinancialStatus, Fulfillment, FulfillmentLineItem,
+    FulfillmentOrderAction, FulfillmentOrderDetail, FulfillmentOrderLineItemDetail,
+    FulfillmentStatus, Image, Money, Order, OrderChannelInfo, OrderConnection, OrderExchange,
+    OrderLineItem, OrderListConnection, OrderListItem, OrderReturnStatus, OrderRisk,
+    OrderRiskLevel, OrderShippingLine, OrderTimelineEvent, PageInfo, TrackingInfo,
 };
 // Note: Image is also imported at bottom of file for order_edit conversions, using same type.
 
@@ -35,10 +38,10 @@ struct OrderPricing {
 // GetOrder conversions
 // =============================================================================
 
-pub fn convert_order(order: get_order::GetOrderOrder) -> Order {
+pub fn convert_order(order: get_order::GetOrderOrder) -> Result<Order, GidError> {
     let pricing = build_pricing_single(&order);
-    Order {
-        id: order.id,
+    Ok(Order {
+        id: order.id.try_into()?,
         name: order.name,
         number: order.number,
         created_at: order.created_at,
@@ -66,7 +69,7 @@ pub fn convert_order(order: get_order::GetOrderOrder) -> Order {
             .edges
             .into_iter()
             .map(|e| convert_line_item_single(e.node))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         fulfillments: order
             .fulfillments
             .into_iter()
@@ -77,13 +80,64 @@ pub fn convert_order(order: get_order::GetOrderOrder) -> Order {
             .edges
             .into_iter()
             .map(|e| convert_fulfillment_order_single(e.node))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         billing_address: order.billing_address.map(convert_billing_single),
         shipping_address: order.shipping_address.map(convert_shipping_single),
-        customer_id: order.customer.map(|c| c.id),
+        customer_id: order.customer.map(|c| c.id.try_into()).transpose()?,
+        exchanges: order
+            .returns
+            .edges
+            .into_iter()
+            .filter_map(|e| convert_exchange_single(e.node))
+            .collect(),
+        timeline_events: order
+            .events
+            .edges
+            .into_iter()
+            .map(|e| convert_timeline_event_single(e.node))
+            .collect(),
+    })
+}
+
+/// Convert an order event to a timeline event. Comments keep the raw text
+/// and staff author; every other event is a status change.
+fn convert_timeline_event_single(
+    event: get_order::GetOrderOrderEventsEdgesNode,
+) -> OrderTimelineEvent {
+    match event.on {
+        get_order::GetOrderOrderEventsEdgesNodeOn::CommentEvent(comment) => {
+            OrderTimelineEvent::Comment {
+                body: comment.raw_message,
+                author: comment.author.name,
+                created_at: event.created_at,
+            }
+        }
+        get_order::GetOrderOrderEventsEdgesNodeOn::BasicEvent(basic) => {
+            OrderTimelineEvent::StatusChange {
+                action: basic.action,
+                message: event.message,
+                created_at: event.created_at,
+            }
+        }
     }
 }
 
+/// Convert a return to an exchange, skipping returns without replacement items.
+fn convert_exchange_single(ret: get_order::GetOrderOrderReturnsEdgesNode) -> Option<OrderExchange> {
+    let exchange_quantity: i64 = ret
+        .exchange_line_items
+        .edges
+        .iter()
+        .map(|e| e.node.quantity)
+        .sum();
+    (exchange_quantity > 0).then_some(OrderExchange {
+        id: ret.id,
+        name: ret.name,
+        created_at: ret.created_at,
+        exchange_quantity,
+    })
+}
+
 fn build_pricing_single(order: &get_order::GetOrderOrder) -> OrderPricing {
     OrderPricing {
         subtotal: order
@@ -167,8 +221,10 @@ const fn convert_fulfillment_single(
     }
 }
 
-fn convert_line_item_single(item: get_order::GetOrderOrderLineItemsEdgesNode) -> OrderLineItem {
-    OrderLineItem {
+fn convert_line_item_single(
+    item: get_order::GetOrderOrderLineItemsEdgesNode,
+) -> Result<OrderLineItem, GidError> {
+    Ok(OrderLineItem {
         id: item.id,
         title: item.title,
         variant_title: item.variant_title,
@@ -192,11 +248,11 @@ fn convert_line_item_single(item: get_order::GetOrderOrderLineItemsEdgesNode) ->
                 item.total_discount_set.shop_money.currency_code,
             ),
         },
-        product_id: item.product.map(|p| p.id),
-        variant_id: item.variant.map(|v| v.id),
+        product_id: item.product.map(|p| p.id.try_into()).transpose()?,
+        variant_id: item.variant.map(|v| v.id.try_into()).transpose()?,
         requires_shipping: item.requires_shipping,
         is_gift_card: item.is_gift_card,
-    }
+    })
 }
 
 fn convert_fulfillment_obj_single(f: get_order::GetOrderOrderFulfillments) -> Fulfillment {
@@ -214,6 +270,33 @@ fn convert_fulfillment_obj_single(f: get_order::GetOrderOrderFulfillments) -> Fu
             .collect(),
         created_at: f.created_at,
         updated_at: f.updated_at,
+        line_items: f
+            .fulfillment_line_items
+            .edges
+            .into_iter()
+            .map(|e| convert_fulfillment_line_item_single(e.node))
+            .collect(),
+    }
+}
+
+fn convert_fulfillment_line_item_single(
+    li: get_order::GetOrderOrderFulfillmentsFulfillmentLineItemsEdgesNode,
+) -> FulfillmentLineItem {
+    let line_item = li.line_item;
+    FulfillmentLineItem {
+        id: li.id,
+        quantity: li.quantity.unwrap_or(0),
+        line_item_id: line_item.id,
+        title: line_item.title,
+        variant_title: line_item.variant_title,
+        sku: line_item.sku,
+        image: line_item.image.map(|img| Image {
+            id: None,
+            url: img.url,
+            alt_text: img.alt_text,
+            width: None,
+            height: None,
+        }),
     }
 }
 
@@ -251,8 +334,8 @@ fn convert_shipping_single(a: get_order::GetOrderOrderShippingAddress) -> Addres
 
 fn convert_fulfillment_order_single(
     fo: get_order::GetOrderOrderFulfillmentOrdersEdgesNode,
-) -> FulfillmentOrderDetail {
-    FulfillmentOrderDetail {
+) -> Result<FulfillmentOrderDetail, GidError> {
+    Ok(FulfillmentOrderDetail {
         id: fo.id,
         status: format!("{:?}", fo.status),
         request_status: Some(format!("{:?}", fo.request_status)),
@@ -260,7 +343,8 @@ fn convert_fulfillment_order_single(
             .assigned_location
             .location
             .as_ref()
-            .map(|loc| loc.id.clone()),
+            .map(|loc| loc.id.as_str().try_into())
+            .transpose()?,
         location_name: Some(fo.assigned_location.name),
         supported_actions: fo
             .supported_actions
@@ -273,7 +357,7 @@ fn convert_fulfillment_order_single(
             .into_iter()
             .map(|e| convert_fulfillment_order_line_item_single(e.node))
             .collect(),
-    }
+    })
 }
 
 const fn convert_fulfillment_order_action(
@@ -325,26 +409,28 @@ fn convert_fulfillment_order_line_item_single(
 // GetOrders conversions
 // =============================================================================
 
-pub fn convert_order_connection(conn: get_orders::GetOrdersOrders) -> OrderConnection {
-    OrderConnection {
+pub fn convert_order_connection(
+    conn: get_orders::GetOrdersOrders,
+) -> Result<OrderConnection, GidError> {
+    Ok(OrderConnection {
         orders: conn
             .edges
             .into_iter()
             .map(|e| convert_order_list(e.node))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         page_info: PageInfo {
             has_next_page: conn.page_info.has_next_page,
             has_previous_page: conn.page_info.has_previous_page,
             start_cursor: conn.page_info.start_cursor,
             end_cursor: conn.page_info.end_cursor,
         },
-    }
+    })
 }
 
-fn convert_order_list(order: get_orders::GetOrdersOrdersEdgesNode) -> Order {
+fn convert_order_list(order: get_orders::GetOrdersOrdersEdgesNode) -> Result<Order, GidError> {
     let pricing = build_pricing_list(&order);
-    Order {
-        id: order.id,
+    Ok(Order {
+        id: order.id.try_into()?,
         name: order.name,
         number: order.number,
         created_at: order.created_at,
@@ -370,7 +456,7 @@ fn convert_order_list(order: get_orders::GetOrdersOrdersEdgesNode) -> Order {
             .edges
             .into_iter()
             .map(|e| convert_line_item_list(e.node))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         fulfillments: order
             .fulfillments
             .into_iter()
@@ -379,8 +465,10 @@ fn convert_order_list(order: get_orders::GetOrdersOrdersEdgesNode) -> Order {
         fulfillment_orders: vec![], // GetOrders query doesn't include fulfillment orders
         billing_address: order.billing_address.map(convert_billing_list),
         shipping_address: order.shipping_address.map(convert_shipping_list),
-        customer_id: order.customer.map(|c| c.id),
-    }
+        customer_id: order.customer.map(|c| c.id.try_into()).transpose()?,
+        exchanges: vec![],       // GetOrders query doesn't include returns
+        timeline_events: vec![], // GetOrders query doesn't include events
+    })
 }
 
 fn build_pricing_list(order: &get_orders::GetOrdersOrdersEdgesNode) -> OrderPricing {
@@ -468,8 +556,8 @@ const fn convert_fulfillment_list(
 
 fn convert_line_item_list(
     item: get_orders::GetOrdersOrdersEdgesNodeLineItemsEdgesNode,
-) -> OrderLineItem {
-    OrderLineItem {
+) -> Result<OrderLineItem, GidError> {
+    Ok(OrderLineItem {
         id: item.id,
         title: item.title,
         variant_title: item.variant_title,
@@ -493,11 +581,11 @@ fn convert_line_item_list(
                 item.total_discount_set.shop_money.currency_code,
             ),
         },
-        product_id: item.product.map(|p| p.id),
-        variant_id: item.variant.map(|v| v.id),
+        product_id: item.product.map(|p| p.id.try_into()).transpose()?,
+        variant_id: item.variant.map(|v| v.id.try_into()).transpose()?,
         requires_shipping: item.requires_shipping,
         is_gift_card: item.is_gift_card,
-    }
+    })
 }
 
 fn convert_fulfillment_obj_list(
@@ -517,6 +605,7 @@ fn convert_fulfillment_obj_list(
             .collect(),
         created_at: f.created_at,
         updated_at: f.updated_at,
+        line_items: Vec::new(),
     }
 }
 
@@ -557,26 +646,30 @@ fn convert_shipping_list(a: get_orders::GetOrdersOrdersEdgesNodeShippingAddress)
 // =============================================================================
 
 /// Convert `GetOrders` response to `OrderListConnection` with extended fields.
-pub fn convert_order_list_connection(conn: get_orders::GetOrdersOrders) -> OrderListConnection {
-    OrderListConnection {
+pub fn convert_order_list_connection(
+    conn: get_orders::GetOrdersOrders,
+) -> Result<OrderListConnection, GidError> {
+    Ok(OrderListConnection {
         orders: conn
             .edges
             .into_iter()
             .map(|e| convert_order_list_item(e.node))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         page_info: PageInfo {
             has_next_page: conn.page_info.has_next_page,
             has_previous_page: conn.page_info.has_previous_page,
             start_cursor: conn.page_info.start_cursor,
             end_cursor: conn.page_info.end_cursor,
         },
-    }
+    })
 }
 
 // Allow deprecated field usage: Shopify risks field is deprecated but we still use it
 // for backwards compatibility until OrderRiskAssessment is fully rolled out.
 #[allow(deprecated)]
-fn convert_order_list_item(order: get_orders::GetOrdersOrdersEdgesNode) -> OrderListItem {
+fn convert_order_list_item(
+    order: get_orders::GetOrdersOrdersEdgesNode,
+) -> Result<OrderListItem, GidError> {
     let pricing = build_pricing_list(&order);
 
     // Calculate total items quantity from line items
@@ -613,8 +706,8 @@ fn convert_order_list_item(order: get_orders::GetOrdersOrdersEdgesNode) -> Order
     let cancelled = order.cancelled_at.is_some();
     let closed = order.closed_at.is_some();
 
-    OrderListItem {
-        id: order.id,
+    Ok(OrderListItem {
+        id: order.id.try_into()?,
         name: order.name,
         number: order.number,
         created_at: order.created_at,
@@ -640,13 +733,19 @@ fn convert_order_list_item(order: get_orders::GetOrdersOrdersEdgesNode) -> Order
         total_tax: pricing.tax,
         total_price: pricing.total,
         total_discounts: pricing.discounts,
+        total_refunded: Money {
+            amount: order.total_refunded_set.shop_money.amount,
+            currency_code: currency_code_to_string(
+                order.total_refunded_set.shop_money.currency_code,
+            ),
+        },
         currency_code: pricing.currency,
         line_items: order
             .line_items
             .edges
             .into_iter()
             .map(|e| convert_line_item_list(e.node))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         total_items_quantity,
         fulfillments: order
             .fulfillments
@@ -655,13 +754,17 @@ fn convert_order_list_item(order: get_orders::GetOrdersOrdersEdgesNode) -> Order
             .collect(),
         billing_address: order.billing_address.map(convert_billing_list),
         shipping_address: order.shipping_address.map(convert_shipping_list),
-        customer_id: order.customer.as_ref().map(|c| c.id.clone()),
+        customer_id: order
+            .customer
+            .as_ref()
+            .map(|c| c.id.as_str().try_into())
+            .transpose()?,
         customer_name: order.customer.map(|c| c.display_name),
         risks,
         channel_info,
         shipping_line,
         discount_codes: order.discount_codes,
-    }
+    })
 }
 
 const fn convert_risk_level(level: &get_orders::OrderRiskLevel) -> OrderRiskLevel {
@@ -705,9 +808,9 @@ use crate::shopify::types::{FulfillmentOrder, FulfillmentOrderLineItem};
 /// Convert `GetFulfillmentOrders` response to `Vec<FulfillmentOrder>`.
 pub fn convert_fulfillment_orders(
     order: Option<get_fulfillment_orders::GetFulfillmentOrdersOrder>,
-) -> Vec<FulfillmentOrder> {
+) -> Result<Vec<FulfillmentOrder>, GidError> {
     let Some(order) = order else {
-        return vec![];
+        return Ok(vec![]);
     };
 
     order
@@ -720,13 +823,13 @@ pub fn convert_fulfillment_orders(
 
 fn convert_fulfillment_order(
     fo: get_fulfillment_orders::GetFulfillmentOrdersOrderFulfillmentOrdersEdgesNode,
-) -> FulfillmentOrder {
-    let (location_id, location_name) = fo
-        .assigned_location
-        .location
-        .map_or((None, None), |loc| (Some(loc.id), Some(loc.name)));
+) -> Result<FulfillmentOrder, GidError> {
+    let (location_id, location_name) = match fo.assigned_location.location {
+        Some(loc) => (Some(loc.id.try_into()?), Some(loc.name)),
+        None => (None, None),
+    };
 
-    FulfillmentOrder {
+    Ok(FulfillmentOrder {
         id: fo.id,
         status: format!("{:?}", fo.status),
         location_id,
@@ -737,7 +840,7 @@ fn convert_fulfillment_order(
             .into_iter()
             .map(|e| convert_fulfillment_order_line_item(e.node))
             .collect(),
-    }
+    })
 }
 
 fn convert_fulfillment_order_line_item(
@@ -766,23 +869,23 @@ use crate::shopify::types::{
 /// Convert `OrderEditBegin` response to `CalculatedOrder`.
 pub fn convert_calculated_order(
     data: order_edit_begin::OrderEditBeginOrderEditBeginCalculatedOrder,
-) -> CalculatedOrder {
-    CalculatedOrder {
+) -> Result<CalculatedOrder, GidError> {
+    Ok(CalculatedOrder {
         id: data.id,
-        original_order_id: data.original_order.id,
+        original_order_id: data.original_order.id.try_into()?,
         original_order_name: data.original_order.name,
         line_items: data
             .line_items
             .edges
             .into_iter()
             .map(|e| convert_calculated_line_item_begin(e.node))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         added_line_items: data
             .added_line_items
             .edges
             .into_iter()
             .map(|e| convert_calculated_line_item_added(e.node))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         shipping_lines: data
             .shipping_lines
             .into_iter()
@@ -806,13 +909,13 @@ pub fn convert_calculated_order(
         },
         subtotal_line_items_quantity: data.subtotal_line_items_quantity,
         notification_preview_title: Some(data.notification_preview_title),
-    }
+    })
 }
 
 fn convert_calculated_line_item_begin(
     item: order_edit_begin::OrderEditBeginOrderEditBeginCalculatedOrderLineItemsEdgesNode,
-) -> CalculatedLineItem {
-    CalculatedLineItem {
+) -> Result<CalculatedLineItem, GidError> {
+    Ok(CalculatedLineItem {
         id: item.id,
         title: item.title,
         variant_title: item.variant_title,
@@ -848,7 +951,7 @@ fn convert_calculated_line_item_begin(
             width: None,
             height: None,
         }),
-        variant_id: item.variant.map(|v| v.id),
+        variant_id: item.variant.map(|v| v.id.try_into()).transpose()?,
         discount_allocations: item
             .calculated_discount_allocations
             .into_iter()
@@ -862,13 +965,13 @@ fn convert_calculated_line_item_begin(
                 description: None,
             })
             .collect(),
-    }
+    })
 }
 
 fn convert_calculated_line_item_added(
     item: order_edit_begin::OrderEditBeginOrderEditBeginCalculatedOrderAddedLineItemsEdgesNode,
-) -> CalculatedLineItem {
-    CalculatedLineItem {
+) -> Result<CalculatedLineItem, GidError> {
+    Ok(CalculatedLineItem {
         id: item.id,
         title: item.title,
         variant_title: item.variant_title,
@@ -904,9 +1007,9 @@ fn convert_calculated_line_item_added(
             width: None,
             height: None,
         }),
-        variant_id: item.variant.map(|v| v.id),
+        variant_id: item.variant.map(|v| v.id.try_into()).transpose()?,
         discount_allocations: vec![],
-    }
+    })
 }
 
 fn convert_calculated_shipping_line(
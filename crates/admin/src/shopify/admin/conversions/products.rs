@@ -1,8 +1,10 @@
 //! Product type conversion functions.
 
+use naked_pineapple_core::GidError;
+
 use crate::shopify::types::{
-    AdminProduct, AdminProductConnection, AdminProductVariant, Image, Money, PageInfo,
-    ProductStatus,
+    AdminProduct, AdminProductConnection, AdminProductVariant, CollectionRef, Image, Money,
+    PageInfo, ProductStatus, Publication, ResourcePublication, VariantDimensions,
 };
 
 use super::super::queries::{get_product, get_products};
@@ -12,7 +14,7 @@ use super::currency_code_to_string;
 // GetProduct conversions
 // =============================================================================
 
-pub fn convert_product(product: get_product::GetProductProduct) -> AdminProduct {
+pub fn convert_product(product: get_product::GetProductProduct) -> Result<AdminProduct, GidError> {
     let status = match product.status {
         get_product::ProductStatus::ACTIVE => ProductStatus::Active,
         get_product::ProductStatus::ARCHIVED => ProductStatus::Archived,
@@ -22,8 +24,8 @@ pub fn convert_product(product: get_product::GetProductProduct) -> AdminProduct
         }
     };
 
-    AdminProduct {
-        id: product.id,
+    Ok(AdminProduct {
+        id: product.id.try_into()?,
         handle: product.handle,
         title: product.title,
         description: product.description,
@@ -67,32 +69,69 @@ pub fn convert_product(product: get_product::GetProductProduct) -> AdminProduct
             .variants
             .edges
             .into_iter()
-            .map(|e| convert_variant_single(e.node))
+            .map(|e| convert_variant(e.node))
+            .collect::<Result<_, _>>()?,
+        collections: product
+            .collections
+            .edges
+            .into_iter()
+            .map(|e| {
+                Ok(CollectionRef {
+                    id: e.node.id.try_into()?,
+                    title: e.node.title,
+                    handle: e.node.handle,
+                    image_url: e.node.image.map(|i| i.url),
+                    is_smart: e.node.rule_set.is_some(),
+                })
+            })
+            .collect::<Result<_, GidError>>()?,
+        publications: product
+            .resource_publications_v2
+            .edges
+            .into_iter()
+            .map(|e| ResourcePublication {
+                publication: Publication {
+                    id: e.node.publication.id.clone(),
+                    #[allow(deprecated)]
+                    name: e
+                        .node
+                        .publication
+                        .catalog
+                        .map(|c| c.title)
+                        .unwrap_or(e.node.publication.name),
+                },
+                is_published: e.node.is_published,
+            })
             .collect(),
-    }
+    })
 }
 
-fn convert_variant_single(
+pub fn convert_variant(
     v: get_product::GetProductProductVariantsEdgesNode,
-) -> AdminProductVariant {
-    // Extract image from first media item's preview
-    let image = v
-        .media
-        .edges
-        .into_iter()
-        .next()
-        .and_then(|e| e.node.preview)
-        .and_then(|p| p.image)
-        .map(|i| Image {
-            id: i.id,
+) -> Result<AdminProductVariant, GidError> {
+    // Extract image from first media item's preview, keeping the media node's
+    // id (MediaImage) so the image can be detached from the variant
+    let image = v.media.edges.into_iter().next().and_then(|e| {
+        let media_id = e.node.id;
+        e.node.preview.and_then(|p| p.image).map(|i| Image {
+            id: Some(media_id),
             url: i.url,
             alt_text: i.alt_text,
             width: i.width,
             height: i.height,
-        });
+        })
+    });
 
-    AdminProductVariant {
-        id: v.id,
+    let weight = v.inventory_item.measurement.weight;
+    let dimensions = VariantDimensions::from_metafields(
+        v.metafields
+            .edges
+            .iter()
+            .map(|e| (e.node.key.as_str(), e.node.value.as_str())),
+    );
+
+    Ok(AdminProductVariant {
+        id: v.id.try_into()?,
         title: v.title,
         sku: v.sku,
         barcode: v.barcode,
@@ -105,15 +144,16 @@ fn convert_variant_single(
             currency_code: "USD".to_string(),
         }),
         inventory_quantity: v.inventory_quantity.unwrap_or(0),
-        inventory_item_id: v.inventory_item.id.clone(),
+        inventory_item_id: v.inventory_item.id.try_into()?,
         inventory_management: Some(format!("{:?}", v.inventory_policy)),
-        weight: None, // Weight not included in query
-        weight_unit: None,
+        weight: weight.as_ref().map(|w| w.value),
+        weight_unit: weight.map(|w| format!("{:?}", w.unit)),
+        dimensions,
         requires_shipping: v.inventory_item.requires_shipping,
         image,
         created_at: Some(v.created_at),
         updated_at: Some(v.updated_at),
-    }
+    })
 }
 
 // =============================================================================
@@ -122,25 +162,25 @@ fn convert_variant_single(
 
 pub fn convert_product_connection(
     conn: get_products::GetProductsProducts,
-) -> AdminProductConnection {
-    AdminProductConnection {
+) -> Result<AdminProductConnection, GidError> {
+    Ok(AdminProductConnection {
         products: conn
             .edges
             .into_iter()
             .map(|e| convert_products_list_product(e.node))
-            .collect(),
+            .collect::<Result<_, _>>()?,
         page_info: PageInfo {
             has_next_page: conn.page_info.has_next_page,
             has_previous_page: conn.page_info.has_previous_page,
             start_cursor: conn.page_info.start_cursor,
             end_cursor: conn.page_info.end_cursor,
         },
-    }
+    })
 }
 
 fn convert_products_list_product(
     product: get_products::GetProductsProductsEdgesNode,
-) -> AdminProduct {
+) -> Result<AdminProduct, GidError> {
     let status = match product.status {
         get_products::ProductStatus::ACTIVE => ProductStatus::Active,
         get_products::ProductStatus::ARCHIVED => ProductStatus::Archived,
@@ -150,8 +190,8 @@ fn convert_products_list_product(
         }
     };
 
-    AdminProduct {
-        id: product.id,
+    Ok(AdminProduct {
+        id: product.id.try_into()?,
         handle: product.handle,
         title: product.title,
         description: product.description,
@@ -196,13 +236,16 @@ fn convert_products_list_product(
             .edges
             .into_iter()
             .map(|e| convert_products_list_variant(e.node))
-            .collect(),
-    }
+            .collect::<Result<_, _>>()?,
+        // Not selected by GetProducts; fetched with the single product
+        collections: Vec::new(),
+        publications: Vec::new(),
+    })
 }
 
 fn convert_products_list_variant(
     v: get_products::GetProductsProductsEdgesNodeVariantsEdgesNode,
-) -> AdminProductVariant {
+) -> Result<AdminProductVariant, GidError> {
     // Extract image from first media item's preview
     let image = v
         .media
@@ -219,8 +262,8 @@ fn convert_products_list_variant(
             height: i.height,
         });
 
-    AdminProductVariant {
-        id: v.id,
+    Ok(AdminProductVariant {
+        id: v.id.try_into()?,
         title: v.title,
         sku: v.sku,
         barcode: v.barcode,
@@ -233,13 +276,14 @@ fn convert_products_list_variant(
             currency_code: "USD".to_string(),
         }),
         inventory_quantity: v.inventory_quantity.unwrap_or(0),
-        inventory_item_id: v.inventory_item.id.clone(),
+        inventory_item_id: v.inventory_item.id.try_into()?,
         inventory_management: Some(format!("{:?}", v.inventory_policy)),
         weight: None, // Weight not included in query
         weight_unit: None,
+        dimensions: None,
         requires_shipping: v.inventory_item.requires_shipping,
         image,
         created_at: Some(v.created_at),
         updated_at: Some(v.updated_at),
-    }
+    })
 }
@@ -0,0 +1,172 @@
+//! Query cost tracking for the Admin API.
+//!
+//! Shopify rate-limits GraphQL by query cost, using a leaky bucket of points
+//! per shop. Every response carries `extensions.cost` with what the query
+//! cost and how full the bucket is. The client keeps the latest
+//! [`ThrottleStatus`], so callers running large batches can slow down before
+//! Shopify starts answering `429`, and logs a warning when the bucket runs
+//! low.
+
+use serde::Deserialize;
+
+use super::AdminClient;
+
+/// Fraction of the bucket below which a warning is logged.
+const LOW_BUDGET_FRACTION: f64 = 0.2;
+
+/// State of the shop's query cost bucket after the latest request.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThrottleStatus {
+    /// Bucket size in cost points.
+    pub maximum_available: f64,
+    /// Points left in the bucket.
+    pub currently_available: f64,
+    /// Points restored per second.
+    pub restore_rate: f64,
+}
+
+impl ThrottleStatus {
+    /// Whether less than 20% of the bucket is left.
+    #[must_use]
+    pub const fn is_low(&self) -> bool {
+        self.currently_available < self.maximum_available * LOW_BUDGET_FRACTION
+    }
+
+    /// Seconds until `cost` points are available again (zero if they
+    /// already are).
+    #[must_use]
+    pub const fn seconds_until_available(&self, cost: f64) -> f64 {
+        if self.currently_available >= cost || self.restore_rate <= 0.0 {
+            return 0.0;
+        }
+        (cost - self.currently_available) / self.restore_rate
+    }
+}
+
+/// The `extensions` object of a GraphQL response.
+#[derive(Debug, Default, Deserialize)]
+pub(super) struct ResponseExtensions {
+    cost: Option<QueryCost>,
+}
+
+/// `extensions.cost` of a GraphQL response.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryCost {
+    actual_query_cost: Option<f64>,
+    throttle_status: ThrottleStatus,
+}
+
+impl AdminClient {
+    /// Query cost bucket state reported with the latest response, if any
+    /// response has reported one yet.
+    pub async fn cost_status(&self) -> Option<ThrottleStatus> {
+        *self.inner.throttle_status.read().await
+    }
+
+    /// Store the throttle status from a response's `extensions`.
+    ///
+    /// Warns when the bucket drops below 20%, once per drop rather than on
+    /// every request made while it's low.
+    pub(super) async fn record_cost(&self, extensions: Option<ResponseExtensions>) {
+        let Some(cost) = extensions.and_then(|e| e.cost) else {
+            return;
+        };
+        let status = cost.throttle_status;
+
+        let previous = self.inner.throttle_status.write().await.replace(status);
+        if status.is_low() && !previous.as_ref().is_some_and(ThrottleStatus::is_low) {
+            tracing::warn!(
+                shop = %self.inner.store,
+                currently_available = status.currently_available,
+                maximum_available = status.maximum_available,
+                restore_rate = status.restore_rate,
+                actual_query_cost = cost.actual_query_cost,
+                "Shopify query cost budget is running low"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use secrecy::SecretString;
+
+    use super::*;
+    use crate::config::ShopifyAdminConfig;
+
+    fn extensions(currently_available: f64) -> ResponseExtensions {
+        serde_json::from_value(serde_json::json!({
+            "cost": {
+                "requestedQueryCost": 12,
+                "actualQueryCost": 10,
+                "throttleStatus": {
+                    "maximumAvailable": 2000.0,
+                    "currentlyAvailable": currently_available,
+                    "restoreRate": 100.0
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn client() -> AdminClient {
+        AdminClient::new(&ShopifyAdminConfig {
+            store: "test.myshopify.com".to_string(),
+            api_version: "2026-01".to_string(),
+            client_id: "test_client_id".to_string(),
+            client_secret: SecretString::from("test_client_secret"),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_is_low() {
+        let status = |currently_available| ThrottleStatus {
+            maximum_available: 1000.0,
+            currently_available,
+            restore_rate: 50.0,
+        };
+        assert!(status(199.0).is_low());
+        assert!(!status(200.0).is_low());
+    }
+
+    #[test]
+    fn test_seconds_until_available() {
+        let status = ThrottleStatus {
+            maximum_available: 1000.0,
+            currently_available: 100.0,
+            restore_rate: 50.0,
+        };
+        assert!((status.seconds_until_available(300.0) - 4.0).abs() < f64::EPSILON);
+        assert!(status.seconds_until_available(50.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_record_cost_keeps_latest_status() {
+        let client = client();
+        assert!(client.cost_status().await.is_none());
+
+        client.record_cost(Some(extensions(1990.0))).await;
+        client.record_cost(Some(extensions(300.0))).await;
+
+        let status = client.cost_status().await.unwrap();
+        assert!((status.currently_available - 300.0).abs() < f64::EPSILON);
+        assert!(status.is_low());
+    }
+
+    #[tokio::test]
+    async fn test_response_without_cost_keeps_previous_status() {
+        let client = client();
+        client.record_cost(Some(extensions(1500.0))).await;
+
+        client.record_cost(None).await;
+        client
+            .record_cost(Some(ResponseExtensions::default()))
+            .await;
+
+        assert!(client.cost_status().await.is_some());
+    }
+}
@@ -1,6 +1,7 @@
 //! Customer management operations for the Admin API.
 
 use graphql_client::GraphQLQuery;
+use naked_pineapple_core::CustomerId;
 use tracing::instrument;
 
 use super::{
@@ -16,7 +17,7 @@ use super::{
     sort_customers,
 };
 use crate::shopify::types::{
-    Address, AddressInput, Customer, CustomerConnection, CustomerListParams,
+    Address, AddressInput, CountryCode, Customer, CustomerConnection, CustomerListParams,
     CustomerMergeOverrides, CustomerSortKey, CustomerUpdateParams,
 };
 
@@ -31,7 +32,10 @@ impl AdminClient {
     ///
     /// Returns an error if the API request fails or returns an error response.
     #[instrument(skip(self), fields(customer_id = %id))]
-    pub async fn get_customer(&self, id: &str) -> Result<Option<Customer>, AdminShopifyError> {
+    pub async fn get_customer(
+        &self,
+        id: &CustomerId,
+    ) -> Result<Option<Customer>, AdminShopifyError> {
         let variables = super::queries::get_customer::Variables {
             id: id.to_string(),
             address_count: Some(10),
@@ -40,7 +44,7 @@ impl AdminClient {
 
         let response = self.execute::<GetCustomer>(variables).await?;
 
-        Ok(response.customer.map(convert_customer))
+        Ok(response.customer.map(convert_customer).transpose()?)
     }
 
     /// Get a paginated list of customers.
@@ -84,7 +88,7 @@ impl AdminClient {
         };
 
         let response = self.execute::<GetCustomers>(variables).await?;
-        let mut connection = convert_customer_connection(response.customers);
+        let mut connection = convert_customer_connection(response.customers)?;
 
         if client_side_sort && let Some(sk) = params.sort_key {
             sort_customers(&mut connection.customers, sk, params.reverse);
@@ -188,7 +192,7 @@ impl AdminClient {
     #[instrument(skip(self, params))]
     pub async fn update_customer(
         &self,
-        id: &str,
+        id: &CustomerId,
         params: CustomerUpdateParams,
     ) -> Result<String, AdminShopifyError> {
         use super::queries::customer_update::{CustomerInput, Variables};
@@ -258,7 +262,7 @@ impl AdminClient {
     /// Returns an error if the API request fails, the customer has orders,
     /// or returns user errors.
     #[instrument(skip(self), fields(customer_id = %id))]
-    pub async fn delete_customer(&self, id: &str) -> Result<String, AdminShopifyError> {
+    pub async fn delete_customer(&self, id: &CustomerId) -> Result<String, AdminShopifyError> {
         use super::queries::customer_delete::{CustomerDeleteInput, Variables};
 
         let variables = Variables {
@@ -310,7 +314,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(customer_id = %id))]
     pub async fn add_customer_tags(
         &self,
-        id: &str,
+        id: &CustomerId,
         tags: Vec<String>,
     ) -> Result<Vec<String>, AdminShopifyError> {
         use super::queries::tags_add::Variables;
@@ -363,7 +367,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(customer_id = %id))]
     pub async fn remove_customer_tags(
         &self,
-        id: &str,
+        id: &CustomerId,
         tags: Vec<String>,
     ) -> Result<Vec<String>, AdminShopifyError> {
         use super::queries::tags_remove::Variables;
@@ -409,7 +413,10 @@ impl AdminClient {
     ///
     /// Returns an error if the API request fails or returns user errors.
     #[instrument(skip(self), fields(customer_id = %customer_id))]
-    pub async fn send_customer_invite(&self, customer_id: &str) -> Result<(), AdminShopifyError> {
+    pub async fn send_customer_invite(
+        &self,
+        customer_id: &CustomerId,
+    ) -> Result<(), AdminShopifyError> {
         use super::queries::customer_send_account_invite_email::Variables;
 
         let variables = Variables {
@@ -458,7 +465,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(customer_id = %customer_id))]
     pub async fn generate_customer_activation_url(
         &self,
-        customer_id: &str,
+        customer_id: &CustomerId,
     ) -> Result<String, AdminShopifyError> {
         use super::queries::customer_generate_account_activation_url::Variables;
 
@@ -508,29 +515,15 @@ impl AdminClient {
     #[instrument(skip(self, address), fields(customer_id = %customer_id))]
     pub async fn create_customer_address(
         &self,
-        customer_id: &str,
+        customer_id: &CustomerId,
         address: AddressInput,
     ) -> Result<Address, AdminShopifyError> {
-        use super::queries::customer_address_create::{
-            CountryCode, MailingAddressInput, Variables,
-        };
+        use super::queries::customer_address_create::{MailingAddressInput, Variables};
 
-        let country_code =
-            address
-                .country_code
-                .and_then(|code| match code.to_uppercase().as_str() {
-                    "US" => Some(CountryCode::US),
-                    "CA" => Some(CountryCode::CA),
-                    "GB" => Some(CountryCode::GB),
-                    "AU" => Some(CountryCode::AU),
-                    "DE" => Some(CountryCode::DE),
-                    "FR" => Some(CountryCode::FR),
-                    "JP" => Some(CountryCode::JP),
-                    "CN" => Some(CountryCode::CN),
-                    "MX" => Some(CountryCode::MX),
-                    "BR" => Some(CountryCode::BR),
-                    _ => None,
-                });
+        let country_code = address
+            .country_code
+            .as_deref()
+            .and_then(CountryCode::from_iso_2);
 
         let variables = Variables {
             customer_id: customer_id.to_string(),
@@ -570,7 +563,7 @@ impl AdminClient {
                     address2: addr.address2,
                     city: addr.city,
                     province_code: addr.province_code,
-                    country_code: addr.country_code_v2.map(|c| format!("{c:?}")),
+                    country_code: addr.country_code_v2.map(|c| c.as_str().to_string()),
                     zip: addr.zip,
                     first_name: addr.first_name,
                     last_name: addr.last_name,
@@ -601,30 +594,16 @@ impl AdminClient {
     #[instrument(skip(self, address), fields(customer_id = %customer_id, address_id = %address_id))]
     pub async fn update_customer_address(
         &self,
-        customer_id: &str,
+        customer_id: &CustomerId,
         address_id: &str,
         address: AddressInput,
     ) -> Result<Address, AdminShopifyError> {
-        use super::queries::customer_address_update::{
-            CountryCode, MailingAddressInput, Variables,
-        };
+        use super::queries::customer_address_update::{MailingAddressInput, Variables};
 
-        let country_code =
-            address
-                .country_code
-                .and_then(|code| match code.to_uppercase().as_str() {
-                    "US" => Some(CountryCode::US),
-                    "CA" => Some(CountryCode::CA),
-                    "GB" => Some(CountryCode::GB),
-                    "AU" => Some(CountryCode::AU),
-                    "DE" => Some(CountryCode::DE),
-                    "FR" => Some(CountryCode::FR),
-                    "JP" => Some(CountryCode::JP),
-                    "CN" => Some(CountryCode::CN),
-                    "MX" => Some(CountryCode::MX),
-                    "BR" => Some(CountryCode::BR),
-                    _ => None,
-                });
+        let country_code = address
+            .country_code
+            .as_deref()
+            .and_then(CountryCode::from_iso_2);
 
         let variables = Variables {
             customer_id: customer_id.to_string(),
@@ -665,7 +644,7 @@ impl AdminClient {
                     address2: addr.address2,
                     city: addr.city,
                     province_code: addr.province_code,
-                    country_code: addr.country_code_v2.map(|c| format!("{c:?}")),
+                    country_code: addr.country_code_v2.map(|c| c.as_str().to_string()),
                     zip: addr.zip,
                     first_name: addr.first_name,
                     last_name: addr.last_name,
@@ -695,7 +674,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(customer_id = %customer_id, address_id = %address_id))]
     pub async fn delete_customer_address(
         &self,
-        customer_id: &str,
+        customer_id: &CustomerId,
         address_id: &str,
     ) -> Result<String, AdminShopifyError> {
         use super::queries::customer_address_delete::Variables;
@@ -746,7 +725,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(customer_id = %customer_id, address_id = %address_id))]
     pub async fn set_customer_default_address(
         &self,
-        customer_id: &str,
+        customer_id: &CustomerId,
         address_id: &str,
     ) -> Result<(), AdminShopifyError> {
         use super::queries::customer_update_default_address::Variables;
@@ -796,7 +775,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(customer_id = %customer_id))]
     pub async fn update_customer_email_marketing(
         &self,
-        customer_id: &str,
+        customer_id: &CustomerId,
         marketing_state: &str,
     ) -> Result<(), AdminShopifyError> {
         use super::queries::customer_email_marketing_consent_update::{
@@ -863,7 +842,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(customer_id = %customer_id))]
     pub async fn update_customer_sms_marketing(
         &self,
-        customer_id: &str,
+        customer_id: &CustomerId,
         marketing_state: &str,
     ) -> Result<(), AdminShopifyError> {
         use super::queries::customer_sms_marketing_consent_update::{
@@ -931,8 +910,8 @@ impl AdminClient {
     #[instrument(skip(self), fields(customer_one_id = %customer_one_id, customer_two_id = %customer_two_id))]
     pub async fn merge_customers(
         &self,
-        customer_one_id: &str,
-        customer_two_id: &str,
+        customer_one_id: &CustomerId,
+        customer_two_id: &CustomerId,
         overrides: CustomerMergeOverrides,
     ) -> Result<String, AdminShopifyError> {
         use super::queries::customer_merge::{CustomerMergeOverrideFields, Variables};
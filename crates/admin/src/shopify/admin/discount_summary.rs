@@ -0,0 +1,277 @@
+//! Discount performance summary for the Admin API.
+//!
+//! Shopify only reports lifetime usage on a discount, so redemptions within a
+//! period are counted from the orders placed in it. The period is split into
+//! one-day windows fetched concurrently, bounded by a semaphore to stay
+//! within the API's rate limits.
+
+use std::collections::HashMap;
+
+use chrono::{Days, NaiveDate};
+use futures::future::try_join_all;
+use rust_decimal::Decimal;
+use tokio::sync::Semaphore;
+use tracing::instrument;
+
+use super::{
+    AdminClient, AdminShopifyError,
+    queries::{GetDiscountSummaryNodes, GetDiscountSummaryOrders},
+};
+use crate::shopify::types::{DiscountCodeUsage, DiscountSummary, Money};
+
+/// Nodes fetched per request when paging through discounts and orders.
+const PAGE_SIZE: i64 = 250;
+
+/// Maximum day windows fetched at once.
+const MAX_CONCURRENT_WINDOWS: usize = 4;
+
+/// Number of codes in [`DiscountSummary::top_codes`].
+const TOP_CODES_LIMIT: usize = 10;
+
+/// Currency reported when no orders used a code.
+const DEFAULT_CURRENCY: &str = "USD";
+
+/// An order placed with at least one discount code.
+#[derive(Debug, Clone)]
+struct DiscountedOrder {
+    codes: Vec<String>,
+    total_discount: Decimal,
+    currency_code: String,
+}
+
+impl AdminClient {
+    /// Summarize discount code performance between two dates (inclusive).
+    ///
+    /// # Arguments
+    ///
+    /// * `period_start` - First day of the period (`YYYY-MM-DD`)
+    /// * `period_end` - Last day of the period (`YYYY-MM-DD`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a date isn't `YYYY-MM-DD` or an API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_discount_summary(
+        &self,
+        period_start: &str,
+        period_end: &str,
+    ) -> Result<DiscountSummary, AdminShopifyError> {
+        let start = parse_date(period_start)?;
+        let end = parse_date(period_end)?;
+
+        let semaphore = &Semaphore::new(MAX_CONCURRENT_WINDOWS);
+        let windows = start
+            .iter_days()
+            .take_while(|day| *day <= end)
+            .map(|day| async move {
+                // The semaphore is never closed, so acquiring can't fail
+                let _permit = semaphore.acquire().await.ok();
+                self.get_discounted_orders(day).await
+            });
+
+        let (total_active_codes, orders) = tokio::try_join!(
+            self.count_discount_nodes("method:code status:active"),
+            try_join_all(windows),
+        )?;
+
+        Ok(summarize(
+            total_active_codes,
+            &orders.into_iter().flatten().collect::<Vec<_>>(),
+        ))
+    }
+
+    /// Count discounts matching a search query.
+    async fn count_discount_nodes(&self, query: &str) -> Result<i64, AdminShopifyError> {
+        let mut count = 0;
+        let mut after = None;
+
+        loop {
+            let variables = super::queries::get_discount_summary_nodes::Variables {
+                first: PAGE_SIZE,
+                after,
+                query: Some(query.to_string()),
+            };
+            let response = self.execute::<GetDiscountSummaryNodes>(variables).await?;
+            let connection = response.discount_nodes;
+
+            count += i64::try_from(connection.nodes.len()).unwrap_or(i64::MAX);
+            match connection.page_info.end_cursor {
+                Some(cursor) if connection.page_info.has_next_page => after = Some(cursor),
+                _ => return Ok(count),
+            }
+        }
+    }
+
+    /// Fetch the orders placed on `day` that used a discount code.
+    async fn get_discounted_orders(
+        &self,
+        day: NaiveDate,
+    ) -> Result<Vec<DiscountedOrder>, AdminShopifyError> {
+        let next_day = day.checked_add_days(Days::new(1)).unwrap_or(day);
+        let query = format!("created_at:>={day} created_at:<{next_day}");
+
+        let mut orders = Vec::new();
+        let mut after = None;
+
+        loop {
+            let variables = super::queries::get_discount_summary_orders::Variables {
+                first: PAGE_SIZE,
+                after,
+                query: query.clone(),
+            };
+            let response = self.execute::<GetDiscountSummaryOrders>(variables).await?;
+            let connection = response.orders;
+
+            orders.extend(
+                connection
+                    .nodes
+                    .into_iter()
+                    .filter(|order| !order.discount_codes.is_empty())
+                    .map(|order| {
+                        let (total_discount, currency_code) = order
+                            .total_discounts_set
+                            .map(|set| {
+                                (
+                                    set.shop_money.amount.parse().unwrap_or_default(),
+                                    format!("{:?}", set.shop_money.currency_code),
+                                )
+                            })
+                            .unwrap_or_default();
+                        DiscountedOrder {
+                            codes: order.discount_codes,
+                            total_discount,
+                            currency_code,
+                        }
+                    }),
+            );
+
+            match connection.page_info.end_cursor {
+                Some(cursor) if connection.page_info.has_next_page => after = Some(cursor),
+                _ => return Ok(orders),
+            }
+        }
+    }
+}
+
+/// Parse a `YYYY-MM-DD` date.
+fn parse_date(date: &str) -> Result<NaiveDate, AdminShopifyError> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+        AdminShopifyError::UserError(format!("Invalid date {date:?}, expected YYYY-MM-DD"))
+    })
+}
+
+/// Aggregate discounted orders into a [`DiscountSummary`].
+///
+/// Each code on an order counts as one redemption. An order's discount total
+/// is counted once, however many codes it used.
+fn summarize(total_active_codes: i64, orders: &[DiscountedOrder]) -> DiscountSummary {
+    let mut usage: HashMap<String, i64> = HashMap::new();
+    for code in orders.iter().flat_map(|order| &order.codes) {
+        *usage.entry(code.to_uppercase()).or_default() += 1;
+    }
+
+    let mut top_codes: Vec<DiscountCodeUsage> = usage
+        .into_iter()
+        .map(|(code, usage_count)| DiscountCodeUsage { code, usage_count })
+        .collect();
+    top_codes.sort_by(|a, b| {
+        b.usage_count
+            .cmp(&a.usage_count)
+            .then_with(|| a.code.cmp(&b.code))
+    });
+    let total_redemptions_in_period = top_codes.iter().map(|c| c.usage_count).sum();
+    top_codes.truncate(TOP_CODES_LIMIT);
+
+    let total_discount: Decimal = orders.iter().map(|order| order.total_discount).sum();
+    let currency_code = orders
+        .iter()
+        .map(|order| order.currency_code.as_str())
+        .find(|code| !code.is_empty())
+        .unwrap_or(DEFAULT_CURRENCY)
+        .to_string();
+
+    DiscountSummary {
+        total_active_codes,
+        total_redemptions_in_period,
+        total_discount_value_in_period: Money {
+            amount: total_discount.round_dp(2).to_string(),
+            currency_code,
+        },
+        top_codes,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn order(codes: &[&str], total_discount: &str) -> DiscountedOrder {
+        DiscountedOrder {
+            codes: codes.iter().map(ToString::to_string).collect(),
+            total_discount: total_discount.parse().unwrap(),
+            currency_code: "CAD".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_summarize_counts_codes_case_insensitively() {
+        let summary = summarize(
+            7,
+            &[
+                order(&["SUMMER10"], "5.00"),
+                order(&["summer10", "FREESHIP"], "12.50"),
+                order(&["FREESHIP"], "8.25"),
+                order(&["WELCOME"], "3.00"),
+            ],
+        );
+
+        assert_eq!(summary.total_active_codes, 7);
+        assert_eq!(summary.total_redemptions_in_period, 5);
+        assert_eq!(summary.total_discount_value_in_period.amount, "28.75");
+        assert_eq!(summary.total_discount_value_in_period.currency_code, "CAD");
+        assert_eq!(
+            summary.top_codes,
+            vec![
+                DiscountCodeUsage {
+                    code: "FREESHIP".to_string(),
+                    usage_count: 2
+                },
+                DiscountCodeUsage {
+                    code: "SUMMER10".to_string(),
+                    usage_count: 2
+                },
+                DiscountCodeUsage {
+                    code: "WELCOME".to_string(),
+                    usage_count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summarize_keeps_top_ten_codes() {
+        let orders: Vec<_> = (0..12)
+            .map(|i| order(&[format!("CODE{i:02}").as_str()], "1"))
+            .collect();
+        let summary = summarize(0, &orders);
+
+        assert_eq!(summary.total_redemptions_in_period, 12);
+        assert_eq!(summary.top_codes.len(), TOP_CODES_LIMIT);
+    }
+
+    #[test]
+    fn test_summarize_without_orders() {
+        let summary = summarize(3, &[]);
+        assert_eq!(summary.total_redemptions_in_period, 0);
+        assert_eq!(summary.total_discount_value_in_period.amount, "0");
+        assert_eq!(summary.total_discount_value_in_period.currency_code, "USD");
+        assert!(summary.top_codes.is_empty());
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert!(parse_date("2026-02-01").is_ok());
+        assert!(parse_date("02/01/2026").is_err());
+    }
+}
@@ -3,21 +3,25 @@
 use tracing::instrument;
 
 use super::{
-    AdminClient, AdminShopifyError, DiscountCreateInput, DiscountUpdateInput, GraphQLError,
+    AdminClient, AdminShopifyError, CustomerEligibility, DiscountCreateInput, DiscountItems,
+    DiscountUpdateInput, GraphQLError,
     queries::{
         DiscountAutomaticActivate, DiscountAutomaticDeactivate, DiscountAutomaticDelete,
         DiscountCodeActivate, DiscountCodeBasicCreate, DiscountCodeBasicUpdate,
         DiscountCodeBulkActivate, DiscountCodeBulkDeactivate, DiscountCodeBulkDelete,
-        DiscountCodeDeactivate, DiscountCodeDelete, GetCustomerSegments, GetDiscountCode,
-        GetDiscountCodes, GetDiscountNodes,
+        DiscountCodeDeactivate, DiscountCodeDelete, GetDiscountCode, GetDiscountCodes,
+        GetDiscountNodeCodes, GetDiscountNodes,
     },
 };
 use crate::shopify::types::{
-    CustomerSegment, DiscountCode, DiscountCodeConnection, DiscountCombinesWith,
-    DiscountListConnection, DiscountListItem, DiscountMethod, DiscountMinimumRequirement,
-    DiscountSortKey, DiscountStatus, DiscountType, DiscountValue, PageInfo,
+    DiscountCode, DiscountCodeConnection, DiscountCombinesWith, DiscountListConnection,
+    DiscountListItem, DiscountMethod, DiscountMinimumRequirement, DiscountSortKey, DiscountStatus,
+    DiscountType, DiscountValue, PageInfo,
 };
 
+/// Redeem codes fetched per request when paging through a discount's codes.
+const DISCOUNT_CODES_PAGE_SIZE: i64 = 250;
+
 /// Convert GraphQL discount status to domain type.
 const fn convert_discount_status(
     status: &super::queries::get_discount_codes::DiscountStatus,
@@ -42,6 +46,108 @@ const fn convert_discount_status_single(
     }
 }
 
+/// Build the entitled items input for a basic discount.
+///
+/// An empty collection list entitles all items.
+fn build_discount_items(
+    items: DiscountItems,
+) -> super::queries::discount_code_basic_create::DiscountItemsInput {
+    use super::queries::discount_code_basic_create::{
+        DiscountCollectionsInput, DiscountItemsInput, DiscountProductsInput,
+    };
+
+    match items {
+        DiscountItems::Collections(collection_ids) if !collection_ids.is_empty() => {
+            DiscountItemsInput {
+                all: None,
+                collections: Some(DiscountCollectionsInput {
+                    add: Some(collection_ids.into_iter().map(String::from).collect()),
+                    remove: None,
+                }),
+                products: None,
+            }
+        }
+        DiscountItems::Products {
+            product_ids,
+            variant_ids,
+        } => DiscountItemsInput {
+            all: None,
+            collections: None,
+            products: Some(DiscountProductsInput {
+                products_to_add: Some(product_ids.into_iter().map(String::from).collect()),
+                products_to_remove: None,
+                product_variants_to_add: Some(variant_ids.into_iter().map(String::from).collect()),
+                product_variants_to_remove: None,
+            }),
+        },
+        DiscountItems::All | DiscountItems::Collections(_) => DiscountItemsInput {
+            all: Some(true),
+            collections: None,
+            products: None,
+        },
+    }
+}
+
+/// Convert a domain minimum requirement to the GraphQL input type.
+fn build_minimum_requirement(
+    requirement: DiscountMinimumRequirement,
+) -> Option<super::queries::discount_code_basic_create::DiscountMinimumRequirementInput> {
+    use super::queries::discount_code_basic_create::{
+        DiscountMinimumQuantityInput, DiscountMinimumRequirementInput, DiscountMinimumSubtotalInput,
+    };
+
+    match requirement {
+        DiscountMinimumRequirement::None => None,
+        DiscountMinimumRequirement::Quantity { quantity } => {
+            Some(DiscountMinimumRequirementInput {
+                quantity: Some(DiscountMinimumQuantityInput {
+                    greater_than_or_equal_to_quantity: Some(quantity),
+                }),
+                subtotal: None,
+            })
+        }
+        DiscountMinimumRequirement::Subtotal { amount, .. } => {
+            Some(DiscountMinimumRequirementInput {
+                quantity: None,
+                subtotal: Some(DiscountMinimumSubtotalInput {
+                    greater_than_or_equal_to_subtotal: Some(amount),
+                }),
+            })
+        }
+    }
+}
+
+/// Convert customer eligibility to the GraphQL discount context.
+///
+/// `None` leaves the discount open to all customers.
+fn build_discount_context(
+    eligibility: CustomerEligibility,
+) -> Option<super::queries::discount_code_basic_create::DiscountContextInput> {
+    use super::queries::discount_code_basic_create::{
+        DiscountContextInput, DiscountCustomerSegmentsInput, DiscountCustomersInput,
+    };
+
+    match eligibility {
+        CustomerEligibility::All => None,
+        CustomerEligibility::CustomerSegments(segment_ids) => Some(DiscountContextInput {
+            all: None,
+            customers: None,
+            customer_segments: Some(DiscountCustomerSegmentsInput {
+                add: Some(segment_ids),
+                remove: None,
+            }),
+        }),
+        CustomerEligibility::SpecificCustomers(customer_ids) => Some(DiscountContextInput {
+            all: None,
+            customers: Some(DiscountCustomersInput {
+                add: Some(customer_ids.into_iter().map(String::from).collect()),
+                remove: None,
+            }),
+            customer_segments: None,
+        }),
+    }
+}
+
 impl AdminClient {
     /// Get a paginated list of discount codes.
     ///
@@ -99,6 +205,7 @@ impl AdminClient {
                             id: node.id,
                             title: basic.title,
                             code,
+                            discount_type: DiscountType::Basic,
                             status: convert_discount_status(&basic.status),
                             starts_at: Some(basic.starts_at),
                             ends_at: basic.ends_at,
@@ -113,6 +220,7 @@ impl AdminClient {
                             id: node.id,
                             title: bxgy.title,
                             code,
+                            discount_type: DiscountType::BuyXGetY,
                             status: convert_discount_status(&bxgy.status),
                             starts_at: Some(bxgy.starts_at),
                             ends_at: bxgy.ends_at,
@@ -127,6 +235,7 @@ impl AdminClient {
                             id: node.id,
                             title: fs.title,
                             code,
+                            discount_type: DiscountType::FreeShipping,
                             status: convert_discount_status(&fs.status),
                             starts_at: Some(fs.starts_at),
                             ends_at: fs.ends_at,
@@ -151,6 +260,76 @@ impl AdminClient {
         })
     }
 
+    /// Get every redeem code of a code discount, following pagination.
+    ///
+    /// [`Self::get_discounts`] only returns the first code of each discount;
+    /// use this for discounts with many codes (e.g., bulk-generated ones).
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - Discount node ID
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or the discount is not a
+    /// code discount.
+    #[instrument(skip(self), fields(discount_id = %node_id))]
+    pub async fn get_all_discount_codes_for_node(
+        &self,
+        node_id: &str,
+    ) -> Result<Vec<String>, AdminShopifyError> {
+        use super::queries::get_discount_node_codes::GetDiscountNodeCodesCodeDiscountNodeCodeDiscount as CodeDiscount;
+
+        let mut codes = Vec::new();
+        let mut after = None;
+        loop {
+            let variables = super::queries::get_discount_node_codes::Variables {
+                id: node_id.to_string(),
+                first: DISCOUNT_CODES_PAGE_SIZE,
+                after: after.take(),
+            };
+
+            let response = self.execute::<GetDiscountNodeCodes>(variables).await?;
+
+            let Some(node) = response.code_discount_node else {
+                return Err(AdminShopifyError::NotFound(format!(
+                    "Code discount {node_id} not found"
+                )));
+            };
+
+            let (page, has_next_page, end_cursor) = match node.code_discount {
+                CodeDiscount::DiscountCodeBasic(d) => (
+                    d.codes
+                        .nodes
+                        .into_iter()
+                        .map(|c| c.code)
+                        .collect::<Vec<_>>(),
+                    d.codes.page_info.has_next_page,
+                    d.codes.page_info.end_cursor,
+                ),
+                CodeDiscount::DiscountCodeBxgy(d) => (
+                    d.codes.nodes.into_iter().map(|c| c.code).collect(),
+                    d.codes.page_info.has_next_page,
+                    d.codes.page_info.end_cursor,
+                ),
+                CodeDiscount::DiscountCodeFreeShipping(d) => (
+                    d.codes.nodes.into_iter().map(|c| c.code).collect(),
+                    d.codes.page_info.has_next_page,
+                    d.codes.page_info.end_cursor,
+                ),
+                CodeDiscount::DiscountCodeApp => break,
+            };
+            codes.extend(page);
+
+            match end_cursor {
+                Some(cursor) if has_next_page => after = Some(cursor),
+                _ => break,
+            }
+        }
+
+        Ok(codes)
+    }
+
     /// Create a basic discount code (percentage or fixed amount).
     ///
     /// # Arguments
@@ -167,7 +346,7 @@ impl AdminClient {
     ) -> Result<String, AdminShopifyError> {
         use super::queries::discount_code_basic_create::{
             DiscountCodeBasicInput, DiscountCustomerGetsInput, DiscountCustomerGetsValueInput,
-            DiscountItemsInput, Variables,
+            Variables,
         };
 
         let value = if let Some(pct) = input.percentage {
@@ -192,6 +371,10 @@ impl AdminClient {
             ));
         };
 
+        let items = build_discount_items(input.items);
+        let minimum_requirement = build_minimum_requirement(input.minimum_requirement);
+        let context = build_discount_context(input.customer_eligibility);
+
         let variables = Variables {
             basic_code_discount: DiscountCodeBasicInput {
                 title: Some(input.title.to_string()),
@@ -201,19 +384,15 @@ impl AdminClient {
                 usage_limit: input.usage_limit,
                 customer_gets: Some(DiscountCustomerGetsInput {
                     value: Some(value),
-                    items: Some(DiscountItemsInput {
-                        all: Some(true),
-                        collections: None,
-                        products: None,
-                    }),
+                    items: Some(items),
                     applies_on_one_time_purchase: None,
                     applies_on_subscription: None,
                 }),
-                applies_once_per_customer: Some(false),
+                applies_once_per_customer: Some(input.once_per_customer),
                 combines_with: None,
-                minimum_requirement: None,
+                minimum_requirement,
                 recurring_cycle_limit: None,
-                context: None,
+                context,
             },
         };
 
@@ -244,6 +423,125 @@ impl AdminClient {
         }]))
     }
 
+    /// Clone an existing basic discount code under a new code and validity window.
+    ///
+    /// Copies the source discount's value (percentage or fixed amount), usage
+    /// limit, minimum purchase requirement, entitled collections or products,
+    /// and the once-per-customer flag. Customer eligibility is not copied, so the new
+    /// discount is open to all customers.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_id` - Discount node ID of the discount to copy
+    /// * `new_code` - Customer-facing code for the new discount
+    /// * `starts_at` - When the new discount becomes active (ISO 8601 datetime)
+    /// * `ends_at` - When the new discount expires (optional)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source discount is not a basic code discount,
+    /// or if either API request fails or returns user errors.
+    #[instrument(skip(self), fields(source_id = %source_id))]
+    pub async fn clone_discount(
+        &self,
+        source_id: &str,
+        new_code: &str,
+        starts_at: &str,
+        ends_at: Option<&str>,
+    ) -> Result<String, AdminShopifyError> {
+        use super::queries::get_discount_code::{
+            GetDiscountCodeDiscountNodeDiscount as Discount,
+            GetDiscountCodeDiscountNodeDiscountOnDiscountCodeBasicCustomerGetsItems as Items,
+            GetDiscountCodeDiscountNodeDiscountOnDiscountCodeBasicCustomerGetsValue as BasicValue,
+            GetDiscountCodeDiscountNodeDiscountOnDiscountCodeBasicMinimumRequirement as Minimum,
+        };
+
+        let variables = super::queries::get_discount_code::Variables {
+            id: source_id.to_string(),
+        };
+        let response = self.execute::<GetDiscountCode>(variables).await?;
+
+        let Some(node) = response.discount_node else {
+            return Err(AdminShopifyError::NotFound(format!(
+                "Discount {source_id} not found"
+            )));
+        };
+
+        let Discount::DiscountCodeBasic(basic) = node.discount else {
+            return Err(AdminShopifyError::UserError(format!(
+                "Discount {source_id} is not a basic discount code and cannot be duplicated"
+            )));
+        };
+
+        let (percentage, amount) = match basic.customer_gets.value {
+            BasicValue::DiscountPercentage(p) => (Some(p.percentage), None),
+            BasicValue::DiscountAmount(a) => (
+                None,
+                Some((a.amount.amount, format!("{:?}", a.amount.currency_code))),
+            ),
+            BasicValue::DiscountOnQuantity => {
+                return Err(AdminShopifyError::UserError(format!(
+                    "Discount {source_id} uses a quantity-based value and cannot be duplicated"
+                )));
+            }
+        };
+
+        let items = match basic.customer_gets.items {
+            Items::AllDiscountItems => DiscountItems::All,
+            Items::DiscountCollections(c) => DiscountItems::Collections(
+                c.collections
+                    .edges
+                    .into_iter()
+                    .map(|e| e.node.id.try_into())
+                    .collect::<Result<_, _>>()?,
+            ),
+            Items::DiscountProducts(p) => DiscountItems::Products {
+                product_ids: p
+                    .products
+                    .edges
+                    .into_iter()
+                    .map(|e| e.node.id.try_into())
+                    .collect::<Result<_, _>>()?,
+                variant_ids: p
+                    .product_variants
+                    .edges
+                    .into_iter()
+                    .map(|e| e.node.id.try_into())
+                    .collect::<Result<_, _>>()?,
+            },
+        };
+
+        let minimum_requirement = match basic.minimum_requirement {
+            Some(Minimum::DiscountMinimumQuantity(q)) => DiscountMinimumRequirement::Quantity {
+                quantity: q.greater_than_or_equal_to_quantity,
+            },
+            Some(Minimum::DiscountMinimumSubtotal(s)) => DiscountMinimumRequirement::Subtotal {
+                amount: s.greater_than_or_equal_to_subtotal.amount,
+                currency: format!("{:?}", s.greater_than_or_equal_to_subtotal.currency_code),
+            },
+            None => DiscountMinimumRequirement::None,
+        };
+
+        let title = format!("{} ({new_code})", basic.title);
+
+        self.create_discount(DiscountCreateInput {
+            title: &title,
+            code: new_code,
+            percentage,
+            amount: amount
+                .as_ref()
+                .map(|(amt, currency)| (amt.as_str(), currency.as_str())),
+            starts_at,
+            ends_at,
+            usage_limit: basic.usage_limit,
+            minimum_requirement,
+            items,
+            once_per_customer: basic.applies_once_per_customer,
+            customer_eligibility: CustomerEligibility::All,
+        })
+        .await
+    }
+
     /// Deactivate a discount code.
     ///
     /// # Arguments
@@ -322,6 +620,7 @@ impl AdminClient {
                     id: node.id,
                     title: basic.title,
                     code,
+                    discount_type: DiscountType::Basic,
                     status: convert_discount_status_single(&basic.status),
                     starts_at: Some(basic.starts_at),
                     ends_at: basic.ends_at,
@@ -983,34 +1282,54 @@ impl AdminClient {
 
         Ok(())
     }
+}
 
-    /// Get customer segments for eligibility picker.
-    ///
-    /// # Arguments
-    ///
-    /// * `first` - Number of segments to return
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the API request fails.
-    #[instrument(skip(self))]
-    pub async fn get_customer_segments(
-        &self,
-        first: i64,
-    ) -> Result<Vec<CustomerSegment>, AdminShopifyError> {
-        let variables = super::queries::get_customer_segments::Variables { first: Some(first) };
-        let response = self.execute::<GetCustomerSegments>(variables).await?;
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_discount_items_all() {
+        for items in [DiscountItems::All, DiscountItems::Collections(Vec::new())] {
+            let input = build_discount_items(items);
+            assert_eq!(input.all, Some(true));
+            assert!(input.collections.is_none());
+            assert!(input.products.is_none());
+        }
+    }
 
-        let segments = response
-            .segments
-            .edges
-            .into_iter()
-            .map(|e| CustomerSegment {
-                id: e.node.id,
-                name: e.node.name,
-            })
-            .collect();
+    #[test]
+    fn test_build_discount_items_collections() {
+        let input = build_discount_items(DiscountItems::Collections(vec![
+            "gid://shopify/Collection/1".parse().unwrap(),
+        ]));
+
+        assert!(input.all.is_none());
+        assert_eq!(
+            input.collections.and_then(|c| c.add),
+            Some(vec!["gid://shopify/Collection/1".to_string()])
+        );
+        assert!(input.products.is_none());
+    }
+
+    #[test]
+    fn test_build_discount_items_products() {
+        let input = build_discount_items(DiscountItems::Products {
+            product_ids: vec!["gid://shopify/Product/1".parse().unwrap()],
+            variant_ids: vec!["gid://shopify/ProductVariant/2".parse().unwrap()],
+        });
 
-        Ok(segments)
+        assert!(input.all.is_none());
+        assert!(input.collections.is_none());
+        let products = input.products.expect("product scope");
+        assert_eq!(
+            products.products_to_add,
+            Some(vec!["gid://shopify/Product/1".to_string()])
+        );
+        assert_eq!(
+            products.product_variants_to_add,
+            Some(vec!["gid://shopify/ProductVariant/2".to_string()])
+        );
     }
 }
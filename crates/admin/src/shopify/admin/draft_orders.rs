@@ -0,0 +1,400 @@
+//! Draft order operations for the Admin API.
+
+use naked_pineapple_core::{CustomerId, GidError, OrderId};
+use tracing::instrument;
+
+use super::{
+    AdminClient, AdminShopifyError, GraphQLError,
+    conversions::currency_code_to_string,
+    queries::{DraftOrderComplete, DraftOrderCreate, DraftOrderInvoiceSend, GetDraftOrders},
+};
+use crate::shopify::types::{
+    DraftOrder, DraftOrderConnection, DraftOrderDiscount, DraftOrderDiscountType,
+    DraftOrderLineItem, DraftOrderLineItemInput, DraftOrderStatus, Money, Order, PageInfo,
+};
+
+/// Convert a generated draft order node into a [`DraftOrder`].
+///
+/// Each query generates its own node type with the same fields; `$module`
+/// names the query module its status enum lives in. GID fields are parsed
+/// with `?`, so the expansion site must return `Result<_, AdminShopifyError>`.
+macro_rules! convert_draft_order {
+    ($node:expr, $module:ident) => {{
+        use super::queries::$module::DraftOrderStatus as Status;
+
+        let node = $node;
+        DraftOrder {
+            id: node.id,
+            name: node.name,
+            status: match node.status {
+                Status::INVOICE_SENT => DraftOrderStatus::InvoiceSent,
+                Status::COMPLETED => DraftOrderStatus::Completed,
+                Status::OPEN | Status::Other(_) => DraftOrderStatus::Open,
+            },
+            email: node.email,
+            note: node.note2,
+            tags: node.tags,
+            customer_id: node
+                .customer
+                .as_ref()
+                .map(|c| c.id.as_str().try_into())
+                .transpose()?,
+            customer_name: node.customer.map(|c| c.display_name),
+            order_id: node.order.map(|o| o.id.try_into()).transpose()?,
+            invoice_url: node.invoice_url,
+            invoice_sent_at: node.invoice_sent_at,
+            subtotal_price: Money {
+                amount: node.subtotal_price_set.shop_money.amount,
+                currency_code: currency_code_to_string(
+                    node.subtotal_price_set.shop_money.currency_code,
+                ),
+            },
+            total_price: Money {
+                amount: node.total_price_set.shop_money.amount,
+                currency_code: currency_code_to_string(
+                    node.total_price_set.shop_money.currency_code,
+                ),
+            },
+            line_items: node
+                .line_items
+                .nodes
+                .into_iter()
+                .map(|li| {
+                    Ok(DraftOrderLineItem {
+                        id: li.id,
+                        title: li.title,
+                        variant_title: li.variant_title,
+                        sku: li.sku,
+                        quantity: li.quantity,
+                        variant_id: li.variant.map(|v| v.id.try_into()).transpose()?,
+                        original_unit_price: Money {
+                            amount: li.original_unit_price_set.shop_money.amount,
+                            currency_code: currency_code_to_string(
+                                li.original_unit_price_set.shop_money.currency_code,
+                            ),
+                        },
+                        discounted_total: Money {
+                            amount: li.discounted_total_set.shop_money.amount,
+                            currency_code: currency_code_to_string(
+                                li.discounted_total_set.shop_money.currency_code,
+                            ),
+                        },
+                    })
+                })
+                .collect::<Result<Vec<_>, GidError>>()?,
+            created_at: node.created_at,
+            updated_at: node.updated_at,
+            completed_at: node.completed_at,
+        }
+    }};
+}
+
+/// Convert a [`DraftOrderLineItemInput`] into its `draftOrderCreate` input.
+fn draft_order_line_item_input(
+    item: DraftOrderLineItemInput,
+) -> super::queries::draft_order_create::DraftOrderLineItemInput {
+    use super::queries::draft_order_create::{
+        CurrencyCode, DraftOrderLineItemInput as LineItemInput, MoneyInput,
+    };
+
+    let (variant_id, title, price, quantity) = match item {
+        DraftOrderLineItemInput::Variant {
+            variant_id,
+            quantity,
+        } => (Some(variant_id.into()), None, None, quantity),
+        DraftOrderLineItemInput::Custom {
+            title,
+            unit_price,
+            quantity,
+        } => {
+            let price = MoneyInput {
+                currency_code: match unit_price.currency_code.as_str() {
+                    "USD" => CurrencyCode::USD,
+                    "CAD" => CurrencyCode::CAD,
+                    "EUR" => CurrencyCode::EUR,
+                    "GBP" => CurrencyCode::GBP,
+                    "AUD" => CurrencyCode::AUD,
+                    other => CurrencyCode::Other(other.to_string()),
+                },
+                amount: unit_price.amount,
+            };
+            (None, Some(title), Some(price), quantity)
+        }
+    };
+    LineItemInput {
+        applied_discount: None,
+        custom_attributes: None,
+        original_unit_price_with_currency: price,
+        quantity,
+        requires_shipping: None,
+        sku: None,
+        taxable: None,
+        title,
+        variant_id,
+        weight: None,
+        uuid: None,
+        components: None,
+        generate_price_override: None,
+        price_override: None,
+    }
+}
+
+/// Convert a [`DraftOrderDiscount`] into its `draftOrderCreate` input.
+fn draft_order_discount_input(
+    discount: DraftOrderDiscount,
+) -> super::queries::draft_order_create::DraftOrderAppliedDiscountInput {
+    use super::queries::draft_order_create::{
+        DraftOrderAppliedDiscountInput, DraftOrderAppliedDiscountType,
+    };
+
+    DraftOrderAppliedDiscountInput {
+        amount_with_currency: None,
+        description: None,
+        title: discount.title,
+        value: discount.value,
+        value_type: match discount.value_type {
+            DraftOrderDiscountType::Percentage => DraftOrderAppliedDiscountType::PERCENTAGE,
+            DraftOrderDiscountType::FixedAmount => DraftOrderAppliedDiscountType::FIXED_AMOUNT,
+        },
+    }
+}
+
+impl AdminClient {
+    /// Get a paginated list of draft orders, most recently updated first.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - Number of draft orders to return
+    /// * `after` - Cursor for pagination
+    /// * `query` - Optional search query (e.g. `status:open`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_draft_orders(
+        &self,
+        first: i64,
+        after: Option<String>,
+        query: Option<String>,
+    ) -> Result<DraftOrderConnection, AdminShopifyError> {
+        let variables = super::queries::get_draft_orders::Variables {
+            first,
+            after,
+            query,
+            line_item_count: None,
+        };
+        let response = self.execute::<GetDraftOrders>(variables).await?;
+        let connection = response.draft_orders;
+
+        Ok(DraftOrderConnection {
+            draft_orders: connection
+                .nodes
+                .into_iter()
+                .map(|node| Ok(convert_draft_order!(node, get_draft_orders)))
+                .collect::<Result<_, AdminShopifyError>>()?,
+            page_info: PageInfo {
+                has_next_page: connection.page_info.has_next_page,
+                has_previous_page: connection.page_info.has_previous_page,
+                start_cursor: connection.page_info.start_cursor,
+                end_cursor: connection.page_info.end_cursor,
+            },
+        })
+    }
+
+    /// Create a draft order.
+    ///
+    /// # Arguments
+    ///
+    /// * `line_items` - Variants and custom items to include
+    /// * `customer_id` - Customer the draft is for (also sets the invoice email)
+    /// * `note` - Note for the merchant
+    /// * `tags` - Tags to add
+    /// * `discount` - Discount applied to the whole order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or Shopify rejects the input.
+    #[instrument(skip(self, line_items, note, tags, discount))]
+    pub async fn create_draft_order(
+        &self,
+        line_items: Vec<DraftOrderLineItemInput>,
+        customer_id: Option<&CustomerId>,
+        note: Option<&str>,
+        tags: Vec<String>,
+        discount: Option<DraftOrderDiscount>,
+    ) -> Result<DraftOrder, AdminShopifyError> {
+        use super::queries::draft_order_create::{
+            DraftOrderInput, PurchasingEntityInput, Variables,
+        };
+
+        let line_items = line_items
+            .into_iter()
+            .map(draft_order_line_item_input)
+            .collect();
+        let applied_discount = discount.map(draft_order_discount_input);
+
+        let variables = Variables {
+            input: DraftOrderInput {
+                applied_discount,
+                discount_codes: None,
+                accept_automatic_discounts: None,
+                billing_address: None,
+                custom_attributes: None,
+                email: None,
+                line_items: Some(line_items),
+                metafields: None,
+                localized_fields: None,
+                note: note.map(String::from),
+                shipping_address: None,
+                shipping_line: None,
+                tags: (!tags.is_empty()).then_some(tags),
+                tax_exempt: None,
+                use_customer_default_address: customer_id.map(|_| true),
+                visible_to_customer: None,
+                reserve_inventory_until: None,
+                presentment_currency_code: None,
+                phone: None,
+                payment_terms: None,
+                purchasing_entity: customer_id.map(|id| PurchasingEntityInput {
+                    customer_id: Some(id.to_string()),
+                    purchasing_company: None,
+                }),
+                source_name: None,
+                allow_discount_codes_in_checkout: None,
+                po_number: None,
+                session_token: None,
+                transformer_fingerprint: None,
+            },
+            line_item_count: None,
+        };
+
+        let response = self.execute::<DraftOrderCreate>(variables).await?;
+
+        if let Some(payload) = response.draft_order_create {
+            if !payload.user_errors.is_empty() {
+                let error_messages: Vec<String> = payload
+                    .user_errors
+                    .iter()
+                    .map(|e| {
+                        let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                        format!("{}: {}", field, e.message)
+                    })
+                    .collect();
+                return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+            }
+            if let Some(draft_order) = payload.draft_order {
+                return Ok(convert_draft_order!(draft_order, draft_order_create));
+            }
+        }
+
+        Err(AdminShopifyError::GraphQL(vec![GraphQLError {
+            message: "No draft order returned".to_string(),
+            locations: vec![],
+            path: vec![],
+        }]))
+    }
+
+    /// Complete a draft order, converting it into an order.
+    ///
+    /// The order is marked as paid unless the draft has payment terms, in
+    /// which case payment is pending until the terms are met.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails, Shopify rejects the
+    /// completion, or the new order can't be fetched.
+    #[instrument(skip(self), fields(draft_order_id = %id))]
+    pub async fn complete_draft_order(&self, id: &str) -> Result<Order, AdminShopifyError> {
+        let variables = super::queries::draft_order_complete::Variables { id: id.to_string() };
+        let response = self.execute::<DraftOrderComplete>(variables).await?;
+
+        let payload = response.draft_order_complete.ok_or_else(|| {
+            AdminShopifyError::GraphQL(vec![GraphQLError {
+                message: "No response from draftOrderComplete".to_string(),
+                locations: vec![],
+                path: vec![],
+            }])
+        })?;
+        if !payload.user_errors.is_empty() {
+            let error_messages: Vec<String> = payload
+                .user_errors
+                .iter()
+                .map(|e| {
+                    let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                    format!("{}: {}", field, e.message)
+                })
+                .collect();
+            return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+        }
+
+        let order_id: OrderId = payload
+            .draft_order
+            .and_then(|d| d.order)
+            .map(|o| o.id)
+            .ok_or_else(|| {
+                AdminShopifyError::UserError("Draft order was not converted to an order".into())
+            })?
+            .try_into()?;
+
+        self.get_order(&order_id)
+            .await?
+            .ok_or_else(|| AdminShopifyError::NotFound(format!("Order {order_id}")))
+    }
+
+    /// Email a draft order's invoice to the customer.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Draft order ID
+    /// * `to` - Recipient (defaults to the draft order's email)
+    /// * `custom_message` - Message included in the email
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or Shopify rejects the request.
+    #[instrument(skip(self, custom_message), fields(draft_order_id = %id))]
+    pub async fn send_draft_order_invoice(
+        &self,
+        id: &str,
+        to: Option<&str>,
+        custom_message: Option<&str>,
+    ) -> Result<(), AdminShopifyError> {
+        use super::queries::draft_order_invoice_send::{EmailInput, Variables};
+
+        let email = (to.is_some() || custom_message.is_some()).then(|| EmailInput {
+            subject: None,
+            to: to.map(String::from),
+            from: None,
+            body: None,
+            bcc: None,
+            custom_message: custom_message.map(String::from),
+        });
+        let variables = Variables {
+            id: id.to_string(),
+            email,
+        };
+        let response = self.execute::<DraftOrderInvoiceSend>(variables).await?;
+
+        if let Some(payload) = response.draft_order_invoice_send {
+            if !payload.user_errors.is_empty() {
+                let error_messages: Vec<String> = payload
+                    .user_errors
+                    .iter()
+                    .map(|e| {
+                        let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                        format!("{}: {}", field, e.message)
+                    })
+                    .collect();
+                return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+            }
+            return Ok(());
+        }
+
+        Err(AdminShopifyError::GraphQL(vec![GraphQLError {
+            message: "No response from draftOrderInvoiceSend".to_string(),
+            locations: vec![],
+            path: vec![],
+        }]))
+    }
+}
@@ -1,5 +1,6 @@
 //! Finance operations (payouts, disputes, bank accounts) for the Admin API.
 
+use naked_pineapple_core::{GidError, OrderId};
 use tracing::instrument;
 
 use super::{
@@ -604,7 +605,7 @@ impl AdminClient {
             );
         }
 
-        let transactions: Vec<BalanceTransaction> = account
+        let transactions = account
             .balance_transactions
             .edges
             .into_iter()
@@ -618,10 +619,14 @@ impl AdminClient {
                     }
                 }
 
-                let (order_id, order_name) = t
-                    .associated_order
-                    .map_or((None, None), |o| (Some(o.id), Some(o.name)));
-                Some(BalanceTransaction {
+                let (order_id, order_name) = match t.associated_order {
+                    Some(o) => match OrderId::try_from(o.id) {
+                        Ok(id) => (Some(id), Some(o.name)),
+                        Err(e) => return Some(Err(e)),
+                    },
+                    None => (None, None),
+                };
+                Some(Ok(BalanceTransaction {
                     id: t.id,
                     amount: Money {
                         amount: t.amount.amount,
@@ -640,9 +645,9 @@ impl AdminClient {
                     source_type: convert_source_type(t.source_type.as_ref()),
                     order_id,
                     order_name,
-                })
+                }))
             })
-            .collect();
+            .collect::<Result<Vec<_>, GidError>>()?;
 
         tracing::info!(
             transaction_count = transactions.len(),
@@ -688,15 +693,17 @@ impl AdminClient {
             ));
         };
 
-        let disputes: Vec<Dispute> = account
+        let disputes = account
             .disputes
             .edges
             .into_iter()
             .map(|e| {
                 let d = e.node;
-                let (order_id, order_name) =
-                    d.order.map_or((None, None), |o| (Some(o.id), Some(o.name)));
-                Dispute {
+                let (order_id, order_name) = match d.order {
+                    Some(o) => (Some(o.id.try_into()?), Some(o.name)),
+                    None => (None, None),
+                };
+                Ok(Dispute {
                     id: d.id,
                     legacy_resource_id: Some(d.legacy_resource_id.clone()),
                     status: convert_dispute_status(&d.status),
@@ -714,9 +721,9 @@ impl AdminClient {
                     }),
                     order_id,
                     order_name,
-                }
+                })
             })
-            .collect();
+            .collect::<Result<Vec<_>, GidError>>()?;
 
         Ok(DisputeConnection {
             disputes,
@@ -770,7 +777,7 @@ impl AdminClient {
                     evidence_due_by: d.evidence_due_by.clone(),
                     finalized_on: d.finalized_on.clone(),
                     reason_details,
-                    order_id,
+                    order_id: order_id.map(OrderId::try_from).transpose()?,
                     order_name,
                 };
 
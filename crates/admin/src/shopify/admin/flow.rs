@@ -0,0 +1,130 @@
+//! Shopify Flow trigger operations for the Admin API.
+
+use std::collections::HashMap;
+
+use tracing::instrument;
+
+use super::{AdminClient, AdminShopifyError};
+
+impl AdminClient {
+    /// Send a custom trigger to Shopify Flow.
+    ///
+    /// Uses the `flowTriggerReceive` mutation. Order, customer and product
+    /// GIDs are sent as the `order_id`, `customer_id` or `product_id`
+    /// reference field so Flow can load the resource; any other resource ID
+    /// is sent as `resource_id`. `properties` are merged into the payload.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - Handle of the Flow trigger extension
+    /// * `resource_id` - Resource the trigger is about
+    /// * `properties` - Custom trigger properties
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or Shopify rejects the trigger.
+    #[instrument(skip(self, properties))]
+    pub async fn trigger_flow(
+        &self,
+        handle: &str,
+        resource_id: &str,
+        properties: HashMap<String, serde_json::Value>,
+    ) -> Result<(), AdminShopifyError> {
+        let query = r"
+            mutation FlowTriggerReceive($handle: String, $payload: JSON) {
+                flowTriggerReceive(handle: $handle, payload: $payload) {
+                    userErrors {
+                        field
+                        message
+                    }
+                }
+            }
+        ";
+
+        let mut payload: serde_json::Map<String, serde_json::Value> =
+            properties.into_iter().collect();
+        let (field, value) = reference_field(resource_id);
+        payload.insert(field.to_string(), value);
+
+        let body = serde_json::json!({
+            "query": query,
+            "variables": {
+                "handle": handle,
+                "payload": payload
+            }
+        });
+
+        let response = self.execute_raw_graphql(body).await?;
+
+        if let Some(errors) = response
+            .get("flowTriggerReceive")
+            .and_then(|p| p.get("userErrors"))
+            .and_then(|e| e.as_array())
+        {
+            let error_messages: Vec<String> = errors
+                .iter()
+                .filter_map(|e| e.get("message").and_then(|m| m.as_str()))
+                .map(String::from)
+                .collect();
+
+            if !error_messages.is_empty() {
+                return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Payload field for a resource: a Flow reference field with the numeric ID
+/// for orders, customers and products, otherwise `resource_id`.
+fn reference_field(resource_id: &str) -> (&'static str, serde_json::Value) {
+    let reference = resource_id
+        .strip_prefix("gid://shopify/")
+        .and_then(|rest| rest.split_once('/'))
+        .and_then(|(kind, id)| Some((kind, id.parse::<u64>().ok()?)));
+
+    match reference {
+        Some(("Order", id)) => ("order_id", id.into()),
+        Some(("Customer", id)) => ("customer_id", id.into()),
+        Some(("Product", id)) => ("product_id", id.into()),
+        _ => ("resource_id", resource_id.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_field_for_known_resources() {
+        assert_eq!(
+            reference_field("gid://shopify/Order/123"),
+            ("order_id", 123.into())
+        );
+        assert_eq!(
+            reference_field("gid://shopify/Customer/456"),
+            ("customer_id", 456.into())
+        );
+        assert_eq!(
+            reference_field("gid://shopify/Product/789"),
+            ("product_id", 789.into())
+        );
+    }
+
+    #[test]
+    fn test_reference_field_falls_back_to_resource_id() {
+        assert_eq!(
+            reference_field("gid://shopify/Collection/1"),
+            ("resource_id", "gid://shopify/Collection/1".into())
+        );
+        assert_eq!(
+            reference_field("gid://shopify/Order/not-a-number"),
+            ("resource_id", "gid://shopify/Order/not-a-number".into())
+        );
+        assert_eq!(
+            reference_field("PINEAPPLE-SKU"),
+            ("resource_id", "PINEAPPLE-SKU".into())
+        );
+    }
+}
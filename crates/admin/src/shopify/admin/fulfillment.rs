@@ -1,5 +1,6 @@
 //! Fulfillment, refund, hold, and return operations for the Admin API.
 
+use naked_pineapple_core::OrderId;
 use tracing::instrument;
 
 use super::{
@@ -12,8 +13,9 @@ use super::{
     },
 };
 use crate::shopify::types::{
-    FulfillmentHoldInput, FulfillmentHoldReason, FulfillmentOrder, RefundCreateInput,
-    RefundRestockType, ReturnCreateInput, SuggestedRefundLineItem, SuggestedRefundResult,
+    ExchangeOrderInput, FulfillmentHoldInput, FulfillmentHoldReason, FulfillmentOrder,
+    RefundCreateInput, RefundRestockType, ReturnCreateInput, SuggestedRefundLineItem,
+    SuggestedRefundResult,
 };
 
 impl AdminClient {
@@ -29,7 +31,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(order_id = %order_id))]
     pub async fn get_fulfillment_orders(
         &self,
-        order_id: &str,
+        order_id: &OrderId,
     ) -> Result<Vec<FulfillmentOrder>, AdminShopifyError> {
         let variables = super::queries::get_fulfillment_orders::Variables {
             order_id: order_id.to_string(),
@@ -37,7 +39,7 @@ impl AdminClient {
 
         let response = self.execute::<GetFulfillmentOrders>(variables).await?;
 
-        Ok(convert_fulfillment_orders(response.order))
+        Ok(convert_fulfillment_orders(response.order)?)
     }
 
     /// Create a fulfillment.
@@ -185,7 +187,7 @@ impl AdminClient {
     #[instrument(skip(self, input), fields(order_id = %order_id))]
     pub async fn create_refund(
         &self,
-        order_id: &str,
+        order_id: &OrderId,
         input: RefundCreateInput,
     ) -> Result<String, AdminShopifyError> {
         use super::queries::refund_create::{
@@ -204,7 +206,7 @@ impl AdminClient {
                     RefundRestockType::Cancel => RefundLineItemRestockType::CANCEL,
                     RefundRestockType::NoRestock => RefundLineItemRestockType::NO_RESTOCK,
                 }),
-                location_id: item.location_id,
+                location_id: item.location_id.map(String::from),
             })
             .collect();
 
@@ -273,7 +275,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(order_id = %order_id))]
     pub async fn get_suggested_refund(
         &self,
-        order_id: &str,
+        order_id: &OrderId,
     ) -> Result<SuggestedRefundResult, AdminShopifyError> {
         let variables = super::queries::suggested_refund::Variables {
             order_id: order_id.to_string(),
@@ -437,7 +439,7 @@ impl AdminClient {
     #[instrument(skip(self, input), fields(order_id = %order_id))]
     pub async fn create_return(
         &self,
-        order_id: &str,
+        order_id: &OrderId,
         input: ReturnCreateInput,
     ) -> Result<String, AdminShopifyError> {
         use super::queries::return_create::{ReturnInput, ReturnLineItemInput, Variables};
@@ -490,4 +492,106 @@ impl AdminClient {
             path: vec![],
         }]))
     }
+
+    /// Create an exchange for an order.
+    ///
+    /// Shopify has no standalone exchange mutation; an exchange is a return
+    /// that also lists replacement items. The returned ID is that of the
+    /// exchange return, which is tracked on the original order.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Items being returned and replacement items to send
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or returns user errors.
+    #[instrument(skip(self, input), fields(order_id = %input.order_id))]
+    pub async fn create_exchange_order(
+        &self,
+        input: ExchangeOrderInput,
+    ) -> Result<String, AdminShopifyError> {
+        use super::queries::return_create::{
+            CurrencyCode, ExchangeLineItemAppliedDiscountInput,
+            ExchangeLineItemAppliedDiscountValueInput, ExchangeLineItemInput, MoneyInput,
+            ReturnInput, ReturnLineItemInput, Variables,
+        };
+
+        let return_line_items: Vec<ReturnLineItemInput> = input
+            .return_line_items
+            .into_iter()
+            .map(|item| ReturnLineItemInput {
+                fulfillment_line_item_id: item.fulfillment_line_item_id,
+                quantity: item.quantity,
+                return_reason_note: item.return_reason_note.or_else(|| input.note.clone()),
+                return_reason_definition_id: None,
+                restocking_fee: None,
+            })
+            .collect();
+
+        let exchange_line_items: Vec<ExchangeLineItemInput> = input
+            .line_items_to_add
+            .iter()
+            .map(|item| ExchangeLineItemInput {
+                variant_id: Some(item.variant_id.to_string()),
+                quantity: item.quantity,
+                applied_discount: item.discount_amount().map(|amount| {
+                    ExchangeLineItemAppliedDiscountInput {
+                        description: Some("Exchange price adjustment".to_string()),
+                        value: ExchangeLineItemAppliedDiscountValueInput {
+                            amount: Some(MoneyInput {
+                                amount: amount.to_string(),
+                                currency_code: match item.currency_code.as_str() {
+                                    "USD" => CurrencyCode::USD,
+                                    "CAD" => CurrencyCode::CAD,
+                                    "EUR" => CurrencyCode::EUR,
+                                    "GBP" => CurrencyCode::GBP,
+                                    "AUD" => CurrencyCode::AUD,
+                                    other => CurrencyCode::Other(other.to_string()),
+                                },
+                            }),
+                            percentage: None,
+                        },
+                    }
+                }),
+                gift_card_codes: None,
+            })
+            .collect();
+
+        let variables = Variables {
+            return_input: ReturnInput {
+                order_id: input.order_id.into(),
+                return_line_items,
+                requested_at: None,
+                exchange_line_items: Some(exchange_line_items),
+                return_shipping_fee: None,
+            },
+        };
+
+        let response = self.execute::<ReturnCreate>(variables).await?;
+
+        if let Some(payload) = response.return_create {
+            if !payload.user_errors.is_empty() {
+                let error_messages: Vec<String> = payload
+                    .user_errors
+                    .iter()
+                    .map(|e| {
+                        let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                        format!("{}: {}", field, e.message)
+                    })
+                    .collect();
+                return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+            }
+
+            if let Some(ret) = payload.return_ {
+                return Ok(ret.id);
+            }
+        }
+
+        Err(AdminShopifyError::GraphQL(vec![GraphQLError {
+            message: "No exchange created".to_string(),
+            locations: vec![],
+            path: vec![],
+        }]))
+    }
 }
@@ -1,5 +1,6 @@
 //! Gift card management operations for the Admin API.
 
+use naked_pineapple_core::{CustomerId, GidError};
 use tracing::instrument;
 
 use super::{
@@ -69,7 +70,7 @@ impl AdminClient {
             .into_iter()
             .map(|e| {
                 let gc = e.node;
-                GiftCard {
+                Ok(GiftCard {
                     id: gc.id,
                     last_characters: gc.last_characters,
                     masked_code: Some(gc.masked_code),
@@ -86,16 +87,24 @@ impl AdminClient {
                     deactivated_at: gc.deactivated_at,
                     created_at: gc.created_at,
                     updated_at: Some(gc.updated_at),
-                    customer_id: gc.customer.as_ref().map(|c| c.id.clone()),
+                    customer_id: gc
+                        .customer
+                        .as_ref()
+                        .map(|c| c.id.as_str().try_into())
+                        .transpose()?,
                     #[allow(deprecated)]
                     customer_email: gc.customer.as_ref().and_then(|c| c.email.clone()),
                     customer_name: gc.customer.as_ref().map(|c| c.display_name.clone()),
                     note: gc.note,
-                    order_id: gc.order.as_ref().map(|o| o.id.clone()),
+                    order_id: gc
+                        .order
+                        .as_ref()
+                        .map(|o| o.id.as_str().try_into())
+                        .transpose()?,
                     order_name: gc.order.as_ref().map(|o| o.name.clone()),
-                }
+                })
             })
-            .collect();
+            .collect::<Result<Vec<_>, GidError>>()?;
 
         Ok(GiftCardConnection {
             gift_cards,
@@ -182,14 +191,19 @@ impl AdminClient {
             .unwrap_or_default();
 
         #[allow(deprecated)]
-        let recipient = gc.recipient_attributes.map(|r| GiftCardRecipient {
-            recipient_id: Some(r.recipient.id.clone()),
-            recipient_name: Some(r.recipient.display_name.clone()),
-            recipient_email: r.recipient.email.clone(),
-            preferred_name: r.preferred_name,
-            message: r.message,
-            send_notification_at: r.send_notification_at,
-        });
+        let recipient = gc
+            .recipient_attributes
+            .map(|r| {
+                Ok::<_, GidError>(GiftCardRecipient {
+                    recipient_id: Some(r.recipient.id.as_str().try_into()?),
+                    recipient_name: Some(r.recipient.display_name.clone()),
+                    recipient_email: r.recipient.email.clone(),
+                    preferred_name: r.preferred_name,
+                    message: r.message,
+                    send_notification_at: r.send_notification_at,
+                })
+            })
+            .transpose()?;
 
         Ok(GiftCardDetail {
             id: gc.id,
@@ -210,14 +224,22 @@ impl AdminClient {
             updated_at: gc.updated_at,
             note: gc.note,
             template_suffix: gc.template_suffix,
-            customer_id: gc.customer.as_ref().map(|c| c.id.clone()),
+            customer_id: gc
+                .customer
+                .as_ref()
+                .map(|c| c.id.as_str().try_into())
+                .transpose()?,
             customer_name: gc.customer.as_ref().map(|c| c.display_name.clone()),
             #[allow(deprecated)]
             customer_email: gc.customer.as_ref().and_then(|c| c.email.clone()),
             #[allow(deprecated)]
             customer_phone: gc.customer.as_ref().and_then(|c| c.phone.clone()),
             recipient,
-            order_id: gc.order.as_ref().map(|o| o.id.clone()),
+            order_id: gc
+                .order
+                .as_ref()
+                .map(|o| o.id.as_str().try_into())
+                .transpose()?,
             order_name: gc.order.as_ref().map(|o| o.name.clone()),
             order_created_at: gc.order.as_ref().map(|o| o.created_at.clone()),
             transactions,
@@ -269,10 +291,10 @@ impl AdminClient {
     pub async fn create_gift_card(
         &self,
         initial_value: &str,
-        customer_id: Option<&str>,
+        customer_id: Option<&CustomerId>,
         expires_on: Option<&str>,
         note: Option<&str>,
-        recipient_id: Option<&str>,
+        recipient_id: Option<&CustomerId>,
         recipient_message: Option<&str>,
     ) -> Result<(String, String), AdminShopifyError> {
         use super::queries::gift_card_create::{
@@ -289,7 +311,7 @@ impl AdminClient {
         let variables = Variables {
             input: GiftCardCreateInput {
                 initial_value: initial_value.to_string(),
-                customer_id: customer_id.map(String::from),
+                customer_id: customer_id.map(ToString::to_string),
                 expires_on: expires_on.map(String::from),
                 note: note.map(String::from),
                 code: None,
@@ -378,7 +400,7 @@ impl AdminClient {
         id: &str,
         note: Option<&str>,
         expires_on: Option<&str>,
-        customer_id: Option<&str>,
+        customer_id: Option<&CustomerId>,
     ) -> Result<(), AdminShopifyError> {
         use super::queries::gift_card_update::{GiftCardUpdateInput, Variables};
 
@@ -387,7 +409,7 @@ impl AdminClient {
             input: GiftCardUpdateInput {
                 note: note.map(String::from),
                 expires_on: expires_on.map(String::from),
-                customer_id: customer_id.map(String::from),
+                customer_id: customer_id.map(ToString::to_string),
                 template_suffix: None,
                 recipient_attributes: None,
             },
@@ -0,0 +1,184 @@
+//! HTTP connection settings for the Admin API client.
+//!
+//! All clones of an [`AdminClient`](super::AdminClient) share one
+//! `reqwest::Client`, and with it one connection pool. Keeping idle
+//! connections to Shopify warm, and multiplexing requests over HTTP/2, avoids
+//! a TLS handshake per request when many queries run concurrently.
+
+use std::time::Duration;
+
+use super::RetryPolicy;
+
+/// HTTP settings for an [`AdminClient`](super::AdminClient).
+///
+/// Built with chained setters over the defaults:
+///
+/// ```rust,ignore
+/// let http = AdminClientConfig::default()
+///     .pool_max_idle_per_host(20)
+///     .request_timeout(Duration::from_secs(60))
+///     .server_error_retry(RetryPolicy::none());
+/// let client = AdminClient::with_http_config(&config.shopify, http)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdminClientConfig {
+    /// Idle connections kept open per host.
+    pub pool_max_idle_per_host: usize,
+    /// How long an idle connection is kept before closing it (`None` keeps
+    /// it indefinitely).
+    pub pool_idle_timeout: Option<Duration>,
+    /// Timeout for establishing a connection.
+    pub connect_timeout: Duration,
+    /// Timeout for a whole request, from sending it to reading the body.
+    pub request_timeout: Duration,
+    /// Interval of TCP keepalive probes (`None` disables them).
+    pub tcp_keepalive: Option<Duration>,
+    /// Negotiate HTTP/2 with Shopify, multiplexing concurrent requests over
+    /// one connection. HTTP/1.1 only when `false`.
+    pub enable_http2: bool,
+    /// Retries when Shopify answers `429 Too Many Requests`. A `Retry-After`
+    /// header is waited out even when it's longer than the policy's delay.
+    pub rate_limit_retry: RetryPolicy,
+    /// Retries when Shopify answers with a `5xx` status.
+    pub server_error_retry: RetryPolicy,
+}
+
+impl Default for AdminClientConfig {
+    fn default() -> Self {
+        Self {
+            pool_max_idle_per_host: 10,
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+            connect_timeout: Duration::from_secs(5),
+            request_timeout: Duration::from_secs(30),
+            tcp_keepalive: Some(Duration::from_mins(1)),
+            enable_http2: true,
+            rate_limit_retry: RetryPolicy::default(),
+            server_error_retry: RetryPolicy::default(),
+        }
+    }
+}
+
+impl AdminClientConfig {
+    /// Set the number of idle connections kept open per host.
+    #[must_use]
+    pub const fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = max;
+        self
+    }
+
+    /// Set how long idle connections are kept (`None` keeps them indefinitely).
+    #[must_use]
+    pub const fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.pool_idle_timeout = timeout;
+        self
+    }
+
+    /// Set the connection timeout.
+    #[must_use]
+    pub const fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Set the total request timeout.
+    #[must_use]
+    pub const fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Set the TCP keepalive interval (`None` disables keepalive).
+    #[must_use]
+    pub const fn tcp_keepalive(mut self, interval: Option<Duration>) -> Self {
+        self.tcp_keepalive = interval;
+        self
+    }
+
+    /// Set whether HTTP/2 is negotiated.
+    #[must_use]
+    pub const fn enable_http2(mut self, enable: bool) -> Self {
+        self.enable_http2 = enable;
+        self
+    }
+
+    /// Set the retry policy for rate-limited requests.
+    #[must_use]
+    pub const fn rate_limit_retry(mut self, policy: RetryPolicy) -> Self {
+        self.rate_limit_retry = policy;
+        self
+    }
+
+    /// Set the retry policy for Shopify server errors.
+    ///
+    /// Only queries are retried: a mutation may have been applied before the
+    /// server error, so its response is returned as is.
+    #[must_use]
+    pub const fn server_error_retry(mut self, policy: RetryPolicy) -> Self {
+        self.server_error_retry = policy;
+        self
+    }
+
+    /// Build the `reqwest::Client` shared by an `AdminClient` and its clones.
+    pub(super) fn build_client(&self) -> Result<reqwest::Client, reqwest::Error> {
+        let mut builder = reqwest::Client::builder()
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+            .pool_idle_timeout(self.pool_idle_timeout)
+            .connect_timeout(self.connect_timeout)
+            .timeout(self.request_timeout)
+            .tcp_keepalive(self.tcp_keepalive);
+
+        // HTTP/2 is negotiated over ALPN when enabled, so only opting out
+        // needs configuring
+        if !self.enable_http2 {
+            builder = builder.http1_only();
+        }
+
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = AdminClientConfig::default();
+        assert_eq!(config.pool_max_idle_per_host, 10);
+        assert_eq!(config.connect_timeout, Duration::from_secs(5));
+        assert_eq!(config.request_timeout, Duration::from_secs(30));
+        assert_eq!(config.tcp_keepalive, Some(Duration::from_mins(1)));
+        assert!(config.enable_http2);
+        assert_eq!(config.rate_limit_retry.max_retries, 3);
+        assert_eq!(config.server_error_retry.max_retries, 3);
+    }
+
+    #[test]
+    fn test_builder_overrides_defaults() {
+        let config = AdminClientConfig::default()
+            .pool_max_idle_per_host(2)
+            .pool_idle_timeout(None)
+            .request_timeout(Duration::from_mins(2))
+            .enable_http2(false)
+            .server_error_retry(RetryPolicy::none());
+
+        assert_eq!(config.pool_max_idle_per_host, 2);
+        assert_eq!(config.pool_idle_timeout, None);
+        assert_eq!(config.request_timeout, Duration::from_mins(2));
+        assert_eq!(config.connect_timeout, Duration::from_secs(5));
+        assert!(!config.enable_http2);
+        assert_eq!(config.server_error_retry.max_retries, 0);
+        assert_eq!(config.rate_limit_retry, RetryPolicy::default());
+    }
+
+    #[test]
+    fn test_build_client() {
+        assert!(AdminClientConfig::default().build_client().is_ok());
+        assert!(
+            AdminClientConfig::default()
+                .enable_http2(false)
+                .build_client()
+                .is_ok()
+        );
+    }
+}
@@ -1,22 +1,24 @@
 //! Location and inventory management operations for the Admin API.
 
+use naked_pineapple_core::{InventoryItemId, LocationId, ProductId};
 use tracing::instrument;
 
 use super::{
     AdminClient, AdminShopifyError,
     conversions::{
         convert_inventory_item_connection, convert_inventory_level_connection,
-        convert_location_connection, convert_single_inventory_item,
+        convert_location_connection, convert_product_inventory_summary,
+        convert_single_inventory_item,
     },
     queries::{
         ActivateInventory, DeactivateInventory, GetInventoryItem, GetInventoryItems,
-        GetInventoryLevels, GetLocations, InventoryAdjustQuantities, InventorySetQuantities,
-        MoveInventory, UpdateInventoryItem,
+        GetInventoryLevels, GetLocations, GetProductInventorySummary, InventoryAdjustQuantities,
+        InventorySetQuantities, MoveInventory, UpdateInventoryItem,
     },
 };
 use crate::shopify::types::{
-    InventoryItem, InventoryItemConnection, InventoryItemUpdateInput, InventoryLevelConnection,
-    LocationConnection,
+    CountryCode, InventoryItem, InventoryItemConnection, InventoryItemUpdateInput,
+    InventoryLevelConnection, LocationConnection, ProductInventorySummary,
 };
 
 impl AdminClient {
@@ -31,7 +33,7 @@ impl AdminClient {
 
         let response = self.execute::<GetLocations>(variables).await?;
 
-        Ok(convert_location_connection(response.locations))
+        Ok(convert_location_connection(response.locations)?)
     }
 
     /// Get inventory levels at a location.
@@ -48,7 +50,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(location_id = %location_id))]
     pub async fn get_inventory_levels(
         &self,
-        location_id: &str,
+        location_id: &LocationId,
         first: i64,
         after: Option<String>,
     ) -> Result<InventoryLevelConnection, AdminShopifyError> {
@@ -60,10 +62,10 @@ impl AdminClient {
 
         let response = self.execute::<GetInventoryLevels>(variables).await?;
 
-        response
-            .location
-            .map(convert_inventory_level_connection)
-            .ok_or_else(|| AdminShopifyError::NotFound(format!("Location {location_id} not found")))
+        let location = response.location.ok_or_else(|| {
+            AdminShopifyError::NotFound(format!("Location {location_id} not found"))
+        })?;
+        Ok(convert_inventory_level_connection(location)?)
     }
 
     /// Get inventory items with pagination.
@@ -92,7 +94,7 @@ impl AdminClient {
 
         let response = self.execute::<GetInventoryItems>(variables).await?;
 
-        Ok(convert_inventory_item_connection(response))
+        Ok(convert_inventory_item_connection(response)?)
     }
 
     /// Get a single inventory item by ID.
@@ -105,15 +107,46 @@ impl AdminClient {
     ///
     /// Returns an error if the API request fails or the item is not found.
     #[instrument(skip(self), fields(id = %id))]
-    pub async fn get_inventory_item(&self, id: &str) -> Result<InventoryItem, AdminShopifyError> {
+    pub async fn get_inventory_item(
+        &self,
+        id: &InventoryItemId,
+    ) -> Result<InventoryItem, AdminShopifyError> {
         let variables = super::queries::get_inventory_item::Variables { id: id.to_string() };
 
         let response = self.execute::<GetInventoryItem>(variables).await?;
 
-        response
+        let item = response
             .inventory_item
-            .map(convert_single_inventory_item)
-            .ok_or_else(|| AdminShopifyError::NotFound(format!("Inventory item {id} not found")))
+            .ok_or_else(|| AdminShopifyError::NotFound(format!("Inventory item {id} not found")))?;
+        Ok(convert_single_inventory_item(item)?)
+    }
+
+    /// Get a product's inventory at every location, per variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_id` - Shopify product ID (e.g., `gid://shopify/Product/123`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or the product is not found.
+    #[instrument(skip(self), fields(product_id = %product_id))]
+    pub async fn get_product_inventory_summary(
+        &self,
+        product_id: &ProductId,
+    ) -> Result<ProductInventorySummary, AdminShopifyError> {
+        let variables = super::queries::get_product_inventory_summary::Variables {
+            id: product_id.to_string(),
+        };
+
+        let response = self
+            .execute::<GetProductInventorySummary>(variables)
+            .await?;
+
+        let product = response.product.ok_or_else(|| {
+            AdminShopifyError::NotFound(format!("Product {product_id} not found"))
+        })?;
+        Ok(convert_product_inventory_summary(product)?)
     }
 
     /// Adjust inventory quantity (delta adjustment).
@@ -131,8 +164,8 @@ impl AdminClient {
     #[instrument(skip(self), fields(inventory_item_id = %inventory_item_id, location_id = %location_id, delta = %delta))]
     pub async fn adjust_inventory(
         &self,
-        inventory_item_id: &str,
-        location_id: &str,
+        inventory_item_id: &InventoryItemId,
+        location_id: &LocationId,
         delta: i64,
         reason: Option<&str>,
     ) -> Result<(), AdminShopifyError> {
@@ -186,8 +219,8 @@ impl AdminClient {
     #[instrument(skip(self), fields(inventory_item_id = %inventory_item_id, location_id = %location_id, quantity = %quantity))]
     pub async fn set_inventory(
         &self,
-        inventory_item_id: &str,
-        location_id: &str,
+        inventory_item_id: &InventoryItemId,
+        location_id: &LocationId,
         quantity: i64,
         reason: Option<&str>,
     ) -> Result<(), AdminShopifyError> {
@@ -238,37 +271,14 @@ impl AdminClient {
     #[instrument(skip(self, input), fields(id = %id))]
     pub async fn update_inventory_item(
         &self,
-        id: &str,
+        id: &InventoryItemId,
         input: &InventoryItemUpdateInput,
     ) -> Result<InventoryItem, AdminShopifyError> {
-        use super::queries::update_inventory_item::{CountryCode, InventoryItemInput};
-
-        let country_code = input
-            .country_code_of_origin
-            .as_ref()
-            .map(|code| match code.as_str() {
-                "US" => CountryCode::US,
-                "CN" => CountryCode::CN,
-                "VN" => CountryCode::VN,
-                "BD" => CountryCode::BD,
-                "IN" => CountryCode::IN,
-                "ID" => CountryCode::ID,
-                "TH" => CountryCode::TH,
-                "PK" => CountryCode::PK,
-                "TR" => CountryCode::TR,
-                "KH" => CountryCode::KH,
-                "MX" => CountryCode::MX,
-                "IT" => CountryCode::IT,
-                "PT" => CountryCode::PT,
-                "ES" => CountryCode::ES,
-                "GB" => CountryCode::GB,
-                "CA" => CountryCode::CA,
-                "AU" => CountryCode::AU,
-                "JP" => CountryCode::JP,
-                "KR" => CountryCode::KR,
-                "TW" => CountryCode::TW,
-                _ => CountryCode::Other(code.clone()),
-            });
+        use super::queries::update_inventory_item::InventoryItemInput;
+
+        let country_code = input.country_code_of_origin.as_ref().map(|code| {
+            CountryCode::from_iso_2(code).unwrap_or_else(|| CountryCode::Other(code.clone()))
+        });
 
         let variables = super::queries::update_inventory_item::Variables {
             id: id.to_string(),
@@ -317,9 +327,9 @@ impl AdminClient {
     #[instrument(skip(self))]
     pub async fn move_inventory(
         &self,
-        inventory_item_id: &str,
-        from_location_id: &str,
-        to_location_id: &str,
+        inventory_item_id: &InventoryItemId,
+        from_location_id: &LocationId,
+        to_location_id: &LocationId,
         quantity: i64,
         reason: Option<&str>,
     ) -> Result<(), AdminShopifyError> {
@@ -380,8 +390,8 @@ impl AdminClient {
     #[instrument(skip(self))]
     pub async fn activate_inventory(
         &self,
-        inventory_item_id: &str,
-        location_id: &str,
+        inventory_item_id: &InventoryItemId,
+        location_id: &LocationId,
     ) -> Result<(), AdminShopifyError> {
         let variables = super::queries::activate_inventory::Variables {
             inventory_item_id: inventory_item_id.to_string(),
@@ -0,0 +1,36 @@
+//! Store language operations for the Admin API.
+
+use tracing::instrument;
+
+use super::{AdminClient, AdminShopifyError, queries::GetShopLocales};
+use crate::shopify::types::ShopLocale;
+
+impl AdminClient {
+    /// Get the languages enabled on the store, published or not.
+    ///
+    /// The primary language comes first, then the rest by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_shop_locales(&self) -> Result<Vec<ShopLocale>, AdminShopifyError> {
+        let variables = super::queries::get_shop_locales::Variables {};
+
+        let response = self.execute::<GetShopLocales>(variables).await?;
+
+        let mut locales: Vec<ShopLocale> = response
+            .shop_locales
+            .into_iter()
+            .map(|l| ShopLocale {
+                locale: l.locale,
+                name: l.name,
+                primary: l.primary,
+                published: l.published,
+            })
+            .collect();
+        locales.sort_by(|a, b| b.primary.cmp(&a.primary).then_with(|| a.name.cmp(&b.name)));
+
+        Ok(locales)
+    }
+}
@@ -1,10 +1,14 @@
 //! Media and file management operations for the Admin API.
 
+use naked_pineapple_core::{ProductId, VariantId};
 use tracing::instrument;
 
 use super::{
     AdminClient, AdminShopifyError, GraphQLError,
-    queries::{FileDelete, FileUpdate, ProductReorderMedia, ProductSetMedia, StagedUploadsCreate},
+    queries::{
+        FileDelete, FileUpdate, ProductReorderMedia, ProductSetMedia, ProductVariantAppendMedia,
+        ProductVariantDetachMedia, StagedUploadsCreate,
+    },
 };
 use crate::shopify::types::StagedUploadTarget;
 
@@ -65,7 +69,7 @@ impl AdminClient {
     #[instrument(skip(self))]
     pub async fn reorder_product_media(
         &self,
-        product_id: &str,
+        product_id: &ProductId,
         moves: Vec<(String, i64)>,
     ) -> Result<(), AdminShopifyError> {
         use super::queries::product_reorder_media::{MoveInput, Variables};
@@ -163,6 +167,119 @@ impl AdminClient {
         }]))
     }
 
+    /// Assign one of a product's existing images to a variant.
+    ///
+    /// Shopify allows one media per variant, so detach the variant's current
+    /// image first with [`Self::detach_image_from_variant`].
+    ///
+    /// # Arguments
+    ///
+    /// * `product_id` - The product ID
+    /// * `variant_id` - The variant ID
+    /// * `media_id` - The product media ID (`gid://shopify/MediaImage/...`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or returns user errors.
+    #[instrument(skip(self))]
+    pub async fn assign_image_to_variant(
+        &self,
+        product_id: &ProductId,
+        variant_id: &VariantId,
+        media_id: &str,
+    ) -> Result<(), AdminShopifyError> {
+        use super::queries::product_variant_append_media::{
+            ProductVariantAppendMediaInput, Variables,
+        };
+
+        let variables = Variables {
+            product_id: product_id.to_string(),
+            variant_media: vec![ProductVariantAppendMediaInput {
+                variant_id: variant_id.to_string(),
+                media_ids: vec![media_id.to_string()],
+            }],
+        };
+
+        let response = self.execute::<ProductVariantAppendMedia>(variables).await?;
+
+        if let Some(payload) = response.product_variant_append_media {
+            if !payload.user_errors.is_empty() {
+                let error_messages: Vec<String> = payload
+                    .user_errors
+                    .iter()
+                    .map(|e| {
+                        let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                        format!("{}: {}", field, e.message)
+                    })
+                    .collect();
+                return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+            }
+
+            return Ok(());
+        }
+
+        Err(AdminShopifyError::GraphQL(vec![GraphQLError {
+            message: "Variant image assignment failed".to_string(),
+            locations: vec![],
+            path: vec![],
+        }]))
+    }
+
+    /// Detach an image from a variant. The image stays on the product.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_id` - The product ID
+    /// * `variant_id` - The variant ID
+    /// * `media_id` - The product media ID (`gid://shopify/MediaImage/...`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or returns user errors.
+    #[instrument(skip(self))]
+    pub async fn detach_image_from_variant(
+        &self,
+        product_id: &ProductId,
+        variant_id: &VariantId,
+        media_id: &str,
+    ) -> Result<(), AdminShopifyError> {
+        use super::queries::product_variant_detach_media::{
+            ProductVariantDetachMediaInput, Variables,
+        };
+
+        let variables = Variables {
+            product_id: product_id.to_string(),
+            variant_media: vec![ProductVariantDetachMediaInput {
+                variant_id: variant_id.to_string(),
+                media_ids: vec![media_id.to_string()],
+            }],
+        };
+
+        let response = self.execute::<ProductVariantDetachMedia>(variables).await?;
+
+        if let Some(payload) = response.product_variant_detach_media {
+            if !payload.user_errors.is_empty() {
+                let error_messages: Vec<String> = payload
+                    .user_errors
+                    .iter()
+                    .map(|e| {
+                        let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                        format!("{}: {}", field, e.message)
+                    })
+                    .collect();
+                return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+            }
+
+            return Ok(());
+        }
+
+        Err(AdminShopifyError::GraphQL(vec![GraphQLError {
+            message: "Variant image detach failed".to_string(),
+            locations: vec![],
+            path: vec![],
+        }]))
+    }
+
     /// Create a staged upload target for uploading files.
     ///
     /// # Arguments
@@ -258,7 +375,7 @@ impl AdminClient {
     #[instrument(skip(self))]
     pub async fn attach_media_to_product(
         &self,
-        product_id: &str,
+        product_id: &ProductId,
         resource_url: &str,
         alt_text: Option<&str>,
     ) -> Result<(), AdminShopifyError> {
@@ -0,0 +1,184 @@
+//! Product metafield operations for the Admin API.
+
+use naked_pineapple_core::ProductId;
+use tracing::instrument;
+
+use super::{
+    AdminClient, AdminShopifyError, GraphQLError,
+    queries::{GetProductMetafields, MetafieldsDelete, MetafieldsSet},
+};
+use crate::shopify::types::Metafield;
+
+/// Maximum metafields returned by [`AdminClient::get_product_metafields`].
+const MAX_METAFIELDS: i64 = 250;
+
+impl AdminClient {
+    /// Get a product's metafields.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_id` - Product GID
+    /// * `namespace` - Only return metafields in this namespace (all if `None`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `AdminShopifyError::NotFound` if the product doesn't exist, or
+    /// an error if the API request fails.
+    #[instrument(skip(self), fields(product_id = %product_id))]
+    pub async fn get_product_metafields(
+        &self,
+        product_id: &ProductId,
+        namespace: Option<&str>,
+    ) -> Result<Vec<Metafield>, AdminShopifyError> {
+        let variables = super::queries::get_product_metafields::Variables {
+            id: product_id.to_string(),
+            namespace: namespace.map(String::from),
+            first: MAX_METAFIELDS,
+        };
+        let response = self.execute::<GetProductMetafields>(variables).await?;
+
+        let product = response
+            .product
+            .ok_or_else(|| AdminShopifyError::NotFound(format!("Product {product_id}")))?;
+
+        Ok(product
+            .metafields
+            .nodes
+            .into_iter()
+            .map(|m| Metafield {
+                id: Some(m.id),
+                namespace: m.namespace,
+                key: m.key,
+                value: m.value,
+                type_name: m.type_,
+                updated_at: Some(m.updated_at),
+            })
+            .collect())
+    }
+
+    /// Create or update a product metafield.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_id` - Product GID
+    /// * `namespace` - Metafield namespace (e.g. `custom` or `seo`)
+    /// * `key` - Key within the namespace
+    /// * `type_name` - Metafield type (e.g. `single_line_text_field`); may
+    ///   only be omitted when a definition or existing metafield sets it
+    /// * `value` - Serialized value
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or Shopify rejects the value.
+    #[instrument(skip(self, value), fields(product_id = %product_id))]
+    pub async fn set_product_metafield(
+        &self,
+        product_id: &ProductId,
+        namespace: &str,
+        key: &str,
+        type_name: Option<&str>,
+        value: &str,
+    ) -> Result<Metafield, AdminShopifyError> {
+        use super::queries::metafields_set::{MetafieldsSetInput, Variables};
+
+        let variables = Variables {
+            metafields: vec![MetafieldsSetInput {
+                owner_id: product_id.to_string(),
+                namespace: Some(namespace.to_string()),
+                key: key.to_string(),
+                value: value.to_string(),
+                compare_digest: None,
+                type_: type_name.map(String::from),
+            }],
+        };
+        let response = self.execute::<MetafieldsSet>(variables).await?;
+
+        if let Some(payload) = response.metafields_set {
+            if !payload.user_errors.is_empty() {
+                let error_messages: Vec<String> = payload
+                    .user_errors
+                    .iter()
+                    .map(|e| {
+                        let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                        format!("{}: {}", field, e.message)
+                    })
+                    .collect();
+                return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+            }
+
+            if let Some(m) = payload.metafields.into_iter().flatten().next() {
+                return Ok(Metafield {
+                    id: Some(m.id),
+                    namespace: m.namespace,
+                    key: m.key,
+                    value: m.value,
+                    type_name: m.type_,
+                    updated_at: Some(m.updated_at),
+                });
+            }
+        }
+
+        Err(AdminShopifyError::GraphQL(vec![GraphQLError {
+            message: "No metafield returned".to_string(),
+            locations: vec![],
+            path: vec![],
+        }]))
+    }
+
+    /// Delete a product metafield.
+    ///
+    /// Shopify deletes metafields by owner, namespace and key rather than by
+    /// metafield ID.
+    ///
+    /// Returns `false` if the product had no such metafield.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or Shopify rejects the deletion.
+    #[instrument(skip(self), fields(product_id = %product_id))]
+    pub async fn delete_product_metafield(
+        &self,
+        product_id: &ProductId,
+        namespace: &str,
+        key: &str,
+    ) -> Result<bool, AdminShopifyError> {
+        use super::queries::metafields_delete::{MetafieldIdentifierInput, Variables};
+
+        let variables = Variables {
+            metafields: vec![MetafieldIdentifierInput {
+                owner_id: product_id.to_string(),
+                namespace: namespace.to_string(),
+                key: key.to_string(),
+            }],
+        };
+        let response = self.execute::<MetafieldsDelete>(variables).await?;
+
+        let Some(payload) = response.metafields_delete else {
+            return Err(AdminShopifyError::GraphQL(vec![GraphQLError {
+                message: "No response from metafieldsDelete".to_string(),
+                locations: vec![],
+                path: vec![],
+            }]));
+        };
+
+        if !payload.user_errors.is_empty() {
+            let error_messages: Vec<String> = payload
+                .user_errors
+                .iter()
+                .map(|e| {
+                    let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                    format!("{}: {}", field, e.message)
+                })
+                .collect();
+            return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+        }
+
+        Ok(payload
+            .deleted_metafields
+            .into_iter()
+            .flatten()
+            .flatten()
+            .next()
+            .is_some())
+    }
+}
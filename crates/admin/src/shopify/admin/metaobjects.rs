@@ -0,0 +1,282 @@
+//! Metaobject management operations for the Admin API.
+
+use tracing::instrument;
+
+use super::{
+    AdminClient, AdminShopifyError, GraphQLError,
+    queries::{
+        GetMetaobject, GetMetaobjectDefinitions, GetMetaobjects, MetaobjectCreate,
+        MetaobjectDelete, MetaobjectUpdate,
+    },
+};
+use crate::shopify::types::{
+    FieldDefinition, Metaobject, MetaobjectConnection, MetaobjectDefinition, MetaobjectField,
+    MetaobjectFieldInput, PageInfo,
+};
+
+/// Maximum definitions returned by [`AdminClient::get_metaobject_definitions`].
+const MAX_DEFINITIONS: i64 = 250;
+
+/// Convert a generated metaobject node into a [`Metaobject`].
+///
+/// Each query generates its own node type with the same fields.
+macro_rules! convert_metaobject {
+    ($node:expr) => {{
+        let node = $node;
+        Metaobject {
+            id: node.id,
+            handle: node.handle,
+            type_name: node.type_,
+            display_name: node.display_name,
+            updated_at: node.updated_at,
+            fields: node
+                .fields
+                .into_iter()
+                .map(|f| MetaobjectField {
+                    key: f.key,
+                    type_name: f.type_,
+                    value: f.value,
+                })
+                .collect(),
+        }
+    }};
+}
+
+impl AdminClient {
+    /// Get all metaobject definitions (custom content types).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_metaobject_definitions(
+        &self,
+    ) -> Result<Vec<MetaobjectDefinition>, AdminShopifyError> {
+        let variables = super::queries::get_metaobject_definitions::Variables {
+            first: MAX_DEFINITIONS,
+        };
+        let response = self.execute::<GetMetaobjectDefinitions>(variables).await?;
+
+        Ok(response
+            .metaobject_definitions
+            .nodes
+            .into_iter()
+            .map(|d| MetaobjectDefinition {
+                id: d.id,
+                name: d.name,
+                type_name: d.type_,
+                description: d.description,
+                field_definitions: d
+                    .field_definitions
+                    .into_iter()
+                    .map(|f| FieldDefinition {
+                        key: f.key,
+                        name: f.name,
+                        description: f.description,
+                        required: f.required,
+                        type_name: f.type_.name,
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// Get a paginated list of metaobjects of one type.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_name` - Definition type handle (e.g., `testimonial`)
+    /// * `first` - Number of metaobjects to return
+    /// * `after` - Cursor for pagination
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_metaobjects(
+        &self,
+        type_name: &str,
+        first: i64,
+        after: Option<String>,
+    ) -> Result<MetaobjectConnection, AdminShopifyError> {
+        let variables = super::queries::get_metaobjects::Variables {
+            type_: type_name.to_string(),
+            first,
+            after,
+        };
+        let response = self.execute::<GetMetaobjects>(variables).await?;
+        let connection = response.metaobjects;
+
+        Ok(MetaobjectConnection {
+            metaobjects: connection
+                .nodes
+                .into_iter()
+                .map(|m| convert_metaobject!(m))
+                .collect(),
+            page_info: PageInfo {
+                has_next_page: connection.page_info.has_next_page,
+                has_previous_page: connection.page_info.has_previous_page,
+                start_cursor: connection.page_info.start_cursor,
+                end_cursor: connection.page_info.end_cursor,
+            },
+        })
+    }
+
+    /// Get a single metaobject by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_metaobject(&self, id: &str) -> Result<Option<Metaobject>, AdminShopifyError> {
+        let variables = super::queries::get_metaobject::Variables { id: id.to_string() };
+        let response = self.execute::<GetMetaobject>(variables).await?;
+
+        Ok(response.metaobject.map(|m| convert_metaobject!(m)))
+    }
+
+    /// Create a metaobject.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_name` - Definition type handle (e.g., `testimonial`)
+    /// * `fields` - Field values to set
+    ///
+    /// # Returns
+    ///
+    /// Returns the new metaobject's ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or returns user errors.
+    #[instrument(skip(self, fields))]
+    pub async fn create_metaobject(
+        &self,
+        type_name: &str,
+        fields: Vec<MetaobjectFieldInput>,
+    ) -> Result<String, AdminShopifyError> {
+        use super::queries::metaobject_create::{self, MetaobjectCreateInput, Variables};
+
+        let variables = Variables {
+            metaobject: MetaobjectCreateInput {
+                type_: type_name.to_string(),
+                handle: None,
+                fields: Some(
+                    fields
+                        .into_iter()
+                        .map(|f| metaobject_create::MetaobjectFieldInput {
+                            key: f.key,
+                            value: f.value,
+                        })
+                        .collect(),
+                ),
+                capabilities: None,
+            },
+        };
+
+        let response = self.execute::<MetaobjectCreate>(variables).await?;
+
+        if let Some(payload) = response.metaobject_create {
+            if !payload.user_errors.is_empty() {
+                let error_messages: Vec<String> = payload
+                    .user_errors
+                    .iter()
+                    .map(|e| {
+                        let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                        format!("{}: {}", field, e.message)
+                    })
+                    .collect();
+                return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+            }
+
+            if let Some(metaobject) = payload.metaobject {
+                return Ok(metaobject.id);
+            }
+        }
+
+        Err(AdminShopifyError::GraphQL(vec![GraphQLError {
+            message: "No metaobject returned from create".to_string(),
+            locations: vec![],
+            path: vec![],
+        }]))
+    }
+
+    /// Update a metaobject's fields.
+    ///
+    /// Fields not listed keep their current values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or returns user errors.
+    #[instrument(skip(self, fields))]
+    pub async fn update_metaobject(
+        &self,
+        id: &str,
+        fields: Vec<MetaobjectFieldInput>,
+    ) -> Result<(), AdminShopifyError> {
+        use super::queries::metaobject_update::{self, MetaobjectUpdateInput, Variables};
+
+        let variables = Variables {
+            id: id.to_string(),
+            metaobject: MetaobjectUpdateInput {
+                handle: None,
+                fields: Some(
+                    fields
+                        .into_iter()
+                        .map(|f| metaobject_update::MetaobjectFieldInput {
+                            key: f.key,
+                            value: f.value,
+                        })
+                        .collect(),
+                ),
+                capabilities: None,
+                redirect_new_handle: None,
+            },
+        };
+
+        let response = self.execute::<MetaobjectUpdate>(variables).await?;
+
+        if let Some(payload) = response.metaobject_update
+            && !payload.user_errors.is_empty()
+        {
+            let error_messages: Vec<String> = payload
+                .user_errors
+                .iter()
+                .map(|e| {
+                    let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                    format!("{}: {}", field, e.message)
+                })
+                .collect();
+            return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+        }
+
+        Ok(())
+    }
+
+    /// Delete a metaobject.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or returns user errors.
+    #[instrument(skip(self))]
+    pub async fn delete_metaobject(&self, id: &str) -> Result<(), AdminShopifyError> {
+        let variables = super::queries::metaobject_delete::Variables { id: id.to_string() };
+        let response = self.execute::<MetaobjectDelete>(variables).await?;
+
+        if let Some(payload) = response.metaobject_delete
+            && !payload.user_errors.is_empty()
+        {
+            let error_messages: Vec<String> = payload
+                .user_errors
+                .iter()
+                .map(|e| {
+                    let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                    format!("{}: {}", field, e.message)
+                })
+                .collect();
+            return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+        }
+
+        Ok(())
+    }
+}
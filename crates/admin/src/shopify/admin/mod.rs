@@ -3,33 +3,60 @@
 //! This module provides a type-safe client for interacting with the
 //! Shopify Admin API using GraphQL. Requires OAuth authentication.
 
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
 use graphql_client::GraphQLQuery;
+use naked_pineapple_core::{Barcode, CollectionId, CustomerId, ProductId, VariantId};
 use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 
 use crate::config::ShopifyAdminConfig;
 
-use super::types::{Customer, Payout};
+use super::types::{Customer, DiscountMinimumRequirement, Payout, VariantDimensions};
 use super::{AdminShopifyError, GraphQLError, GraphQLErrorLocation};
 
 // Domain-specific operations split into separate modules
 mod analytics;
+mod cogs;
 mod collections;
 mod conversions;
+mod cost;
 mod customers;
+mod discount_summary;
 mod discounts;
+mod draft_orders;
 mod finance;
+mod flow;
 mod fulfillment;
 mod gift_cards;
+mod http;
 mod inventory;
+mod locales;
 mod media;
+mod metafields;
+mod metaobjects;
 mod order_editing;
 mod orders;
+mod paginator;
 mod products;
+mod publications;
 pub mod queries;
+mod retryable;
+mod segments;
+mod shipping;
+mod webhooks;
+
+pub use cost::ThrottleStatus;
+pub use http::AdminClientConfig;
+pub use naked_pineapple_core::verify_webhook_signature;
+pub use paginator::{CursorPaginator, Page};
+pub use retryable::{RetryableAdminClient, TokenRefreshCallback};
+
+use retryable::is_mutation;
+
+include!(concat!(env!("OUT_DIR"), "/api_version.rs"));
 
 /// OAuth token for Admin API access.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +71,90 @@ pub struct OAuthToken {
     pub shop: String,
 }
 
+/// How [`AdminClient`] retries requests that fail with a transient error.
+///
+/// The delay before each retry doubles from `base_delay` up to `max_delay`.
+/// `jitter` randomizes it so concurrent requests don't retry in lockstep:
+/// `0.0` waits the whole delay, `1.0` ("full jitter") waits anywhere between
+/// zero and the whole delay.
+///
+/// Rate-limited (`429`) and server error (`5xx`) responses have separate
+/// policies, set with [`AdminClientConfig::rate_limit_retry`] and
+/// [`AdminClientConfig::server_error_retry`]. Server errors are only retried
+/// for queries, since a mutation may already have been applied.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (`0` disables retrying).
+    pub max_retries: u32,
+    /// Delay before the first retry, before jitter.
+    pub base_delay: Duration,
+    /// Upper bound on the delay, before jitter.
+    pub max_delay: Duration,
+    /// Fraction of the delay that is randomized (`0.0` to `1.0`).
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            jitter: 1.0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            jitter: 0.0,
+        }
+    }
+
+    /// Set the number of retries.
+    #[must_use]
+    pub const fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the delay before the first retry.
+    #[must_use]
+    pub const fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set the upper bound on the delay.
+    #[must_use]
+    pub const fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Set the randomized fraction of the delay (clamped to `0.0..=1.0`).
+    #[must_use]
+    pub const fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Delay before retry number `retry` (counting from 1).
+    fn delay(&self, retry: u32) -> Duration {
+        let factor = 1_u32
+            .checked_shl(retry.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        delay.mul_f64((-self.jitter.clamp(0.0, 1.0)).mul_add(rand::random::<f64>(), 1.0))
+    }
+}
+
 /// Input for updating a product.
 ///
 /// All fields are optional - only provided fields will be updated.
@@ -63,6 +174,92 @@ pub struct ProductUpdateInput<'a> {
     pub status: Option<&'a str>,
 }
 
+impl<'a> ProductUpdateInput<'a> {
+    /// Start building an update with no fields set.
+    #[must_use]
+    pub fn builder() -> ProductUpdateInputBuilder<'a> {
+        ProductUpdateInputBuilder::default()
+    }
+}
+
+/// Builder for [`ProductUpdateInput`].
+#[derive(Debug, Default)]
+pub struct ProductUpdateInputBuilder<'a> {
+    input: ProductUpdateInput<'a>,
+}
+
+impl<'a> ProductUpdateInputBuilder<'a> {
+    /// Set the title (`None` leaves it unchanged).
+    #[must_use]
+    pub fn title(mut self, title: impl Into<Option<&'a str>>) -> Self {
+        self.input.title = title.into();
+        self
+    }
+
+    /// Set the HTML description (`None` leaves it unchanged).
+    #[must_use]
+    pub fn description_html(mut self, description_html: impl Into<Option<&'a str>>) -> Self {
+        self.input.description_html = description_html.into();
+        self
+    }
+
+    /// Set the vendor name (`None` leaves it unchanged).
+    #[must_use]
+    pub fn vendor(mut self, vendor: impl Into<Option<&'a str>>) -> Self {
+        self.input.vendor = vendor.into();
+        self
+    }
+
+    /// Set the product type (`None` leaves it unchanged).
+    #[must_use]
+    pub fn product_type(mut self, product_type: impl Into<Option<&'a str>>) -> Self {
+        self.input.product_type = product_type.into();
+        self
+    }
+
+    /// Set the tags, replacing existing ones (`None` leaves them unchanged).
+    #[must_use]
+    pub fn tags(mut self, tags: impl Into<Option<Vec<String>>>) -> Self {
+        self.input.tags = tags.into();
+        self
+    }
+
+    /// Set the status: "ACTIVE", "DRAFT", or "ARCHIVED" (`None` leaves it unchanged).
+    #[must_use]
+    pub fn status(mut self, status: impl Into<Option<&'a str>>) -> Self {
+        self.input.status = status.into();
+        self
+    }
+
+    /// Finish building the update.
+    #[must_use]
+    pub fn build(self) -> ProductUpdateInput<'a> {
+        self.input
+    }
+}
+
+/// Input for updating a product variant.
+///
+/// All fields are optional - only provided fields will be updated.
+#[derive(Debug, Default)]
+pub struct VariantUpdateInput<'a> {
+    /// New price.
+    pub price: Option<&'a str>,
+    /// New compare-at price.
+    pub compare_at_price: Option<&'a str>,
+    /// New SKU (updated through the inventory item).
+    pub sku: Option<&'a str>,
+    /// New barcode (validated; an empty barcode clears it).
+    pub barcode: Option<Barcode>,
+    /// New weight (updated through the inventory item).
+    pub weight: Option<f64>,
+    /// Unit of `weight` ("KILOGRAMS", "GRAMS", "POUNDS" or "OUNCES").
+    /// Defaults to pounds when a weight is given without a unit.
+    pub weight_unit: Option<&'a str>,
+    /// New package dimensions (stored as variant metafields).
+    pub dimensions: Option<VariantDimensions>,
+}
+
 /// Input for creating a discount code.
 #[derive(Debug)]
 pub struct DiscountCreateInput<'a> {
@@ -80,6 +277,43 @@ pub struct DiscountCreateInput<'a> {
     pub ends_at: Option<&'a str>,
     /// Maximum number of uses (optional).
     pub usage_limit: Option<i64>,
+    /// Minimum purchase requirement.
+    pub minimum_requirement: DiscountMinimumRequirement,
+    /// Items the discount applies to.
+    pub items: DiscountItems,
+    /// Whether each customer may only use the discount once.
+    pub once_per_customer: bool,
+    /// Which customers may use the discount.
+    pub customer_eligibility: CustomerEligibility,
+}
+
+/// Items a new discount code applies to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DiscountItems {
+    /// Every item in the store.
+    #[default]
+    All,
+    /// Items in any of these collections (collection IDs).
+    Collections(Vec<CollectionId>),
+    /// These products and product variants (product and variant IDs).
+    Products {
+        /// Products whose every variant is entitled.
+        product_ids: Vec<ProductId>,
+        /// Individual entitled variants.
+        variant_ids: Vec<VariantId>,
+    },
+}
+
+/// Customers eligible for a new discount code.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CustomerEligibility {
+    /// Any customer.
+    #[default]
+    All,
+    /// Customers in any of these segments (segment IDs).
+    CustomerSegments(Vec<String>),
+    /// Only these customers (customer IDs).
+    SpecificCustomers(Vec<CustomerId>),
 }
 
 /// Input for updating a discount code.
@@ -93,6 +327,49 @@ pub struct DiscountUpdateInput<'a> {
     pub ends_at: Option<&'a str>,
 }
 
+impl<'a> DiscountUpdateInput<'a> {
+    /// Start building an update with no fields set.
+    #[must_use]
+    pub fn builder() -> DiscountUpdateInputBuilder<'a> {
+        DiscountUpdateInputBuilder::default()
+    }
+}
+
+/// Builder for [`DiscountUpdateInput`].
+#[derive(Debug, Default)]
+pub struct DiscountUpdateInputBuilder<'a> {
+    input: DiscountUpdateInput<'a>,
+}
+
+impl<'a> DiscountUpdateInputBuilder<'a> {
+    /// Set the title (`None` leaves it unchanged).
+    #[must_use]
+    pub fn title(mut self, title: impl Into<Option<&'a str>>) -> Self {
+        self.input.title = title.into();
+        self
+    }
+
+    /// Set the start date (`None` leaves it unchanged).
+    #[must_use]
+    pub fn starts_at(mut self, starts_at: impl Into<Option<&'a str>>) -> Self {
+        self.input.starts_at = starts_at.into();
+        self
+    }
+
+    /// Set the end date (`None` leaves it unchanged).
+    #[must_use]
+    pub fn ends_at(mut self, ends_at: impl Into<Option<&'a str>>) -> Self {
+        self.input.ends_at = ends_at.into();
+        self
+    }
+
+    /// Finish building the update.
+    #[must_use]
+    pub const fn build(self) -> DiscountUpdateInput<'a> {
+        self.input
+    }
+}
+
 /// Shopify Admin API GraphQL client.
 ///
 /// Provides type-safe access to the Admin API for managing products,
@@ -113,8 +390,55 @@ struct AdminClientInner {
     api_version: String,
     client_id: String,
     client_secret: String,
+    /// Scheme and host requests are sent to (`https://{store}`)
+    base_url: String,
+    /// Retries for rate-limited requests
+    rate_limit_retry: RetryPolicy,
+    /// Retries for Shopify server errors
+    server_error_retry: RetryPolicy,
+    /// Query cost bucket reported with the latest response
+    throttle_status: RwLock<Option<ThrottleStatus>>,
     /// In-memory token cache (persisted externally via `set_token`/`get_token`)
     token: RwLock<Option<OAuthToken>>,
+    /// Fetches a new token when Shopify rejects the cached one
+    token_refresh: OnceLock<TokenRefreshCallback>,
+    /// Held while refreshing so concurrent requests share one refresh
+    refresh_lock: Mutex<()>,
+}
+
+/// Result of sending a raw GraphQL document with [`AdminClient::check_document`].
+#[derive(Debug, Clone, Default)]
+pub struct DocumentCheck {
+    /// API version Shopify actually served (`X-Shopify-API-Version`).
+    ///
+    /// Differs from the requested version when that version is unsupported.
+    pub api_version: Option<String>,
+    /// Deprecation notice (`X-Shopify-API-Deprecated-Reason`), if any.
+    pub deprecated_reason: Option<String>,
+    /// Messages of any GraphQL errors in the response.
+    pub errors: Vec<String>,
+}
+
+/// Validate that an Admin API version is in `YYYY-MM` format.
+///
+/// # Errors
+///
+/// Returns `AdminShopifyError::InvalidApiVersion` if the version is malformed.
+pub fn validate_api_version(version: &str) -> Result<(), AdminShopifyError> {
+    let valid = version.split_once('-').is_some_and(|(year, month)| {
+        year.len() == 4
+            && month.len() == 2
+            && year.bytes().all(|b| b.is_ascii_digit())
+            && month
+                .parse::<u8>()
+                .is_ok_and(|month| (1..=12).contains(&month))
+    });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AdminShopifyError::InvalidApiVersion(version.to_string()))
+    }
 }
 
 /// GraphQL response wrapper.
@@ -122,6 +446,7 @@ struct AdminClientInner {
 struct GraphQLResponse<T> {
     data: Option<T>,
     errors: Option<Vec<GraphQLErrorResponse>>,
+    extensions: Option<cost::ResponseExtensions>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -170,7 +495,7 @@ fn sort_customers(
             CustomerSortKey::Name => a.display_name.cmp(&b.display_name),
             CustomerSortKey::CreatedAt => a.created_at.cmp(&b.created_at),
             CustomerSortKey::UpdatedAt => a.updated_at.cmp(&b.updated_at),
-            CustomerSortKey::Id => a.id.cmp(&b.id),
+            CustomerSortKey::Id => a.id.as_str().cmp(b.id.as_str()),
             CustomerSortKey::Location => {
                 let a_loc = a
                     .default_address
@@ -225,25 +550,79 @@ fn sort_payouts(payouts: &mut [Payout], sort_key: super::types::PayoutSortKey, r
 }
 
 impl AdminClient {
-    /// Create a new Admin API client.
+    /// Create a new Admin API client with the default HTTP settings.
     ///
     /// # Arguments
     ///
     /// * `config` - Shopify Admin API configuration
-    #[must_use]
-    pub fn new(config: &ShopifyAdminConfig) -> Self {
-        let client = reqwest::Client::new();
+    ///
+    /// # Errors
+    ///
+    /// Returns `AdminShopifyError::InvalidApiVersion` if `config.api_version`
+    /// is not in `YYYY-MM` format, or `AdminShopifyError::Http` if the HTTP
+    /// client can't be built.
+    pub fn new(config: &ShopifyAdminConfig) -> Result<Self, AdminShopifyError> {
+        Self::with_http_config(config, AdminClientConfig::default())
+    }
 
-        Self {
+    /// Create a new Admin API client with custom HTTP settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Shopify Admin API configuration
+    /// * `http` - Connection pool, timeout and protocol settings
+    ///
+    /// # Errors
+    ///
+    /// Returns `AdminShopifyError::InvalidApiVersion` if `config.api_version`
+    /// is not in `YYYY-MM` format, or `AdminShopifyError::Http` if the HTTP
+    /// client can't be built.
+    pub fn with_http_config(
+        config: &ShopifyAdminConfig,
+        http: AdminClientConfig,
+    ) -> Result<Self, AdminShopifyError> {
+        Self::with_base_url(config, http, format!("https://{}", config.store))
+    }
+
+    /// Create a client that sends requests to `base_url` instead of the store,
+    /// such as a mock server in tests.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Shopify Admin API configuration
+    /// * `http` - Connection pool, timeout and protocol settings
+    /// * `base_url` - Scheme and host to send requests to (no trailing slash)
+    ///
+    /// # Errors
+    ///
+    /// Returns `AdminShopifyError::InvalidApiVersion` if `config.api_version`
+    /// is not in `YYYY-MM` format, or `AdminShopifyError::Http` if the HTTP
+    /// client can't be built.
+    pub fn with_base_url(
+        config: &ShopifyAdminConfig,
+        http: AdminClientConfig,
+        base_url: String,
+    ) -> Result<Self, AdminShopifyError> {
+        validate_api_version(&config.api_version)?;
+
+        let client = http.build_client()?;
+
+        Ok(Self {
             inner: Arc::new(AdminClientInner {
                 client,
                 store: config.store.clone(),
                 api_version: config.api_version.clone(),
                 client_id: config.client_id.clone(),
                 client_secret: config.client_secret.expose_secret().to_string(),
+                base_url,
+                rate_limit_retry: http.rate_limit_retry,
+                server_error_retry: http.server_error_retry,
+                throttle_status: RwLock::new(None),
                 token: RwLock::new(None),
+                token_refresh: OnceLock::new(),
+                refresh_lock: Mutex::new(()),
             }),
-        }
+        })
     }
 
     /// Get the store domain.
@@ -252,6 +631,15 @@ impl AdminClient {
         &self.inner.store
     }
 
+    /// Get the configured Admin API version.
+    ///
+    /// Typed queries always use [`SHOPIFY_API_VERSION`]; this version is only
+    /// used by [`Self::check_document`].
+    #[must_use]
+    pub fn api_version(&self) -> &str {
+        &self.inner.api_version
+    }
+
     /// Get the client ID.
     #[must_use]
     pub fn client_id(&self) -> &str {
@@ -345,6 +733,17 @@ impl AdminClient {
         *self.inner.token.write().await = None;
     }
 
+    /// Check that the Admin API is reachable with the current token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no token or the API request fails.
+    pub async fn ping(&self) -> Result<(), AdminShopifyError> {
+        let body = serde_json::json!({ "query": "{ shop { id } }" });
+        self.execute_raw_graphql(body).await?;
+        Ok(())
+    }
+
     /// Get the current access token string.
     async fn get_access_token(&self) -> Result<String, AdminShopifyError> {
         let token = self.inner.token.read().await;
@@ -359,29 +758,26 @@ impl AdminClient {
     // =========================================================================
 
     /// Execute a GraphQL query.
+    ///
+    /// Rate-limited and server error responses are retried according to the
+    /// client's [`RetryPolicy`]s; `RateLimited` is only returned once the
+    /// retries run out.
+    #[tracing::instrument(
+        skip_all,
+        fields(operation = tracing::field::Empty, attempts = tracing::field::Empty)
+    )]
     async fn execute<Q: GraphQLQuery>(
         &self,
         variables: Q::Variables,
     ) -> Result<Q::ResponseData, AdminShopifyError>
     where
+        Q::Variables: Sync,
         Q::ResponseData: DeserializeOwned,
     {
-        let access_token = self.get_access_token().await?;
-        let endpoint = format!(
-            "https://{}/admin/api/{}/graphql.json",
-            self.inner.store, self.inner.api_version
-        );
-
         let body = Q::build_query(variables);
-
+        tracing::Span::current().record("operation", body.operation_name);
         let response = self
-            .inner
-            .client
-            .post(&endpoint)
-            .header("X-Shopify-Access-Token", &access_token)
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
+            .send_authorized(SHOPIFY_API_VERSION, &body, is_mutation(body.query))
             .await?;
 
         // Check for rate limiting
@@ -395,14 +791,8 @@ impl AdminClient {
             return Err(AdminShopifyError::RateLimited(retry_after));
         }
 
-        // Check for unauthorized
-        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-            return Err(AdminShopifyError::Unauthorized(
-                "Invalid or expired access token".to_string(),
-            ));
-        }
-
         let graphql_response: GraphQLResponse<Q::ResponseData> = response.json().await?;
+        self.record_cost(graphql_response.extensions).await;
 
         // Check for GraphQL errors
         if let Some(errors) = graphql_response.errors
@@ -435,6 +825,70 @@ impl AdminClient {
         })
     }
 
+    /// Send a GraphQL document and report what Shopify said about it.
+    ///
+    /// Unlike the typed queries, GraphQL errors are returned in the
+    /// [`DocumentCheck`] rather than as an error, along with the served API
+    /// version and any deprecation notice from the response headers. Used to
+    /// check queries against a new API version before upgrading.
+    ///
+    /// Sent to the configured [`Self::api_version`] rather than
+    /// [`SHOPIFY_API_VERSION`], so a client configured with a newer version
+    /// can check documents before the crate is rebuilt against it.
+    ///
+    /// Shopify only executes `operation_name`, but validates the whole
+    /// document, so pass a read-only operation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no token or the HTTP request fails.
+    pub async fn check_document(
+        &self,
+        document: &str,
+        operation_name: Option<&str>,
+    ) -> Result<DocumentCheck, AdminShopifyError> {
+        let response = self
+            .send_authorized(
+                &self.inner.api_version,
+                &serde_json::json!({
+                "query": document,
+                "operationName": operation_name,
+                "variables": {},
+                }),
+                is_mutation(document),
+            )
+            .await?;
+
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(ToString::to_string)
+        };
+        let api_version = header("X-Shopify-API-Version");
+        let deprecated_reason = header("X-Shopify-API-Deprecated-Reason");
+
+        let body: serde_json::Value = response.json().await?;
+        let errors = body
+            .get("errors")
+            .and_then(|e| e.as_array())
+            .map(|errors| {
+                errors
+                    .iter()
+                    .filter_map(|e| e.get("message").and_then(|m| m.as_str()))
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(DocumentCheck {
+            api_version,
+            deprecated_reason,
+            errors,
+        })
+    }
+
     /// Execute a raw GraphQL query with JSON body.
     ///
     /// This is used for mutations that need dynamic field handling
@@ -443,24 +897,21 @@ impl AdminClient {
         &self,
         body: serde_json::Value,
     ) -> Result<serde_json::Value, AdminShopifyError> {
-        let access_token = self.get_access_token().await?;
-        let endpoint = format!(
-            "https://{}/admin/api/{}/graphql.json",
-            self.inner.store, self.inner.api_version
-        );
-
+        let mutation = body
+            .get("query")
+            .and_then(serde_json::Value::as_str)
+            .is_some_and(is_mutation);
         let response: serde_json::Value = self
-            .inner
-            .client
-            .post(&endpoint)
-            .header("X-Shopify-Access-Token", &access_token)
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
+            .send_authorized(SHOPIFY_API_VERSION, &body, mutation)
             .await?
             .json()
             .await?;
 
+        let extensions = response
+            .get("extensions")
+            .and_then(|e| serde_json::from_value(e.clone()).ok());
+        self.record_cost(extensions).await;
+
         // Check for top-level GraphQL errors
         if let Some(errors) = response.get("errors").and_then(|e| e.as_array())
             && !errors.is_empty()
@@ -1,5 +1,6 @@
 //! Order editing operations for the Admin API.
 
+use naked_pineapple_core::{OrderId, VariantId};
 use tracing::instrument;
 
 use super::{
@@ -33,7 +34,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(order_id = %order_id))]
     pub async fn order_edit_begin(
         &self,
-        order_id: &str,
+        order_id: &OrderId,
     ) -> Result<CalculatedOrder, AdminShopifyError> {
         let variables = super::queries::order_edit_begin::Variables {
             id: order_id.to_string(),
@@ -55,7 +56,7 @@ impl AdminClient {
             }
 
             if let Some(calc_order) = payload.calculated_order {
-                return Ok(convert_calculated_order(calc_order));
+                return Ok(convert_calculated_order(calc_order)?);
             }
         }
 
@@ -81,7 +82,7 @@ impl AdminClient {
     pub async fn order_edit_add_variant(
         &self,
         calculated_order_id: &str,
-        variant_id: &str,
+        variant_id: &VariantId,
         quantity: i64,
     ) -> Result<(), AdminShopifyError> {
         let variables = super::queries::order_edit_add_variant::Variables {
@@ -1,13 +1,17 @@
 //! Order management operations for the Admin API.
 
+use std::collections::HashMap;
+
+use naked_pineapple_core::OrderId;
 use tracing::instrument;
 
 use super::{
     AdminClient, AdminShopifyError, GraphQLError,
     conversions::{convert_order, convert_order_connection, convert_order_list_connection},
     queries::{
-        GetOrder, GetOrderDetail, GetOrders, OrderCancel, OrderCapture, OrderClose,
-        OrderMarkAsPaid, OrderOpen, OrderTagsAdd, OrderTagsRemove, OrderUpdate,
+        GetOrder, GetOrderDetail, GetOrderUnitsSold, GetOrders, GetOrdersCount, OrderCancel,
+        OrderCapture, OrderClose, OrderMarkAsPaid, OrderOpen, OrderTagsAdd, OrderTagsRemove,
+        OrderUpdate,
     },
 };
 use crate::shopify::types::{Order, OrderConnection, OrderListConnection, OrderSortKey};
@@ -23,16 +27,17 @@ impl AdminClient {
     ///
     /// Returns an error if the API request fails or returns an error response.
     #[instrument(skip(self), fields(order_id = %id))]
-    pub async fn get_order(&self, id: &str) -> Result<Option<Order>, AdminShopifyError> {
+    pub async fn get_order(&self, id: &OrderId) -> Result<Option<Order>, AdminShopifyError> {
         let variables = super::queries::get_order::Variables {
             id: id.to_string(),
             line_item_count: Some(50),
             fulfillment_count: Some(10),
+            event_count: Some(100),
         };
 
         let response = self.execute::<GetOrder>(variables).await?;
 
-        Ok(response.order.map(convert_order))
+        Ok(response.order.map(convert_order).transpose()?)
     }
 
     /// Get a paginated list of orders.
@@ -63,7 +68,7 @@ impl AdminClient {
 
         let response = self.execute::<GetOrders>(variables).await?;
 
-        Ok(convert_order_connection(response.orders))
+        Ok(convert_order_connection(response.orders)?)
     }
 
     /// Get detailed order information for the order detail page.
@@ -81,7 +86,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(order_id = %id))]
     pub async fn get_order_detail(
         &self,
-        id: &str,
+        id: &OrderId,
     ) -> Result<Option<super::queries::get_order_detail::GetOrderDetailOrder>, AdminShopifyError>
     {
         let variables = super::queries::get_order_detail::Variables {
@@ -150,7 +155,75 @@ impl AdminClient {
 
         let response = self.execute::<GetOrders>(variables).await?;
 
-        Ok(convert_order_list_connection(response.orders))
+        Ok(convert_order_list_connection(response.orders)?)
+    }
+
+    /// Get the units sold per SKU across every order matching a query.
+    ///
+    /// Quantities are current quantities, so items removed from an order
+    /// don't count; cancelled orders and line items without a SKU are
+    /// skipped. Only the first 50 line items of each order are counted.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Search query (Shopify query syntax), e.g. a `created_at` range
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any API request fails or returns an error response.
+    #[instrument(skip(self))]
+    pub async fn get_units_sold_by_sku(
+        &self,
+        query: &str,
+    ) -> Result<HashMap<String, i64>, AdminShopifyError> {
+        let mut units = HashMap::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let variables = super::queries::get_order_units_sold::Variables {
+                first: Some(25),
+                after: cursor.clone(),
+                query: Some(query.to_string()),
+            };
+
+            let response = self.execute::<GetOrderUnitsSold>(variables).await?;
+
+            for order in response.orders.edges {
+                if order.node.cancelled_at.is_some() {
+                    continue;
+                }
+                for item in order.node.line_items.edges {
+                    if let Some(sku) = item.node.sku.filter(|sku| !sku.is_empty()) {
+                        *units.entry(sku).or_insert(0) += item.node.current_quantity;
+                    }
+                }
+            }
+
+            let page_info = response.orders.page_info;
+            if page_info.has_next_page {
+                cursor = page_info.end_cursor;
+            } else {
+                break;
+            }
+        }
+
+        Ok(units)
+    }
+
+    /// Get the count of orders matching a query.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Optional search query (Shopify query syntax)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_orders_count(&self, query: Option<String>) -> Result<i64, AdminShopifyError> {
+        let variables = super::queries::get_orders_count::Variables { query };
+        let response = self.execute::<GetOrdersCount>(variables).await?;
+        Ok(response.orders_count.map_or(0, |c| c.count))
     }
 
     /// Update an order's note.
@@ -166,7 +239,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(order_id = %id))]
     pub async fn update_order_note(
         &self,
-        id: &str,
+        id: &OrderId,
         note: Option<&str>,
     ) -> Result<(), AdminShopifyError> {
         use super::queries::order_update::{OrderInput, Variables};
@@ -218,7 +291,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(order_id = %id))]
     pub async fn update_order_tags(
         &self,
-        id: &str,
+        id: &OrderId,
         tags: Vec<String>,
     ) -> Result<(), AdminShopifyError> {
         use super::queries::order_update::{OrderInput, Variables};
@@ -267,7 +340,7 @@ impl AdminClient {
     ///
     /// Returns an error if the API request fails or returns user errors.
     #[instrument(skip(self), fields(order_id = %id))]
-    pub async fn mark_order_as_paid(&self, id: &str) -> Result<(), AdminShopifyError> {
+    pub async fn mark_order_as_paid(&self, id: &OrderId) -> Result<(), AdminShopifyError> {
         use super::queries::order_mark_as_paid::{OrderMarkAsPaidInput, Variables};
 
         let variables = Variables {
@@ -309,7 +382,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(order_id = %id))]
     pub async fn cancel_order(
         &self,
-        id: &str,
+        id: &OrderId,
         reason: Option<&str>,
         notify_customer: bool,
         refund: bool,
@@ -365,7 +438,7 @@ impl AdminClient {
     ///
     /// Returns an error if the API request fails or returns user errors.
     #[instrument(skip(self), fields(order_id = %id))]
-    pub async fn archive_order(&self, id: &str) -> Result<(), AdminShopifyError> {
+    pub async fn archive_order(&self, id: &OrderId) -> Result<(), AdminShopifyError> {
         use super::queries::order_close::{OrderCloseInput, Variables};
 
         let variables = Variables {
@@ -401,7 +474,7 @@ impl AdminClient {
     ///
     /// Returns an error if the API request fails or returns user errors.
     #[instrument(skip(self), fields(order_id = %id))]
-    pub async fn unarchive_order(&self, id: &str) -> Result<(), AdminShopifyError> {
+    pub async fn unarchive_order(&self, id: &OrderId) -> Result<(), AdminShopifyError> {
         use super::queries::order_open::{OrderOpenInput, Variables};
 
         let variables = Variables {
@@ -441,7 +514,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(order_id = %id))]
     pub async fn capture_order_payment(
         &self,
-        id: &str,
+        id: &OrderId,
         parent_transaction_id: &str,
         amount: &str,
     ) -> Result<(), AdminShopifyError> {
@@ -489,7 +562,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(order_id = %id))]
     pub async fn add_tags_to_order(
         &self,
-        id: &str,
+        id: &OrderId,
         tags: &[String],
     ) -> Result<Vec<String>, AdminShopifyError> {
         use super::queries::order_tags_add::Variables;
@@ -537,7 +610,7 @@ impl AdminClient {
     #[instrument(skip(self), fields(order_id = %id))]
     pub async fn remove_tags_from_order(
         &self,
-        id: &str,
+        id: &OrderId,
         tags: &[String],
     ) -> Result<Vec<String>, AdminShopifyError> {
         use super::queries::order_tags_remove::Variables;
@@ -0,0 +1,218 @@
+//! Cursor pagination over paginated Admin API list methods.
+//!
+//! ```rust,ignore
+//! use futures::TryStreamExt;
+//!
+//! let all_products: Vec<_> = CursorPaginator::new(|cursor| {
+//!     Box::pin(client.get_products(250, cursor, None))
+//! })
+//! .try_collect()
+//! .await?;
+//! ```
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use async_stream::stream;
+use futures::future::BoxFuture;
+use futures::{Stream, TryStreamExt};
+
+use super::AdminShopifyError;
+use crate::shopify::types::{
+    AdminProduct, AdminProductConnection, Customer, CustomerConnection, DiscountCode,
+    DiscountCodeConnection, GiftCard, GiftCardConnection, InventoryItem, InventoryItemConnection,
+    Metaobject, MetaobjectConnection, Order, OrderConnection, OrderListConnection, OrderListItem,
+    PageInfo,
+};
+
+/// One page of a cursor-paginated list.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    /// Items in this page.
+    pub items: Vec<T>,
+    /// Pagination info.
+    pub page_info: PageInfo,
+}
+
+/// Implement `From<$connection> for Page<$item>` for a connection type.
+macro_rules! impl_page_from {
+    ($($connection:ty => $item:ty, $field:ident;)*) => {
+        $(
+            impl From<$connection> for Page<$item> {
+                fn from(connection: $connection) -> Self {
+                    Self {
+                        items: connection.$field,
+                        page_info: connection.page_info,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_page_from! {
+    AdminProductConnection => AdminProduct, products;
+    CustomerConnection => Customer, customers;
+    DiscountCodeConnection => DiscountCode, discount_codes;
+    GiftCardConnection => GiftCard, gift_cards;
+    InventoryItemConnection => InventoryItem, items;
+    MetaobjectConnection => Metaobject, metaobjects;
+    OrderConnection => Order, orders;
+    OrderListConnection => OrderListItem, orders;
+}
+
+/// Stream of every item across all pages of a paginated list.
+///
+/// `fetch` is called with `None` for the first page and the previous page's
+/// end cursor after that, until a page reports no next page. The stream
+/// ends after the first error.
+pub struct CursorPaginator<'a, T> {
+    inner: Pin<Box<dyn Stream<Item = Result<T, AdminShopifyError>> + Send + 'a>>,
+}
+
+impl<'a, T: Send + 'a> CursorPaginator<'a, T> {
+    /// Paginate with `fetch`, which returns the page after a cursor.
+    ///
+    /// The page can be a [`Page`] or any connection type that converts into
+    /// one, so list methods can be passed through directly.
+    pub fn new<F, P>(fetch: F) -> Self
+    where
+        F: Fn(Option<String>) -> BoxFuture<'a, Result<P, AdminShopifyError>> + Send + 'a,
+        P: Into<Page<T>> + Send + 'a,
+    {
+        let inner = stream! {
+            let mut cursor = None;
+            loop {
+                let page: Page<T> = match fetch(cursor.take()).await {
+                    Ok(page) => page.into(),
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
+                };
+
+                for item in page.items {
+                    yield Ok(item);
+                }
+
+                match page.page_info.end_cursor {
+                    Some(end_cursor) if page.page_info.has_next_page => cursor = Some(end_cursor),
+                    _ => break,
+                }
+            }
+        };
+
+        Self {
+            inner: Box::pin(inner),
+        }
+    }
+
+    /// Fetch every page and collect the items.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from `fetch`.
+    pub async fn collect_all(self) -> Result<Vec<T>, AdminShopifyError> {
+        self.try_collect().await
+    }
+}
+
+impl<T> Stream for CursorPaginator<'_, T> {
+    type Item = Result<T, AdminShopifyError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::StreamExt;
+
+    use super::*;
+
+    /// Mock list method: 3 pages of 2 items each, cursors "1" and "2".
+    fn mock_page(cursor: Option<&str>) -> Result<Page<u32>, AdminShopifyError> {
+        let (items, next) = match cursor {
+            None => (vec![1, 2], Some("1")),
+            Some("1") => (vec![3, 4], Some("2")),
+            Some("2") => (vec![5, 6], None),
+            Some(other) => return Err(AdminShopifyError::NotFound(other.to_string())),
+        };
+        Ok(Page {
+            items,
+            page_info: PageInfo {
+                has_next_page: next.is_some(),
+                has_previous_page: cursor.is_some(),
+                start_cursor: None,
+                end_cursor: next.map(str::to_string),
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_collects_every_page() {
+        let calls = AtomicUsize::new(0);
+
+        let items = CursorPaginator::new(|cursor| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { mock_page(cursor.as_deref()) })
+        })
+        .collect_all()
+        .await
+        .unwrap();
+
+        assert_eq!(items, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_only_fetches_pages_that_are_read() {
+        let calls = AtomicUsize::new(0);
+
+        let first_three: Vec<u32> = CursorPaginator::new(|cursor| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { mock_page(cursor.as_deref()) })
+        })
+        .take(3)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+        assert_eq!(first_three, vec![1, 2, 3]);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stops_at_first_error() {
+        let results: Vec<_> = CursorPaginator::new(|cursor| {
+            Box::pin(async move {
+                match cursor.as_deref() {
+                    Some("1") => mock_page(Some("bad")),
+                    cursor => mock_page(cursor),
+                }
+            })
+        })
+        .collect()
+        .await;
+
+        assert_eq!(results.len(), 3);
+        assert!(results.last().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_page_from_connection() {
+        let page: Page<GiftCard> = GiftCardConnection {
+            gift_cards: Vec::new(),
+            page_info: mock_page(None).unwrap().page_info,
+            total_count: None,
+        }
+        .into();
+
+        assert!(page.items.is_empty());
+        assert_eq!(page.page_info.end_cursor.as_deref(), Some("1"));
+    }
+}
@@ -1,16 +1,27 @@
 //! Product CRUD operations for the Admin API.
 
+use std::collections::HashMap;
+
+use futures::{StreamExt, stream};
+use naked_pineapple_core::{Barcode, ProductId, VariantId};
 use tracing::instrument;
 
 use super::{
-    AdminClient, AdminShopifyError, GraphQLError, ProductUpdateInput,
-    conversions::{convert_product, convert_product_connection},
+    AdminClient, AdminShopifyError, GraphQLError, ProductUpdateInput, VariantUpdateInput,
+    conversions::{convert_product, convert_product_connection, convert_variant},
     queries::{
-        GetProduct, GetProducts, ProductCreate, ProductDelete, ProductUpdate,
-        ProductVariantsBulkUpdate,
+        GetProduct, GetProducts, GetVariantProductId, ProductByVariantId, ProductCreate,
+        ProductDelete, ProductUpdate, ProductVariantsBulkReorder, ProductVariantsBulkUpdate,
+        VariantById,
     },
 };
-use crate::shopify::types::{AdminProduct, AdminProductConnection, AdminProductVariant, Money};
+use crate::shopify::types::{
+    AdminProduct, AdminProductConnection, AdminProductVariant, BulkSkuResult, Money, SkuUpdate,
+    SkuUpdateFailure, VariantDimensions,
+};
+
+/// Maximum number of Admin API requests in flight during a bulk SKU update.
+const BULK_SKU_CONCURRENCY: usize = 4;
 
 impl AdminClient {
     /// Get a product by ID.
@@ -23,7 +34,10 @@ impl AdminClient {
     ///
     /// Returns an error if the API request fails or returns an error response.
     #[instrument(skip(self), fields(product_id = %id))]
-    pub async fn get_product(&self, id: &str) -> Result<Option<AdminProduct>, AdminShopifyError> {
+    pub async fn get_product(
+        &self,
+        id: &ProductId,
+    ) -> Result<Option<AdminProduct>, AdminShopifyError> {
         let variables = super::queries::get_product::Variables {
             id: id.to_string(),
             media_count: Some(10),
@@ -32,7 +46,7 @@ impl AdminClient {
 
         let response = self.execute::<GetProduct>(variables).await?;
 
-        Ok(response.product.map(convert_product))
+        Ok(response.product.map(convert_product).transpose()?)
     }
 
     /// Get a paginated list of products.
@@ -63,7 +77,7 @@ impl AdminClient {
 
         let response = self.execute::<GetProducts>(variables).await?;
 
-        Ok(convert_product_connection(response.products))
+        Ok(convert_product_connection(response.products)?)
     }
 
     /// Create a new product.
@@ -172,7 +186,7 @@ impl AdminClient {
     #[instrument(skip(self, input))]
     pub async fn update_product(
         &self,
-        id: &str,
+        id: &ProductId,
         input: ProductUpdateInput<'_>,
     ) -> Result<String, AdminShopifyError> {
         use super::queries::product_update::{ProductInput, ProductStatus, Variables};
@@ -246,7 +260,7 @@ impl AdminClient {
     ///
     /// Returns an error if the API request fails or returns user errors.
     #[instrument(skip(self))]
-    pub async fn delete_product(&self, id: &str) -> Result<String, AdminShopifyError> {
+    pub async fn delete_product(&self, id: &ProductId) -> Result<String, AdminShopifyError> {
         use super::queries::product_delete::{ProductDeleteInput, Variables};
 
         let variables = Variables {
@@ -282,58 +296,62 @@ impl AdminClient {
 
     /// Update a product variant.
     ///
+    /// SKU and weight are set on the variant's inventory item; dimensions are
+    /// stored as `dimension` metafields (see [`VariantDimensions`]).
+    ///
     /// # Arguments
     ///
     /// * `product_id` - Product ID the variant belongs to
     /// * `variant_id` - Variant ID to update
-    /// * `price` - Optional new price
-    /// * `compare_at_price` - Optional compare-at price
-    /// * `sku` - Optional SKU (updated through inventory item)
-    /// * `barcode` - Optional barcode
+    /// * `input` - Fields to update
     ///
     /// # Errors
     ///
     /// Returns an error if the API request fails or returns user errors.
-    #[instrument(skip(self))]
+    #[instrument(skip(self, input))]
     pub async fn update_variant(
         &self,
-        product_id: &str,
-        variant_id: &str,
-        price: Option<&str>,
-        compare_at_price: Option<&str>,
-        sku: Option<&str>,
-        barcode: Option<&str>,
+        product_id: &ProductId,
+        variant_id: &VariantId,
+        input: VariantUpdateInput<'_>,
     ) -> Result<AdminProductVariant, AdminShopifyError> {
         use super::queries::product_variants_bulk_update::{
             InventoryItemInput, ProductVariantsBulkInput, Variables,
         };
 
-        let inventory_item = sku.map(|s| InventoryItemInput {
-            sku: Some(s.to_string()),
-            cost: None,
-            tracked: None,
-            country_code_of_origin: None,
-            harmonized_system_code: None,
-            country_harmonized_system_codes: None,
-            province_code_of_origin: None,
-            measurement: None,
-            requires_shipping: None,
-        });
+        let measurement = input
+            .weight
+            .map(|value| weight_measurement(value, input.weight_unit));
+
+        let inventory_item =
+            (input.sku.is_some() || measurement.is_some()).then(|| InventoryItemInput {
+                sku: input.sku.map(String::from),
+                cost: None,
+                tracked: None,
+                country_code_of_origin: None,
+                harmonized_system_code: None,
+                country_harmonized_system_codes: None,
+                province_code_of_origin: None,
+                measurement,
+                requires_shipping: None,
+            });
+
+        let metafields = input.dimensions.as_ref().map(dimension_metafields);
 
         let variables = Variables {
             product_id: product_id.to_string(),
             variants: vec![ProductVariantsBulkInput {
                 id: Some(variant_id.to_string()),
-                price: price.map(String::from),
-                compare_at_price: compare_at_price.map(String::from),
-                barcode: barcode.map(String::from),
+                price: input.price.map(String::from),
+                compare_at_price: input.compare_at_price.map(String::from),
+                barcode: input.barcode.map(Barcode::into_inner),
                 inventory_item,
                 inventory_policy: None,
                 inventory_quantities: None,
                 quantity_adjustments: None,
                 media_src: None,
                 media_id: None,
-                metafields: None,
+                metafields,
                 option_values: None,
                 requires_components: None,
                 tax_code: None,
@@ -359,8 +377,17 @@ impl AdminClient {
             }
 
             if let Some(variant) = payload.product_variants.and_then(|v| v.into_iter().next()) {
+                let weight = variant.inventory_item.measurement.weight;
+                let dimensions = VariantDimensions::from_metafields(
+                    variant
+                        .metafields
+                        .edges
+                        .iter()
+                        .map(|e| (e.node.key.as_str(), e.node.value.as_str())),
+                );
+
                 return Ok(AdminProductVariant {
-                    id: variant.id,
+                    id: variant.id.try_into()?,
                     title: variant.title,
                     sku: variant.sku,
                     barcode: variant.barcode,
@@ -373,11 +400,12 @@ impl AdminClient {
                         currency_code: "USD".to_string(),
                     }),
                     inventory_quantity: variant.inventory_quantity.unwrap_or(0),
-                    inventory_item_id: String::new(),
+                    inventory_item_id: variant.inventory_item.id.try_into()?,
                     inventory_management: None,
-                    weight: None,
-                    weight_unit: None,
-                    requires_shipping: true,
+                    weight: weight.as_ref().map(|w| w.value),
+                    weight_unit: weight.map(|w| format!("{:?}", w.unit)),
+                    dimensions,
+                    requires_shipping: variant.inventory_item.requires_shipping,
                     image: None,
                     created_at: None,
                     updated_at: None,
@@ -391,4 +419,338 @@ impl AdminClient {
             path: vec![],
         }]))
     }
+
+    /// Set the display order of a product's variants.
+    ///
+    /// Variants not listed keep their relative order after the moved ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_id` - The product ID
+    /// * `variant_positions` - List of (variant ID, position), positions starting at 1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or returns user errors.
+    #[instrument(skip(self))]
+    pub async fn reorder_variants(
+        &self,
+        product_id: &ProductId,
+        variant_positions: Vec<(VariantId, i64)>,
+    ) -> Result<(), AdminShopifyError> {
+        use super::queries::product_variants_bulk_reorder::{
+            ProductVariantPositionInput, Variables,
+        };
+
+        let variables = Variables {
+            product_id: product_id.to_string(),
+            positions: variant_positions
+                .into_iter()
+                .map(|(id, position)| ProductVariantPositionInput {
+                    id: id.into(),
+                    position,
+                })
+                .collect(),
+        };
+
+        let response = self
+            .execute::<ProductVariantsBulkReorder>(variables)
+            .await?;
+
+        if let Some(payload) = response.product_variants_bulk_reorder {
+            if !payload.user_errors.is_empty() {
+                let error_messages: Vec<String> = payload
+                    .user_errors
+                    .iter()
+                    .map(|e| {
+                        let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                        format!("{}: {}", field, e.message)
+                    })
+                    .collect();
+                return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+            }
+
+            return Ok(());
+        }
+
+        Err(AdminShopifyError::GraphQL(vec![GraphQLError {
+            message: "Variant reorder failed".to_string(),
+            locations: vec![],
+            path: vec![],
+        }]))
+    }
+
+    /// Get the product that owns a variant.
+    ///
+    /// Fetches the product in the same request as the variant lookup.
+    /// Returns `None` if the variant doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant_id` - Shopify variant ID (e.g., `gid://shopify/ProductVariant/123`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self), fields(variant_id = %variant_id))]
+    pub async fn get_product_by_variant_id(
+        &self,
+        variant_id: &VariantId,
+    ) -> Result<Option<AdminProduct>, AdminShopifyError> {
+        let variables = super::queries::get_product_by_variant_id::Variables {
+            id: variant_id.to_string(),
+            media_count: Some(10),
+            variant_count: Some(50),
+        };
+
+        let response = self.execute::<ProductByVariantId>(variables).await?;
+
+        Ok(response
+            .product_variant
+            .map(|variant| convert_product(variant.product))
+            .transpose()?)
+    }
+
+    /// Get a single variant by ID.
+    ///
+    /// Prices are in the shop's default currency.
+    /// Returns `None` if the variant doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant_id` - Shopify variant ID (e.g., `gid://shopify/ProductVariant/123`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self), fields(variant_id = %variant_id))]
+    pub async fn get_variant(
+        &self,
+        variant_id: &VariantId,
+    ) -> Result<Option<AdminProductVariant>, AdminShopifyError> {
+        let variables = super::queries::get_variant::Variables {
+            id: variant_id.to_string(),
+        };
+
+        let response = self.execute::<VariantById>(variables).await?;
+        let currency_code = response.shop.currency_code;
+
+        let Some(node) = response.product_variant else {
+            return Ok(None);
+        };
+        let mut variant = convert_variant(node)?;
+        variant.price.currency_code.clone_from(&currency_code);
+        if let Some(compare_at_price) = &mut variant.compare_at_price {
+            compare_at_price.currency_code = currency_code;
+        }
+        Ok(Some(variant))
+    }
+
+    /// Get the ID of the product that owns a variant.
+    ///
+    /// Returns `None` if the variant doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    async fn get_product_id_by_variant_id(
+        &self,
+        variant_id: &VariantId,
+    ) -> Result<Option<ProductId>, AdminShopifyError> {
+        let variables = super::queries::get_variant_product_id::Variables {
+            id: variant_id.to_string(),
+        };
+
+        let response = self.execute::<GetVariantProductId>(variables).await?;
+
+        Ok(response
+            .product_variant
+            .map(|v| v.product.id.try_into())
+            .transpose()?)
+    }
+
+    /// Update the SKUs of many variants.
+    ///
+    /// Variants are grouped by product so each product needs a single
+    /// `productVariantsBulkUpdate` mutation; lookups and mutations run
+    /// concurrently. Once mutations start, failures are reported per variant
+    /// in [`BulkSkuResult::failed`] rather than aborting the batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a product lookup request fails. No SKUs have been
+    /// changed at that point.
+    #[instrument(skip(self, updates), fields(count = updates.len()))]
+    pub async fn bulk_update_skus(
+        &self,
+        updates: Vec<SkuUpdate>,
+    ) -> Result<BulkSkuResult, AdminShopifyError> {
+        let mut result = BulkSkuResult::default();
+
+        let lookups: Vec<_> = stream::iter(updates)
+            .map(|update| async move {
+                let product_id = self.get_product_id_by_variant_id(&update.variant_id).await;
+                (update, product_id)
+            })
+            .buffer_unordered(BULK_SKU_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut by_product: HashMap<ProductId, Vec<SkuUpdate>> = HashMap::new();
+        for (update, product_id) in lookups {
+            match product_id? {
+                Some(product_id) => by_product.entry(product_id).or_default().push(update),
+                None => result.failed.push(SkuUpdateFailure {
+                    variant_id: update.variant_id,
+                    error: "Variant not found".to_string(),
+                }),
+            }
+        }
+
+        let outcomes: Vec<_> = stream::iter(by_product)
+            .map(|(product_id, updates)| async move {
+                let outcome = self.update_product_skus(&product_id, &updates).await;
+                (updates, outcome)
+            })
+            .buffer_unordered(BULK_SKU_CONCURRENCY)
+            .collect()
+            .await;
+
+        for (updates, outcome) in outcomes {
+            match outcome {
+                Ok(()) => result
+                    .updated
+                    .extend(updates.into_iter().map(|u| u.variant_id)),
+                Err(e) => {
+                    let error = e.to_string();
+                    result
+                        .failed
+                        .extend(updates.into_iter().map(|u| SkuUpdateFailure {
+                            variant_id: u.variant_id,
+                            error: error.clone(),
+                        }));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Set the SKUs of variants belonging to one product in a single mutation.
+    async fn update_product_skus(
+        &self,
+        product_id: &ProductId,
+        updates: &[SkuUpdate],
+    ) -> Result<(), AdminShopifyError> {
+        use super::queries::product_variants_bulk_update::{
+            InventoryItemInput, ProductVariantsBulkInput, Variables,
+        };
+
+        let variants = updates
+            .iter()
+            .map(|update| ProductVariantsBulkInput {
+                id: Some(update.variant_id.to_string()),
+                price: None,
+                compare_at_price: None,
+                barcode: None,
+                inventory_item: Some(InventoryItemInput {
+                    sku: Some(update.new_sku.clone()),
+                    cost: None,
+                    tracked: None,
+                    country_code_of_origin: None,
+                    harmonized_system_code: None,
+                    country_harmonized_system_codes: None,
+                    province_code_of_origin: None,
+                    measurement: None,
+                    requires_shipping: None,
+                }),
+                inventory_policy: None,
+                inventory_quantities: None,
+                quantity_adjustments: None,
+                media_src: None,
+                media_id: None,
+                metafields: None,
+                option_values: None,
+                requires_components: None,
+                tax_code: None,
+                taxable: None,
+                unit_price_measurement: None,
+                show_unit_price: None,
+            })
+            .collect();
+
+        let variables = Variables {
+            product_id: product_id.to_string(),
+            variants,
+        };
+
+        let response = self.execute::<ProductVariantsBulkUpdate>(variables).await?;
+
+        let Some(payload) = response.product_variants_bulk_update else {
+            return Err(AdminShopifyError::GraphQL(vec![GraphQLError {
+                message: "Variant update failed".to_string(),
+                locations: vec![],
+                path: vec![],
+            }]));
+        };
+
+        if !payload.user_errors.is_empty() {
+            let error_messages: Vec<String> = payload
+                .user_errors
+                .iter()
+                .map(|e| {
+                    let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                    format!("{}: {}", field, e.message)
+                })
+                .collect();
+            return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+        }
+
+        Ok(())
+    }
+}
+
+/// Build the inventory item measurement for a weight update.
+///
+/// `unit` defaults to pounds.
+fn weight_measurement(
+    value: f64,
+    unit: Option<&str>,
+) -> super::queries::product_variants_bulk_update::InventoryItemMeasurementInput {
+    use super::queries::product_variants_bulk_update::{
+        InventoryItemMeasurementInput, WeightInput, WeightUnit,
+    };
+
+    InventoryItemMeasurementInput {
+        weight: Some(WeightInput {
+            value,
+            unit: match unit.unwrap_or("POUNDS") {
+                "KILOGRAMS" => WeightUnit::KILOGRAMS,
+                "GRAMS" => WeightUnit::GRAMS,
+                "POUNDS" => WeightUnit::POUNDS,
+                "OUNCES" => WeightUnit::OUNCES,
+                other => WeightUnit::Other(other.to_string()),
+            },
+        }),
+        shipping_package_id: None,
+    }
+}
+
+/// Build the `dimension` metafield inputs for a variant's package dimensions.
+fn dimension_metafields(
+    dimensions: &VariantDimensions,
+) -> Vec<super::queries::product_variants_bulk_update::MetafieldInput> {
+    use super::queries::product_variants_bulk_update::MetafieldInput;
+
+    dimensions
+        .to_metafields()
+        .into_iter()
+        .map(|(key, value)| MetafieldInput {
+            id: None,
+            namespace: Some(VariantDimensions::NAMESPACE.to_string()),
+            key: Some(key.to_string()),
+            value: Some(value),
+            type_: Some(VariantDimensions::METAFIELD_TYPE.to_string()),
+        })
+        .collect()
 }
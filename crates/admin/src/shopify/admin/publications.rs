@@ -0,0 +1,176 @@
+//! Sales channel publishing operations for the Admin API.
+//!
+//! `publishablePublish` and `publishableUnpublish` take any `Publishable`
+//! ID. In the Admin API only products and collections are publishable, so
+//! customers and orders have no publish operations.
+
+use naked_pineapple_core::{CollectionId, ProductId};
+use tracing::instrument;
+
+use super::{
+    AdminClient, AdminShopifyError,
+    queries::{GetPublications, PublishablePublish, PublishableUnpublish},
+};
+use crate::shopify::types::Publication;
+
+impl AdminClient {
+    /// Get all publications (sales channels) for the shop.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_publications(&self) -> Result<Vec<Publication>, AdminShopifyError> {
+        let variables = super::queries::get_publications::Variables {};
+        let response = self.execute::<GetPublications>(variables).await?;
+
+        Ok(response
+            .publications
+            .edges
+            .into_iter()
+            .map(|e| {
+                let id = e.node.id;
+                #[allow(deprecated)]
+                let name = e.node.catalog.map(|c| c.title).unwrap_or(e.node.name);
+                Publication { id, name }
+            })
+            .collect())
+    }
+
+    /// Publish a collection to specified publications.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or returns user errors.
+    #[instrument(skip(self))]
+    pub async fn publish_collection(
+        &self,
+        collection_id: &CollectionId,
+        publication_ids: &[String],
+    ) -> Result<(), AdminShopifyError> {
+        self.publish(collection_id.as_str(), publication_ids).await
+    }
+
+    /// Unpublish a collection from specified publications.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or returns user errors.
+    #[instrument(skip(self))]
+    pub async fn unpublish_collection(
+        &self,
+        collection_id: &CollectionId,
+        publication_ids: &[String],
+    ) -> Result<(), AdminShopifyError> {
+        self.unpublish(collection_id.as_str(), publication_ids)
+            .await
+    }
+
+    /// Publish a product to specified publications.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or returns user errors.
+    #[instrument(skip(self))]
+    pub async fn publish_product(
+        &self,
+        product_id: &ProductId,
+        publication_ids: &[String],
+    ) -> Result<(), AdminShopifyError> {
+        self.publish(product_id.as_str(), publication_ids).await
+    }
+
+    /// Unpublish a product from specified publications.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or returns user errors.
+    #[instrument(skip(self))]
+    pub async fn unpublish_product(
+        &self,
+        product_id: &ProductId,
+        publication_ids: &[String],
+    ) -> Result<(), AdminShopifyError> {
+        self.unpublish(product_id.as_str(), publication_ids).await
+    }
+
+    /// Publish a publishable resource to specified publications.
+    async fn publish(&self, id: &str, publication_ids: &[String]) -> Result<(), AdminShopifyError> {
+        if publication_ids.is_empty() {
+            return Ok(());
+        }
+
+        let variables = super::queries::publishable_publish::Variables {
+            id: id.to_string(),
+            input: publication_ids
+                .iter()
+                .map(
+                    |pub_id| super::queries::publishable_publish::PublicationInput {
+                        publication_id: Some(pub_id.clone()),
+                        publish_date: None,
+                    },
+                )
+                .collect(),
+        };
+
+        let response = self.execute::<PublishablePublish>(variables).await?;
+
+        if let Some(payload) = response.publishable_publish
+            && !payload.user_errors.is_empty()
+        {
+            let error_messages: Vec<String> = payload
+                .user_errors
+                .iter()
+                .map(|e| {
+                    let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                    format!("{field}: {}", e.message)
+                })
+                .collect();
+            return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+        }
+
+        Ok(())
+    }
+
+    /// Unpublish a publishable resource from specified publications.
+    async fn unpublish(
+        &self,
+        id: &str,
+        publication_ids: &[String],
+    ) -> Result<(), AdminShopifyError> {
+        if publication_ids.is_empty() {
+            return Ok(());
+        }
+
+        let variables = super::queries::publishable_unpublish::Variables {
+            id: id.to_string(),
+            input: publication_ids
+                .iter()
+                .map(
+                    |pub_id| super::queries::publishable_unpublish::PublicationInput {
+                        publication_id: Some(pub_id.clone()),
+                        publish_date: None,
+                    },
+                )
+                .collect(),
+        };
+
+        let response = self.execute::<PublishableUnpublish>(variables).await?;
+
+        if let Some(payload) = response.publishable_unpublish
+            && !payload.user_errors.is_empty()
+        {
+            let error_messages: Vec<String> = payload
+                .user_errors
+                .iter()
+                .map(|e| {
+                    let field = e.field.as_ref().map_or_else(String::new, |f| f.join("."));
+                    format!("{field}: {}", e.message)
+                })
+                .collect();
+            return Err(AdminShopifyError::UserError(error_messages.join("; ")));
+        }
+
+        Ok(())
+    }
+}
@@ -4,6 +4,9 @@
 
 use graphql_client::GraphQLQuery;
 
+// Shared enums passed to `extern_enums` so queries use one Rust type.
+use crate::shopify::types::CountryCode;
+
 // =============================================================================
 // Custom scalar type aliases (used by graphql_client)
 // =============================================================================
@@ -58,6 +61,101 @@ pub struct GetProduct;
 )]
 pub struct GetProducts;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/products.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetVariantProductId;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/products.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetProductByVariantId;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/products.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetVariant;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/products.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetProductCollections;
+
+/// [`GetProductByVariantId`] with the product deserialized as
+/// [`get_product::GetProductProduct`], so it converts with `convert_product`.
+///
+/// The two queries select the same product fields.
+pub struct ProductByVariantId;
+
+impl GraphQLQuery for ProductByVariantId {
+    type Variables = get_product_by_variant_id::Variables;
+    type ResponseData = ProductByVariantIdData;
+
+    fn build_query(variables: Self::Variables) -> graphql_client::QueryBody<Self::Variables> {
+        GetProductByVariantId::build_query(variables)
+    }
+}
+
+/// Response data for [`ProductByVariantId`].
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductByVariantIdData {
+    /// The variant, if it exists.
+    pub product_variant: Option<ProductByVariantIdVariant>,
+}
+
+/// The variant in [`ProductByVariantIdData`].
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ProductByVariantIdVariant {
+    /// The product that owns the variant.
+    pub product: get_product::GetProductProduct,
+}
+
+/// [`GetVariant`] with the variant deserialized as `GetProduct`'s variant
+/// type, so it converts like the variants of a product.
+///
+/// The two queries select the same variant fields.
+pub struct VariantById;
+
+impl GraphQLQuery for VariantById {
+    type Variables = get_variant::Variables;
+    type ResponseData = VariantByIdData;
+
+    fn build_query(variables: Self::Variables) -> graphql_client::QueryBody<Self::Variables> {
+        GetVariant::build_query(variables)
+    }
+}
+
+/// Response data for [`VariantById`].
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariantByIdData {
+    /// The shop, for its default currency.
+    pub shop: VariantByIdShop,
+    /// The variant, if it exists.
+    pub product_variant: Option<get_product::GetProductProductVariantsEdgesNode>,
+}
+
+/// The shop in [`VariantByIdData`].
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariantByIdShop {
+    /// The shop's default currency (e.g., "USD").
+    pub currency_code: String,
+}
+
 #[derive(GraphQLQuery)]
 #[graphql(
     schema_path = "graphql/admin/schema.json",
@@ -91,6 +189,14 @@ pub struct ProductDelete;
 )]
 pub struct ProductVariantsBulkUpdate;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/products.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct ProductVariantsBulkReorder;
+
 #[derive(GraphQLQuery)]
 #[graphql(
     schema_path = "graphql/admin/schema.json",
@@ -139,6 +245,22 @@ pub struct FileUpdate;
 )]
 pub struct ProductSetMedia;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/products.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct ProductVariantAppendMedia;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/products.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct ProductVariantDetachMedia;
+
 // =============================================================================
 // Collection queries and mutations
 // =============================================================================
@@ -276,6 +398,22 @@ pub struct GetOrderDetail;
 )]
 pub struct GetOrders;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/orders.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetOrdersCount;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/orders.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetOrderUnitsSold;
+
 #[derive(GraphQLQuery)]
 #[graphql(
     schema_path = "graphql/admin/schema.json",
@@ -432,6 +570,43 @@ pub struct OrderEditRemoveShippingLine;
 )]
 pub struct OrderEditCommit;
 
+// =============================================================================
+// Draft Order queries and mutations
+// =============================================================================
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/draft_orders.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetDraftOrders;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/draft_orders.graphql",
+    response_derives = "Debug, Clone",
+    extern_enums("CountryCode")
+)]
+pub struct DraftOrderCreate;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/draft_orders.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct DraftOrderComplete;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/draft_orders.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct DraftOrderInvoiceSend;
+
 // =============================================================================
 // Customer queries
 // =============================================================================
@@ -553,23 +728,8 @@ pub struct CustomerMerge;
 #[graphql(
     schema_path = "graphql/admin/schema.json",
     query_path = "graphql/admin/queries/customers.graphql",
-    response_derives = "Debug, Clone"
-)]
-pub struct MetafieldsSet;
-
-#[derive(GraphQLQuery)]
-#[graphql(
-    schema_path = "graphql/admin/schema.json",
-    query_path = "graphql/admin/queries/customers.graphql",
-    response_derives = "Debug, Clone"
-)]
-pub struct MetafieldsDelete;
-
-#[derive(GraphQLQuery)]
-#[graphql(
-    schema_path = "graphql/admin/schema.json",
-    query_path = "graphql/admin/queries/customers.graphql",
-    response_derives = "Debug, Clone"
+    response_derives = "Debug, Clone",
+    extern_enums("CountryCode")
 )]
 pub struct CustomerAddressCreate;
 
@@ -577,7 +737,8 @@ pub struct CustomerAddressCreate;
 #[graphql(
     schema_path = "graphql/admin/schema.json",
     query_path = "graphql/admin/queries/customers.graphql",
-    response_derives = "Debug, Clone"
+    response_derives = "Debug, Clone",
+    extern_enums("CountryCode")
 )]
 pub struct CustomerAddressUpdate;
 
@@ -655,6 +816,15 @@ pub struct GetInventoryItem;
     query_path = "graphql/admin/queries/inventory.graphql",
     response_derives = "Debug, Clone"
 )]
+pub struct GetProductInventorySummary;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/inventory.graphql",
+    response_derives = "Debug, Clone",
+    extern_enums("CountryCode")
+)]
 pub struct UpdateInventoryItem;
 
 #[derive(GraphQLQuery)]
@@ -794,6 +964,14 @@ pub struct GetDiscountNodes;
 )]
 pub struct GetDiscountCodes;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/discounts.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetDiscountNodeCodes;
+
 // Detail queries
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -817,7 +995,15 @@ pub struct GetDiscountCode;
     query_path = "graphql/admin/queries/discounts.graphql",
     response_derives = "Debug, Clone"
 )]
-pub struct GetCustomerSegments;
+pub struct GetDiscountSummaryNodes;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/discounts.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetDiscountSummaryOrders;
 
 // Code discount mutations
 #[derive(GraphQLQuery)]
@@ -1178,6 +1364,14 @@ pub struct GetSalesChannels;
 )]
 pub struct GetSalesChannelsCount;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/analytics.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetDashboardCounts;
+
 #[derive(GraphQLQuery)]
 #[graphql(
     schema_path = "graphql/admin/schema.json",
@@ -1193,3 +1387,402 @@ pub struct GetMarketingActivities;
     response_derives = "Debug, Clone"
 )]
 pub struct GetMarketingActivity;
+
+// =============================================================================
+// Metaobject queries and mutations
+// =============================================================================
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/metaobjects.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetMetaobjectDefinitions;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/metaobjects.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetMetaobjects;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/metaobjects.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetMetaobject;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/metaobjects.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct MetaobjectCreate;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/metaobjects.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct MetaobjectUpdate;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/metaobjects.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct MetaobjectDelete;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/shipping.graphql",
+    response_derives = "Debug, Clone",
+    extern_enums("CountryCode")
+)]
+pub struct GetShippingCountries;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/shop.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetShopLocales;
+
+// =============================================================================
+// Metafield queries and mutations
+// =============================================================================
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/metafields.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetProductMetafields;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/metafields.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct MetafieldsSet;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/metafields.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct MetafieldsDelete;
+
+// =============================================================================
+// Customer Segment Queries
+// =============================================================================
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/segments.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetCustomerSegments;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/segments.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetCustomerSegment;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/admin/schema.json",
+    query_path = "graphql/admin/queries/segments.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetCustomerSegmentMembers;
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+mod tests {
+    //! Checks every document in `graphql/admin/queries/` against
+    //! `graphql/admin/schema.json`, the schema of
+    //! [`SHOPIFY_API_VERSION`](super::super::SHOPIFY_API_VERSION).
+    //!
+    //! `graphql_client` only validates the operations that are derived above,
+    //! so this also covers operations that aren't (yet) used from Rust.
+
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    use serde_json::Value;
+
+    /// Field type names, by type name and then field name.
+    type Fields = HashMap<String, HashMap<String, String>>;
+
+    struct Schema {
+        fields: Fields,
+        query_root: String,
+        mutation_root: String,
+    }
+
+    fn load_schema(dir: &Path) -> Schema {
+        let content = std::fs::read_to_string(dir.join("schema.json")).unwrap();
+        let schema: Value = serde_json::from_str(&content).unwrap();
+        let schema = &schema["__schema"];
+
+        let fields = schema["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ty| {
+                let fields = ty["fields"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|f| {
+                        (
+                            f["name"].as_str().unwrap().to_string(),
+                            named_type(&f["type"]),
+                        )
+                    })
+                    .collect();
+                (ty["name"].as_str().unwrap().to_string(), fields)
+            })
+            .collect();
+
+        Schema {
+            fields,
+            query_root: schema["queryType"]["name"].as_str().unwrap().to_string(),
+            mutation_root: schema["mutationType"]["name"].as_str().unwrap().to_string(),
+        }
+    }
+
+    /// The named type inside any `NON_NULL` and `LIST` wrappers.
+    fn named_type(mut ty: &Value) -> String {
+        while ty["ofType"].is_object() {
+            ty = &ty["ofType"];
+        }
+        ty["name"].as_str().unwrap().to_string()
+    }
+
+    /// Split a document into names, punctuation, `...` and string literals.
+    fn tokenize(source: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut chars = source.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                c if c.is_whitespace() || c == ',' => {}
+                '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+                '"' => {
+                    let mut literal = String::from('"');
+                    while let Some(c) = chars.next() {
+                        literal.push(c);
+                        if c == '\\' {
+                            literal.extend(chars.next());
+                        } else if c == '"' {
+                            break;
+                        }
+                    }
+                    tokens.push(literal);
+                }
+                '.' => {
+                    while chars.next_if_eq(&'.').is_some() {}
+                    tokens.push("...".to_string());
+                }
+                c if c.is_alphanumeric() || c == '_' || c == '-' => {
+                    let mut name = String::from(c);
+                    while let Some(c) =
+                        chars.next_if(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+                    {
+                        name.push(c);
+                    }
+                    tokens.push(name);
+                }
+                c => tokens.push(c.to_string()),
+            }
+        }
+        tokens
+    }
+
+    /// Walks the selection sets of one document, recording unknown fields.
+    struct Checker<'a> {
+        schema: &'a Schema,
+        file: String,
+        tokens: Vec<String>,
+        pos: usize,
+        errors: Vec<String>,
+    }
+
+    impl Checker<'_> {
+        fn peek(&self) -> &str {
+            self.tokens.get(self.pos).map_or("", String::as_str)
+        }
+
+        fn next(&mut self) -> String {
+            let token = self.peek().to_string();
+            self.pos += 1;
+            token
+        }
+
+        /// Skip a balanced `(...)`, `[...]` or `{...}` group if one starts here.
+        fn skip_group(&mut self, open: &str, close: &str) {
+            if self.peek() != open {
+                return;
+            }
+            let mut depth = 0;
+            loop {
+                let token = self.next();
+                if token == open {
+                    depth += 1;
+                } else if token == close {
+                    depth -= 1;
+                    if depth == 0 {
+                        return;
+                    }
+                } else if token.is_empty() {
+                    panic!("{}: unbalanced {open}", self.file);
+                }
+            }
+        }
+
+        fn skip_directives(&mut self) {
+            while self.peek() == "@" {
+                self.pos += 2;
+                self.skip_group("(", ")");
+            }
+        }
+
+        fn document(&mut self) {
+            while self.pos < self.tokens.len() {
+                match self.next().as_str() {
+                    operation @ ("query" | "mutation") => {
+                        let root = if operation == "query" {
+                            self.schema.query_root.clone()
+                        } else {
+                            self.schema.mutation_root.clone()
+                        };
+                        if self.peek() != "(" && self.peek() != "{" {
+                            self.pos += 1;
+                        }
+                        self.skip_group("(", ")");
+                        self.skip_directives();
+                        self.selection_set(Some(&root));
+                    }
+                    "fragment" => {
+                        self.pos += 2;
+                        let on = self.next();
+                        self.skip_directives();
+                        let known = self.check_type(&on);
+                        self.selection_set(known.then_some(on.as_str()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        /// Whether `name` is a type in the schema, recording it if not.
+        fn check_type(&mut self, name: &str) -> bool {
+            let known = self.schema.fields.contains_key(name);
+            if !known {
+                self.errors
+                    .push(format!("{}: unknown type {name}", self.file));
+            }
+            known
+        }
+
+        /// Check a `{ ... }` selection on `parent`, or just skip it if the
+        /// parent type is unknown.
+        fn selection_set(&mut self, parent: Option<&str>) {
+            assert_eq!(self.next(), "{", "{}: expected a selection set", self.file);
+            while self.peek() != "}" {
+                assert!(!self.peek().is_empty(), "{}: unbalanced {{", self.file);
+
+                if self.peek() == "..." {
+                    self.pos += 1;
+                    if self.peek() == "on" {
+                        self.pos += 1;
+                        let on = self.next();
+                        self.skip_directives();
+                        let known = self.check_type(&on);
+                        self.selection_set(known.then_some(on.as_str()));
+                    } else {
+                        self.pos += 1;
+                        self.skip_directives();
+                    }
+                    continue;
+                }
+
+                // `alias: field` selects `field`
+                if self.tokens.get(self.pos + 1).is_some_and(|t| t == ":") {
+                    self.pos += 2;
+                }
+                let name = self.next();
+                self.skip_group("(", ")");
+                self.skip_directives();
+
+                if name == "__typename" {
+                    continue;
+                }
+                let field_type = parent.and_then(|parent| {
+                    let field_type = self.schema.fields.get(parent)?.get(&name);
+                    if field_type.is_none() {
+                        self.errors
+                            .push(format!("{}: {parent} has no field {name}", self.file));
+                    }
+                    field_type.cloned()
+                });
+
+                if self.peek() == "{" {
+                    let known = field_type.filter(|ty| self.schema.fields.contains_key(ty));
+                    self.selection_set(known.as_deref());
+                }
+            }
+            self.pos += 1;
+        }
+    }
+
+    #[test]
+    fn test_queries_match_schema() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("graphql/admin");
+        let schema = load_schema(&dir);
+
+        let mut paths: Vec<_> = std::fs::read_dir(dir.join("queries"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "graphql"))
+            .collect();
+        paths.sort();
+        assert!(!paths.is_empty(), "no query documents found");
+
+        let mut errors = Vec::new();
+        for path in paths {
+            let mut checker = Checker {
+                schema: &schema,
+                file: path.file_name().unwrap().to_string_lossy().into_owned(),
+                tokens: tokenize(&std::fs::read_to_string(&path).unwrap()),
+                pos: 0,
+                errors: Vec::new(),
+            };
+            checker.document();
+            errors.append(&mut checker.errors);
+        }
+
+        assert!(
+            errors.is_empty(),
+            "queries don't match the schema:\n{}",
+            errors.join("\n")
+        );
+    }
+}
@@ -0,0 +1,605 @@
+//! Retries of Admin API requests.
+//!
+//! Shopify OAuth tokens don't expire, but they can be revoked, and only the
+//! merchant can issue a new one by re-authorizing the app. A
+//! [`RetryableAdminClient`] attaches a [`TokenRefreshCallback`] to its
+//! [`AdminClient`]: when Shopify answers `401`, the client clears its cached
+//! token and asks the callback for a replacement. If the callback has a
+//! different token (e.g. one saved by re-authorizing), the request is retried
+//! once with it; otherwise the callback's error, or `Unauthorized`, is
+//! returned without retrying.
+//!
+//! Every client also retries `429` responses, and `5xx` responses to
+//! queries, with exponential backoff, following its [`RetryPolicy`]s. A
+//! mutation that fails with a server error may still have been applied, so
+//! it is never sent twice.
+
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use serde::Serialize;
+
+use super::{AdminClient, AdminShopifyError, OAuthToken, RetryPolicy};
+
+/// Callback that replaces an OAuth access token Shopify rejected.
+///
+/// Called with the rejected access token. Returns a newer token, or an error
+/// if the merchant has to re-authorize the app first.
+pub type TokenRefreshCallback =
+    Arc<dyn Fn(String) -> BoxFuture<'static, Result<OAuthToken, AdminShopifyError>> + Send + Sync>;
+
+/// An [`AdminClient`] that re-authorizes and retries once on `Unauthorized`.
+///
+/// Derefs to [`AdminClient`], so every Admin API method gets the retry.
+#[derive(Clone)]
+pub struct RetryableAdminClient {
+    client: AdminClient,
+}
+
+impl RetryableAdminClient {
+    /// Attach a token refresh callback to `client`.
+    ///
+    /// Clones of `client` share the callback. If one is already attached it
+    /// is kept.
+    #[must_use]
+    pub fn new(client: AdminClient, refresh: TokenRefreshCallback) -> Self {
+        if client.inner.token_refresh.set(refresh).is_err() {
+            tracing::debug!("Shopify token refresh callback already attached");
+        }
+        Self { client }
+    }
+}
+
+impl Deref for RetryableAdminClient {
+    type Target = AdminClient;
+
+    fn deref(&self) -> &AdminClient {
+        &self.client
+    }
+}
+
+impl AdminClient {
+    /// POST a GraphQL body to `api_version` with the current token.
+    ///
+    /// On a `401`, refreshes the token through the attached callback (if
+    /// any) and retries once. Server errors are not retried if `mutation`.
+    pub(super) async fn send_authorized<B: Serialize + Sync + ?Sized>(
+        &self,
+        api_version: &str,
+        body: &B,
+        mutation: bool,
+    ) -> Result<reqwest::Response, AdminShopifyError> {
+        let access_token = self.get_access_token().await?;
+        let response = self
+            .post_graphql(api_version, &access_token, body, mutation)
+            .await?;
+        if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        let Some(refresh) = self.inner.token_refresh.get() else {
+            return Err(unauthorized());
+        };
+        let access_token = self.refresh_token(&access_token, refresh).await?;
+
+        let response = self
+            .post_graphql(api_version, &access_token, body, mutation)
+            .await?;
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(unauthorized());
+        }
+        Ok(response)
+    }
+
+    /// Replace a rejected token with one from `refresh`.
+    ///
+    /// Concurrent requests rejected with the same token share one refresh:
+    /// whoever waits on the lock reuses the token that's already been fetched.
+    /// Returns `Unauthorized` if `refresh` hands back the rejected token.
+    async fn refresh_token(
+        &self,
+        rejected: &str,
+        refresh: &TokenRefreshCallback,
+    ) -> Result<String, AdminShopifyError> {
+        let _guard = self.inner.refresh_lock.lock().await;
+
+        if let Some(current) = self.get_token().await
+            && current.access_token != rejected
+        {
+            return Ok(current.access_token);
+        }
+
+        tracing::warn!(
+            shop = %self.inner.store,
+            "Shopify rejected the access token, refreshing"
+        );
+        self.clear_token().await;
+
+        let token = refresh(rejected.to_string()).await?;
+        if token.access_token == rejected {
+            return Err(unauthorized());
+        }
+        let access_token = token.access_token.clone();
+        self.set_token(token).await;
+
+        Ok(access_token)
+    }
+
+    /// POST a GraphQL body, retrying rate-limited and server error responses.
+    ///
+    /// Server errors are only retried for queries; a `mutation` gets its
+    /// first server error back. Once a policy's retries run out the failed
+    /// response is returned for the caller to report. Records the number of
+    /// attempts on the current span's `attempts` field, if it has one.
+    async fn post_graphql<B: Serialize + Sync + ?Sized>(
+        &self,
+        api_version: &str,
+        access_token: &str,
+        body: &B,
+        mutation: bool,
+    ) -> Result<reqwest::Response, AdminShopifyError> {
+        let mut attempt = 0_u32;
+        let mut rate_limited = 0;
+        let mut server_errors = 0;
+
+        loop {
+            attempt += 1;
+            tracing::Span::current().record("attempts", attempt);
+            let response = self
+                .post_graphql_once(api_version, access_token, body)
+                .await?;
+            let status = response.status();
+
+            let delay = if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                rate_limited += 1;
+                next_delay(&self.inner.rate_limit_retry, rate_limited)
+                    .map(|delay| delay.max(retry_after(&response).unwrap_or_default()))
+            } else if status.is_server_error() && !mutation {
+                server_errors += 1;
+                next_delay(&self.inner.server_error_retry, server_errors)
+            } else {
+                None
+            };
+            let Some(delay) = delay else {
+                return Ok(response);
+            };
+
+            tracing::warn!(
+                shop = %self.inner.store,
+                %status,
+                attempt,
+                delay_ms = delay.as_millis(),
+                "Shopify request failed, retrying"
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    async fn post_graphql_once<B: Serialize + Sync + ?Sized>(
+        &self,
+        api_version: &str,
+        access_token: &str,
+        body: &B,
+    ) -> Result<reqwest::Response, AdminShopifyError> {
+        let endpoint = format!(
+            "{}/admin/api/{api_version}/graphql.json",
+            self.inner.base_url
+        );
+
+        Ok(self
+            .inner
+            .client
+            .post(&endpoint)
+            .header("X-Shopify-Access-Token", access_token)
+            .header("Content-Type", "application/json")
+            .json(body)
+            .send()
+            .await?)
+    }
+}
+
+/// Whether a GraphQL document contains a mutation operation.
+///
+/// Looks for the `mutation` keyword outside selection sets, strings and
+/// comments, so a query that only mentions a mutation field isn't one.
+pub(super) fn is_mutation(document: &str) -> bool {
+    let mut depth = 0_usize;
+    let mut chars = document.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            '#' => {
+                chars.by_ref().find(|&c| c == '\n');
+            }
+            '"' => {
+                let mut escaped = false;
+                chars.by_ref().find(|&c| {
+                    let end = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    end
+                });
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut word = String::from(c);
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                if depth == 0 && word == "mutation" {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Delay before retry number `retry`, or `None` once `policy` is exhausted.
+fn next_delay(policy: &RetryPolicy, retry: u32) -> Option<Duration> {
+    (retry <= policy.max_retries).then(|| policy.delay(retry))
+}
+
+/// The `Retry-After` header of a response, in (possibly fractional) seconds.
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+}
+
+fn unauthorized() -> AdminShopifyError {
+    AdminShopifyError::Unauthorized("Invalid or expired access token".to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::FutureExt;
+    use secrecy::SecretString;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::config::ShopifyAdminConfig;
+    use crate::shopify::admin::{AdminClientConfig, SHOPIFY_API_VERSION};
+
+    fn token(access_token: &str) -> OAuthToken {
+        OAuthToken {
+            access_token: access_token.to_string(),
+            scope: String::new(),
+            obtained_at: 0,
+            shop: "test.myshopify.com".to_string(),
+        }
+    }
+
+    fn config() -> ShopifyAdminConfig {
+        ShopifyAdminConfig {
+            store: "test.myshopify.com".to_string(),
+            api_version: "2026-01".to_string(),
+            client_id: "test_client_id".to_string(),
+            client_secret: SecretString::from("test_client_secret"),
+        }
+    }
+
+    fn client() -> AdminClient {
+        AdminClient::new(&config()).unwrap()
+    }
+
+    /// A client for `server` that retries without noticeable delays.
+    async fn mock_client(server: &MockServer) -> AdminClient {
+        let policy = RetryPolicy::default()
+            .base_delay(Duration::from_millis(1))
+            .max_delay(Duration::from_millis(5));
+        let http = AdminClientConfig::default()
+            .rate_limit_retry(policy)
+            .server_error_retry(policy);
+        let client = AdminClient::with_base_url(&config(), http, server.uri()).unwrap();
+        client.set_token(token("token")).await;
+        client
+    }
+
+    fn graphql_path() -> String {
+        format!("/admin/api/{SHOPIFY_API_VERSION}/graphql.json")
+    }
+
+    fn shop_response() -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(
+            serde_json::json!({ "data": { "shop": { "id": "gid://shopify/Shop/1" } } }),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_refresh_replaces_rejected_token_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let refresh: TokenRefreshCallback = Arc::new(move |rejected| {
+            assert_eq!(rejected, "old");
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { Ok::<_, AdminShopifyError>(token("new")) }.boxed()
+        });
+
+        let client = client();
+        client.set_token(token("old")).await;
+
+        assert_eq!(client.refresh_token("old", &refresh).await.unwrap(), "new");
+        // A second request rejected with the old token reuses the new one
+        assert_eq!(client.refresh_token("old", &refresh).await.unwrap(), "new");
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(client.get_token().await.unwrap().access_token, "new");
+    }
+
+    #[tokio::test]
+    async fn test_failed_refresh_leaves_no_token() {
+        let refresh: TokenRefreshCallback =
+            Arc::new(|_| async { Err::<OAuthToken, _>(AdminShopifyError::NoAccessToken) }.boxed());
+
+        let client = client();
+        client.set_token(token("old")).await;
+
+        assert!(client.refresh_token("old", &refresh).await.is_err());
+        assert!(!client.has_token().await);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_returning_the_rejected_token_fails() {
+        let refresh: TokenRefreshCallback = Arc::new(|rejected| {
+            async move { Ok::<_, AdminShopifyError>(token(&rejected)) }.boxed()
+        });
+
+        let client = client();
+        client.set_token(token("old")).await;
+
+        assert!(matches!(
+            client.refresh_token("old", &refresh).await,
+            Err(AdminShopifyError::Unauthorized(_))
+        ));
+        assert!(!client.has_token().await);
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_request_is_retried_with_refreshed_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .and(header("X-Shopify-Access-Token", "token"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .and(header("X-Shopify-Access-Token", "new"))
+            .respond_with(shop_response())
+            .mount(&server)
+            .await;
+
+        let client = RetryableAdminClient::new(
+            mock_client(&server).await,
+            Arc::new(|_| async { Ok::<_, AdminShopifyError>(token("new")) }.boxed()),
+        );
+        client.ping().await.unwrap();
+
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        assert_eq!(client.get_token().await.unwrap().access_token, "new");
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_request_is_not_retried_without_new_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let client = RetryableAdminClient::new(
+            mock_client(&server).await,
+            Arc::new(|_| {
+                async {
+                    Err::<OAuthToken, _>(AdminShopifyError::Unauthorized("revoked".to_string()))
+                }
+                .boxed()
+            }),
+        );
+
+        assert!(client.ping().await.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_callback_is_shared_with_clones() {
+        let client = client();
+        let retryable = RetryableAdminClient::new(
+            client.clone(),
+            Arc::new(|_| async { Ok::<_, AdminShopifyError>(token("new")) }.boxed()),
+        );
+
+        assert!(client.inner.token_refresh.get().is_some());
+        assert_eq!(retryable.store(), "test.myshopify.com");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_requests_are_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .respond_with(ResponseTemplate::new(429))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .respond_with(shop_response())
+            .mount(&server)
+            .await;
+
+        mock_client(&server).await.ping().await.unwrap();
+
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_are_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .respond_with(shop_response())
+            .mount(&server)
+            .await;
+
+        mock_client(&server).await.ping().await.unwrap();
+
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mutation_server_errors_are_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .respond_with(shop_response())
+            .mount(&server)
+            .await;
+
+        let body = serde_json::json!({
+            "query": "mutation { tagsAdd(id: \"gid://shopify/Order/1\", tags: [\"vip\"]) \
+                { userErrors { message } } }"
+        });
+        assert!(
+            mock_client(&server)
+                .await
+                .execute_raw_graphql(body)
+                .await
+                .is_err()
+        );
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mutations_are_retried_when_rate_limited() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .respond_with(ResponseTemplate::new(429))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .respond_with(shop_response())
+            .mount(&server)
+            .await;
+
+        let body = serde_json::json!({ "query": "mutation Ping { shop { id } }" });
+        mock_client(&server)
+            .await
+            .execute_raw_graphql(body)
+            .await
+            .unwrap();
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_is_mutation() {
+        assert!(is_mutation("mutation { tagsAdd { node { id } } }"));
+        assert!(is_mutation(
+            "fragment Ids on Order { id }\n\
+             mutation CancelOrder($id: ID!) { orderCancel { job { id } } }"
+        ));
+        assert!(!is_mutation("{ shop { id } }"));
+        assert!(!is_mutation(
+            "query Orders { orders(query: \"mutation\") { nodes { id } } }"
+        ));
+        assert!(!is_mutation("# mutation\nquery { mutationLog { id } }"));
+        assert!(!is_mutation("query { shop { mutation: id } }"));
+    }
+
+    #[tokio::test]
+    async fn test_retries_stop_when_policy_is_exhausted() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&server)
+            .await;
+
+        assert!(mock_client(&server).await.ping().await.is_err());
+        // The first attempt and three retries
+        assert_eq!(server.received_requests().await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_is_a_floor() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0.2"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(graphql_path()))
+            .respond_with(shop_response())
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).await;
+        let started = std::time::Instant::now();
+        client.ping().await.unwrap();
+
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_delay_doubles_up_to_max() {
+        let policy = RetryPolicy::default()
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(300))
+            .jitter(0.0);
+
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(300));
+        assert_eq!(policy.delay(40), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_full_jitter_stays_within_delay() {
+        let policy = RetryPolicy::default().base_delay(Duration::from_millis(100));
+
+        for _ in 0..100 {
+            assert!(policy.delay(1) <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_exhausted_policy_has_no_delay() {
+        assert!(next_delay(&RetryPolicy::none(), 1).is_none());
+        assert!(next_delay(&RetryPolicy::default(), 3).is_some());
+        assert!(next_delay(&RetryPolicy::default(), 4).is_none());
+    }
+}
@@ -0,0 +1,149 @@
+//! Customer segment operations for the Admin API.
+
+use tracing::instrument;
+
+use super::{
+    AdminClient, AdminShopifyError,
+    conversions::currency_code_to_string,
+    queries::{GetCustomerSegment, GetCustomerSegmentMembers, GetCustomerSegments},
+};
+use crate::shopify::types::{
+    CustomerSegment, CustomerSegmentConnection, CustomerSegmentMember,
+    CustomerSegmentMemberConnection, Money, PageInfo,
+};
+
+impl AdminClient {
+    /// Get a page of customer segments.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - Number of segments to return (max 250)
+    /// * `after` - Cursor for pagination
+    /// * `query` - Optional search query (e.g., `name:VIP`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_customer_segments(
+        &self,
+        first: i64,
+        after: Option<String>,
+        query: Option<String>,
+    ) -> Result<CustomerSegmentConnection, AdminShopifyError> {
+        let variables = super::queries::get_customer_segments::Variables {
+            first,
+            after,
+            query,
+        };
+
+        let response = self.execute::<GetCustomerSegments>(variables).await?;
+
+        let segments = response
+            .segments
+            .nodes
+            .into_iter()
+            .map(|s| CustomerSegment {
+                id: s.id,
+                name: s.name,
+                query: s.query,
+                creation_date: s.creation_date,
+                last_edit_date: s.last_edit_date,
+            })
+            .collect();
+
+        let page_info = response.segments.page_info;
+        Ok(CustomerSegmentConnection {
+            segments,
+            page_info: PageInfo {
+                has_next_page: page_info.has_next_page,
+                has_previous_page: page_info.has_previous_page,
+                start_cursor: page_info.start_cursor,
+                end_cursor: page_info.end_cursor,
+            },
+        })
+    }
+
+    /// Get a customer segment by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self), fields(segment_id = %id))]
+    pub async fn get_customer_segment(
+        &self,
+        id: &str,
+    ) -> Result<Option<CustomerSegment>, AdminShopifyError> {
+        let variables = super::queries::get_customer_segment::Variables { id: id.to_string() };
+
+        let response = self.execute::<GetCustomerSegment>(variables).await?;
+
+        Ok(response.segment.map(|s| CustomerSegment {
+            id: s.id,
+            name: s.name,
+            query: s.query,
+            creation_date: s.creation_date,
+            last_edit_date: s.last_edit_date,
+        }))
+    }
+
+    /// Get a page of the customers in a segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `segment_id` - Segment ID (`gid://shopify/Segment/...`)
+    /// * `first` - Number of members to return (max 250)
+    /// * `after` - Cursor for pagination
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self), fields(segment_id = %segment_id))]
+    pub async fn get_customer_segment_members(
+        &self,
+        segment_id: &str,
+        first: i64,
+        after: Option<String>,
+    ) -> Result<CustomerSegmentMemberConnection, AdminShopifyError> {
+        let variables = super::queries::get_customer_segment_members::Variables {
+            segment_id: segment_id.to_string(),
+            first,
+            after,
+        };
+
+        let response = self.execute::<GetCustomerSegmentMembers>(variables).await?;
+        let connection = response.customer_segment_members;
+
+        let members = connection
+            .edges
+            .into_iter()
+            .map(|e| CustomerSegmentMember {
+                id: e.node.id,
+                display_name: e.node.display_name,
+                first_name: e.node.first_name,
+                last_name: e.node.last_name,
+                email: e.node.default_email_address.map(|a| a.email_address),
+                orders_count: e
+                    .node
+                    .number_of_orders
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or(0),
+                amount_spent: e.node.amount_spent.map(|m| Money {
+                    amount: m.amount,
+                    currency_code: currency_code_to_string(m.currency_code),
+                }),
+            })
+            .collect();
+
+        Ok(CustomerSegmentMemberConnection {
+            members,
+            total_count: connection.total_count,
+            page_info: PageInfo {
+                has_next_page: connection.page_info.has_next_page,
+                has_previous_page: connection.page_info.has_previous_page,
+                start_cursor: connection.page_info.start_cursor,
+                end_cursor: connection.page_info.end_cursor,
+            },
+        })
+    }
+}
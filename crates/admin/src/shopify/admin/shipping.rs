@@ -0,0 +1,123 @@
+//! Shipping zone operations for the Admin API.
+
+use std::collections::BTreeMap;
+
+use tracing::instrument;
+
+use super::{AdminClient, AdminShopifyError, queries::GetShippingCountries};
+use crate::shopify::types::{Country, Province};
+
+impl AdminClient {
+    /// Get the countries the store ships to, with their provinces.
+    ///
+    /// Countries come from the zones of every delivery profile, so a country
+    /// in several zones is listed once with the provinces of all of them.
+    /// The "Rest of World" zone has no country of its own and is skipped.
+    /// Sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_countries(&self) -> Result<Vec<Country>, AdminShopifyError> {
+        let variables = super::queries::get_shipping_countries::Variables { first: Some(50) };
+
+        let response = self.execute::<GetShippingCountries>(variables).await?;
+
+        let countries = response
+            .delivery_profiles
+            .nodes
+            .into_iter()
+            .flat_map(|profile| profile.profile_location_groups)
+            .flat_map(|group| group.location_group_zones.nodes)
+            .flat_map(|zone| zone.zone.countries)
+            .filter_map(|country| {
+                let code = country.code.country_code?;
+                Some(Country {
+                    code: code.as_str().to_string(),
+                    name: country.name,
+                    provinces: country
+                        .provinces
+                        .into_iter()
+                        .map(|p| Province {
+                            code: p.code,
+                            name: p.name,
+                        })
+                        .collect(),
+                })
+            });
+
+        Ok(merge_countries(countries))
+    }
+}
+
+/// Combine duplicate countries, keeping every province once, and sort
+/// countries and provinces by name.
+fn merge_countries(countries: impl IntoIterator<Item = Country>) -> Vec<Country> {
+    let mut by_code: BTreeMap<String, Country> = BTreeMap::new();
+    for country in countries {
+        match by_code.get_mut(&country.code) {
+            Some(existing) => {
+                for province in country.provinces {
+                    if !existing.provinces.iter().any(|p| p.code == province.code) {
+                        existing.provinces.push(province);
+                    }
+                }
+            }
+            None => {
+                by_code.insert(country.code.clone(), country);
+            }
+        }
+    }
+
+    let mut merged: Vec<Country> = by_code.into_values().collect();
+    for country in &mut merged {
+        country.provinces.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    merged.sort_by(|a, b| a.name.cmp(&b.name));
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn country(code: &str, name: &str, provinces: &[(&str, &str)]) -> Country {
+        Country {
+            code: code.to_string(),
+            name: name.to_string(),
+            provinces: provinces
+                .iter()
+                .map(|(code, name)| Province {
+                    code: (*code).to_string(),
+                    name: (*name).to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_merge_countries_combines_zones() {
+        let merged = merge_countries([
+            country("US", "United States", &[("WA", "Washington")]),
+            country("CA", "Canada", &[]),
+            country(
+                "US",
+                "United States",
+                &[("CA", "California"), ("WA", "Washington")],
+            ),
+        ]);
+
+        assert_eq!(
+            merged,
+            vec![
+                country("CA", "Canada", &[]),
+                country(
+                    "US",
+                    "United States",
+                    &[("CA", "California"), ("WA", "Washington")]
+                ),
+            ]
+        );
+    }
+}
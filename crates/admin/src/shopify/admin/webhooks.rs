@@ -0,0 +1,209 @@
+//! Webhook subscription operations for the Admin API.
+
+use tracing::instrument;
+
+use super::{AdminClient, AdminShopifyError};
+use crate::shopify::types::{WebhookSubscription, webhook_topic_enum};
+
+/// Maximum subscriptions fetched per page.
+const PAGE_SIZE: i64 = 100;
+
+impl AdminClient {
+    /// List every webhook subscription on the shop.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn list_webhooks(&self) -> Result<Vec<WebhookSubscription>, AdminShopifyError> {
+        let query = r"
+            query WebhookSubscriptions($first: Int!, $after: String) {
+                webhookSubscriptions(first: $first, after: $after) {
+                    nodes {
+                        id
+                        topic
+                        uri
+                        createdAt
+                    }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
+                }
+            }
+        ";
+
+        let mut webhooks = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let body = serde_json::json!({
+                "query": query,
+                "variables": {
+                    "first": PAGE_SIZE,
+                    "after": after
+                }
+            });
+
+            let response = self.execute_raw_graphql(body).await?;
+            let connection = response
+                .get("webhookSubscriptions")
+                .cloned()
+                .unwrap_or_default();
+
+            if let Some(nodes) = connection.get("nodes").and_then(|n| n.as_array()) {
+                webhooks.extend(nodes.iter().map(webhook_from_json));
+            }
+
+            let page_info = connection.get("pageInfo");
+            let has_next_page = page_info
+                .and_then(|p| p.get("hasNextPage"))
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false);
+            after = page_info
+                .and_then(|p| p.get("endCursor"))
+                .and_then(|c| c.as_str())
+                .map(String::from);
+
+            if !has_next_page || after.is_none() {
+                break;
+            }
+        }
+
+        Ok(webhooks)
+    }
+
+    /// Subscribe `uri` to a webhook topic, delivering JSON payloads.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic` - Topic as `orders/create` or `ORDERS_CREATE`
+    /// * `uri` - HTTPS endpoint that receives the webhook
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or Shopify rejects the subscription.
+    #[instrument(skip(self))]
+    pub async fn create_webhook(
+        &self,
+        topic: &str,
+        uri: &str,
+    ) -> Result<WebhookSubscription, AdminShopifyError> {
+        let query = r"
+            mutation WebhookSubscriptionCreate(
+                $topic: WebhookSubscriptionTopic!
+                $webhookSubscription: WebhookSubscriptionInput!
+            ) {
+                webhookSubscriptionCreate(topic: $topic, webhookSubscription: $webhookSubscription) {
+                    webhookSubscription {
+                        id
+                        topic
+                        uri
+                        createdAt
+                    }
+                    userErrors {
+                        field
+                        message
+                    }
+                }
+            }
+        ";
+
+        let body = serde_json::json!({
+            "query": query,
+            "variables": {
+                "topic": webhook_topic_enum(topic),
+                "webhookSubscription": {
+                    "uri": uri,
+                    "format": "JSON"
+                }
+            }
+        });
+
+        let response = self.execute_raw_graphql(body).await?;
+        let payload = response
+            .get("webhookSubscriptionCreate")
+            .cloned()
+            .unwrap_or_default();
+        check_user_errors(&payload)?;
+
+        payload
+            .get("webhookSubscription")
+            .filter(|w| !w.is_null())
+            .map(webhook_from_json)
+            .ok_or_else(|| AdminShopifyError::UserError("No webhook subscription returned".into()))
+    }
+
+    /// Delete a webhook subscription.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Subscription GID
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or Shopify rejects the deletion.
+    #[instrument(skip(self))]
+    pub async fn delete_webhook(&self, id: &str) -> Result<(), AdminShopifyError> {
+        let query = r"
+            mutation WebhookSubscriptionDelete($id: ID!) {
+                webhookSubscriptionDelete(id: $id) {
+                    deletedWebhookSubscriptionId
+                    userErrors {
+                        field
+                        message
+                    }
+                }
+            }
+        ";
+
+        let body = serde_json::json!({
+            "query": query,
+            "variables": { "id": id }
+        });
+
+        let response = self.execute_raw_graphql(body).await?;
+        let payload = response
+            .get("webhookSubscriptionDelete")
+            .cloned()
+            .unwrap_or_default();
+        check_user_errors(&payload)
+    }
+}
+
+/// Convert a `WebhookSubscription` node to the domain type.
+fn webhook_from_json(node: &serde_json::Value) -> WebhookSubscription {
+    let field = |name: &str| {
+        node.get(name)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    WebhookSubscription {
+        id: field("id"),
+        topic: field("topic"),
+        uri: field("uri"),
+        created_at: field("createdAt"),
+    }
+}
+
+/// Return the payload's `userErrors` as an error, if there are any.
+fn check_user_errors(payload: &serde_json::Value) -> Result<(), AdminShopifyError> {
+    let messages: Vec<String> = payload
+        .get("userErrors")
+        .and_then(|e| e.as_array())
+        .map(|errors| {
+            errors
+                .iter()
+                .filter_map(|e| e.get("message").and_then(|m| m.as_str()))
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if messages.is_empty() {
+        Ok(())
+    } else {
+        Err(AdminShopifyError::UserError(messages.join("; ")))
+    }
+}
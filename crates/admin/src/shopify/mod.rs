@@ -23,19 +23,20 @@
 //! ```rust,ignore
 //! use naked_pineapple_admin::shopify::AdminClient;
 //!
-//! let client = AdminClient::new(&config.shopify);
+//! let client = AdminClient::new(&config.shopify)?;
 //!
 //! // Get products
 //! let products = client.get_products(10, None, None).await?;
 //!
 //! // Get a specific order
-//! let order = client.get_order("gid://shopify/Order/123").await?;
+//! let order = client.get_order(&"gid://shopify/Order/123".parse()?).await?;
 //!
 //! // Adjust inventory
 //! client.adjust_inventory(
-//!     "gid://shopify/InventoryItem/123",
-//!     "gid://shopify/Location/456",
+//!     &"gid://shopify/InventoryItem/123".parse()?,
+//!     &"gid://shopify/Location/456".parse()?,
 //!     -1, // decrease by 1
+//!     None,
 //! ).await?;
 //! ```
 
@@ -47,10 +48,15 @@ pub mod admin;
 pub mod types;
 
 pub use admin::{
-    AdminClient, DiscountCreateInput, DiscountUpdateInput, OAuthToken, ProductUpdateInput,
+    AdminClient, AdminClientConfig, CursorPaginator, CustomerEligibility, DiscountCreateInput,
+    DiscountItems, DiscountUpdateInput, DiscountUpdateInputBuilder, DocumentCheck, OAuthToken,
+    Page, ProductUpdateInput, ProductUpdateInputBuilder, RetryPolicy, RetryableAdminClient,
+    SHOPIFY_API_VERSION, ThrottleStatus, TokenRefreshCallback, VariantUpdateInput,
+    validate_api_version, verify_webhook_signature,
 };
 pub use types::*;
 
+use naked_pineapple_core::{BarcodeError, GidError};
 use thiserror::Error;
 
 /// Errors that can occur when interacting with Shopify Admin API.
@@ -91,6 +97,10 @@ pub enum AdminShopifyError {
     /// No valid access token available (OAuth flow required).
     #[error("No access token - OAuth authorization required")]
     NoAccessToken,
+
+    /// API version is not in `YYYY-MM` format.
+    #[error("Invalid API version {0:?}, expected YYYY-MM")]
+    InvalidApiVersion(String),
 }
 
 /// A GraphQL error returned by the Shopify Admin API.
@@ -113,6 +123,18 @@ pub struct GraphQLErrorLocation {
     pub column: i64,
 }
 
+impl From<BarcodeError> for AdminShopifyError {
+    fn from(e: BarcodeError) -> Self {
+        Self::UserError(format!("Invalid barcode: {e}"))
+    }
+}
+
+impl From<GidError> for AdminShopifyError {
+    fn from(e: GidError) -> Self {
+        Self::UserError(e.to_string())
+    }
+}
+
 fn format_graphql_errors(errors: &[GraphQLError]) -> String {
     errors
         .iter()
@@ -169,4 +191,80 @@ mod tests {
         let err = AdminShopifyError::UserError("Invalid quantity".to_string());
         assert_eq!(err.to_string(), "User error: Invalid quantity");
     }
+
+    #[test]
+    fn test_validate_api_version() {
+        assert!(validate_api_version("2026-01").is_ok());
+        assert!(validate_api_version("2025-12").is_ok());
+
+        for invalid in [
+            "", "2026-1", "2026-13", "2026-00", "26-01", "2026/01", "unstable",
+        ] {
+            assert!(
+                matches!(
+                    validate_api_version(invalid),
+                    Err(AdminShopifyError::InvalidApiVersion(_))
+                ),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_webhook_topic_enum() {
+        assert_eq!(webhook_topic_enum("orders/create"), "ORDERS_CREATE");
+        assert_eq!(
+            webhook_topic_enum("inventory_levels/update"),
+            "INVENTORY_LEVELS_UPDATE"
+        );
+        assert_eq!(webhook_topic_enum("APP_UNINSTALLED"), "APP_UNINSTALLED");
+    }
+
+    #[test]
+    fn test_variant_dimensions_metafield_round_trip() {
+        let dimensions = VariantDimensions {
+            length: 30.0,
+            width: 20.5,
+            height: 10.0,
+            unit: "cm".to_string(),
+        };
+        let metafields = dimensions.to_metafields();
+
+        assert_eq!(
+            VariantDimensions::from_metafields(
+                metafields.iter().map(|(key, value)| (*key, value.as_str()))
+            ),
+            Some(dimensions)
+        );
+    }
+
+    #[test]
+    fn test_variant_dimensions_require_all_three() {
+        let length = r#"{"value": 1.0, "unit": "in"}"#;
+        let width = r#"{"value": 2.0, "unit": "in"}"#;
+        let height_cm = r#"{"value": 3.0, "unit": "cm"}"#;
+
+        assert!(
+            VariantDimensions::from_metafields([("length", length), ("width", width)]).is_none()
+        );
+        assert!(
+            VariantDimensions::from_metafields([
+                ("length", length),
+                ("width", width),
+                ("height", height_cm)
+            ])
+            .is_none()
+        );
+        assert!(VariantDimensions::from_metafields([("length", "not json")]).is_none());
+    }
+
+    #[test]
+    fn test_barcode_error_is_user_error() {
+        let err =
+            naked_pineapple_core::Barcode::parse("036000291453").map_err(AdminShopifyError::from);
+        assert!(matches!(
+            err,
+            Err(AdminShopifyError::UserError(msg)) if msg.contains("UPC-A check digit")
+        ));
+    }
 }
@@ -152,6 +152,17 @@ pub struct DailyMetrics {
     pub channel_name: Option<String>,
 }
 
+/// Sales for a single product over a period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductRevenue {
+    /// Product title.
+    pub product_title: String,
+    /// Total sales for this product.
+    pub total_sales: f64,
+    /// Units sold.
+    pub units_sold: i64,
+}
+
 // =============================================================================
 // Date Range Types
 // =============================================================================
@@ -90,6 +90,39 @@ pub struct AddressInput {
     pub phone: Option<String>,
 }
 
+/// Country an address can be in, with its provinces.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Country {
+    /// Country code (ISO 3166-1 alpha-2).
+    pub code: String,
+    /// Country name.
+    pub name: String,
+    /// Provinces or states (empty if the country has none).
+    pub provinces: Vec<Province>,
+}
+
+/// Province or state of a [`Country`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Province {
+    /// Province code (e.g., `CA` for California).
+    pub code: String,
+    /// Province name.
+    pub name: String,
+}
+
+/// A language enabled on the store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShopLocale {
+    /// BCP 47 language code (e.g., `en`, `fr-CA`).
+    pub locale: String,
+    /// Language name in English.
+    pub name: String,
+    /// Whether this is the store's default language.
+    pub primary: bool,
+    /// Whether buyers can see the storefront in this language.
+    pub published: bool,
+}
+
 // =============================================================================
 // Metafield Types
 // =============================================================================
@@ -105,6 +138,10 @@ pub struct Metafield {
     pub key: String,
     /// The metafield value.
     pub value: String,
+    /// Metafield type, e.g. `single_line_text_field` or `json`.
+    pub type_name: String,
+    /// When the metafield was last updated (`None` for unsaved metafields).
+    pub updated_at: Option<String>,
 }
 
 /// Input for creating/updating metafields.
@@ -137,6 +174,70 @@ pub struct CustomerUpdateParams {
     pub tags: Option<Vec<String>>,
 }
 
+impl CustomerUpdateParams {
+    /// Start building update parameters with no fields set.
+    #[must_use]
+    pub fn builder() -> CustomerUpdateParamsBuilder {
+        CustomerUpdateParamsBuilder::default()
+    }
+}
+
+/// Builder for [`CustomerUpdateParams`].
+#[derive(Debug, Clone, Default)]
+pub struct CustomerUpdateParamsBuilder {
+    params: CustomerUpdateParams,
+}
+
+impl CustomerUpdateParamsBuilder {
+    /// Set the email address (`None` leaves it unchanged).
+    #[must_use]
+    pub fn email(mut self, email: impl Into<Option<String>>) -> Self {
+        self.params.email = email.into();
+        self
+    }
+
+    /// Set the first name (`None` leaves it unchanged).
+    #[must_use]
+    pub fn first_name(mut self, first_name: impl Into<Option<String>>) -> Self {
+        self.params.first_name = first_name.into();
+        self
+    }
+
+    /// Set the last name (`None` leaves it unchanged).
+    #[must_use]
+    pub fn last_name(mut self, last_name: impl Into<Option<String>>) -> Self {
+        self.params.last_name = last_name.into();
+        self
+    }
+
+    /// Set the phone number (`None` leaves it unchanged).
+    #[must_use]
+    pub fn phone(mut self, phone: impl Into<Option<String>>) -> Self {
+        self.params.phone = phone.into();
+        self
+    }
+
+    /// Set the note (`None` leaves it unchanged).
+    #[must_use]
+    pub fn note(mut self, note: impl Into<Option<String>>) -> Self {
+        self.params.note = note.into();
+        self
+    }
+
+    /// Set the tags, replacing existing ones (`None` leaves them unchanged).
+    #[must_use]
+    pub fn tags(mut self, tags: impl Into<Option<Vec<String>>>) -> Self {
+        self.params.tags = tags.into();
+        self
+    }
+
+    /// Finish building the parameters.
+    #[must_use]
+    pub fn build(self) -> CustomerUpdateParams {
+        self.params
+    }
+}
+
 /// Override settings for customer merge operation.
 ///
 /// Each field indicates whether to take the value from the source customer
@@ -0,0 +1,34 @@
+//! Shopify country codes.
+//!
+//! The enum is generated by `build.rs` from Shopify's full `CountryCode`
+//! GraphQL enum and shared with `graphql_client` via `extern_enums`, so every
+//! query that takes a country accepts the same type.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+include!(concat!(env!("OUT_DIR"), "/country_code.rs"));
+
+impl CountryCode {
+    /// Look up a country by its ISO 3166-1 alpha-2 code (case-insensitive).
+    ///
+    /// Returns `None` if Shopify doesn't recognize the code.
+    #[must_use]
+    pub fn from_iso_2(code: &str) -> Option<Self> {
+        ISO_2
+            .get(code.trim().to_ascii_uppercase().as_str())
+            .cloned()
+    }
+}
+
+impl Serialize for CountryCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for CountryCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Ok(Self::from_iso_2(&code).unwrap_or(Self::Other(code)))
+    }
+}
@@ -1,5 +1,6 @@
 //! Customer and collection domain types for Shopify Admin API.
 
+use naked_pineapple_core::{CollectionId, CustomerId, OrderId, ProductId};
 use serde::{Deserialize, Serialize};
 
 use super::common::{Address, Image, Money, PageInfo};
@@ -146,7 +147,7 @@ pub struct MarketingConsent {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CustomerOrder {
     /// Order ID.
-    pub id: String,
+    pub id: OrderId,
     /// Order name (e.g., "#1001").
     pub name: String,
     /// Creation timestamp.
@@ -166,7 +167,7 @@ pub struct CustomerOrder {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Customer {
     /// Customer ID.
-    pub id: String,
+    pub id: CustomerId,
     /// Email address.
     pub email: Option<String>,
     /// First name.
@@ -228,6 +229,64 @@ pub struct CustomerConnection {
     pub page_info: PageInfo,
 }
 
+// =============================================================================
+// Customer Segment Types
+// =============================================================================
+
+/// A customer segment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerSegment {
+    /// Segment ID.
+    pub id: String,
+    /// Segment name.
+    pub name: String,
+    /// Segment query (`ShopifyQL`), e.g. `number_of_orders > 5`.
+    pub query: String,
+    /// Creation timestamp.
+    pub creation_date: String,
+    /// Last time the segment query was edited.
+    pub last_edit_date: String,
+}
+
+/// Paginated list of customer segments.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerSegmentConnection {
+    /// Segments in this page.
+    pub segments: Vec<CustomerSegment>,
+    /// Pagination info.
+    pub page_info: PageInfo,
+}
+
+/// A customer who belongs to a segment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerSegmentMember {
+    /// Customer ID.
+    pub id: String,
+    /// Display name.
+    pub display_name: String,
+    /// First name.
+    pub first_name: Option<String>,
+    /// Last name.
+    pub last_name: Option<String>,
+    /// Email address.
+    pub email: Option<String>,
+    /// Number of orders.
+    pub orders_count: i64,
+    /// Total amount spent.
+    pub amount_spent: Option<Money>,
+}
+
+/// Paginated list of a segment's members.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerSegmentMemberConnection {
+    /// Members in this page.
+    pub members: Vec<CustomerSegmentMember>,
+    /// Total number of customers in the segment.
+    pub total_count: i64,
+    /// Pagination info.
+    pub page_info: PageInfo,
+}
+
 // =============================================================================
 // Collection Types
 // =============================================================================
@@ -284,7 +343,7 @@ pub struct ResourcePublication {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Collection {
     /// Collection ID.
-    pub id: String,
+    pub id: CollectionId,
     /// Collection title.
     pub title: String,
     /// URL handle.
@@ -313,7 +372,7 @@ pub struct Collection {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollectionProduct {
     /// Product ID.
-    pub id: String,
+    pub id: ProductId,
     /// Product title.
     pub title: String,
     /// URL handle.
@@ -328,6 +387,8 @@ pub struct CollectionProduct {
     pub price: String,
     /// Currency code.
     pub currency_code: String,
+    /// Creation timestamp.
+    pub created_at: Option<String>,
 }
 
 /// A collection with its products.
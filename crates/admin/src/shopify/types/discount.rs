@@ -1,8 +1,10 @@
 //! Discount domain types for Shopify Admin API.
 
+use naked_pineapple_core::{CollectionId, CustomerId, LocationId, ProductId};
 use serde::{Deserialize, Serialize};
 
 use super::common::{Money, PageInfo};
+use super::customer::CustomerSegment;
 
 // =============================================================================
 // Discount Types
@@ -185,20 +187,11 @@ pub enum DiscountCustomerEligibility {
     Customers { customers: Vec<CustomerRef> },
 }
 
-/// Reference to a customer segment.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CustomerSegment {
-    /// Segment ID.
-    pub id: String,
-    /// Segment name.
-    pub name: String,
-}
-
 /// Reference to a customer.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CustomerRef {
     /// Customer ID.
-    pub id: String,
+    pub id: CustomerId,
     /// Display name.
     pub display_name: String,
     /// Email.
@@ -220,7 +213,7 @@ pub enum DiscountItems {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProductRef {
     /// Product ID.
-    pub id: String,
+    pub id: ProductId,
     /// Product title.
     pub title: String,
     /// Featured image URL.
@@ -231,11 +224,16 @@ pub struct ProductRef {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollectionRef {
     /// Collection ID.
-    pub id: String,
+    pub id: CollectionId,
     /// Collection title.
     pub title: String,
+    /// URL handle.
+    pub handle: String,
     /// Collection image URL.
     pub image_url: Option<String>,
+    /// Whether products are chosen by rules (smart collection) rather than
+    /// added by hand.
+    pub is_smart: bool,
 }
 
 /// Buy X Get Y requirement type.
@@ -306,6 +304,8 @@ pub struct DiscountCode {
     pub title: String,
     /// The actual code customers enter.
     pub code: String,
+    /// Discount type (Basic, `BuyXGetY`, `FreeShipping`).
+    pub discount_type: DiscountType,
     /// Discount status.
     pub status: DiscountStatus,
     /// Start date.
@@ -424,6 +424,32 @@ pub struct DiscountListConnection {
     pub page_info: PageInfo,
 }
 
+// =============================================================================
+// Discount Summary Types
+// =============================================================================
+
+/// Discount performance over a period, for the dashboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscountSummary {
+    /// Code discounts that are currently active.
+    pub total_active_codes: i64,
+    /// Discount codes applied to orders placed in the period.
+    pub total_redemptions_in_period: i64,
+    /// Total discounts on orders that used a code in the period.
+    pub total_discount_value_in_period: Money,
+    /// Most-used codes in the period, most redemptions first.
+    pub top_codes: Vec<DiscountCodeUsage>,
+}
+
+/// Redemptions of a single discount code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscountCodeUsage {
+    /// The code, uppercased (codes are case-insensitive).
+    pub code: String,
+    /// Orders in the period that used the code.
+    pub usage_count: i64,
+}
+
 // =============================================================================
 // Fulfillment Order Types
 // =============================================================================
@@ -436,7 +462,7 @@ pub struct FulfillmentOrder {
     /// Fulfillment order status.
     pub status: String,
     /// Location ID where items will be fulfilled from.
-    pub location_id: Option<String>,
+    pub location_id: Option<LocationId>,
     /// Location name.
     pub location_name: Option<String>,
     /// Line items in this fulfillment order.
@@ -0,0 +1,141 @@
+//! Draft order types for Shopify Admin API.
+//!
+//! Draft orders are quotes built in the admin. The customer pays through the
+//! emailed invoice, or the draft is completed directly into an order.
+
+use naked_pineapple_core::{CustomerId, OrderId, VariantId};
+use serde::{Deserialize, Serialize};
+
+use super::common::{Money, PageInfo};
+
+// =============================================================================
+// Draft Order Types
+// =============================================================================
+
+/// Draft order status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DraftOrderStatus {
+    /// Being edited; no invoice sent yet.
+    Open,
+    /// Invoice emailed to the customer.
+    InvoiceSent,
+    /// Converted into an order.
+    Completed,
+}
+
+/// A draft order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DraftOrder {
+    /// Draft order ID.
+    pub id: String,
+    /// Draft order name (e.g., "#D12").
+    pub name: String,
+    /// Status.
+    pub status: DraftOrderStatus,
+    /// Email the invoice is sent to.
+    pub email: Option<String>,
+    /// Note for the merchant.
+    pub note: Option<String>,
+    /// Tags.
+    pub tags: Vec<String>,
+    /// Customer ID.
+    pub customer_id: Option<CustomerId>,
+    /// Customer display name.
+    pub customer_name: Option<String>,
+    /// ID of the order created on completion.
+    pub order_id: Option<OrderId>,
+    /// Checkout link sent with the invoice.
+    pub invoice_url: Option<String>,
+    /// When the invoice was last sent.
+    pub invoice_sent_at: Option<String>,
+    /// Subtotal price.
+    pub subtotal_price: Money,
+    /// Total price.
+    pub total_price: Money,
+    /// Line items.
+    pub line_items: Vec<DraftOrderLineItem>,
+    /// Created timestamp.
+    pub created_at: String,
+    /// Updated timestamp.
+    pub updated_at: String,
+    /// When the draft was completed.
+    pub completed_at: Option<String>,
+}
+
+/// A line item in a draft order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DraftOrderLineItem {
+    /// Line item ID.
+    pub id: String,
+    /// Product title, or the title of a custom item.
+    pub title: String,
+    /// Variant title.
+    pub variant_title: Option<String>,
+    /// SKU.
+    pub sku: Option<String>,
+    /// Quantity.
+    pub quantity: i64,
+    /// Variant ID (`None` for custom items).
+    pub variant_id: Option<VariantId>,
+    /// Unit price before discounts.
+    pub original_unit_price: Money,
+    /// Line total after discounts.
+    pub discounted_total: Money,
+}
+
+/// Paginated list of draft orders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DraftOrderConnection {
+    /// Draft orders in this page.
+    pub draft_orders: Vec<DraftOrder>,
+    /// Pagination info.
+    pub page_info: PageInfo,
+}
+
+// =============================================================================
+// Draft Order Input Types
+// =============================================================================
+
+/// A line item for a new draft order.
+///
+/// Either a variant, or a custom item with a title and unit price.
+#[derive(Debug, Clone)]
+pub enum DraftOrderLineItemInput {
+    /// A product variant at its current price.
+    Variant {
+        /// Variant ID.
+        variant_id: VariantId,
+        /// Quantity.
+        quantity: i64,
+    },
+    /// An item that isn't in the catalog.
+    Custom {
+        /// Item title.
+        title: String,
+        /// Unit price.
+        unit_price: Money,
+        /// Quantity.
+        quantity: i64,
+    },
+}
+
+/// How a draft order discount's value is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftOrderDiscountType {
+    /// `value` is a percentage (0-100).
+    Percentage,
+    /// `value` is an amount in the shop's currency.
+    FixedAmount,
+}
+
+/// A discount applied to a whole draft order.
+#[derive(Debug, Clone)]
+pub struct DraftOrderDiscount {
+    /// Title shown to the customer.
+    pub title: Option<String>,
+    /// Percentage or amount, depending on `value_type`.
+    pub value: f64,
+    /// How `value` is applied.
+    pub value_type: DraftOrderDiscountType,
+}
@@ -0,0 +1,89 @@
+//! Shopify Flow trigger types.
+
+use std::collections::HashMap;
+
+use serde_json::json;
+
+/// A well-known event sent to Shopify Flow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlowTrigger {
+    /// An order was held for review as a high risk of fraud (order GID).
+    OrderFlaggedForReview(String),
+    /// A SKU's available quantity dropped to the low stock threshold.
+    LowInventoryAlert {
+        /// SKU of the variant.
+        sku: String,
+        /// Available quantity after the change.
+        quantity: i64,
+    },
+    /// A customer was marked as high value (customer GID).
+    HighValueCustomer(String),
+}
+
+impl FlowTrigger {
+    /// ID of the resource the trigger is about: the order or customer GID,
+    /// or the SKU for inventory alerts.
+    #[must_use]
+    pub fn resource_id(&self) -> &str {
+        match self {
+            Self::OrderFlaggedForReview(id) | Self::HighValueCustomer(id) => id,
+            Self::LowInventoryAlert { sku, .. } => sku,
+        }
+    }
+
+    /// Custom properties sent with the trigger.
+    #[must_use]
+    pub fn properties(&self) -> HashMap<String, serde_json::Value> {
+        match self {
+            Self::OrderFlaggedForReview(_) => {
+                HashMap::from([("reason".to_string(), json!("HIGH_RISK_OF_FRAUD"))])
+            }
+            Self::LowInventoryAlert { sku, quantity } => HashMap::from([
+                ("sku".to_string(), json!(sku)),
+                ("quantity".to_string(), json!(quantity)),
+            ]),
+            Self::HighValueCustomer(_) => HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_flagged_for_review() {
+        let trigger = FlowTrigger::OrderFlaggedForReview("gid://shopify/Order/1".to_string());
+
+        assert_eq!(trigger.resource_id(), "gid://shopify/Order/1");
+        assert_eq!(
+            trigger.properties(),
+            HashMap::from([("reason".to_string(), json!("HIGH_RISK_OF_FRAUD"))])
+        );
+    }
+
+    #[test]
+    fn test_low_inventory_alert() {
+        let trigger = FlowTrigger::LowInventoryAlert {
+            sku: "PINEAPPLE-1".to_string(),
+            quantity: 3,
+        };
+
+        assert_eq!(trigger.resource_id(), "PINEAPPLE-1");
+        assert_eq!(
+            trigger.properties(),
+            HashMap::from([
+                ("sku".to_string(), json!("PINEAPPLE-1")),
+                ("quantity".to_string(), json!(3)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_high_value_customer() {
+        let trigger = FlowTrigger::HighValueCustomer("gid://shopify/Customer/2".to_string());
+
+        assert_eq!(trigger.resource_id(), "gid://shopify/Customer/2");
+        assert!(trigger.properties().is_empty());
+    }
+}
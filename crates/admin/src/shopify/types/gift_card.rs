@@ -1,5 +1,6 @@
 //! Gift card domain types for Shopify Admin API.
 
+use naked_pineapple_core::{CustomerId, OrderId};
 use serde::{Deserialize, Serialize};
 
 use super::common::{Money, PageInfo};
@@ -76,7 +77,7 @@ pub struct GiftCard {
     /// Last update timestamp.
     pub updated_at: Option<String>,
     /// Associated customer ID.
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
     /// Associated customer email.
     pub customer_email: Option<String>,
     /// Associated customer name.
@@ -84,7 +85,7 @@ pub struct GiftCard {
     /// Internal note.
     pub note: Option<String>,
     /// Associated order ID.
-    pub order_id: Option<String>,
+    pub order_id: Option<OrderId>,
     /// Associated order name.
     pub order_name: Option<String>,
 }
@@ -119,7 +120,7 @@ pub struct GiftCardTransaction {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GiftCardRecipient {
     /// Recipient customer ID.
-    pub recipient_id: Option<String>,
+    pub recipient_id: Option<CustomerId>,
     /// Recipient display name.
     pub recipient_name: Option<String>,
     /// Recipient email.
@@ -160,7 +161,7 @@ pub struct GiftCardDetail {
     /// Template suffix for rendering.
     pub template_suffix: Option<String>,
     /// Associated customer ID.
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
     /// Associated customer name.
     pub customer_name: Option<String>,
     /// Associated customer email.
@@ -170,7 +171,7 @@ pub struct GiftCardDetail {
     /// Recipient details.
     pub recipient: Option<GiftCardRecipient>,
     /// Associated order ID.
-    pub order_id: Option<String>,
+    pub order_id: Option<OrderId>,
     /// Associated order name.
     pub order_name: Option<String>,
     /// Associated order creation date.
@@ -1,5 +1,6 @@
 //! Location and inventory domain types for Shopify Admin API.
 
+use naked_pineapple_core::{InventoryItemId, LocationId, ProductId, VariantId};
 use serde::{Deserialize, Serialize};
 
 use super::common::{Image, Money, PageInfo};
@@ -13,7 +14,7 @@ use super::product::ProductStatus;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Location {
     /// Location ID.
-    pub id: String,
+    pub id: LocationId,
     /// Location name.
     pub name: String,
     /// Whether the location is active.
@@ -56,21 +57,104 @@ pub struct LocationConnection {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InventoryLevel {
     /// Inventory item ID.
-    pub inventory_item_id: String,
+    pub inventory_item_id: InventoryItemId,
     /// Location ID.
-    pub location_id: String,
+    pub location_id: LocationId,
     /// Location name.
     pub location_name: Option<String>,
     /// Quantity available.
     pub available: i64,
     /// Quantity on hand.
     pub on_hand: i64,
+    /// Quantity committed to unfulfilled orders.
+    pub committed: i64,
     /// Quantity incoming.
     pub incoming: i64,
+    /// Quantity reserved (e.g. held for draft orders).
+    pub reserved: i64,
     /// Last update timestamp.
     pub updated_at: Option<String>,
 }
 
+/// Inventory of one variant at one location.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationInventory {
+    /// Location ID.
+    pub location_id: LocationId,
+    /// Location name.
+    pub location_name: String,
+    /// Quantity available to sell.
+    pub available: i64,
+    /// Quantity physically at the location.
+    pub on_hand: i64,
+    /// Quantity committed to unfulfilled orders.
+    pub committed: i64,
+    /// Quantity on its way to the location.
+    pub incoming: i64,
+}
+
+/// Inventory of one variant across all locations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantInventory {
+    /// Variant ID.
+    pub variant_id: VariantId,
+    /// Inventory item ID (for inventory operations).
+    pub inventory_item_id: InventoryItemId,
+    /// SKU code.
+    pub sku: Option<String>,
+    /// Variant title.
+    pub title: String,
+    /// Sale price.
+    pub price: String,
+    /// Unit cost (COGS), if set.
+    pub unit_cost: Option<Money>,
+    /// Quantity available across all locations.
+    pub total_available: i64,
+    /// Inventory at each stocking location.
+    pub locations: Vec<LocationInventory>,
+}
+
+/// Cross-location inventory snapshot for a product.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductInventorySummary {
+    /// Product ID.
+    pub product_id: ProductId,
+    /// Inventory for each variant.
+    pub variants: Vec<VariantInventory>,
+}
+
+/// Cost of goods sold and margin for one variant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantCogs {
+    /// Variant ID.
+    pub variant_id: VariantId,
+    /// Inventory item ID (for cost updates).
+    pub inventory_item_id: InventoryItemId,
+    /// Variant title.
+    pub title: String,
+    /// Sale price.
+    pub price: String,
+    /// Unit cost, if set.
+    pub unit_cost: Option<Money>,
+    /// Gross margin as a percentage of the sale price, if the cost is set.
+    pub margin_percent: Option<f64>,
+}
+
+/// Cost of goods sold summary for a product.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CogsSummary {
+    /// Product ID.
+    pub product_id: ProductId,
+    /// Available quantity times unit cost, summed over every location.
+    pub total_value_on_hand: Money,
+    /// Mean margin of the variants with a cost set.
+    pub average_margin_percent: f64,
+    /// Title of the variant with the lowest margin.
+    pub lowest_margin_variant: Option<String>,
+    /// Cost and margin for each variant.
+    pub variants: Vec<VariantCogs>,
+}
+
 /// Result of an inventory adjustment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InventoryAdjustmentResult {
@@ -82,7 +166,7 @@ pub struct InventoryAdjustmentResult {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InventoryItem {
     /// Inventory item ID.
-    pub id: String,
+    pub id: InventoryItemId,
     /// SKU code.
     pub sku: Option<String>,
     /// Whether inventory is tracked.
@@ -107,7 +191,7 @@ pub struct InventoryItem {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InventoryItemVariant {
     /// Variant ID.
-    pub id: String,
+    pub id: VariantId,
     /// Variant title.
     pub title: String,
     /// Display name.
@@ -124,7 +208,7 @@ pub struct InventoryItemVariant {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InventoryItemProduct {
     /// Product ID.
-    pub id: String,
+    pub id: ProductId,
     /// Product title.
     pub title: String,
     /// URL handle.
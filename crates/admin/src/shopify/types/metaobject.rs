@@ -0,0 +1,105 @@
+//! Metaobject domain types for Shopify Admin API.
+//!
+//! Metaobjects are custom content types defined in the Shopify admin
+//! (testimonials, FAQs, team members, ...). A [`MetaobjectDefinition`]
+//! describes a type's fields; each [`Metaobject`] is one entry of that type.
+
+use serde::{Deserialize, Serialize};
+
+use super::common::PageInfo;
+
+// =============================================================================
+// Definition Types
+// =============================================================================
+
+/// A metaobject definition (custom content type).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaobjectDefinition {
+    /// Definition GID.
+    pub id: String,
+    /// Human-readable name, e.g. "Testimonial".
+    pub name: String,
+    /// Type handle used to query entries, e.g. `testimonial`.
+    pub type_name: String,
+    /// Description shown in the Shopify admin.
+    pub description: Option<String>,
+    /// Fields each entry of this type has.
+    pub field_definitions: Vec<FieldDefinition>,
+}
+
+/// A field on a metaobject definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldDefinition {
+    /// Field key, e.g. `quote`.
+    pub key: String,
+    /// Human-readable field name.
+    pub name: String,
+    /// Description shown in the Shopify admin.
+    pub description: Option<String>,
+    /// Whether every entry must set this field.
+    pub required: bool,
+    /// Metafield type, e.g. `single_line_text_field` or `product_reference`.
+    pub type_name: String,
+}
+
+// =============================================================================
+// Entry Types
+// =============================================================================
+
+/// A metaobject entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Metaobject {
+    /// Metaobject GID.
+    pub id: String,
+    /// Unique handle within the type.
+    pub handle: String,
+    /// Type handle of the definition.
+    pub type_name: String,
+    /// Display name (from the definition's display name field).
+    pub display_name: String,
+    /// When the entry was last updated.
+    pub updated_at: String,
+    /// Field values.
+    pub fields: Vec<MetaobjectField>,
+}
+
+impl Metaobject {
+    /// Get the value of a field by key.
+    #[must_use]
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|f| f.key == key)
+            .and_then(|f| f.value.as_deref())
+    }
+}
+
+/// A field value on a metaobject.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaobjectField {
+    /// Field key.
+    pub key: String,
+    /// Metafield type.
+    pub type_name: String,
+    /// Serialized value (`None` if unset). References are GIDs and lists are
+    /// JSON arrays.
+    pub value: Option<String>,
+}
+
+/// Paginated list of metaobjects.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaobjectConnection {
+    /// Metaobjects in this page.
+    pub metaobjects: Vec<Metaobject>,
+    /// Pagination info.
+    pub page_info: PageInfo,
+}
+
+/// A field value to set when creating or updating a metaobject.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaobjectFieldInput {
+    /// Field key.
+    pub key: String,
+    /// Serialized value, in the format of the field's type.
+    pub value: String,
+}
@@ -5,25 +5,35 @@
 
 pub mod analytics;
 pub mod common;
+pub mod country_code;
 pub mod customer;
 pub mod discount;
+pub mod draft_order;
+pub mod flow;
 pub mod gift_card;
 pub mod inventory;
+pub mod metaobject;
 pub mod order;
 pub mod order_edit;
 pub mod payments;
 pub mod product;
 pub mod refund;
+pub mod webhook;
 
 // Re-export all types for convenience
 pub use analytics::*;
 pub use common::*;
+pub use country_code::CountryCode;
 pub use customer::*;
 pub use discount::*;
+pub use draft_order::*;
+pub use flow::*;
 pub use gift_card::*;
 pub use inventory::*;
+pub use metaobject::*;
 pub use order::*;
 pub use order_edit::*;
 pub use payments::*;
 pub use product::*;
 pub use refund::*;
+pub use webhook::*;
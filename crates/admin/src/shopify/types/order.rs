@@ -1,5 +1,6 @@
 //! Order domain types for Shopify Admin API.
 
+use naked_pineapple_core::{CustomerId, LocationId, OrderId, ProductId, VariantId};
 use serde::{Deserialize, Serialize};
 
 use super::common::{Address, Image, Money, PageInfo};
@@ -166,6 +167,9 @@ pub struct Fulfillment {
     pub created_at: String,
     /// Last update timestamp.
     pub updated_at: String,
+    /// Line items in this fulfillment (only fetched for single orders).
+    #[serde(default)]
+    pub line_items: Vec<FulfillmentLineItem>,
 }
 
 /// A line item in a fulfillment.
@@ -267,7 +271,7 @@ pub struct FulfillmentOrderDetail {
     /// Request status.
     pub request_status: Option<String>,
     /// Location ID.
-    pub location_id: Option<String>,
+    pub location_id: Option<LocationId>,
     /// Location name.
     pub location_name: Option<String>,
     /// Supported actions.
@@ -300,9 +304,9 @@ pub struct OrderLineItem {
     /// Total discount amount.
     pub total_discount: Money,
     /// Product ID.
-    pub product_id: Option<String>,
+    pub product_id: Option<ProductId>,
     /// Variant ID.
-    pub variant_id: Option<String>,
+    pub variant_id: Option<VariantId>,
     /// Whether requires shipping.
     pub requires_shipping: bool,
     /// Whether is a gift card.
@@ -343,9 +347,9 @@ pub struct OrderDetailLineItem {
     /// Discounted total (quantity × discounted price).
     pub discounted_total: Money,
     /// Product ID.
-    pub product_id: Option<String>,
+    pub product_id: Option<ProductId>,
     /// Variant ID.
-    pub variant_id: Option<String>,
+    pub variant_id: Option<VariantId>,
     /// Line item image.
     pub image: Option<Image>,
     /// Whether requires shipping.
@@ -576,6 +580,19 @@ pub struct OrderReturn {
     pub line_items: Vec<ReturnLineItem>,
 }
 
+/// An exchange on an order (a return that includes replacement items).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderExchange {
+    /// Return ID.
+    pub id: String,
+    /// Return name (e.g., "#1001-R1").
+    pub name: String,
+    /// When the exchange was created.
+    pub created_at: String,
+    /// Total quantity of replacement items.
+    pub exchange_quantity: i64,
+}
+
 // =============================================================================
 // Risk and Event Types
 // =============================================================================
@@ -630,6 +647,30 @@ pub struct OrderEvent {
     pub author_name: Option<String>,
 }
 
+/// An entry in an order's timeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum OrderTimelineEvent {
+    /// Something happened to the order (placed, paid, fulfilled, ...).
+    StatusChange {
+        /// Shopify's event action (e.g., `placed`, `fulfillment_success`).
+        action: String,
+        /// Event message.
+        message: String,
+        /// When the event occurred.
+        created_at: String,
+    },
+    /// A staff member commented on the order.
+    Comment {
+        /// Comment text.
+        body: String,
+        /// Name of the staff member who wrote the comment.
+        author: String,
+        /// When the comment was posted.
+        created_at: String,
+    },
+}
+
 // =============================================================================
 // Shipping and Channel Types
 // =============================================================================
@@ -669,7 +710,7 @@ pub struct OrderChannelInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderDetailCustomer {
     /// Customer ID.
-    pub id: String,
+    pub id: CustomerId,
     /// Display name.
     pub display_name: String,
     /// Email.
@@ -694,7 +735,7 @@ pub struct OrderDetailCustomer {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
     /// Order ID.
-    pub id: String,
+    pub id: OrderId,
     /// Order name (e.g., "#1001").
     pub name: String,
     /// Order number.
@@ -740,7 +781,11 @@ pub struct Order {
     /// Shipping address.
     pub shipping_address: Option<Address>,
     /// Customer ID.
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
+    /// Exchanges created on this order.
+    pub exchanges: Vec<OrderExchange>,
+    /// Timeline events, oldest first.
+    pub timeline_events: Vec<OrderTimelineEvent>,
 }
 
 /// Comprehensive order detail for the order detail page.
@@ -750,7 +795,7 @@ pub struct Order {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderDetail {
     /// Order ID.
-    pub id: String,
+    pub id: OrderId,
     /// Order name (e.g., "#1001").
     pub name: String,
     /// Order number.
@@ -850,7 +895,7 @@ pub struct OrderDetail {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderListItem {
     /// Order ID.
-    pub id: String,
+    pub id: OrderId,
     /// Order name (e.g., "#1001").
     pub name: String,
     /// Order number.
@@ -895,6 +940,8 @@ pub struct OrderListItem {
     pub total_price: Money,
     /// Total discount amount.
     pub total_discounts: Money,
+    /// Total amount refunded.
+    pub total_refunded: Money,
     /// Currency code.
     pub currency_code: String,
     /// Line items (limited for list view).
@@ -908,7 +955,7 @@ pub struct OrderListItem {
     /// Shipping address.
     pub shipping_address: Option<Address>,
     /// Customer ID.
-    pub customer_id: Option<String>,
+    pub customer_id: Option<CustomerId>,
     /// Customer display name.
     pub customer_name: Option<String>,
     /// Order risk assessments.
@@ -1,5 +1,6 @@
 //! Order edit domain types for Shopify Admin API.
 
+use naked_pineapple_core::{OrderId, VariantId};
 use serde::{Deserialize, Serialize};
 
 use super::common::{Image, Money};
@@ -84,7 +85,7 @@ pub struct CalculatedLineItem {
     /// Line item image.
     pub image: Option<Image>,
     /// Variant ID.
-    pub variant_id: Option<String>,
+    pub variant_id: Option<VariantId>,
     /// Applied discount allocations.
     pub discount_allocations: Vec<CalculatedDiscountAllocation>,
 }
@@ -172,7 +173,7 @@ pub struct CalculatedOrder {
     /// Calculated order ID (used in subsequent mutations).
     pub id: String,
     /// The original order being edited.
-    pub original_order_id: String,
+    pub original_order_id: OrderId,
     /// Original order name.
     pub original_order_name: String,
     /// Pre-existing line items with applied changes.
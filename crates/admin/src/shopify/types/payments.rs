@@ -1,5 +1,6 @@
 //! Payment, payout, and dispute domain types for Shopify Admin API.
 
+use naked_pineapple_core::OrderId;
 use serde::{Deserialize, Serialize};
 
 use super::common::{Money, PageInfo};
@@ -281,7 +282,7 @@ pub struct BalanceTransaction {
     /// Source type.
     pub source_type: BalanceTransactionSourceType,
     /// Associated order ID (if any).
-    pub order_id: Option<String>,
+    pub order_id: Option<OrderId>,
     /// Associated order name (if any).
     pub order_name: Option<String>,
 }
@@ -378,7 +379,7 @@ pub struct Dispute {
     /// Reason details.
     pub reason_details: Option<DisputeReasonDetails>,
     /// Associated order ID.
-    pub order_id: Option<String>,
+    pub order_id: Option<OrderId>,
     /// Associated order name.
     pub order_name: Option<String>,
 }
@@ -1,8 +1,11 @@
 //! Product domain types for Shopify Admin API.
 
+use naked_pineapple_core::{InventoryItemId, ProductId, VariantId};
 use serde::{Deserialize, Serialize};
 
 use super::common::{Image, Money, PageInfo};
+use super::customer::ResourcePublication;
+use super::discount::CollectionRef;
 
 // =============================================================================
 // Product Types
@@ -26,7 +29,7 @@ pub enum ProductStatus {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdminProductVariant {
     /// Variant ID.
-    pub id: String,
+    pub id: VariantId,
     /// Variant title (combination of option values).
     pub title: String,
     /// SKU code.
@@ -40,13 +43,15 @@ pub struct AdminProductVariant {
     /// Inventory quantity (across all locations).
     pub inventory_quantity: i64,
     /// Inventory item ID (for inventory operations).
-    pub inventory_item_id: String,
+    pub inventory_item_id: InventoryItemId,
     /// Whether inventory is tracked.
     pub inventory_management: Option<String>,
     /// Weight value.
     pub weight: Option<f64>,
     /// Weight unit (KILOGRAMS, GRAMS, POUNDS, OUNCES).
     pub weight_unit: Option<String>,
+    /// Package dimensions, if set.
+    pub dimensions: Option<VariantDimensions>,
     /// Whether requires shipping.
     pub requires_shipping: bool,
     /// Variant image.
@@ -57,11 +62,93 @@ pub struct AdminProductVariant {
     pub updated_at: Option<String>,
 }
 
+/// Package dimensions of a variant.
+///
+/// Shopify has no dimension fields on variants, so these are stored as
+/// `dimension` metafields (`shipping.length`, `shipping.width` and
+/// `shipping.height`) for shipping carriers to read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VariantDimensions {
+    /// Length.
+    pub length: f64,
+    /// Width.
+    pub width: f64,
+    /// Height.
+    pub height: f64,
+    /// Unit of all three measurements (`mm`, `cm`, `m`, `in`, `ft` or `yd`).
+    pub unit: String,
+}
+
+/// Value of a Shopify `dimension` metafield.
+#[derive(Debug, Serialize, Deserialize)]
+struct DimensionValue {
+    value: f64,
+    unit: String,
+}
+
+impl VariantDimensions {
+    /// Metafield namespace the dimensions are stored under.
+    pub const NAMESPACE: &'static str = "shipping";
+    /// Metafield type of each dimension.
+    pub const METAFIELD_TYPE: &'static str = "dimension";
+    /// Units Shopify accepts for `dimension` metafields.
+    pub const UNITS: [&'static str; 6] = ["mm", "cm", "m", "in", "ft", "yd"];
+
+    /// Read dimensions from a variant's `(key, value)` metafields in
+    /// [`Self::NAMESPACE`].
+    ///
+    /// Returns `None` unless length, width and height are all present with
+    /// the same unit.
+    #[must_use]
+    pub fn from_metafields<'a>(
+        metafields: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Option<Self> {
+        let (mut length, mut width, mut height) = (None, None, None);
+        for (key, value) in metafields {
+            let slot = match key {
+                "length" => &mut length,
+                "width" => &mut width,
+                "height" => &mut height,
+                _ => continue,
+            };
+            *slot = serde_json::from_str::<DimensionValue>(value).ok();
+        }
+
+        let (length, width, height) = (length?, width?, height?);
+        if length.unit != width.unit || length.unit != height.unit {
+            return None;
+        }
+
+        Some(Self {
+            length: length.value,
+            width: width.value,
+            height: height.value,
+            unit: length.unit,
+        })
+    }
+
+    /// `(key, value)` metafields in [`Self::NAMESPACE`] for these dimensions.
+    #[must_use]
+    pub fn to_metafields(&self) -> Vec<(&'static str, String)> {
+        [
+            ("length", self.length),
+            ("width", self.width),
+            ("height", self.height),
+        ]
+        .into_iter()
+        .map(|(key, value)| {
+            let value = serde_json::json!({ "value": value, "unit": self.unit }).to_string();
+            (key, value)
+        })
+        .collect()
+    }
+}
+
 /// A product in the admin.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdminProduct {
     /// Product ID.
-    pub id: String,
+    pub id: ProductId,
     /// URL handle.
     pub handle: String,
     /// Product title.
@@ -91,6 +178,12 @@ pub struct AdminProduct {
     pub images: Vec<Image>,
     /// Product variants.
     pub variants: Vec<AdminProductVariant>,
+    /// Collections the product belongs to (first 50; empty in product lists).
+    #[serde(default)]
+    pub collections: Vec<CollectionRef>,
+    /// Sales channels the product is published on (empty in product lists).
+    #[serde(default)]
+    pub publications: Vec<ResourcePublication>,
 }
 
 // =============================================================================
@@ -106,6 +199,33 @@ pub struct AdminProductConnection {
     pub page_info: PageInfo,
 }
 
+/// A SKU change for a single variant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkuUpdate {
+    /// Variant ID.
+    pub variant_id: VariantId,
+    /// SKU to assign.
+    pub new_sku: String,
+}
+
+/// A SKU change that could not be applied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkuUpdateFailure {
+    /// Variant ID.
+    pub variant_id: VariantId,
+    /// Why the update failed.
+    pub error: String,
+}
+
+/// Outcome of a bulk SKU update.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkSkuResult {
+    /// IDs of variants whose SKU was updated.
+    pub updated: Vec<VariantId>,
+    /// Updates that failed, with the reason.
+    pub failed: Vec<SkuUpdateFailure>,
+}
+
 // =============================================================================
 // Sort Keys
 // =============================================================================
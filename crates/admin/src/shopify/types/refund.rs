@@ -1,5 +1,8 @@
 //! Refund, fulfillment hold, and return input types for Shopify Admin API.
 
+use naked_pineapple_core::{LocationId, OrderId, VariantId};
+use rust_decimal::Decimal;
+
 // =============================================================================
 // Refund Types
 // =============================================================================
@@ -61,7 +64,7 @@ pub struct RefundLineItemInput {
     /// How to handle restocking.
     pub restock_type: RefundRestockType,
     /// Location ID for restocking (required for RETURN type).
-    pub location_id: Option<String>,
+    pub location_id: Option<LocationId>,
 }
 
 /// How to handle restocking for a refund.
@@ -133,3 +136,89 @@ pub struct ReturnLineItemCreateInput {
     /// Note about the return reason.
     pub return_reason_note: Option<String>,
 }
+
+// =============================================================================
+// Exchange Input Types
+// =============================================================================
+
+/// Input for creating an exchange.
+///
+/// Shopify models an exchange as a return that also carries the replacement
+/// items, so the returned items are submitted together with the items to add.
+#[derive(Debug, Clone)]
+pub struct ExchangeOrderInput {
+    /// Order the exchange is created on.
+    pub order_id: OrderId,
+    /// Fulfillment line items the customer is sending back.
+    pub return_line_items: Vec<ReturnLineItemCreateInput>,
+    /// Replacement items to send to the customer.
+    pub line_items_to_add: Vec<ExchangeLineItemInput>,
+    /// Note about the exchange, recorded on each returned line item.
+    pub note: Option<String>,
+}
+
+/// Input for a replacement item in an exchange.
+#[derive(Debug, Clone)]
+pub struct ExchangeLineItemInput {
+    /// Product variant ID.
+    pub variant_id: VariantId,
+    /// Quantity to send.
+    pub quantity: i64,
+    /// The variant's current unit price.
+    pub list_price: Decimal,
+    /// Unit price to charge, if different from the list price.
+    ///
+    /// A lower price is applied as a fixed-amount discount on the exchange item.
+    pub price: Option<Decimal>,
+    /// ISO 4217 currency code of the prices.
+    pub currency_code: String,
+}
+
+impl ExchangeLineItemInput {
+    /// Unit discount needed to charge `price` instead of `list_price`.
+    ///
+    /// Returns `None` when no discount applies.
+    #[must_use]
+    pub fn discount_amount(&self) -> Option<Decimal> {
+        let price = self.price?.max(Decimal::ZERO);
+        (price < self.list_price).then(|| self.list_price - price)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn exchange_item(list_price: &str, price: Option<&str>) -> ExchangeLineItemInput {
+        ExchangeLineItemInput {
+            variant_id: "gid://shopify/ProductVariant/1".parse().unwrap(),
+            quantity: 1,
+            list_price: list_price.parse().unwrap(),
+            price: price.map(|p| p.parse().unwrap()),
+            currency_code: "USD".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_discount_amount_is_exact_difference() {
+        let item = exchange_item("29.99", Some("19.99"));
+        assert_eq!(item.discount_amount(), Some("10.00".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_discount_amount_caps_at_list_price() {
+        let item = exchange_item("29.99", Some("-5"));
+        assert_eq!(item.discount_amount(), Some("29.99".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_no_discount_at_or_above_list_price() {
+        assert_eq!(exchange_item("29.99", None).discount_amount(), None);
+        assert_eq!(
+            exchange_item("29.99", Some("29.99")).discount_amount(),
+            None
+        );
+        assert_eq!(exchange_item("29.99", Some("35")).discount_amount(), None);
+    }
+}
@@ -0,0 +1,25 @@
+//! Webhook subscription types for Shopify Admin API.
+
+use serde::{Deserialize, Serialize};
+
+/// A webhook subscription on the shop.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    /// Subscription GID.
+    pub id: String,
+    /// Event topic, e.g. `ORDERS_CREATE`.
+    pub topic: String,
+    /// Delivery URI (HTTPS endpoint, Pub/Sub or `EventBridge` ARN).
+    pub uri: String,
+    /// When the subscription was created.
+    pub created_at: String,
+}
+
+/// Convert a REST-style webhook topic (`orders/create`) to the GraphQL
+/// `WebhookSubscriptionTopic` value (`ORDERS_CREATE`).
+///
+/// Topics already in enum form are returned uppercased.
+#[must_use]
+pub fn webhook_topic_enum(topic: &str) -> String {
+    topic.replace('/', "_").to_ascii_uppercase()
+}
@@ -10,6 +10,7 @@ use sha2::Sha256;
 use tracing::{debug, error, instrument};
 
 use super::error::SlackError;
+use super::messages::build_fulfillment_message;
 use super::types::{Block, PostMessageResponse, SlackMessage, Text, UpdateMessageResponse};
 
 /// Slack Web API base URL.
@@ -293,6 +294,26 @@ impl SlackClient {
 
         self.post_message(channel, blocks, Some(text)).await
     }
+
+    /// Notify the default channel that an order has shipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if posting fails.
+    #[instrument(skip(self, tracking_url))]
+    pub async fn send_fulfillment_notification(
+        &self,
+        order_name: &str,
+        carrier: &str,
+        tracking_number: &str,
+        tracking_url: Option<&str>,
+    ) -> Result<PostMessageResponse, SlackError> {
+        let blocks = build_fulfillment_message(order_name, carrier, tracking_number, tracking_url);
+        let fallback = format!("Order {order_name} shipped via {carrier} ({tracking_number})");
+
+        self.post_message(&self.default_channel, blocks, Some(&fallback))
+            .await
+    }
 }
 
 /// Constant-time string comparison to prevent timing attacks.
@@ -4,6 +4,7 @@
 //! - Tool execution confirmation requests
 //! - Approval/rejection confirmations
 //! - Timeout notifications
+//! - Fulfillment notifications
 
 use uuid::Uuid;
 
@@ -159,6 +160,32 @@ pub fn build_error_message(tool_name: &str, error: &str) -> Vec<Block> {
     ]
 }
 
+/// Build a notification for an order shipped from the warehouse.
+#[must_use]
+pub fn build_fulfillment_message(
+    order_name: &str,
+    carrier: &str,
+    tracking_number: &str,
+    tracking_url: Option<&str>,
+) -> Vec<Block> {
+    let tracking = tracking_url.map_or_else(
+        || format!("`{tracking_number}`"),
+        |url| format!("<{url}|{tracking_number}>"),
+    );
+
+    vec![
+        Block::Header {
+            text: PlainText::new("🚚 Order Shipped"),
+        },
+        Block::Section {
+            text: Text::mrkdwn(format!(
+                "*Order:* {order_name}\n*Carrier:* {carrier}\n*Tracking:* {tracking}"
+            )),
+            accessory: None,
+        },
+    ]
+}
+
 /// Get an emoji for a tool domain.
 fn domain_emoji(domain: &str) -> &'static str {
     match domain {
@@ -218,6 +245,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fulfillment_message_links_tracking_url() {
+        let blocks = build_fulfillment_message(
+            "#1001",
+            "UPS",
+            "1Z999",
+            Some("https://ups.com/track?n=1Z999"),
+        );
+
+        assert_eq!(blocks.len(), 2);
+        match blocks.get(1) {
+            Some(Block::Section { text, .. }) => {
+                let text = serde_json::to_string(text).unwrap_or_default();
+                assert!(text.contains("#1001"));
+                assert!(text.contains("<https://ups.com/track?n=1Z999|1Z999>"));
+            }
+            _ => panic!("Expected Section block"),
+        }
+    }
+
     #[test]
     fn test_domain_emoji() {
         assert_eq!(domain_emoji("orders"), "📦");
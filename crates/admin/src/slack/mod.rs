@@ -23,7 +23,7 @@ pub use client::SlackClient;
 pub use error::SlackError;
 pub use messages::{
     build_approved_message, build_confirmation_message, build_error_message,
-    build_rejected_message, build_timeout_message,
+    build_fulfillment_message, build_rejected_message, build_timeout_message,
 };
 pub use types::{
     ActionElement, Block, ButtonStyle, ContextElement, InteractionAction, InteractionPayload,
@@ -2,17 +2,23 @@
 
 use std::sync::Arc;
 
+use futures::FutureExt;
 use secrecy::ExposeSecret;
 use sqlx::PgPool;
+use tokio::sync::OnceCell;
 use url::Url;
 use webauthn_rs::prelude::*;
 
+use crate::claude::{PromptError, PromptRegistry, PromptTemplate, ToolConfirmations};
 use crate::config::AdminConfig;
 use crate::db::{ShipHeroCredentialsRepository, ShopifyTokenRepository};
-use crate::services::EmailService;
+use crate::services::{DashboardService, EmailService, OrderSummaryService, SessionCleanupStats};
 use crate::shiphero::ShipHeroClient;
 use crate::shiphero::auth::ShipHeroToken;
-use crate::shopify::{AdminClient, OAuthToken};
+use crate::shopify::{
+    AdminClient, AdminShopifyError, Country, OAuthToken, RetryableAdminClient, SHOPIFY_API_VERSION,
+    ShopLocale,
+};
 use crate::slack::SlackClient;
 
 /// Error that can occur when creating `AppState`.
@@ -29,6 +35,24 @@ pub enum AppStateError {
     /// Email service initialization failed.
     #[error("email service initialization failed: {0}")]
     Email(String),
+
+    /// Shopify client configuration is invalid.
+    #[error("invalid Shopify configuration: {0}")]
+    Shopify(#[from] AdminShopifyError),
+
+    /// The configured Shopify API version differs from the one the GraphQL
+    /// queries were compiled against.
+    #[error(
+        "SHOPIFY_API_VERSION is {configured} but the queries were built for {compiled}; rebuild with SHOPIFY_API_VERSION={configured}"
+    )]
+    ApiVersionMismatch {
+        configured: String,
+        compiled: &'static str,
+    },
+
+    /// Claude prompt templates failed to load.
+    #[error("prompt template error: {0}")]
+    Prompt(#[from] PromptError),
 }
 
 /// Application state shared across all handlers.
@@ -43,54 +67,51 @@ pub struct AppState {
 struct AppStateInner {
     config: AdminConfig,
     pool: PgPool,
-    shopify: AdminClient,
+    read_pool: Option<PgPool>,
+    shopify: RetryableAdminClient,
     shiphero: Option<ShipHeroClient>,
     slack: Option<SlackClient>,
     webauthn: Webauthn,
     email_service: Option<EmailService>,
+    dashboard: DashboardService,
+    order_summary: OrderSummaryService,
+    /// Result of the last expired session cleanup
+    session_cleanup: SessionCleanupStats,
+    /// Confirmation tokens issued by chat tools
+    tool_confirmations: ToolConfirmations,
+    /// Claude chat system prompt template, chosen at startup
+    prompt_template: PromptTemplate,
+    /// Countries and provinces for address forms, fetched on first use
+    countries: OnceCell<Vec<Country>>,
+    /// Languages enabled on the store, fetched on first use
+    shop_locales: OnceCell<Vec<ShopLocale>>,
 }
 
 impl AppState {
     /// Create a new application state.
     ///
-    /// Loads any existing Shopify OAuth token from the database.
+    /// Loads any existing Shopify OAuth token from the database. If Shopify
+    /// later rejects it, a token saved since by re-authorizing is used
+    /// instead; otherwise the revoked token is deleted so `/shopify` asks
+    /// for re-authorization.
     ///
     /// # Arguments
     ///
     /// * `config` - Admin configuration
     /// * `pool` - `PostgreSQL` connection pool
+    /// * `read_pool` - Read replica connection pool (reads use `pool` if `None`)
     ///
     /// # Errors
     ///
-    /// Returns `AppStateError` if `WebAuthn` initialization fails.
-    pub async fn new(config: AdminConfig, pool: PgPool) -> Result<Self, AppStateError> {
-        let shopify = AdminClient::new(&config.shopify);
-
-        // Load OAuth token from database if available
-        let shop = &config.shopify.store;
-        let repo = ShopifyTokenRepository::new(&pool);
-        match repo.get_by_shop(shop).await {
-            Ok(Some(token)) => {
-                tracing::info!(shop = %shop, "Loaded Shopify OAuth token from database");
-                shopify
-                    .set_token(OAuthToken {
-                        access_token: token.access_token.expose_secret().to_string(),
-                        scope: token.scopes.join(","),
-                        obtained_at: token.obtained_at,
-                        shop: token.shop,
-                    })
-                    .await;
-            }
-            Ok(None) => {
-                tracing::warn!(
-                    shop = %shop,
-                    "No Shopify OAuth token found - authorization required via /settings/shopify"
-                );
-            }
-            Err(e) => {
-                tracing::error!(error = %e, "Failed to load Shopify OAuth token from database");
-            }
-        }
+    /// Returns `AppStateError` if the Shopify API version is malformed or
+    /// differs from [`SHOPIFY_API_VERSION`], `WebAuthn` initialization fails,
+    /// or the configured Claude prompt template doesn't exist.
+    pub async fn new(
+        config: AdminConfig,
+        pool: PgPool,
+        read_pool: Option<PgPool>,
+    ) -> Result<Self, AppStateError> {
+        let shopify = Self::create_shopify_client(&config, &pool).await?;
 
         // Initialize WebAuthn
         let base_url =
@@ -134,6 +155,8 @@ impl AppState {
             );
         }
 
+        let prompt_template = Self::load_prompt_template(&pool, &config).await?;
+
         // Initialize ShipHero client (optional - load credentials from database if available)
         let shiphero = Self::load_shiphero_client(&pool).await;
         if shiphero.is_some() {
@@ -148,11 +171,19 @@ impl AppState {
             inner: Arc::new(AppStateInner {
                 config,
                 pool,
+                read_pool,
                 shopify,
                 shiphero,
                 slack,
                 webauthn,
                 email_service,
+                dashboard: DashboardService::new(),
+                order_summary: OrderSummaryService::new(),
+                session_cleanup: SessionCleanupStats::default(),
+                tool_confirmations: ToolConfirmations::new(),
+                prompt_template,
+                countries: OnceCell::new(),
+                shop_locales: OnceCell::new(),
             }),
         })
     }
@@ -169,12 +200,28 @@ impl AppState {
         &self.inner.pool
     }
 
+    /// Get a reference to the read-only connection pool.
+    ///
+    /// Returns the read replica pool if configured, otherwise the primary pool.
+    #[must_use]
+    pub fn read_pool(&self) -> &PgPool {
+        self.inner.read_pool.as_ref().unwrap_or(&self.inner.pool)
+    }
+
     /// Get a reference to the Shopify Admin API client.
     #[must_use]
     pub fn shopify(&self) -> &AdminClient {
         &self.inner.shopify
     }
 
+    /// Get the Shopify Admin API version.
+    ///
+    /// Always equal to [`SHOPIFY_API_VERSION`]; checked in [`Self::new`].
+    #[must_use]
+    pub fn shopify_api_version(&self) -> &str {
+        &self.inner.config.shopify.api_version
+    }
+
     /// Get a reference to the `WebAuthn` instance.
     #[must_use]
     pub fn webauthn(&self) -> &Webauthn {
@@ -187,6 +234,70 @@ impl AppState {
         self.inner.email_service.as_ref()
     }
 
+    /// Get a reference to the dashboard metrics service.
+    #[must_use]
+    pub fn dashboard(&self) -> &DashboardService {
+        &self.inner.dashboard
+    }
+
+    /// Get a reference to the orders list summary service.
+    #[must_use]
+    pub fn order_summary(&self) -> &OrderSummaryService {
+        &self.inner.order_summary
+    }
+
+    /// Get the result of the last expired session cleanup.
+    #[must_use]
+    pub fn session_cleanup(&self) -> &SessionCleanupStats {
+        &self.inner.session_cleanup
+    }
+
+    /// Get the confirmation tokens issued by chat tools.
+    #[must_use]
+    pub fn tool_confirmations(&self) -> &ToolConfirmations {
+        &self.inner.tool_confirmations
+    }
+
+    /// Get the system prompt template configured for the chat assistant.
+    #[must_use]
+    pub fn prompt_template(&self) -> &PromptTemplate {
+        &self.inner.prompt_template
+    }
+
+    /// Get the countries and provinces for address forms.
+    ///
+    /// Fetched from Shopify on first use and cached for the life of the
+    /// process; a failed fetch is retried on the next call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the countries haven't been fetched yet and the
+    /// Shopify request fails.
+    pub async fn countries(&self) -> Result<&[Country], AdminShopifyError> {
+        self.inner
+            .countries
+            .get_or_try_init(|| self.inner.shopify.get_countries())
+            .await
+            .map(Vec::as_slice)
+    }
+
+    /// Get the languages enabled on the store.
+    ///
+    /// Fetched from Shopify on first use and cached for the life of the
+    /// process; a failed fetch is retried on the next call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the locales haven't been fetched yet and the
+    /// Shopify request fails.
+    pub async fn shop_locales(&self) -> Result<&[ShopLocale], AdminShopifyError> {
+        self.inner
+            .shop_locales
+            .get_or_try_init(|| self.inner.shopify.get_shop_locales())
+            .await
+            .map(Vec::as_slice)
+    }
+
     /// Get a reference to the Slack client (if configured).
     #[must_use]
     pub fn slack(&self) -> Option<&SlackClient> {
@@ -205,6 +316,124 @@ impl AppState {
         self.inner.shiphero.as_ref()
     }
 
+    /// Create the Shopify Admin API client, with the stored OAuth token and a
+    /// callback that replaces it once Shopify rejects it.
+    async fn create_shopify_client(
+        config: &AdminConfig,
+        pool: &PgPool,
+    ) -> Result<RetryableAdminClient, AppStateError> {
+        if config.shopify.api_version != SHOPIFY_API_VERSION {
+            return Err(AppStateError::ApiVersionMismatch {
+                configured: config.shopify.api_version.clone(),
+                compiled: SHOPIFY_API_VERSION,
+            });
+        }
+        let shopify = AdminClient::new(&config.shopify)?;
+
+        // Load OAuth token from database if available
+        let shop = &config.shopify.store;
+        match Self::load_shopify_token(pool, shop).await {
+            Ok(token) => {
+                tracing::info!(shop = %shop, "Loaded Shopify OAuth token from database");
+                shopify.set_token(token).await;
+            }
+            Err(AdminShopifyError::NoAccessToken) => {
+                tracing::warn!(
+                    shop = %shop,
+                    "No Shopify OAuth token found - authorization required via /shopify"
+                );
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to load Shopify OAuth token from database");
+            }
+        }
+
+        let refresh_pool = pool.clone();
+        let refresh_shop = shop.clone();
+        Ok(RetryableAdminClient::new(
+            shopify,
+            Arc::new(move |rejected| {
+                let pool = refresh_pool.clone();
+                let shop = refresh_shop.clone();
+                async move { Self::replace_rejected_shopify_token(&pool, &shop, &rejected).await }
+                    .boxed()
+            }),
+        ))
+    }
+
+    /// Replace an OAuth token Shopify rejected.
+    ///
+    /// Shopify tokens can only be replaced by re-authorizing at `/shopify`,
+    /// so this returns the stored token if it has changed since. Otherwise
+    /// the stored token is the revoked one: it's deleted, so `/shopify` shows
+    /// the store as disconnected, and `Unauthorized` is returned.
+    async fn replace_rejected_shopify_token(
+        pool: &PgPool,
+        shop: &str,
+        rejected: &str,
+    ) -> Result<OAuthToken, AdminShopifyError> {
+        let token = Self::load_shopify_token(pool, shop).await?;
+        if token.access_token != rejected {
+            tracing::info!(shop = %shop, "Using Shopify OAuth token saved by re-authorizing");
+            return Ok(token);
+        }
+
+        match ShopifyTokenRepository::new(pool)
+            .invalidate(shop, rejected)
+            .await
+        {
+            Ok(_) => tracing::error!(
+                shop = %shop,
+                "Shopify revoked the OAuth token - re-authorization required via /shopify"
+            ),
+            Err(e) => tracing::error!(error = %e, "Failed to delete revoked Shopify OAuth token"),
+        }
+        Err(AdminShopifyError::Unauthorized(
+            "Shopify revoked the access token - reconnect the store at /shopify".to_string(),
+        ))
+    }
+
+    /// Load the configured Claude prompt template (bundled templates,
+    /// overridden by seeded versions).
+    async fn load_prompt_template(
+        pool: &PgPool,
+        config: &AdminConfig,
+    ) -> Result<PromptTemplate, AppStateError> {
+        let prompts = PromptRegistry::load(pool).await?;
+        let prompt_name = &config.claude.prompt_template;
+        let prompt_template = prompts
+            .get(prompt_name)
+            .cloned()
+            .ok_or_else(|| PromptError::NotFound(prompt_name.clone()))?;
+        tracing::info!(
+            name = %prompt_template.name,
+            version = prompt_template.version,
+            "Loaded Claude prompt template"
+        );
+        Ok(prompt_template)
+    }
+
+    /// Load the stored Shopify OAuth token for a shop.
+    ///
+    /// Returns `AdminShopifyError::NoAccessToken` if no token is stored.
+    async fn load_shopify_token(
+        pool: &PgPool,
+        shop: &str,
+    ) -> Result<OAuthToken, AdminShopifyError> {
+        let token = ShopifyTokenRepository::new(pool)
+            .get_by_shop(shop)
+            .await
+            .map_err(|e| AdminShopifyError::OAuth(format!("Failed to load token: {e}")))?
+            .ok_or(AdminShopifyError::NoAccessToken)?;
+
+        Ok(OAuthToken {
+            access_token: token.access_token.expose_secret().to_string(),
+            scope: token.scopes.join(","),
+            obtained_at: token.obtained_at,
+            shop: token.shop,
+        })
+    }
+
     /// Load `ShipHero` client from stored credentials.
     async fn load_shiphero_client(pool: &PgPool) -> Option<ShipHeroClient> {
         let repo = ShipHeroCredentialsRepository::new(pool);
@@ -106,6 +106,7 @@ impl ToolSelector {
     ///
     /// Uses pgvector's cosine distance operator (`<=>`) for similarity search.
     /// `SQLx` doesn't have built-in pgvector support, so we use runtime queries.
+    #[allow(clippy::disallowed_methods)] // every value is still a bound parameter
     async fn search_similar_tools(
         &self,
         embedding: &[f32],
@@ -158,23 +159,21 @@ impl ToolSelector {
         limit: usize,
     ) -> Result<Vec<String>, ToolSelectionError> {
         // Use subquery to get distinct tools ordered by max usage count
-        let rows = sqlx::query(
+        let tools = sqlx::query_scalar!(
             r"
-            SELECT tool_name, MAX(usage_count) as max_usage
+            SELECT tool_name
             FROM admin.tool_example_queries
             WHERE domain = ANY($1)
             GROUP BY tool_name
-            ORDER BY max_usage DESC
+            ORDER BY MAX(usage_count) DESC
             LIMIT $2
             ",
+            domains,
+            i64::try_from(limit).unwrap_or(i64::MAX)
         )
-        .bind(domains)
-        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
         .fetch_all(&self.pool)
         .await?;
 
-        let tools: Vec<String> = rows.into_iter().map(|r| r.get("tool_name")).collect();
-
         Ok(tools)
     }
 
@@ -192,6 +191,7 @@ impl ToolSelector {
     ///
     /// Returns an error if the database operation fails.
     #[instrument(skip(self, query))]
+    #[allow(clippy::disallowed_methods)] // pgvector insert; the embedding is a bound parameter
     pub async fn learn_from_success(
         &self,
         query: &str,
@@ -199,28 +199,28 @@ impl ToolSelector {
         domain: &str,
     ) -> Result<(), ToolSelectionError> {
         // Check if this exact query already exists
-        let existing: Option<i32> = sqlx::query_scalar(
+        let existing = sqlx::query_scalar!(
             r"
             SELECT id FROM admin.tool_example_queries
             WHERE tool_name = $1 AND example_query = $2
             LIMIT 1
             ",
+            tool_name,
+            query
         )
-        .bind(tool_name)
-        .bind(query)
         .fetch_optional(&self.pool)
         .await?;
 
         if let Some(id) = existing {
             // Increment usage count
-            sqlx::query(
+            sqlx::query!(
                 r"
                 UPDATE admin.tool_example_queries
                 SET usage_count = usage_count + 1
                 WHERE id = $1
                 ",
+                id
             )
-            .bind(id)
             .execute(&self.pool)
             .await?;
 
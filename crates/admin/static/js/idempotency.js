@@ -0,0 +1,36 @@
+/**
+ * Idempotency Keys
+ *
+ * Sends an X-Idempotency-Key header with every HTMX mutation so the server
+ * can replay its response if the same submission arrives twice. Each form
+ * (or standalone hx-post button) keeps its key until the server answers;
+ * a retry after a timeout or network error reuses it, while the next
+ * deliberate submission gets a new one.
+ */
+(function() {
+    var KEY_ATTRIBUTE = 'data-idempotency-key';
+
+    function keyOwner(elt) {
+        return elt.closest('form') || elt;
+    }
+
+    document.body.addEventListener('htmx:configRequest', function(evt) {
+        if (evt.detail.verb === 'get') {
+            return;
+        }
+        var owner = keyOwner(evt.detail.elt);
+        var key = owner.getAttribute(KEY_ATTRIBUTE);
+        if (!key) {
+            key = crypto.randomUUID();
+            owner.setAttribute(KEY_ATTRIBUTE, key);
+        }
+        evt.detail.headers['X-Idempotency-Key'] = key;
+    });
+
+    document.body.addEventListener('htmx:afterRequest', function(evt) {
+        // Status 0 means no response arrived, so the request may be retried
+        if (evt.detail.xhr && evt.detail.xhr.status !== 0) {
+            keyOwner(evt.detail.elt).removeAttribute(KEY_ATTRIBUTE);
+        }
+    });
+})();
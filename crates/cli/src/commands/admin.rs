@@ -8,6 +8,9 @@
 //!
 //! # Create a new admin user directly (without passkey)
 //! np-cli admin create -e admin@example.com -n "Admin Name" -r super_admin
+//!
+//! # Delete expired admin sessions (or only those that expired before a date)
+//! np-cli admin cleanup-sessions --before 2025-01-01
 //! ```
 //!
 //! # Environment Variables
@@ -15,6 +18,8 @@
 //! - `ADMIN_DATABASE_URL` - `PostgreSQL` connection string for admin database
 //! - `ADMIN_BASE_URL` - Base URL for generating setup links
 
+use chrono::{NaiveDate, NaiveTime, Utc};
+use naked_pineapple_admin::db::{RepositoryError, SessionRepository};
 use naked_pineapple_core::AdminRole;
 use sqlx::PgPool;
 use thiserror::Error;
@@ -45,6 +50,14 @@ pub enum AdminError {
     /// Invite already exists.
     #[error("Invite already exists for email: {0}")]
     InviteExists(String),
+
+    /// Cleanup cutoff is in the future (would delete live sessions).
+    #[error("Cutoff date {0} is in the future")]
+    FutureCutoff(NaiveDate),
+
+    /// Session cleanup failed.
+    #[error("Failed to delete sessions: {0}")]
+    Repository(#[from] RepositoryError),
 }
 
 /// Create a new admin user.
@@ -214,3 +227,47 @@ pub async fn create_invite(
 
     Ok(invite_id)
 }
+
+/// Delete expired admin sessions.
+///
+/// The admin server does this every few hours; this is a one-shot
+/// alternative for maintenance windows.
+///
+/// # Arguments
+///
+/// * `before` - Only delete sessions that expired before this date (UTC
+///   midnight). Defaults to now.
+///
+/// # Returns
+///
+/// The number of sessions deleted.
+pub async fn cleanup_sessions(before: Option<NaiveDate>) -> Result<u64, AdminError> {
+    dotenvy::dotenv().ok();
+
+    let now = Utc::now();
+    let cutoff = match before {
+        Some(date) => {
+            let cutoff = date.and_time(NaiveTime::MIN).and_utc();
+            if cutoff > now {
+                return Err(AdminError::FutureCutoff(date));
+            }
+            cutoff
+        }
+        None => now,
+    };
+
+    let database_url = std::env::var("ADMIN_DATABASE_URL")
+        .map_err(|_| AdminError::MissingEnvVar("ADMIN_DATABASE_URL"))?;
+
+    tracing::info!("Connecting to admin database...");
+    let pool = PgPool::connect(&database_url).await?;
+
+    let deleted = SessionRepository::new(&pool).delete_expired(cutoff).await?;
+    tracing::info!(
+        "Deleted {} sessions that expired before {}",
+        deleted,
+        cutoff
+    );
+
+    Ok(deleted)
+}
@@ -0,0 +1,214 @@
+//! Shopify Admin API version upgrade checks.
+//!
+//! Shopify releases a new API version every quarter. Before bumping
+//! `SHOPIFY_API_VERSION`, run the admin GraphQL documents against the new
+//! version and list anything Shopify reports as deprecated or invalid.
+//!
+//! # Usage
+//!
+//! ```bash
+//! np-cli shopify check-api-version --target-version 2026-04
+//! ```
+//!
+//! # Environment Variables
+//!
+//! - `ADMIN_DATABASE_URL` - `PostgreSQL` connection string (for the stored Shopify token)
+//! - `SHOPIFY_STORE`, `SHOPIFY_ADMIN_CLIENT_ID`, `SHOPIFY_ADMIN_CLIENT_SECRET` - Shopify Admin API
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use naked_pineapple_admin::config::{ConfigError, ShopifyAdminConfig};
+use naked_pineapple_admin::shopify::{AdminClient, AdminShopifyError, validate_api_version};
+
+use super::shopify::{self, ConnectError};
+
+/// Minimal read-only query used to probe the target version.
+const PROBE_QUERY: &str = "query ApiVersionProbe { shop { name } }";
+
+/// Errors that can occur while checking an API version.
+#[derive(Debug, Error)]
+pub enum ApiVersionError {
+    /// Configuration is invalid.
+    #[error("Configuration error: {0}")]
+    Config(#[from] ConfigError),
+
+    /// Failed to create the Shopify client.
+    #[error(transparent)]
+    Connect(#[from] ConnectError),
+
+    /// Shopify API error.
+    #[error("Shopify API error: {0}")]
+    Shopify(#[from] AdminShopifyError),
+
+    /// Failed to read the GraphQL documents.
+    #[error("Failed to read {0}: {1}")]
+    Read(String, std::io::Error),
+}
+
+/// Options for `shopify check-api-version`.
+#[derive(Debug)]
+pub struct CheckOptions {
+    pub target_version: String,
+    pub graphql_dir: PathBuf,
+}
+
+/// An operation defined in a GraphQL document.
+#[derive(Debug)]
+struct Operation {
+    name: String,
+    is_query: bool,
+}
+
+/// Check every GraphQL document against a new Admin API version.
+///
+/// Query operations are sent to the target version as-is; mutations are
+/// never executed, but Shopify validates them along with the queries in the
+/// same file. Deprecation notices and schema errors are logged per query.
+///
+/// # Errors
+///
+/// Returns an error if the target version is malformed, configuration is
+/// missing, the documents can't be read, or the probe request fails.
+pub async fn check(options: &CheckOptions) -> Result<(), ApiVersionError> {
+    dotenvy::dotenv().ok();
+    validate_api_version(&options.target_version)?;
+
+    // Staging copy of the config that points at the target version
+    let mut config = ShopifyAdminConfig::from_env()?;
+    tracing::info!(
+        current = %config.api_version,
+        target = %options.target_version,
+        "Checking Shopify Admin API version"
+    );
+    config.api_version.clone_from(&options.target_version);
+    let client = shopify::connect_with(&config).await?;
+
+    let probe = client.check_document(PROBE_QUERY, None).await?;
+    if let Some(served) = probe.api_version.as_deref()
+        && served != options.target_version
+    {
+        tracing::warn!(
+            served,
+            "Shopify did not serve {}; it may not be released or is no longer supported",
+            options.target_version
+        );
+    }
+    if let Some(reason) = &probe.deprecated_reason {
+        tracing::warn!("API version deprecated: {reason}");
+    }
+
+    let files = graphql_files(&options.graphql_dir)?;
+    let mut flagged = 0;
+    let mut checked = 0;
+    for path in files {
+        let document = std::fs::read_to_string(&path)
+            .map_err(|e| ApiVersionError::Read(path.display().to_string(), e))?;
+        let file = path.file_name().unwrap_or_default().to_string_lossy();
+        let (queries, mutations): (Vec<_>, Vec<_>) = operations(&document)
+            .into_iter()
+            .partition(|op| op.is_query);
+
+        if queries.is_empty() {
+            tracing::warn!(
+                file = %file,
+                mutations = mutations.len(),
+                "Skipped: no query operation to validate the mutations with"
+            );
+            continue;
+        }
+
+        let mut schema_errors = BTreeSet::new();
+        for query in &queries {
+            let result = check_operation(&client, &document, &query.name).await?;
+            checked += 1;
+            if let Some(reason) = result.deprecated_reason {
+                flagged += 1;
+                tracing::warn!(file = %file, query = %query.name, "Deprecated: {reason}");
+            }
+            schema_errors.extend(result.schema_errors);
+        }
+
+        for error in &schema_errors {
+            tracing::warn!(file = %file, "Invalid in {}: {error}", options.target_version);
+        }
+        flagged += schema_errors.len();
+    }
+
+    if flagged == 0 {
+        tracing::info!(
+            "Checked {checked} queries: none deprecated in {}",
+            options.target_version
+        );
+    } else {
+        tracing::warn!(
+            "Checked {checked} queries: {flagged} warnings for {}",
+            options.target_version
+        );
+    }
+    Ok(())
+}
+
+/// Outcome of checking one query operation.
+struct OperationResult {
+    deprecated_reason: Option<String>,
+    schema_errors: Vec<String>,
+}
+
+/// Send one query operation from `document` to the target version.
+async fn check_operation(
+    client: &AdminClient,
+    document: &str,
+    name: &str,
+) -> Result<OperationResult, ApiVersionError> {
+    let result = client.check_document(document, Some(name)).await?;
+
+    // Queries are sent without variables, so errors about missing variables
+    // are expected; anything else is a problem with the document itself
+    let schema_errors = result
+        .errors
+        .into_iter()
+        .filter(|error| !error.contains("Variable $"))
+        .collect();
+
+    Ok(OperationResult {
+        deprecated_reason: result.deprecated_reason,
+        schema_errors,
+    })
+}
+
+/// `.graphql` files in `dir`, sorted by name.
+fn graphql_files(dir: &Path) -> Result<Vec<PathBuf>, ApiVersionError> {
+    let read_error = |e| ApiVersionError::Read(dir.display().to_string(), e);
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+        if path.extension().is_some_and(|ext| ext == "graphql") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Named query and mutation operations declared at the start of a line.
+fn operations(document: &str) -> Vec<Operation> {
+    document
+        .lines()
+        .filter_map(|line| {
+            let (keyword, rest) = line.split_once(' ')?;
+            let is_query = match keyword {
+                "query" => true,
+                "mutation" => false,
+                _ => return None,
+            };
+            let name: String = rest
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect();
+            (!name.is_empty()).then_some(Operation { name, is_query })
+        })
+        .collect()
+}
@@ -0,0 +1,281 @@
+//! Collection maintenance commands.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # Sort a manually sorted collection by title
+//! np-cli shopify sync-collections --collection-id gid://shopify/Collection/123 --sort-by title-asc
+//!
+//! # Sort by a file of product handles, one per line, previewing first
+//! np-cli shopify sync-collections --collection-id gid://shopify/Collection/123 \
+//!     --sort-by custom-from-file --custom-from-file order.txt --dry-run
+//! ```
+//!
+//! # Environment Variables
+//!
+//! - `ADMIN_DATABASE_URL` - `PostgreSQL` connection string (for the stored Shopify token)
+//! - `SHOPIFY_STORE`, `SHOPIFY_ADMIN_CLIENT_ID`, `SHOPIFY_ADMIN_CLIENT_SECRET` - Shopify Admin API
+
+use std::cmp::{Ordering, Reverse};
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use thiserror::Error;
+
+use naked_pineapple_admin::shopify::{AdminClient, AdminShopifyError, CollectionProduct};
+use naked_pineapple_core::{CollectionId, ProductId};
+
+use super::shopify::{self, ConnectError};
+
+/// Products fetched per page when listing a collection.
+const PAGE_SIZE: i64 = 250;
+
+/// Maximum moves Shopify accepts in one `collectionReorderProducts` call.
+const MAX_MOVES_PER_CALL: usize = 250;
+
+/// Errors that can occur during collection operations.
+#[derive(Debug, Error)]
+pub enum CollectionError {
+    /// Failed to create the Shopify client.
+    #[error(transparent)]
+    Connect(#[from] ConnectError),
+
+    /// Shopify API error.
+    #[error("Shopify API error: {0}")]
+    Shopify(#[from] AdminShopifyError),
+
+    /// The collection doesn't exist.
+    #[error("Collection not found: {0}")]
+    NotFound(String),
+
+    /// The collection isn't sorted manually, so it can't be reordered.
+    #[error("Collection is sorted by {0}; set its sort order to MANUAL first")]
+    NotManual(String),
+
+    /// `--sort-by custom-from-file` was given without a file.
+    #[error("--sort-by custom-from-file requires --custom-from-file <PATH>")]
+    MissingOrderFile,
+
+    /// Failed to read the order file.
+    #[error("Failed to read {0}: {1}")]
+    Read(String, std::io::Error),
+}
+
+/// Desired product order for a collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SortBy {
+    /// Title, A to Z.
+    TitleAsc,
+    /// Title, Z to A.
+    TitleDesc,
+    /// Lowest price first.
+    PriceAsc,
+    /// Oldest product first.
+    CreatedAsc,
+    /// Order of product handles in `--custom-from-file`.
+    CustomFromFile,
+}
+
+/// Options for [`sync`].
+#[derive(Debug)]
+pub struct SyncOptions {
+    /// Collection GID.
+    pub collection_id: CollectionId,
+    /// Desired order.
+    pub sort_by: SortBy,
+    /// File of product handles, one per line, for [`SortBy::CustomFromFile`].
+    pub custom_from_file: Option<PathBuf>,
+    /// Print the diff without reordering.
+    pub dry_run: bool,
+}
+
+/// Reorder a manually sorted collection's products.
+///
+/// Prints the current and desired order, then moves only the products whose
+/// position changes. Nothing is sent to Shopify if the order already matches
+/// or `dry_run` is set.
+///
+/// # Errors
+///
+/// Returns an error if the collection doesn't exist or isn't manually
+/// sorted, the order file can't be read, or a Shopify request fails.
+pub async fn sync(options: &SyncOptions) -> Result<(), CollectionError> {
+    dotenvy::dotenv().ok();
+
+    let handles = match (options.sort_by, &options.custom_from_file) {
+        (SortBy::CustomFromFile, Some(path)) => Some(read_handles(path).await?),
+        (SortBy::CustomFromFile, None) => return Err(CollectionError::MissingOrderFile),
+        _ => None,
+    };
+
+    let client = shopify::connect().await?;
+    let current = list_products(&client, &options.collection_id).await?;
+
+    let desired = handles.as_ref().map_or_else(
+        || sorted(&current, options.sort_by),
+        |handles| {
+            for handle in handles {
+                if !current.iter().any(|p| &p.handle == handle) {
+                    tracing::warn!("{handle} is not in the collection, skipping");
+                }
+            }
+            order_by_handles(&current, handles)
+        },
+    );
+
+    print_diff(&current, &desired);
+
+    let moves = compute_moves(
+        &current.iter().map(|p| &p.id).collect::<Vec<_>>(),
+        &desired.iter().map(|p| &p.id).collect::<Vec<_>>(),
+    );
+    if moves.is_empty() {
+        tracing::info!("Collection is already in the desired order");
+        return Ok(());
+    }
+    if options.dry_run {
+        tracing::info!("Dry run: {} moves not applied", moves.len());
+        return Ok(());
+    }
+
+    for chunk in moves.chunks(MAX_MOVES_PER_CALL) {
+        client
+            .reorder_collection_products(&options.collection_id, chunk.to_vec())
+            .await?;
+    }
+    tracing::info!("Applied {} moves", moves.len());
+
+    Ok(())
+}
+
+/// Fetch every product in a manually sorted collection, in its current order.
+async fn list_products(
+    client: &AdminClient,
+    collection_id: &CollectionId,
+) -> Result<Vec<CollectionProduct>, CollectionError> {
+    let mut products = Vec::new();
+    let mut after = None;
+
+    loop {
+        let page = client
+            .get_collection_with_products(collection_id, PAGE_SIZE, after)
+            .await?
+            .ok_or_else(|| CollectionError::NotFound(collection_id.to_string()))?;
+
+        let sort_order = page.collection.sort_order.unwrap_or_default();
+        if sort_order != "MANUAL" {
+            return Err(CollectionError::NotManual(sort_order));
+        }
+
+        products.extend(page.products);
+        match page.end_cursor {
+            Some(cursor) if page.has_next_page => after = Some(cursor),
+            _ => return Ok(products),
+        }
+    }
+}
+
+/// Read product handles from a file, one per line, skipping blank lines.
+async fn read_handles(path: &Path) -> Result<Vec<String>, CollectionError> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| CollectionError::Read(path.display().to_string(), e))?;
+
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Sort products by a built-in order.
+///
+/// The sort is stable, so ties keep their current relative order.
+fn sorted(products: &[CollectionProduct], sort_by: SortBy) -> Vec<CollectionProduct> {
+    let mut sorted = products.to_vec();
+    match sort_by {
+        SortBy::TitleAsc => sorted.sort_by_key(|p| p.title.to_lowercase()),
+        SortBy::TitleDesc => sorted.sort_by_key(|p| Reverse(p.title.to_lowercase())),
+        SortBy::PriceAsc => sorted.sort_by(|a, b| compare_prices(&a.price, &b.price)),
+        SortBy::CreatedAsc => sorted.sort_by(|a, b| a.created_at.cmp(&b.created_at)),
+        SortBy::CustomFromFile => {}
+    }
+    sorted
+}
+
+/// Compare decimal price strings; unparseable prices sort last.
+fn compare_prices(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => Ordering::Equal,
+    }
+}
+
+/// Put products in the order of `handles`.
+///
+/// Products whose handle isn't listed follow, in their current order.
+fn order_by_handles(products: &[CollectionProduct], handles: &[String]) -> Vec<CollectionProduct> {
+    let rank = |product: &CollectionProduct| {
+        handles
+            .iter()
+            .position(|h| h == &product.handle)
+            .unwrap_or(handles.len())
+    };
+
+    let mut ordered = products.to_vec();
+    ordered.sort_by_key(rank);
+    ordered
+}
+
+/// Compute the moves that turn `current` into `desired`.
+///
+/// Shopify applies moves in sequence, so each move puts the product that
+/// belongs at a position there, shifting the rest down. Products already in
+/// place get no move.
+fn compute_moves(current: &[&ProductId], desired: &[&ProductId]) -> Vec<(ProductId, i64)> {
+    let mut order = current.to_vec();
+    let mut moves = Vec::new();
+
+    for (position, &id) in desired.iter().enumerate() {
+        if order.get(position) == Some(&id) {
+            continue;
+        }
+        let Some(from) = order.iter().position(|&p| p == id) else {
+            continue;
+        };
+        order.remove(from);
+        order.insert(position, id);
+        moves.push((id.clone(), i64::try_from(position).unwrap_or(i64::MAX)));
+    }
+
+    moves
+}
+
+/// Log the current and desired order side by side, marking changed positions.
+fn print_diff(current: &[CollectionProduct], desired: &[CollectionProduct]) {
+    let width = current
+        .iter()
+        .map(|p| p.handle.len())
+        .fold("CURRENT".len(), usize::max);
+
+    tracing::info!("   {:>4}  {:width$}  DESIRED", "POS", "CURRENT");
+    for (position, (before, after)) in current.iter().zip(desired).enumerate() {
+        let marker = if before.id == after.id { ' ' } else { '*' };
+        tracing::info!(
+            "{marker}  {:>4}  {:width$}  {}",
+            position + 1,
+            before.handle,
+            after.handle
+        );
+    }
+
+    let changed = current
+        .iter()
+        .zip(desired)
+        .filter(|(before, after)| before.id != after.id)
+        .count();
+    tracing::info!("{changed} of {} positions change", current.len());
+}
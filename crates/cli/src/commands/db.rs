@@ -0,0 +1,877 @@
+//! Database schema checks.
+//!
+//! Compares the live database against the schema its migrations describe,
+//! catching drift (hand-edited tables, half-applied or skipped migrations)
+//! before it surfaces as "column does not exist" errors at runtime.
+//!
+//! The expected schema is derived by replaying the `CREATE TABLE`,
+//! `ALTER TABLE` and `DROP TABLE` statements of every embedded up migration.
+//! Other objects (indexes, constraints, triggers, functions) aren't checked.
+//!
+//! # Usage
+//!
+//! ```bash
+//! np-cli db check storefront
+//! np-cli db check admin
+//! np-cli db check all
+//! ```
+//!
+//! # Environment Variables
+//!
+//! - `STOREFRONT_DATABASE_URL` - `PostgreSQL` connection string for storefront
+//! - `ADMIN_DATABASE_URL` - `PostgreSQL` connection string for admin
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use sqlx::PgPool;
+use thiserror::Error;
+
+use super::migrate::{Database, MigrationError};
+
+/// Schema checked even when no migration creates a table in it.
+const DEFAULT_SCHEMA: &str = "public";
+
+/// Table sqlx records applied migrations in.
+const MIGRATIONS_TABLE: &str = "public._sqlx_migrations";
+
+/// Keywords that end the type in a column definition.
+const COLUMN_CONSTRAINTS: &[&str] = &[
+    "NOT",
+    "NULL",
+    "DEFAULT",
+    "PRIMARY",
+    "REFERENCES",
+    "UNIQUE",
+    "CHECK",
+    "GENERATED",
+    "CONSTRAINT",
+    "COLLATE",
+];
+
+/// Keywords that start a table constraint rather than a column definition.
+const TABLE_CONSTRAINTS: &[&str] = &[
+    "CONSTRAINT",
+    "PRIMARY",
+    "UNIQUE",
+    "CHECK",
+    "FOREIGN",
+    "EXCLUDE",
+    "LIKE",
+];
+
+/// Errors that can occur while checking a schema.
+#[derive(Debug, Error)]
+pub enum SchemaCheckError {
+    /// Failed to connect to the database.
+    #[error(transparent)]
+    Connect(#[from] MigrationError),
+
+    /// Failed to query the live schema.
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    /// The live schema doesn't match the migrations.
+    #[error("Schema check failed: {0} problem(s) found")]
+    Drift(usize),
+}
+
+/// A column's type and nullability.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Column {
+    /// Postgres type name as in `information_schema.columns.udt_name`
+    /// (e.g., `int4`, `timestamptz`, `_text` for `TEXT[]`).
+    udt_name: String,
+    nullable: bool,
+}
+
+/// Tables keyed by `schema.table`, with their columns by name.
+type Schema = BTreeMap<String, BTreeMap<String, Column>>;
+
+/// A difference between the live schema and the migrations.
+#[derive(Debug)]
+enum Problem {
+    /// Table exists in the database but not in the migrations.
+    OrphanedTable(String),
+    /// Table exists in the migrations but not in the database.
+    MissingTable(String),
+    /// Column exists in the database but not in the migrations.
+    OrphanedColumn { table: String, column: String },
+    /// Column exists in the migrations but not in the database.
+    MissingColumn { table: String, column: String },
+    /// Column has a different type than the migrations give it.
+    WrongType {
+        table: String,
+        column: String,
+        expected: String,
+        actual: String,
+    },
+    /// Column is nullable when the migrations make it `NOT NULL`, or the
+    /// other way around.
+    WrongNullability {
+        table: String,
+        column: String,
+        expected_nullable: bool,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let null = |nullable: bool| if nullable { "NULL" } else { "NOT NULL" };
+        match self {
+            Self::OrphanedTable(table) => write!(f, "orphaned table {table} (not in migrations)"),
+            Self::MissingTable(table) => write!(f, "missing table {table}"),
+            Self::OrphanedColumn { table, column } => {
+                write!(f, "orphaned column {table}.{column} (not in migrations)")
+            }
+            Self::MissingColumn { table, column } => write!(f, "missing column {table}.{column}"),
+            Self::WrongType {
+                table,
+                column,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "column {table}.{column} is {actual}, expected {expected}"
+            ),
+            Self::WrongNullability {
+                table,
+                column,
+                expected_nullable,
+            } => write!(
+                f,
+                "column {table}.{column} is {}, expected {}",
+                null(!expected_nullable),
+                null(*expected_nullable)
+            ),
+        }
+    }
+}
+
+/// Check the live schema of each of `databases` against its migrations.
+///
+/// Every difference is logged as a warning.
+///
+/// # Errors
+///
+/// Returns `SchemaCheckError::Drift` if any database differs from its
+/// migrations, or an error if a database can't be queried.
+pub async fn check(databases: &[Database]) -> Result<(), SchemaCheckError> {
+    dotenvy::dotenv().ok();
+
+    let mut total = 0;
+    for &database in databases {
+        let pool = database.connect().await?;
+        let expected = expected_schema(database);
+
+        let mut schemas: BTreeSet<String> = expected
+            .keys()
+            .filter_map(|table| table.split_once('.'))
+            .map(|(schema, _)| schema.to_string())
+            .collect();
+        schemas.insert(DEFAULT_SCHEMA.to_string());
+        let actual = live_schema(&pool, &schemas).await?;
+
+        let problems = compare(&expected, &actual);
+        for problem in &problems {
+            tracing::warn!(database = database.name(), "{problem}");
+        }
+        if problems.is_empty() {
+            tracing::info!(
+                "{} schema matches its migrations ({} tables)",
+                database.name(),
+                expected.len()
+            );
+        }
+        total += problems.len();
+    }
+
+    if total == 0 {
+        Ok(())
+    } else {
+        Err(SchemaCheckError::Drift(total))
+    }
+}
+
+/// The schema left by replaying every up migration of `database`.
+fn expected_schema(database: Database) -> Schema {
+    let mut schema = Schema::new();
+    for migration in database
+        .migrator()
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+    {
+        apply_migration(&mut schema, &migration.sql);
+    }
+    schema
+}
+
+/// Tables and columns in `schemas` of the live database.
+async fn live_schema(pool: &PgPool, schemas: &BTreeSet<String>) -> Result<Schema, sqlx::Error> {
+    let schemas: Vec<String> = schemas.iter().cloned().collect();
+
+    let tables = sqlx::query!(
+        r#"
+        SELECT table_schema::text AS "table_schema!", table_name::text AS "table_name!"
+        FROM information_schema.tables
+        WHERE table_type = 'BASE TABLE' AND table_schema::text = ANY($1::text[])
+        "#,
+        &schemas
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let columns = sqlx::query!(
+        r#"
+        SELECT table_schema::text AS "table_schema!", table_name::text AS "table_name!",
+               column_name::text AS "column_name!", udt_name::text AS "udt_name!",
+               is_nullable = 'YES' AS "nullable!"
+        FROM information_schema.columns
+        WHERE table_schema::text = ANY($1::text[])
+        "#,
+        &schemas
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut schema: Schema = tables
+        .into_iter()
+        .map(|row| {
+            (
+                format!("{}.{}", row.table_schema, row.table_name),
+                BTreeMap::new(),
+            )
+        })
+        .filter(|(table, _)| table != MIGRATIONS_TABLE)
+        .collect();
+
+    for row in columns {
+        // Views also have columns; only base tables were selected above
+        if let Some(columns) = schema.get_mut(&format!("{}.{}", row.table_schema, row.table_name)) {
+            columns.insert(
+                row.column_name,
+                Column {
+                    udt_name: row.udt_name,
+                    nullable: row.nullable,
+                },
+            );
+        }
+    }
+
+    Ok(schema)
+}
+
+/// List the differences between the migrations and the live schema.
+fn compare(expected: &Schema, actual: &Schema) -> Vec<Problem> {
+    let mut problems: Vec<Problem> = actual
+        .keys()
+        .filter(|table| !expected.contains_key(*table))
+        .map(|table| Problem::OrphanedTable(table.clone()))
+        .collect();
+
+    for (table, expected_columns) in expected {
+        let Some(actual_columns) = actual.get(table) else {
+            problems.push(Problem::MissingTable(table.clone()));
+            continue;
+        };
+
+        for column in actual_columns.keys() {
+            if !expected_columns.contains_key(column) {
+                problems.push(Problem::OrphanedColumn {
+                    table: table.clone(),
+                    column: column.clone(),
+                });
+            }
+        }
+
+        for (column, expected_column) in expected_columns {
+            let Some(actual_column) = actual_columns.get(column) else {
+                problems.push(Problem::MissingColumn {
+                    table: table.clone(),
+                    column: column.clone(),
+                });
+                continue;
+            };
+
+            if actual_column.udt_name != expected_column.udt_name {
+                problems.push(Problem::WrongType {
+                    table: table.clone(),
+                    column: column.clone(),
+                    expected: expected_column.udt_name.clone(),
+                    actual: actual_column.udt_name.clone(),
+                });
+            }
+            if actual_column.nullable != expected_column.nullable {
+                problems.push(Problem::WrongNullability {
+                    table: table.clone(),
+                    column: column.clone(),
+                    expected_nullable: expected_column.nullable,
+                });
+            }
+        }
+    }
+
+    problems
+}
+
+// =============================================================================
+// Migration SQL
+// =============================================================================
+
+/// Apply the table DDL of one migration to `schema`.
+///
+/// Unqualified table names resolve to the first schema of the migration's
+/// latest `SET search_path`.
+fn apply_migration(schema: &mut Schema, sql: &str) {
+    let mut search_schema = DEFAULT_SCHEMA.to_string();
+
+    for statement in statements(sql) {
+        if let Some(rest) = strip_keywords(&statement, "SET search_path TO")
+            .or_else(|| strip_keywords(&statement, "SET search_path ="))
+        {
+            if let Some(first) = split_top_level(rest).first() {
+                search_schema = identifier(first);
+            }
+        } else if let Some(rest) = strip_keywords(&statement, "CREATE TABLE")
+            .or_else(|| strip_keywords(&statement, "CREATE UNLOGGED TABLE"))
+        {
+            create_table(schema, &search_schema, rest);
+        } else if let Some(rest) = strip_keywords(&statement, "ALTER TABLE") {
+            alter_table(schema, &search_schema, rest);
+        } else if let Some(rest) = strip_keywords(&statement, "DROP TABLE") {
+            let rest = strip_keywords(rest, "IF EXISTS").unwrap_or(rest);
+            for name in split_top_level(rest) {
+                let (name, _) = split_word(name);
+                schema.remove(&table_name(name, &search_schema));
+            }
+        }
+    }
+}
+
+/// Apply the rest of a `CREATE TABLE` statement.
+fn create_table(schema: &mut Schema, search_schema: &str, rest: &str) {
+    let rest = strip_keywords(rest, "IF NOT EXISTS").unwrap_or(rest);
+    let (Some(open), Some(close)) = (rest.find('('), rest.rfind(')')) else {
+        return;
+    };
+    let (Some(name), Some(body)) = (rest.get(..open), rest.get(open + 1..close)) else {
+        return;
+    };
+
+    let table = table_name(name.trim(), search_schema);
+    let mut columns = BTreeMap::new();
+    let mut primary_key = Vec::new();
+
+    for element in split_top_level(body) {
+        if starts_with_keyword(element, TABLE_CONSTRAINTS) {
+            primary_key.extend(primary_key_columns(element));
+        } else if let Some((name, column)) = column_definition(element) {
+            columns.insert(name, column);
+        }
+    }
+
+    for name in primary_key {
+        if let Some(column) = columns.get_mut(&name) {
+            column.nullable = false;
+        }
+    }
+
+    // `IF NOT EXISTS` leaves an existing table as it was
+    schema.entry(table).or_insert(columns);
+}
+
+/// Apply the rest of an `ALTER TABLE` statement.
+fn alter_table(schema: &mut Schema, search_schema: &str, rest: &str) {
+    let rest = strip_keywords(rest, "IF EXISTS").unwrap_or(rest);
+    let rest = strip_keywords(rest, "ONLY").unwrap_or(rest);
+    let (name, actions) = split_word(rest);
+    let table = table_name(name, search_schema);
+
+    // Renaming the table is the only action of its statement
+    if let Some(new_name) = strip_keywords(actions, "RENAME TO") {
+        if let Some(columns) = schema.remove(&table) {
+            let (table_schema, _) = table.split_once('.').unwrap_or((search_schema, ""));
+            schema.insert(format!("{table_schema}.{}", identifier(new_name)), columns);
+        }
+        return;
+    }
+
+    let Some(columns) = schema.get_mut(&table) else {
+        return;
+    };
+    for action in split_top_level(actions) {
+        alter_table_action(columns, action);
+    }
+}
+
+/// Apply one action of an `ALTER TABLE` statement to a table's columns.
+fn alter_table_action(columns: &mut BTreeMap<String, Column>, action: &str) {
+    if let Some(rest) = strip_keywords(action, "ADD") {
+        if starts_with_keyword(rest, TABLE_CONSTRAINTS) {
+            for name in primary_key_columns(rest) {
+                if let Some(column) = columns.get_mut(&name) {
+                    column.nullable = false;
+                }
+            }
+            return;
+        }
+
+        let rest = strip_keywords(rest, "COLUMN").unwrap_or(rest);
+        let rest = strip_keywords(rest, "IF NOT EXISTS").unwrap_or(rest);
+        if let Some((name, column)) = column_definition(rest) {
+            columns.entry(name).or_insert(column);
+        }
+    } else if let Some(rest) = strip_keywords(action, "DROP") {
+        if strip_keywords(rest, "CONSTRAINT").is_some() {
+            return;
+        }
+        let rest = strip_keywords(rest, "COLUMN").unwrap_or(rest);
+        let rest = strip_keywords(rest, "IF EXISTS").unwrap_or(rest);
+        let (name, _) = split_word(rest);
+        columns.remove(&identifier(name));
+    } else if let Some(rest) = strip_keywords(action, "ALTER") {
+        let rest = strip_keywords(rest, "COLUMN").unwrap_or(rest);
+        let (name, change) = split_word(rest);
+        let Some(column) = columns.get_mut(&identifier(name)) else {
+            return;
+        };
+
+        if strip_keywords(change, "SET NOT NULL").is_some() {
+            column.nullable = false;
+        } else if strip_keywords(change, "DROP NOT NULL").is_some() {
+            column.nullable = true;
+        } else if let Some(sql_type) =
+            strip_keywords(change, "TYPE").or_else(|| strip_keywords(change, "SET DATA TYPE"))
+        {
+            let sql_type = sql_type
+                .split_once(" USING ")
+                .map_or(sql_type, |(sql_type, _)| sql_type);
+            column.udt_name = udt_name(sql_type);
+        }
+    } else if let Some(rest) = strip_keywords(action, "RENAME") {
+        if strip_keywords(rest, "CONSTRAINT").is_some() {
+            return;
+        }
+        let rest = strip_keywords(rest, "COLUMN").unwrap_or(rest);
+        let (old, rest) = split_word(rest);
+        if let Some(new) = strip_keywords(rest, "TO")
+            && let Some(column) = columns.remove(&identifier(old))
+        {
+            columns.insert(identifier(new), column);
+        }
+    }
+}
+
+/// Parse a column definition (`name type [constraints]`).
+fn column_definition(definition: &str) -> Option<(String, Column)> {
+    let (name, rest) = split_word(definition);
+    if name.is_empty() || rest.is_empty() {
+        return None;
+    }
+
+    let mut type_words = Vec::new();
+    for word in rest.split(' ') {
+        let keyword = word.split('(').next().unwrap_or(word);
+        if !type_words.is_empty() && is_keyword(keyword, COLUMN_CONSTRAINTS) {
+            break;
+        }
+        type_words.push(word);
+    }
+    let sql_type = type_words.join(" ");
+
+    // Constraints without their parenthesized parts, so e.g.
+    // `CHECK (x IS NOT NULL)` doesn't read as `NOT NULL`
+    let constraints = strip_parens(definition).to_uppercase();
+    let udt_name = udt_name(&sql_type);
+    let serial = strip_parens(&sql_type).to_uppercase().contains("SERIAL");
+    let nullable =
+        !(serial || constraints.contains(" NOT NULL") || constraints.contains(" PRIMARY KEY"));
+
+    Some((identifier(name), Column { udt_name, nullable }))
+}
+
+/// Columns of a `PRIMARY KEY (...)` table constraint, if it is one.
+fn primary_key_columns(constraint: &str) -> Vec<String> {
+    let upper = constraint.to_uppercase();
+    let Some(start) = upper.find("PRIMARY KEY") else {
+        return Vec::new();
+    };
+    let rest = constraint.get(start..).unwrap_or_default();
+    let (Some(open), Some(close)) = (rest.find('('), rest.find(')')) else {
+        return Vec::new();
+    };
+    rest.get(open + 1..close)
+        .map(|columns| {
+            split_top_level(columns)
+                .into_iter()
+                .map(identifier)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The `information_schema` `udt_name` of a SQL type.
+fn udt_name(sql_type: &str) -> String {
+    let sql_type = strip_parens(sql_type);
+    let sql_type = sql_type.split_whitespace().collect::<Vec<_>>().join(" ");
+    if let Some(element) = sql_type.strip_suffix("[]") {
+        return format!("_{}", udt_name(element));
+    }
+
+    let name = match sql_type.to_uppercase().as_str() {
+        "SERIAL" | "SERIAL4" | "INTEGER" | "INT" | "INT4" => "int4",
+        "BIGSERIAL" | "SERIAL8" | "BIGINT" | "INT8" => "int8",
+        "SMALLSERIAL" | "SERIAL2" | "SMALLINT" | "INT2" => "int2",
+        "BOOLEAN" | "BOOL" => "bool",
+        "VARCHAR" | "CHARACTER VARYING" => "varchar",
+        "CHAR" | "CHARACTER" | "BPCHAR" => "bpchar",
+        "TIMESTAMPTZ" | "TIMESTAMP WITH TIME ZONE" => "timestamptz",
+        "TIMESTAMP" | "TIMESTAMP WITHOUT TIME ZONE" => "timestamp",
+        "TIMETZ" | "TIME WITH TIME ZONE" => "timetz",
+        "TIME" | "TIME WITHOUT TIME ZONE" => "time",
+        "DECIMAL" | "NUMERIC" => "numeric",
+        "REAL" | "FLOAT4" => "float4",
+        "DOUBLE PRECISION" | "FLOAT" | "FLOAT8" => "float8",
+        // Extension and user-defined types (citext, vector, enums) are
+        // reported without their schema
+        _ => {
+            let name = sql_type.rsplit('.').next().unwrap_or(sql_type.as_str());
+            return identifier(name);
+        }
+    };
+    name.to_string()
+}
+
+/// Split SQL into statements with comments removed and whitespace collapsed.
+///
+/// Semicolons inside string literals and dollar-quoted bodies (functions,
+/// `DO` blocks) don't end a statement.
+fn statements(sql: &str) -> Vec<String> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut i = 0;
+
+    while let Some(&c) = chars.get(i) {
+        let next = chars.get(i + 1).copied();
+        let end = match (c, next) {
+            ('-', Some('-')) => {
+                current.push(' ');
+                i = find_end(&chars, i, &['\n']);
+                continue;
+            }
+            ('/', Some('*')) => {
+                current.push(' ');
+                i = find_end(&chars, i + 2, &['*', '/']);
+                continue;
+            }
+            ('\'', _) => find_end(&chars, i + 1, &['\'']),
+            ('$', _) => dollar_quote_end(&chars, i).unwrap_or(i + 1),
+            (';', _) => {
+                let statement = current.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !statement.is_empty() {
+                    statements.push(statement);
+                }
+                current.clear();
+                i += 1;
+                continue;
+            }
+            _ => i + 1,
+        };
+        current.extend(chars.get(i..end).unwrap_or_default());
+        i = end;
+    }
+
+    let statement = current.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !statement.is_empty() {
+        statements.push(statement);
+    }
+    statements
+}
+
+/// End of the dollar-quoted string (`$tag$...$tag$`) starting at `start`.
+fn dollar_quote_end(chars: &[char], start: usize) -> Option<usize> {
+    let close = (start + 1..chars.len())
+        .take_while(|&i| {
+            chars
+                .get(i)
+                .is_some_and(|&c| c == '$' || c.is_alphanumeric() || c == '_')
+        })
+        .find(|&i| chars.get(i) == Some(&'$'))?;
+    let tag = chars.get(start..=close)?;
+    if tag.get(1).is_some_and(char::is_ascii_digit) {
+        // Positional parameter ($1), not a quote
+        return None;
+    }
+    Some(find_end(chars, close + 1, tag))
+}
+
+/// Index just past the first `pattern` at or after `from`, or the end.
+fn find_end(chars: &[char], from: usize, pattern: &[char]) -> usize {
+    (from..chars.len())
+        .find(|&i| chars.get(i..i + pattern.len()) == Some(pattern))
+        .map_or(chars.len(), |i| i + pattern.len())
+}
+
+/// Split on commas outside parentheses and string literals.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0_usize;
+    let mut in_string = false;
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        match c {
+            '\'' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => depth = depth.saturating_sub(1),
+            ',' if !in_string && depth == 0 => {
+                parts.push(text.get(start..i).unwrap_or_default().trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text.get(start..).unwrap_or_default().trim());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Remove parenthesized text, including nested parentheses.
+fn strip_parens(text: &str) -> String {
+    let mut depth = 0_usize;
+    text.chars()
+        .filter(|&c| match c {
+            '(' => {
+                depth += 1;
+                false
+            }
+            ')' => {
+                depth = depth.saturating_sub(1);
+                false
+            }
+            _ => depth == 0,
+        })
+        .collect()
+}
+
+/// Strip leading `keywords` (case-insensitive, single-spaced) from `text`.
+fn strip_keywords<'a>(text: &'a str, keywords: &str) -> Option<&'a str> {
+    let text = text.trim_start();
+    let head = text.get(..keywords.len())?;
+    let rest = text.get(keywords.len()..)?;
+    let at_boundary = !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_');
+    (head.eq_ignore_ascii_case(keywords) && at_boundary).then(|| rest.trim_start())
+}
+
+/// Split off the first word of `text`.
+fn split_word(text: &str) -> (&str, &str) {
+    text.trim().split_once(' ').map_or_else(
+        || (text.trim(), ""),
+        |(word, rest)| (word, rest.trim_start()),
+    )
+}
+
+/// Whether the first word of `text` (up to any parenthesis) is one of
+/// `keywords`.
+fn starts_with_keyword(text: &str, keywords: &[&str]) -> bool {
+    let (word, _) = split_word(text);
+    is_keyword(word.split('(').next().unwrap_or(word), keywords)
+}
+
+/// Whether `word` is one of `keywords` (case-insensitive).
+fn is_keyword(word: &str, keywords: &[&str]) -> bool {
+    keywords.iter().any(|k| k.eq_ignore_ascii_case(word))
+}
+
+/// Normalize an identifier: quoted identifiers keep their case, others are
+/// folded to lowercase.
+fn identifier(name: &str) -> String {
+    let name = name.trim();
+    name.strip_prefix('"')
+        .and_then(|n| n.strip_suffix('"'))
+        .map_or_else(|| name.to_lowercase(), ToString::to_string)
+}
+
+/// Qualify a table name with `search_schema` unless it has a schema.
+fn table_name(name: &str, search_schema: &str) -> String {
+    match name.split_once('.') {
+        Some((schema, table)) => format!("{}.{}", identifier(schema), identifier(table)),
+        None => format!("{search_schema}.{}", identifier(name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(udt_name: &str, nullable: bool) -> Column {
+        Column {
+            udt_name: udt_name.to_string(),
+            nullable,
+        }
+    }
+
+    fn migrated(sql: &str) -> Schema {
+        let mut schema = Schema::new();
+        apply_migration(&mut schema, sql);
+        schema
+    }
+
+    #[test]
+    fn test_expected_admin_schema_has_reconciliation_counts() {
+        let schema = expected_schema(Database::Admin);
+        let counts = schema
+            .get("admin.reconciliation_counts")
+            .expect("reconciliation_counts table");
+
+        let expected = BTreeMap::from([
+            ("session_id".to_string(), column("uuid", false)),
+            ("sku".to_string(), column("varchar", false)),
+            ("inventory_item_id".to_string(), column("varchar", false)),
+            ("system_quantity".to_string(), column("int4", false)),
+            ("counted_quantity".to_string(), column("int4", true)),
+            ("delta".to_string(), column("int4", true)),
+            ("counted_at".to_string(), column("timestamptz", true)),
+            ("applied_at".to_string(), column("timestamptz", true)),
+        ]);
+        assert_eq!(counts, &expected);
+    }
+
+    #[test]
+    fn test_create_table_with_search_path_and_primary_key() {
+        let schema = migrated(
+            "SET search_path TO shop, public;
+             CREATE TABLE IF NOT EXISTS \"Orders\" (
+                 id BIGINT,
+                 tags TEXT[] DEFAULT '{}',
+                 total NUMERIC(10, 2) CHECK (total IS NOT NULL),
+                 placed_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                 PRIMARY KEY (id)
+             );",
+        );
+
+        let orders = schema.get("shop.Orders").expect("shop.Orders table");
+        assert_eq!(orders.get("id"), Some(&column("int8", false)));
+        assert_eq!(orders.get("tags"), Some(&column("_text", true)));
+        assert_eq!(orders.get("total"), Some(&column("numeric", true)));
+        assert_eq!(orders.get("placed_at"), Some(&column("timestamptz", false)));
+    }
+
+    #[test]
+    fn test_alter_table_columns() {
+        let schema = migrated(
+            "CREATE TABLE admin.item (id SERIAL PRIMARY KEY, name TEXT, old_flag BOOLEAN);
+             ALTER TABLE admin.item ADD COLUMN IF NOT EXISTS sku VARCHAR(64) NOT NULL,
+                 ADD notes TEXT;
+             ALTER TABLE admin.item DROP COLUMN IF EXISTS old_flag;
+             ALTER TABLE admin.item ALTER COLUMN name SET NOT NULL;
+             ALTER TABLE admin.item ALTER COLUMN notes TYPE VARCHAR(500) USING notes::varchar;
+             ALTER TABLE admin.item RENAME COLUMN notes TO note;",
+        );
+
+        let item = schema.get("admin.item").expect("admin.item table");
+        let expected = BTreeMap::from([
+            ("id".to_string(), column("int4", false)),
+            ("name".to_string(), column("text", false)),
+            ("sku".to_string(), column("varchar", false)),
+            ("note".to_string(), column("varchar", true)),
+        ]);
+        assert_eq!(item, &expected);
+    }
+
+    #[test]
+    fn test_alter_and_drop_table() {
+        let schema = migrated(
+            "CREATE TABLE a (id INT);
+             CREATE TABLE b (id INT);
+             ALTER TABLE a ALTER COLUMN id DROP NOT NULL;
+             ALTER TABLE ONLY a ADD CONSTRAINT a_pkey PRIMARY KEY (id);
+             ALTER TABLE b RENAME TO c;
+             DROP TABLE IF EXISTS missing, public.c CASCADE;",
+        );
+
+        assert_eq!(schema.keys().collect::<Vec<_>>(), ["public.a"]);
+        assert_eq!(
+            schema.get("public.a").and_then(|a| a.get("id")),
+            Some(&column("int4", false))
+        );
+    }
+
+    #[test]
+    fn test_statements_keep_dollar_quoted_bodies() {
+        let sql = "-- a comment; not a statement
+            CREATE FUNCTION touch() RETURNS trigger AS $$
+            BEGIN
+                NEW.updated_at = NOW();
+                RETURN NEW;
+            END;
+            $$ LANGUAGE plpgsql;
+            /* block; comment */
+            DO $body$ BEGIN PERFORM 1; END $body$;
+            INSERT INTO t (a) VALUES ('x;y'), ($1)";
+
+        let statements = statements(sql);
+        assert_eq!(statements.len(), 3, "{statements:?}");
+        assert!(statements.iter().all(|s| !s.contains("comment")));
+        assert_eq!(
+            statements.first().map(String::as_str),
+            Some(
+                "CREATE FUNCTION touch() RETURNS trigger AS $$ BEGIN NEW.updated_at = NOW(); \
+                 RETURN NEW; END; $$ LANGUAGE plpgsql"
+            )
+        );
+        assert_eq!(
+            statements.get(1).map(String::as_str),
+            Some("DO $body$ BEGIN PERFORM 1; END $body$")
+        );
+        assert_eq!(
+            statements.get(2).map(String::as_str),
+            Some("INSERT INTO t (a) VALUES ('x;y'), ($1)")
+        );
+    }
+
+    #[test]
+    fn test_split_top_level() {
+        assert_eq!(
+            split_top_level("a NUMERIC(10, 2), b TEXT DEFAULT 'x,y', , c INT"),
+            ["a NUMERIC(10, 2)", "b TEXT DEFAULT 'x,y'", "c INT"]
+        );
+        assert!(split_top_level("  ").is_empty());
+    }
+
+    #[test]
+    fn test_udt_name() {
+        assert_eq!(udt_name("INTEGER"), "int4");
+        assert_eq!(udt_name("bigserial"), "int8");
+        assert_eq!(udt_name("VARCHAR(255)"), "varchar");
+        assert_eq!(udt_name("character  varying(20)"), "varchar");
+        assert_eq!(udt_name("TIMESTAMP WITHOUT TIME ZONE"), "timestamp");
+        assert_eq!(udt_name("NUMERIC(12, 4)"), "numeric");
+        assert_eq!(udt_name("TEXT[]"), "_text");
+        assert_eq!(udt_name("INT[]"), "_int4");
+        assert_eq!(udt_name("public.CITEXT"), "citext");
+        assert_eq!(udt_name("vector(1536)"), "vector");
+    }
+
+    #[test]
+    fn test_column_definition() {
+        assert_eq!(
+            column_definition("email CITEXT NOT NULL UNIQUE"),
+            Some(("email".to_string(), column("citext", false)))
+        );
+        assert_eq!(
+            column_definition("\"Total\" DOUBLE PRECISION DEFAULT 0"),
+            Some(("Total".to_string(), column("float8", true)))
+        );
+        assert_eq!(
+            column_definition("id UUID PRIMARY KEY DEFAULT gen_random_uuid()"),
+            Some(("id".to_string(), column("uuid", false)))
+        );
+        assert_eq!(
+            column_definition("qty INTEGER CHECK (qty IS NOT NULL)"),
+            Some(("qty".to_string(), column("int4", true)))
+        );
+        assert_eq!(column_definition("lonely"), None);
+    }
+}
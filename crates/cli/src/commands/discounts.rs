@@ -0,0 +1,185 @@
+//! Discount commands.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # Export all active discounts to CSV
+//! np-cli discounts export --format csv --output discounts.csv --status active
+//!
+//! # Export every discount, with all of its codes, to JSON
+//! np-cli discounts export --format json --output discounts.json
+//! ```
+//!
+//! # Environment Variables
+//!
+//! - `ADMIN_DATABASE_URL` - `PostgreSQL` connection string (for the stored Shopify token)
+//! - `SHOPIFY_STORE`, `SHOPIFY_ADMIN_CLIENT_ID`, `SHOPIFY_ADMIN_CLIENT_SECRET` - Shopify Admin API
+
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use thiserror::Error;
+
+use naked_pineapple_admin::shopify::{
+    AdminShopifyError, CursorPaginator, DiscountCode, DiscountType, DiscountValue,
+};
+
+use super::shopify::{self, ConnectError};
+use crate::csv::escape_field;
+
+/// Discounts fetched per Admin API request.
+const PAGE_SIZE: i64 = 250;
+
+/// Errors that can occur during discount operations.
+#[derive(Debug, Error)]
+pub enum DiscountError {
+    /// Failed to create the Shopify client.
+    #[error(transparent)]
+    Connect(#[from] ConnectError),
+
+    /// Shopify API error.
+    #[error("Shopify API error: {0}")]
+    Shopify(#[from] AdminShopifyError),
+
+    /// JSON serialization error.
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Failed to write the export file.
+    #[error("Failed to write {0}: {1}")]
+    Write(String, std::io::Error),
+}
+
+/// Export file format.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    /// One row per discount.
+    Csv,
+    /// Array of full discount records, with all codes.
+    Json,
+}
+
+/// Discount status filter.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum StatusFilter {
+    /// Currently active discounts only.
+    Active,
+    /// Expired discounts only.
+    Expired,
+    /// All discounts, including scheduled ones.
+    All,
+}
+
+/// Options for `discounts export`.
+#[derive(Debug)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    pub output: String,
+    pub status: StatusFilter,
+}
+
+/// A discount in the export, with every redeem code.
+#[derive(Debug, Serialize)]
+struct DiscountRecord {
+    #[serde(flatten)]
+    discount: DiscountCode,
+    codes: Vec<String>,
+}
+
+/// Export code discounts and their usage to a CSV or JSON file.
+///
+/// # Errors
+///
+/// Returns an error if configuration is missing, the store isn't connected,
+/// a Shopify request fails, or the file can't be written.
+pub async fn export(options: &ExportOptions) -> Result<(), DiscountError> {
+    dotenvy::dotenv().ok();
+
+    let client = shopify::connect().await?;
+    let query = build_query(options.status);
+
+    tracing::info!(query = ?query, "Fetching discounts...");
+    let discounts = CursorPaginator::new(|cursor| {
+        Box::pin(client.get_discounts(PAGE_SIZE, cursor, query.clone()))
+    })
+    .collect_all()
+    .await?;
+    tracing::info!("Fetched {} discounts", discounts.len());
+
+    tracing::info!("Fetching discount codes...");
+    let mut records = Vec::with_capacity(discounts.len());
+    for discount in discounts {
+        let codes = client.get_all_discount_codes_for_node(&discount.id).await?;
+        records.push(DiscountRecord { discount, codes });
+    }
+
+    let content = match options.format {
+        ExportFormat::Csv => to_csv(&records),
+        ExportFormat::Json => serde_json::to_string_pretty(&records)?,
+    };
+
+    tokio::fs::write(Path::new(&options.output), content)
+        .await
+        .map_err(|e| DiscountError::Write(options.output.clone(), e))?;
+
+    tracing::info!("Exported {} discounts to {}", records.len(), options.output);
+    Ok(())
+}
+
+/// Build the Shopify search query for the status filter.
+fn build_query(status: StatusFilter) -> Option<String> {
+    match status {
+        StatusFilter::Active => Some("status:active".to_string()),
+        StatusFilter::Expired => Some("status:expired".to_string()),
+        StatusFilter::All => None,
+    }
+}
+
+/// Report type of a discount: `percentage`, `fixed`, `free-shipping` or `bxgy`.
+const fn type_label(discount: &DiscountCode) -> &'static str {
+    match (discount.discount_type, &discount.value) {
+        (DiscountType::FreeShipping, _) => "free-shipping",
+        (DiscountType::BuyXGetY, _) => "bxgy",
+        (DiscountType::Basic, Some(DiscountValue::FixedAmount { .. })) => "fixed",
+        (DiscountType::Basic, _) => "percentage",
+    }
+}
+
+/// Render discount records as CSV.
+///
+/// The `code` column holds the first code; every code is in the JSON export.
+fn to_csv(records: &[DiscountRecord]) -> String {
+    let mut csv = String::from(
+        "discount_id,title,code,type,value,starts_at,ends_at,status,usage_limit,usage_count\n",
+    );
+
+    for record in records {
+        let discount = &record.discount;
+        let code = record.codes.first().unwrap_or(&discount.code).clone();
+        let fields = [
+            discount.id.clone(),
+            discount.title.clone(),
+            code,
+            type_label(discount).to_string(),
+            discount
+                .value
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            discount.starts_at.clone().unwrap_or_default(),
+            discount.ends_at.clone().unwrap_or_default(),
+            discount.status.to_string(),
+            discount
+                .usage_limit
+                .map(|limit| limit.to_string())
+                .unwrap_or_default(),
+            discount.usage_count.to_string(),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| escape_field(f)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
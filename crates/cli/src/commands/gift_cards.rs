@@ -0,0 +1,286 @@
+//! Gift card commands.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # Export all active gift cards to CSV
+//! np-cli gift-cards export --format csv --output gift_cards.csv --status active
+//!
+//! # Export cards issued since the start of 2024, with transaction history
+//! np-cli gift-cards export --format json --output ledger.json --issued-after 2024-01-01 --include-transactions
+//!
+//! # List gift cards due an expiry reminder without sending anything
+//! np-cli gift-cards send-reminders --dry-run
+//! ```
+//!
+//! # Environment Variables
+//!
+//! - `ADMIN_DATABASE_URL` - `PostgreSQL` connection string (for the stored Shopify token)
+//! - `SHOPIFY_STORE`, `SHOPIFY_ADMIN_CLIENT_ID`, `SHOPIFY_ADMIN_CLIENT_SECRET` - Shopify Admin API
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use secrecy::SecretString;
+use serde::Serialize;
+use thiserror::Error;
+
+use naked_pineapple_admin::db;
+use naked_pineapple_admin::services::{GiftCardExpiryReminderService, GiftCardReminderError};
+use naked_pineapple_admin::shopify::{
+    AdminShopifyError, CursorPaginator, GiftCard, GiftCardSortKey, GiftCardTransaction,
+};
+
+use super::shopify::{self, ConnectError};
+use crate::csv::escape_field;
+
+/// Gift cards fetched per Admin API request.
+const PAGE_SIZE: i64 = 250;
+
+/// Errors that can occur during gift card operations.
+#[derive(Debug, Error)]
+pub enum GiftCardError {
+    /// Failed to create the Shopify client.
+    #[error(transparent)]
+    Connect(#[from] ConnectError),
+
+    /// Shopify API error.
+    #[error("Shopify API error: {0}")]
+    Shopify(#[from] AdminShopifyError),
+
+    /// Database connection error.
+    #[error("Database connection error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    /// Expiry reminder error.
+    #[error(transparent)]
+    Reminder(#[from] GiftCardReminderError),
+
+    /// JSON serialization error.
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Failed to write the export file.
+    #[error("Failed to write {0}: {1}")]
+    Write(String, std::io::Error),
+}
+
+/// Export file format.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    /// One row per gift card.
+    Csv,
+    /// Array of full gift card records.
+    Json,
+}
+
+/// Gift card status filter.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum StatusFilter {
+    /// Enabled gift cards only.
+    Active,
+    /// Disabled gift cards only.
+    Disabled,
+    /// All gift cards.
+    All,
+}
+
+/// Options for `gift-cards export`.
+#[derive(Debug)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    pub output: String,
+    pub status: StatusFilter,
+    pub issued_after: Option<NaiveDate>,
+    pub include_transactions: bool,
+}
+
+/// A gift card in the export, optionally with its transaction history.
+#[derive(Debug, Serialize)]
+struct GiftCardRecord {
+    #[serde(flatten)]
+    gift_card: GiftCard,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transactions: Option<Vec<GiftCardTransaction>>,
+}
+
+/// Export the gift card ledger to a CSV or JSON file.
+///
+/// # Errors
+///
+/// Returns an error if configuration is missing, the store isn't connected,
+/// a Shopify request fails, or the file can't be written.
+pub async fn export(options: &ExportOptions) -> Result<(), GiftCardError> {
+    dotenvy::dotenv().ok();
+
+    let client = shopify::connect().await?;
+    let query = build_query(options.status, options.issued_after);
+
+    tracing::info!(query = ?query, "Fetching gift cards...");
+    let mut records: Vec<GiftCardRecord> = CursorPaginator::new(|cursor| {
+        Box::pin(client.get_gift_cards(
+            PAGE_SIZE,
+            cursor,
+            query.clone(),
+            Some(GiftCardSortKey::CreatedAt),
+            false,
+        ))
+    })
+    .collect_all()
+    .await?
+    .into_iter()
+    .map(|gift_card| GiftCardRecord {
+        gift_card,
+        transactions: None,
+    })
+    .collect();
+    tracing::info!("Fetched {} gift cards", records.len());
+
+    if options.include_transactions {
+        tracing::info!("Fetching transaction history...");
+        for record in &mut records {
+            let detail = client.get_gift_card_detail(&record.gift_card.id).await?;
+            record.transactions = Some(detail.transactions);
+        }
+    }
+
+    let content = match options.format {
+        ExportFormat::Csv => to_csv(&records, options.include_transactions),
+        ExportFormat::Json => serde_json::to_string_pretty(&records)?,
+    };
+
+    tokio::fs::write(Path::new(&options.output), content)
+        .await
+        .map_err(|e| GiftCardError::Write(options.output.clone(), e))?;
+
+    tracing::info!(
+        "Exported {} gift cards to {}",
+        records.len(),
+        options.output
+    );
+    Ok(())
+}
+
+/// Send expiry reminders for gift cards expiring in about 30 days, as the
+/// admin's daily task does. With `dry_run`, only list the cards that are due.
+///
+/// # Errors
+///
+/// Returns an error if configuration is missing, the store isn't connected,
+/// a Shopify request fails, or the database is unreachable.
+pub async fn send_reminders(dry_run: bool) -> Result<(), GiftCardError> {
+    dotenvy::dotenv().ok();
+
+    let client = shopify::connect().await?;
+    let database_url = std::env::var("ADMIN_DATABASE_URL")
+        .map(SecretString::from)
+        .map_err(|_| ConnectError::MissingEnvVar("ADMIN_DATABASE_URL"))?;
+    let pool = db::create_pool(&database_url).await?;
+    let service = GiftCardExpiryReminderService::new(pool, client);
+
+    if dry_run {
+        let due = service.due_reminders().await?;
+        for reminder in &due {
+            tracing::info!(
+                "Would remind {} about ****{} ({} {}), expiring {}",
+                reminder.customer_email,
+                reminder.last_characters,
+                reminder.balance,
+                reminder.currency_code,
+                reminder.expires_on
+            );
+        }
+        tracing::info!("{} gift cards due a reminder (dry run)", due.len());
+        return Ok(());
+    }
+
+    let result = service.run_once().await?;
+    for (gift_card_id, error) in &result.failed {
+        tracing::error!("Failed to remind {gift_card_id}: {error}");
+    }
+    tracing::info!(
+        "Sent {} reminders, {} failed",
+        result.sent,
+        result.failed.len()
+    );
+    Ok(())
+}
+
+/// Build the Shopify search query for the status and date filters.
+fn build_query(status: StatusFilter, issued_after: Option<NaiveDate>) -> Option<String> {
+    let mut parts = Vec::new();
+    match status {
+        StatusFilter::Active => parts.push("status:enabled".to_string()),
+        StatusFilter::Disabled => parts.push("status:disabled".to_string()),
+        StatusFilter::All => {}
+    }
+    if let Some(date) = issued_after {
+        parts.push(format!("created_at:>={}", date.format("%Y-%m-%d")));
+    }
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" AND "))
+    }
+}
+
+/// Render gift card records as CSV.
+///
+/// With transactions, an extra column lists each event as
+/// `processed_at credit|debit amount`, separated by semicolons.
+fn to_csv(records: &[GiftCardRecord], include_transactions: bool) -> String {
+    let mut csv = String::from(
+        "gift_card_id,masked_code,initial_value,current_balance,currency,expires_on,\
+         customer_email,customer_name,created_at,deactivated_at,order_name,note",
+    );
+    if include_transactions {
+        csv.push_str(",transactions");
+    }
+    csv.push('\n');
+
+    for record in records {
+        let card = &record.gift_card;
+        let fields = [
+            card.id.clone(),
+            card.masked_code
+                .clone()
+                .unwrap_or_else(|| format!("****{}", card.last_characters)),
+            card.initial_value.amount.clone(),
+            card.balance.amount.clone(),
+            card.balance.currency_code.clone(),
+            card.expires_on.clone().unwrap_or_default(),
+            card.customer_email.clone().unwrap_or_default(),
+            card.customer_name.clone().unwrap_or_default(),
+            card.created_at.clone(),
+            card.deactivated_at.clone().unwrap_or_default(),
+            card.order_name.clone().unwrap_or_default(),
+            card.note.clone().unwrap_or_default(),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| escape_field(f)).collect();
+        csv.push_str(&row.join(","));
+
+        if include_transactions {
+            let history = record
+                .transactions
+                .iter()
+                .flatten()
+                .map(|tx| {
+                    format!(
+                        "{} {} {}",
+                        tx.processed_at,
+                        if tx.is_credit { "credit" } else { "debit" },
+                        tx.amount.amount
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+            let _ = write!(csv, ",{}", escape_field(&history));
+        }
+        csv.push('\n');
+    }
+
+    csv
+}
@@ -0,0 +1,150 @@
+//! Inventory commands.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # Rename SKUs from a CSV mapping (columns: old_sku,new_sku)
+//! np-cli inventory update-skus --file sku_map.csv
+//! ```
+//!
+//! # Environment Variables
+//!
+//! - `ADMIN_DATABASE_URL` - `PostgreSQL` connection string (for the stored Shopify token)
+//! - `SHOPIFY_STORE`, `SHOPIFY_ADMIN_CLIENT_ID`, `SHOPIFY_ADMIN_CLIENT_SECRET` - Shopify Admin API
+
+use thiserror::Error;
+
+use naked_pineapple_admin::shopify::{AdminClient, AdminShopifyError, SkuUpdate};
+use naked_pineapple_core::VariantId;
+
+use super::shopify::{self, ConnectError};
+
+/// Errors that can occur during inventory operations.
+#[derive(Debug, Error)]
+pub enum InventoryError {
+    /// Failed to create the Shopify client.
+    #[error(transparent)]
+    Connect(#[from] ConnectError),
+
+    /// Shopify API error.
+    #[error("Shopify API error: {0}")]
+    Shopify(#[from] AdminShopifyError),
+
+    /// Failed to read the mapping file.
+    #[error("Failed to read {0}: {1}")]
+    Read(String, std::io::Error),
+
+    /// The mapping file is malformed.
+    #[error("Invalid SKU mapping on line {0}: {1}")]
+    InvalidMapping(usize, String),
+
+    /// Some SKUs could not be updated.
+    #[error("{0} SKU updates failed")]
+    PartialFailure(usize),
+}
+
+/// A row from the SKU mapping file.
+#[derive(Debug)]
+struct SkuMapping {
+    old_sku: String,
+    new_sku: String,
+}
+
+/// Rename SKUs using a CSV file with `old_sku,new_sku` columns.
+///
+/// Each old SKU is resolved to its variant, then all variants are updated
+/// with [`AdminClient::bulk_update_skus`].
+///
+/// # Errors
+///
+/// Returns an error if the file is invalid, an old SKU doesn't match exactly
+/// one variant, or any update fails.
+pub async fn update_skus(file: &str) -> Result<(), InventoryError> {
+    dotenvy::dotenv().ok();
+
+    let content = tokio::fs::read_to_string(file)
+        .await
+        .map_err(|e| InventoryError::Read(file.to_string(), e))?;
+    let mappings = parse_mappings(&content)?;
+    tracing::info!("Loaded {} SKU mappings from {file}", mappings.len());
+
+    let client = shopify::connect().await?;
+
+    let mut updates = Vec::with_capacity(mappings.len());
+    for mapping in mappings {
+        let variant_id = find_variant_by_sku(&client, &mapping.old_sku).await?;
+        updates.push(SkuUpdate {
+            variant_id,
+            new_sku: mapping.new_sku,
+        });
+    }
+
+    let result = client.bulk_update_skus(updates).await?;
+    tracing::info!("Updated {} SKUs", result.updated.len());
+
+    for failure in &result.failed {
+        tracing::error!(variant_id = %failure.variant_id, "SKU update failed: {}", failure.error);
+    }
+    if !result.failed.is_empty() {
+        return Err(InventoryError::PartialFailure(result.failed.len()));
+    }
+
+    Ok(())
+}
+
+/// Parse the `old_sku,new_sku` mapping file.
+fn parse_mappings(content: &str) -> Result<Vec<SkuMapping>, InventoryError> {
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    match lines.next() {
+        Some((_, header)) if header.replace(' ', "") == "old_sku,new_sku" => {}
+        Some((line_no, _)) => {
+            return Err(InventoryError::InvalidMapping(
+                line_no,
+                "expected header `old_sku,new_sku`".to_string(),
+            ));
+        }
+        None => return Ok(Vec::new()),
+    }
+
+    lines
+        .map(|(line_no, line)| {
+            let (old_sku, new_sku) = line
+                .split_once(',')
+                .map(|(old, new)| (old.trim(), new.trim()))
+                .filter(|(old, new)| !old.is_empty() && !new.is_empty() && !new.contains(','))
+                .ok_or_else(|| {
+                    InventoryError::InvalidMapping(line_no, "expected two columns".to_string())
+                })?;
+            Ok(SkuMapping {
+                old_sku: old_sku.to_string(),
+                new_sku: new_sku.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Find the variant ID for an exact SKU match.
+async fn find_variant_by_sku(client: &AdminClient, sku: &str) -> Result<VariantId, InventoryError> {
+    let query = format!("sku:\"{}\"", sku.replace('"', "\\\""));
+    let items = client.get_inventory_items(2, None, Some(query)).await?;
+
+    let mut variant_ids = items
+        .items
+        .into_iter()
+        .filter(|item| item.sku.as_deref() == Some(sku))
+        .filter_map(|item| item.variant.map(|v| v.id));
+
+    match (variant_ids.next(), variant_ids.next()) {
+        (Some(variant_id), None) => Ok(variant_id),
+        (None, _) => Err(AdminShopifyError::NotFound(format!("SKU {sku}")).into()),
+        (Some(_), Some(_)) => Err(AdminShopifyError::UserError(format!(
+            "SKU {sku} matches more than one variant"
+        ))
+        .into()),
+    }
+}
@@ -17,6 +17,10 @@
 //!
 //! # Rollback multiple migrations
 //! np-cli migrate rollback storefront --count 3
+//!
+//! # Preview pending migration SQL without running it
+//! np-cli migrate dry-run all
+//! np-cli migrate dry-run admin --json
 //! ```
 //!
 //! # Environment Variables
@@ -29,9 +33,27 @@
 //! Storefront migrations: `crates/storefront/migrations/`
 //! Admin migrations: `crates/admin/migrations/`
 
+use std::collections::HashSet;
+use std::io::{IsTerminal, Write};
+
+use serde::Serialize;
 use sqlx::PgPool;
+use sqlx::migrate::Migrator;
 use thiserror::Error;
 
+/// Embedded storefront migrations.
+static STOREFRONT_MIGRATOR: Migrator = sqlx::migrate!("../storefront/migrations");
+
+/// Embedded admin migrations.
+static ADMIN_MIGRATOR: Migrator = sqlx::migrate!("../admin/migrations");
+
+/// ANSI color for applied migrations.
+const GREEN: &str = "\x1b[32m";
+/// ANSI color for pending migrations.
+const YELLOW: &str = "\x1b[33m";
+/// ANSI reset.
+const RESET: &str = "\x1b[0m";
+
 /// Errors that can occur during migration.
 #[derive(Debug, Error)]
 pub enum MigrationError {
@@ -46,6 +68,75 @@ pub enum MigrationError {
     /// Migration execution error.
     #[error("Migration error: {0}")]
     Migration(#[from] sqlx::migrate::MigrateError),
+
+    /// Failed to write the dry-run output.
+    #[error("Failed to write output: {0}")]
+    Output(#[from] std::io::Error),
+
+    /// Failed to serialize the dry-run output.
+    #[error("Failed to serialize output: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A database with its own set of migrations.
+#[derive(Debug, Clone, Copy)]
+pub enum Database {
+    /// Storefront database.
+    Storefront,
+    /// Admin database.
+    Admin,
+}
+
+impl Database {
+    /// Name used in logs and output.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Storefront => "storefront",
+            Self::Admin => "admin",
+        }
+    }
+
+    const fn env_var(self) -> &'static str {
+        match self {
+            Self::Storefront => "STOREFRONT_DATABASE_URL",
+            Self::Admin => "ADMIN_DATABASE_URL",
+        }
+    }
+
+    /// The database's embedded migrations.
+    #[must_use]
+    pub const fn migrator(self) -> &'static Migrator {
+        match self {
+            Self::Storefront => &STOREFRONT_MIGRATOR,
+            Self::Admin => &ADMIN_MIGRATOR,
+        }
+    }
+
+    /// Connect using the database's URL variable (or `DATABASE_URL` fallback).
+    pub async fn connect(self) -> Result<PgPool, MigrationError> {
+        let database_url = std::env::var(self.env_var())
+            .or_else(|_| std::env::var("DATABASE_URL"))
+            .map_err(|_| MigrationError::MissingEnvVar(self.env_var()))?;
+
+        tracing::info!("Connecting to {} database...", self.name());
+        Ok(PgPool::connect(&database_url).await?)
+    }
+}
+
+/// A migration as shown by a dry run.
+#[derive(Debug, Serialize)]
+pub struct MigrationPreview {
+    /// Database the migration belongs to.
+    pub database: &'static str,
+    /// Migration version (timestamp prefix of the file name).
+    pub version: i64,
+    /// Migration description (rest of the file name).
+    pub description: String,
+    /// Full migration SQL.
+    pub sql: String,
+    /// Whether the migration has already been applied.
+    pub applied: bool,
 }
 
 /// Run storefront database migrations.
@@ -63,9 +154,7 @@ pub async fn storefront() -> Result<(), MigrationError> {
     let pool = PgPool::connect(&database_url).await?;
 
     tracing::info!("Running storefront migrations...");
-    sqlx::migrate!("../storefront/migrations")
-        .run(&pool)
-        .await?;
+    STOREFRONT_MIGRATOR.run(&pool).await?;
 
     tracing::info!("Storefront migrations complete!");
     Ok(())
@@ -86,7 +175,7 @@ pub async fn admin() -> Result<(), MigrationError> {
     let pool = PgPool::connect(&database_url).await?;
 
     tracing::info!("Running admin migrations...");
-    sqlx::migrate!("../admin/migrations").run(&pool).await?;
+    ADMIN_MIGRATOR.run(&pool).await?;
 
     tracing::info!("Admin migrations complete!");
     Ok(())
@@ -107,9 +196,7 @@ pub async fn rollback_storefront(count: i64) -> Result<(), MigrationError> {
     let pool = PgPool::connect(&database_url).await?;
 
     tracing::info!("Rolling back {count} storefront migration(s)...");
-    sqlx::migrate!("../storefront/migrations")
-        .undo(&pool, count)
-        .await?;
+    STOREFRONT_MIGRATOR.undo(&pool, count).await?;
 
     tracing::info!("Storefront rollback complete!");
     Ok(())
@@ -130,10 +217,112 @@ pub async fn rollback_admin(count: i64) -> Result<(), MigrationError> {
     let pool = PgPool::connect(&database_url).await?;
 
     tracing::info!("Rolling back {count} admin migration(s)...");
-    sqlx::migrate!("../admin/migrations")
-        .undo(&pool, count)
-        .await?;
+    ADMIN_MIGRATOR.undo(&pool, count).await?;
 
     tracing::info!("Admin rollback complete!");
     Ok(())
 }
+
+/// Print the migrations of `databases` without running any of them.
+///
+/// Applied migrations are listed in green for context; pending ones are
+/// listed in yellow followed by their full SQL. With `json`, prints a JSON
+/// array of [`MigrationPreview`]s instead.
+///
+/// # Errors
+///
+/// Returns an error if a database can't be queried or stdout can't be written.
+pub async fn dry_run(databases: &[Database], json: bool) -> Result<(), MigrationError> {
+    dotenvy::dotenv().ok();
+
+    let mut previews = Vec::new();
+    for &database in databases {
+        previews.extend(preview(database).await?);
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    if json {
+        serde_json::to_writer_pretty(&mut stdout, &previews)?;
+        writeln!(stdout)?;
+    } else {
+        let color = stdout.is_terminal() && std::env::var_os("NO_COLOR").is_none();
+        write_previews(&mut stdout, &previews, color)?;
+    }
+
+    Ok(())
+}
+
+/// List the up migrations of `database`, marking those already applied.
+async fn preview(database: Database) -> Result<Vec<MigrationPreview>, MigrationError> {
+    let pool = database.connect().await?;
+    let applied = applied_versions(&pool).await?;
+
+    Ok(database
+        .migrator()
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .map(|m| MigrationPreview {
+            database: database.name(),
+            version: m.version,
+            description: m.description.to_string(),
+            sql: m.sql.to_string(),
+            applied: applied.contains(&m.version),
+        })
+        .collect())
+}
+
+/// Versions recorded as successfully applied in `_sqlx_migrations`.
+///
+/// Returns an empty set if migrations have never run against the database.
+async fn applied_versions(pool: &PgPool) -> Result<HashSet<i64>, sqlx::Error> {
+    let table_exists =
+        sqlx::query_scalar!(r#"SELECT to_regclass('_sqlx_migrations') IS NOT NULL AS "exists!""#)
+            .fetch_one(pool)
+            .await?;
+    if !table_exists {
+        return Ok(HashSet::new());
+    }
+
+    let versions = sqlx::query_scalar!("SELECT version FROM _sqlx_migrations WHERE success")
+        .fetch_all(pool)
+        .await?;
+    Ok(versions.into_iter().collect())
+}
+
+/// Write the human-readable dry-run listing.
+fn write_previews(
+    out: &mut impl Write,
+    previews: &[MigrationPreview],
+    color: bool,
+) -> std::io::Result<()> {
+    let paint = |code: &'static str| if color { code } else { "" };
+
+    for preview in previews {
+        if preview.applied {
+            writeln!(
+                out,
+                "{}[applied] {} {} {}{}",
+                paint(GREEN),
+                preview.database,
+                preview.version,
+                preview.description,
+                paint(RESET)
+            )?;
+        } else {
+            writeln!(
+                out,
+                "{}[pending] {} {} {}{}",
+                paint(YELLOW),
+                preview.database,
+                preview.version,
+                preview.description,
+                paint(RESET)
+            )?;
+            writeln!(out, "{}", preview.sql.trim_end())?;
+            writeln!(out)?;
+        }
+    }
+
+    let pending = previews.iter().filter(|p| !p.applied).count();
+    writeln!(out, "{pending} pending migration(s)")
+}
@@ -1,5 +1,14 @@
 //! CLI command implementations.
 
 pub mod admin;
+pub mod api_version;
+pub mod audit_log;
+pub mod collections;
+pub mod db;
+pub mod discounts;
+pub mod gift_cards;
+pub mod inventory;
 pub mod migrate;
 pub mod seed;
+pub mod shopify;
+pub mod webhooks;
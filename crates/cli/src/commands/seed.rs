@@ -1,15 +1,20 @@
-//! Seed database with tool example queries for AI chat.
+//! Seed database with data for AI chat.
 //!
-//! This command reads example queries from a YAML file, generates embeddings
-//! via `OpenAI`, and inserts them into the `tool_example_queries` table for
-//! embedding-based tool selection.
+//! `tool-examples` reads example queries from a YAML file, generates
+//! embeddings via `OpenAI`, and inserts them into the `tool_example_queries`
+//! table for embedding-based tool selection.
+//!
+//! `prompts` reads system prompt templates from a TOML file and inserts them
+//! into the `claude_prompt_template` table. The admin server picks up the
+//! highest version of each template on its next start.
 
 use std::path::Path;
 
 use secrecy::SecretString;
 use tracing::{error, info};
 
-use naked_pineapple_admin::db;
+use naked_pineapple_admin::claude::parse_prompts;
+use naked_pineapple_admin::db::{self, PromptTemplateRepository};
 use naked_pineapple_admin::tool_selection::{
     EmbeddingClient, ToolExamplesConfig, seed_from_file, validate_config,
 };
@@ -123,3 +128,56 @@ pub async fn tool_examples_stats() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Seed Claude prompt templates from a TOML file.
+///
+/// Versions that already exist are skipped rather than overwritten, so
+/// usage records keep pointing at the prompt text they were sent with.
+///
+/// # Errors
+///
+/// Returns an error if `ADMIN_DATABASE_URL` is missing, the file can't be
+/// read or is invalid, or database operations fail.
+pub async fn prompts(file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    dotenvy::dotenv().ok();
+
+    let database_url = std::env::var("ADMIN_DATABASE_URL")
+        .map(SecretString::from)
+        .map_err(|_| "ADMIN_DATABASE_URL not set")?;
+
+    let path = Path::new(file_path);
+    if !path.exists() {
+        return Err(format!("File not found: {file_path}").into());
+    }
+
+    info!(path = %file_path, "Loading prompt templates from file");
+
+    // Parse and validate before connecting to database
+    let content = tokio::fs::read_to_string(path).await?;
+    let templates = parse_prompts(&content)?;
+
+    info!(templates = templates.len(), "Parsed prompt templates");
+
+    let pool = db::create_pool(&database_url).await?;
+    let repo = PromptTemplateRepository::new(&pool);
+
+    let mut inserted = 0;
+    for template in &templates {
+        if repo.insert(template).await? {
+            info!("  Inserted {} v{}", template.name, template.version);
+            inserted += 1;
+        } else {
+            info!(
+                "  Skipped {} v{} (already exists)",
+                template.name, template.version
+            );
+        }
+    }
+
+    info!("Seeding complete!");
+    info!("  Templates inserted: {inserted}");
+    info!("  Templates skipped: {}", templates.len() - inserted);
+    info!("Restart the admin server to use new versions");
+
+    Ok(())
+}
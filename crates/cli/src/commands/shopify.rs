@@ -0,0 +1,79 @@
+//! Shared Shopify Admin API access for CLI commands.
+//!
+//! Commands reuse the OAuth token the admin app stored when the store was
+//! connected, so the CLI never runs the OAuth flow itself.
+
+use secrecy::{ExposeSecret, SecretString};
+use thiserror::Error;
+
+use naked_pineapple_admin::config::{ConfigError, ShopifyAdminConfig};
+use naked_pineapple_admin::db::{self, RepositoryError, ShopifyTokenRepository};
+use naked_pineapple_admin::shopify::{AdminClient, AdminShopifyError, OAuthToken};
+
+/// Errors that can occur while creating a Shopify client.
+#[derive(Debug, Error)]
+pub enum ConnectError {
+    /// Required environment variable is missing.
+    #[error("Missing environment variable: {0}")]
+    MissingEnvVar(&'static str),
+
+    /// Shopify configuration is invalid.
+    #[error("Configuration error: {0}")]
+    Config(#[from] ConfigError),
+
+    /// Shopify client could not be created.
+    #[error("Shopify client error: {0}")]
+    Shopify(#[from] AdminShopifyError),
+
+    /// Database connection error.
+    #[error("Database connection error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    /// Token lookup failed.
+    #[error("Failed to load Shopify token: {0}")]
+    Repository(#[from] RepositoryError),
+
+    /// No Shopify token has been stored yet.
+    #[error("No Shopify token found for {0}. Connect the store in the admin first.")]
+    NotConnected(String),
+}
+
+/// Create an Admin API client using the token stored by the admin app.
+///
+/// # Errors
+///
+/// Returns an error if configuration is missing, the database is unreachable,
+/// or the store hasn't been connected yet.
+pub async fn connect() -> Result<AdminClient, ConnectError> {
+    connect_with(&ShopifyAdminConfig::from_env()?).await
+}
+
+/// Create an Admin API client for `config`, using the token stored by the
+/// admin app for `config.store`.
+///
+/// # Errors
+///
+/// Returns an error if the API version is malformed, the database is
+/// unreachable, or the store hasn't been connected yet.
+pub async fn connect_with(config: &ShopifyAdminConfig) -> Result<AdminClient, ConnectError> {
+    let database_url = std::env::var("ADMIN_DATABASE_URL")
+        .map(SecretString::from)
+        .map_err(|_| ConnectError::MissingEnvVar("ADMIN_DATABASE_URL"))?;
+
+    let pool = db::create_pool(&database_url).await?;
+    let token = ShopifyTokenRepository::new(&pool)
+        .get_by_shop(&config.store)
+        .await?
+        .ok_or_else(|| ConnectError::NotConnected(config.store.clone()))?;
+
+    let client = AdminClient::new(config)?;
+    client
+        .set_token(OAuthToken {
+            access_token: token.access_token.expose_secret().to_string(),
+            scope: token.scopes.join(","),
+            obtained_at: token.obtained_at,
+            shop: token.shop,
+        })
+        .await;
+    Ok(client)
+}
@@ -0,0 +1,151 @@
+//! Shopify webhook subscription commands.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # List webhook subscriptions
+//! np-cli shopify webhook list
+//!
+//! # Subscribe an endpoint to a topic
+//! np-cli shopify webhook create --topic orders/create --url https://admin.example.com/webhooks/orders-create
+//!
+//! # Delete a subscription
+//! np-cli shopify webhook delete --id gid://shopify/WebhookSubscription/123
+//!
+//! # Check a captured payload's signature offline
+//! np-cli shopify webhook verify --secret <client_secret> --payload body.json --signature <base64>
+//! ```
+//!
+//! # Environment Variables
+//!
+//! `list`, `create` and `delete` call the Admin API:
+//!
+//! - `ADMIN_DATABASE_URL` - `PostgreSQL` connection string (for the stored Shopify token)
+//! - `SHOPIFY_STORE`, `SHOPIFY_ADMIN_CLIENT_ID`, `SHOPIFY_ADMIN_CLIENT_SECRET` - Shopify Admin API
+
+use thiserror::Error;
+
+use naked_pineapple_admin::shopify::{AdminShopifyError, verify_webhook_signature};
+
+use super::shopify::{self, ConnectError};
+
+/// Errors that can occur during webhook operations.
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    /// Failed to create the Shopify client.
+    #[error(transparent)]
+    Connect(#[from] ConnectError),
+
+    /// Shopify API error.
+    #[error("Shopify API error: {0}")]
+    Shopify(#[from] AdminShopifyError),
+
+    /// Failed to read the payload file.
+    #[error("Failed to read {0}: {1}")]
+    Read(String, std::io::Error),
+
+    /// The signature doesn't match the payload.
+    #[error("Signature does not match the payload")]
+    InvalidSignature,
+}
+
+/// List every webhook subscription as a table.
+///
+/// # Errors
+///
+/// Returns an error if the Shopify client can't be created or the request fails.
+pub async fn list() -> Result<(), WebhookError> {
+    dotenvy::dotenv().ok();
+    let client = shopify::connect().await?;
+
+    let webhooks = client.list_webhooks().await?;
+    if webhooks.is_empty() {
+        tracing::info!("No webhook subscriptions");
+        return Ok(());
+    }
+
+    let id_width = column_width("ID", webhooks.iter().map(|w| w.id.as_str()));
+    let topic_width = column_width("TOPIC", webhooks.iter().map(|w| w.topic.as_str()));
+    let uri_width = column_width("ADDRESS", webhooks.iter().map(|w| w.uri.as_str()));
+
+    tracing::info!(
+        "{:id_width$}  {:topic_width$}  {:uri_width$}  CREATED_AT",
+        "ID",
+        "TOPIC",
+        "ADDRESS"
+    );
+    for webhook in &webhooks {
+        tracing::info!(
+            "{:id_width$}  {:topic_width$}  {:uri_width$}  {}",
+            webhook.id,
+            webhook.topic,
+            webhook.uri,
+            webhook.created_at
+        );
+    }
+    tracing::info!("{} webhook subscriptions", webhooks.len());
+
+    Ok(())
+}
+
+/// Subscribe `url` to `topic`.
+///
+/// # Errors
+///
+/// Returns an error if the Shopify client can't be created or Shopify
+/// rejects the subscription.
+pub async fn create(topic: &str, url: &str) -> Result<(), WebhookError> {
+    dotenvy::dotenv().ok();
+    let client = shopify::connect().await?;
+
+    let webhook = client.create_webhook(topic, url).await?;
+    tracing::info!(
+        id = %webhook.id,
+        topic = %webhook.topic,
+        address = %webhook.uri,
+        "Webhook subscription created"
+    );
+
+    Ok(())
+}
+
+/// Delete the webhook subscription `id`.
+///
+/// # Errors
+///
+/// Returns an error if the Shopify client can't be created or Shopify
+/// rejects the deletion.
+pub async fn delete(id: &str) -> Result<(), WebhookError> {
+    dotenvy::dotenv().ok();
+    let client = shopify::connect().await?;
+
+    client.delete_webhook(id).await?;
+    tracing::info!(id, "Webhook subscription deleted");
+
+    Ok(())
+}
+
+/// Check a webhook payload's `X-Shopify-Hmac-Sha256` signature offline.
+///
+/// The payload file must hold the raw request body, byte for byte.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read or the signature doesn't match.
+pub async fn verify(secret: &str, payload: &str, signature: &str) -> Result<(), WebhookError> {
+    let body = tokio::fs::read(payload)
+        .await
+        .map_err(|e| WebhookError::Read(payload.to_string(), e))?;
+
+    if !verify_webhook_signature(secret, &body, signature) {
+        return Err(WebhookError::InvalidSignature);
+    }
+
+    tracing::info!("Signature is valid for {payload} ({} bytes)", body.len());
+    Ok(())
+}
+
+/// Width of a table column: the longest of the header and values.
+fn column_width<'a>(header: &str, values: impl Iterator<Item = &'a str>) -> usize {
+    values.map(str::len).fold(header.len(), usize::max)
+}
@@ -0,0 +1,54 @@
+//! CSV helpers shared by the export commands.
+
+/// Escape a value for a CSV field.
+///
+/// Values starting with `=`, `+`, `-` or `@` are prefixed with `'` so
+/// spreadsheet apps don't evaluate them as formulas (negative numbers are
+/// left alone). The field is quoted if it contains a delimiter, quote, or
+/// newline.
+#[must_use]
+pub fn escape_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) && value.parse::<f64>().is_err() {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_values_unchanged() {
+        assert_eq!(escape_field("HOLIDAY20"), "HOLIDAY20");
+        assert_eq!(escape_field(""), "");
+        assert_eq!(escape_field("-12.50"), "-12.50");
+    }
+
+    #[test]
+    fn test_quotes_delimiters_and_newlines() {
+        assert_eq!(escape_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_field("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(escape_field("cr\rlf"), "\"cr\rlf\"");
+    }
+
+    #[test]
+    fn test_neutralizes_formulas() {
+        assert_eq!(escape_field("=SUM(A1:A9)"), "'=SUM(A1:A9)");
+        assert_eq!(escape_field("+cmd"), "'+cmd");
+        assert_eq!(escape_field("-2+3"), "'-2+3");
+        assert_eq!(escape_field("@import"), "'@import");
+        assert_eq!(
+            escape_field("=HYPERLINK(\"http://x\",\"a,b\")"),
+            "\"'=HYPERLINK(\"\"http://x\"\",\"\"a,b\"\")\""
+        );
+    }
+}
@@ -18,33 +18,96 @@
 //! # Rollback multiple migrations
 //! np-cli migrate rollback storefront --count 3
 //!
+//! # Preview pending migration SQL without running it
+//! np-cli migrate dry-run all --json
+//!
+//! # Check the live database schemas against the migrations
+//! np-cli db check all
+//!
 //! # Create an invite for a new admin (recommended)
 //! np-cli admin invite -e admin@example.com -n "Admin Name" -r super_admin
 //!
 //! # Create admin user directly (no passkey)
 //! np-cli admin create -e admin@example.com -n "Admin Name" -r super_admin
 //!
+//! # Delete expired admin sessions
+//! np-cli admin cleanup-sessions
+//!
+//! # Query the audit trail (table, json or csv)
+//! np-cli admin audit-log --user admin@example.com --from 2024-01-01 --to 2024-01-31 --format json
+//!
 //! # Seed tool examples for AI chat
 //! np-cli seed tool-examples --file crates/admin/data/tool_examples.yaml
 //!
 //! # Show tool examples statistics
 //! np-cli seed tool-examples-stats
+//!
+//! # Seed Claude system prompt templates
+//! np-cli seed prompts --file crates/admin/data/prompts.toml
+//!
+//! # Export the gift card ledger
+//! np-cli gift-cards export --format csv --output gift_cards.csv --status active
+//!
+//! # Preview gift card expiry reminders
+//! np-cli gift-cards send-reminders --dry-run
+//!
+//! # Export active discounts with their usage
+//! np-cli discounts export --format csv --output discounts.csv --status active
+//!
+//! # Rename SKUs from a CSV mapping (columns: old_sku,new_sku)
+//! np-cli inventory update-skus --file sku_map.csv
+//!
+//! # Check the admin GraphQL queries against a new Shopify API version
+//! np-cli shopify check-api-version --target-version 2026-04
+//!
+//! # Re-sort a manually sorted collection (preview with --dry-run)
+//! np-cli shopify sync-collections --collection-id gid://shopify/Collection/123 --sort-by title-asc --dry-run
+//!
+//! # List, create and delete Shopify webhook subscriptions
+//! np-cli shopify webhook list
+//! np-cli shopify webhook create --topic orders/create --url https://admin.example.com/webhooks/orders-create
+//! np-cli shopify webhook delete --id gid://shopify/WebhookSubscription/123
+//!
+//! # Verify a webhook signature offline
+//! np-cli shopify webhook verify --secret <client_secret> --payload body.json --signature <base64>
 //! ```
 //!
 //! # Commands
 //!
 //! - `migrate` - Run database migrations
 //! - `migrate rollback` - Rollback database migrations
+//! - `migrate dry-run` - Print pending migration SQL without running it
+//! - `db check` - Check the live schema against the migrations
 //! - `admin invite` - Create invite for new admin (recommended)
 //! - `admin create` - Create admin user directly (no passkey)
+//! - `admin cleanup-sessions` - Delete expired admin sessions
+//! - `admin audit-log` - Query the audit trail of admin actions
 //! - `seed tool-examples` - Seed tool example queries for AI chat
 //! - `seed tool-examples-stats` - Show tool examples statistics
+//! - `seed prompts` - Seed Claude system prompt templates
+//! - `gift-cards export` - Export gift cards to CSV or JSON
+//! - `gift-cards send-reminders` - Send expiry reminders for gift cards expiring in 30 days
+//! - `discounts export` - Export discounts and usage to CSV or JSON
+//! - `inventory update-skus` - Bulk rename SKUs from a CSV mapping
+//! - `shopify check-api-version` - Check GraphQL queries against a new API version
+//! - `shopify sync-collections` - Reorder a manually sorted collection's products
+//! - `shopify webhook` - Manage webhook subscriptions and verify signatures
 
 #![cfg_attr(not(test), forbid(unsafe_code))]
 
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
 use clap::{Parser, Subcommand};
+use naked_pineapple_core::CollectionId;
 
 mod commands;
+mod csv;
+
+use commands::collections::SortBy;
+use commands::gift_cards::{ExportFormat, ExportOptions, StatusFilter};
+use commands::migrate::Database;
 
 #[derive(Parser)]
 #[command(name = "np-cli")]
@@ -61,6 +124,11 @@ enum Commands {
         #[command(subcommand)]
         target: MigrateTarget,
     },
+    /// Database maintenance
+    Db {
+        #[command(subcommand)]
+        action: DbAction,
+    },
     /// Manage admin users
     Admin {
         #[command(subcommand)]
@@ -71,6 +139,26 @@ enum Commands {
         #[command(subcommand)]
         action: SeedAction,
     },
+    /// Manage gift cards
+    GiftCards {
+        #[command(subcommand)]
+        action: GiftCardAction,
+    },
+    /// Manage discounts
+    Discounts {
+        #[command(subcommand)]
+        action: DiscountAction,
+    },
+    /// Manage inventory
+    Inventory {
+        #[command(subcommand)]
+        action: InventoryAction,
+    },
+    /// Shopify API maintenance
+    Shopify {
+        #[command(subcommand)]
+        action: ShopifyAction,
+    },
 }
 
 #[derive(Subcommand)]
@@ -89,6 +177,24 @@ enum MigrateTarget {
         #[arg(short, long, default_value = "1", global = true)]
         count: i64,
     },
+    /// Print pending migration SQL without running it
+    DryRun {
+        #[command(subcommand)]
+        target: DryRunTarget,
+        /// Output a JSON array instead of colored text
+        #[arg(long, global = true)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DryRunTarget {
+    /// Preview storefront database migrations
+    Storefront,
+    /// Preview admin database migrations
+    Admin,
+    /// Preview all database migrations
+    All,
 }
 
 #[derive(Subcommand)]
@@ -99,6 +205,25 @@ enum RollbackTarget {
     Admin,
 }
 
+#[derive(Subcommand)]
+enum DbAction {
+    /// Check the live schema against the migrations (exits non-zero on drift)
+    Check {
+        #[command(subcommand)]
+        target: CheckTarget,
+    },
+}
+
+#[derive(Subcommand)]
+enum CheckTarget {
+    /// Check the storefront database
+    Storefront,
+    /// Check the admin database
+    Admin,
+    /// Check all databases
+    All,
+}
+
 #[derive(Subcommand)]
 enum AdminAction {
     /// Create a new admin user (requires passkey registered separately)
@@ -133,6 +258,50 @@ enum AdminAction {
         #[arg(short = 'x', long, default_value = "7")]
         expires_in_days: i32,
     },
+    /// Delete expired admin sessions
+    CleanupSessions {
+        /// Only delete sessions that expired before this date (YYYY-MM-DD)
+        #[arg(long)]
+        before: Option<NaiveDate>,
+    },
+    /// Query the audit trail of admin actions
+    AuditLog {
+        /// Only actions by this admin email
+        #[arg(short, long)]
+        user: Option<String>,
+
+        /// Only this action (e.g., `ORDER_CANCEL`)
+        #[arg(short, long)]
+        action: Option<String>,
+
+        /// Only this resource type (e.g., `Order`)
+        #[arg(long)]
+        resource_type: Option<String>,
+
+        /// Only actions from this IP address
+        #[arg(long)]
+        ip: Option<IpAddr>,
+
+        /// Only actions on or after this date (YYYY-MM-DD, UTC)
+        #[arg(long)]
+        from: Option<NaiveDate>,
+
+        /// Only actions on or before this date (YYYY-MM-DD, UTC)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+
+        /// Maximum entries to return
+        #[arg(short, long, default_value = "100")]
+        limit: i64,
+
+        /// Entries to skip (for paging through results)
+        #[arg(long, default_value = "0")]
+        offset: i64,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: commands::audit_log::OutputFormat,
+    },
 }
 
 #[derive(Subcommand)]
@@ -149,6 +318,145 @@ enum SeedAction {
     },
     /// Show statistics about existing tool examples
     ToolExamplesStats,
+    /// Seed Claude system prompt templates (new versions apply on admin restart)
+    Prompts {
+        /// Path to TOML file containing prompt templates
+        #[arg(short, long)]
+        file: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum GiftCardAction {
+    /// Export gift cards for accounting reconciliation
+    Export {
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "csv")]
+        format: ExportFormat,
+
+        /// Path to write the export to
+        #[arg(short, long)]
+        output: String,
+
+        /// Only export gift cards with this status
+        #[arg(short, long, value_enum, default_value = "all")]
+        status: StatusFilter,
+
+        /// Only export gift cards issued on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        issued_after: Option<NaiveDate>,
+
+        /// Fetch each gift card's transaction history (one request per card)
+        #[arg(long, default_value = "false")]
+        include_transactions: bool,
+    },
+    /// Remind customers of gift cards expiring in 30 days
+    SendReminders {
+        /// List the gift cards due a reminder without sending anything
+        #[arg(long, default_value = "false")]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DiscountAction {
+    /// Export discounts and their usage for reporting
+    Export {
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "csv")]
+        format: commands::discounts::ExportFormat,
+
+        /// Path to write the export to
+        #[arg(short, long)]
+        output: String,
+
+        /// Only export discounts with this status
+        #[arg(short, long, value_enum, default_value = "all")]
+        status: commands::discounts::StatusFilter,
+    },
+}
+
+#[derive(Subcommand)]
+enum InventoryAction {
+    /// Bulk rename SKUs
+    UpdateSkus {
+        /// Path to CSV file with `old_sku` and `new_sku` columns
+        #[arg(short, long)]
+        file: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ShopifyAction {
+    /// Check the admin GraphQL queries against a new Admin API version
+    CheckApiVersion {
+        /// API version to check against (YYYY-MM)
+        #[arg(short, long)]
+        target_version: String,
+
+        /// Directory of Admin API `.graphql` documents
+        #[arg(short, long, default_value = "crates/admin/graphql/admin/queries")]
+        graphql_dir: PathBuf,
+    },
+    /// Reorder the products of a manually sorted collection
+    SyncCollections {
+        /// Collection GID (`gid://shopify/Collection/...`)
+        #[arg(long)]
+        collection_id: CollectionId,
+
+        /// Desired product order
+        #[arg(short, long, value_enum)]
+        sort_by: SortBy,
+
+        /// File of product handles, one per line (with `--sort-by custom-from-file`)
+        #[arg(long, required_if_eq("sort_by", "custom-from-file"))]
+        custom_from_file: Option<PathBuf>,
+
+        /// Print the current and desired order without reordering
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Manage webhook subscriptions
+    Webhook {
+        #[command(subcommand)]
+        action: WebhookAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum WebhookAction {
+    /// List webhook subscriptions
+    List,
+    /// Subscribe an endpoint to a webhook topic
+    Create {
+        /// Webhook topic (e.g., orders/create)
+        #[arg(short, long)]
+        topic: String,
+
+        /// HTTPS endpoint that receives the webhook
+        #[arg(short, long)]
+        url: String,
+    },
+    /// Delete a webhook subscription
+    Delete {
+        /// Subscription GID (`gid://shopify/WebhookSubscription/...`)
+        #[arg(long)]
+        id: String,
+    },
+    /// Verify a webhook payload's HMAC signature offline
+    Verify {
+        /// App client secret the webhook was signed with
+        #[arg(long)]
+        secret: String,
+
+        /// File containing the raw webhook body
+        #[arg(short, long)]
+        payload: String,
+
+        /// Base64 `X-Shopify-Hmac-Sha256` header value
+        #[arg(short, long)]
+        signature: String,
+    },
 }
 
 #[tokio::main]
@@ -183,20 +491,26 @@ async fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
                     commands::migrate::rollback_admin(count).await?;
                 }
             },
-        },
-        Commands::Admin { action } => match action {
-            AdminAction::Create { email, name, role } => {
-                commands::admin::create_user(&email, &name, &role).await?;
+            MigrateTarget::DryRun { target, json } => {
+                let databases: &[Database] = match target {
+                    DryRunTarget::Storefront => &[Database::Storefront],
+                    DryRunTarget::Admin => &[Database::Admin],
+                    DryRunTarget::All => &[Database::Storefront, Database::Admin],
+                };
+                commands::migrate::dry_run(databases, json).await?;
             }
-            AdminAction::Invite {
-                email,
-                name,
-                role,
-                expires_in_days,
-            } => {
-                commands::admin::create_invite(&email, &name, &role, expires_in_days).await?;
+        },
+        Commands::Db { action } => match action {
+            DbAction::Check { target } => {
+                let databases: &[Database] = match target {
+                    CheckTarget::Storefront => &[Database::Storefront],
+                    CheckTarget::Admin => &[Database::Admin],
+                    CheckTarget::All => &[Database::Storefront, Database::Admin],
+                };
+                commands::db::check(databases).await?;
             }
         },
+        Commands::Admin { action } => run_admin(action).await?,
         Commands::Seed { action } => match action {
             SeedAction::ToolExamples { file, clear } => {
                 commands::seed::tool_examples(&file, clear).await?;
@@ -204,6 +518,136 @@ async fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
             SeedAction::ToolExamplesStats => {
                 commands::seed::tool_examples_stats().await?;
             }
+            SeedAction::Prompts { file } => {
+                commands::seed::prompts(&file).await?;
+            }
+        },
+        Commands::GiftCards { action } => match action {
+            GiftCardAction::Export {
+                format,
+                output,
+                status,
+                issued_after,
+                include_transactions,
+            } => {
+                commands::gift_cards::export(&ExportOptions {
+                    format,
+                    output,
+                    status,
+                    issued_after,
+                    include_transactions,
+                })
+                .await?;
+            }
+            GiftCardAction::SendReminders { dry_run } => {
+                commands::gift_cards::send_reminders(dry_run).await?;
+            }
+        },
+        Commands::Discounts { action } => match action {
+            DiscountAction::Export {
+                format,
+                output,
+                status,
+            } => {
+                commands::discounts::export(&commands::discounts::ExportOptions {
+                    format,
+                    output,
+                    status,
+                })
+                .await?;
+            }
+        },
+        Commands::Inventory { action } => match action {
+            InventoryAction::UpdateSkus { file } => {
+                commands::inventory::update_skus(&file).await?;
+            }
+        },
+        Commands::Shopify { action } => run_shopify(action).await?,
+    }
+    Ok(())
+}
+
+async fn run_admin(action: AdminAction) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        AdminAction::Create { email, name, role } => {
+            commands::admin::create_user(&email, &name, &role).await?;
+        }
+        AdminAction::Invite {
+            email,
+            name,
+            role,
+            expires_in_days,
+        } => {
+            commands::admin::create_invite(&email, &name, &role, expires_in_days).await?;
+        }
+        AdminAction::CleanupSessions { before } => {
+            commands::admin::cleanup_sessions(before).await?;
+        }
+        AdminAction::AuditLog {
+            user,
+            action,
+            resource_type,
+            ip,
+            from,
+            to,
+            limit,
+            offset,
+            format,
+        } => {
+            commands::audit_log::audit_log(&commands::audit_log::AuditLogQuery {
+                user,
+                action,
+                resource_type,
+                ip,
+                from,
+                to,
+                limit,
+                offset,
+                format,
+            })
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn run_shopify(action: ShopifyAction) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        ShopifyAction::CheckApiVersion {
+            target_version,
+            graphql_dir,
+        } => {
+            commands::api_version::check(&commands::api_version::CheckOptions {
+                target_version,
+                graphql_dir,
+            })
+            .await?;
+        }
+        ShopifyAction::SyncCollections {
+            collection_id,
+            sort_by,
+            custom_from_file,
+            dry_run,
+        } => {
+            commands::collections::sync(&commands::collections::SyncOptions {
+                collection_id,
+                sort_by,
+                custom_from_file,
+                dry_run,
+            })
+            .await?;
+        }
+        ShopifyAction::Webhook { action } => match action {
+            WebhookAction::List => commands::webhooks::list().await?,
+            WebhookAction::Create { topic, url } => {
+                commands::webhooks::create(&topic, &url).await?;
+            }
+            WebhookAction::Delete { id } => commands::webhooks::delete(&id).await?,
+            WebhookAction::Verify {
+                secret,
+                payload,
+                signature,
+            } => commands::webhooks::verify(&secret, &payload, &signature).await?,
         },
     }
     Ok(())
@@ -7,15 +7,21 @@
 //!
 //! # Architecture
 //!
-//! The core crate contains only types and traits - no I/O, no database access,
-//! no HTTP clients. This keeps it lightweight and allows it to be used anywhere.
+//! The core crate contains only types, traits and pure helpers - no I/O, no
+//! database access, no HTTP clients. This keeps it lightweight and allows it
+//! to be used anywhere.
 //!
 //! # Modules
 //!
-//! - [`types`] - Newtype wrappers for type-safe IDs, prices, emails, and statuses
+//! - [`types`] - Newtype wrappers for type-safe IDs, prices, emails, and statuses,
+//!   the readiness health report, and country/postal code helpers for addresses
+//! - [`webhook`] - Shopify webhook signature verification, shared by the
+//!   storefront and admin webhook endpoints
 
 #![cfg_attr(not(test), forbid(unsafe_code))]
 
 pub mod types;
+pub mod webhook;
 
 pub use types::*;
+pub use webhook::verify_webhook_signature;
@@ -0,0 +1,268 @@
+//! Postal address helpers: country names and postal code validation.
+
+/// ISO 3166-1 alpha-2 codes and English names of the countries customers
+/// can save addresses in, sorted by name.
+pub const COUNTRIES: &[(&str, &str)] = &[
+    ("AR", "Argentina"),
+    ("AU", "Australia"),
+    ("AT", "Austria"),
+    ("BE", "Belgium"),
+    ("BR", "Brazil"),
+    ("BG", "Bulgaria"),
+    ("CA", "Canada"),
+    ("CL", "Chile"),
+    ("CN", "China"),
+    ("CO", "Colombia"),
+    ("HR", "Croatia"),
+    ("CY", "Cyprus"),
+    ("CZ", "Czechia"),
+    ("DK", "Denmark"),
+    ("EE", "Estonia"),
+    ("FI", "Finland"),
+    ("FR", "France"),
+    ("DE", "Germany"),
+    ("GR", "Greece"),
+    ("HK", "Hong Kong SAR"),
+    ("HU", "Hungary"),
+    ("IS", "Iceland"),
+    ("IN", "India"),
+    ("ID", "Indonesia"),
+    ("IE", "Ireland"),
+    ("IL", "Israel"),
+    ("IT", "Italy"),
+    ("JP", "Japan"),
+    ("LV", "Latvia"),
+    ("LT", "Lithuania"),
+    ("LU", "Luxembourg"),
+    ("MY", "Malaysia"),
+    ("MT", "Malta"),
+    ("MX", "Mexico"),
+    ("NL", "Netherlands"),
+    ("NZ", "New Zealand"),
+    ("NO", "Norway"),
+    ("PE", "Peru"),
+    ("PH", "Philippines"),
+    ("PL", "Poland"),
+    ("PT", "Portugal"),
+    ("PR", "Puerto Rico"),
+    ("RO", "Romania"),
+    ("SA", "Saudi Arabia"),
+    ("SG", "Singapore"),
+    ("SK", "Slovakia"),
+    ("SI", "Slovenia"),
+    ("ZA", "South Africa"),
+    ("KR", "South Korea"),
+    ("ES", "Spain"),
+    ("SE", "Sweden"),
+    ("CH", "Switzerland"),
+    ("TW", "Taiwan"),
+    ("TH", "Thailand"),
+    ("AE", "United Arab Emirates"),
+    ("GB", "United Kingdom"),
+    ("US", "United States"),
+    ("VN", "Vietnam"),
+];
+
+/// Look up the English name of a country by its ISO 3166-1 alpha-2 code.
+///
+/// The lookup is case-insensitive. Returns `None` for unknown codes.
+///
+/// ```
+/// use naked_pineapple_core::country_name;
+///
+/// assert_eq!(country_name("us"), Some("United States"));
+/// assert_eq!(country_name("XX"), None);
+/// ```
+#[must_use]
+pub fn country_name(code: &str) -> Option<&'static str> {
+    COUNTRIES
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(code))
+        .map(|(_, name)| *name)
+}
+
+/// Errors that can occur when validating a postal code.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum PostalCodeError {
+    /// The postal code is empty.
+    #[error("postal code cannot be empty")]
+    Empty,
+    /// The postal code doesn't match the country's format.
+    #[error("postal code must look like {example}")]
+    InvalidFormat {
+        /// An example of a valid postal code for the country.
+        example: &'static str,
+    },
+}
+
+/// Validate and normalize a postal code for a country.
+///
+/// US ZIP codes must be 5 digits, optionally followed by `-` and 4 digits.
+/// Canadian codes must look like `A1A 1A1` and UK codes like `SW1A 1AA`;
+/// both are uppercased and get a single space before the last three
+/// characters. Other countries are only checked for being non-empty.
+///
+/// ```
+/// use naked_pineapple_core::normalize_postal_code;
+///
+/// assert_eq!(normalize_postal_code("CA", "k1a0b1").unwrap(), "K1A 0B1");
+/// assert!(normalize_postal_code("US", "1234").is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the postal code is empty or doesn't match the
+/// country's format.
+pub fn normalize_postal_code(
+    country_code: &str,
+    postal_code: &str,
+) -> Result<String, PostalCodeError> {
+    let trimmed = postal_code.trim();
+    if trimmed.is_empty() {
+        return Err(PostalCodeError::Empty);
+    }
+
+    match country_code.to_ascii_uppercase().as_str() {
+        "US" => {
+            if matches_pattern(trimmed, "99999") || matches_pattern(trimmed, "99999-9999") {
+                Ok(trimmed.to_owned())
+            } else {
+                Err(PostalCodeError::InvalidFormat { example: "12345" })
+            }
+        }
+        "CA" => {
+            let compact = compact_upper(trimmed);
+            if matches_pattern(&compact, "A9A9A9") {
+                Ok(split_inward(&compact))
+            } else {
+                Err(PostalCodeError::InvalidFormat { example: "A1A 1A1" })
+            }
+        }
+        "GB" => {
+            let compact = compact_upper(trimmed);
+            if is_uk_postcode(&compact) {
+                Ok(split_inward(&compact))
+            } else {
+                Err(PostalCodeError::InvalidFormat {
+                    example: "SW1A 1AA",
+                })
+            }
+        }
+        _ => Ok(trimmed.to_owned()),
+    }
+}
+
+/// Whether `compact` (uppercase, no spaces) is a UK postcode.
+///
+/// The outward code is one of `A9`, `A99`, `AA9`, `AA99`, `A9A` or `AA9A`;
+/// the inward code is always `9AA`.
+fn is_uk_postcode(compact: &str) -> bool {
+    const OUTWARD: [&str; 6] = ["A9", "A99", "AA9", "AA99", "A9A", "AA9A"];
+
+    let Some(split) = compact.len().checked_sub(3) else {
+        return false;
+    };
+    let (Some(outward), Some(inward)) = (compact.get(..split), compact.get(split..)) else {
+        return false;
+    };
+
+    matches_pattern(inward, "9AA")
+        && OUTWARD
+            .iter()
+            .any(|pattern| matches_pattern(outward, pattern))
+}
+
+/// Match `value` against a pattern where `A` is an ASCII letter, `9` is an
+/// ASCII digit and any other character matches itself.
+fn matches_pattern(value: &str, pattern: &str) -> bool {
+    value.len() == pattern.len()
+        && value.chars().zip(pattern.chars()).all(|(c, p)| match p {
+            'A' => c.is_ascii_alphabetic(),
+            '9' => c.is_ascii_digit(),
+            _ => c == p,
+        })
+}
+
+/// Uppercase and strip whitespace.
+fn compact_upper(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Insert a space before the last three characters of an ASCII code.
+fn split_inward(compact: &str) -> String {
+    let split = compact.len().saturating_sub(3);
+    format!(
+        "{} {}",
+        compact.get(..split).unwrap_or_default(),
+        compact.get(split..).unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_country_name() {
+        assert_eq!(country_name("GB"), Some("United Kingdom"));
+        assert_eq!(country_name("ca"), Some("Canada"));
+        assert_eq!(country_name(""), None);
+        assert_eq!(country_name("ZZ"), None);
+    }
+
+    #[test]
+    fn test_countries_sorted_and_unique() {
+        assert!(COUNTRIES.windows(2).all(|w| match w {
+            [(a_code, a), (b_code, b)] => a < b && a_code != b_code,
+            _ => true,
+        }));
+    }
+
+    #[test]
+    fn test_us_zip() {
+        assert_eq!(normalize_postal_code("US", " 90210 ").unwrap(), "90210");
+        assert_eq!(
+            normalize_postal_code("us", "90210-1234").unwrap(),
+            "90210-1234"
+        );
+        assert!(normalize_postal_code("US", "9021").is_err());
+        assert!(normalize_postal_code("US", "9021A").is_err());
+        assert!(normalize_postal_code("US", "902101").is_err());
+    }
+
+    #[test]
+    fn test_canadian_postal_code() {
+        assert_eq!(normalize_postal_code("CA", "K1A 0B1").unwrap(), "K1A 0B1");
+        assert_eq!(normalize_postal_code("CA", "m5v3l9").unwrap(), "M5V 3L9");
+        assert!(normalize_postal_code("CA", "12345").is_err());
+        assert!(normalize_postal_code("CA", "K1A 0B").is_err());
+    }
+
+    #[test]
+    fn test_uk_postcode() {
+        for (input, expected) in [
+            ("SW1A 1AA", "SW1A 1AA"),
+            ("m1 1ae", "M1 1AE"),
+            ("B338TH", "B33 8TH"),
+            ("CR2 6XH", "CR2 6XH"),
+            ("DN55 1PT", "DN55 1PT"),
+            ("W1A 0AX", "W1A 0AX"),
+        ] {
+            assert_eq!(normalize_postal_code("GB", input).unwrap(), expected);
+        }
+        assert!(normalize_postal_code("GB", "1AA 1AA").is_err());
+        assert!(normalize_postal_code("GB", "SW1A").is_err());
+        assert!(normalize_postal_code("GB", "AAAAA 1AA").is_err());
+    }
+
+    #[test]
+    fn test_other_countries_only_require_value() {
+        assert_eq!(normalize_postal_code("DE", " 10115 ").unwrap(), "10115");
+        assert_eq!(normalize_postal_code("FR", ""), Err(PostalCodeError::Empty));
+    }
+}
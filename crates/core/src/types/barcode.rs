@@ -0,0 +1,268 @@
+//! Product barcode type.
+
+use core::fmt;
+
+use serde::Serialize;
+
+/// Errors that can occur when parsing a [`Barcode`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum BarcodeError {
+    /// The barcode contains a character that is not an ASCII digit.
+    #[error("barcode must contain only digits")]
+    NonDigit,
+    /// The barcode length doesn't match any supported format.
+    #[error("barcode must be 8, 12 or 13 digits, got {0}")]
+    InvalidLength(usize),
+    /// The last digit doesn't match the GS1 check digit.
+    #[error("invalid {format} check digit: expected {expected}, got {found}")]
+    InvalidCheckDigit {
+        /// The format the barcode was parsed as.
+        format: BarcodeFormat,
+        /// The check digit computed from the other digits.
+        expected: u8,
+        /// The check digit in the barcode.
+        found: u8,
+    },
+}
+
+/// A barcode symbology, detected from the length and prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum BarcodeFormat {
+    /// UPC-A (12 digits).
+    UpcA,
+    /// EAN-13 (13 digits).
+    Ean13,
+    /// EAN-8 (8 digits).
+    Ean8,
+    /// ISBN-13 (13 digits starting with 978 or 979).
+    Isbn13,
+}
+
+impl fmt::Display for BarcodeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UpcA => "UPC-A",
+            Self::Ean13 => "EAN-13",
+            Self::Ean8 => "EAN-8",
+            Self::Isbn13 => "ISBN-13",
+        })
+    }
+}
+
+/// A validated product barcode.
+///
+/// ## Constraints
+///
+/// - Digits only (surrounding whitespace is trimmed)
+/// - UPC-A (12 digits), EAN-13 (13 digits), EAN-8 (8 digits) or
+///   ISBN-13 (13 digits starting with 978 or 979)
+/// - The last digit must be the GS1 check digit
+/// - An empty string is allowed and means "no barcode"
+///
+/// ## Examples
+///
+/// ```
+/// use naked_pineapple_core::{Barcode, BarcodeFormat};
+///
+/// let upc = Barcode::parse("036000291452").unwrap();
+/// assert_eq!(upc.format(), Some(BarcodeFormat::UpcA));
+///
+/// assert!(Barcode::parse("").unwrap().is_empty());
+/// assert!(Barcode::parse("036000291453").is_err()); // wrong check digit
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Barcode(String);
+
+impl Barcode {
+    /// Parse a `Barcode` from a string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input (after trimming):
+    /// - Contains a non-digit character
+    /// - Is not 8, 12 or 13 digits long
+    /// - Has an incorrect check digit
+    pub fn parse(s: &str) -> Result<Self, BarcodeError> {
+        let trimmed = s.trim();
+        if trimmed.is_empty() {
+            return Ok(Self(String::new()));
+        }
+
+        let digits = trimmed
+            .bytes()
+            .map(|b| b.is_ascii_digit().then(|| b - b'0'))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or(BarcodeError::NonDigit)?;
+
+        let format = detect_format(&digits).ok_or(BarcodeError::InvalidLength(digits.len()))?;
+
+        if let Some((&found, payload)) = digits.split_last() {
+            let expected = gs1_check_digit(payload);
+            if expected != found {
+                return Err(BarcodeError::InvalidCheckDigit {
+                    format,
+                    expected,
+                    found,
+                });
+            }
+        }
+
+        Ok(Self(trimmed.to_owned()))
+    }
+
+    /// Returns the barcode's format, or `None` for an empty barcode.
+    #[must_use]
+    pub fn format(&self) -> Option<BarcodeFormat> {
+        let digits: Vec<u8> = self.0.bytes().map(|b| b - b'0').collect();
+        detect_format(&digits)
+    }
+
+    /// Returns `true` if this is the empty "no barcode" value.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the barcode as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes the `Barcode` and returns its inner string.
+    #[must_use]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+/// Detect the format of a barcode from its digits.
+fn detect_format(digits: &[u8]) -> Option<BarcodeFormat> {
+    match (digits.len(), digits) {
+        (8, _) => Some(BarcodeFormat::Ean8),
+        (12, _) => Some(BarcodeFormat::UpcA),
+        (13, [9, 7, 8 | 9, ..]) => Some(BarcodeFormat::Isbn13),
+        (13, _) => Some(BarcodeFormat::Ean13),
+        _ => None,
+    }
+}
+
+/// Compute the GS1 (mod 10) check digit for the digits before it.
+///
+/// Digits are weighted 3, 1, 3, ... starting from the rightmost digit.
+fn gs1_check_digit(payload: &[u8]) -> u8 {
+    let sum: u32 = payload
+        .iter()
+        .rev()
+        .zip([3, 1].into_iter().cycle())
+        .map(|(&digit, weight)| u32::from(digit) * weight)
+        .sum();
+
+    u8::try_from((10 - sum % 10) % 10).unwrap_or_default()
+}
+
+impl fmt::Display for Barcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for Barcode {
+    type Err = BarcodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl AsRef<str> for Barcode {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_upc_a() {
+        let barcode = Barcode::parse("036000291452").unwrap();
+        assert_eq!(barcode.format(), Some(BarcodeFormat::UpcA));
+        assert_eq!(barcode.as_str(), "036000291452");
+    }
+
+    #[test]
+    fn test_parse_ean_13() {
+        let barcode = Barcode::parse("4006381333931").unwrap();
+        assert_eq!(barcode.format(), Some(BarcodeFormat::Ean13));
+    }
+
+    #[test]
+    fn test_parse_ean_8() {
+        let barcode = Barcode::parse("96385074").unwrap();
+        assert_eq!(barcode.format(), Some(BarcodeFormat::Ean8));
+    }
+
+    #[test]
+    fn test_parse_isbn_13() {
+        for isbn in ["9780306406157", "9791032300824"] {
+            let barcode = Barcode::parse(isbn).unwrap();
+            assert_eq!(barcode.format(), Some(BarcodeFormat::Isbn13));
+        }
+    }
+
+    #[test]
+    fn test_parse_trims_whitespace() {
+        assert_eq!(
+            Barcode::parse(" 036000291452\n").unwrap().as_str(),
+            "036000291452"
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_means_no_barcode() {
+        let barcode = Barcode::parse("  ").unwrap();
+        assert!(barcode.is_empty());
+        assert_eq!(barcode.format(), None);
+    }
+
+    #[test]
+    fn test_invalid_check_digits() {
+        for (input, format, expected, found) in [
+            ("036000291453", BarcodeFormat::UpcA, 2, 3),
+            ("4006381333932", BarcodeFormat::Ean13, 1, 2),
+            ("96385075", BarcodeFormat::Ean8, 4, 5),
+            ("9780306406158", BarcodeFormat::Isbn13, 7, 8),
+        ] {
+            assert_eq!(
+                Barcode::parse(input),
+                Err(BarcodeError::InvalidCheckDigit {
+                    format,
+                    expected,
+                    found
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_length() {
+        assert_eq!(Barcode::parse("12345"), Err(BarcodeError::InvalidLength(5)));
+        assert_eq!(
+            Barcode::parse("12345678901234"),
+            Err(BarcodeError::InvalidLength(14))
+        );
+    }
+
+    #[test]
+    fn test_non_digit() {
+        assert_eq!(Barcode::parse("03600029145A"), Err(BarcodeError::NonDigit));
+        assert_eq!(
+            Barcode::parse("0360-0029-1452"),
+            Err(BarcodeError::NonDigit)
+        );
+    }
+}
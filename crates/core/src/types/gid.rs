@@ -0,0 +1,273 @@
+//! Shopify global IDs.
+//!
+//! Shopify identifies every resource with a GID such as
+//! `gid://shopify/Product/123`. Each resource gets its own newtype, so an
+//! order ID can't be passed where a product ID is expected:
+//!
+//! ```compile_fail
+//! use naked_pineapple_core::{OrderId, ProductId};
+//!
+//! fn get_product(_id: &ProductId) {}
+//!
+//! let order_id: OrderId = "gid://shopify/Order/1".parse().unwrap();
+//! get_product(&order_id);
+//! ```
+
+use core::fmt;
+use core::str::FromStr;
+
+/// Error returned when a string isn't a GID for the expected resource.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid Shopify {resource} ID: {value:?}")]
+pub struct GidError {
+    /// Resource type that was expected (e.g., "Product").
+    pub resource: &'static str,
+    /// The rejected input.
+    pub value: String,
+}
+
+/// Define a Shopify GID newtype for a resource type.
+///
+/// The generated type wraps the full GID string, parses with [`FromStr`]
+/// (rejecting GIDs for other resources), and serializes as the plain string.
+macro_rules! define_gid {
+    ($(#[$meta:meta])* $name:ident, $resource:literal) => {
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize,
+        )]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl $name {
+            /// Shopify resource type in the GID.
+            pub const RESOURCE: &'static str = $resource;
+
+            /// GID prefix, up to and including the final `/`.
+            pub const PREFIX: &'static str = concat!("gid://shopify/", $resource, "/");
+
+            /// Build a GID from the numeric ID used in admin URLs (e.g., "123").
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if `id` is empty or not all ASCII digits.
+            pub fn from_numeric(id: &str) -> Result<Self, GidError> {
+                if !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()) {
+                    Ok(Self(format!("{}{id}", Self::PREFIX)))
+                } else {
+                    Err(GidError {
+                        resource: Self::RESOURCE,
+                        value: id.to_string(),
+                    })
+                }
+            }
+
+            /// Parse an ID from an admin URL or form, which may be a full GID or
+            /// just the numeric ID.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if `id` is neither a GID for this resource nor
+            /// a numeric ID.
+            pub fn parse_admin_id(id: &str) -> Result<Self, GidError> {
+                if id.starts_with("gid://") {
+                    id.parse()
+                } else {
+                    Self::from_numeric(id)
+                }
+            }
+
+            /// The full GID string.
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// The ID after the prefix (e.g., "123").
+            #[must_use]
+            pub fn numeric_id(&self) -> &str {
+                self.0.get(Self::PREFIX.len()..).unwrap_or_default()
+            }
+
+            /// Consume the ID, returning the full GID string.
+            #[must_use]
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = GidError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                if is_valid(s, Self::PREFIX) {
+                    Ok(Self(s.to_string()))
+                } else {
+                    Err(GidError {
+                        resource: Self::RESOURCE,
+                        value: s.to_string(),
+                    })
+                }
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = GidError;
+
+            fn try_from(s: String) -> Result<Self, Self::Error> {
+                if is_valid(&s, Self::PREFIX) {
+                    Ok(Self(s))
+                } else {
+                    Err(GidError {
+                        resource: Self::RESOURCE,
+                        value: s,
+                    })
+                }
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = GidError;
+
+            fn try_from(s: &str) -> Result<Self, Self::Error> {
+                s.parse()
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+
+/// Whether `s` is `prefix` followed by a non-empty ID with no further path
+/// segments.
+fn is_valid(s: &str, prefix: &str) -> bool {
+    s.strip_prefix(prefix)
+        .is_some_and(|id| !id.is_empty() && !id.contains('/'))
+}
+
+define_gid!(
+    /// Shopify product GID (`gid://shopify/Product/...`).
+    ProductId,
+    "Product"
+);
+define_gid!(
+    /// Shopify product variant GID (`gid://shopify/ProductVariant/...`).
+    VariantId,
+    "ProductVariant"
+);
+define_gid!(
+    /// Shopify order GID (`gid://shopify/Order/...`).
+    OrderId,
+    "Order"
+);
+define_gid!(
+    /// Shopify customer GID (`gid://shopify/Customer/...`).
+    CustomerId,
+    "Customer"
+);
+define_gid!(
+    /// Shopify collection GID (`gid://shopify/Collection/...`).
+    CollectionId,
+    "Collection"
+);
+define_gid!(
+    /// Shopify inventory item GID (`gid://shopify/InventoryItem/...`).
+    InventoryItemId,
+    "InventoryItem"
+);
+define_gid!(
+    /// Shopify location GID (`gid://shopify/Location/...`).
+    LocationId,
+    "Location"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid() {
+        let id: ProductId = "gid://shopify/Product/123".parse().expect("valid GID");
+        assert_eq!(id.as_str(), "gid://shopify/Product/123");
+        assert_eq!(id.numeric_id(), "123");
+        assert_eq!(id.to_string(), "gid://shopify/Product/123");
+
+        let id: VariantId = "gid://shopify/ProductVariant/456"
+            .parse()
+            .expect("valid GID");
+        assert_eq!(id.numeric_id(), "456");
+    }
+
+    #[test]
+    fn test_parse_rejects_other_resources() {
+        let err = "gid://shopify/Order/123"
+            .parse::<ProductId>()
+            .expect_err("wrong resource");
+        assert_eq!(err.resource, "Product");
+        assert_eq!(err.value, "gid://shopify/Order/123");
+
+        // A variant is not a product, even though the prefix overlaps
+        assert!(
+            "gid://shopify/ProductVariant/1"
+                .parse::<ProductId>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        assert!("".parse::<OrderId>().is_err());
+        assert!("123".parse::<OrderId>().is_err());
+        assert!("gid://shopify/Order/".parse::<OrderId>().is_err());
+        assert!("gid://shopify/Order/1/2".parse::<OrderId>().is_err());
+    }
+
+    #[test]
+    fn test_from_numeric() {
+        let id = CustomerId::from_numeric("42").expect("numeric ID");
+        assert_eq!(id.as_str(), "gid://shopify/Customer/42");
+
+        assert!(CustomerId::from_numeric("").is_err());
+        assert!(CustomerId::from_numeric("42abc").is_err());
+        assert!(CustomerId::from_numeric("../42").is_err());
+    }
+
+    #[test]
+    fn test_parse_admin_id() {
+        let from_gid = OrderId::parse_admin_id("gid://shopify/Order/9").expect("GID");
+        let from_numeric = OrderId::parse_admin_id("9").expect("numeric ID");
+        assert_eq!(from_gid, from_numeric);
+
+        assert!(OrderId::parse_admin_id("gid://shopify/Customer/9").is_err());
+        assert!(OrderId::parse_admin_id("").is_err());
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let id = LocationId::from_numeric("7").expect("numeric ID");
+        let json = serde_json::to_string(&id).expect("serialize");
+        assert_eq!(json, "\"gid://shopify/Location/7\"");
+        assert_eq!(
+            serde_json::from_str::<LocationId>(&json).expect("deserialize"),
+            id
+        );
+
+        assert!(serde_json::from_str::<LocationId>("\"gid://shopify/Product/7\"").is_err());
+    }
+}
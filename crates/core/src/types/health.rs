@@ -0,0 +1,202 @@
+//! Readiness health check report.
+//!
+//! Both servers expose `/health/ready`, which runs each dependency check and
+//! aggregates the results into a [`HealthReport`]:
+//!
+//! - Any critical check down (the database): `down`, served as 503
+//! - Any non-critical check down (Shopify, other external APIs): `degraded`
+//! - Otherwise: `ok`
+//!
+//! Reports can also carry diagnostics (e.g., row counts) that never affect
+//! the status.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::time::Instant;
+
+use serde::Serialize;
+
+/// Status of a single check or of the service as a whole.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// Everything is working.
+    Ok,
+    /// Serving traffic, but a non-critical dependency is unavailable.
+    Degraded,
+    /// Not able to serve traffic.
+    Down,
+}
+
+/// Result of checking one dependency.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthCheck {
+    /// Whether the dependency responded.
+    pub status: HealthStatus,
+    /// Round-trip time in milliseconds (when the check succeeded).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Why the check failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HealthCheck {
+    /// Run a check, recording its latency on success or its error on failure.
+    pub async fn measure<T, E: Display>(check: impl Future<Output = Result<T, E>>) -> Self {
+        let start = Instant::now();
+        match check.await {
+            Ok(_) => Self {
+                status: HealthStatus::Ok,
+                latency_ms: Some(u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX)),
+                error: None,
+            },
+            Err(e) => Self {
+                status: HealthStatus::Down,
+                latency_ms: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    /// Whether the check passed.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.status == HealthStatus::Ok
+    }
+}
+
+/// Aggregated readiness report.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// Overall service status.
+    pub status: HealthStatus,
+    /// Individual check results, keyed by dependency name.
+    pub checks: BTreeMap<&'static str, HealthCheck>,
+    /// Informational values that don't affect the status.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub diagnostics: BTreeMap<&'static str, i64>,
+}
+
+impl HealthReport {
+    /// Build a report from critical and non-critical check results.
+    #[must_use]
+    pub fn new(
+        critical: Vec<(&'static str, HealthCheck)>,
+        non_critical: Vec<(&'static str, HealthCheck)>,
+    ) -> Self {
+        let status = if critical.iter().any(|(_, check)| !check.is_ok()) {
+            HealthStatus::Down
+        } else if non_critical.iter().any(|(_, check)| !check.is_ok()) {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Ok
+        };
+
+        Self {
+            status,
+            checks: critical.into_iter().chain(non_critical).collect(),
+            diagnostics: BTreeMap::new(),
+        }
+    }
+
+    /// Add an informational value to the report.
+    #[must_use]
+    pub fn with_diagnostic(mut self, name: &'static str, value: i64) -> Self {
+        self.diagnostics.insert(name, value);
+        self
+    }
+
+    /// Whether the service can take traffic (`ok` or `degraded`).
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        self.status != HealthStatus::Down
+    }
+
+    /// Remove error details, for responses served to untrusted clients.
+    #[must_use]
+    pub fn without_errors(mut self) -> Self {
+        for check in self.checks.values_mut() {
+            if check.error.is_some() {
+                check.error = Some("unavailable".to_string());
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok() -> HealthCheck {
+        HealthCheck {
+            status: HealthStatus::Ok,
+            latency_ms: Some(1),
+            error: None,
+        }
+    }
+
+    fn down() -> HealthCheck {
+        HealthCheck {
+            status: HealthStatus::Down,
+            latency_ms: None,
+            error: Some("connection refused".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_all_ok() {
+        let report = HealthReport::new(vec![("database", ok())], vec![("shopify", ok())]);
+        assert_eq!(report.status, HealthStatus::Ok);
+        assert!(report.is_ready());
+    }
+
+    #[test]
+    fn test_non_critical_failure_is_degraded() {
+        let report = HealthReport::new(vec![("database", ok())], vec![("shopify", down())]);
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert!(report.is_ready());
+    }
+
+    #[test]
+    fn test_critical_failure_is_down() {
+        let report = HealthReport::new(vec![("database", down())], vec![("shopify", ok())]);
+        assert_eq!(report.status, HealthStatus::Down);
+        assert!(!report.is_ready());
+    }
+
+    #[test]
+    fn test_without_errors_masks_details() {
+        let report = HealthReport::new(vec![("database", down())], Vec::new()).without_errors();
+        let json = serde_json::to_value(&report).expect("serialize report");
+        assert_eq!(json.get("status"), Some(&"down".into()));
+        assert_eq!(
+            json.pointer("/checks/database/error"),
+            Some(&"unavailable".into())
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_do_not_affect_status() {
+        let report = HealthReport::new(vec![("database", ok())], Vec::new());
+        let json = serde_json::to_value(&report).expect("serialize report");
+        assert!(json.get("diagnostics").is_none());
+
+        let report = report.with_diagnostic("expired_sessions_deleted", 42);
+        assert_eq!(report.status, HealthStatus::Ok);
+        let json = serde_json::to_value(&report).expect("serialize report");
+        assert_eq!(
+            json.pointer("/diagnostics/expired_sessions_deleted"),
+            Some(&42.into())
+        );
+    }
+
+    #[test]
+    fn test_ok_check_omits_error() {
+        let json = serde_json::to_value(ok()).expect("serialize check");
+        assert_eq!(json.get("status"), Some(&"ok".into()));
+        assert_eq!(json.get("latency_ms"), Some(&1.into()));
+        assert!(json.get("error").is_none());
+    }
+}
@@ -104,9 +104,6 @@ macro_rules! define_id {
 // Define standard entity IDs
 define_id!(UserId);
 define_id!(CredentialId);
-define_id!(ProductId);
-define_id!(VariantId);
-define_id!(OrderId);
 define_id!(CartId);
 define_id!(AddressId);
 define_id!(AdminUserId);
@@ -2,14 +2,22 @@
 //!
 //! This module provides type-safe wrappers for common domain concepts.
 
+pub mod address;
+pub mod barcode;
 pub mod credential;
 pub mod email;
+pub mod gid;
+pub mod health;
 pub mod id;
 pub mod price;
 pub mod status;
 
+pub use address::{COUNTRIES, PostalCodeError, country_name, normalize_postal_code};
+pub use barcode::{Barcode, BarcodeError, BarcodeFormat};
 pub use credential::{StoredPasskey, WebAuthnCredentialId};
 pub use email::{Email, EmailError};
+pub use gid::*;
+pub use health::{HealthCheck, HealthReport, HealthStatus};
 pub use id::*;
-pub use price::Price;
+pub use price::{CurrencyCode, CurrencyMismatch, Price};
 pub use status::*;
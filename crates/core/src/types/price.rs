@@ -1,76 +1,46 @@
 //! Type-safe price representation using decimal arithmetic.
 //!
-//! # Future Implementation
+//! Prices carry their currency, and arithmetic between two prices checks
+//! that the currencies match instead of silently mixing them:
 //!
-//! ```rust,ignore
+//! ```
+//! use naked_pineapple_core::{CurrencyCode, Price};
 //! use rust_decimal::Decimal;
-//! use serde::{Deserialize, Serialize};
-//!
-//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-//! pub struct Price {
-//!     /// Amount in the smallest currency unit (e.g., cents for USD).
-//!     amount: Decimal,
-//!     /// ISO 4217 currency code (e.g., "USD", "EUR").
-//!     currency: Currency,
-//! }
-//!
-//! impl Price {
-//!     pub fn new(amount: Decimal, currency: Currency) -> Self {
-//!         Self { amount, currency }
-//!     }
-//!
-//!     pub fn from_cents(cents: i64, currency: Currency) -> Self {
-//!         Self {
-//!             amount: Decimal::new(cents, 2),
-//!             currency,
-//!         }
-//!     }
-//!
-//!     pub fn amount(&self) -> Decimal { self.amount }
-//!     pub fn currency(&self) -> Currency { self.currency }
-//!
-//!     /// Format for display (e.g., "$19.99").
-//!     pub fn display(&self) -> String {
-//!         format!("{}{:.2}", self.currency.symbol(), self.amount)
-//!     }
-//! }
 //!
-//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-//! pub enum Currency {
-//!     USD,
-//!     EUR,
-//!     GBP,
-//!     CAD,
-//!     AUD,
-//! }
+//! let unit = Price::new(Decimal::new(1250, 2), CurrencyCode::USD);
+//! let shipping = Price::new(Decimal::new(500, 2), CurrencyCode::USD);
 //!
-//! impl Currency {
-//!     pub fn symbol(&self) -> &'static str {
-//!         match self {
-//!             Self::USD | Self::CAD | Self::AUD => "$",
-//!             Self::EUR => "€",
-//!             Self::GBP => "£",
-//!         }
-//!     }
+//! let total = ((unit * 2) + shipping).unwrap();
+//! assert_eq!(total.to_string(), "$30.00");
 //!
-//!     pub fn code(&self) -> &'static str {
-//!         match self {
-//!             Self::USD => "USD",
-//!             Self::EUR => "EUR",
-//!             Self::GBP => "GBP",
-//!             Self::CAD => "CAD",
-//!             Self::AUD => "AUD",
-//!         }
-//!     }
-//! }
+//! let euros = Price::new(Decimal::ONE, CurrencyCode::EUR);
+//! assert!((total + euros).is_err());
 //! ```
 
-use rust_decimal::Decimal;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Sub};
+
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::{Deserialize, Serialize};
 
+/// Decimal places used when no precision is given.
+const DEFAULT_DECIMAL_PLACES: u32 = 2;
+
+/// Error returned when combining prices in different currencies.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("currency mismatch: {left:?} and {right:?}")]
+pub struct CurrencyMismatch {
+    /// Currency of the left operand.
+    pub left: CurrencyCode,
+    /// Currency of the right operand.
+    pub right: CurrencyCode,
+}
+
 /// A price with currency information.
 ///
-/// TODO: Implement full currency support and formatting.
+/// `Display` formats with the currency symbol and two decimal places
+/// (`$1,234.50`); a precision overrides the decimal places (`{:.0}`). Use
+/// [`format_locale`](Self::format_locale) for other number conventions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Price {
     /// Amount in the currency's standard unit (e.g., dollars, not cents).
@@ -88,6 +58,120 @@ impl Price {
             currency_code,
         }
     }
+
+    /// A zero amount in `currency_code`.
+    #[must_use]
+    pub const fn zero(currency_code: CurrencyCode) -> Self {
+        Self::new(Decimal::ZERO, currency_code)
+    }
+
+    /// Subtract `other`, returning `None` if the currencies differ or the
+    /// result would be negative.
+    ///
+    /// ```
+    /// use naked_pineapple_core::{CurrencyCode, Price};
+    /// use rust_decimal::Decimal;
+    ///
+    /// let balance = Price::new(Decimal::new(2500, 2), CurrencyCode::USD);
+    /// let charge = Price::new(Decimal::new(3000, 2), CurrencyCode::USD);
+    /// assert!(balance.checked_sub(charge).is_none());
+    /// ```
+    #[must_use]
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        if self.currency_code != other.currency_code {
+            return None;
+        }
+        self.amount
+            .checked_sub(other.amount)
+            .filter(|amount| !amount.is_sign_negative())
+            .map(|amount| Self::new(amount, self.currency_code))
+    }
+
+    /// Format for `locale` (a BCP 47 tag such as `en-US` or `de-DE`) with
+    /// two decimal places.
+    ///
+    /// German, Spanish, Italian, Dutch and Portuguese group with `.`, use a
+    /// decimal comma and put the symbol last (`1.234,50 €`); French groups
+    /// with a narrow no-break space. Everything else uses English
+    /// conventions (`€1,234.50`).
+    #[must_use]
+    pub fn format_locale(&self, locale: &str) -> String {
+        let language = locale
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let (group, decimal, symbol_first) = match language.as_str() {
+            "de" | "es" | "it" | "nl" | "pt" => (".", ",", false),
+            "fr" => ("\u{202f}", ",", false),
+            _ => (",", ".", true),
+        };
+
+        let number = format_number(self.amount, DEFAULT_DECIMAL_PLACES, group, decimal);
+        let symbol = self.currency_code.symbol();
+        if symbol_first {
+            number.strip_prefix('-').map_or_else(
+                || format!("{symbol}{number}"),
+                |abs| format!("-{symbol}{abs}"),
+            )
+        } else {
+            format!("{number}\u{a0}{symbol}")
+        }
+    }
+}
+
+impl Add for Price {
+    type Output = Result<Self, CurrencyMismatch>;
+
+    fn add(self, other: Self) -> Self::Output {
+        same_currency(self, other)?;
+        Ok(Self::new(self.amount + other.amount, self.currency_code))
+    }
+}
+
+impl Sub for Price {
+    type Output = Result<Self, CurrencyMismatch>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        same_currency(self, other)?;
+        Ok(Self::new(self.amount - other.amount, self.currency_code))
+    }
+}
+
+impl Mul<u32> for Price {
+    type Output = Self;
+
+    fn mul(self, quantity: u32) -> Self {
+        Self::new(self.amount * Decimal::from(quantity), self.currency_code)
+    }
+}
+
+impl Div<u32> for Price {
+    type Output = Self;
+
+    /// Split the price into `parts` equal shares (not rounded to cents).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parts` is zero.
+    fn div(self, parts: u32) -> Self {
+        Self::new(self.amount / Decimal::from(parts), self.currency_code)
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimal_places = f
+            .precision()
+            .and_then(|p| u32::try_from(p).ok())
+            .unwrap_or(DEFAULT_DECIMAL_PLACES);
+        let number = format_number(self.amount, decimal_places, ",", ".");
+        let symbol = self.currency_code.symbol();
+        match number.strip_prefix('-') {
+            Some(abs) => write!(f, "-{symbol}{abs}"),
+            None => write!(f, "{symbol}{number}"),
+        }
+    }
 }
 
 /// ISO 4217 currency codes.
@@ -100,3 +184,134 @@ pub enum CurrencyCode {
     CAD,
     AUD,
 }
+
+impl CurrencyCode {
+    /// ISO 4217 code (e.g., "USD").
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::USD => "USD",
+            Self::EUR => "EUR",
+            Self::GBP => "GBP",
+            Self::CAD => "CAD",
+            Self::AUD => "AUD",
+        }
+    }
+
+    /// Currency symbol (e.g., "$").
+    #[must_use]
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Self::USD | Self::CAD | Self::AUD => "$",
+            Self::EUR => "€",
+            Self::GBP => "£",
+        }
+    }
+}
+
+impl fmt::Display for CurrencyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+fn same_currency(left: Price, right: Price) -> Result<(), CurrencyMismatch> {
+    if left.currency_code == right.currency_code {
+        Ok(())
+    } else {
+        Err(CurrencyMismatch {
+            left: left.currency_code,
+            right: right.currency_code,
+        })
+    }
+}
+
+/// Round `amount` half away from zero to `decimal_places` and format it with
+/// the given separators, e.g. `-1,234.50`.
+fn format_number(amount: Decimal, decimal_places: u32, group: &str, decimal: &str) -> String {
+    let mut rounded =
+        amount.round_dp_with_strategy(decimal_places, RoundingStrategy::MidpointAwayFromZero);
+    rounded.rescale(decimal_places);
+
+    let text = rounded.abs().to_string();
+    let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+
+    let mut out = String::with_capacity(text.len() + integer.len() / 3 + 1);
+    if rounded.is_sign_negative() && !rounded.is_zero() {
+        out.push('-');
+    }
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            out.push_str(group);
+        }
+        out.push(digit);
+    }
+    if !fraction.is_empty() {
+        out.push_str(decimal);
+        out.push_str(fraction);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usd(cents: i64) -> Price {
+        Price::new(Decimal::new(cents, 2), CurrencyCode::USD)
+    }
+
+    #[test]
+    fn test_add_and_sub_same_currency() {
+        assert_eq!(usd(1250) + usd(250), Ok(usd(1500)));
+        assert_eq!(usd(1250) - usd(2000), Ok(usd(-750)));
+    }
+
+    #[test]
+    fn test_currency_mismatch() {
+        let euros = Price::new(Decimal::ONE, CurrencyCode::EUR);
+        let expected = Err(CurrencyMismatch {
+            left: CurrencyCode::USD,
+            right: CurrencyCode::EUR,
+        });
+        assert_eq!(usd(100) + euros, expected);
+        assert_eq!(usd(100) - euros, expected);
+        assert_eq!(usd(100).checked_sub(euros), None);
+    }
+
+    #[test]
+    fn test_mul_and_div() {
+        assert_eq!(usd(1250) * 3, usd(3750));
+        assert_eq!(usd(3000) / 4, usd(750));
+    }
+
+    #[test]
+    fn test_checked_sub() {
+        assert_eq!(usd(2500).checked_sub(usd(1000)), Some(usd(1500)));
+        assert_eq!(usd(2500).checked_sub(usd(2500)), Some(usd(0)));
+        assert_eq!(usd(2500).checked_sub(usd(2501)), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(usd(5).to_string(), "$0.05");
+        assert_eq!(usd(123_450).to_string(), "$1,234.50");
+        assert_eq!(usd(-100_000_000).to_string(), "-$1,000,000.00");
+        assert_eq!(format!("{:.0}", usd(1250)), "$13");
+        assert_eq!(
+            Price::new(Decimal::new(10, 0), CurrencyCode::GBP).to_string(),
+            "£10.00"
+        );
+        // Rounded for display, half away from zero
+        assert_eq!((usd(1) / 2).to_string(), "$0.01");
+    }
+
+    #[test]
+    fn test_format_locale() {
+        let price = Price::new(Decimal::new(123_450, 2), CurrencyCode::EUR);
+        assert_eq!(price.format_locale("en-US"), "€1,234.50");
+        assert_eq!(price.format_locale("de-DE"), "1.234,50\u{a0}€");
+        assert_eq!(price.format_locale("fr_FR"), "1\u{202f}234,50\u{a0}€");
+        assert_eq!(price.format_locale(""), "€1,234.50");
+    }
+}
@@ -0,0 +1,88 @@
+//! Test configuration from environment variables.
+
+use naked_pineapple_admin::config::ShopifyAdminConfig;
+use naked_pineapple_admin::shopify::SHOPIFY_API_VERSION;
+use secrecy::SecretString;
+
+/// Configuration for an integration test run.
+///
+/// # Environment Variables
+///
+/// - `STOREFRONT_BASE_URL` - Running storefront (default `http://localhost:3000`)
+/// - `ADMIN_BASE_URL` - Running admin (default `http://localhost:3001`)
+/// - `STOREFRONT_DATABASE_URL` - Storefront test database (falls back to `DATABASE_URL`)
+/// - `ADMIN_DATABASE_URL` - Admin test database (falls back to `DATABASE_URL`)
+/// - `SHOPIFY_STORE`, `SHOPIFY_ADMIN_CLIENT_ID`, `SHOPIFY_ADMIN_CLIENT_SECRET` -
+///   Sandbox store app, as for the admin server
+/// - `SHOPIFY_ADMIN_ACCESS_TOKEN` - Admin API token for the sandbox store, so
+///   tests don't need the OAuth flow
+#[derive(Debug, Clone)]
+pub struct TestConfig {
+    /// Storefront base URL.
+    pub storefront_url: String,
+    /// Admin base URL.
+    pub admin_url: String,
+    /// Storefront database connection string.
+    pub storefront_database_url: SecretString,
+    /// Admin database connection string.
+    pub admin_database_url: SecretString,
+    /// Sandbox store app configuration.
+    pub shopify: ShopifyAdminConfig,
+    /// Admin API access token for the sandbox store.
+    pub shopify_access_token: SecretString,
+}
+
+impl TestConfig {
+    /// Read the configuration from the environment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a required variable is missing, naming the variable.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self {
+            storefront_url: env_or("STOREFRONT_BASE_URL", "http://localhost:3000"),
+            admin_url: env_or("ADMIN_BASE_URL", "http://localhost:3001"),
+            storefront_database_url: database_url("STOREFRONT_DATABASE_URL"),
+            admin_database_url: database_url("ADMIN_DATABASE_URL"),
+            shopify: ShopifyAdminConfig::from_env()
+                .unwrap_or_else(|e| panic!("Invalid Shopify test configuration: {e}")),
+            shopify_access_token: SecretString::from(required("SHOPIFY_ADMIN_ACCESS_TOKEN")),
+        }
+    }
+
+    /// Placeholder configuration for a mock context: a fake store and
+    /// credentials, and empty database URLs (mock contexts don't connect).
+    pub(crate) fn mock() -> Self {
+        Self {
+            storefront_url: "http://localhost:3000".to_string(),
+            admin_url: "http://localhost:3001".to_string(),
+            storefront_database_url: SecretString::from(""),
+            admin_database_url: SecretString::from(""),
+            shopify: ShopifyAdminConfig {
+                store: "test.myshopify.com".to_string(),
+                api_version: SHOPIFY_API_VERSION.to_string(),
+                client_id: "test_client_id".to_string(),
+                client_secret: SecretString::from("test_client_secret"),
+            },
+            shopify_access_token: SecretString::from("test_access_token"),
+        }
+    }
+}
+
+fn env_or(name: &str, default: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| default.to_string())
+}
+
+fn required(name: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| panic!("{name} must be set for integration tests"))
+}
+
+/// A database URL variable, with the same `DATABASE_URL` fallback as
+/// `np-cli migrate`.
+fn database_url(name: &str) -> SecretString {
+    let url = std::env::var(name)
+        .or_else(|_| std::env::var("DATABASE_URL"))
+        .unwrap_or_else(|_| panic!("{name} (or DATABASE_URL) must be set for integration tests"));
+    SecretString::from(url)
+}
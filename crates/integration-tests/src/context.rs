@@ -0,0 +1,481 @@
+//! Shared state for an integration test.
+
+use std::sync::{Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use naked_pineapple_admin::shopify::{
+    AdminClient, AdminClientConfig, DraftOrderLineItemInput, OAuthToken, Order, RetryPolicy,
+    VariantUpdateInput,
+};
+use naked_pineapple_core::{CustomerId, OrderId, ProductId};
+use reqwest::Client;
+use secrecy::{ExposeSecret, SecretString};
+use sqlx::PgPool;
+use sqlx::migrate::Migrator;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use uuid::Uuid;
+
+use crate::config::TestConfig;
+use crate::fixtures::{TestCustomer, TestFixtures, TestProduct};
+use crate::mock::MockShopifyServer;
+
+/// Embedded storefront migrations.
+static STOREFRONT_MIGRATOR: Migrator = sqlx::migrate!("../storefront/migrations");
+
+/// Embedded admin migrations.
+static ADMIN_MIGRATOR: Migrator = sqlx::migrate!("../admin/migrations");
+
+/// Tag put on everything the tests create, so leftovers are easy to find.
+const TEST_TAG: &str = "integration-test";
+
+/// Header the storefront expects the CSRF token in.
+const CSRF_HEADER: &str = "X-CSRF-Token";
+
+/// Where the storefront's base layout exposes the session's CSRF token.
+const CSRF_TOKEN_MARKER: &str = "window.csrfToken = '";
+
+/// A sandbox store resource to remove when the test ends.
+#[derive(Debug)]
+enum TestResource {
+    Product(ProductId),
+    Customer(CustomerId),
+    Order(OrderId),
+}
+
+/// Clients, databases and created resources for one test.
+///
+/// Everything created through the `create_test_*` helpers is registered for
+/// cleanup. Call [`cleanup`](Self::cleanup) at the end of the test; if the
+/// test panics first, `Drop` runs the cleanup instead, which needs the
+/// multi-threaded runtime (`#[tokio::test(flavor = "multi_thread")]`).
+///
+/// [`with_mock`](Self::with_mock) builds an offline context instead, with
+/// the Admin API client pointed at a [`MockShopifyServer`] and no databases.
+pub struct TestContext {
+    /// Configuration the context was built from.
+    pub config: TestConfig,
+    /// HTTP client for the running storefront, with a cookie store so the
+    /// session (and cart) persists across requests.
+    pub storefront_client: Client,
+    /// Admin API client for the sandbox store.
+    pub admin_client: AdminClient,
+    /// Storefront database, migrated (`None` in mock contexts).
+    pub storefront_pool: Option<PgPool>,
+    /// Admin database, migrated (`None` in mock contexts).
+    pub admin_pool: Option<PgPool>,
+    /// Products seeded from [`TestFixtures`].
+    pub products: Vec<TestProduct>,
+    /// Customers seeded from [`TestFixtures`].
+    pub customers: Vec<TestCustomer>,
+    mock: Option<MockShopifyServer>,
+    resources: Mutex<Vec<TestResource>>,
+}
+
+impl TestContext {
+    /// Set up a context without fixtures.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configuration is incomplete or a database can't be
+    /// connected to or migrated.
+    pub async fn new() -> Self {
+        Self::with_fixtures(TestFixtures::new()).await
+    }
+
+    /// Set up a context and seed `fixtures` into the sandbox store.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configuration is incomplete, a database can't be
+    /// connected to or migrated, or a fixture can't be created.
+    pub async fn with_fixtures(fixtures: TestFixtures) -> Self {
+        let config = TestConfig::from_env();
+
+        let storefront_pool = connect(&config.storefront_database_url, &STOREFRONT_MIGRATOR).await;
+        let admin_pool = connect(&config.admin_database_url, &ADMIN_MIGRATOR).await;
+
+        let admin_client =
+            AdminClient::new(&config.shopify).expect("Failed to create Admin API client");
+        set_token(&admin_client, &config).await;
+
+        let mut ctx = Self {
+            config,
+            storefront_client: storefront_client(),
+            admin_client,
+            storefront_pool: Some(storefront_pool),
+            admin_pool: Some(admin_pool),
+            products: Vec::new(),
+            customers: Vec::new(),
+            mock: None,
+            resources: Mutex::default(),
+        };
+
+        for price in &fixtures.product_prices {
+            let product = ctx.create_test_product(price).await;
+            ctx.products.push(product);
+        }
+        for _ in 0..fixtures.customers {
+            let customer = ctx.create_test_customer().await;
+            ctx.customers.push(customer);
+        }
+
+        ctx
+    }
+
+    /// Set up an offline context whose Admin API client talks to a
+    /// [`MockShopifyServer`].
+    ///
+    /// Nothing is read from the environment and no database is used. The
+    /// client doesn't retry, so an unmocked operation fails immediately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Admin API client can't be created.
+    pub async fn with_mock() -> Self {
+        let config = TestConfig::mock();
+        let mock = MockShopifyServer::start().await;
+
+        let http = AdminClientConfig::default()
+            .rate_limit_retry(RetryPolicy::none())
+            .server_error_retry(RetryPolicy::none());
+        let admin_client = AdminClient::with_base_url(&config.shopify, http, mock.uri())
+            .expect("Failed to create Admin API client");
+        set_token(&admin_client, &config).await;
+
+        Self {
+            config,
+            storefront_client: storefront_client(),
+            admin_client,
+            storefront_pool: None,
+            admin_pool: None,
+            products: Vec::new(),
+            customers: Vec::new(),
+            mock: Some(mock),
+            resources: Mutex::default(),
+        }
+    }
+
+    /// The mock server behind a [`with_mock`](Self::with_mock) context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the context talks to the sandbox store instead.
+    #[must_use]
+    pub const fn mock(&self) -> &MockShopifyServer {
+        self.mock
+            .as_ref()
+            .expect("TestContext was not created with with_mock()")
+    }
+
+    // =========================================================================
+    // Sandbox Store Helpers
+    // =========================================================================
+
+    /// Create an active single-variant product priced at `price`, published
+    /// to every sales channel so the storefront can sell it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any Admin API call fails.
+    pub async fn create_test_product(&self, price: &str) -> TestProduct {
+        let title = format!("Integration Test Product {}", Uuid::new_v4());
+        let id = self
+            .admin_client
+            .create_product(
+                &title,
+                None,
+                Some("Integration Tests"),
+                None,
+                vec![TEST_TAG.to_string()],
+                "ACTIVE",
+            )
+            .await
+            .expect("Failed to create test product")
+            .parse::<ProductId>()
+            .expect("Shopify returned an invalid product GID");
+        self.register(TestResource::Product(id.clone()));
+
+        let variant_id = self
+            .admin_client
+            .get_product(&id)
+            .await
+            .expect("Failed to fetch test product")
+            .and_then(|p| p.variants.into_iter().next())
+            .map(|v| v.id)
+            .expect("Test product has no variant");
+
+        self.admin_client
+            .update_variant(
+                &id,
+                &variant_id,
+                VariantUpdateInput {
+                    price: Some(price),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("Failed to set test product price");
+
+        let publication_ids: Vec<String> = self
+            .admin_client
+            .get_publications()
+            .await
+            .expect("Failed to list publications")
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+        self.admin_client
+            .publish_product(&id, &publication_ids)
+            .await
+            .expect("Failed to publish test product");
+
+        TestProduct {
+            id,
+            variant_id,
+            price: price.to_string(),
+        }
+    }
+
+    /// Create a customer with a unique `example.com` email address.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Admin API call fails.
+    pub async fn create_test_customer(&self) -> TestCustomer {
+        let email = format!("integration-test+{}@example.com", Uuid::new_v4());
+        let id = self
+            .admin_client
+            .create_customer(
+                &email,
+                Some("Integration"),
+                Some("Test"),
+                None,
+                None,
+                vec![TEST_TAG.to_string()],
+            )
+            .await
+            .expect("Failed to create test customer")
+            .parse::<CustomerId>()
+            .expect("Shopify returned an invalid customer GID");
+        self.register(TestResource::Customer(id.clone()));
+
+        TestCustomer { id, email }
+    }
+
+    /// Create a paid order for `customer`.
+    ///
+    /// Checkout can't be completed through the Storefront API, so the order
+    /// is built as a draft order and completed, which marks it as paid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any Admin API call fails.
+    pub async fn create_test_order(
+        &self,
+        customer: &TestCustomer,
+        lines: &[(&TestProduct, i64)],
+    ) -> Order {
+        let line_items = lines
+            .iter()
+            .map(|(product, quantity)| DraftOrderLineItemInput::Variant {
+                variant_id: product.variant_id.clone(),
+                quantity: *quantity,
+            })
+            .collect();
+
+        let draft_order = self
+            .admin_client
+            .create_draft_order(
+                line_items,
+                Some(&customer.id),
+                Some("Created by integration tests"),
+                vec![TEST_TAG.to_string()],
+                None,
+            )
+            .await
+            .expect("Failed to create test draft order");
+
+        let order = self
+            .admin_client
+            .complete_draft_order(&draft_order.id)
+            .await
+            .expect("Failed to complete test draft order");
+        self.register(TestResource::Order(order.id.clone()));
+
+        order
+    }
+
+    // =========================================================================
+    // Storefront Helpers
+    // =========================================================================
+
+    /// Add `quantity` of a variant to the storefront session's cart.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storefront can't be reached or rejects the request.
+    pub async fn add_to_cart(&self, variant_id: &str, quantity: u32) {
+        let token = self.storefront_csrf_token().await;
+        let resp = self
+            .storefront_client
+            .post(format!("{}/cart/add", self.config.storefront_url))
+            .header(CSRF_HEADER, token)
+            .form(&[
+                ("variant_id", variant_id.to_string()),
+                ("quantity", quantity.to_string()),
+            ])
+            .send()
+            .await
+            .expect("Failed to add to cart");
+
+        assert!(
+            resp.status().is_success(),
+            "Add to cart failed with {}",
+            resp.status()
+        );
+    }
+
+    /// Number of items in the storefront session's cart.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storefront can't be reached.
+    pub async fn cart_count(&self) -> u32 {
+        let body = self
+            .storefront_client
+            .get(format!("{}/cart/count", self.config.storefront_url))
+            .send()
+            .await
+            .expect("Failed to get cart count")
+            .text()
+            .await
+            .expect("Failed to read cart count");
+
+        // `<span id="cart-count" ...>3</span>`; the span is empty for an empty cart.
+        body.split_once('>')
+            .and_then(|(_, rest)| rest.split_once('<'))
+            .and_then(|(count, _)| count.trim().parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// The storefront session's CSRF token, read from the cart page.
+    async fn storefront_csrf_token(&self) -> String {
+        let page = self
+            .storefront_client
+            .get(format!("{}/cart", self.config.storefront_url))
+            .send()
+            .await
+            .expect("Failed to load cart page")
+            .text()
+            .await
+            .expect("Failed to read cart page");
+
+        page.split_once(CSRF_TOKEN_MARKER)
+            .and_then(|(_, rest)| rest.split_once('\''))
+            .map(|(token, _)| token.to_string())
+            .expect("Cart page has no CSRF token")
+    }
+
+    // =========================================================================
+    // Cleanup
+    // =========================================================================
+
+    /// Remove everything the test created from the sandbox store.
+    ///
+    /// Products and customers are deleted. Orders can't be deleted, so
+    /// they're archived; Shopify also refuses to delete customers with
+    /// orders, which is logged and otherwise ignored.
+    pub async fn cleanup(&self) {
+        let resources = std::mem::take(
+            &mut *self
+                .resources
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        cleanup_resources(&self.admin_client, resources).await;
+    }
+
+    fn register(&self, resource: TestResource) {
+        // Nothing to clean up on a mock server
+        if self.mock.is_some() {
+            return;
+        }
+        self.resources
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(resource);
+    }
+}
+
+impl Drop for TestContext {
+    fn drop(&mut self) {
+        let resources = std::mem::take(
+            self.resources
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        if resources.is_empty() {
+            return;
+        }
+
+        match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| {
+                    handle.block_on(cleanup_resources(&self.admin_client, resources));
+                });
+            }
+            _ => tracing::warn!(
+                ?resources,
+                "TestContext dropped outside a multi-threaded runtime; \
+                 sandbox resources were not cleaned up"
+            ),
+        }
+    }
+}
+
+fn storefront_client() -> Client {
+    Client::builder()
+        .cookie_store(true)
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+async fn set_token(client: &AdminClient, config: &TestConfig) {
+    client
+        .set_token(OAuthToken {
+            access_token: config.shopify_access_token.expose_secret().to_string(),
+            scope: String::new(),
+            obtained_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX)),
+            shop: config.shopify.store.clone(),
+        })
+        .await;
+}
+
+/// Connect to a test database and bring it up to date.
+async fn connect(url: &SecretString, migrator: &Migrator) -> PgPool {
+    let pool = PgPool::connect(url.expose_secret())
+        .await
+        .expect("Failed to connect to test database");
+    migrator
+        .run(&pool)
+        .await
+        .expect("Failed to run test database migrations");
+    pool
+}
+
+/// Remove `resources`, newest first so orders go before the customers and
+/// products they reference.
+async fn cleanup_resources(client: &AdminClient, resources: Vec<TestResource>) {
+    for resource in resources.into_iter().rev() {
+        let result = match &resource {
+            TestResource::Order(id) => client.archive_order(id).await,
+            TestResource::Customer(id) => client.delete_customer(id).await.map(drop),
+            TestResource::Product(id) => client.delete_product(id).await.map(drop),
+        };
+        if let Err(e) = result {
+            tracing::warn!(?resource, error = %e, "Failed to clean up test resource");
+        }
+    }
+}
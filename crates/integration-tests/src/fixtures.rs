@@ -0,0 +1,60 @@
+//! Fixture data seeded into the sandbox store.
+
+use naked_pineapple_core::{CustomerId, ProductId, VariantId};
+
+/// Fixtures to create when a [`TestContext`](crate::TestContext) starts.
+///
+/// ```rust,ignore
+/// let ctx = TestContext::with_fixtures(
+///     TestFixtures::new().product("12.50").product("4.00").customer(),
+/// )
+/// .await;
+/// let first = &ctx.products[0];
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TestFixtures {
+    pub(crate) product_prices: Vec<String>,
+    pub(crate) customers: usize,
+}
+
+impl TestFixtures {
+    /// No fixtures.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a single-variant product priced at `price` (e.g. `"12.50"`).
+    #[must_use]
+    pub fn product(mut self, price: impl Into<String>) -> Self {
+        self.product_prices.push(price.into());
+        self
+    }
+
+    /// Add a customer with a unique email address.
+    #[must_use]
+    pub const fn customer(mut self) -> Self {
+        self.customers += 1;
+        self
+    }
+}
+
+/// A product created in the sandbox store.
+#[derive(Debug, Clone)]
+pub struct TestProduct {
+    /// Product GID.
+    pub id: ProductId,
+    /// GID of the product's only variant.
+    pub variant_id: VariantId,
+    /// Variant price.
+    pub price: String,
+}
+
+/// A customer created in the sandbox store.
+#[derive(Debug, Clone)]
+pub struct TestCustomer {
+    /// Customer GID.
+    pub id: CustomerId,
+    /// Customer email address.
+    pub email: String,
+}
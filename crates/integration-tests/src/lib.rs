@@ -16,38 +16,39 @@
 //! - `admin` - Admin API tests
 //! - `database` - Database integration tests
 //!
-//! # Future Implementation
+//! # Test Context
 //!
-//! ```rust,ignore
-//! use reqwest::Client;
-//! use sqlx::PgPool;
-//!
-//! pub struct TestContext {
-//!     pub client: Client,
-//!     pub storefront_url: String,
-//!     pub admin_url: String,
-//!     pub storefront_pool: PgPool,
-//!     pub admin_pool: PgPool,
-//! }
-//!
-//! impl TestContext {
-//!     pub async fn new() -> Self {
-//!         // Load test configuration
-//!         // Connect to test databases
-//!         // Start test servers or use existing
-//!     }
-//! }
+//! Tests that touch the sandbox store or the databases build a
+//! [`TestContext`], which reads [`TestConfig`] from the environment, migrates
+//! both databases and seeds any [`TestFixtures`]. Resources created through
+//! the context are removed again by [`TestContext::cleanup`].
 //!
-//! #[tokio::test]
-//! async fn test_storefront_health() {
-//!     let ctx = TestContext::new().await;
-//!     let resp = ctx.client
-//!         .get(&format!("{}/health", ctx.storefront_url))
-//!         .send()
-//!         .await
-//!         .unwrap();
-//!     assert_eq!(resp.status(), 200);
+//! ```rust,ignore
+//! use naked_pineapple_integration_tests::{TestContext, TestFixtures};
+//!
+//! #[tokio::test(flavor = "multi_thread")]
+//! #[ignore = "Requires Shopify sandbox credentials and test databases"]
+//! async fn test_order_for_seeded_customer() {
+//!     let ctx = TestContext::with_fixtures(TestFixtures::new().product("12.50").customer()).await;
+//!     let order = ctx
+//!         .create_test_order(&ctx.customers[0], &[(&ctx.products[0], 1)])
+//!         .await;
+//!     assert_eq!(order.line_items.len(), 1);
+//!     ctx.cleanup().await;
 //! }
 //! ```
+//!
+//! [`TestContext::with_mock`] runs offline instead, answering Admin API
+//! requests from canned fixtures in `fixtures/shopify/` through a
+//! [`MockShopifyServer`]. Those tests aren't ignored and run with the rest
+//! of the suite.
+
+mod config;
+mod context;
+mod fixtures;
+mod mock;
 
-// TODO: Implement integration test framework
+pub use config::TestConfig;
+pub use context::TestContext;
+pub use fixtures::{TestCustomer, TestFixtures, TestProduct};
+pub use mock::{MockShopifyServer, load_fixture};
@@ -0,0 +1,164 @@
+//! Offline mock of the Shopify Admin API.
+//!
+//! [`MockShopifyServer`] answers GraphQL requests by operation name with
+//! canned `data`, so `AdminClient` methods can be tested without a sandbox
+//! store. Fixtures are checked against the query's generated response type
+//! when they're mounted, so a fixture that drifts from its query fails with
+//! a deserialization error instead of a confusing assertion later.
+//!
+//! ```rust,ignore
+//! let ctx = TestContext::with_mock().await;
+//! ctx.mock()
+//!     .mount_graphql_response::<GetProduct>("GetProduct", load_fixture("get_product.json"))
+//!     .await;
+//!
+//! let product = ctx.admin_client.get_product(&"gid://shopify/Product/1".parse()?).await?;
+//!
+//! ctx.mock().verify().await;
+//! ```
+
+use std::path::Path;
+
+use graphql_client::GraphQLQuery;
+use naked_pineapple_admin::shopify::SHOPIFY_API_VERSION;
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Match, Mock, MockServer, Request, ResponseTemplate};
+
+/// Directory canned responses are loaded from by [`load_fixture`].
+const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/shopify");
+
+/// A local HTTP server standing in for the Shopify Admin GraphQL endpoint.
+pub struct MockShopifyServer {
+    server: MockServer,
+}
+
+impl MockShopifyServer {
+    /// Start a server on a random local port.
+    pub async fn start() -> Self {
+        Self {
+            server: MockServer::start().await,
+        }
+    }
+
+    /// Base URL to point `AdminClient::with_base_url` at.
+    #[must_use]
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// Respond to every `operation_name` request with `response_data` as the
+    /// GraphQL `data` object.
+    ///
+    /// The mount is expected to be hit at least once; see
+    /// [`verify`](Self::verify).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `response_data` doesn't deserialize as `Q`'s response data.
+    pub async fn mount_graphql_response<Q: GraphQLQuery>(
+        &self,
+        operation_name: &str,
+        response_data: Value,
+    ) {
+        if let Err(e) = serde_json::from_value::<Q::ResponseData>(response_data.clone()) {
+            panic!("Fixture for {operation_name} doesn't match the query: {e}");
+        }
+
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/admin/api/{SHOPIFY_API_VERSION}/graphql.json"
+            )))
+            .and(OperationName(operation_name.to_string()))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "data": response_data })),
+            )
+            .expect(1..)
+            .named(operation_name)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Operation names of all GraphQL requests received, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if request recording is disabled (it's on by default).
+    pub async fn received_operations(&self) -> Vec<String> {
+        self.received_requests()
+            .await
+            .iter()
+            .filter_map(|body| body.get("operationName")?.as_str().map(String::from))
+            .collect()
+    }
+
+    /// Variables of each received `operation_name` request, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if request recording is disabled (it's on by default).
+    pub async fn variables_for(&self, operation_name: &str) -> Vec<Value> {
+        self.received_requests()
+            .await
+            .into_iter()
+            .filter(|body| {
+                body.get("operationName").and_then(Value::as_str) == Some(operation_name)
+            })
+            .map(|mut body| {
+                body.get_mut("variables")
+                    .map(Value::take)
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Assert that every mounted response was requested at least once.
+    ///
+    /// # Panics
+    ///
+    /// Panics, listing the unused mounts, if any wasn't requested.
+    pub async fn verify(&self) {
+        self.server.verify().await;
+    }
+
+    /// JSON bodies of all received requests.
+    async fn received_requests(&self) -> Vec<Value> {
+        self.server
+            .received_requests()
+            .await
+            .expect("Request recording is disabled")
+            .iter()
+            .filter_map(|request| serde_json::from_slice(&request.body).ok())
+            .collect()
+    }
+}
+
+/// Load a canned response from `fixtures/shopify/`.
+///
+/// # Panics
+///
+/// Panics if the file doesn't exist or isn't valid JSON.
+#[must_use]
+pub fn load_fixture(name: &str) -> Value {
+    let file = Path::new(FIXTURE_DIR).join(name);
+    let contents = std::fs::read_to_string(&file)
+        .unwrap_or_else(|e| panic!("Failed to read fixture {}: {e}", file.display()));
+    serde_json::from_str(&contents)
+        .unwrap_or_else(|e| panic!("Invalid JSON in fixture {}: {e}", file.display()))
+}
+
+/// Matches GraphQL requests by the `operationName` in their JSON body.
+struct OperationName(String);
+
+impl Match for OperationName {
+    fn matches(&self, request: &Request) -> bool {
+        serde_json::from_slice::<Value>(&request.body)
+            .ok()
+            .and_then(|body| {
+                body.get("operationName")?
+                    .as_str()
+                    .map(|name| name == self.0)
+            })
+            .unwrap_or(false)
+    }
+}
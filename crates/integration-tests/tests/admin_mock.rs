@@ -0,0 +1,176 @@
+//! Offline tests for `AdminClient` against a mocked Admin API.
+//!
+//! Responses come from canned fixtures in `fixtures/shopify/`, so these run
+//! without a sandbox store or database.
+
+use naked_pineapple_admin::shopify::admin::queries::{FulfillmentCreate, GetOrder, GetProduct};
+use naked_pineapple_admin::shopify::{
+    AdminShopifyError, FinancialStatus, FulfillmentStatus, ProductStatus,
+};
+use naked_pineapple_core::CustomerId;
+use naked_pineapple_integration_tests::{TestContext, load_fixture};
+use serde_json::json;
+
+// =============================================================================
+// Products
+// =============================================================================
+
+#[tokio::test]
+async fn test_get_product() {
+    let ctx = TestContext::with_mock().await;
+    ctx.mock()
+        .mount_graphql_response::<GetProduct>("GetProduct", load_fixture("get_product.json"))
+        .await;
+
+    let product = ctx
+        .admin_client
+        .get_product(&"gid://shopify/Product/1001".parse().expect("Invalid GID"))
+        .await
+        .expect("Failed to get product")
+        .expect("Product not found");
+
+    assert_eq!(product.title, "Pineapple Hot Sauce");
+    assert_eq!(product.status, ProductStatus::Active);
+    assert_eq!(product.tags, vec!["hot-sauce", "bestseller"]);
+
+    let variant = product.variants.first().expect("Product has no variants");
+    assert_eq!(variant.id.as_str(), "gid://shopify/ProductVariant/2001");
+    assert_eq!(variant.sku.as_deref(), Some("NP-HS-5"));
+    assert_eq!(variant.price.amount, "12.50");
+
+    let variables = ctx.mock().variables_for("GetProduct").await;
+    assert_eq!(
+        variables,
+        vec![json!({ "id": "gid://shopify/Product/1001", "mediaCount": 10, "variantCount": 50 })]
+    );
+
+    ctx.mock().verify().await;
+}
+
+#[tokio::test]
+async fn test_get_product_not_found() {
+    let ctx = TestContext::with_mock().await;
+    ctx.mock()
+        .mount_graphql_response::<GetProduct>("GetProduct", json!({ "product": null }))
+        .await;
+
+    let product = ctx
+        .admin_client
+        .get_product(&"gid://shopify/Product/404".parse().expect("Invalid GID"))
+        .await
+        .expect("Failed to get product");
+
+    assert!(product.is_none());
+    ctx.mock().verify().await;
+}
+
+// =============================================================================
+// Orders
+// =============================================================================
+
+#[tokio::test]
+async fn test_get_order() {
+    let ctx = TestContext::with_mock().await;
+    ctx.mock()
+        .mount_graphql_response::<GetOrder>("GetOrder", load_fixture("get_order.json"))
+        .await;
+
+    let order = ctx
+        .admin_client
+        .get_order(&"gid://shopify/Order/7001".parse().expect("Invalid GID"))
+        .await
+        .expect("Failed to get order")
+        .expect("Order not found");
+
+    assert_eq!(order.name, "#1042");
+    assert_eq!(order.financial_status, Some(FinancialStatus::Paid));
+    assert_eq!(
+        order.fulfillment_status,
+        Some(FulfillmentStatus::Unfulfilled)
+    );
+    assert_eq!(order.total_price.amount, "32.25");
+    assert_eq!(
+        order.customer_id.as_ref().map(CustomerId::as_str),
+        Some("gid://shopify/Customer/8001")
+    );
+
+    let line_item = order.line_items.first().expect("Order has no line items");
+    assert_eq!(line_item.quantity, 2);
+    assert_eq!(line_item.sku.as_deref(), Some("NP-HS-5"));
+
+    assert_eq!(ctx.mock().received_operations().await, vec!["GetOrder"]);
+    ctx.mock().verify().await;
+}
+
+// =============================================================================
+// Fulfillments
+// =============================================================================
+
+#[tokio::test]
+async fn test_create_fulfillment() {
+    let ctx = TestContext::with_mock().await;
+    ctx.mock()
+        .mount_graphql_response::<FulfillmentCreate>(
+            "FulfillmentCreate",
+            load_fixture("fulfillment_create.json"),
+        )
+        .await;
+
+    let fulfillment_id = ctx
+        .admin_client
+        .create_fulfillment(
+            "gid://shopify/FulfillmentOrder/7201",
+            Some("USPS"),
+            Some("9400100000000000000000"),
+            None,
+        )
+        .await
+        .expect("Failed to create fulfillment");
+
+    assert_eq!(fulfillment_id, "gid://shopify/Fulfillment/9001");
+
+    let variables = ctx.mock().variables_for("FulfillmentCreate").await;
+    let fulfillment = variables
+        .first()
+        .and_then(|v| v.get("fulfillment"))
+        .expect("No fulfillment variables");
+    assert_eq!(
+        fulfillment.pointer("/lineItemsByFulfillmentOrder/0/fulfillmentOrderId"),
+        Some(&json!("gid://shopify/FulfillmentOrder/7201"))
+    );
+    assert_eq!(
+        fulfillment.pointer("/trackingInfo/number"),
+        Some(&json!("9400100000000000000000"))
+    );
+    assert_eq!(fulfillment.get("notifyCustomer"), Some(&json!(true)));
+
+    ctx.mock().verify().await;
+}
+
+#[tokio::test]
+async fn test_create_fulfillment_user_error() {
+    let ctx = TestContext::with_mock().await;
+    ctx.mock()
+        .mount_graphql_response::<FulfillmentCreate>(
+            "FulfillmentCreate",
+            load_fixture("fulfillment_create_user_error.json"),
+        )
+        .await;
+
+    let result = ctx
+        .admin_client
+        .create_fulfillment("gid://shopify/FulfillmentOrder/7201", None, None, None)
+        .await;
+
+    match result {
+        Err(AdminShopifyError::UserError(message)) => {
+            assert_eq!(
+                message,
+                "fulfillment.lineItemsByFulfillmentOrder: Fulfillment order is already closed"
+            );
+        }
+        other => panic!("Expected a user error, got {other:?}"),
+    }
+
+    ctx.mock().verify().await;
+}
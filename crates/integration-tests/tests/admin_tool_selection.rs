@@ -18,8 +18,9 @@ use naked_pineapple_admin::tool_selection::{
 #[test]
 fn test_all_tools_count() {
     let tools = all_shopify_tools();
-    // 15 analytics + 111 low-level Shopify = 126 total
-    assert_eq!(tools.len(), 126, "Should have 126 tools total");
+    // 15 analytics + order and product search + discount code creation
+    // + inventory adjustment + 111 low-level Shopify = 130 total
+    assert_eq!(tools.len(), 130, "Should have 130 tools total");
 }
 
 #[test]
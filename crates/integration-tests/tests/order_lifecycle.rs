@@ -0,0 +1,97 @@
+//! Integration tests for the full order lifecycle against the sandbox store.
+//!
+//! These tests require:
+//! - A running `PostgreSQL` database (task db:start)
+//! - The storefront server running (cargo run -p naked-pineapple-storefront)
+//! - Sandbox store credentials in environment (see `TestConfig`)
+//!
+//! Run with: task test:integration -- --ignored
+
+use naked_pineapple_admin::shopify::{
+    FinancialStatus, FulfillmentStatus, RefundCreateInput, RefundLineItemInput, RefundRestockType,
+};
+use naked_pineapple_integration_tests::{TestContext, TestFixtures};
+
+/// Parse a Shopify decimal string, which drops trailing zeros ("12.5").
+fn amount(value: &str) -> f64 {
+    value.parse().expect("Invalid amount")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "Requires running storefront, test databases and Shopify sandbox credentials"]
+async fn test_order_lifecycle() {
+    let ctx = TestContext::with_fixtures(TestFixtures::new().product("12.50").customer()).await;
+    let product = ctx.products.first().expect("Missing product fixture");
+    let customer = ctx.customers.first().expect("Missing customer fixture");
+
+    // Add to cart
+    ctx.add_to_cart(product.variant_id.as_str(), 2).await;
+    assert_eq!(ctx.cart_count().await, 2);
+
+    // Create order
+    let order = ctx.create_test_order(customer, &[(product, 2)]).await;
+    assert_eq!(order.customer_id.as_ref(), Some(&customer.id));
+    assert_eq!(order.financial_status, Some(FinancialStatus::Paid));
+
+    let line_item = order.line_items.first().expect("Order has no line items");
+    assert_eq!(line_item.quantity, 2);
+    assert!(
+        (amount(&line_item.original_unit_price.amount) - amount(&product.price)).abs()
+            < f64::EPSILON
+    );
+
+    // Fulfill
+    let fulfillment_orders = ctx
+        .admin_client
+        .get_fulfillment_orders(&order.id)
+        .await
+        .expect("Failed to get fulfillment orders");
+    assert!(!fulfillment_orders.is_empty());
+
+    for fulfillment_order in fulfillment_orders.iter().filter(|fo| fo.status == "OPEN") {
+        ctx.admin_client
+            .create_fulfillment(
+                &fulfillment_order.id,
+                Some("Other"),
+                Some("TEST-0001"),
+                None,
+            )
+            .await
+            .expect("Failed to create fulfillment");
+    }
+
+    let fulfilled = ctx
+        .admin_client
+        .get_order(&order.id)
+        .await
+        .expect("Failed to get order")
+        .expect("Order not found");
+    assert_eq!(
+        fulfilled.fulfillment_status,
+        Some(FulfillmentStatus::Fulfilled)
+    );
+
+    // Refund
+    let refund_id = ctx
+        .admin_client
+        .create_refund(
+            &order.id,
+            RefundCreateInput {
+                note: Some("Integration test refund".to_string()),
+                notify: false,
+                line_items: vec![RefundLineItemInput {
+                    line_item_id: line_item.id.clone(),
+                    quantity: line_item.quantity,
+                    restock_type: RefundRestockType::NoRestock,
+                    location_id: None,
+                }],
+                shipping_amount: None,
+                full_shipping_refund: false,
+            },
+        )
+        .await
+        .expect("Failed to create refund");
+    assert!(refund_id.starts_with("gid://shopify/Refund/"));
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,79 @@
+//! SQL injection tests for the public storefront search.
+//!
+//! These tests require:
+//! - The storefront server running (cargo run -p naked-pineapple-storefront)
+//!
+//! Run with: task test:integration
+
+use reqwest::{Client, StatusCode};
+
+/// Base URL for the storefront (configurable via environment).
+fn storefront_base_url() -> String {
+    std::env::var("STOREFRONT_BASE_URL").unwrap_or_else(|_| "http://localhost:3000".to_string())
+}
+
+/// Classic injection payloads. Each is a single search term with no real
+/// words in it, so a correctly parameterized search matches nothing.
+const INJECTION_PAYLOADS: &[&str] = &[
+    "zqxjv'OR'1'='1",
+    "zqxjv'OR'1'='1'--",
+    "zqxjv\"OR\"1\"=\"1",
+    "zqxjv';DROP/**/TABLE/**/storefront.users;--",
+    "zqxjv'/**/UNION/**/SELECT/**/email,password_hash/**/FROM/**/storefront.users--",
+    "zqxjv');SELECT/**/pg_sleep(5);--",
+];
+
+#[tokio::test]
+#[ignore = "Requires running storefront server"]
+async fn test_search_suggest_rejects_sql_injection() {
+    let client = Client::new();
+    let base_url = storefront_base_url();
+
+    for payload in INJECTION_PAYLOADS {
+        let resp = client
+            .get(format!("{base_url}/search/suggest"))
+            .query(&[("q", payload)])
+            .send()
+            .await
+            .expect("Failed to query search suggestions");
+
+        assert_eq!(resp.status(), StatusCode::OK, "payload: {payload}");
+        let body = resp.text().await.expect("Failed to read response");
+
+        assert!(
+            !body.contains("href=\"/products/"),
+            "payload returned products: {payload}"
+        );
+        assert!(
+            !body.contains('@'),
+            "payload leaked an email address: {payload}"
+        );
+    }
+}
+
+#[tokio::test]
+#[ignore = "Requires running storefront server"]
+async fn test_search_page_rejects_sql_injection() {
+    let client = Client::new();
+    let base_url = storefront_base_url();
+
+    for payload in INJECTION_PAYLOADS {
+        let resp = client
+            .get(format!("{base_url}/search"))
+            .query(&[
+                ("q", *payload),
+                ("sort_by", "price-ascending;DROP/**/TABLE/**/x"),
+            ])
+            .send()
+            .await
+            .expect("Failed to load search page");
+
+        assert_eq!(resp.status(), StatusCode::OK, "payload: {payload}");
+        let body = resp.text().await.expect("Failed to read response");
+
+        assert!(
+            body.contains("No results found for"),
+            "payload returned products: {payload}"
+        );
+    }
+}
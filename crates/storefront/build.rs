@@ -2,6 +2,10 @@
 //!
 //! Generates content-based hashes for static assets (CSS) to enable
 //! immutable CDN caching.
+//!
+//! With `FETCH_SHOPIFY_SCHEMA=1`, also checks `graphql/storefront/schema.json`
+//! against Shopify when it is more than 30 days old. Only
+//! `cargo xtask update-schema` changes the checked-in schema.
 
 use std::env;
 use std::fs;
@@ -9,8 +13,68 @@ use std::path::Path;
 
 use sha2::{Digest, Sha256};
 
+// Shared with `cargo xtask update-schema`; only the Storefront API source is used here
+#[allow(dead_code)]
+#[path = "../xtask/src/schema.rs"]
+mod schema;
+
+/// Storefront API version used when `SHOPIFY_API_VERSION` is not set.
+const DEFAULT_API_VERSION: &str = "2026-01";
+
 fn main() {
     hash_css();
+    fetch_schema();
+}
+
+/// Check the Storefront API schema against Shopify if `FETCH_SHOPIFY_SCHEMA=1` and it is stale.
+///
+/// CI leaves the variable unset and builds against the checked-in schema.
+/// The current schema is downloaded to `OUT_DIR` and the build fails if it
+/// differs from the checked-in one, which the `graphql_client` derives read;
+/// the build never rewrites it. Fails the build if `SHOPIFY_STORE` or
+/// `SHOPIFY_STOREFRONT_PRIVATE_TOKEN` is not set.
+fn fetch_schema() {
+    println!("cargo:rerun-if-env-changed={}", schema::FETCH_ENV);
+    if env::var(schema::FETCH_ENV).as_deref() != Ok("1") {
+        return;
+    }
+    println!("cargo:rerun-if-env-changed=SHOPIFY_STORE");
+    println!("cargo:rerun-if-env-changed=SHOPIFY_API_VERSION");
+    println!(
+        "cargo:rerun-if-env-changed={}",
+        schema::STOREFRONT.token_env
+    );
+
+    let manifest_dir =
+        env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR must be set by Cargo");
+    let path = Path::new(&manifest_dir).join("graphql/storefront/schema.json");
+    println!("cargo:rerun-if-changed={}", path.display());
+    if schema::is_fresh(&path, schema::MAX_AGE) {
+        return;
+    }
+
+    let version = env::var("SHOPIFY_API_VERSION")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| DEFAULT_API_VERSION.to_string());
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR must be set by Cargo");
+    let fetched = Path::new(&out_dir).join("schema.json");
+    if let Err(e) = schema::STOREFRONT.update(&version, &fetched) {
+        panic!(
+            "{}=1 but the schema could not be checked: {e}",
+            schema::FETCH_ENV
+        );
+    }
+
+    let current = fs::read(&fetched).expect("Failed to read the downloaded schema");
+    assert!(
+        fs::read(&path).is_ok_and(|checked_in| checked_in == current),
+        "{} is out of date with Storefront API {version}. Run \
+         `cargo xtask update-schema storefront` to update it, or unset {} to build against \
+         it as is.",
+        path.display(),
+        schema::FETCH_ENV
+    );
 }
 
 /// Hash main.css and copy to derived directory with hash in filename.
@@ -0,0 +1,3 @@
+-- Revert back_in_stock_subscriptions table creation
+
+DROP TABLE IF EXISTS storefront.back_in_stock_subscriptions;
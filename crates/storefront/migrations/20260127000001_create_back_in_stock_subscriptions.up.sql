@@ -0,0 +1,23 @@
+-- Create back_in_stock_subscriptions table for restock email notifications
+-- Customers subscribe to an out-of-stock variant and are notified once it
+-- becomes available again. notified_at is set after the notification is sent.
+
+SET search_path TO storefront, public;
+
+CREATE TABLE storefront.back_in_stock_subscriptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    email VARCHAR(255) NOT NULL,
+    variant_id VARCHAR(255) NOT NULL,
+    product_handle VARCHAR(255) NOT NULL,
+    subscribed_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc'),
+    notified_at TIMESTAMPTZ
+);
+
+-- Only one pending subscription per email/variant pair
+CREATE UNIQUE INDEX idx_back_in_stock_subscriptions_pending
+    ON storefront.back_in_stock_subscriptions (lower(email), variant_id)
+    WHERE notified_at IS NULL;
+
+CREATE INDEX idx_back_in_stock_subscriptions_variant_id
+    ON storefront.back_in_stock_subscriptions (variant_id)
+    WHERE notified_at IS NULL;
@@ -0,0 +1,3 @@
+-- Revert ab_test_assignments table creation
+
+DROP TABLE IF EXISTS storefront.ab_test_assignments;
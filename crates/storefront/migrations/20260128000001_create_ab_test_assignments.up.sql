@@ -0,0 +1,16 @@
+-- Create ab_test_assignments table for experiment analysis
+-- One row is recorded the first time a session is assigned a variant of an
+-- experiment; later requests reuse the assignment cached in the session.
+
+SET search_path TO storefront, public;
+
+CREATE TABLE storefront.ab_test_assignments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    experiment VARCHAR(255) NOT NULL,
+    variant VARCHAR(255) NOT NULL,
+    session_id VARCHAR(255) NOT NULL,
+    assigned_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc')
+);
+
+CREATE INDEX idx_ab_test_assignments_experiment
+    ON storefront.ab_test_assignments (experiment, variant);
@@ -0,0 +1,6 @@
+-- Revert read-only role creation
+
+ALTER DEFAULT PRIVILEGES IN SCHEMA storefront REVOKE SELECT ON TABLES FROM np_storefront_reader;
+REVOKE SELECT ON ALL TABLES IN SCHEMA storefront FROM np_storefront_reader;
+REVOKE USAGE ON SCHEMA storefront FROM np_storefront_reader;
+DROP ROLE IF EXISTS np_storefront_reader;
@@ -0,0 +1,19 @@
+-- Create a read-only role for STOREFRONT_DATABASE_READ_URL connections
+-- The role can't log in; grant it to a login user created outside migrations
+-- (so no password is committed), e.g.:
+--   CREATE ROLE np_storefront_reader_app LOGIN PASSWORD '...' IN ROLE np_storefront_reader;
+-- Roles replicate to physical read replicas along with the data.
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = 'np_storefront_reader') THEN
+        CREATE ROLE np_storefront_reader NOLOGIN;
+    END IF;
+END
+$$;
+
+GRANT USAGE ON SCHEMA storefront TO np_storefront_reader;
+GRANT SELECT ON ALL TABLES IN SCHEMA storefront TO np_storefront_reader;
+
+-- Tables created by later migrations are readable too
+ALTER DEFAULT PRIVILEGES IN SCHEMA storefront GRANT SELECT ON TABLES TO np_storefront_reader;
@@ -0,0 +1,22 @@
+-- Revert carts table creation, restoring shopify_cart_cache
+
+SET search_path TO storefront, public;
+
+CREATE TABLE storefront.shopify_cart_cache (
+    session_id TEXT PRIMARY KEY,
+    shopify_cart_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc'),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc')
+);
+
+CREATE TRIGGER shopify_cart_cache_updated_at
+    BEFORE UPDATE ON storefront.shopify_cart_cache
+    FOR EACH ROW
+    EXECUTE FUNCTION storefront.update_updated_at_column();
+
+INSERT INTO storefront.shopify_cart_cache (session_id, shopify_cart_id, created_at, updated_at)
+SELECT session_id, shopify_cart_id, created_at, updated_at
+FROM storefront.carts;
+
+DROP TRIGGER IF EXISTS carts_updated_at ON storefront.carts;
+DROP TABLE IF EXISTS storefront.carts;
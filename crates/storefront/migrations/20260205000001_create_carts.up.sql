@@ -0,0 +1,28 @@
+-- Create carts table to persist each session's cart
+-- Replaces shopify_cart_cache, which only stored the Shopify cart ID. The
+-- line snapshot lets a cart be rebuilt after Shopify expires it. Rows are
+-- deleted along with their session.
+
+SET search_path TO storefront, public;
+
+CREATE TABLE storefront.carts (
+    cart_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    session_id TEXT NOT NULL UNIQUE REFERENCES storefront.sessions(id) ON DELETE CASCADE,
+    shopify_cart_id TEXT NOT NULL,
+    line_items JSONB NOT NULL DEFAULT '[]'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc'),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'utc')
+);
+
+CREATE TRIGGER carts_updated_at
+    BEFORE UPDATE ON storefront.carts
+    FOR EACH ROW
+    EXECUTE FUNCTION storefront.update_updated_at_column();
+
+INSERT INTO storefront.carts (session_id, shopify_cart_id, created_at, updated_at)
+SELECT c.session_id, c.shopify_cart_id, c.created_at, c.updated_at
+FROM storefront.shopify_cart_cache c
+JOIN storefront.sessions s ON s.id = c.session_id;
+
+DROP TRIGGER IF EXISTS shopify_cart_cache_updated_at ON storefront.shopify_cart_cache;
+DROP TABLE IF EXISTS storefront.shopify_cart_cache;
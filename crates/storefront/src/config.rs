@@ -13,9 +13,12 @@
 //! - `SHOPIFY_CUSTOMER_CLIENT_SECRET` - Customer Account API OAuth client secret
 //!
 //! ## Optional
+//! - `STOREFRONT_DATABASE_READ_URL` - `PostgreSQL` read replica connection string (default: primary)
 //! - `STOREFRONT_HOST` - Bind address (default: 127.0.0.1)
 //! - `STOREFRONT_PORT` - Listen port (default: 3000)
 //! - `SHOPIFY_API_VERSION` - API version (default: 2026-01)
+//! - `SHOPIFY_WEBHOOK_SECRET` - Secret for verifying Shopify webhook signatures
+//! - `SHOPIFY_STOREFRONT_CACHE_TTL_SECS` - Storefront API response cache TTL (default: 120)
 //! - `GA4_MEASUREMENT_ID` - Google Analytics 4 measurement ID
 //! - `META_PIXEL_ID` - Meta (Facebook) pixel ID
 //! - `TIKTOK_PIXEL_ID` - TikTok pixel ID
@@ -26,6 +29,7 @@
 
 use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
 
 use secrecy::{ExposeSecret, SecretString};
 use thiserror::Error;
@@ -33,6 +37,9 @@ use thiserror::Error;
 const MIN_SESSION_SECRET_LENGTH: usize = 32;
 const MIN_ENTROPY_BITS_PER_CHAR: f64 = 3.3;
 
+/// Default Storefront API response cache TTL (2 minutes).
+const DEFAULT_STOREFRONT_CACHE_TTL_SECS: &str = "120";
+
 /// Blocklist of common placeholder patterns (case-insensitive)
 const PLACEHOLDER_PATTERNS: &[&str] = &[
     "your-",
@@ -67,6 +74,8 @@ pub enum ConfigError {
 pub struct StorefrontConfig {
     /// `PostgreSQL` database connection URL (contains password)
     pub database_url: SecretString,
+    /// `PostgreSQL` read replica connection URL (contains password)
+    pub database_read_url: Option<SecretString>,
     /// IP address to bind the server to
     pub host: IpAddr,
     /// Port to listen on
@@ -141,6 +150,11 @@ pub struct ShopifyStorefrontConfig {
     pub customer_client_id: String,
     /// Customer Account API OAuth client secret
     pub customer_client_secret: SecretString,
+    /// Secret used to verify `X-Shopify-Hmac-Sha256` webhook signatures.
+    /// Webhooks are rejected when this is not configured.
+    pub webhook_secret: Option<SecretString>,
+    /// How long Storefront API responses are cached.
+    pub cache_ttl: Duration,
 }
 
 impl std::fmt::Debug for ShopifyStorefrontConfig {
@@ -153,6 +167,11 @@ impl std::fmt::Debug for ShopifyStorefrontConfig {
             .field("customer_shop_id", &self.customer_shop_id)
             .field("customer_client_id", &self.customer_client_id)
             .field("customer_client_secret", &"[REDACTED]")
+            .field(
+                "webhook_secret",
+                &self.webhook_secret.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("cache_ttl", &self.cache_ttl)
             .finish()
     }
 }
@@ -198,6 +217,8 @@ impl StorefrontConfig {
         let _ = dotenvy::dotenv();
 
         let database_url = get_database_url("STOREFRONT_DATABASE_URL")?;
+        let database_read_url =
+            get_optional_env("STOREFRONT_DATABASE_READ_URL").map(SecretString::from);
         let host = get_env_or_default("STOREFRONT_HOST", "127.0.0.1")
             .parse::<IpAddr>()
             .map_err(|e| {
@@ -227,6 +248,7 @@ impl StorefrontConfig {
 
         Ok(Self {
             database_url,
+            database_read_url,
             host,
             port,
             base_url,
@@ -251,6 +273,18 @@ impl StorefrontConfig {
 
 impl ShopifyStorefrontConfig {
     fn from_env() -> Result<Self, ConfigError> {
+        let cache_ttl_secs = get_env_or_default(
+            "SHOPIFY_STOREFRONT_CACHE_TTL_SECS",
+            DEFAULT_STOREFRONT_CACHE_TTL_SECS,
+        )
+        .parse::<u64>()
+        .map_err(|e| {
+            ConfigError::InvalidEnvVar(
+                "SHOPIFY_STOREFRONT_CACHE_TTL_SECS".to_string(),
+                e.to_string(),
+            )
+        })?;
+
         Ok(Self {
             store: get_required_env("SHOPIFY_STORE")?,
             api_version: get_env_or_default("SHOPIFY_API_VERSION", "2026-01"),
@@ -259,6 +293,8 @@ impl ShopifyStorefrontConfig {
             customer_shop_id: get_required_env("SHOPIFY_CUSTOMER_SHOP_ID")?,
             customer_client_id: get_required_env("SHOPIFY_CUSTOMER_CLIENT_ID")?,
             customer_client_secret: get_validated_secret("SHOPIFY_CUSTOMER_CLIENT_SECRET")?,
+            webhook_secret: get_optional_env("SHOPIFY_WEBHOOK_SECRET").map(SecretString::from),
+            cache_ttl: Duration::from_secs(cache_ttl_secs),
         })
     }
 }
@@ -294,6 +330,18 @@ impl AnalyticsConfig {
             || self.mixpanel_project_token.is_some()
             || self.crazy_egg_account_id.is_some()
     }
+
+    /// ID to load gtag.js with, given the visitor's consent.
+    ///
+    /// GA4 needs analytics consent and Google Ads needs marketing consent;
+    /// GA4 is preferred when both are allowed.
+    #[must_use]
+    pub fn gtag_id(&self, analytics_allowed: bool, marketing_allowed: bool) -> Option<&str> {
+        self.ga4_measurement_id
+            .as_deref()
+            .filter(|_| analytics_allowed)
+            .or_else(|| self.google_ads_id.as_deref().filter(|_| marketing_allowed))
+    }
 }
 
 // =============================================================================
@@ -484,6 +532,7 @@ mod tests {
     fn test_socket_addr() {
         let config = StorefrontConfig {
             database_url: SecretString::from("postgres://localhost/test"),
+            database_read_url: None,
             host: "127.0.0.1".parse().unwrap(),
             port: 3000,
             base_url: "http://localhost:3000".to_string(),
@@ -496,6 +545,8 @@ mod tests {
                 customer_shop_id: "12345678901".to_string(),
                 customer_client_id: "client_id".to_string(),
                 customer_client_secret: SecretString::from("client_secret"),
+                webhook_secret: None,
+                cache_ttl: Duration::from_mins(2),
             },
             analytics: AnalyticsConfig::default(),
             klaviyo: None,
@@ -521,6 +572,8 @@ mod tests {
             customer_shop_id: "12345678901".to_string(),
             customer_client_id: "client_id_value".to_string(),
             customer_client_secret: SecretString::from("super_secret_client_secret"),
+            webhook_secret: Some(SecretString::from("super_secret_webhook_secret")),
+            cache_ttl: Duration::from_mins(2),
         };
 
         let debug_output = format!("{config:?}");
@@ -534,5 +587,20 @@ mod tests {
         assert!(debug_output.contains("[REDACTED]"));
         assert!(!debug_output.contains("super_secret_private_token"));
         assert!(!debug_output.contains("super_secret_client_secret"));
+        assert!(!debug_output.contains("super_secret_webhook_secret"));
+    }
+
+    #[test]
+    fn test_gtag_id_follows_consent() {
+        let analytics = AnalyticsConfig {
+            ga4_measurement_id: Some("G-TEST".to_string()),
+            google_ads_id: Some("AW-TEST".to_string()),
+            ..AnalyticsConfig::default()
+        };
+
+        assert_eq!(analytics.gtag_id(true, true), Some("G-TEST"));
+        assert_eq!(analytics.gtag_id(true, false), Some("G-TEST"));
+        assert_eq!(analytics.gtag_id(false, true), Some("AW-TEST"));
+        assert_eq!(analytics.gtag_id(false, false), None);
     }
 }
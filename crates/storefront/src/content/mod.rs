@@ -12,6 +12,15 @@
 //! ```
 //!
 //! This generates a `<picture>` element with AVIF, WebP, and JPEG sources.
+//!
+//! # URL Convention
+//!
+//! Each file is served at a URL derived from its location; see
+//! [`ContentRouter`] for the mapping.
+
+pub mod router;
+
+pub use router::{ContentEntry, ContentRouter, ContentType};
 
 use chrono::NaiveDate;
 use comrak::{Options, markdown_to_html};
@@ -70,11 +79,33 @@ pub struct Post {
     pub reading_time_minutes: u32,
 }
 
+impl Post {
+    /// URL path of the post (e.g., `/blog/2025/01/my-post`).
+    #[must_use]
+    pub fn url_path(&self) -> String {
+        format!(
+            "{}/{}/{}",
+            ContentType::Post.url_prefix(),
+            self.meta.published_at.format("%Y/%m"),
+            self.slug
+        )
+    }
+}
+
+impl Page {
+    /// URL path of the page (e.g., `/pages/about`).
+    #[must_use]
+    pub fn url_path(&self) -> String {
+        format!("{}/{}", ContentType::Page.url_prefix(), self.slug)
+    }
+}
+
 /// Content store that holds all loaded content in memory
 #[derive(Debug, Clone)]
 pub struct ContentStore {
     pages: Arc<HashMap<String, Page>>,
     posts: Arc<Vec<Post>>,
+    router: Arc<ContentRouter>,
 }
 
 impl ContentStore {
@@ -84,12 +115,15 @@ impl ContentStore {
     ///
     /// Returns an error if the content directory cannot be read.
     pub fn load(content_dir: &Path) -> Result<Self, ContentError> {
-        let pages = Self::load_pages(&content_dir.join("pages"))?;
-        let posts = Self::load_posts(&content_dir.join("blog"))?;
+        let pages = Self::load_pages(&content_dir.join(ContentType::Page.directory()))?;
+        let posts = Self::load_posts(&content_dir.join(ContentType::Post.directory()))?;
+        let router = ContentRouter::new(&pages, &posts);
+        tracing::info!("Routed {} content entries", router.len());
 
         Ok(Self {
             pages: Arc::new(pages),
             posts: Arc::new(posts),
+            router: Arc::new(router),
         })
     }
 
@@ -225,6 +259,12 @@ impl ContentStore {
         })
     }
 
+    /// Get the URL path to content entry map.
+    #[must_use]
+    pub fn router(&self) -> &ContentRouter {
+        &self.router
+    }
+
     /// Get a page by slug
     #[must_use]
     pub fn get_page(&self, slug: &str) -> Option<&Page> {
@@ -0,0 +1,125 @@
+//! URL routing for markdown content.
+//!
+//! Content URLs follow the file layout under `content/`:
+//!
+//! | File                                   | URL                        |
+//! |----------------------------------------|----------------------------|
+//! | `content/pages/about.md`               | `/pages/about`             |
+//! | `content/blog/2025-01-05-my-post.md`   | `/blog/2025/01/my-post`    |
+//!
+//! Blog URLs use the post's `published_at` year and month. Drafts are not
+//! routed.
+
+use std::collections::HashMap;
+
+use super::{Page, Post};
+
+/// Kind of content, which determines its frontmatter schema and template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentType {
+    /// Static page with [`super::PageMeta`] frontmatter.
+    Page,
+    /// Blog post with [`super::PostMeta`] frontmatter.
+    Post,
+}
+
+impl ContentType {
+    /// Directory under `content/` holding this kind of content.
+    #[must_use]
+    pub const fn directory(self) -> &'static str {
+        match self {
+            Self::Page => "pages",
+            Self::Post => "blog",
+        }
+    }
+
+    /// URL prefix this kind of content is served under.
+    #[must_use]
+    pub const fn url_prefix(self) -> &'static str {
+        match self {
+            Self::Page => "/pages",
+            Self::Post => "/blog",
+        }
+    }
+}
+
+/// A routable piece of content.
+#[derive(Debug, Clone)]
+pub enum ContentEntry {
+    Page(Page),
+    Post(Post),
+}
+
+impl ContentEntry {
+    /// Kind of this entry.
+    #[must_use]
+    pub const fn content_type(&self) -> ContentType {
+        match self {
+            Self::Page(_) => ContentType::Page,
+            Self::Post(_) => ContentType::Post,
+        }
+    }
+
+    /// URL path this entry is served at.
+    #[must_use]
+    pub fn url_path(&self) -> String {
+        match self {
+            Self::Page(page) => page.url_path(),
+            Self::Post(post) => post.url_path(),
+        }
+    }
+}
+
+/// Maps URL paths to content entries.
+#[derive(Debug, Clone, Default)]
+pub struct ContentRouter {
+    entries: HashMap<String, ContentEntry>,
+}
+
+impl ContentRouter {
+    /// Build the route map from loaded pages and posts.
+    #[must_use]
+    pub fn new(pages: &HashMap<String, Page>, posts: &[Post]) -> Self {
+        let pages = pages.values().cloned().map(ContentEntry::Page);
+        let posts = posts
+            .iter()
+            .filter(|post| !post.meta.draft)
+            .cloned()
+            .map(ContentEntry::Post);
+
+        let mut entries = HashMap::new();
+        for entry in pages.chain(posts) {
+            let path = entry.url_path();
+            if entries.contains_key(&path) {
+                tracing::warn!("Duplicate content URL, keeping the first: {path}");
+                continue;
+            }
+            entries.insert(path, entry);
+        }
+
+        Self { entries }
+    }
+
+    /// Look up the entry for a URL path of the given kind.
+    ///
+    /// `slug` is the path after the type's prefix (e.g., `2025/01/my-post`).
+    #[must_use]
+    pub fn resolve(&self, content_type: ContentType, slug: &str) -> Option<&ContentEntry> {
+        let path = format!("{}/{}", content_type.url_prefix(), slug.trim_matches('/'));
+        self.entries
+            .get(&path)
+            .filter(|entry| entry.content_type() == content_type)
+    }
+
+    /// Number of routed entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no content is routed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
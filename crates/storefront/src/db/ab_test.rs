@@ -0,0 +1,47 @@
+//! A/B test assignment repository.
+//!
+//! Records which experiment variant each session was assigned, for analysis.
+//! All queries use sqlx macros for compile-time verification.
+
+use sqlx::PgPool;
+
+use super::RepositoryError;
+
+/// Repository for A/B test assignment database operations.
+pub struct AbTestRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> AbTestRepository<'a> {
+    /// Create a new A/B test repository.
+    #[must_use]
+    pub const fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record that a session was assigned a variant of an experiment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn record_assignment(
+        &self,
+        experiment: &str,
+        variant: &str,
+        session_id: &str,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO storefront.ab_test_assignments (experiment, variant, session_id)
+            VALUES ($1, $2, $3)
+            "#,
+            experiment,
+            variant,
+            session_id
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+//! Back-in-stock subscription repository.
+//!
+//! Stores customer requests to be notified when an out-of-stock product
+//! variant becomes available again. All queries use sqlx macros for
+//! compile-time verification.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use naked_pineapple_core::Email;
+
+use super::RepositoryError;
+use crate::models::BackInStockSubscription;
+
+// =============================================================================
+// Internal Row Types
+// =============================================================================
+
+/// Internal row type for `PostgreSQL` back-in-stock subscription queries.
+#[derive(Debug, sqlx::FromRow)]
+struct BackInStockSubscriptionRow {
+    id: Uuid,
+    email: String,
+    variant_id: String,
+    product_handle: String,
+    subscribed_at: DateTime<Utc>,
+    notified_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<BackInStockSubscriptionRow> for BackInStockSubscription {
+    type Error = RepositoryError;
+
+    fn try_from(row: BackInStockSubscriptionRow) -> Result<Self, Self::Error> {
+        let email = Email::parse(&row.email).map_err(|e| {
+            RepositoryError::DataCorruption(format!("invalid email in subscription: {e}"))
+        })?;
+
+        Ok(Self {
+            id: row.id,
+            email,
+            variant_id: row.variant_id,
+            product_handle: row.product_handle,
+            subscribed_at: row.subscribed_at,
+            notified_at: row.notified_at,
+        })
+    }
+}
+
+// =============================================================================
+// Repository
+// =============================================================================
+
+/// Repository for back-in-stock subscription database operations.
+pub struct BackInStockRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> BackInStockRepository<'a> {
+    /// Create a new back-in-stock repository.
+    #[must_use]
+    pub const fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Subscribe an email address to restock notifications for a variant.
+    ///
+    /// Subscribing again while a notification is still pending returns the
+    /// existing subscription rather than creating a duplicate.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn subscribe(
+        &self,
+        email: &Email,
+        variant_id: &str,
+        product_handle: &str,
+    ) -> Result<BackInStockSubscription, RepositoryError> {
+        let row = sqlx::query_as!(
+            BackInStockSubscriptionRow,
+            r#"
+            INSERT INTO storefront.back_in_stock_subscriptions (email, variant_id, product_handle)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (lower(email), variant_id) WHERE notified_at IS NULL
+            DO UPDATE SET product_handle = EXCLUDED.product_handle
+            RETURNING id, email, variant_id, product_handle,
+                      subscribed_at as "subscribed_at: DateTime<Utc>",
+                      notified_at as "notified_at: DateTime<Utc>"
+            "#,
+            email.as_str(),
+            variant_id,
+            product_handle
+        )
+        .fetch_one(self.pool)
+        .await?;
+
+        row.try_into()
+    }
+
+    /// Get the distinct variant IDs that have pending (unsent) notifications.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn pending_variant_ids(&self) -> Result<Vec<String>, RepositoryError> {
+        let ids = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT variant_id
+            FROM storefront.back_in_stock_subscriptions
+            WHERE notified_at IS NULL
+            "#
+        )
+        .fetch_all(self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Claim all pending subscriptions for the given variants.
+    ///
+    /// Marks them as notified and returns them in one statement, so
+    /// concurrent runs (overlapping webhooks) never claim, and notify, the
+    /// same subscription twice. Use [`Self::release`] to put back a claim
+    /// whose notification couldn't be sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    /// Returns `RepositoryError::DataCorruption` if a stored email is invalid.
+    pub async fn claim_pending_for_variants(
+        &self,
+        variant_ids: &[String],
+    ) -> Result<Vec<BackInStockSubscription>, RepositoryError> {
+        let rows = sqlx::query_as!(
+            BackInStockSubscriptionRow,
+            r#"
+            UPDATE storefront.back_in_stock_subscriptions
+            SET notified_at = (CURRENT_TIMESTAMP AT TIME ZONE 'utc')
+            WHERE variant_id = ANY($1) AND notified_at IS NULL
+            RETURNING id, email, variant_id, product_handle,
+                      subscribed_at as "subscribed_at: DateTime<Utc>",
+                      notified_at as "notified_at: DateTime<Utc>"
+            "#,
+            variant_ids
+        )
+        .fetch_all(self.pool)
+        .await?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Return a claimed subscription to pending, so it is retried.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::NotFound` if the subscription doesn't exist.
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn release(&self, id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE storefront.back_in_stock_subscriptions
+            SET notified_at = NULL
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+//! Cart repository.
+//!
+//! Maps each session to its Shopify cart, with a snapshot of the cart's lines.
+//! All queries use sqlx macros for compile-time verification.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use super::RepositoryError;
+use crate::models::{StoredCart, StoredCartLine};
+
+// =============================================================================
+// Internal Row Types
+// =============================================================================
+
+/// Internal row type for `PostgreSQL` cart queries.
+#[derive(Debug, sqlx::FromRow)]
+struct CartRow {
+    cart_id: Uuid,
+    session_id: String,
+    shopify_cart_id: String,
+    line_items: Json<Vec<StoredCartLine>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<CartRow> for StoredCart {
+    fn from(row: CartRow) -> Self {
+        Self {
+            id: row.cart_id,
+            session_id: row.session_id,
+            shopify_cart_id: row.shopify_cart_id,
+            line_items: row.line_items.0,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+// =============================================================================
+// Repository
+// =============================================================================
+
+/// Repository for cart database operations.
+pub struct CartRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> CartRepository<'a> {
+    /// Create a new cart repository.
+    #[must_use]
+    pub const fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get the cart belonging to a session.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails.
+    pub async fn get_by_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Option<StoredCart>, RepositoryError> {
+        let row = sqlx::query_as!(
+            CartRow,
+            r#"
+            SELECT cart_id, session_id, shopify_cart_id,
+                   line_items as "line_items: Json<Vec<StoredCartLine>>",
+                   created_at as "created_at: DateTime<Utc>",
+                   updated_at as "updated_at: DateTime<Utc>"
+            FROM storefront.carts
+            WHERE session_id = $1
+            "#,
+            session_id
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    /// Save a session's Shopify cart and line snapshot.
+    ///
+    /// Replaces the session's existing cart, if it has one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RepositoryError::Database` if the query fails (including when
+    /// the session hasn't been saved yet).
+    pub async fn save(
+        &self,
+        session_id: &str,
+        shopify_cart_id: &str,
+        line_items: &[StoredCartLine],
+    ) -> Result<StoredCart, RepositoryError> {
+        let row = sqlx::query_as!(
+            CartRow,
+            r#"
+            INSERT INTO storefront.carts (session_id, shopify_cart_id, line_items)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (session_id) DO UPDATE
+            SET shopify_cart_id = EXCLUDED.shopify_cart_id,
+                line_items = EXCLUDED.line_items
+            RETURNING cart_id, session_id, shopify_cart_id,
+                      line_items as "line_items: Json<Vec<StoredCartLine>>",
+                      created_at as "created_at: DateTime<Utc>",
+                      updated_at as "updated_at: DateTime<Utc>"
+            "#,
+            session_id,
+            shopify_cart_id,
+            Json(line_items) as _
+        )
+        .fetch_one(self.pool)
+        .await?;
+
+        Ok(row.into())
+    }
+}
@@ -12,7 +12,9 @@
 //! - `password_reset_tokens`
 //! - `email_verification_codes`
 //! - `addresses` - User shipping/billing addresses
-//! - `shopify_cart_cache` - Persist Shopify cart IDs across sessions
+//! - `carts` - Each session's Shopify cart and a snapshot of its lines
+//! - `back_in_stock_subscriptions` - Restock email notification requests
+//! - `ab_test_assignments` - Experiment variant assignments per session
 //!
 //! # Migrations
 //!
@@ -21,15 +23,23 @@
 //! cargo run -p naked-pineapple-cli -- migrate storefront
 //! ```
 
+pub mod ab_test;
+pub mod back_in_stock;
+pub mod carts;
+pub mod sessions;
 pub mod users;
 
 use std::time::Duration;
 
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
 use secrecy::ExposeSecret;
 use sqlx::PgPool;
-use sqlx::postgres::PgPoolOptions;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use thiserror::Error;
 
+use crate::state::AppState;
+
 /// Errors that can occur during repository operations.
 #[derive(Debug, Error)]
 pub enum RepositoryError {
@@ -67,3 +77,51 @@ pub async fn create_pool(database_url: &secrecy::SecretString) -> Result<PgPool,
         .connect(database_url.expose_secret())
         .await
 }
+
+/// Create a connection pool for a read replica.
+///
+/// Every connection defaults to read-only transactions, so a write sent to
+/// the replica pool fails instead of silently going to the wrong server.
+///
+/// # Arguments
+///
+/// * `database_url` - Read replica connection string (wrapped in `SecretString`)
+///
+/// # Errors
+///
+/// Returns `sqlx::Error` if the URL is invalid or the connection cannot be
+/// established.
+pub async fn create_read_pool(database_url: &secrecy::SecretString) -> Result<PgPool, sqlx::Error> {
+    let options = database_url
+        .expose_secret()
+        .parse::<PgConnectOptions>()?
+        .options([("default_transaction_read_only", "on")]);
+
+    PgPoolOptions::new()
+        .max_connections(20)
+        .min_connections(2)
+        .acquire_timeout(Duration::from_secs(10))
+        .connect_with(options)
+        .await
+}
+
+/// Extractor for the read-only connection pool.
+///
+/// Yields the read replica pool when `STOREFRONT_DATABASE_READ_URL` is set and
+/// the primary pool otherwise. Use it for handlers that only `SELECT`; writes
+/// go through [`AppState::pool`].
+///
+/// Replicas can lag behind the primary, so a read that must see a write made
+/// earlier in the same request should also use [`AppState::pool`].
+pub struct ReadPool(pub PgPool);
+
+impl FromRequestParts<AppState> for ReadPool {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(
+        _parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self(state.read_pool().clone()))
+    }
+}
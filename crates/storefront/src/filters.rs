@@ -8,6 +8,7 @@ use std::sync::LazyLock;
 use regex::Regex;
 
 use crate::image_manifest;
+use crate::middleware::ConsentState;
 
 /// Base URL for images, read from `IMAGE_BASE_URL` env var at runtime.
 /// Defaults to "/static/images/derived" for local development.
@@ -71,6 +72,19 @@ pub fn image_max_width(base_path: impl Display, _env: &dyn askama::Values) -> as
     Ok(image_manifest::get_image_max_width(&path))
 }
 
+/// Returns the `BlurHash` placeholder for an image path.
+///
+/// The input should be the base path without extension, e.g., "lifestyle/DSC_1068".
+/// Returns an empty string for SVGs or if the image has no placeholder.
+///
+/// Usage in templates: `<canvas data-blurhash="{{ base|blurhash }}">`
+#[allow(clippy::unnecessary_wraps)]
+#[askama::filter_fn]
+pub fn blurhash(base_path: impl Display, _env: &dyn askama::Values) -> askama::Result<String> {
+    let path = base_path.to_string();
+    Ok(image_manifest::get_image_blurhash(&path).to_string())
+}
+
 /// Generates a srcset string for responsive images, only including sizes that exist.
 ///
 /// Parameters: base_path, hash, format (avif/webp/jpg)
@@ -86,32 +100,8 @@ pub fn image_srcset(
 ) -> askama::Result<String> {
     const SIZES: [u32; 5] = [320, 640, 1024, 1600, 2400];
 
-    let max_width = image_manifest::get_image_max_width(base_path);
-
-    // If max_width is 0 (SVG or not found), include all preset sizes as fallback
-    if max_width == 0 {
-        let base_url = &*IMAGE_BASE_URL;
-        let srcset: Vec<String> = SIZES
-            .iter()
-            .map(|&size| format!("{base_url}/{base_path}.{hash}-{size}.{format} {size}w"))
-            .collect();
-        return Ok(srcset.join(", "));
-    }
-
-    // Build list of sizes: preset sizes up to max_width + max_width itself if not a preset
     let base_url = &*IMAGE_BASE_URL;
-    let mut sizes_to_include: Vec<u32> = SIZES
-        .iter()
-        .filter(|&&size| size <= max_width)
-        .copied()
-        .collect();
-
-    // Add max_width if it's not already a preset size
-    if !SIZES.contains(&max_width) {
-        sizes_to_include.push(max_width);
-    }
-
-    let srcset: Vec<String> = sizes_to_include
+    let srcset: Vec<String> = image_manifest::get_srcset_widths(base_path, &SIZES)
         .iter()
         .map(|&size| format!("{base_url}/{base_path}.{hash}-{size}.{format} {size}w"))
         .collect();
@@ -274,3 +264,60 @@ static CF_BEACON_TOKEN: LazyLock<String> =
 pub fn cf_beacon_token(_value: impl Display, _env: &dyn askama::Values) -> askama::Result<String> {
     Ok(CF_BEACON_TOKEN.clone())
 }
+
+// =============================================================================
+// Cookie Consent Filters
+// =============================================================================
+
+/// Returns whether the visitor has consented to analytics cookies.
+///
+/// Usage in templates: `{% if ""|analytics_consent %}`
+#[allow(clippy::unnecessary_wraps)]
+#[askama::filter_fn]
+pub fn analytics_consent(_value: impl Display, _env: &dyn askama::Values) -> askama::Result<bool> {
+    Ok(ConsentState::current().analytics())
+}
+
+/// Returns whether the visitor has consented to marketing cookies.
+///
+/// Usage in templates: `{% if ""|marketing_consent %}`
+#[allow(clippy::unnecessary_wraps)]
+#[askama::filter_fn]
+pub fn marketing_consent(_value: impl Display, _env: &dyn askama::Values) -> askama::Result<bool> {
+    Ok(ConsentState::current().marketing())
+}
+
+/// Returns whether the visitor hasn't made a cookie consent choice yet.
+///
+/// Usage in templates: `{% if ""|consent_pending %}`
+#[allow(clippy::unnecessary_wraps)]
+#[askama::filter_fn]
+pub fn consent_pending(_value: impl Display, _env: &dyn askama::Values) -> askama::Result<bool> {
+    Ok(ConsentState::current().is_pending())
+}
+
+// =============================================================================
+// A/B Test Filters
+// =============================================================================
+
+/// Returns the session's assigned variant of an experiment.
+///
+/// Usage in templates: `{% if "add_to_cart_text"|ab_test == "buy_now" %}`
+#[allow(clippy::unnecessary_wraps)]
+#[askama::filter_fn]
+pub fn ab_test(experiment: impl Display, _env: &dyn askama::Values) -> askama::Result<String> {
+    Ok(crate::middleware::ab_test_variant(&experiment.to_string()))
+}
+
+// =============================================================================
+// CSRF Filters
+// =============================================================================
+
+/// Returns the CSRF token for the current session.
+///
+/// Usage in templates: `<input type="hidden" name="csrf_token" value="{{ ""|csrf_token }}">`
+#[allow(clippy::unnecessary_wraps)]
+#[askama::filter_fn]
+pub fn csrf_token(_value: impl Display, _env: &dyn askama::Values) -> askama::Result<String> {
+    Ok(crate::middleware::csrf_token())
+}
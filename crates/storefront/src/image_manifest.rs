@@ -192,6 +192,12 @@ pub static IMAGE_INFO: LazyLock<HashMap<&'static str, ImageInfo>> = LazyLock::ne
     ])
 });
 
+/// Maps image base paths to their `BlurHash` placeholders.
+///
+/// Only raster images have an entry.
+pub static IMAGE_BLURHASH: LazyLock<HashMap<&'static str, &'static str>> =
+    LazyLock::new(|| HashMap::from([]));
+
 /// Look up the content hash for an image path.
 ///
 /// Returns the hash if found, or an empty string if not found.
@@ -208,3 +214,33 @@ pub fn get_image_hash(base_path: &str) -> &'static str {
 pub fn get_image_max_width(base_path: &str) -> u32 {
     IMAGE_INFO.get(base_path).map_or(0, |(_, width)| *width)
 }
+
+/// Look up the `BlurHash` placeholder for an image path.
+///
+/// Returns the `BlurHash` string if found, or an empty string if not found.
+#[must_use]
+pub fn get_image_blurhash(base_path: &str) -> &'static str {
+    IMAGE_BLURHASH
+        .get(base_path)
+        .map_or("", |blurhash| *blurhash)
+}
+
+/// Widths to list in a `srcset` for an image, out of the requested `widths`.
+///
+/// Keeps the requested widths that were generated (no wider than the image's
+/// max width) and adds the max width itself when it isn't one of them, so
+/// the largest available size is always offered. Returns every requested
+/// width for SVGs and unknown images.
+#[must_use]
+pub fn get_srcset_widths(base_path: &str, widths: &[u32]) -> Vec<u32> {
+    let max_width = get_image_max_width(base_path);
+    if max_width == 0 {
+        return widths.to_vec();
+    }
+
+    let mut available: Vec<u32> = widths.iter().copied().filter(|&w| w <= max_width).collect();
+    if !available.contains(&max_width) {
+        available.push(max_width);
+    }
+    available
+}
@@ -31,8 +31,10 @@ use std::path::Path;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::http::header::{CACHE_CONTROL, HeaderValue};
-use axum::middleware::from_fn;
-use axum::{Router, routing::get};
+use axum::middleware::{from_fn, from_fn_with_state};
+use axum::{Json, Router, routing::get};
+use naked_pineapple_core::{HealthCheck, HealthReport};
+use tokio::task::JoinHandle;
 use tower::ServiceBuilder;
 use tower_http::services::ServeDir;
 use tower_http::set_header::SetResponseHeaderLayer;
@@ -206,23 +208,38 @@ async fn main() {
         .expect("Failed to create database pool");
     tracing::info!("Database pool created");
 
+    // Initialize read replica pool (reads fall back to the primary if unset)
+    let read_pool = match &config.database_read_url {
+        Some(url) => {
+            let read_pool = db::create_read_pool(url)
+                .await
+                .expect("Failed to create read replica pool");
+            tracing::info!("Read replica pool created");
+            Some(read_pool)
+        }
+        None => None,
+    };
+
     // NOTE: Migrations are NOT run automatically on startup.
     // Run them explicitly via: cargo run -p naked-pineapple-cli -- migrate storefront
 
-    // TODO: Initialize Shopify clients
-    // let storefront_client = shopify::StorefrontClient::new(&config.shopify);
-    // let customer_client = shopify::CustomerClient::new(&config.shopify);
-
-    // Build application state
+    // Build application state (this also creates the Shopify Storefront and
+    // Customer API clients)
     // Content is loaded from the storefront crate's `content/` directory
     let content_dir = Path::new("crates/storefront/content");
-    let state = AppState::new(config.clone(), pool, content_dir)
+    let state = AppState::new(config.clone(), pool, read_pool, content_dir)
         .expect("Failed to initialize application state");
 
     // Start building search index in background
     state.start_search_indexing();
     tracing::info!("Search index build started (async)");
 
+    // Delete expired sessions now and every few hours
+    let background_tasks = vec![
+        services::SessionCleanupTask::new(state.pool().clone(), state.session_cleanup().clone())
+            .spawn(),
+    ];
+
     // Create session layer
     let session_layer = middleware::create_session_layer(state.pool(), state.config());
 
@@ -230,12 +247,24 @@ async fn main() {
     let app = Router::new()
         .route("/health", get(health))
         .route("/health/ready", get(readiness))
-        .merge(routes::routes())
+        .merge(
+            routes::routes()
+                .layer(from_fn_with_state(
+                    state.clone(),
+                    middleware::ab_test_middleware,
+                ))
+                .layer(from_fn(middleware::csrf_middleware))
+                .layer(from_fn(middleware::sentry_context_middleware))
+                .layer(from_fn(middleware::session_expiry_middleware)),
+        )
         .merge(build_static_routes())
         .layer(session_layer)
         .layer(axum::middleware::from_fn(
             middleware::security_headers_middleware,
         ))
+        .layer(axum::middleware::from_fn(
+            middleware::cookie_consent_middleware,
+        ))
         .layer(axum::middleware::from_fn(middleware::csp_nonce_middleware))
         .layer(from_fn(middleware::request_id_middleware))
         .layer(
@@ -280,6 +309,8 @@ async fn main() {
         .with_graceful_shutdown(shutdown_signal())
         .await
         .expect("Server error");
+
+    stop_background_tasks(background_tasks).await;
 }
 
 /// Liveness health check endpoint.
@@ -291,12 +322,52 @@ async fn health() -> &'static str {
 
 /// Readiness health check endpoint.
 ///
-/// Verifies database connectivity before returning OK.
-/// Returns 503 Service Unavailable if the database is not reachable.
-async fn readiness(State(state): State<AppState>) -> StatusCode {
-    match sqlx::query("SELECT 1").fetch_one(state.pool()).await {
-        Ok(_) => StatusCode::OK,
-        Err(_) => StatusCode::SERVICE_UNAVAILABLE,
+/// Checks the database (critical) and the Shopify Storefront API (non-critical)
+/// concurrently and returns a JSON [`HealthReport`]. Returns 503 Service
+/// Unavailable if the database is not reachable; a failing Shopify check only
+/// marks the service as degraded. The number of expired sessions deleted by
+/// the last cleanup run is included as a diagnostic. Error details are
+/// logged, and only included in the response in debug builds.
+async fn readiness(State(state): State<AppState>) -> (StatusCode, Json<HealthReport>) {
+    let (database, shopify) = tokio::join!(
+        HealthCheck::measure(sqlx::query!("SELECT 1 AS one").fetch_one(state.pool())),
+        HealthCheck::measure(state.storefront().ping()),
+    );
+
+    let mut report = HealthReport::new(vec![("database", database)], vec![("shopify", shopify)]);
+    if let Some(deleted) = state.session_cleanup().last_deleted() {
+        report = report.with_diagnostic("expired_sessions_deleted", deleted);
+    }
+    for (name, check) in &report.checks {
+        if let Some(error) = &check.error {
+            tracing::warn!(check = *name, "Readiness check failed: {error}");
+        }
+    }
+
+    let status = if report.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let report = if cfg!(debug_assertions) {
+        report
+    } else {
+        report.without_errors()
+    };
+
+    (status, Json(report))
+}
+
+/// Stop background tasks after the server has shut down, logging any that
+/// panicked while it was running.
+async fn stop_background_tasks(tasks: Vec<JoinHandle<()>>) {
+    for task in tasks {
+        task.abort();
+        if let Err(e) = task.await
+            && e.is_panic()
+        {
+            tracing::error!("Background task panicked: {e}");
+        }
     }
 }
 
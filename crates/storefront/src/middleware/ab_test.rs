@@ -0,0 +1,128 @@
+//! A/B test assignment middleware.
+//!
+//! Assigns the session to a variant of every active experiment on its first
+//! request, caches the assignments in the session, and makes them available
+//! to templates (task-local, read by the `ab_test` filter).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use tower_sessions::Session;
+
+use crate::db::ab_test::AbTestRepository;
+use crate::models::session_keys;
+use crate::state::AppState;
+
+tokio::task_local! {
+    /// Variant of each experiment for the request being handled.
+    static AB_TEST_VARIANTS: Arc<HashMap<String, String>>;
+}
+
+/// Assigned variant of an experiment for the current request.
+///
+/// Returns an empty string for unknown experiments or outside of
+/// [`ab_test_middleware`].
+#[must_use]
+pub fn ab_test_variant(experiment: &str) -> String {
+    AB_TEST_VARIANTS
+        .try_with(|variants| variants.get(experiment).cloned())
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// Middleware that resolves experiment variants for the session.
+///
+/// Must run inside the session layer. Active experiments reuse the variant
+/// cached in the session; new assignments are bucketed by the session ID
+/// (saving a new session first), saved to the session and logged to
+/// `ab_test_assignments` in the background. Inactive experiments resolve to
+/// their control variant and aren't recorded.
+pub async fn ab_test_middleware(
+    State(state): State<AppState>,
+    session: Session,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut assignments: HashMap<String, String> = session
+        .get(session_keys::AB_TEST_ASSIGNMENTS)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+
+    let ab_tests = state.ab_tests();
+    let needs_assignment = ab_tests
+        .experiments()
+        .any(|test| test.active && !assignments.contains_key(&test.name));
+    let session_id = if needs_assignment {
+        saved_session_id(&session).await
+    } else {
+        None
+    };
+
+    let mut variants = HashMap::new();
+    let mut new_assignments = Vec::new();
+    for test in ab_tests.experiments() {
+        let cached = assignments.get(&test.name).cloned();
+        let variant = match (test.active, cached, &session_id) {
+            (true, Some(variant), _) => variant,
+            (true, None, Some(session_id)) => {
+                let variant = ab_tests.assign_variant(&test.name, session_id).to_string();
+                assignments.insert(test.name.clone(), variant.clone());
+                new_assignments.push((test.name.clone(), variant.clone()));
+                variant
+            }
+            _ => test.control().to_string(),
+        };
+        variants.insert(test.name.clone(), variant);
+    }
+
+    if let Some(session_id) = session_id
+        && !new_assignments.is_empty()
+    {
+        if let Err(e) = session
+            .insert(session_keys::AB_TEST_ASSIGNMENTS, &assignments)
+            .await
+        {
+            tracing::error!("Failed to save A/B test assignments to session: {e}");
+        }
+
+        let pool = state.pool().clone();
+        tokio::spawn(async move {
+            let repo = AbTestRepository::new(&pool);
+            for (experiment, variant) in new_assignments {
+                if let Err(e) = repo
+                    .record_assignment(&experiment, &variant, &session_id)
+                    .await
+                {
+                    tracing::error!(%experiment, "Failed to record A/B test assignment: {e}");
+                }
+            }
+        });
+    }
+
+    AB_TEST_VARIANTS
+        .scope(Arc::new(variants), next.run(request))
+        .await
+}
+
+/// The session's ID, saving the session first so a new visitor has one to
+/// be bucketed by.
+///
+/// Returns `None` if the session can't be saved; the visitor then sees
+/// control variants and nothing is recorded.
+async fn saved_session_id(session: &Session) -> Option<String> {
+    if session.id().is_none()
+        && let Err(e) = session.save().await
+    {
+        tracing::error!("Failed to save session for A/B test assignment: {e}");
+        return None;
+    }
+    session.id().map(|id| id.to_string())
+}
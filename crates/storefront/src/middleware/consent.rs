@@ -0,0 +1,142 @@
+//! Cookie consent middleware.
+//!
+//! Reads the visitor's consent from the `np_consent` cookie and makes it
+//! available for the rest of the request, both to handlers (request
+//! extensions) and to templates (task-local, read by the consent filters).
+
+use axum::{
+    extract::{FromRequestParts, Request},
+    http::{HeaderMap, header, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use tower_sessions::cookie::Cookie;
+
+use crate::models::CookieConsent;
+use crate::models::cookie_consent::CONSENT_COOKIE_NAME;
+
+tokio::task_local! {
+    /// Consent state of the request being handled.
+    static CONSENT: ConsentState;
+}
+
+/// The visitor's cookie consent for the current request.
+///
+/// `None` means the visitor hasn't made a choice yet, so the consent banner
+/// is shown and optional cookies stay blocked.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsentState(pub Option<CookieConsent>);
+
+impl ConsentState {
+    /// Read consent from the request's cookies.
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let consent = headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(Cookie::split_parse)
+            .filter_map(Result::ok)
+            .find(|cookie| cookie.name() == CONSENT_COOKIE_NAME)
+            .and_then(|cookie| CookieConsent::from_cookie_value(cookie.value()));
+
+        Self(consent)
+    }
+
+    /// Consent state of the request currently being handled.
+    ///
+    /// Returns "no choice yet" outside of [`cookie_consent_middleware`].
+    #[must_use]
+    pub fn current() -> Self {
+        CONSENT.try_with(|state| *state).unwrap_or_default()
+    }
+
+    /// Whether analytics cookies are allowed.
+    #[must_use]
+    pub fn analytics(self) -> bool {
+        self.0.is_some_and(|c| c.analytics)
+    }
+
+    /// Whether marketing cookies are allowed.
+    #[must_use]
+    pub fn marketing(self) -> bool {
+        self.0.is_some_and(|c| c.marketing)
+    }
+
+    /// Whether the visitor still needs to be asked for consent.
+    #[must_use]
+    pub const fn is_pending(self) -> bool {
+        self.0.is_none()
+    }
+}
+
+/// Middleware that resolves the consent state for the request.
+///
+/// Templates rendered while handling the request read it through the
+/// `analytics_consent`, `marketing_consent` and `consent_pending` filters.
+pub async fn cookie_consent_middleware(mut request: Request, next: Next) -> Response {
+    let state = ConsentState::from_headers(request.headers());
+    request.extensions_mut().insert(state);
+    CONSENT.scope(state, next.run(request)).await
+}
+
+/// Extractor to get the consent state from request extensions.
+impl<S> FromRequestParts<S> for ConsentState
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<Self>()
+            .copied()
+            .unwrap_or_else(|| Self::from_headers(&parts.headers)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn state(cookie: &str) -> ConsentState {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_str(cookie).expect("valid cookie header"),
+        );
+        ConsentState::from_headers(&headers)
+    }
+
+    #[test]
+    fn test_valid_cookie() {
+        let consent = state("id=abc; np_consent=analytics=1&marketing=0");
+        assert!(!consent.is_pending());
+        assert!(consent.analytics());
+        assert!(!consent.marketing());
+    }
+
+    #[test]
+    fn test_garbage_cookie_blocks_optional_cookies() {
+        for cookie in [
+            "np_consent=garbage",
+            "np_consent=analytics=1",
+            "np_consent=",
+            "other=analytics=1&marketing=1",
+        ] {
+            let consent = state(cookie);
+            assert!(consent.is_pending(), "{cookie:?}");
+            assert!(!consent.analytics(), "{cookie:?}");
+            assert!(!consent.marketing(), "{cookie:?}");
+        }
+    }
+
+    #[test]
+    fn test_no_cookie_is_pending() {
+        let consent = ConsentState::from_headers(&HeaderMap::new());
+        assert!(consent.is_pending());
+        assert!(!consent.analytics());
+    }
+}
@@ -0,0 +1,425 @@
+//! CSRF protection middleware.
+//!
+//! Templates embed a per-session random token in forms (`csrf_token` hidden
+//! field) and in the global HTMX/fetch header (`X-CSRF-Token`) through the
+//! `csrf_token` filter. The token is minted the first time a response renders
+//! it, so requests that never render a form (assets, fragments, crawlers)
+//! don't create a session. Every `POST`, `PUT`, `PATCH` and `DELETE` request
+//! must echo the token back, or it is rejected with 403 Forbidden.
+
+use std::sync::{Arc, OnceLock};
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::Request,
+    http::{Method, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use rand::RngCore;
+use tower_sessions::Session;
+
+use crate::models::session_keys;
+
+/// Header HTMX and `fetch()` requests send the token in.
+pub const CSRF_HEADER: &str = "X-CSRF-Token";
+
+/// Form field regular form posts send the token in.
+pub const CSRF_FORM_FIELD: &str = "csrf_token";
+
+/// Largest form body buffered while looking for the token field.
+const MAX_FORM_BODY_BYTES: usize = 64 * 1024;
+
+/// Paths that accept cross-site requests.
+///
+/// The OAuth callback is reached by redirect from Shopify with its own `state`
+/// check, and webhooks are authenticated by HMAC signature.
+const EXEMPT_PATH_PREFIXES: &[&str] = &["/auth/shopify/callback", "/webhooks/"];
+
+/// CSRF token of the session handling the current request.
+#[derive(Debug)]
+struct RequestToken {
+    /// Token already stored in the session.
+    stored: Option<String>,
+    /// Token minted while rendering this response, saved once it completes.
+    minted: OnceLock<String>,
+}
+
+impl RequestToken {
+    fn get_or_mint(&self) -> String {
+        self.stored
+            .clone()
+            .unwrap_or_else(|| self.minted.get_or_init(generate_token).clone())
+    }
+}
+
+tokio::task_local! {
+    static CSRF_TOKEN: Arc<RequestToken>;
+}
+
+/// CSRF token of the request currently being handled.
+///
+/// Mints the session's token if it doesn't have one yet. Returns an empty
+/// string outside of [`csrf_middleware`], which will fail validation rather
+/// than leak another session's token.
+#[must_use]
+pub fn csrf_token() -> String {
+    CSRF_TOKEN
+        .try_with(|token| token.get_or_mint())
+        .unwrap_or_default()
+}
+
+/// Generate a new random token (256-bit, base64url-encoded).
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Compare tokens without short-circuiting on the first differing byte.
+///
+/// An empty expected token (a session that was never issued one) matches
+/// nothing.
+fn tokens_match(expected: &str, submitted: &str) -> bool {
+    !expected.is_empty()
+        && expected.len() == submitted.len()
+        && expected
+            .bytes()
+            .zip(submitted.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Whether a request must carry a valid token.
+fn requires_token(method: &Method, path: &str) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    ) && !EXEMPT_PATH_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+}
+
+/// Middleware that issues and validates CSRF tokens.
+///
+/// Must run inside the session layer. The token is read from the
+/// `X-CSRF-Token` header, or for URL-encoded forms from the `csrf_token`
+/// field; the body is buffered and passed on unchanged in that case. A
+/// session without a token can't pass validation, and only gets one once a
+/// response renders it.
+pub async fn csrf_middleware(session: Session, request: Request, next: Next) -> Response {
+    let stored = session
+        .get::<String>(session_keys::CSRF_TOKEN)
+        .await
+        .ok()
+        .flatten();
+
+    let request = if requires_token(request.method(), request.uri().path()) {
+        match validate(request, stored.as_deref().unwrap_or_default()).await {
+            Ok(request) => request,
+            Err(response) => return response,
+        }
+    } else {
+        request
+    };
+
+    let token = Arc::new(RequestToken {
+        stored,
+        minted: OnceLock::new(),
+    });
+    let response = CSRF_TOKEN
+        .scope(Arc::clone(&token), next.run(request))
+        .await;
+
+    if let Some(minted) = token.minted.get()
+        && let Err(e) = session.insert(session_keys::CSRF_TOKEN, minted).await
+    {
+        tracing::error!("Failed to store CSRF token in session: {e}");
+    }
+
+    response
+}
+
+/// Check the submitted token, returning the (rebuilt) request if it matches.
+async fn validate(request: Request, token: &str) -> Result<Request, Response> {
+    let forbidden = || (StatusCode::FORBIDDEN, "Invalid or missing CSRF token").into_response();
+
+    if let Some(submitted) = request
+        .headers()
+        .get(CSRF_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        return if tokens_match(token, submitted) {
+            Ok(request)
+        } else {
+            tracing::warn!(path = %request.uri().path(), "CSRF header token mismatch");
+            Err(forbidden())
+        };
+    }
+
+    let is_form = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/x-www-form-urlencoded"));
+    if !is_form {
+        tracing::warn!(path = %request.uri().path(), "CSRF token missing");
+        return Err(forbidden());
+    }
+
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = to_bytes(body, MAX_FORM_BODY_BYTES).await else {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE.into_response());
+    };
+
+    let valid = url::form_urlencoded::parse(&bytes)
+        .find(|(key, _)| key == CSRF_FORM_FIELD)
+        .is_some_and(|(_, submitted)| tokens_match(token, &submitted));
+    if !valid {
+        tracing::warn!(path = %parts.uri.path(), "CSRF form token missing or mismatched");
+        return Err(forbidden());
+    }
+
+    Ok(Request::from_parts(parts, Body::from(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::{
+        Router,
+        routing::{get, post},
+    };
+    use tower::ServiceExt;
+    use tower_sessions::cookie::time::{Duration, OffsetDateTime};
+    use tower_sessions::session::{Id, Record};
+    use tower_sessions::{MemoryStore, SessionManagerLayer, SessionStore};
+
+    use super::*;
+
+    const TOKEN: &str = "known-csrf-token";
+
+    /// Store a session that already has a CSRF token and return its ID.
+    async fn create_session(store: &MemoryStore) -> Id {
+        let mut record = Record {
+            id: Id::default(),
+            data: HashMap::from([(
+                session_keys::CSRF_TOKEN.to_string(),
+                serde_json::json!(TOKEN),
+            )]),
+            expiry_date: OffsetDateTime::now_utc() + Duration::hours(1),
+        };
+        store.create(&mut record).await.expect("create session");
+        record.id
+    }
+
+    /// Router behind the CSRF and session middleware.
+    ///
+    /// Handlers echo the request body, so tests can check it arrives intact.
+    fn app(store: &MemoryStore) -> Router {
+        Router::new()
+            .route("/cart/add", post(|body: String| async move { body }))
+            .route("/webhooks/orders/create", post(|| async { "ok" }))
+            .route("/auth/shopify/callback", post(|| async { "ok" }))
+            .route("/token", get(|| async { csrf_token() }))
+            .route("/fragment", get(|| async { "no form here" }))
+            .layer(axum::middleware::from_fn(csrf_middleware))
+            .layer(SessionManagerLayer::new(store.clone()).with_secure(false))
+    }
+
+    /// Send a request through the CSRF middleware with the session cookie.
+    async fn send(
+        store: &MemoryStore,
+        request: axum::http::request::Builder,
+        body: Body,
+    ) -> Response {
+        let id = create_session(store).await;
+        let request = request
+            .header(header::COOKIE, format!("id={id}"))
+            .body(body)
+            .expect("valid request");
+        app(store).oneshot(request).await.expect("infallible")
+    }
+
+    /// Send a request without a session cookie.
+    async fn send_anonymous(store: &MemoryStore, request: Request) -> Response {
+        app(store).oneshot(request).await.expect("infallible")
+    }
+
+    /// Session cookie set by a response, as a `Cookie` header value.
+    fn session_cookie(response: &Response) -> Option<String> {
+        response
+            .headers()
+            .get(header::SET_COOKIE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(';').next())
+            .map(str::to_string)
+    }
+
+    fn post_to(uri: &str) -> axum::http::request::Builder {
+        Request::builder().method(Method::POST).uri(uri)
+    }
+
+    fn form_post() -> axum::http::request::Builder {
+        post_to("/cart/add").header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+    }
+
+    async fn body_text(response: Response) -> String {
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        String::from_utf8(bytes.to_vec()).expect("utf-8 body")
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc", "abc"));
+        assert!(!tokens_match("abc", "abd"));
+        assert!(!tokens_match("abc", "abcd"));
+        assert!(!tokens_match("abc", "ab"));
+        assert!(!tokens_match("abc", ""));
+        assert!(!tokens_match("", ""));
+    }
+
+    #[tokio::test]
+    async fn test_get_exposes_session_token() {
+        let store = MemoryStore::default();
+        let response = send(&store, Request::builder().uri("/token"), Body::empty()).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_text(response).await, TOKEN);
+    }
+
+    #[tokio::test]
+    async fn test_get_without_form_creates_no_session() {
+        let store = MemoryStore::default();
+        let request = Request::builder()
+            .uri("/fragment")
+            .body(Body::empty())
+            .expect("valid request");
+
+        let response = send_anonymous(&store, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(session_cookie(&response).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rendered_token_is_minted_and_saved() {
+        let store = MemoryStore::default();
+        let request = Request::builder()
+            .uri("/token")
+            .body(Body::empty())
+            .expect("valid request");
+
+        let response = send_anonymous(&store, request).await;
+        let cookie = session_cookie(&response).expect("session cookie");
+        let token = body_text(response).await;
+        assert!(!token.is_empty());
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/cart/add")
+            .header(header::COOKIE, cookie)
+            .header(CSRF_HEADER, &token)
+            .body(Body::empty())
+            .expect("valid request");
+        let response = send_anonymous(&store, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_post_without_session_token_is_forbidden() {
+        let store = MemoryStore::default();
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/cart/add")
+            .header(CSRF_HEADER, "")
+            .body(Body::empty())
+            .expect("valid request");
+
+        let response = send_anonymous(&store, request).await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_post_without_token_is_forbidden() {
+        let store = MemoryStore::default();
+
+        let response = send(&store, post_to("/cart/add"), Body::from("quantity=1")).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = send(&store, form_post(), Body::from("quantity=1")).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_header_token_is_forbidden() {
+        let store = MemoryStore::default();
+        let request = post_to("/cart/add").header(CSRF_HEADER, "known-csrf-tokex");
+
+        let response = send(&store, request, Body::empty()).await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_valid_header_token_passes() {
+        let store = MemoryStore::default();
+        let request = post_to("/cart/add").header(CSRF_HEADER, TOKEN);
+
+        let response = send(&store, request, Body::from("quantity=1")).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_text(response).await, "quantity=1");
+    }
+
+    #[tokio::test]
+    async fn test_valid_form_token_passes_with_body_intact() {
+        let store = MemoryStore::default();
+        let body = format!(
+            "variant_id=gid%3A%2F%2Fshopify%2FProductVariant%2F1&{CSRF_FORM_FIELD}={TOKEN}&quantity=2"
+        );
+
+        let response = send(&store, form_post(), Body::from(body.clone())).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_text(response).await, body);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_form_token_is_forbidden() {
+        let store = MemoryStore::default();
+        let body = format!("{CSRF_FORM_FIELD}=wrong&quantity=2");
+
+        let response = send(&store, form_post(), Body::from(body)).await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_form_body_is_rejected() {
+        let store = MemoryStore::default();
+        let body = format!(
+            "{CSRF_FORM_FIELD}={TOKEN}&note={}",
+            "a".repeat(MAX_FORM_BODY_BYTES)
+        );
+
+        let response = send(&store, form_post(), Body::from(body)).await;
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_exempt_paths_skip_validation() {
+        let store = MemoryStore::default();
+
+        for uri in ["/webhooks/orders/create", "/auth/shopify/callback"] {
+            let response = send(&store, post_to(uri), Body::from("{}")).await;
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+        }
+    }
+}
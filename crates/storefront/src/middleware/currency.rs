@@ -0,0 +1,187 @@
+//! Buyer currency extractor.
+//!
+//! Resolves the buyer's display currency (see [`crate::services::currency`])
+//! and content language (see [`crate::services::language`]), and provides a
+//! price formatter and a localized Storefront client for them. Signed-in
+//! customers get an uncached client so they always see live data.
+
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{header::ACCEPT_LANGUAGE, request::Parts},
+};
+use serde::Deserialize;
+use tower_sessions::Session;
+
+use crate::models::session_keys;
+use crate::services::{
+    CurrencyDetectionService, CurrencyPreference, LanguageDetectionService, PriceFormatter,
+};
+use crate::shopify::StorefrontClient;
+use crate::state::AppState;
+
+/// Cloudflare geolocation header with the visitor's ISO country code.
+const IP_COUNTRY_HEADER: &str = "cf-ipcountry";
+
+/// Query parameters that select a display currency or language.
+#[derive(Debug, Deserialize)]
+struct CurrencyQuery {
+    currency: Option<String>,
+    language: Option<String>,
+}
+
+/// The buyer's resolved display currency and content language.
+///
+/// An explicit `?currency=` or `?language=` choice is saved to the session so
+/// it sticks across pages. If the shop's localization settings can't be
+/// loaded, prices fall back to the shop's base currency and content to its
+/// primary language.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// async fn handler(State(state): State<AppState>, currency: BuyerCurrency) {
+///     let product = currency
+///         .storefront(state.storefront())
+///         .get_product_by_handle("soap")
+///         .await?;
+///     let price = currency.formatter.format(&product.price_range.min_variant_price);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BuyerCurrency {
+    /// Selected currency, or `None` for the shop's base currency.
+    pub preference: Option<CurrencyPreference>,
+    /// Selected Shopify `LanguageCode`, or `None` for the shop's primary
+    /// language.
+    pub language: Option<String>,
+    /// Formatter for prices returned in this currency.
+    pub formatter: PriceFormatter,
+    /// Whether the buyer is a signed-in customer (bypasses the API cache).
+    pub signed_in: bool,
+}
+
+impl BuyerCurrency {
+    /// Country code to localize Storefront API prices to.
+    #[must_use]
+    pub fn country(&self) -> Option<&str> {
+        self.preference.as_ref().map(|p| p.country_code.as_str())
+    }
+
+    /// Get a Storefront client that returns prices in this currency and
+    /// content in this language.
+    #[must_use]
+    pub fn storefront(&self, client: &StorefrontClient) -> StorefrontClient {
+        let client = client
+            .with_country(self.country())
+            .with_language(self.language.as_deref());
+        if self.signed_in {
+            client.without_cache()
+        } else {
+            client
+        }
+    }
+}
+
+impl FromRequestParts<AppState> for BuyerCurrency {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let localization = match state.storefront().get_shop_localization().await {
+            Ok(localization) => localization,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load shop localization, using base currency");
+                return Ok(Self::default());
+            }
+        };
+
+        let session = parts.extensions.get::<Session>().cloned();
+        let saved: Option<CurrencyPreference> = match &session {
+            Some(session) => session
+                .get(session_keys::CURRENCY_PREFERENCE)
+                .await
+                .ok()
+                .flatten(),
+            None => None,
+        };
+        let saved_language: Option<String> = match &session {
+            Some(session) => session
+                .get(session_keys::LANGUAGE_PREFERENCE)
+                .await
+                .ok()
+                .flatten(),
+            None => None,
+        };
+        let (query_currency, query_language) = Query::<CurrencyQuery>::try_from_uri(&parts.uri)
+            .map_or((None, None), |Query(q)| (q.currency, q.language));
+        let ip_country = parts
+            .headers
+            .get(IP_COUNTRY_HEADER)
+            .and_then(|v| v.to_str().ok());
+        let accept_language = parts
+            .headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|v| v.to_str().ok());
+
+        let signed_in = match &session {
+            Some(session) => is_signed_in(session).await,
+            None => false,
+        };
+
+        let service = CurrencyDetectionService::new(&localization);
+        let preference = service.detect(query_currency.as_deref(), saved.as_ref(), ip_country);
+
+        // Remember an explicit choice from the query string
+        if query_currency.is_some()
+            && preference != saved
+            && let (Some(session), Some(preference)) = (&session, &preference)
+            && let Err(e) = session
+                .insert(session_keys::CURRENCY_PREFERENCE, preference)
+                .await
+        {
+            tracing::warn!(error = %e, "Failed to save currency preference");
+        }
+
+        let languages = LanguageDetectionService::new(&localization);
+        let language = languages.detect(
+            query_language.as_deref(),
+            saved_language.as_deref(),
+            accept_language,
+        );
+
+        // Remember an explicit language choice, including the primary
+        // language so it overrides `Accept-Language` on later pages
+        if let (Some(session), Some(chosen)) = (
+            &session,
+            query_language.as_deref().and_then(|l| languages.for_tag(l)),
+        ) && saved_language.as_ref() != Some(&chosen)
+            && let Err(e) = session
+                .insert(session_keys::LANGUAGE_PREFERENCE, &chosen)
+                .await
+        {
+            tracing::warn!(error = %e, "Failed to save language preference");
+        }
+
+        Ok(Self {
+            preference,
+            language,
+            formatter: PriceFormatter::new(&localization),
+            signed_in,
+        })
+    }
+}
+
+/// Whether the session belongs to a signed-in customer.
+async fn is_signed_in(session: &Session) -> bool {
+    for key in [
+        session_keys::CURRENT_CUSTOMER,
+        session_keys::SHOPIFY_CUSTOMER_TOKEN,
+    ] {
+        if matches!(session.get::<serde_json::Value>(key).await, Ok(Some(_))) {
+            return true;
+        }
+    }
+    false
+}
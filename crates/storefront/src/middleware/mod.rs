@@ -6,25 +6,40 @@
 //! 2. `TraceLayer` (request tracing)
 //! 3. Request ID (add unique ID to each request)
 //! 4. CSP nonce (generate per-request nonce for inline scripts)
-//! 5. Session layer (tower-sessions with `PostgreSQL` store)
-//! 6. User context (add user info to tracing span)
-//! 7. Security headers (CSP, HSTS, etc.)
-//! 8. Rate limiting (governor)
+//! 5. Cookie consent (read `np_consent` for templates)
+//! 6. Session layer (tower-sessions with `PostgreSQL` store)
+//! 7. Session expiry (slide the expiry for logged-in customers; app routes only)
+//! 8. Sentry context (tag errors with the signed-in customer; app routes only)
+//! 9. CSRF (issue session token, validate mutating requests; app routes only)
+//! 10. A/B tests (assign experiment variants for templates; app routes only)
+//! 11. User context (add user info to tracing span)
+//! 12. Security headers (CSP, HSTS, etc.)
+//! 13. Rate limiting (governor)
 
+pub mod ab_test;
 pub mod auth;
+pub mod consent;
 pub mod csp;
+pub mod csrf;
+pub mod currency;
 pub mod rate_limit;
 pub mod request_id;
 pub mod security_headers;
+pub mod sentry_context;
 pub mod session;
 pub mod shopify_customer;
 
+pub use ab_test::{ab_test_middleware, ab_test_variant};
 pub use auth::{OptionalAuth, RequireAuth, clear_current_customer, set_current_customer};
+pub use consent::{ConsentState, cookie_consent_middleware};
 pub use csp::{CspNonce, csp_nonce_middleware};
+pub use csrf::{csrf_middleware, csrf_token};
+pub use currency::BuyerCurrency;
 pub use rate_limit::{api_rate_limiter, auth_rate_limiter};
 pub use request_id::request_id_middleware;
 pub use security_headers::security_headers_middleware;
-pub use session::create_session_layer;
+pub use sentry_context::sentry_context_middleware;
+pub use session::{create_session_layer, session_expiry_middleware};
 pub use shopify_customer::{
     OptionalShopifyCustomer, RequireShopifyCustomer, clear_shopify_customer_token,
     set_shopify_customer_token,
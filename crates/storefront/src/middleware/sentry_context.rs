@@ -0,0 +1,26 @@
+//! Sentry customer context.
+//!
+//! Sets the Sentry user to the signed-in customer's Shopify ID so errors can
+//! be filtered by customer. Only the ID is sent; email and name stay out of
+//! Sentry. `NewSentryLayer` gives every request its own scope, so the user
+//! never leaks between requests.
+
+use axum::{extract::Request, middleware::Next, response::Response};
+use tower_sessions::Session;
+
+use crate::error::set_sentry_user;
+use crate::models::{CurrentCustomer, session_keys};
+
+/// Middleware that sets the Sentry user for signed-in customers.
+///
+/// Must run inside the session layer.
+pub async fn sentry_context_middleware(session: Session, request: Request, next: Next) -> Response {
+    if let Ok(Some(customer)) = session
+        .get::<CurrentCustomer>(session_keys::CURRENT_CUSTOMER)
+        .await
+    {
+        set_sentry_user(&customer.shopify_customer_id, None);
+    }
+
+    next.run(request).await
+}
@@ -1,19 +1,27 @@
 //! Session middleware configuration.
 //!
 //! Sets up `PostgreSQL`-backed sessions using tower-sessions.
+//!
+//! tower-sessions only writes a session back when it changes, so an
+//! unchanged session keeps the expiry it was saved with.
+//! [`session_expiry_middleware`] slides the expiry forward while a customer
+//! is logged in and active.
 
+use axum::{extract::Request, middleware::Next, response::Response};
+use chrono::{DateTime, Duration, Utc};
 use secrecy::ExposeSecret;
 use sqlx::PgPool;
-use tower_sessions::{Expiry, SessionManagerLayer};
+use tower_sessions::{Expiry, Session, SessionManagerLayer};
 use tower_sessions_sqlx_store::PostgresStore;
 
 use crate::config::StorefrontConfig;
+use crate::models::session_keys;
 
 /// Session cookie name.
 pub const SESSION_COOKIE_NAME: &str = "np_session";
 
-/// Session expiry time in seconds (7 days).
-const SESSION_EXPIRY_SECONDS: i64 = 7 * 24 * 60 * 60;
+/// Session expiry time in seconds (30 days of inactivity).
+const SESSION_EXPIRY_SECONDS: i64 = 30 * 24 * 60 * 60;
 
 /// Create the session layer with `PostgreSQL` store.
 ///
@@ -53,3 +61,45 @@ pub fn create_session_layer(
         .with_http_only(true)
         .with_path("/")
 }
+
+/// Extend a logged-in customer's session expiry while they are active.
+///
+/// Once less than half of the session lifetime remains, records
+/// `session_last_active`, which saves the session with a fresh expiry and
+/// cookie. Guest sessions are left to expire on their original schedule.
+pub async fn session_expiry_middleware(session: Session, request: Request, next: Next) -> Response {
+    if is_logged_in(&session).await {
+        let now = Utc::now();
+        let last_active: Option<DateTime<Utc>> =
+            session.get(session_keys::LAST_ACTIVE).await.ok().flatten();
+
+        if needs_extension(last_active, now)
+            && let Err(e) = session.insert(session_keys::LAST_ACTIVE, now).await
+        {
+            tracing::warn!(error = %e, "Failed to extend customer session");
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Whether the session belongs to a logged-in customer (either login flow).
+async fn is_logged_in(session: &Session) -> bool {
+    for key in [
+        session_keys::CURRENT_CUSTOMER,
+        session_keys::SHOPIFY_CUSTOMER_TOKEN,
+    ] {
+        if matches!(session.get_value(key).await, Ok(Some(_))) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Whether less than half of the session lifetime remains, given when the
+/// expiry was last extended.
+fn needs_extension(last_active: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    last_active.is_none_or(|last_active| {
+        now - last_active >= Duration::seconds(SESSION_EXPIRY_SECONDS / 2)
+    })
+}
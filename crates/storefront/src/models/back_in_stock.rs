@@ -0,0 +1,23 @@
+//! Back-in-stock subscription domain types.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use naked_pineapple_core::Email;
+
+/// A request to be emailed when an out-of-stock variant is restocked.
+#[derive(Debug, Clone)]
+pub struct BackInStockSubscription {
+    /// Unique subscription ID.
+    pub id: Uuid,
+    /// Email address to notify.
+    pub email: Email,
+    /// Shopify variant ID (e.g., `gid://shopify/ProductVariant/123`).
+    pub variant_id: String,
+    /// Handle of the product the variant belongs to (for building links).
+    pub product_handle: String,
+    /// When the customer subscribed.
+    pub subscribed_at: DateTime<Utc>,
+    /// When the restock notification was sent, if it has been.
+    pub notified_at: Option<DateTime<Utc>>,
+}
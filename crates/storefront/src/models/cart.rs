@@ -0,0 +1,36 @@
+//! Persisted cart domain types.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A session's cart as stored locally.
+///
+/// Shopify holds the cart itself; the stored lines are a snapshot taken after
+/// every change, used to rebuild the cart if Shopify expires it.
+#[derive(Debug, Clone)]
+pub struct StoredCart {
+    /// Unique local cart ID.
+    pub id: Uuid,
+    /// ID of the session that owns the cart.
+    pub session_id: String,
+    /// Shopify cart ID (e.g., `gid://shopify/Cart/...`).
+    pub shopify_cart_id: String,
+    /// Lines on the Shopify cart as of the last change.
+    pub line_items: Vec<StoredCartLine>,
+    /// When the cart was created.
+    pub created_at: DateTime<Utc>,
+    /// When the cart was last changed.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A cart line in the stored snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCartLine {
+    /// Shopify cart line ID.
+    pub line_id: String,
+    /// Shopify variant ID (e.g., `gid://shopify/ProductVariant/123`).
+    pub merchandise_id: String,
+    /// Quantity.
+    pub quantity: i64,
+}
@@ -0,0 +1,133 @@
+//! Product comparison list.
+//!
+//! Stored in the session, so it is cleared automatically when the session
+//! expires.
+
+use serde::{Deserialize, Serialize};
+
+/// Variants the buyer has selected for side-by-side comparison.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompareList {
+    variant_ids: Vec<String>,
+}
+
+/// Result of adding a variant to a [`CompareList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareAddOutcome {
+    /// The variant was added.
+    Added,
+    /// The variant was already in the list.
+    AlreadyPresent,
+    /// The list already holds [`CompareList::MAX_ITEMS`] variants.
+    Full,
+}
+
+impl CompareList {
+    /// Maximum number of variants that can be compared at once.
+    pub const MAX_ITEMS: usize = 4;
+
+    /// Add a variant to the list.
+    pub fn add(&mut self, variant_id: &str) -> CompareAddOutcome {
+        if self.contains(variant_id) {
+            return CompareAddOutcome::AlreadyPresent;
+        }
+        if self.variant_ids.len() >= Self::MAX_ITEMS {
+            return CompareAddOutcome::Full;
+        }
+
+        self.variant_ids.push(variant_id.to_string());
+        CompareAddOutcome::Added
+    }
+
+    /// Remove a variant from the list. Returns whether it was present.
+    pub fn remove(&mut self, variant_id: &str) -> bool {
+        let len = self.variant_ids.len();
+        self.variant_ids.retain(|id| id != variant_id);
+        self.variant_ids.len() != len
+    }
+
+    /// Check whether a variant is in the list.
+    #[must_use]
+    pub fn contains(&self, variant_id: &str) -> bool {
+        self.variant_ids.iter().any(|id| id == variant_id)
+    }
+
+    /// Variant IDs in the order they were added.
+    #[must_use]
+    pub const fn variant_ids(&self) -> &[String] {
+        self.variant_ids.as_slice()
+    }
+
+    /// Number of variants in the list.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.variant_ids.len()
+    }
+
+    /// Whether the list is empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.variant_ids.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(n: usize) -> String {
+        format!("gid://shopify/ProductVariant/{n}")
+    }
+
+    #[test]
+    fn test_add_duplicate_is_already_present() {
+        let mut list = CompareList::default();
+        assert_eq!(list.add(&variant(1)), CompareAddOutcome::Added);
+        assert_eq!(list.add(&variant(1)), CompareAddOutcome::AlreadyPresent);
+        assert_eq!(list.len(), 1);
+        assert!(list.contains(&variant(1)));
+    }
+
+    #[test]
+    fn test_add_rejects_item_past_max() {
+        let mut list = CompareList::default();
+        for n in 0..CompareList::MAX_ITEMS {
+            assert_eq!(list.add(&variant(n)), CompareAddOutcome::Added);
+        }
+
+        assert_eq!(list.add(&variant(99)), CompareAddOutcome::Full);
+        assert_eq!(list.len(), CompareList::MAX_ITEMS);
+        assert!(!list.contains(&variant(99)));
+        // A full list still recognizes variants already in it
+        assert_eq!(list.add(&variant(0)), CompareAddOutcome::AlreadyPresent);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut list = CompareList::default();
+        list.add(&variant(1));
+        list.add(&variant(2));
+
+        assert!(!list.remove(&variant(3)));
+        assert_eq!(list.len(), 2);
+
+        assert!(list.remove(&variant(1)));
+        assert_eq!(list.variant_ids(), [variant(2)]);
+        assert!(!list.remove(&variant(1)));
+    }
+
+    #[test]
+    fn test_remove_frees_a_slot() {
+        let mut list = CompareList::default();
+        for n in 0..CompareList::MAX_ITEMS {
+            list.add(&variant(n));
+        }
+
+        assert!(list.remove(&variant(0)));
+        assert_eq!(list.add(&variant(99)), CompareAddOutcome::Added);
+        assert_eq!(
+            list.variant_ids().last().map(String::as_str),
+            Some(variant(99).as_str())
+        );
+    }
+}
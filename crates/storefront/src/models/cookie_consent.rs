@@ -0,0 +1,152 @@
+//! Cookie consent preferences.
+//!
+//! Stored in the long-lived first-party `np_consent` cookie rather than the
+//! session, so a visitor's choice outlives their session.
+
+/// Name of the consent cookie.
+pub const CONSENT_COOKIE_NAME: &str = "np_consent";
+
+/// Consent cookie lifetime in seconds (1 year).
+pub const CONSENT_COOKIE_MAX_AGE: i64 = 31_536_000;
+
+/// A visitor's consent to optional cookie categories.
+///
+/// Strictly necessary cookies (session, cart) don't need consent and are
+/// always set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CookieConsent {
+    /// Analytics cookies (Google Analytics, Mixpanel, Crazy Egg).
+    pub analytics: bool,
+    /// Marketing cookies (advertising pixels).
+    pub marketing: bool,
+}
+
+impl CookieConsent {
+    /// Consent to every optional category.
+    #[must_use]
+    pub const fn accept_all() -> Self {
+        Self {
+            analytics: true,
+            marketing: true,
+        }
+    }
+
+    /// Consent to no optional categories.
+    #[must_use]
+    pub const fn reject_all() -> Self {
+        Self {
+            analytics: false,
+            marketing: false,
+        }
+    }
+
+    /// Parse the consent cookie value (e.g. `analytics=1&marketing=0`).
+    ///
+    /// Returns `None` for malformed values so the banner is shown again.
+    #[must_use]
+    pub fn from_cookie_value(value: &str) -> Option<Self> {
+        let mut analytics = None;
+        let mut marketing = None;
+
+        for pair in value.split('&') {
+            let (key, flag) = pair.split_once('=')?;
+            let flag = match flag {
+                "1" => true,
+                "0" => false,
+                _ => return None,
+            };
+            match key {
+                "analytics" => analytics = Some(flag),
+                "marketing" => marketing = Some(flag),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            analytics: analytics?,
+            marketing: marketing?,
+        })
+    }
+
+    /// Serialize to the consent cookie value.
+    #[must_use]
+    pub fn to_cookie_value(self) -> String {
+        format!(
+            "analytics={}&marketing={}",
+            u8::from(self.analytics),
+            u8::from(self.marketing)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cookie_value_round_trip() {
+        for consent in [
+            CookieConsent::accept_all(),
+            CookieConsent::reject_all(),
+            CookieConsent {
+                analytics: true,
+                marketing: false,
+            },
+        ] {
+            assert_eq!(
+                CookieConsent::from_cookie_value(&consent.to_cookie_value()),
+                Some(consent)
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_cookie_value_any_order() {
+        assert_eq!(
+            CookieConsent::from_cookie_value("marketing=1&analytics=0"),
+            Some(CookieConsent {
+                analytics: false,
+                marketing: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_cookie_value_malformed() {
+        for value in [
+            "",
+            "garbage",
+            "analytics=1&marketing",
+            "analytics=yes&marketing=0",
+            "analytics=1&&marketing=0",
+            " analytics=1&marketing=1",
+        ] {
+            assert_eq!(CookieConsent::from_cookie_value(value), None, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn test_from_cookie_value_partial() {
+        assert_eq!(CookieConsent::from_cookie_value("analytics=1"), None);
+        assert_eq!(CookieConsent::from_cookie_value("marketing=0"), None);
+    }
+
+    #[test]
+    fn test_from_cookie_value_legacy() {
+        // Earlier banners stored a single accept/reject flag or booleans
+        assert_eq!(CookieConsent::from_cookie_value("accepted"), None);
+        assert_eq!(CookieConsent::from_cookie_value("1"), None);
+        assert_eq!(
+            CookieConsent::from_cookie_value("analytics=true&marketing=false"),
+            None
+        );
+        // Categories this version doesn't know about are ignored
+        assert_eq!(
+            CookieConsent::from_cookie_value("necessary=1&analytics=1&marketing=0"),
+            Some(CookieConsent {
+                analytics: true,
+                marketing: false,
+            })
+        );
+    }
+}
@@ -2,8 +2,16 @@
 //!
 //! These types represent validated domain objects used throughout the application.
 
+pub mod back_in_stock;
+pub mod cart;
+pub mod compare;
+pub mod cookie_consent;
 pub mod session;
 pub mod user;
 
+pub use back_in_stock::BackInStockSubscription;
+pub use cart::{StoredCart, StoredCartLine};
+pub use compare::{CompareAddOutcome, CompareList};
+pub use cookie_consent::CookieConsent;
 pub use session::{CurrentCustomer, keys as session_keys};
 pub use user::{User, UserCredential};
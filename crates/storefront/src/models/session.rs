@@ -120,8 +120,20 @@ pub mod keys {
     /// Key for `WebAuthn` authentication challenge state.
     pub const WEBAUTHN_AUTH: &str = "webauthn_auth";
 
-    /// Key for storing the Shopify cart ID.
-    pub const CART_ID: &str = "cart_id";
+    /// Key for the cart's shipping estimate and selected rate.
+    pub const SHIPPING_ESTIMATE: &str = "shipping_estimate";
+
+    /// Key for the product comparison list.
+    pub const COMPARE_LIST: &str = "compare_list";
+
+    /// Key for the buyer's display currency preference.
+    pub const CURRENCY_PREFERENCE: &str = "currency_preference";
+
+    /// Key for the buyer's content language preference.
+    pub const LANGUAGE_PREFERENCE: &str = "language_preference";
+
+    /// Key for the buyer's collection sort preference.
+    pub const COLLECTION_SORT: &str = "collection_sort";
 
     /// Key for Shopify OAuth state (CSRF protection).
     pub const SHOPIFY_OAUTH_STATE: &str = "shopify_oauth_state";
@@ -129,6 +141,18 @@ pub mod keys {
     /// Key for Shopify OAuth nonce (`OpenID` Connect replay protection).
     pub const SHOPIFY_OAUTH_NONCE: &str = "shopify_oauth_nonce";
 
+    /// Key for Shopify OAuth PKCE code verifier.
+    pub const SHOPIFY_OAUTH_CODE_VERIFIER: &str = "shopify_oauth_code_verifier";
+
     /// Key for Shopify customer access token (Customer Account API OAuth).
     pub const SHOPIFY_CUSTOMER_TOKEN: &str = "shopify_customer_token";
+
+    /// Key for the session's CSRF token.
+    pub const CSRF_TOKEN: &str = "csrf_token";
+
+    /// Key for the session's A/B test variant assignments.
+    pub const AB_TEST_ASSIGNMENTS: &str = "ab_test_assignments";
+
+    /// Key for when the session expiry was last extended.
+    pub const LAST_ACTIVE: &str = "session_last_active";
 }
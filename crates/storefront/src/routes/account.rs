@@ -6,28 +6,38 @@
 //!
 //! - `GET /account` - Account overview
 //! - `GET /account/orders` - Order history
+//! - `GET /account/orders/:id` - Order details
+//! - `GET /account/profile` - Profile form
+//! - `POST /account/profile` - Update profile
 //! - `GET /account/addresses` - Address list
 //! - `GET /account/addresses/new` - New address form
-//! - `POST /account/addresses` - Create address
+//! - `POST /account/addresses/new` - Create address (also `POST /account/addresses`)
 //! - `GET /account/addresses/:id/edit` - Edit address form
-//! - `POST /account/addresses/:id` - Update address
+//! - `PUT /account/addresses/:id` - Update address (also `POST` for non-JS forms)
 //! - `DELETE /account/addresses/:id` - Delete address
+//! - `POST /account/addresses/:id/default` - Set default address
+//!
+//! The address routes answer HTMX requests with fragments (the address book
+//! or the address form) so the address page never reloads, and fall back to
+//! full pages and redirects otherwise. The profile form works the same way,
+//! raising a toast once the profile is saved.
 
 use askama::Template;
 use askama_web::WebTemplate;
 use axum::{
     Form,
     extract::{Path, State},
-    http::StatusCode,
-    response::{IntoResponse, Redirect, Response},
+    http::{HeaderMap, StatusCode},
+    response::{AppendHeaders, IntoResponse, Redirect, Response},
 };
+use naked_pineapple_core::{COUNTRIES, PostalCodeError, country_name, normalize_postal_code};
 use serde::Deserialize;
 
 use crate::config::AnalyticsConfig;
 use crate::filters;
 use crate::middleware::RequireShopifyCustomer;
-use crate::shopify::Money;
-use crate::shopify::customer::{Address, AddressInput, Order};
+use crate::shopify::customer::{Address, AddressInput, CustomerOrder, Order};
+use crate::shopify::{Money, ShopifyError};
 use crate::state::AppState;
 
 // =============================================================================
@@ -48,6 +58,144 @@ pub struct OrderView {
     pub total: String,
 }
 
+/// Order detail display data for templates.
+#[derive(Clone)]
+pub struct OrderDetailView {
+    pub name: String,
+    pub placed_on: String,
+    pub financial_status: Option<String>,
+    pub fulfillment_status: String,
+    /// Line items shown up front.
+    pub visible_items: Vec<OrderLineItemView>,
+    /// Remaining line items, shown in a collapsible section.
+    pub hidden_items: Vec<OrderLineItemView>,
+    /// Shipping address lines.
+    pub shipping_address: Vec<String>,
+    pub shipping_method: Option<String>,
+    pub shipping_price: Option<String>,
+    pub subtotal: Option<String>,
+    pub tax: Option<String>,
+    pub total: String,
+    pub discount_codes: Vec<String>,
+    pub tracking: Vec<TrackingView>,
+}
+
+/// Order line item display data for templates.
+#[derive(Clone)]
+pub struct OrderLineItemView {
+    pub title: String,
+    pub variant_title: Option<String>,
+    pub image_url: Option<String>,
+    pub image_alt: String,
+    pub quantity: i64,
+    pub price: Option<String>,
+    pub fulfilled_quantity: i64,
+    pub returnable_quantity: i64,
+}
+
+/// Shipment tracking display data for templates.
+#[derive(Clone)]
+pub struct TrackingView {
+    pub carrier: Option<String>,
+    pub number: String,
+    pub url: Option<String>,
+}
+
+/// Number of line items shown before the rest are collapsed.
+const VISIBLE_LINE_ITEMS: usize = 5;
+
+impl From<CustomerOrder> for OrderDetailView {
+    fn from(order: CustomerOrder) -> Self {
+        let placed_on = order.created_at_datetime().map_or_else(
+            || order.created_at.clone(),
+            |dt| dt.format("%B %-d, %Y").to_string(),
+        );
+
+        let mut visible_items: Vec<OrderLineItemView> = order
+            .line_items
+            .into_iter()
+            .map(|item| OrderLineItemView {
+                variant_title: item
+                    .variant_title
+                    .filter(|v| !v.is_empty() && v != "Default Title"),
+                image_url: item.image.as_ref().map(|img| img.url.clone()),
+                image_alt: item
+                    .image
+                    .and_then(|img| img.alt_text)
+                    .unwrap_or_else(|| item.title.clone()),
+                title: item.title,
+                quantity: item.quantity,
+                price: item.price.as_ref().map(format_money),
+                fulfilled_quantity: item.fulfilled_quantity,
+                returnable_quantity: item.returnable_quantity,
+            })
+            .collect();
+        let hidden_items = if visible_items.len() > VISIBLE_LINE_ITEMS {
+            visible_items.split_off(VISIBLE_LINE_ITEMS)
+        } else {
+            Vec::new()
+        };
+
+        let shipping_address = order
+            .shipping_address
+            .map(|addr| {
+                let name = format!(
+                    "{} {}",
+                    addr.first_name.as_deref().unwrap_or_default(),
+                    addr.last_name.as_deref().unwrap_or_default()
+                );
+                [
+                    Some(name.trim().to_string()),
+                    addr.company,
+                    addr.address1,
+                    addr.address2,
+                    Some(
+                        [addr.city, addr.province_code, addr.zip]
+                            .into_iter()
+                            .flatten()
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                    ),
+                    addr.country,
+                ]
+                .into_iter()
+                .flatten()
+                .filter(|line| !line.trim().is_empty())
+                .collect()
+            })
+            .unwrap_or_default();
+
+        let tracking = order
+            .tracking
+            .iter()
+            .filter_map(|t| {
+                Some(TrackingView {
+                    carrier: t.company.clone(),
+                    number: t.number.clone()?,
+                    url: t.tracking_url(),
+                })
+            })
+            .collect();
+
+        Self {
+            name: order.name,
+            placed_on,
+            financial_status: order.financial_status.as_deref().map(humanize_status),
+            fulfillment_status: humanize_status(&order.fulfillment_status),
+            visible_items,
+            hidden_items,
+            shipping_address,
+            shipping_method: order.shipping_line.as_ref().map(|s| s.title.clone()),
+            shipping_price: order.shipping_line.as_ref().map(|s| format_money(&s.price)),
+            subtotal: order.subtotal.as_ref().map(format_money),
+            tax: order.total_tax.as_ref().map(format_money),
+            total: format_money(&order.total_price),
+            discount_codes: order.discount_codes,
+            tracking,
+        }
+    }
+}
+
 /// Address display data for templates.
 #[derive(Clone)]
 pub struct AddressView {
@@ -58,6 +206,142 @@ pub struct AddressView {
     pub zip: String,
 }
 
+/// Saved address card for the address book.
+#[derive(Clone)]
+pub struct AddressCardView {
+    pub id: String,
+    pub name: String,
+    pub company: Option<String>,
+    pub address1: Option<String>,
+    pub address2: Option<String>,
+    pub locality: String,
+    pub country: Option<String>,
+    pub phone: Option<String>,
+    pub is_default: bool,
+}
+
+impl AddressCardView {
+    /// Build a card, naming the country from its code where known.
+    fn new(address: Address, default_address_id: Option<&str>) -> Self {
+        let name = [address.first_name.as_deref(), address.last_name.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let region = [address.province_code.as_deref(), address.zip.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let locality = match address.city.as_deref() {
+            Some(city) if !region.is_empty() => format!("{city}, {region}"),
+            Some(city) => city.to_string(),
+            None => region,
+        };
+        let country = address
+            .country_code
+            .as_deref()
+            .and_then(country_name)
+            .map(str::to_string)
+            .or(address.country);
+
+        Self {
+            is_default: default_address_id == Some(address.id.as_str()),
+            id: address.id,
+            name,
+            company: address.company,
+            address1: address.address1,
+            address2: address.address2,
+            locality,
+            country,
+            phone: address.phone,
+        }
+    }
+}
+
+/// Country option for the address form.
+#[derive(Clone)]
+pub struct CountryOption {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub selected: bool,
+}
+
+/// Address form state for templates.
+#[derive(Clone)]
+pub struct AddressFormView {
+    pub is_edit: bool,
+    /// Rendered inside the address book, submitting over HTMX.
+    pub inline: bool,
+    pub action: String,
+    pub values: AddressForm,
+    pub countries: Vec<CountryOption>,
+    pub error: Option<String>,
+}
+
+impl AddressFormView {
+    /// Build a form for a new address (`address_id` is `None`) or an edit.
+    fn new(address_id: Option<&str>, values: AddressForm, inline: bool) -> Self {
+        let countries = COUNTRIES
+            .iter()
+            .map(|&(code, name)| CountryOption {
+                code,
+                name,
+                selected: code.eq_ignore_ascii_case(&values.country),
+            })
+            .collect();
+
+        Self {
+            is_edit: address_id.is_some(),
+            inline,
+            action: address_id.map_or_else(
+                || "/account/addresses/new".to_string(),
+                |id| format!("/account/addresses/{id}"),
+            ),
+            values,
+            countries,
+            error: None,
+        }
+    }
+
+    /// Show `error` above the form.
+    fn with_error(mut self, error: String) -> Self {
+        self.error = Some(error);
+        self
+    }
+}
+
+/// Profile form state for templates.
+#[derive(Clone)]
+pub struct ProfileFormView {
+    pub values: ProfileForm,
+    pub error: Option<String>,
+    /// Informational message, e.g. that a change awaits verification.
+    pub notice: Option<String>,
+}
+
+impl ProfileFormView {
+    const fn new(values: ProfileForm) -> Self {
+        Self {
+            values,
+            error: None,
+            notice: None,
+        }
+    }
+
+    /// Show `error` above the form.
+    fn with_error(mut self, error: String) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    /// Show `notice` above the form.
+    fn with_notice(mut self, notice: String) -> Self {
+        self.notice = Some(notice);
+        self
+    }
+}
+
 // =============================================================================
 // Templates
 // =============================================================================
@@ -84,44 +368,72 @@ pub struct OrdersTemplate {
     pub nonce: String,
 }
 
+/// Order detail page template.
+#[derive(Template, WebTemplate)]
+#[template(path = "account/order.html")]
+pub struct OrderDetailTemplate {
+    pub order: OrderDetailView,
+    pub analytics: AnalyticsConfig,
+    pub nonce: String,
+}
+
 /// Addresses list page template.
 #[derive(Template, WebTemplate)]
 #[template(path = "account/addresses.html")]
 pub struct AddressesTemplate {
-    pub addresses: Vec<Address>,
-    pub default_address_id: Option<String>,
+    pub addresses: Vec<AddressCardView>,
+    pub error: Option<String>,
     pub analytics: AnalyticsConfig,
     pub nonce: String,
 }
 
-impl AddressesTemplate {
-    /// Check if an address ID is the default address.
-    #[must_use]
-    pub fn is_default(&self, address_id: &str) -> bool {
-        self.default_address_id
-            .as_ref()
-            .is_some_and(|id| id == address_id)
-    }
+/// Address book fragment template (for HTMX).
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/address_book.html")]
+pub struct AddressBookTemplate {
+    pub addresses: Vec<AddressCardView>,
+    pub error: Option<String>,
 }
 
-/// Address form template (create/edit).
+/// Address form page template (create/edit).
 #[derive(Template, WebTemplate)]
 #[template(path = "account/address_form.html")]
 pub struct AddressFormTemplate {
-    pub is_edit: bool,
-    pub address_id: Option<String>,
-    pub address: Option<Address>,
-    pub error: Option<String>,
+    pub form: AddressFormView,
+    pub analytics: AnalyticsConfig,
+    pub nonce: String,
+}
+
+/// Address form fragment template (for HTMX).
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/address_form.html")]
+pub struct AddressFormFragmentTemplate {
+    pub form: AddressFormView,
+}
+
+/// Profile page template.
+#[derive(Template, WebTemplate)]
+#[template(path = "account/profile.html")]
+pub struct ProfileTemplate {
+    pub email: String,
+    pub form: ProfileFormView,
     pub analytics: AnalyticsConfig,
     pub nonce: String,
 }
 
+/// Profile form fragment template (for HTMX).
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/profile_form.html")]
+pub struct ProfileFormFragmentTemplate {
+    pub form: ProfileFormView,
+}
+
 // =============================================================================
 // Form Data
 // =============================================================================
 
 /// Address form data.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct AddressForm {
     pub first_name: String,
     pub last_name: String,
@@ -135,6 +447,35 @@ pub struct AddressForm {
     pub phone: Option<String>,
 }
 
+impl AddressForm {
+    /// Validate the postal code for the selected country, normalizing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the postal code doesn't match the country's format.
+    pub fn validate(&mut self) -> Result<(), PostalCodeError> {
+        self.zip = normalize_postal_code(&self.country, &self.zip)?;
+        Ok(())
+    }
+}
+
+impl From<Address> for AddressForm {
+    fn from(address: Address) -> Self {
+        Self {
+            first_name: address.first_name.unwrap_or_default(),
+            last_name: address.last_name.unwrap_or_default(),
+            company: address.company,
+            address1: address.address1.unwrap_or_default(),
+            address2: address.address2,
+            city: address.city.unwrap_or_default(),
+            province: address.province_code.unwrap_or_default(),
+            zip: address.zip.unwrap_or_default(),
+            country: address.country_code.unwrap_or_default(),
+            phone: address.phone,
+        }
+    }
+}
+
 impl From<AddressForm> for AddressInput {
     fn from(form: AddressForm) -> Self {
         Self {
@@ -152,6 +493,47 @@ impl From<AddressForm> for AddressInput {
     }
 }
 
+/// Profile form data.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProfileForm {
+    pub first_name: String,
+    pub last_name: String,
+    /// Phone number, empty to leave it unchanged.
+    #[serde(default)]
+    pub phone: String,
+}
+
+impl ProfileForm {
+    /// Validate the phone number, normalizing it to E.164.
+    ///
+    /// Numbers without a country code are read as US numbers.
+    ///
+    /// # Errors
+    ///
+    /// Returns a user-facing message if the phone number isn't valid.
+    pub fn validate(&mut self) -> Result<(), String> {
+        self.first_name = self.first_name.trim().to_string();
+        self.last_name = self.last_name.trim().to_string();
+
+        let phone = self.phone.trim();
+        if phone.is_empty() {
+            self.phone = String::new();
+            return Ok(());
+        }
+
+        match phonenumber::parse(Some(phonenumber::country::Id::US), phone) {
+            Ok(number) if phonenumber::is_valid(&number) => {
+                self.phone = number.format().mode(phonenumber::Mode::E164).to_string();
+                Ok(())
+            }
+            _ => Err(
+                "Enter a valid phone number, with the country code if it's outside the US."
+                    .to_string(),
+            ),
+        }
+    }
+}
+
 // =============================================================================
 // Route Handlers
 // =============================================================================
@@ -253,41 +635,148 @@ pub async fn orders(
     }
 }
 
-/// Display addresses list page.
+/// Display order details page.
+///
+/// `id` is the numeric order ID.
 ///
 /// # Route
 ///
-/// `GET /account/addresses`
-pub async fn addresses(
+/// `GET /account/orders/:id`
+pub async fn order(
     State(state): State<AppState>,
     RequireShopifyCustomer(token): RequireShopifyCustomer,
+    Path(order_id): Path<String>,
     crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
-) -> impl IntoResponse {
-    // Fetch addresses
-    let addresses = match state
-        .customer()
-        .get_addresses(&token.access_token, 50)
-        .await
-    {
-        Ok(addresses) => addresses,
+) -> Response {
+    if !order_id.chars().all(|c| c.is_ascii_digit()) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    let gid = format!("gid://shopify/Order/{order_id}");
+    match state.customer().get_order(&token.access_token, &gid).await {
+        Ok(Some(order)) => OrderDetailTemplate {
+            order: order.into(),
+            analytics: state.config().analytics.clone(),
+            nonce,
+        }
+        .into_response(),
+        Ok(None) => Redirect::to("/account/orders").into_response(),
         Err(e) => {
-            tracing::error!("Failed to fetch addresses: {}", e);
-            Vec::new()
+            tracing::error!("Failed to fetch order {}: {}", order_id, e);
+            Redirect::to("/account/orders").into_response()
+        }
+    }
+}
+
+/// Display profile page.
+///
+/// # Route
+///
+/// `GET /account/profile`
+pub async fn profile(
+    State(state): State<AppState>,
+    RequireShopifyCustomer(token): RequireShopifyCustomer,
+    crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
+) -> Response {
+    let customer = match state.customer().get_customer(&token.access_token).await {
+        Ok(customer) => customer,
+        Err(e) => {
+            tracing::error!("Failed to fetch customer: {}", e);
+            return Redirect::to("/account").into_response();
         }
     };
 
-    // Fetch customer to get default address ID
-    let default_address_id = match state.customer().get_customer(&token.access_token).await {
-        Ok(customer) => customer.default_address.map(|a| a.id),
-        Err(_) => None,
+    let values = ProfileForm {
+        first_name: customer.first_name.unwrap_or_default(),
+        last_name: customer.last_name.unwrap_or_default(),
+        phone: customer.phone.unwrap_or_default(),
+    };
+
+    ProfileTemplate {
+        email: customer.email.unwrap_or_default(),
+        form: ProfileFormView::new(values),
+        analytics: state.config().analytics.clone(),
+        nonce,
+    }
+    .into_response()
+}
+
+/// Update the customer's name and phone number.
+///
+/// HTMX requests get the form back with a success toast. Changes Shopify
+/// holds for email verification are reported as a notice, not an error.
+///
+/// # Route
+///
+/// `POST /account/profile`
+pub async fn update_profile(
+    State(state): State<AppState>,
+    RequireShopifyCustomer(token): RequireShopifyCustomer,
+    headers: HeaderMap,
+    crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
+    Form(mut form): Form<ProfileForm>,
+) -> Response {
+    if let Err(message) = form.validate() {
+        let view = ProfileFormView::new(form).with_error(message);
+        return profile_form(&state, &token.access_token, &headers, nonce, view).await;
+    }
+
+    let phone = Some(form.phone.clone()).filter(|p| !p.is_empty());
+    let result = state
+        .customer()
+        .update_customer_profile(
+            &token.access_token,
+            Some(form.first_name.clone()),
+            Some(form.last_name.clone()),
+            phone,
+        )
+        .await;
+
+    let view = match result {
+        Ok(()) if is_fragment_request(&headers) => {
+            return (
+                AppendHeaders([("HX-Trigger", r#"{"showToast": "Profile updated"}"#)]),
+                ProfileFormFragmentTemplate {
+                    form: ProfileFormView::new(form),
+                },
+            )
+                .into_response();
+        }
+        Ok(()) => return Redirect::to("/account/profile").into_response(),
+        Err(ShopifyError::EmailVerificationRequired) => ProfileFormView::new(form).with_notice(
+            "Verification email sent. Your changes take effect once you confirm them.".to_string(),
+        ),
+        Err(e) => {
+            tracing::error!("Failed to update profile: {}", e);
+            ProfileFormView::new(form).with_error(e.to_string())
+        }
     };
 
+    profile_form(&state, &token.access_token, &headers, nonce, view).await
+}
+
+/// Display addresses list page.
+///
+/// # Route
+///
+/// `GET /account/addresses`
+pub async fn addresses(
+    State(state): State<AppState>,
+    RequireShopifyCustomer(token): RequireShopifyCustomer,
+    headers: HeaderMap,
+    crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
+) -> Response {
+    if is_fragment_request(&headers) {
+        return address_book(&state, &token.access_token, None).await;
+    }
+
     AddressesTemplate {
-        addresses,
-        default_address_id,
+        addresses: fetch_address_cards(&state, &token.access_token).await,
+        error: None,
         analytics: state.config().analytics.clone(),
         nonce,
     }
+    .into_response()
 }
 
 /// Display new address form.
@@ -298,48 +787,46 @@ pub async fn addresses(
 pub async fn new_address(
     State(state): State<AppState>,
     RequireShopifyCustomer(_token): RequireShopifyCustomer,
+    headers: HeaderMap,
     crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
-) -> impl IntoResponse {
-    AddressFormTemplate {
-        is_edit: false,
-        address_id: None,
-        address: None,
-        error: None,
-        analytics: state.config().analytics.clone(),
+) -> Response {
+    let inline = is_fragment_request(&headers);
+    address_form(
+        &state,
         nonce,
-    }
+        AddressFormView::new(None, AddressForm::default(), inline),
+    )
 }
 
 /// Create a new address.
 ///
 /// # Route
 ///
-/// `POST /account/addresses`
+/// `POST /account/addresses/new` (or `POST /account/addresses`)
 pub async fn create_address(
     State(state): State<AppState>,
     RequireShopifyCustomer(token): RequireShopifyCustomer,
+    headers: HeaderMap,
     crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
-    Form(form): Form<AddressForm>,
+    Form(mut form): Form<AddressForm>,
 ) -> Response {
-    let input: AddressInput = form.into();
+    let inline = is_fragment_request(&headers);
+    if let Err(e) = form.validate() {
+        let view = AddressFormView::new(None, form, inline).with_error(postal_code_message(&e));
+        return address_form(&state, nonce, view);
+    }
 
     match state
         .customer()
-        .create_address(&token.access_token, input)
+        .create_address(&token.access_token, form.clone().into())
         .await
     {
+        Ok(_) if inline => address_book(&state, &token.access_token, None).await,
         Ok(_) => Redirect::to("/account/addresses").into_response(),
         Err(e) => {
             tracing::error!("Failed to create address: {}", e);
-            AddressFormTemplate {
-                is_edit: false,
-                address_id: None,
-                address: None,
-                error: Some(e.to_string()),
-                analytics: state.config().analytics.clone(),
-                nonce,
-            }
-            .into_response()
+            let view = AddressFormView::new(None, form, inline).with_error(e.to_string());
+            address_form(&state, nonce, view)
         }
     }
 }
@@ -353,6 +840,7 @@ pub async fn edit_address(
     State(state): State<AppState>,
     RequireShopifyCustomer(token): RequireShopifyCustomer,
     Path(address_id): Path<String>,
+    headers: HeaderMap,
     crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
 ) -> Response {
     // Fetch addresses and find the one we want
@@ -373,56 +861,46 @@ pub async fn edit_address(
         return Redirect::to("/account/addresses").into_response();
     };
 
-    AddressFormTemplate {
-        is_edit: true,
-        address_id: Some(addr.id.clone()),
-        address: Some(addr),
-        error: None,
-        analytics: state.config().analytics.clone(),
+    let inline = is_fragment_request(&headers);
+    address_form(
+        &state,
         nonce,
-    }
-    .into_response()
+        AddressFormView::new(Some(&address_id), addr.into(), inline),
+    )
 }
 
 /// Update an existing address.
 ///
 /// # Route
 ///
-/// `POST /account/addresses/:id`
+/// `PUT /account/addresses/:id` (or `POST` from a non-JS form)
 pub async fn update_address(
     State(state): State<AppState>,
     RequireShopifyCustomer(token): RequireShopifyCustomer,
     Path(address_id): Path<String>,
+    headers: HeaderMap,
     crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
-    Form(form): Form<AddressForm>,
+    Form(mut form): Form<AddressForm>,
 ) -> Response {
-    let input: AddressInput = form.into();
+    let inline = is_fragment_request(&headers);
+    if let Err(e) = form.validate() {
+        let view = AddressFormView::new(Some(&address_id), form, inline)
+            .with_error(postal_code_message(&e));
+        return address_form(&state, nonce, view);
+    }
 
     match state
         .customer()
-        .update_address(&token.access_token, &address_id, input)
+        .update_address(&token.access_token, &address_id, form.clone().into())
         .await
     {
+        Ok(_) if inline => address_book(&state, &token.access_token, None).await,
         Ok(_) => Redirect::to("/account/addresses").into_response(),
         Err(e) => {
             tracing::error!("Failed to update address: {}", e);
-            // Fetch the address again to show the form with error
-            let addresses = state
-                .customer()
-                .get_addresses(&token.access_token, 50)
-                .await
-                .unwrap_or_default();
-            let address = addresses.into_iter().find(|a| a.id == address_id);
-
-            AddressFormTemplate {
-                is_edit: true,
-                address_id: Some(address_id),
-                address,
-                error: Some(e.to_string()),
-                analytics: state.config().analytics.clone(),
-                nonce,
-            }
-            .into_response()
+            let view =
+                AddressFormView::new(Some(&address_id), form, inline).with_error(e.to_string());
+            address_form(&state, nonce, view)
         }
     }
 }
@@ -437,19 +915,48 @@ pub async fn delete_address(
     RequireShopifyCustomer(token): RequireShopifyCustomer,
     Path(address_id): Path<String>,
 ) -> Response {
-    match state
+    let error = match state
         .customer()
         .delete_address(&token.access_token, &address_id)
         .await
     {
-        Ok(()) => {
-            // Return empty response for HTMX (removes the element)
-            StatusCode::OK.into_response()
-        }
+        Ok(()) => None,
         Err(e) => {
             tracing::error!("Failed to delete address: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            Some(format!("Couldn't delete the address: {e}"))
         }
+    };
+
+    address_book(&state, &token.access_token, error).await
+}
+
+/// Make an address the default.
+///
+/// # Route
+///
+/// `POST /account/addresses/:id/default`
+pub async fn set_default_address(
+    State(state): State<AppState>,
+    RequireShopifyCustomer(token): RequireShopifyCustomer,
+    Path(address_id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let error = match state
+        .customer()
+        .set_default_address(&token.access_token, &address_id)
+        .await
+    {
+        Ok(()) => None,
+        Err(e) => {
+            tracing::error!("Failed to set default address: {}", e);
+            Some(format!("Couldn't set the default address: {e}"))
+        }
+    };
+
+    if is_fragment_request(&headers) {
+        address_book(&state, &token.access_token, error).await
+    } else {
+        Redirect::to("/account/addresses").into_response()
     }
 }
 
@@ -457,6 +964,112 @@ pub async fn delete_address(
 // Helpers
 // =============================================================================
 
+/// Whether the request is an HTMX request for a fragment, rather than a
+/// boosted page navigation.
+fn is_fragment_request(headers: &HeaderMap) -> bool {
+    headers.contains_key("HX-Request") && !headers.contains_key("HX-Boosted")
+}
+
+/// Fetch the customer's addresses as cards, default address first.
+async fn fetch_address_cards(state: &AppState, access_token: &str) -> Vec<AddressCardView> {
+    let customer = state.customer();
+    let (addresses, account) = tokio::join!(
+        customer.get_addresses(access_token, 50),
+        customer.get_customer(access_token)
+    );
+
+    let addresses = addresses.unwrap_or_else(|e| {
+        tracing::error!("Failed to fetch addresses: {}", e);
+        Vec::new()
+    });
+    let default_address_id = account.ok().and_then(|c| c.default_address).map(|a| a.id);
+
+    let mut cards: Vec<AddressCardView> = addresses
+        .into_iter()
+        .map(|address| AddressCardView::new(address, default_address_id.as_deref()))
+        .collect();
+    cards.sort_by_key(|card| !card.is_default);
+    cards
+}
+
+/// Render the address book fragment.
+async fn address_book(state: &AppState, access_token: &str, error: Option<String>) -> Response {
+    AddressBookTemplate {
+        addresses: fetch_address_cards(state, access_token).await,
+        error,
+    }
+    .into_response()
+}
+
+/// Render the address form, as a fragment inside the address book when
+/// `form.inline`, otherwise as a full page.
+///
+/// Inline forms target the whole address book, so a form with an error is
+/// retargeted at the form panel instead.
+fn address_form(state: &AppState, nonce: String, form: AddressFormView) -> Response {
+    if !form.inline {
+        return AddressFormTemplate {
+            form,
+            analytics: state.config().analytics.clone(),
+            nonce,
+        }
+        .into_response();
+    }
+
+    if form.error.is_some() {
+        (
+            AppendHeaders([
+                ("HX-Retarget", "#address-form-panel"),
+                ("HX-Reswap", "innerHTML"),
+            ]),
+            AddressFormFragmentTemplate { form },
+        )
+            .into_response()
+    } else {
+        AddressFormFragmentTemplate { form }.into_response()
+    }
+}
+
+/// Render the profile form, as a fragment for HTMX requests, otherwise as the
+/// full profile page.
+async fn profile_form(
+    state: &AppState,
+    access_token: &str,
+    headers: &HeaderMap,
+    nonce: String,
+    form: ProfileFormView,
+) -> Response {
+    if is_fragment_request(headers) {
+        return ProfileFormFragmentTemplate { form }.into_response();
+    }
+
+    let email = match state.customer().get_customer(access_token).await {
+        Ok(customer) => customer.email.unwrap_or_default(),
+        Err(e) => {
+            tracing::warn!("Failed to fetch customer: {}", e);
+            String::new()
+        }
+    };
+
+    ProfileTemplate {
+        email,
+        form,
+        analytics: state.config().analytics.clone(),
+        nonce,
+    }
+    .into_response()
+}
+
+/// User-facing message for an invalid postal code.
+fn postal_code_message(error: &PostalCodeError) -> String {
+    match error {
+        PostalCodeError::Empty => "Enter a postal code.".to_string(),
+        PostalCodeError::InvalidFormat { example } => {
+            format!("Enter a valid postal code for the selected country, like {example}.")
+        }
+    }
+}
+
 /// Format a Money value for display.
 fn format_money(money: &Money) -> String {
     let amount: f64 = money.amount.parse().unwrap_or(0.0);
@@ -469,3 +1082,51 @@ fn format_money(money: &Money) -> String {
         _ => format!("{amount:.2} {currency}"),
     }
 }
+
+/// Turn a Shopify status enum value into a label (e.g., `PARTIALLY_FULFILLED`
+/// becomes "Partially fulfilled").
+fn humanize_status(status: &str) -> String {
+    let lower = status.replace('_', " ").to_lowercase();
+    let mut chars = lower.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form_with_phone(phone: &str) -> ProfileForm {
+        ProfileForm {
+            first_name: " Ada ".to_string(),
+            last_name: "Lovelace".to_string(),
+            phone: phone.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_profile_form_normalizes_phone() {
+        let mut form = form_with_phone("(415) 555-2671");
+        assert!(form.validate().is_ok());
+        assert_eq!(form.phone, "+14155552671");
+        assert_eq!(form.first_name, "Ada");
+
+        let mut form = form_with_phone("+44 20 7946 0958");
+        assert!(form.validate().is_ok());
+        assert_eq!(form.phone, "+442079460958");
+    }
+
+    #[test]
+    fn test_profile_form_allows_empty_phone() {
+        let mut form = form_with_phone("  ");
+        assert!(form.validate().is_ok());
+        assert!(form.phone.is_empty());
+    }
+
+    #[test]
+    fn test_profile_form_rejects_invalid_phone() {
+        assert!(form_with_phone("12345").validate().is_err());
+        assert!(form_with_phone("not a number").validate().is_err());
+    }
+}
@@ -5,16 +5,16 @@ use askama_web::WebTemplate;
 use axum::{
     Router,
     extract::{Path, State},
-    http::StatusCode,
-    response::IntoResponse,
+    response::{IntoResponse, Redirect, Response},
     routing::get,
 };
 use chrono::NaiveDate;
 use tracing::instrument;
 
 use crate::config::AnalyticsConfig;
-use crate::content::Post;
+use crate::content::{ContentEntry, ContentType, Post};
 use crate::filters;
+use crate::routes::pages::not_found;
 use crate::routes::products::BreadcrumbItem;
 use crate::state::AppState;
 
@@ -22,6 +22,8 @@ use crate::state::AppState;
 #[derive(Clone)]
 pub struct PostView {
     pub slug: String,
+    /// URL path (e.g., `/blog/2025/01/my-post`).
+    pub url: String,
     pub title: String,
     pub description: Option<String>,
     pub author: Option<String>,
@@ -36,6 +38,7 @@ impl From<&Post> for PostView {
     fn from(post: &Post) -> Self {
         Self {
             slug: post.slug.clone(),
+            url: post.url_path(),
             title: post.meta.title.clone(),
             description: post.meta.description.clone(),
             author: post.meta.author.clone(),
@@ -97,30 +100,28 @@ pub async fn index(
     }
 }
 
-/// Display a single blog post by slug.
+/// Display a single blog post at `/blog/{year}/{month}/{slug}`.
 ///
-/// # Errors
-///
-/// Returns 404 if the post doesn't exist or is a draft.
+/// Bare `/blog/{slug}` URLs redirect permanently to the dated URL. Unknown
+/// paths and drafts render the 404 page.
 #[instrument(skip(state, nonce))]
 pub async fn show(
     State(state): State<AppState>,
-    Path(slug): Path<String>,
+    Path(path): Path<String>,
     crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
-) -> Result<impl IntoResponse, StatusCode> {
-    let post = state
-        .content()
-        .get_post(&slug)
-        .ok_or(StatusCode::NOT_FOUND)?;
-
-    // Don't show draft posts
-    if post.meta.draft {
-        return Err(StatusCode::NOT_FOUND);
-    }
+) -> Response {
+    let Some(ContentEntry::Post(post)) = state.content().router().resolve(ContentType::Post, &path)
+    else {
+        return match state.content().get_post(&path) {
+            Some(post) if !post.meta.draft => Redirect::permanent(&post.url_path()).into_response(),
+            _ => not_found(&state, nonce),
+        };
+    };
+    let slug = post.slug.as_str();
 
     let recent_posts: Vec<PostView> = state
         .content()
-        .get_recent_posts(RECENT_POSTS_COUNT, Some(&slug))
+        .get_recent_posts(RECENT_POSTS_COUNT, Some(slug))
         .into_iter()
         .map(PostView::from)
         .collect();
@@ -146,7 +147,7 @@ pub async fn show(
     let base_url = state.config().base_url.clone();
     let logo_url = crate::filters::get_logo_url(&base_url);
 
-    Ok(BlogShowTemplate {
+    BlogShowTemplate {
         post: post_view,
         recent_posts,
         analytics: state.config().analytics.clone(),
@@ -154,12 +155,13 @@ pub async fn show(
         base_url,
         logo_url,
         breadcrumbs,
-    })
+    }
+    .into_response()
 }
 
 /// Create the blog routes router.
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(index))
-        .route("/{slug}", get(show))
+        .route("/{*path}", get(show))
 }
@@ -1,26 +1,32 @@
 //! Cart route handlers.
 //!
 //! Cart operations use HTMX for dynamic updates without full page reloads.
-//! Cart IDs are stored in the session and mapped to Shopify carts.
+//! Carts are keyed by session ID and persisted through [`CartService`].
 
 use askama::Template;
 use askama_web::WebTemplate;
 use axum::{
     Form,
-    extract::State,
+    extract::{Path, State},
     http::StatusCode,
     response::{AppendHeaders, Html, IntoResponse, Redirect, Response},
 };
-use serde::Deserialize;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use tower_sessions::Session;
 use tracing::instrument;
 
 use crate::config::AnalyticsConfig;
 use crate::filters;
+use crate::middleware::BuyerCurrency;
 use crate::models::session_keys;
-use crate::shopify::types::{Cart as ShopifyCart, CartLineInput, CartLineUpdateInput, Money};
+use crate::services::{CartError, CartService, PriceFormatter};
+use crate::shopify::ShopifyError;
+use crate::shopify::types::{Cart as ShopifyCart, CartLineInput, Money, ShippingRate};
 use crate::state::AppState;
 
+use super::products::apply_bundle_selections;
+
 /// Cart item display data for templates.
 #[derive(Clone)]
 pub struct CartItemView {
@@ -60,30 +66,199 @@ impl CartView {
     }
 }
 
+/// Shipping rate option display data for templates.
+#[derive(Clone)]
+pub struct ShippingRateView {
+    pub handle: String,
+    pub title: String,
+    pub price: String,
+    /// Delivery estimate (e.g., "Up to 5 days").
+    pub delivery_estimate: Option<String>,
+}
+
+/// Shipping estimate display data for the cart summary.
+#[derive(Clone, Default)]
+pub struct ShippingEstimateView {
+    pub country_code: String,
+    pub province_code: String,
+    pub zip: String,
+    pub rates: Vec<ShippingRateView>,
+    pub selected_handle: Option<String>,
+    /// Price of the selected shipping rate, if one is selected.
+    pub shipping: Option<String>,
+    /// Estimated total (subtotal plus the selected shipping rate).
+    pub total: String,
+    pub error: Option<String>,
+}
+
+/// Discount code applied to the cart, for templates.
+#[derive(Clone)]
+pub struct DiscountCodeView {
+    pub code: String,
+    /// Whether the code currently applies to the cart.
+    pub applicable: bool,
+}
+
+/// Discount codes and totals for the cart summary.
+#[derive(Clone, Default)]
+pub struct CartDiscountView {
+    pub codes: Vec<DiscountCodeView>,
+    /// Total discounted across all lines, if anything was discounted.
+    pub savings: Option<String>,
+    /// Cart total after discounts.
+    pub total: String,
+    /// Code that was just applied (shows the confirmation).
+    pub applied: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Shipping estimate stored in the session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ShippingEstimate {
+    country_code: String,
+    province_code: Option<String>,
+    zip: Option<String>,
+    rates: Vec<ShippingRate>,
+    selected_handle: Option<String>,
+}
+
+impl ShippingEstimate {
+    /// The currently selected rate, if it is still one of the estimated rates.
+    fn selected_rate(&self) -> Option<&ShippingRate> {
+        let handle = self.selected_handle.as_deref()?;
+        self.rates.iter().find(|rate| rate.handle == handle)
+    }
+}
+
 // =============================================================================
 // Type Conversions
 // =============================================================================
 
-/// Format a Shopify Money type as a price string.
-fn format_price(money: &Money) -> String {
-    money.amount.parse::<f64>().map_or_else(
-        |_| format!("${}", money.amount),
-        |amount| format!("${amount:.2}"),
-    )
+/// Sum Shopify Money amounts that are all in `currency_code`.
+///
+/// Returns `None` if an amount can't be parsed or is in another currency.
+fn sum_amounts<'a>(
+    currency_code: &str,
+    amounts: impl IntoIterator<Item = &'a Money>,
+) -> Option<Decimal> {
+    amounts.into_iter().try_fold(Decimal::ZERO, |total, money| {
+        if money.currency_code != currency_code {
+            return None;
+        }
+        Some(total + money.amount.parse::<Decimal>().ok()?)
+    })
 }
 
-impl From<&ShopifyCart> for CartView {
-    fn from(cart: &ShopifyCart) -> Self {
+impl ShippingRateView {
+    /// Build the view for a rate, with its price in the buyer's currency.
+    fn localized(rate: &ShippingRate, formatter: &PriceFormatter) -> Self {
         Self {
-            items: cart.lines.iter().map(CartItemView::from).collect(),
-            subtotal: format_price(&cart.cost.subtotal),
+            handle: rate.handle.clone(),
+            title: rate.title.clone(),
+            price: formatter.format(&rate.price),
+            delivery_estimate: rate.estimated_delivery_days.map(|days| match days {
+                1 => "Up to 1 day".to_string(),
+                n => format!("Up to {n} days"),
+            }),
+        }
+    }
+}
+
+impl ShippingEstimateView {
+    /// Build the summary view for a cart subtotal and optional estimate.
+    fn new(
+        subtotal: &Money,
+        estimate: Option<&ShippingEstimate>,
+        error: Option<String>,
+        formatter: &PriceFormatter,
+    ) -> Self {
+        let Some(estimate) = estimate else {
+            return Self {
+                country_code: DEFAULT_COUNTRY_CODE.to_string(),
+                total: formatter.format(subtotal),
+                error,
+                ..Self::default()
+            };
+        };
+
+        let selected = estimate.selected_rate();
+        let total = selected
+            .and_then(|rate| sum_amounts(&subtotal.currency_code, [subtotal, &rate.price]))
+            .map_or_else(
+                || formatter.format(subtotal),
+                |total| formatter.format_amount(total, &subtotal.currency_code),
+            );
+
+        Self {
+            country_code: estimate.country_code.clone(),
+            province_code: estimate.province_code.clone().unwrap_or_default(),
+            zip: estimate.zip.clone().unwrap_or_default(),
+            rates: estimate
+                .rates
+                .iter()
+                .map(|rate| ShippingRateView::localized(rate, formatter))
+                .collect(),
+            selected_handle: selected.map(|rate| rate.handle.clone()),
+            shipping: selected.map(|rate| formatter.format(&rate.price)),
+            total,
+            error,
+        }
+    }
+}
+
+impl CartDiscountView {
+    /// Build the discount summary for a cart.
+    fn new(
+        cart: &ShopifyCart,
+        applied: Option<String>,
+        error: Option<String>,
+        formatter: &PriceFormatter,
+    ) -> Self {
+        let currency_code = &cart.cost.total.currency_code;
+        let savings = sum_amounts(
+            currency_code,
+            cart.lines
+                .iter()
+                .flat_map(|line| &line.discount_allocations)
+                .map(|allocation| &allocation.discounted_amount),
+        )
+        .filter(|savings| *savings > Decimal::ZERO);
+
+        Self {
+            codes: cart
+                .discount_codes
+                .iter()
+                .map(|code| DiscountCodeView {
+                    code: code.code.clone(),
+                    applicable: code.applicable,
+                })
+                .collect(),
+            savings: savings.map(|savings| formatter.format_amount(savings, currency_code)),
+            total: formatter.format(&cart.cost.total),
+            applied,
+            error,
+        }
+    }
+}
+
+impl CartView {
+    /// Build the view for a cart, with prices in the buyer's currency.
+    fn localized(cart: &ShopifyCart, formatter: &PriceFormatter) -> Self {
+        Self {
+            items: cart
+                .lines
+                .iter()
+                .map(|line| CartItemView::localized(line, formatter))
+                .collect(),
+            subtotal: formatter.format(&cart.cost.subtotal),
             item_count: u32::try_from(cart.total_quantity).unwrap_or(0),
         }
     }
 }
 
-impl From<&crate::shopify::types::CartLine> for CartItemView {
-    fn from(line: &crate::shopify::types::CartLine) -> Self {
+impl CartItemView {
+    /// Build the view for a cart line, with prices in the buyer's currency.
+    fn localized(line: &crate::shopify::types::CartLine, formatter: &PriceFormatter) -> Self {
         Self {
             id: line.id.clone(),
             handle: line.merchandise.product.handle.clone(),
@@ -94,8 +269,8 @@ impl From<&crate::shopify::types::CartLine> for CartItemView {
                 Some(line.merchandise.title.clone())
             },
             quantity: u32::try_from(line.quantity).unwrap_or(1),
-            price: format_price(&line.cost.amount_per_quantity),
-            line_price: format_price(&line.cost.total_amount),
+            price: formatter.format(&line.cost.amount_per_quantity),
+            line_price: formatter.format(&line.cost.total_amount),
             image: line.merchandise.image.as_ref().map(|img| ImageView {
                 url: img.url.clone(),
             }),
@@ -107,23 +282,71 @@ impl From<&crate::shopify::types::CartLine> for CartItemView {
 // Session Helpers
 // =============================================================================
 
-/// Get the cart ID from the session.
-async fn get_cart_id(session: &Session) -> Option<String> {
+/// Get the session ID, which keys the session's cart.
+///
+/// A session has no ID until it is first saved, so a new session has no cart.
+fn cart_key(session: &Session) -> Option<String> {
+    session.id().map(|id| id.to_string())
+}
+
+/// Get the session ID for adding to the cart, saving a new session first.
+///
+/// The cart row references the session row, so the session must exist in the
+/// store before its cart does.
+async fn cart_key_for_add(session: &Session) -> Result<String, tower_sessions::session::Error> {
+    if let Some(key) = cart_key(session) {
+        return Ok(key);
+    }
+    session.save().await?;
+    Ok(cart_key(session).unwrap_or_default())
+}
+
+/// Get the session's cart, if it has one.
+async fn session_cart(
+    carts: &CartService,
+    session: &Session,
+) -> Result<Option<ShopifyCart>, CartError> {
+    match cart_key(session) {
+        Some(key) => carts.get(&key).await,
+        None => Ok(None),
+    }
+}
+
+/// Get the shipping estimate from the session.
+async fn get_shipping_estimate(session: &Session) -> Option<ShippingEstimate> {
     session
-        .get::<String>(session_keys::CART_ID)
+        .get::<ShippingEstimate>(session_keys::SHIPPING_ESTIMATE)
         .await
         .ok()
         .flatten()
 }
 
-/// Set the cart ID in the session.
-async fn set_cart_id(
+/// Save the shipping estimate in the session.
+async fn set_shipping_estimate(
     session: &Session,
-    cart_id: &str,
+    estimate: &ShippingEstimate,
 ) -> Result<(), tower_sessions::session::Error> {
-    session.insert(session_keys::CART_ID, cart_id).await
+    session
+        .insert(session_keys::SHIPPING_ESTIMATE, estimate)
+        .await
 }
 
+/// Remove the shipping estimate from the session.
+///
+/// Called whenever the cart's lines change, since the estimated rates and
+/// total were quoted for the old lines.
+async fn clear_shipping_estimate(session: &Session) {
+    if let Err(e) = session
+        .remove::<ShippingEstimate>(session_keys::SHIPPING_ESTIMATE)
+        .await
+    {
+        tracing::warn!("Failed to clear shipping estimate from session: {e}");
+    }
+}
+
+/// Country preselected in the shipping estimate form.
+const DEFAULT_COUNTRY_CODE: &str = "US";
+
 /// Add to cart form data.
 #[derive(Debug, Deserialize)]
 pub struct AddToCartForm {
@@ -144,11 +367,33 @@ pub struct RemoveFromCartForm {
     pub line_id: String,
 }
 
+/// Shipping estimate form data.
+#[derive(Debug, Deserialize)]
+pub struct EstimateShippingForm {
+    pub country_code: String,
+    pub province_code: Option<String>,
+    pub zip: Option<String>,
+}
+
+/// Shipping rate selection form data.
+#[derive(Debug, Deserialize)]
+pub struct SelectShippingRateForm {
+    pub handle: String,
+}
+
+/// Discount code form data.
+#[derive(Debug, Deserialize)]
+pub struct ApplyDiscountForm {
+    pub code: String,
+}
+
 /// Cart page template.
 #[derive(Template, WebTemplate)]
 #[template(path = "cart/show.html")]
 pub struct CartShowTemplate {
     pub cart: CartView,
+    pub estimate: ShippingEstimateView,
+    pub discount: CartDiscountView,
     pub analytics: AnalyticsConfig,
     pub nonce: String,
 }
@@ -160,37 +405,96 @@ pub struct CartItemsTemplate {
     pub cart: CartView,
 }
 
-/// Cart count badge fragment template (for HTMX).
+/// Shipping estimate fragment template (for HTMX).
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/shipping_estimate.html")]
+pub struct ShippingEstimateTemplate {
+    pub estimate: ShippingEstimateView,
+}
+
+/// Discount code fragment template (for HTMX).
 #[derive(Template, WebTemplate)]
-#[template(path = "partials/cart_count.html")]
-pub struct CartCountTemplate {
-    pub count: u32,
+#[template(path = "partials/cart_discount.html")]
+pub struct CartDiscountTemplate {
+    pub discount: CartDiscountView,
+}
+
+/// Classes of the cart count badge in the header (`#cart-count`).
+const CART_COUNT_CLASS: &str = "absolute -top-0.5 -right-0.5 min-w-[18px] h-[18px] px-1 bg-primary text-primary-foreground text-[11px] font-semibold rounded-full items-center justify-center";
+
+/// Render the header cart count badge as an HTMX out-of-band swap.
+///
+/// Appended to cart mutation responses so the badge updates whatever the
+/// request's main swap target is (or with `hx-swap="none"`). The badge is
+/// hidden when the cart is empty.
+#[must_use]
+pub fn oob_cart_count(count: usize) -> String {
+    if count == 0 {
+        format!(
+            r#"<span id="cart-count" hx-swap-oob="true" class="{CART_COUNT_CLASS} hidden"></span>"#
+        )
+    } else {
+        format!(
+            r#"<span id="cart-count" hx-swap-oob="true" class="{CART_COUNT_CLASS} flex">{count}</span>"#
+        )
+    }
+}
+
+/// Render the cart items fragment followed by the out-of-band count badge.
+fn cart_items_with_count(cart: CartView) -> Response {
+    let count = usize::try_from(cart.item_count).unwrap_or(0);
+    match (CartItemsTemplate { cart }).render() {
+        Ok(items) => (
+            AppendHeaders([("HX-Trigger", "cart-updated")]),
+            Html(items + &oob_cart_count(count)),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to render cart items: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
 }
 
 /// Display cart page.
-#[instrument(skip(state, session, nonce))]
+#[instrument(skip(state, session, currency, nonce))]
 pub async fn show(
     State(state): State<AppState>,
     session: Session,
+    currency: BuyerCurrency,
     crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
 ) -> impl IntoResponse {
-    // Get cart ID from session
-    let cart = match get_cart_id(&session).await {
-        Some(cart_id) => {
-            // Fetch cart from Shopify
-            match state.storefront().get_cart(&cart_id).await {
-                Ok(shopify_cart) => CartView::from(&shopify_cart),
-                Err(e) => {
-                    tracing::warn!("Failed to fetch cart {cart_id}: {e}");
-                    CartView::empty()
-                }
-            }
+    let carts = state.carts().with_country(currency.country());
+    let shopify_cart = session_cart(&carts, &session).await.unwrap_or_else(|e| {
+        tracing::warn!("Failed to fetch cart: {e}");
+        None
+    });
+
+    let (cart, estimate, discount) = match shopify_cart {
+        Some(shopify_cart) => {
+            let estimate = get_shipping_estimate(&session).await;
+            (
+                CartView::localized(&shopify_cart, &currency.formatter),
+                ShippingEstimateView::new(
+                    &shopify_cart.cost.subtotal,
+                    estimate.as_ref(),
+                    None,
+                    &currency.formatter,
+                ),
+                CartDiscountView::new(&shopify_cart, None, None, &currency.formatter),
+            )
         }
-        None => CartView::empty(),
+        None => (
+            CartView::empty(),
+            ShippingEstimateView::default(),
+            CartDiscountView::default(),
+        ),
     };
 
     CartShowTemplate {
         cart,
+        estimate,
+        discount,
         analytics: state.config().analytics.clone(),
         nonce,
     }
@@ -199,11 +503,12 @@ pub async fn show(
 /// Add item to cart (HTMX).
 ///
 /// Creates a new cart if one doesn't exist, or adds to existing cart.
-/// Returns an HTMX trigger to update the cart count badge.
-#[instrument(skip(state, session))]
+/// Returns the cart count badge as an out-of-band swap.
+#[instrument(skip(state, session, currency))]
 pub async fn add(
     State(state): State<AppState>,
     session: Session,
+    currency: BuyerCurrency,
     Form(form): Form<AddToCartForm>,
 ) -> Response {
     let quantity = i64::from(form.quantity.unwrap_or(1));
@@ -214,30 +519,99 @@ pub async fn add(
         selling_plan_id: None,
     };
 
-    let result = match get_cart_id(&session).await {
-        Some(cart_id) => {
-            // Add to existing cart
-            state.storefront().add_to_cart(&cart_id, vec![line]).await
+    let carts = state.carts().with_country(currency.country());
+    add_lines(&carts, &session, vec![line]).await
+}
+
+/// Add every component of a bundle to the cart (HTMX).
+///
+/// Form fields are the bundle `handle` plus the buyer's `component_{index}`
+/// variant selections; components without a selection fall back to the
+/// bundle's configured variant.
+#[instrument(skip(state, session, currency, form))]
+pub async fn add_bundle(
+    State(state): State<AppState>,
+    session: Session,
+    currency: BuyerCurrency,
+    Form(form): Form<Vec<(String, String)>>,
+) -> Response {
+    let Some(handle) = form
+        .iter()
+        .find_map(|(key, value)| (key == "handle").then_some(value))
+    else {
+        return (StatusCode::BAD_REQUEST, "Missing bundle handle").into_response();
+    };
+
+    let mut bundle = match state.storefront().get_product_bundle(handle).await {
+        Ok(Some(bundle)) => bundle,
+        Ok(None) => {
+            return (StatusCode::BAD_REQUEST, "Product is not a bundle").into_response();
         }
-        None => {
-            // Create new cart with this item
-            state.storefront().create_cart(Some(vec![line]), None).await
+        Err(e) => {
+            tracing::error!("Failed to fetch bundle {handle}: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("<span class=\"text-red-500\">Error adding to cart</span>"),
+            )
+                .into_response();
+        }
+    };
+
+    apply_bundle_selections(&mut bundle, &form);
+
+    let lines: Option<Vec<CartLineInput>> = bundle
+        .components
+        .iter()
+        .map(|component| {
+            component
+                .selected_variant
+                .as_ref()
+                .map(|variant| CartLineInput {
+                    merchandise_id: variant.id.clone(),
+                    quantity: i64::from(component.quantity),
+                    attributes: None,
+                    selling_plan_id: None,
+                })
+        })
+        .collect();
+
+    let Some(lines) = lines else {
+        return (
+            StatusCode::BAD_REQUEST,
+            "Select an option for every bundle item",
+        )
+            .into_response();
+    };
+
+    let carts = state.carts().with_country(currency.country());
+    add_lines(&carts, &session, lines).await
+}
+
+/// Add lines to the session's cart, creating the cart if needed.
+///
+/// Returns the cart count badge as an out-of-band swap, so the caller's own
+/// swap target (e.g. the add-to-cart button) is left alone.
+async fn add_lines(carts: &CartService, session: &Session, lines: Vec<CartLineInput>) -> Response {
+    let result = match cart_key_for_add(session).await {
+        Ok(key) => carts.add(&key, lines).await,
+        Err(e) => {
+            tracing::error!("Failed to save session for cart: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("<span class=\"text-red-500\">Error adding to cart</span>"),
+            )
+                .into_response();
         }
     };
 
     match result {
         Ok(cart) => {
-            // Save cart ID to session
-            if let Err(e) = set_cart_id(&session, &cart.id).await {
-                tracing::error!("Failed to save cart ID to session: {e}");
-            }
-
-            let count = u32::try_from(cart.total_quantity).unwrap_or(0);
+            clear_shipping_estimate(session).await;
+            let count = usize::try_from(cart.total_quantity).unwrap_or(0);
 
-            // Return cart count with HTMX trigger to update other elements
             (
                 AppendHeaders([("HX-Trigger", "cart-updated")]),
-                CartCountTemplate { count },
+                Html(oob_cart_count(count)),
             )
                 .into_response()
         }
@@ -253,40 +627,34 @@ pub async fn add(
 }
 
 /// Update cart item quantity (HTMX).
-#[instrument(skip(state, session))]
+#[instrument(skip(state, session, currency))]
 pub async fn update(
     State(state): State<AppState>,
     session: Session,
+    currency: BuyerCurrency,
     Form(form): Form<UpdateCartForm>,
 ) -> Response {
-    let Some(cart_id) = get_cart_id(&session).await else {
+    let Some(key) = cart_key(&session) else {
         return CartItemsTemplate {
             cart: CartView::empty(),
         }
         .into_response();
     };
 
-    let line_update = CartLineUpdateInput {
-        id: form.line_id,
-        quantity: Some(i64::from(form.quantity)),
-        merchandise_id: None,
-        attributes: None,
-        selling_plan_id: None,
-    };
-
     match state
-        .storefront()
-        .update_cart(&cart_id, vec![line_update])
+        .carts()
+        .with_country(currency.country())
+        .update(&key, &form.line_id, i64::from(form.quantity))
         .await
     {
-        Ok(shopify_cart) => {
-            let cart = CartView::from(&shopify_cart);
-            (
-                AppendHeaders([("HX-Trigger", "cart-updated")]),
-                CartItemsTemplate { cart },
-            )
-                .into_response()
+        Ok(Some(shopify_cart)) => {
+            clear_shipping_estimate(&session).await;
+            cart_items_with_count(CartView::localized(&shopify_cart, &currency.formatter))
         }
+        Ok(None) => CartItemsTemplate {
+            cart: CartView::empty(),
+        }
+        .into_response(),
         Err(e) => {
             tracing::error!("Failed to update cart: {e}");
             CartItemsTemplate {
@@ -298,13 +666,14 @@ pub async fn update(
 }
 
 /// Remove item from cart (HTMX).
-#[instrument(skip(state, session))]
+#[instrument(skip(state, session, currency))]
 pub async fn remove(
     State(state): State<AppState>,
     session: Session,
+    currency: BuyerCurrency,
     Form(form): Form<RemoveFromCartForm>,
 ) -> Response {
-    let Some(cart_id) = get_cart_id(&session).await else {
+    let Some(key) = cart_key(&session) else {
         return CartItemsTemplate {
             cart: CartView::empty(),
         }
@@ -312,18 +681,19 @@ pub async fn remove(
     };
 
     match state
-        .storefront()
-        .remove_from_cart(&cart_id, vec![form.line_id])
+        .carts()
+        .with_country(currency.country())
+        .remove(&key, &form.line_id)
         .await
     {
-        Ok(shopify_cart) => {
-            let cart = CartView::from(&shopify_cart);
-            (
-                AppendHeaders([("HX-Trigger", "cart-updated")]),
-                CartItemsTemplate { cart },
-            )
-                .into_response()
+        Ok(Some(shopify_cart)) => {
+            clear_shipping_estimate(&session).await;
+            cart_items_with_count(CartView::localized(&shopify_cart, &currency.formatter))
         }
+        Ok(None) => CartItemsTemplate {
+            cart: CartView::empty(),
+        }
+        .into_response(),
         Err(e) => {
             tracing::error!("Failed to remove from cart: {e}");
             CartItemsTemplate {
@@ -335,34 +705,315 @@ pub async fn remove(
 }
 
 /// Get cart count badge (HTMX).
+///
+/// Returned as an out-of-band swap, like the badge on cart mutations.
 #[instrument(skip(state, session))]
 pub async fn count(State(state): State<AppState>, session: Session) -> impl IntoResponse {
-    let count = match get_cart_id(&session).await {
-        Some(cart_id) => state
-            .storefront()
-            .get_cart(&cart_id)
-            .await
-            .map(|cart| u32::try_from(cart.total_quantity).unwrap_or(0))
-            .unwrap_or(0),
-        None => 0,
+    let count = match session_cart(state.carts(), &session).await {
+        Ok(Some(cart)) => usize::try_from(cart.total_quantity).unwrap_or(0),
+        Ok(None) => 0,
+        Err(e) => {
+            tracing::warn!("Failed to fetch cart for count: {e}");
+            0
+        }
     };
 
-    CartCountTemplate { count }
+    Html(oob_cart_count(count))
+}
+
+/// Estimate shipping rates for a partial address (HTMX).
+///
+/// Stores the rates in the session and preselects the first (cheapest)
+/// option. Returns the shipping estimate fragment for the cart summary.
+#[instrument(skip(state, session, currency))]
+pub async fn estimate_shipping(
+    State(state): State<AppState>,
+    session: Session,
+    currency: BuyerCurrency,
+    Form(form): Form<EstimateShippingForm>,
+) -> Response {
+    let cart = match session_cart(state.carts(), &session).await {
+        Ok(Some(cart)) => cart,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch cart for shipping estimate: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let country_code = form.country_code.trim().to_uppercase();
+    let province_code = form
+        .province_code
+        .map(|p| p.trim().to_uppercase())
+        .filter(|p| !p.is_empty());
+    let zip = form
+        .zip
+        .map(|z| z.trim().to_string())
+        .filter(|z| !z.is_empty());
+
+    let rates = state
+        .storefront()
+        .estimate_shipping(
+            &cart.id,
+            &country_code,
+            province_code.as_deref(),
+            zip.as_deref(),
+        )
+        .await;
+
+    let (estimate, error) = match rates {
+        Ok(rates) if rates.is_empty() => (
+            None,
+            Some("No shipping options are available for this address.".to_string()),
+        ),
+        Ok(rates) => {
+            let estimate = ShippingEstimate {
+                country_code,
+                province_code,
+                zip,
+                selected_handle: rates.first().map(|rate| rate.handle.clone()),
+                rates,
+            };
+            if let Err(e) = set_shipping_estimate(&session, &estimate).await {
+                tracing::error!("Failed to save shipping estimate to session: {e}");
+            }
+            (Some(estimate), None)
+        }
+        Err(e) => {
+            tracing::warn!("Failed to estimate shipping for cart {}: {e}", cart.id);
+            (
+                None,
+                Some("We couldn't estimate shipping for that address.".to_string()),
+            )
+        }
+    };
+
+    ShippingEstimateTemplate {
+        estimate: ShippingEstimateView::new(
+            &cart.cost.subtotal,
+            estimate.as_ref(),
+            error,
+            &currency.formatter,
+        ),
+    }
+    .into_response()
+}
+
+/// Select one of the estimated shipping rates (HTMX).
+///
+/// Stores the selection in the session and returns the updated shipping
+/// estimate fragment with the new estimated total.
+#[instrument(skip(state, session, currency))]
+pub async fn select_shipping_rate(
+    State(state): State<AppState>,
+    session: Session,
+    currency: BuyerCurrency,
+    Form(form): Form<SelectShippingRateForm>,
+) -> Response {
+    let cart = match session_cart(state.carts(), &session).await {
+        Ok(Some(cart)) => cart,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch cart for shipping selection: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let mut estimate = get_shipping_estimate(&session).await;
+    if let Some(estimate) = estimate.as_mut()
+        && estimate.rates.iter().any(|rate| rate.handle == form.handle)
+    {
+        estimate.selected_handle = Some(form.handle);
+        if let Err(e) = set_shipping_estimate(&session, estimate).await {
+            tracing::error!("Failed to save shipping selection to session: {e}");
+        }
+    }
+
+    ShippingEstimateTemplate {
+        estimate: ShippingEstimateView::new(
+            &cart.cost.subtotal,
+            estimate.as_ref(),
+            None,
+            &currency.formatter,
+        ),
+    }
+    .into_response()
+}
+
+/// Apply a discount code to the cart (HTMX).
+///
+/// Keeps any codes already applied. Codes Shopify doesn't recognize or that
+/// have expired are removed again and reported inline. Returns the discount
+/// fragment for the cart summary.
+#[instrument(skip(state, session, currency, form))]
+pub async fn apply_discount(
+    State(state): State<AppState>,
+    session: Session,
+    currency: BuyerCurrency,
+    Form(form): Form<ApplyDiscountForm>,
+) -> Response {
+    let cart = match session_cart(state.carts(), &session).await {
+        Ok(Some(cart)) => cart,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch cart for discount: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let code = form.code.trim().to_string();
+    if code.is_empty() {
+        return discount_error(
+            &cart,
+            "Please enter a discount code.".to_string(),
+            &currency.formatter,
+        );
+    }
+
+    let mut codes: Vec<String> = cart
+        .discount_codes
+        .iter()
+        .filter(|c| c.applicable && !c.code.eq_ignore_ascii_case(&code))
+        .map(|c| c.code.clone())
+        .collect();
+    codes.push(code.clone());
+
+    let updated = match state
+        .storefront()
+        .apply_cart_discount(&cart.id, codes.clone())
+        .await
+    {
+        Ok(updated) => updated,
+        Err(ShopifyError::UserError(message)) => {
+            return discount_error(&cart, message, &currency.formatter);
+        }
+        Err(e) => {
+            tracing::error!("Failed to apply discount code: {e}");
+            return discount_error(
+                &cart,
+                "We couldn't apply that code. Please try again.".to_string(),
+                &currency.formatter,
+            );
+        }
+    };
+
+    let applicable = updated
+        .discount_codes
+        .iter()
+        .any(|c| c.applicable && c.code.eq_ignore_ascii_case(&code));
+    if applicable {
+        return (
+            AppendHeaders([("HX-Trigger", "cart-updated")]),
+            CartDiscountTemplate {
+                discount: CartDiscountView::new(&updated, Some(code), None, &currency.formatter),
+            },
+        )
+            .into_response();
+    }
+
+    // Unknown or expired code: take it back off the cart
+    codes.pop();
+    let cart = match state
+        .storefront()
+        .apply_cart_discount(&cart.id, codes)
+        .await
+    {
+        Ok(cart) => cart,
+        Err(e) => {
+            tracing::warn!("Failed to remove inapplicable discount code: {e}");
+            updated
+        }
+    };
+
+    discount_error(
+        &cart,
+        format!("\"{code}\" isn't a valid code or has expired."),
+        &currency.formatter,
+    )
+}
+
+/// Remove a discount code from the cart (HTMX).
+///
+/// Returns the discount fragment for the cart summary.
+#[instrument(skip(state, session, currency))]
+pub async fn remove_discount(
+    State(state): State<AppState>,
+    session: Session,
+    currency: BuyerCurrency,
+    Path(code): Path<String>,
+) -> Response {
+    let cart = match session_cart(state.carts(), &session).await {
+        Ok(Some(cart)) => cart,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch cart for discount removal: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let codes = cart
+        .discount_codes
+        .iter()
+        .filter(|c| !c.code.eq_ignore_ascii_case(&code))
+        .map(|c| c.code.clone())
+        .collect();
+
+    match state
+        .storefront()
+        .apply_cart_discount(&cart.id, codes)
+        .await
+    {
+        Ok(updated) => (
+            AppendHeaders([("HX-Trigger", "cart-updated")]),
+            CartDiscountTemplate {
+                discount: CartDiscountView::new(&updated, None, None, &currency.formatter),
+            },
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to remove discount code: {e}");
+            discount_error(
+                &cart,
+                "We couldn't remove that code. Please try again.".to_string(),
+                &currency.formatter,
+            )
+        }
+    }
+}
+
+/// Render the discount fragment for a cart with an inline error.
+fn discount_error(cart: &ShopifyCart, message: String, formatter: &PriceFormatter) -> Response {
+    CartDiscountTemplate {
+        discount: CartDiscountView::new(cart, None, Some(message), formatter),
+    }
+    .into_response()
 }
 
 /// Redirect to Shopify checkout.
 #[instrument(skip(state, session))]
 pub async fn checkout(State(state): State<AppState>, session: Session) -> Response {
-    let Some(cart_id) = get_cart_id(&session).await else {
+    match session_cart(state.carts(), &session).await {
+        Ok(Some(cart)) => Redirect::to(&cart.checkout_url).into_response(),
         // No cart, redirect to cart page
-        return Redirect::to("/cart").into_response();
-    };
-
-    match state.storefront().get_cart(&cart_id).await {
-        Ok(cart) => Redirect::to(&cart.checkout_url).into_response(),
+        Ok(None) => Redirect::to("/cart").into_response(),
         Err(e) => {
             tracing::error!("Failed to get cart for checkout: {e}");
             Redirect::to("/cart").into_response()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oob_cart_count() {
+        let badge = oob_cart_count(3);
+        assert!(badge.starts_with(r#"<span id="cart-count" hx-swap-oob="true""#));
+        assert!(badge.contains(" flex\">3</span>"));
+
+        let empty = oob_cart_count(0);
+        assert!(empty.contains(" hidden\"></span>"));
+    }
+}
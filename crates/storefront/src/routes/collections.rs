@@ -1,18 +1,23 @@
 //! Collection route handlers.
 
+use std::fmt::Write as _;
+
 use askama::Template;
 use askama_web::WebTemplate;
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::{IntoResponse, Response},
+    extract::{FromRequestParts, Path, Query, State},
+    http::{StatusCode, request::Parts},
+    response::{AppendHeaders, IntoResponse, Response},
 };
 use serde::Deserialize;
+use tower_sessions::Session;
 use tracing::instrument;
 
 use crate::config::AnalyticsConfig;
 use crate::filters;
-use crate::shopify::types::Collection as ShopifyCollection;
+use crate::middleware::BuyerCurrency;
+use crate::models::session_keys;
+use crate::shopify::types::{Collection as ShopifyCollection, TagCount};
 use crate::shopify::{PriceRangeFilter, ProductCollectionSortKeys, ProductFilter, ShopifyError};
 use crate::state::AppState;
 
@@ -28,10 +33,28 @@ pub struct CollectionView {
     pub image: Option<ImageView>,
 }
 
+/// A tag in the collection's tag filter.
+#[derive(Clone)]
+pub struct TagFilterView {
+    pub tag: String,
+    /// Number of products with the tag (given the other active filters).
+    pub count: i64,
+    pub selected: bool,
+}
+
 /// Pagination and filter query parameters.
+///
+/// Selected tags (`?tags[]=womens&tags[]=sale`) can repeat, so they're read
+/// separately from the raw query pairs.
 #[derive(Debug, Deserialize)]
 pub struct PaginationQuery {
-    pub page: Option<u32>,
+    /// Cursor of the last product already shown (infinite scroll).
+    pub cursor: Option<String>,
+    /// Number of pages of products loaded by infinite scroll.
+    ///
+    /// A full page load without a cursor shows all of them, so refreshing
+    /// the page keeps the products already scrolled past.
+    pub pages: Option<i64>,
     pub sort: Option<String>,
     /// Filter to show only in-stock products.
     pub available: Option<bool>,
@@ -41,6 +64,60 @@ pub struct PaginationQuery {
     pub price_max: Option<f64>,
 }
 
+/// Query parameters of a collection page request, and whether it came from
+/// HTMX.
+#[derive(Debug)]
+pub struct CollectionRequest {
+    pub query: PaginationQuery,
+    /// Selected tags, from repeated `tags[]` parameters.
+    pub tags: Vec<String>,
+    pub is_htmx: bool,
+}
+
+impl<S> FromRequestParts<S> for CollectionRequest
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<PaginationQuery>::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        let Query(params) = Query::<Vec<(String, String)>>::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        Ok(Self {
+            query,
+            tags: selected_tags(&params),
+            is_htmx: parts.headers.contains_key("HX-Request"),
+        })
+    }
+}
+
+impl CollectionRequest {
+    /// Whether this is an infinite scroll request for the next batch only.
+    const fn is_next_page(&self) -> bool {
+        self.query.cursor.is_some() && self.is_htmx
+    }
+
+    /// Number of pages of products loaded once this request is shown.
+    fn pages(&self) -> i64 {
+        self.query.pages.unwrap_or(1).clamp(1, MAX_RELOADED_PAGES)
+    }
+
+    /// Number of products to fetch: one page after a cursor, otherwise
+    /// every page loaded so far.
+    fn first(&self) -> i64 {
+        if self.query.cursor.is_none() {
+            PRODUCTS_PER_PAGE * self.pages()
+        } else {
+            PRODUCTS_PER_PAGE
+        }
+    }
+}
+
 // =============================================================================
 // Type Conversions
 // =============================================================================
@@ -85,9 +162,8 @@ pub struct CollectionsIndexTemplate {
 pub struct CollectionShowTemplate {
     pub collection: CollectionView,
     pub products: Vec<ProductView>,
-    pub current_page: u32,
-    pub total_pages: u32,
-    pub has_more_pages: bool,
+    /// URL of the next batch of products, if there is one.
+    pub next_page_url: Option<String>,
     pub analytics: AnalyticsConfig,
     pub nonce: String,
     /// Base URL for canonical links and structured data.
@@ -104,10 +180,29 @@ pub struct CollectionShowTemplate {
     pub filter_price_max: Option<f64>,
     /// Whether a price filter is actively applied (not at default 0-200 range).
     pub has_price_filter: bool,
+    /// Tags to filter by, with product counts.
+    pub tag_filters: Vec<TagFilterView>,
+    /// Tags currently filtered by.
+    pub selected_tags: Vec<String>,
+    /// Selected tags as URL-encoded `&tags[]=` parameters, for links.
+    pub tag_query: String,
+}
+
+/// Next batch of collection products (HTMX infinite scroll fragment).
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/collection_products_page.html")]
+pub struct CollectionProductsPageTemplate {
+    pub products: Vec<ProductView>,
+    /// URL of the next batch of products, if there is one.
+    pub next_page_url: Option<String>,
 }
 
 /// Products per page for collection view.
-const PRODUCTS_PER_PAGE: usize = 12;
+const PRODUCTS_PER_PAGE: i64 = 12;
+
+/// Most pages loaded at once when a scrolled page is reloaded (the Storefront
+/// API returns at most 250 products per request).
+const MAX_RELOADED_PAGES: i64 = 20;
 
 /// Display collection listing page.
 #[instrument(skip(state, nonce))]
@@ -150,21 +245,131 @@ pub async fn index(
     }
 }
 
-/// Parse sort query parameter into Shopify sort key and reverse flag.
-fn parse_sort(sort: Option<&str>) -> (Option<ProductCollectionSortKeys>, Option<bool>) {
-    match sort {
-        Some("price-asc") => (Some(ProductCollectionSortKeys::PRICE), Some(false)),
-        Some("price-desc") => (Some(ProductCollectionSortKeys::PRICE), Some(true)),
-        Some("newest") => (Some(ProductCollectionSortKeys::CREATED), Some(true)),
-        Some("title-asc") => (Some(ProductCollectionSortKeys::TITLE), Some(false)),
-        Some("title-desc") => (Some(ProductCollectionSortKeys::TITLE), Some(true)),
-        // "best-selling" or default
-        _ => (Some(ProductCollectionSortKeys::BEST_SELLING), None),
+/// Collection product sort order.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionSort {
+    #[default]
+    BestSelling,
+    PriceAsc,
+    PriceDesc,
+    Newest,
+    TitleAsc,
+    TitleDesc,
+}
+
+impl CollectionSort {
+    /// Parse from URL parameter value.
+    ///
+    /// Also accepts the hyphenated values (`price-asc`) of older links.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "best_selling" | "best-selling" => Some(Self::BestSelling),
+            "price_asc" | "price-asc" => Some(Self::PriceAsc),
+            "price_desc" | "price-desc" => Some(Self::PriceDesc),
+            "newest" => Some(Self::Newest),
+            "title_asc" | "title-asc" => Some(Self::TitleAsc),
+            "title_desc" | "title-desc" => Some(Self::TitleDesc),
+            _ => None,
+        }
+    }
+
+    /// Convert to URL parameter value.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::BestSelling => "best_selling",
+            Self::PriceAsc => "price_asc",
+            Self::PriceDesc => "price_desc",
+            Self::Newest => "newest",
+            Self::TitleAsc => "title_asc",
+            Self::TitleDesc => "title_desc",
+        }
+    }
+
+    /// Shopify sort key and reverse flag.
+    #[must_use]
+    pub const fn sort_key(self) -> (ProductCollectionSortKeys, Option<bool>) {
+        match self {
+            Self::BestSelling => (ProductCollectionSortKeys::BEST_SELLING, None),
+            Self::PriceAsc => (ProductCollectionSortKeys::PRICE, Some(false)),
+            Self::PriceDesc => (ProductCollectionSortKeys::PRICE, Some(true)),
+            Self::Newest => (ProductCollectionSortKeys::CREATED, Some(true)),
+            Self::TitleAsc => (ProductCollectionSortKeys::TITLE, Some(false)),
+            Self::TitleDesc => (ProductCollectionSortKeys::TITLE, Some(true)),
+        }
     }
 }
 
+/// Resolve the sort order: the `?sort=` parameter if valid, else the
+/// buyer's saved preference, else best selling.
+///
+/// A sort chosen in the URL is saved so it applies to later collection pages.
+async fn resolve_sort(session: &Session, requested: Option<&str>) -> CollectionSort {
+    if let Some(sort) = requested.and_then(CollectionSort::parse) {
+        if let Err(e) = session
+            .insert(session_keys::COLLECTION_SORT, sort.as_str())
+            .await
+        {
+            tracing::warn!("Failed to save collection sort to session: {e}");
+        }
+        return sort;
+    }
+
+    session
+        .get::<String>(session_keys::COLLECTION_SORT)
+        .await
+        .ok()
+        .flatten()
+        .as_deref()
+        .and_then(CollectionSort::parse)
+        .unwrap_or_default()
+}
+
+/// Read the selected tags from repeated `tags[]` (or `tags`) parameters.
+fn selected_tags(params: &[(String, String)]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for (key, value) in params {
+        let tag = value.trim();
+        if (key == "tags[]" || key == "tags") && !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Encode tags as `&tags[]=` query parameters.
+fn tag_query(tags: &[String]) -> String {
+    tags.iter().fold(String::new(), |mut query, tag| {
+        let _ = write!(query, "&tags%5B%5D={}", urlencoding::encode(tag));
+        query
+    })
+}
+
+/// Build the tag filter list, keeping selected tags Shopify didn't return.
+fn tag_filter_views(available: &[TagCount], selected: &[String]) -> Vec<TagFilterView> {
+    let mut views: Vec<TagFilterView> = available
+        .iter()
+        .map(|t| TagFilterView {
+            tag: t.tag.clone(),
+            count: t.count,
+            selected: selected.contains(&t.tag),
+        })
+        .collect();
+    for tag in selected {
+        if !views.iter().any(|v| &v.tag == tag) {
+            views.push(TagFilterView {
+                tag: tag.clone(),
+                count: 0,
+                selected: true,
+            });
+        }
+    }
+    views
+}
+
 /// Build Shopify product filters from query parameters.
-fn build_filters(query: &PaginationQuery) -> Option<Vec<ProductFilter>> {
+fn build_filters(query: &PaginationQuery, tags: &[String]) -> Option<Vec<ProductFilter>> {
     let mut filters = Vec::new();
 
     // In-stock filter
@@ -226,6 +431,22 @@ fn build_filters(query: &PaginationQuery) -> Option<Vec<ProductFilter>> {
         });
     }
 
+    // Tag filters - one per tag, which Shopify combines with AND
+    for tag in tags {
+        filters.push(ProductFilter {
+            available: None,
+            category: None,
+            price: None,
+            product_metafield: None,
+            product_type: None,
+            product_vendor: None,
+            tag: Some(tag.clone()),
+            taxonomy_metafield: None,
+            variant_metafield: None,
+            variant_option: None,
+        });
+    }
+
     let result = if filters.is_empty() {
         None
     } else {
@@ -240,6 +461,55 @@ fn build_filters(query: &PaginationQuery) -> Option<Vec<ProductFilter>> {
     result
 }
 
+/// Build the URL for the next batch of products, preserving sort and filters.
+///
+/// `pages` is the number of pages loaded once the batch is shown.
+fn next_page_url(
+    handle: &str,
+    cursor: &str,
+    pages: i64,
+    sort: CollectionSort,
+    query: &PaginationQuery,
+    tags: &[String],
+) -> String {
+    format!(
+        "/collections/{handle}?cursor={}&pages={pages}&{}",
+        urlencoding::encode(cursor),
+        filter_query(sort, query, tags)
+    )
+}
+
+/// Build the URL that reloads the first `pages` pages of products, for the
+/// address bar while infinite scrolling.
+fn loaded_pages_url(
+    handle: &str,
+    pages: i64,
+    sort: CollectionSort,
+    query: &PaginationQuery,
+    tags: &[String],
+) -> String {
+    format!(
+        "/collections/{handle}?pages={pages}&{}",
+        filter_query(sort, query, tags)
+    )
+}
+
+/// Sort and filter query parameters, without a leading `?` or `&`.
+fn filter_query(sort: CollectionSort, query: &PaginationQuery, tags: &[String]) -> String {
+    let mut params = format!("sort={}", sort.as_str());
+    if query.available == Some(true) {
+        params.push_str("&available=true");
+    }
+    if let Some(min) = query.price_min {
+        let _ = write!(params, "&price_min={min}");
+    }
+    if let Some(max) = query.price_max {
+        let _ = write!(params, "&price_max={max}");
+    }
+    params.push_str(&tag_query(tags));
+    params
+}
+
 /// Parameters for building an error collection template.
 struct ErrorParams {
     status: StatusCode,
@@ -286,9 +556,7 @@ fn error_template(params: ErrorParams, state: &AppState, nonce: String) -> Respo
                 image: None,
             },
             products: Vec::new(),
-            current_page: 1,
-            total_pages: 1,
-            has_more_pages: false,
+            next_page_url: None,
             analytics: state.config().analytics.clone(),
             nonce,
             base_url: state.config().base_url.clone(),
@@ -298,62 +566,17 @@ fn error_template(params: ErrorParams, state: &AppState, nonce: String) -> Respo
             filter_price_min: params.filter_price_min,
             filter_price_max: params.filter_price_max,
             has_price_filter,
+            tag_filters: Vec::new(),
+            selected_tags: Vec::new(),
+            tag_query: String::new(),
         },
     )
         .into_response()
 }
 
-/// Display collection detail page with products.
-#[instrument(skip(state, nonce))]
-pub async fn show(
-    State(state): State<AppState>,
-    Path(handle): Path<String>,
-    Query(query): Query<PaginationQuery>,
-    crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
-) -> Response {
-    // Debug: Log incoming query parameters
-    tracing::debug!(?query, "Collection show request");
-
-    let current_page = query.page.unwrap_or(1);
-    let current_sort = query
-        .sort
-        .clone()
-        .unwrap_or_else(|| "best-selling".to_string());
-    let (sort_key, reverse) = parse_sort(query.sort.as_deref());
-
-    // Build filters from query params
-    let filter_available = query.available.unwrap_or(false);
-    let filter_price_min = query.price_min;
-    let filter_price_max = query.price_max;
-    let filters = build_filters(&query);
-
-    // Debug: Log the filters being sent to Shopify
-    tracing::debug!(
-        filter_available,
-        ?filter_price_min,
-        ?filter_price_max,
-        has_filters = filters.is_some(),
-        filter_count = filters.as_ref().map_or(0, |f| f.len()),
-        "Built filters for Shopify"
-    );
-
-    // Fetch collection and products from Shopify Storefront API
-    #[allow(clippy::cast_possible_wrap)]
-    let products_per_page = PRODUCTS_PER_PAGE as i64;
-    let result = state
-        .storefront()
-        .get_collection_by_handle(
-            &handle,
-            Some(products_per_page),
-            None,
-            sort_key,
-            reverse,
-            filters,
-        )
-        .await;
-
-    // Debug: Log the result including product prices
-    match &result {
+/// Log the products (and prices) Shopify returned for a collection.
+fn log_collection_result(result: &Result<ShopifyCollection, ShopifyError>) {
+    match result {
         Ok(collection) => {
             let prices: Vec<String> = collection
                 .products
@@ -375,6 +598,54 @@ pub async fn show(
             );
         }
     }
+}
+
+/// Display collection detail page with products.
+///
+/// With `?cursor=` on an HTMX request, returns only the next batch of product
+/// cards for infinite scroll, and replaces the address bar URL with one that
+/// reloads every page loaded so far (`?pages=3`). Without HTMX, renders the
+/// full page starting from the cursor.
+///
+/// Tag checkboxes and the sort dropdown reload only the product grid
+/// (`#collection-products`). The sort is remembered in the session, so
+/// links without `?sort=` keep the buyer's last choice.
+#[instrument(skip(state, session, currency, nonce))]
+pub async fn show(
+    State(state): State<AppState>,
+    Path(handle): Path<String>,
+    request: CollectionRequest,
+    session: Session,
+    currency: BuyerCurrency,
+    crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
+) -> Response {
+    let is_next_page = request.is_next_page();
+    let pages = request.pages();
+    let first = request.first();
+    let CollectionRequest { query, tags, .. } = request;
+    let sort = resolve_sort(&session, query.sort.as_deref()).await;
+    let current_sort = sort.as_str().to_string();
+    let (sort_key, reverse) = sort.sort_key();
+
+    let filter_available = query.available.unwrap_or(false);
+    let filter_price_min = query.price_min;
+    let filter_price_max = query.price_max;
+    let filters = build_filters(&query, &tags);
+
+    // Fetch collection and products from Shopify Storefront API
+    let result = currency
+        .storefront(state.storefront())
+        .get_collection_by_handle(
+            &handle,
+            Some(first),
+            query.cursor.clone(),
+            Some(sort_key),
+            reverse,
+            filters,
+        )
+        .await;
+
+    log_collection_result(&result);
 
     let err_params = |status, title, desc| ErrorParams {
         status,
@@ -387,15 +658,38 @@ pub async fn show(
         filter_price_max,
     };
 
+    // A failed infinite scroll request just stops loading more products
+    if is_next_page && let Err(e) = &result {
+        tracing::error!("Failed to fetch next collection page for {handle}: {e}");
+        return ().into_response();
+    }
+
     match result {
         Ok(shopify_collection) => {
             let collection = CollectionView::from(&shopify_collection);
             let products: Vec<ProductView> = shopify_collection
                 .products
                 .iter()
-                .map(ProductView::from)
+                .map(|p| ProductView::localized(p, &currency.formatter))
                 .collect();
-            let has_more = products.len() >= PRODUCTS_PER_PAGE;
+            let next_page_url = shopify_collection
+                .products_page_info
+                .as_ref()
+                .filter(|info| info.has_next_page)
+                .and_then(|info| info.end_cursor.as_deref())
+                .map(|cursor| next_page_url(&handle, cursor, pages + 1, sort, &query, &tags));
+
+            if is_next_page {
+                let loaded_url = loaded_pages_url(&handle, pages, sort, &query, &tags);
+                return (
+                    AppendHeaders([("HX-Replace-Url", loaded_url)]),
+                    CollectionProductsPageTemplate {
+                        products,
+                        next_page_url,
+                    },
+                )
+                    .into_response();
+            }
 
             // Determine if we have an active price filter (not at default 0-200 range)
             let has_price_filter = filter_price_min.is_some_and(|v| v > 0.0)
@@ -405,13 +699,7 @@ pub async fn show(
                 breadcrumbs: build_breadcrumbs(&collection.title),
                 collection,
                 products,
-                current_page,
-                total_pages: if has_more {
-                    current_page + 1
-                } else {
-                    current_page
-                },
-                has_more_pages: has_more,
+                next_page_url,
                 analytics: state.config().analytics.clone(),
                 nonce,
                 base_url: state.config().base_url.clone(),
@@ -420,6 +708,9 @@ pub async fn show(
                 filter_price_min,
                 filter_price_max,
                 has_price_filter,
+                tag_filters: tag_filter_views(&shopify_collection.product_tags, &tags),
+                tag_query: tag_query(&tags),
+                selected_tags: tags,
             }
             .into_response()
         }
@@ -439,3 +730,95 @@ pub async fn show(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(raw: &[(&str, &str)]) -> Vec<(String, String)> {
+        raw.iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_collection_sort_parse() {
+        assert_eq!(
+            CollectionSort::parse("price_desc"),
+            Some(CollectionSort::PriceDesc)
+        );
+        assert_eq!(
+            CollectionSort::parse("title-asc"),
+            Some(CollectionSort::TitleAsc)
+        );
+        assert_eq!(CollectionSort::parse("random"), None);
+        assert_eq!(
+            CollectionSort::parse(CollectionSort::Newest.as_str()),
+            Some(CollectionSort::Newest)
+        );
+    }
+
+    #[test]
+    fn test_collection_sort_price_reverses() {
+        let (key, reverse) = CollectionSort::PriceDesc.sort_key();
+        assert!(matches!(key, ProductCollectionSortKeys::PRICE));
+        assert_eq!(reverse, Some(true));
+        assert_eq!(CollectionSort::BestSelling.sort_key().1, None);
+    }
+
+    #[test]
+    fn test_selected_tags() {
+        let params = pairs(&[
+            ("tags[]", "womens"),
+            ("sort", "newest"),
+            ("tags[]", " sale "),
+            ("tags", "womens"),
+            ("tags[]", ""),
+        ]);
+        assert_eq!(selected_tags(&params), vec!["womens", "sale"]);
+    }
+
+    #[test]
+    fn test_infinite_scroll_urls_keep_filters() {
+        let query = PaginationQuery {
+            cursor: Some("abc".to_string()),
+            pages: Some(2),
+            sort: None,
+            available: Some(true),
+            price_min: Some(10.0),
+            price_max: None,
+        };
+        let tags = vec!["sale".to_string()];
+
+        assert_eq!(
+            next_page_url("soap", "a b=", 3, CollectionSort::Newest, &query, &tags),
+            "/collections/soap?cursor=a%20b%3D&pages=3&sort=newest&available=true\
+             &price_min=10&tags%5B%5D=sale"
+        );
+        // The address bar URL drops the cursor so a refresh reloads every page
+        assert_eq!(
+            loaded_pages_url("soap", 2, CollectionSort::Newest, &query, &tags),
+            "/collections/soap?pages=2&sort=newest&available=true&price_min=10&tags%5B%5D=sale"
+        );
+    }
+
+    #[test]
+    fn test_tag_query_is_url_encoded() {
+        let tags = vec!["gift sets".to_string(), "a&b".to_string()];
+        assert_eq!(tag_query(&tags), "&tags%5B%5D=gift%20sets&tags%5B%5D=a%26b");
+    }
+
+    #[test]
+    fn test_tag_filter_views_keep_selected_tags() {
+        let available = vec![TagCount {
+            tag: "sale".to_string(),
+            count: 4,
+        }];
+        let selected = vec!["sale".to_string(), "womens".to_string()];
+
+        let views = tag_filter_views(&available, &selected);
+        assert_eq!(views.len(), 2);
+        assert!(views.iter().all(|v| v.selected));
+        assert_eq!(views.last().map(|v| v.count), Some(0));
+    }
+}
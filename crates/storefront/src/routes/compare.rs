@@ -0,0 +1,289 @@
+//! Product comparison route handlers.
+//!
+//! Buyers can compare up to four variants side by side. The compare list is
+//! stored in the session; adding and removing return the compare bar
+//! fragment, which is only shown once two or more products are selected.
+
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    Form,
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use tower_sessions::Session;
+use tracing::instrument;
+
+use crate::config::AnalyticsConfig;
+use crate::filters;
+use crate::middleware::BuyerCurrency;
+use crate::models::{CompareAddOutcome, CompareList, session_keys};
+use crate::services::PriceFormatter;
+use crate::shopify::types::ComparisonItem;
+use crate::state::AppState;
+
+use super::products::ImageView;
+
+/// Prefix of Shopify product variant GIDs.
+const VARIANT_GID_PREFIX: &str = "gid://shopify/ProductVariant/";
+
+// =============================================================================
+// View Types
+// =============================================================================
+
+/// A compared variant for templates.
+#[derive(Clone)]
+pub struct CompareItemView {
+    /// Full variant GID.
+    pub variant_id: String,
+    /// Numeric variant ID (used in remove URLs).
+    pub remove_id: String,
+    pub product_handle: String,
+    /// Product title, with the variant title unless it's the default variant.
+    pub title: String,
+    pub description: String,
+    pub image: Option<ImageView>,
+    pub price: String,
+    pub sku: Option<String>,
+    /// Formatted weight (e.g., "120 g").
+    pub weight: Option<String>,
+    pub dimensions: Option<String>,
+    pub promotes: Vec<String>,
+    pub free_from: Vec<String>,
+    pub available_for_sale: bool,
+}
+
+impl CompareItemView {
+    fn new(item: &ComparisonItem, formatter: &PriceFormatter) -> Self {
+        let title = if item.variant_title == "Default Title" {
+            item.product_title.clone()
+        } else {
+            format!("{} - {}", item.product_title, item.variant_title)
+        };
+
+        Self {
+            variant_id: item.variant_id.clone(),
+            remove_id: item
+                .variant_id
+                .trim_start_matches(VARIANT_GID_PREFIX)
+                .to_string(),
+            product_handle: item.product_handle.clone(),
+            title,
+            description: item.description.clone(),
+            image: item.image.as_ref().map(|img| ImageView {
+                url: img.url.clone(),
+                alt: img.alt_text.clone().unwrap_or_default(),
+            }),
+            price: formatter.format(&item.price),
+            sku: item.sku.clone(),
+            weight: item
+                .weight
+                .filter(|w| *w > 0.0)
+                .map(|w| format_weight(w, &item.weight_unit)),
+            dimensions: item.dimensions.clone(),
+            promotes: item.promotes.clone(),
+            free_from: item.free_from.clone(),
+            available_for_sale: item.available_for_sale,
+        }
+    }
+}
+
+/// Format a weight with its unit abbreviation.
+fn format_weight(weight: f64, unit: &str) -> String {
+    let unit = match unit {
+        "GRAMS" => "g",
+        "KILOGRAMS" => "kg",
+        "OUNCES" => "oz",
+        "POUNDS" => "lb",
+        other => other,
+    };
+    format!("{weight} {unit}")
+}
+
+/// Add to compare form data.
+#[derive(Debug, Deserialize)]
+pub struct CompareAddForm {
+    pub variant_id: String,
+}
+
+// =============================================================================
+// Templates
+// =============================================================================
+
+/// Product comparison page template.
+#[derive(Template, WebTemplate)]
+#[template(path = "compare/show.html")]
+pub struct CompareShowTemplate {
+    pub items: Vec<CompareItemView>,
+    pub analytics: AnalyticsConfig,
+    pub nonce: String,
+}
+
+/// Sticky compare bar fragment template (for HTMX).
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/compare_bar.html")]
+pub struct CompareBarTemplate {
+    pub items: Vec<CompareItemView>,
+    /// Message shown when an item couldn't be added.
+    pub warning: Option<String>,
+}
+
+// =============================================================================
+// Session Helpers
+// =============================================================================
+
+/// Get the compare list from the session.
+async fn get_compare_list(session: &Session) -> CompareList {
+    session
+        .get::<CompareList>(session_keys::COMPARE_LIST)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// Save the compare list in the session.
+async fn set_compare_list(
+    session: &Session,
+    list: &CompareList,
+) -> Result<(), tower_sessions::session::Error> {
+    session.insert(session_keys::COMPARE_LIST, list).await
+}
+
+/// Fetch display data for every variant in the compare list.
+async fn load_items(
+    state: &AppState,
+    currency: &BuyerCurrency,
+    list: &CompareList,
+) -> Vec<CompareItemView> {
+    match currency
+        .storefront(state.storefront())
+        .get_compare_variants(list.variant_ids())
+        .await
+    {
+        Ok(items) => items
+            .iter()
+            .map(|item| CompareItemView::new(item, &currency.formatter))
+            .collect(),
+        Err(e) => {
+            tracing::error!("Failed to fetch compare variants: {e}");
+            Vec::new()
+        }
+    }
+}
+
+// =============================================================================
+// Route Handlers
+// =============================================================================
+
+/// Display the product comparison table.
+#[instrument(skip(state, session, currency, nonce))]
+pub async fn show(
+    State(state): State<AppState>,
+    session: Session,
+    currency: BuyerCurrency,
+    crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
+) -> Response {
+    let list = get_compare_list(&session).await;
+    let items = load_items(&state, &currency, &list).await;
+
+    CompareShowTemplate {
+        items,
+        analytics: state.config().analytics.clone(),
+        nonce,
+    }
+    .into_response()
+}
+
+/// Display the compare bar fragment (HTMX).
+#[instrument(skip(state, session, currency))]
+pub async fn bar(
+    State(state): State<AppState>,
+    session: Session,
+    currency: BuyerCurrency,
+) -> Response {
+    let list = get_compare_list(&session).await;
+    let items = if list.len() >= 2 {
+        load_items(&state, &currency, &list).await
+    } else {
+        Vec::new()
+    };
+
+    CompareBarTemplate {
+        items,
+        warning: None,
+    }
+    .into_response()
+}
+
+/// Add a variant to the compare list (HTMX).
+///
+/// Returns the updated compare bar, with a warning if the list is full.
+#[instrument(skip(state, session, currency, form), fields(variant_id = %form.variant_id))]
+pub async fn add(
+    State(state): State<AppState>,
+    session: Session,
+    currency: BuyerCurrency,
+    Form(form): Form<CompareAddForm>,
+) -> Response {
+    let mut list = get_compare_list(&session).await;
+
+    let warning = if form.variant_id.starts_with(VARIANT_GID_PREFIX) {
+        match list.add(&form.variant_id) {
+            CompareAddOutcome::Added => {
+                if let Err(e) = set_compare_list(&session, &list).await {
+                    tracing::error!("Failed to save compare list to session: {e}");
+                }
+                None
+            }
+            CompareAddOutcome::AlreadyPresent => None,
+            CompareAddOutcome::Full => Some(format!(
+                "You can compare up to {} products. Remove one to add another.",
+                CompareList::MAX_ITEMS
+            )),
+        }
+    } else {
+        Some("That product can't be compared.".to_string())
+    };
+
+    let items = if list.len() >= 2 {
+        load_items(&state, &currency, &list).await
+    } else {
+        Vec::new()
+    };
+
+    CompareBarTemplate { items, warning }.into_response()
+}
+
+/// Remove a variant from the compare list (HTMX).
+///
+/// `variant_id` is the numeric variant ID. Returns the updated compare bar;
+/// the compare page reloads itself when the bar changes.
+#[instrument(skip(state, session, currency))]
+pub async fn remove(
+    State(state): State<AppState>,
+    session: Session,
+    currency: BuyerCurrency,
+    Path(variant_id): Path<String>,
+) -> Response {
+    let mut list = get_compare_list(&session).await;
+
+    if list.remove(&format!("{VARIANT_GID_PREFIX}{variant_id}"))
+        && let Err(e) = set_compare_list(&session, &list).await
+    {
+        tracing::error!("Failed to save compare list to session: {e}");
+    }
+
+    let items = if list.len() >= 2 {
+        load_items(&state, &currency, &list).await
+    } else {
+        Vec::new()
+    };
+
+    CompareBarTemplate {
+        items,
+        warning: None,
+    }
+    .into_response()
+}
@@ -0,0 +1,106 @@
+//! Cookie consent route handlers.
+//!
+//! The consent banner is rendered by the base layout until the visitor makes
+//! a choice. Choices are stored in the first-party `np_consent` cookie, which
+//! the [`cookie_consent_middleware`](crate::middleware::cookie_consent_middleware)
+//! reads on every request.
+
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    Form,
+    extract::State,
+    http::header,
+    response::{AppendHeaders, IntoResponse, Response},
+};
+use serde::Deserialize;
+use tower_sessions::cookie::{Cookie, SameSite, time::Duration};
+use tracing::instrument;
+
+use crate::filters;
+use crate::models::CookieConsent;
+use crate::models::cookie_consent::{CONSENT_COOKIE_MAX_AGE, CONSENT_COOKIE_NAME};
+use crate::state::AppState;
+
+/// Consent choice submitted from the banner.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsentChoice {
+    AcceptAll,
+    RejectAll,
+    /// Use the per-category checkboxes.
+    Custom,
+}
+
+/// Consent update form data.
+///
+/// Checkboxes are only submitted when checked, so their presence is the value.
+#[derive(Debug, Deserialize)]
+pub struct ConsentForm {
+    pub choice: ConsentChoice,
+    pub analytics: Option<String>,
+    pub marketing: Option<String>,
+}
+
+impl ConsentForm {
+    /// Resolve the submitted consent.
+    const fn consent(&self) -> CookieConsent {
+        match self.choice {
+            ConsentChoice::AcceptAll => CookieConsent::accept_all(),
+            ConsentChoice::RejectAll => CookieConsent::reject_all(),
+            ConsentChoice::Custom => CookieConsent {
+                analytics: self.analytics.is_some(),
+                marketing: self.marketing.is_some(),
+            },
+        }
+    }
+}
+
+/// Cookie preference form fragment (replaces the banner via HTMX).
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/cookie_consent_customize.html")]
+pub struct ConsentCustomizeTemplate;
+
+/// Display the per-category cookie preference form (HTMX).
+///
+/// GET /consent/customize
+#[instrument]
+pub async fn customize() -> ConsentCustomizeTemplate {
+    ConsentCustomizeTemplate
+}
+
+/// Save the visitor's cookie consent (HTMX).
+///
+/// POST /consent/update
+///
+/// Sets the `np_consent` cookie and returns an empty body, removing the
+/// banner. When any optional category is allowed, responds with `HX-Refresh`
+/// so the newly permitted scripts load.
+#[instrument(skip(state, form), fields(choice = ?form.choice))]
+pub async fn update(State(state): State<AppState>, Form(form): Form<ConsentForm>) -> Response {
+    let consent = form.consent();
+    let is_secure = state.config().base_url.starts_with("https://");
+
+    let cookie = Cookie::build((CONSENT_COOKIE_NAME, consent.to_cookie_value()))
+        .path("/")
+        .max_age(Duration::seconds(CONSENT_COOKIE_MAX_AGE))
+        .same_site(SameSite::Lax)
+        .secure(is_secure)
+        .http_only(true)
+        .build();
+
+    let refresh = if consent.analytics || consent.marketing {
+        "true"
+    } else {
+        "false"
+    };
+
+    AppendHeaders([
+        (header::SET_COOKIE, cookie.to_string()),
+        (
+            header::HeaderName::from_static("hx-refresh"),
+            refresh.to_string(),
+        ),
+    ])
+    .into_response()
+}
@@ -7,7 +7,10 @@ use tracing::instrument;
 
 use crate::config::AnalyticsConfig;
 use crate::filters;
-use crate::shopify::types::{Money, Product as ShopifyProduct};
+use crate::middleware::BuyerCurrency;
+use crate::services::PriceFormatter;
+use crate::shopify::StorefrontClient;
+use crate::shopify::types::{Metaobject, Product as ShopifyProduct};
 use crate::state::AppState;
 
 // =============================================================================
@@ -161,6 +164,16 @@ impl Default for HeroConfig {
 // Review Data
 // =============================================================================
 
+/// Metaobject type for homepage testimonials.
+///
+/// Entries need `name`, `quote` and `product` (product reference) fields and
+/// may set `rating` (1-5, defaults to 5). The definition must have Storefront
+/// access enabled.
+const TESTIMONIAL_TYPE: &str = "testimonial";
+
+/// Maximum testimonials shown on the homepage.
+const MAX_TESTIMONIALS: i64 = 12;
+
 /// A customer review for display on the homepage.
 #[derive(Clone)]
 pub struct ReviewView {
@@ -169,10 +182,80 @@ pub struct ReviewView {
     pub content: String,
     pub product_title: String,
     pub product_handle: String,
+    /// Local image under `/static/images/original/` (static reviews).
     pub product_image_path: Option<String>,
+    /// Shopify CDN image URL (testimonial metaobjects).
+    pub product_image_url: Option<String>,
+}
+
+impl ReviewView {
+    /// Build a review from a testimonial metaobject.
+    ///
+    /// Returns `None` if the name, quote or product is missing.
+    fn from_testimonial(testimonial: &Metaobject) -> Option<Self> {
+        let product = testimonial.field("product")?.product.as_ref()?;
+        let rating = testimonial
+            .value("rating")
+            .and_then(parse_rating)
+            .unwrap_or(5);
+
+        Some(Self {
+            reviewer_name: testimonial.value("name")?.to_string(),
+            rating,
+            content: testimonial.value("quote")?.to_string(),
+            product_title: product.title.clone(),
+            product_handle: product.handle.clone(),
+            product_image_path: None,
+            product_image_url: product.featured_image.as_ref().map(|i| i.url.clone()),
+        })
+    }
+}
+
+/// Parse a testimonial rating into whole stars (1-5).
+///
+/// Accepts an integer field ("4") or a Shopify rating field
+/// (`{"value": "4.5", "scale_min": "1.0", "scale_max": "5.0"}`).
+fn parse_rating(value: &str) -> Option<i64> {
+    let stars = value.parse::<f64>().ok().or_else(|| {
+        serde_json::from_str::<serde_json::Value>(value)
+            .ok()?
+            .get("value")?
+            .as_str()?
+            .parse()
+            .ok()
+    })?;
+
+    #[allow(clippy::cast_possible_truncation)]
+    let stars = stars.round() as i64;
+    Some(stars.clamp(1, 5))
+}
+
+/// Load testimonials from Shopify metaobjects, falling back to the static
+/// reviews if none are published or the request fails.
+async fn load_featured_reviews(storefront: &StorefrontClient) -> Vec<ReviewView> {
+    match storefront
+        .get_metaobjects(TESTIMONIAL_TYPE, MAX_TESTIMONIALS)
+        .await
+    {
+        Ok(testimonials) => {
+            let reviews: Vec<ReviewView> = testimonials
+                .iter()
+                .filter_map(ReviewView::from_testimonial)
+                .collect();
+            if reviews.is_empty() {
+                get_featured_reviews()
+            } else {
+                reviews
+            }
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch testimonials: {e}");
+            get_featured_reviews()
+        }
+    }
 }
 
-/// Static reviews for the homepage (can be replaced with dynamic data later).
+/// Static reviews shown when no testimonial metaobjects are published.
 fn get_featured_reviews() -> Vec<ReviewView> {
     vec![
         ReviewView {
@@ -182,6 +265,7 @@ fn get_featured_reviews() -> Vec<ReviewView> {
             product_title: "Bright & Tight Super Serum".to_string(),
             product_handle: "bright-tight-super-serum".to_string(),
             product_image_path: Some("/static/images/original/products/bright-tight-super-serum/NP_SuperSerum_SET.png".to_string()),
+            product_image_url: None,
         },
         ReviewView {
             reviewer_name: "Amanda".to_string(),
@@ -190,6 +274,7 @@ fn get_featured_reviews() -> Vec<ReviewView> {
             product_title: "Pineapple Exfoliating Gel Cleanser".to_string(),
             product_handle: "pineapple-enzyme-cleanser".to_string(),
             product_image_path: Some("/static/images/original/products/pineapple-enzyme-cleanser/NP_Cleanser_SET.png".to_string()),
+            product_image_url: None,
         },
         ReviewView {
             reviewer_name: "Shenae".to_string(),
@@ -198,6 +283,7 @@ fn get_featured_reviews() -> Vec<ReviewView> {
             product_title: "Exotic Nourishing Cream".to_string(),
             product_handle: "skin-tight-exotic-cream".to_string(),
             product_image_path: Some("/static/images/original/products/skin-tight-exotic-cream/NP_ExoticCream_SET.png".to_string()),
+            product_image_url: None,
         },
         ReviewView {
             reviewer_name: "Brie".to_string(),
@@ -206,6 +292,7 @@ fn get_featured_reviews() -> Vec<ReviewView> {
             product_title: "Hydrating Glow Up Bronzing Elixir".to_string(),
             product_handle: "glow-up-bronzing-facial-oil".to_string(),
             product_image_path: Some("/static/images/original/products/glow-up-bronzing-facial-oil/NP_BronzingOil_SET.png".to_string()),
+            product_image_url: None,
         },
         ReviewView {
             reviewer_name: "Tyler".to_string(),
@@ -214,6 +301,7 @@ fn get_featured_reviews() -> Vec<ReviewView> {
             product_title: "Facial Repair Tropical Oil".to_string(),
             product_handle: "pineapple-facial-oil".to_string(),
             product_image_path: Some("/static/images/original/products/pineapple-facial-oil/NP_TropicalOil_SET.png".to_string()),
+            product_image_url: None,
         },
     ]
 }
@@ -245,16 +333,10 @@ pub struct ImageView {
 // Type Conversions
 // =============================================================================
 
-/// Format a Shopify Money type as a price string.
-fn format_price(money: &Money) -> String {
-    money.amount.parse::<f64>().map_or_else(
-        |_| format!("${}", money.amount),
-        |amount| format!("${amount:.2}"),
-    )
-}
-
-impl From<&ShopifyProduct> for ProductView {
-    fn from(product: &ShopifyProduct) -> Self {
+impl ProductView {
+    /// Build a product view with prices formatted for the buyer's currency.
+    #[must_use]
+    pub fn localized(product: &ShopifyProduct, formatter: &PriceFormatter) -> Self {
         // Get featured image (first image)
         let featured_image = product.featured_image.as_ref().map(|img| ImageView {
             url: img.url.clone(),
@@ -277,12 +359,12 @@ impl From<&ShopifyProduct> for ProductView {
         Self {
             handle: product.handle.clone(),
             title: product.title.clone(),
-            price: format_price(&product.price_range.min_variant_price),
+            price: formatter.format(&product.price_range.min_variant_price),
             compare_at_price: product
                 .compare_at_price_range
                 .as_ref()
                 .filter(|r| r.min_variant_price.amount != "0.0")
-                .map(|r| format_price(&r.min_variant_price)),
+                .map(|r| formatter.format(&r.min_variant_price)),
             featured_image,
             hover_image,
             product_type,
@@ -320,14 +402,16 @@ const SKINCARE_COLLECTION: &str = "frontpage";
 const MERCH_COLLECTION: &str = "merch";
 
 /// Display the home page.
-#[instrument(skip(state, nonce))]
+#[instrument(skip(state, currency, nonce))]
 pub async fn home(
     State(state): State<AppState>,
+    currency: BuyerCurrency,
     crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
 ) -> impl IntoResponse {
+    let storefront = currency.storefront(state.storefront());
+
     // Fetch skincare products from collection
-    let skincare_products = state
-        .storefront()
+    let skincare_products = storefront
         .get_collection_by_handle(
             SKINCARE_COLLECTION,
             Some(PRODUCTS_PER_COLLECTION),
@@ -342,12 +426,17 @@ pub async fn home(
                 tracing::error!("Failed to fetch skincare collection: {e}");
                 Vec::new()
             },
-            |collection| collection.products.iter().map(ProductView::from).collect(),
+            |collection| {
+                collection
+                    .products
+                    .iter()
+                    .map(|p| ProductView::localized(p, &currency.formatter))
+                    .collect()
+            },
         );
 
     // Fetch merch products from collection
-    let merch_products = state
-        .storefront()
+    let merch_products = storefront
         .get_collection_by_handle(
             MERCH_COLLECTION,
             Some(PRODUCTS_PER_COLLECTION),
@@ -362,9 +451,17 @@ pub async fn home(
                 tracing::error!("Failed to fetch merch collection: {e}");
                 Vec::new()
             },
-            |collection| collection.products.iter().map(ProductView::from).collect(),
+            |collection| {
+                collection
+                    .products
+                    .iter()
+                    .map(|p| ProductView::localized(p, &currency.formatter))
+                    .collect()
+            },
         );
 
+    let featured_reviews = load_featured_reviews(state.storefront()).await;
+
     let base_url = state.config().base_url.clone();
     let logo_url = crate::filters::get_logo_url(&base_url);
 
@@ -372,10 +469,27 @@ pub async fn home(
         hero: HeroConfig::default(),
         skincare_products,
         merch_products,
-        featured_reviews: get_featured_reviews(),
+        featured_reviews,
         analytics: state.config().analytics.clone(),
         nonce,
         base_url,
         logo_url,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rating() {
+        assert_eq!(parse_rating("4"), Some(4));
+        assert_eq!(
+            parse_rating(r#"{"value": "4.5", "scale_min": "1.0", "scale_max": "5.0"}"#),
+            Some(5)
+        );
+        assert_eq!(parse_rating("9"), Some(5));
+        assert_eq!(parse_rating("0"), Some(1));
+        assert_eq!(parse_rating("great"), None);
+    }
+}
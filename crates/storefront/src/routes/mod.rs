@@ -9,16 +9,31 @@
 //! # Products
 //! GET  /products               - Product listing
 //! GET  /products/:handle       - Product detail
+//! GET  /products/:handle/variant/:variant_id - Variant selector fragment (HTMX)
 //! GET  /products/:handle/quick-view - Quick view fragment (HTMX)
+//! GET  /products/:handle/recommendations - Recommendations carousel (HTMX)
+//! GET  /products/:handle/bundle - Bundle builder fragment (HTMX)
+//! POST /products/:handle/notify - Back-in-stock subscription (HTMX fragment)
 //! GET  /collections            - Collection listing
-//! GET  /collections/:handle    - Collection detail
+//! GET  /collections/:handle    - Collection detail (filter by `?tags[]=`)
 //!
 //! # Cart (HTMX fragments)
 //! GET  /cart                   - Cart page
-//! POST /cart/add               - Add to cart (returns empty, triggers cartUpdated)
-//! POST /cart/update            - Update quantity (returns cart_items fragment)
-//! POST /cart/remove            - Remove item (returns cart_items fragment)
-//! GET  /cart/count             - Cart count badge (fragment)
+//! POST /cart/add               - Add to cart (returns OOB cart count badge)
+//! POST /cart/add-bundle        - Add every bundle component to cart (returns OOB cart count badge)
+//! POST /cart/update            - Update quantity (returns cart_items fragment + OOB badge)
+//! POST /cart/remove            - Remove item (returns cart_items fragment + OOB badge)
+//! GET  /cart/count             - Cart count badge (OOB fragment)
+//! POST /cart/estimate-shipping - Estimate shipping rates (returns shipping_estimate fragment)
+//! POST /cart/shipping-rate     - Select shipping rate (returns shipping_estimate fragment)
+//! POST /cart/discount          - Apply discount code (returns cart_discount fragment)
+//! DELETE /cart/discount/:code  - Remove discount code (returns cart_discount fragment)
+//!
+//! # Compare
+//! GET    /compare              - Product comparison table
+//! GET    /compare/bar          - Compare bar (HTMX fragment)
+//! POST   /compare/add          - Add variant to compare list (returns compare_bar fragment)
+//! DELETE /compare/remove/:variant_id - Remove variant (returns compare_bar fragment)
 //!
 //! # Checkout
 //! GET  /checkout               - Redirect to Shopify checkout
@@ -26,9 +41,24 @@
 //! # Newsletter
 //! POST /newsletter/subscribe   - Subscribe to newsletter (HTMX fragment)
 //!
+//! # Cookie Consent
+//! GET  /consent/customize      - Cookie preference form (HTMX fragment)
+//! POST /consent/update         - Save consent to np_consent cookie (removes banner)
+//!
+//! # Preferences
+//! GET  /preferences/currency   - Currency selector (HTMX fragment)
+//! POST /preferences/currency   - Set display currency (HX-Refresh)
+//! GET  /preferences/language   - Language selector (HTMX fragment)
+//! POST /preferences/language   - Set content language (HX-Refresh)
+//!
 //! # Contact
 //! POST /contact/product-question - Submit product question (JSON API)
 //!
+//! # Shopify Webhooks
+//! POST /webhooks/inventory-levels/update - Trigger back-in-stock notifications
+//! POST /webhooks/products/{create,update,delete} - Evict cached product data
+//! POST /webhooks/collections/{create,update,delete} - Evict cached collection data
+//!
 //! # Auth
 //! GET  /auth/login             - Login page
 //! POST /auth/login             - Login action
@@ -50,7 +80,14 @@
 //! # Account (requires auth)
 //! GET  /account                - Account overview
 //! GET  /account/orders         - Order history
-//! GET  /account/addresses      - Address list
+//! GET  /account/orders/:id     - Order details
+//! GET  /account/profile        - Profile (name and phone)
+//! POST /account/profile        - Update profile
+//! GET  /account/addresses      - Address list (HTMX address book)
+//! POST /account/addresses/new  - Create address
+//! PUT  /account/addresses/:id  - Update address
+//! DELETE /account/addresses/:id - Delete address
+//! POST /account/addresses/:id/default - Set default address
 //! GET  /account/passkeys       - Passkey management
 //! ```
 
@@ -60,14 +97,18 @@ pub mod auth;
 pub mod blog;
 pub mod cart;
 pub mod collections;
+pub mod compare;
+pub mod consent;
 pub mod contact;
 pub mod home;
 pub mod manifest;
 pub mod newsletter;
 pub mod pages;
+pub mod preferences;
 pub mod products;
 pub mod search;
 pub mod shopify_auth;
+pub mod webhooks;
 
 use axum::{
     Router,
@@ -127,7 +168,14 @@ pub fn product_routes() -> Router<AppState> {
     Router::new()
         .route("/", get(products::index))
         .route("/{handle}", get(products::show))
+        .route("/{handle}/variant/{variant_id}", get(products::variant))
         .route("/{handle}/quick-view", get(products::quick_view))
+        .route("/{handle}/recommendations", get(products::recommendations))
+        .route("/{handle}/bundle", get(products::bundle))
+        .route(
+            "/{handle}/notify",
+            post(products::notify).layer(api_rate_limiter()),
+        )
 }
 
 /// Create the collection routes router.
@@ -141,32 +189,64 @@ pub fn collection_routes() -> Router<AppState> {
 ///
 /// Rate limited to ~100 requests per minute per IP to prevent cart abuse.
 pub fn cart_routes() -> Router<AppState> {
+    use axum::routing::delete;
+
     Router::new()
         .route("/", get(cart::show))
         .route("/add", post(cart::add))
+        .route("/add-bundle", post(cart::add_bundle))
         .route("/update", post(cart::update))
         .route("/remove", post(cart::remove))
         .route("/count", get(cart::count))
+        .route("/estimate-shipping", post(cart::estimate_shipping))
+        .route("/shipping-rate", post(cart::select_shipping_rate))
+        .route("/discount", post(cart::apply_discount))
+        .route("/discount/{code}", delete(cart::remove_discount))
         .layer(api_rate_limiter())
 }
 
+/// Create the compare routes router.
+pub fn compare_routes() -> Router<AppState> {
+    use axum::routing::delete;
+
+    Router::new()
+        .route("/", get(compare::show))
+        .route("/bar", get(compare::bar))
+        .route("/add", post(compare::add))
+        .route("/remove/{variant_id}", delete(compare::remove))
+}
+
 /// Create the account routes router.
 pub fn account_routes() -> Router<AppState> {
-    use axum::routing::delete;
+    use axum::routing::put;
 
     Router::new()
         .route("/", get(account::index))
         .route("/orders", get(account::orders))
+        .route("/orders/{id}", get(account::order))
+        .route(
+            "/profile",
+            get(account::profile).post(account::update_profile),
+        )
         .route(
             "/addresses",
             get(account::addresses).post(account::create_address),
         )
-        .route("/addresses/new", get(account::new_address))
+        .route(
+            "/addresses/new",
+            get(account::new_address).post(account::create_address),
+        )
         .route(
             "/addresses/{id}",
-            post(account::update_address).delete(account::delete_address),
+            put(account::update_address)
+                .post(account::update_address)
+                .delete(account::delete_address),
         )
         .route("/addresses/{id}/edit", get(account::edit_address))
+        .route(
+            "/addresses/{id}/default",
+            post(account::set_default_address),
+        )
     // TODO: Add passkey management routes
     // .route("/passkeys", get(account::passkeys))
 }
@@ -188,6 +268,8 @@ pub fn routes() -> Router<AppState> {
         .merge(pages::router())
         // Cart routes
         .nest("/cart", cart_routes())
+        // Compare routes
+        .nest("/compare", compare_routes())
         // Checkout redirect
         .route("/checkout", get(cart::checkout))
         // Search routes
@@ -204,6 +286,38 @@ pub fn routes() -> Router<AppState> {
             "/newsletter/unsubscribe",
             get(newsletter::unsubscribe_page).post(newsletter::unsubscribe),
         )
+        // Cookie consent routes
+        .route("/consent/customize", get(consent::customize))
+        .route("/consent/update", post(consent::update))
+        // Preference routes
+        .route(
+            "/preferences/currency",
+            get(preferences::currency_selector).post(preferences::set_currency),
+        )
+        .route(
+            "/preferences/language",
+            get(preferences::language_selector).post(preferences::set_language),
+        )
         // Contact routes
         .route("/contact/product-question", post(contact::product_question))
+        // Shopify webhooks
+        .route(
+            "/webhooks/inventory-levels/update",
+            post(webhooks::inventory_levels_update),
+        )
+        .route("/webhooks/products/create", post(webhooks::product_changed))
+        .route("/webhooks/products/update", post(webhooks::product_changed))
+        .route("/webhooks/products/delete", post(webhooks::product_changed))
+        .route(
+            "/webhooks/collections/create",
+            post(webhooks::collection_changed),
+        )
+        .route(
+            "/webhooks/collections/update",
+            post(webhooks::collection_changed),
+        )
+        .route(
+            "/webhooks/collections/delete",
+            post(webhooks::collection_changed),
+        )
 }
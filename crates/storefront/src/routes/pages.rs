@@ -5,11 +5,18 @@
 
 use askama::Template;
 use askama_web::WebTemplate;
-use axum::{Router, extract::State, http::StatusCode, response::IntoResponse, routing::get};
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+};
 use chrono::NaiveDate;
 use tracing::instrument;
 
 use crate::config::AnalyticsConfig;
+use crate::content::{ContentEntry, ContentType};
 use crate::filters;
 use crate::state::AppState;
 
@@ -81,6 +88,27 @@ pub struct ContentPageTemplate {
     pub nonce: String,
 }
 
+/// Content not found page template.
+#[derive(Template, WebTemplate)]
+#[template(path = "pages/not_found.html")]
+pub struct NotFoundTemplate {
+    pub analytics: AnalyticsConfig,
+    pub nonce: String,
+}
+
+/// Respond with the 404 page.
+#[must_use]
+pub fn not_found(state: &AppState, nonce: String) -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        NotFoundTemplate {
+            analytics: state.config().analytics.clone(),
+            nonce,
+        },
+    )
+        .into_response()
+}
+
 /// Serve a content page by slug.
 fn serve_content_page(
     state: &AppState,
@@ -280,6 +308,31 @@ pub async fn collabs(
     serve_content_page(&state, "collabs", nonce)
 }
 
+/// Display a markdown page at `/pages/{slug}`.
+///
+/// Template-based pages registered under `/pages/` take precedence; any
+/// other slug is looked up in the content router, falling back to the 404
+/// page.
+#[instrument(skip(state, nonce))]
+pub async fn content_page(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
+) -> Response {
+    match state.content().router().resolve(ContentType::Page, &slug) {
+        Some(ContentEntry::Page(page)) => ContentPageTemplate {
+            title: page.meta.title.clone(),
+            description: page.meta.description.clone().unwrap_or_default(),
+            updated_at: page.meta.updated_at,
+            content_html: page.content_html.clone(),
+            analytics: state.config().analytics.clone(),
+            nonce,
+        }
+        .into_response(),
+        _ => not_found(&state, nonce),
+    }
+}
+
 // =============================================================================
 // Router
 // =============================================================================
@@ -294,6 +347,8 @@ pub fn router() -> Router<AppState> {
         .route("/pages/affiliate-program", get(affiliate_program))
         .route("/pages/teen-program", get(teen_program))
         .route("/pages/subscriptions", get(subscriptions))
+        // Markdown pages by slug (content/pages/{slug}.md)
+        .route("/pages/{*slug}", get(content_page))
         // Markdown-based content pages
         .route("/terms", get(terms))
         .route("/privacy", get(privacy))
@@ -0,0 +1,192 @@
+//! Buyer preference route handlers.
+//!
+//! Lets buyers override the detected display currency and content language.
+//! The choice is stored in the session and picked up by the [`BuyerCurrency`]
+//! extractor.
+
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    Form,
+    extract::State,
+    http::StatusCode,
+    response::{AppendHeaders, IntoResponse, Response},
+};
+use serde::Deserialize;
+use tower_sessions::Session;
+use tracing::instrument;
+
+use crate::filters;
+use crate::middleware::BuyerCurrency;
+use crate::models::session_keys;
+use crate::services::{CurrencyDetectionService, LanguageDetectionService};
+use crate::shopify::types::LocalizationLanguage;
+use crate::state::AppState;
+
+/// Currency selection form data.
+#[derive(Debug, Deserialize)]
+pub struct CurrencyForm {
+    pub currency: String,
+}
+
+/// Language selection form data.
+#[derive(Debug, Deserialize)]
+pub struct LanguageForm {
+    pub language: String,
+}
+
+/// Currency selector fragment (lazy-loaded into the footer).
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/currency_selector.html")]
+pub struct CurrencySelectorTemplate {
+    /// Currency codes the shop sells in.
+    pub currencies: Vec<String>,
+    /// Currently selected currency code.
+    pub selected: String,
+}
+
+/// Language selector fragment (lazy-loaded into the header).
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/language_selector.html")]
+pub struct LanguageSelectorTemplate {
+    /// Languages the shop is published in.
+    pub languages: Vec<LocalizationLanguage>,
+    /// Currently selected language code.
+    pub selected: String,
+}
+
+/// Display the currency selector fragment (HTMX).
+///
+/// GET /preferences/currency
+///
+/// Returns an empty response when the shop only sells in one currency.
+#[instrument(skip(state, currency))]
+pub async fn currency_selector(State(state): State<AppState>, currency: BuyerCurrency) -> Response {
+    let localization = match state.storefront().get_shop_localization().await {
+        Ok(localization) => localization,
+        Err(e) => {
+            tracing::error!("Failed to load shop localization: {e}");
+            return ().into_response();
+        }
+    };
+
+    let currencies = CurrencyDetectionService::new(&localization).available_currencies();
+    if currencies.len() < 2 {
+        return ().into_response();
+    }
+
+    let selected = currency
+        .preference
+        .map_or(localization.currency_code, |p| p.currency_code);
+
+    CurrencySelectorTemplate {
+        currencies,
+        selected,
+    }
+    .into_response()
+}
+
+/// Save the buyer's display currency (HTMX).
+///
+/// POST /preferences/currency
+///
+/// Responds with `HX-Refresh` so the page reloads with localized prices.
+#[instrument(skip(state, session))]
+pub async fn set_currency(
+    State(state): State<AppState>,
+    session: Session,
+    Form(form): Form<CurrencyForm>,
+) -> Response {
+    let localization = match state.storefront().get_shop_localization().await {
+        Ok(localization) => localization,
+        Err(e) => {
+            tracing::error!("Failed to load shop localization: {e}");
+            return StatusCode::SERVICE_UNAVAILABLE.into_response();
+        }
+    };
+
+    let Some(preference) =
+        CurrencyDetectionService::new(&localization).for_currency(&form.currency)
+    else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+
+    if let Err(e) = session
+        .insert(session_keys::CURRENCY_PREFERENCE, &preference)
+        .await
+    {
+        tracing::error!("Failed to save currency preference to session: {e}");
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+
+    (
+        AppendHeaders([("HX-Refresh", "true")]),
+        StatusCode::NO_CONTENT,
+    )
+        .into_response()
+}
+
+/// Display the language selector fragment (HTMX).
+///
+/// GET /preferences/language
+///
+/// Returns an empty response when the shop is only published in one language.
+#[instrument(skip(state, currency))]
+pub async fn language_selector(State(state): State<AppState>, currency: BuyerCurrency) -> Response {
+    let localization = match state.storefront().get_shop_localization().await {
+        Ok(localization) => localization,
+        Err(e) => {
+            tracing::error!("Failed to load shop localization: {e}");
+            return ().into_response();
+        }
+    };
+
+    if localization.languages.len() < 2 {
+        return ().into_response();
+    }
+
+    LanguageSelectorTemplate {
+        selected: currency.language.unwrap_or(localization.language_code),
+        languages: localization.languages,
+    }
+    .into_response()
+}
+
+/// Save the buyer's content language (HTMX).
+///
+/// POST /preferences/language
+///
+/// Responds with `HX-Refresh` so the page reloads with translated content.
+#[instrument(skip(state, session))]
+pub async fn set_language(
+    State(state): State<AppState>,
+    session: Session,
+    Form(form): Form<LanguageForm>,
+) -> Response {
+    let localization = match state.storefront().get_shop_localization().await {
+        Ok(localization) => localization,
+        Err(e) => {
+            tracing::error!("Failed to load shop localization: {e}");
+            return StatusCode::SERVICE_UNAVAILABLE.into_response();
+        }
+    };
+
+    let Some(language) = LanguageDetectionService::new(&localization).for_tag(&form.language)
+    else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+
+    if let Err(e) = session
+        .insert(session_keys::LANGUAGE_PREFERENCE, &language)
+        .await
+    {
+        tracing::error!("Failed to save language preference to session: {e}");
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+
+    (
+        AppendHeaders([("HX-Refresh", "true")]),
+        StatusCode::NO_CONTENT,
+    )
+        .into_response()
+}
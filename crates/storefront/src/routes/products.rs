@@ -3,18 +3,25 @@
 use askama::Template;
 use askama_web::WebTemplate;
 use axum::{
+    Form,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::{IntoResponse, Response},
+    http::{HeaderMap, StatusCode},
+    response::{AppendHeaders, IntoResponse, Redirect, Response},
 };
 use serde::Deserialize;
 use tracing::instrument;
 
+use naked_pineapple_core::Email;
+
 use crate::config::AnalyticsConfig;
+use crate::db::back_in_stock::BackInStockRepository;
 use crate::filters;
+use crate::middleware::BuyerCurrency;
+use crate::services::PriceFormatter;
 use crate::shopify::ShopifyError;
 use crate::shopify::types::{
-    Money, Product as ShopifyProduct, ProductRecommendationIntent, SellingPlanPriceAdjustmentValue,
+    Product as ShopifyProduct, ProductBundle, ProductRecommendationIntent,
+    ProductVariant as ShopifyVariant, SellingPlanPriceAdjustmentValue,
 };
 use crate::state::AppState;
 
@@ -105,11 +112,32 @@ pub struct VariantView {
     pub id: String,
     pub title: String,
     pub price: String,
+    pub compare_at_price: Option<String>,
+    pub image: Option<ImageView>,
     pub available_for_sale: bool,
     pub quantity_available: Option<i64>,
     pub shop_pay_installments: Option<ShopPayInstallmentsView>,
 }
 
+impl VariantView {
+    /// Numeric variant ID (the last segment of the GID), used in URLs.
+    #[must_use]
+    pub fn legacy_id(&self) -> &str {
+        self.id.rsplit('/').next().unwrap_or(&self.id)
+    }
+}
+
+/// A bundle component for templates.
+#[derive(Clone)]
+pub struct BundleComponentView {
+    /// Position in the bundle (used in `component_{index}` form fields).
+    pub index: usize,
+    pub quantity: u32,
+    pub product: ProductView,
+    /// Currently selected variant ID.
+    pub selected_variant_id: Option<String>,
+}
+
 /// Breadcrumb item for SEO structured data.
 #[derive(Clone)]
 pub struct BreadcrumbItem {
@@ -117,6 +145,13 @@ pub struct BreadcrumbItem {
     pub url: Option<String>,
 }
 
+/// Product page query parameters.
+#[derive(Debug, Deserialize)]
+pub struct ProductQuery {
+    /// Variant to pre-select (GID or numeric ID).
+    pub variant: Option<String>,
+}
+
 /// Pagination query parameters.
 #[derive(Debug, Deserialize)]
 pub struct PaginationQuery {
@@ -128,28 +163,50 @@ pub struct PaginationQuery {
 // Type Conversions
 // =============================================================================
 
-/// Format a Shopify Money type as a price string.
-fn format_price(money: &Money) -> String {
-    // Parse the amount string to format it properly
-    money.amount.parse::<f64>().map_or_else(
-        |_| format!("${}", money.amount),
-        |amount| format!("${amount:.2}"),
-    )
+impl VariantView {
+    /// Build a variant view with prices formatted for the buyer's currency.
+    fn localized(v: &ShopifyVariant, formatter: &PriceFormatter) -> Self {
+        Self {
+            id: v.id.clone(),
+            title: v.title.clone(),
+            price: formatter.format(&v.price),
+            compare_at_price: v
+                .compare_at_price
+                .as_ref()
+                .filter(|p| p.amount != "0.0")
+                .map(|p| formatter.format(p)),
+            image: v.image.as_ref().map(|img| ImageView {
+                url: img.url.clone(),
+                alt: img.alt_text.clone().unwrap_or_default(),
+            }),
+            available_for_sale: v.available_for_sale,
+            quantity_available: v.quantity_available,
+            shop_pay_installments: v.shop_pay_installments.as_ref().map(|sp| {
+                ShopPayInstallmentsView {
+                    eligible: sp.eligible,
+                    price_per_term: sp.price_per_term.as_ref().map(|p| formatter.format(p)),
+                    installments_count: sp.installments_count.as_ref().map(|c| c.count),
+                }
+            }),
+        }
+    }
 }
 
-impl From<&ShopifyProduct> for ProductView {
-    fn from(product: &ShopifyProduct) -> Self {
+impl ProductView {
+    /// Build a product view with prices formatted for the buyer's currency.
+    #[must_use]
+    pub fn localized(product: &ShopifyProduct, formatter: &PriceFormatter) -> Self {
         Self {
             handle: product.handle.clone(),
             title: product.title.clone(),
             description: product.description_html.clone(),
             product_type: product.kind.clone(),
-            price: format_price(&product.price_range.min_variant_price),
+            price: formatter.format(&product.price_range.min_variant_price),
             compare_at_price: product
                 .compare_at_price_range
                 .as_ref()
                 .filter(|r| r.min_variant_price.amount != "0.0")
-                .map(|r| format_price(&r.min_variant_price)),
+                .map(|r| formatter.format(&r.min_variant_price)),
             featured_image: product.featured_image.as_ref().map(|img| ImageView {
                 url: img.url.clone(),
                 alt: img.alt_text.clone().unwrap_or_default(),
@@ -165,20 +222,7 @@ impl From<&ShopifyProduct> for ProductView {
             variants: product
                 .variants
                 .iter()
-                .map(|v| VariantView {
-                    id: v.id.clone(),
-                    title: v.title.clone(),
-                    price: format_price(&v.price),
-                    available_for_sale: v.available_for_sale,
-                    quantity_available: v.quantity_available,
-                    shop_pay_installments: v.shop_pay_installments.as_ref().map(|sp| {
-                        ShopPayInstallmentsView {
-                            eligible: sp.eligible,
-                            price_per_term: sp.price_per_term.as_ref().map(format_price),
-                            installments_count: sp.installments_count.as_ref().map(|c| c.count),
-                        }
-                    }),
-                })
+                .map(|v| VariantView::localized(v, formatter))
                 .collect(),
             ingredients: product.ingredients.clone(),
             directions: product.directions.clone(),
@@ -248,6 +292,17 @@ impl From<&ShopifyProduct> for ProductView {
                 .collect(),
         }
     }
+
+    /// Find a variant by GID or numeric ID, falling back to the first variant.
+    #[must_use]
+    pub fn variant(&self, id: Option<&str>) -> Option<&VariantView> {
+        id.and_then(|id| {
+            self.variants
+                .iter()
+                .find(|v| v.id == id || v.legacy_id() == id)
+        })
+        .or_else(|| self.variants.first())
+    }
 }
 
 /// Product listing page template.
@@ -269,7 +324,6 @@ pub struct ProductsIndexTemplate {
 #[template(path = "products/show.html")]
 pub struct ProductShowTemplate {
     pub product: ProductView,
-    pub related_products: Vec<ProductView>,
     pub analytics: AnalyticsConfig,
     pub nonce: String,
     /// Base URL for canonical links and structured data.
@@ -278,39 +332,115 @@ pub struct ProductShowTemplate {
     pub breadcrumbs: Vec<BreadcrumbItem>,
     /// Shopify store URL for Shop Pay button (e.g., "your-store.myshopify.com").
     pub store_url: String,
+    /// Variant from `?variant=` (GID or numeric ID); defaults to the first.
+    pub selected_variant_id: Option<String>,
+}
+
+impl ProductShowTemplate {
+    /// The variant the page renders prices and purchase buttons for.
+    fn selected_variant(&self) -> Option<&VariantView> {
+        self.product.variant(self.selected_variant_id.as_deref())
+    }
+}
+
+/// Variant selector fragment template.
+///
+/// Replaces the selector and swaps the variant's image, price, stock and
+/// purchase buttons into the product page out of band.
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/product_variant.html")]
+pub struct ProductVariantTemplate {
+    pub product: ProductView,
+    pub selected_variant_id: Option<String>,
+    /// Shopify store URL for Shop Pay button.
+    pub store_url: String,
+}
+
+impl ProductVariantTemplate {
+    fn selected_variant(&self) -> Option<&VariantView> {
+        self.product.variant(self.selected_variant_id.as_deref())
+    }
 }
 
-/// Quick view fragment template.
+/// Quick view dialog fragment template.
 #[derive(Template, WebTemplate)]
 #[template(path = "partials/quick_view.html")]
 pub struct QuickViewTemplate {
     pub product: ProductView,
-    /// Shopify store URL for Shop Pay button (e.g., "your-store.myshopify.com")
-    pub store_url: String,
 }
 
+/// Product recommendations carousel fragment template.
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/product_recommendations.html")]
+pub struct ProductRecommendationsTemplate {
+    pub products: Vec<ProductView>,
+}
+
+/// Product bundle fragment template (for HTMX).
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/product_bundle.html")]
+pub struct ProductBundleTemplate {
+    pub handle: String,
+    pub components: Vec<BundleComponentView>,
+    /// Formatted total of the selected variants (e.g., "$42.00").
+    pub total_price: Option<String>,
+    /// Whether every selected variant is in stock.
+    pub available: bool,
+}
+
+/// Back-in-stock subscription result fragment template.
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/back_in_stock_status.html")]
+pub struct BackInStockStatusTemplate {
+    pub success: bool,
+    pub message: String,
+}
+
+impl BackInStockStatusTemplate {
+    fn error(message: &str) -> Self {
+        Self {
+            success: false,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Back-in-stock subscription form data.
+#[derive(Debug, Deserialize)]
+pub struct NotifyForm {
+    pub email: String,
+    pub variant_id: String,
+}
+
+/// Maximum number of recommended products shown in the carousel.
+const MAX_RECOMMENDATIONS: usize = 6;
+
 /// Products per page for pagination.
 const PRODUCTS_PER_PAGE: i64 = 12;
 
 /// Display product listing page.
-#[instrument(skip(state, nonce))]
+#[instrument(skip(state, currency, nonce))]
 pub async fn index(
     State(state): State<AppState>,
     Query(query): Query<PaginationQuery>,
+    currency: BuyerCurrency,
     crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
 ) -> Response {
     let current_page = query.page.unwrap_or(1);
 
     // Fetch products from Shopify Storefront API
-    let result = state
-        .storefront()
+    let result = currency
+        .storefront(state.storefront())
         .get_products(Some(PRODUCTS_PER_PAGE), None, None, None, None)
         .await;
 
     match result {
         Ok(connection) => {
-            let products: Vec<ProductView> =
-                connection.products.iter().map(ProductView::from).collect();
+            let products: Vec<ProductView> = connection
+                .products
+                .iter()
+                .map(|p| ProductView::localized(p, &currency.formatter))
+                .collect();
 
             // Estimate total pages (Shopify doesn't give total count easily)
             let has_more = connection.page_info.has_next_page;
@@ -348,29 +478,26 @@ pub async fn index(
 }
 
 /// Display product detail page.
-#[instrument(skip(state, nonce))]
+///
+/// `?variant=` pre-selects a variant, so variant URLs pushed by the selector
+/// can be shared and restored from history.
+#[instrument(skip(state, currency, nonce))]
 pub async fn show(
     State(state): State<AppState>,
     Path(handle): Path<String>,
+    Query(query): Query<ProductQuery>,
+    currency: BuyerCurrency,
     crate::middleware::CspNonce(nonce): crate::middleware::CspNonce,
 ) -> Response {
     // Fetch product from Shopify Storefront API
-    let result = state.storefront().get_product_by_handle(&handle).await;
+    let result = currency
+        .storefront(state.storefront())
+        .get_product_by_handle(&handle)
+        .await;
 
     match result {
         Ok(shopify_product) => {
-            let product = ProductView::from(&shopify_product);
-
-            // Fetch related products
-            let related_products = state
-                .storefront()
-                .get_product_recommendations(
-                    &shopify_product.id,
-                    Some(ProductRecommendationIntent::Related),
-                )
-                .await
-                .map(|products| products.iter().take(4).map(ProductView::from).collect())
-                .unwrap_or_default();
+            let product = ProductView::localized(&shopify_product, &currency.formatter);
 
             // SEO breadcrumbs
             let breadcrumbs = vec![
@@ -390,12 +517,12 @@ pub async fn show(
 
             ProductShowTemplate {
                 product,
-                related_products,
                 analytics: state.config().analytics.clone(),
                 nonce,
                 base_url: state.config().base_url.clone(),
                 breadcrumbs,
                 store_url: state.config().shopify.store.clone(),
+                selected_variant_id: query.variant,
             }
             .into_response()
         }
@@ -424,12 +551,12 @@ pub async fn show(
                         requires_selling_plan: false,
                         selling_plan_groups: Vec::new(),
                     },
-                    related_products: Vec::new(),
                     analytics: state.config().analytics.clone(),
                     nonce,
                     base_url: state.config().base_url.clone(),
                     breadcrumbs: Vec::new(),
                     store_url: state.config().shopify.store.clone(),
+                    selected_variant_id: None,
                 },
             )
                 .into_response()
@@ -459,12 +586,12 @@ pub async fn show(
                         requires_selling_plan: false,
                         selling_plan_groups: Vec::new(),
                     },
-                    related_products: Vec::new(),
                     analytics: state.config().analytics.clone(),
                     nonce,
                     base_url: state.config().base_url.clone(),
                     breadcrumbs: Vec::new(),
                     store_url: state.config().shopify.store.clone(),
+                    selected_variant_id: None,
                 },
             )
                 .into_response()
@@ -472,19 +599,79 @@ pub async fn show(
     }
 }
 
-/// Display quick view fragment (for HTMX).
-#[instrument(skip(state))]
-pub async fn quick_view(State(state): State<AppState>, Path(handle): Path<String>) -> Response {
-    // Fetch product from Shopify Storefront API
-    let result = state.storefront().get_product_by_handle(&handle).await;
+/// Swap in a variant on the product page (HTMX fragment).
+///
+/// Responds with `HX-Push-Url` set to `/products/{handle}?variant={id}`, so
+/// the history entry is the shareable product URL rather than this fragment.
+/// Non-HTMX requests are redirected there.
+#[instrument(skip(state, headers, currency))]
+pub async fn variant(
+    State(state): State<AppState>,
+    Path((handle, variant_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    currency: BuyerCurrency,
+) -> Response {
+    let page_url = format!(
+        "/products/{}?variant={}",
+        urlencoding::encode(&handle),
+        urlencoding::encode(&variant_id)
+    );
+    if !headers.contains_key("HX-Request") {
+        return Redirect::to(&page_url).into_response();
+    }
+
+    let product = match currency
+        .storefront(state.storefront())
+        .get_product_by_handle(&handle)
+        .await
+    {
+        Ok(product) => ProductView::localized(&product, &currency.formatter),
+        Err(ShopifyError::NotFound(_)) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch product {handle}: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
 
-    // Store URL for Shop Pay button (e.g., "your-store.myshopify.com")
-    let store_url = state.config().shopify.store.clone();
+    if !product
+        .variants
+        .iter()
+        .any(|v| v.legacy_id() == variant_id || v.id == variant_id)
+    {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    (
+        AppendHeaders([("HX-Push-Url", page_url)]),
+        ProductVariantTemplate {
+            product,
+            selected_variant_id: Some(variant_id),
+            store_url: state.config().shopify.store.clone(),
+        },
+    )
+        .into_response()
+}
+
+/// Display quick view dialog fragment (for HTMX).
+///
+/// The fragment is a self-contained `<dialog>` appended to `<body>`; the
+/// base layout opens it as a modal once it has settled.
+#[instrument(skip(state, currency))]
+pub async fn quick_view(
+    State(state): State<AppState>,
+    Path(handle): Path<String>,
+    currency: BuyerCurrency,
+) -> Response {
+    // Fetch product from Shopify Storefront API
+    let result = currency
+        .storefront(state.storefront())
+        .get_product_by_handle(&handle)
+        .await;
 
     match result {
         Ok(shopify_product) => {
-            let product = ProductView::from(&shopify_product);
-            QuickViewTemplate { product, store_url }.into_response()
+            let product = ProductView::localized(&shopify_product, &currency.formatter);
+            QuickViewTemplate { product }.into_response()
         }
         Err(e) => {
             tracing::error!("Failed to fetch product for quick view {handle}: {e}");
@@ -509,7 +696,355 @@ pub async fn quick_view(State(state): State<AppState>, Path(handle): Path<String
                 requires_selling_plan: false,
                 selling_plan_groups: Vec::new(),
             };
-            QuickViewTemplate { product, store_url }.into_response()
+            QuickViewTemplate { product }.into_response()
         }
     }
 }
+
+/// Display product recommendations carousel fragment (for HTMX).
+///
+/// Lazy-loaded from the product page so the initial render doesn't block
+/// on the recommendations API call. Returns an empty 200 response when there
+/// are no recommendations so HTMX swaps out the placeholder.
+#[instrument(skip(state, currency))]
+pub async fn recommendations(
+    State(state): State<AppState>,
+    Path(handle): Path<String>,
+    currency: BuyerCurrency,
+) -> Response {
+    let storefront = currency.storefront(state.storefront());
+
+    let product = match storefront.get_product_by_handle(&handle).await {
+        Ok(product) => product,
+        Err(e) => {
+            tracing::error!("Failed to fetch product for recommendations {handle}: {e}");
+            return ().into_response();
+        }
+    };
+
+    let products: Vec<ProductView> = match storefront
+        .get_product_recommendations(&product.id, Some(ProductRecommendationIntent::Related))
+        .await
+    {
+        Ok(products) => products
+            .iter()
+            .take(MAX_RECOMMENDATIONS)
+            .map(|p| ProductView::localized(p, &currency.formatter))
+            .collect(),
+        Err(e) => {
+            tracing::error!("Failed to fetch recommendations for {handle}: {e}");
+            Vec::new()
+        }
+    };
+
+    if products.is_empty() {
+        return ().into_response();
+    }
+
+    ProductRecommendationsTemplate { products }.into_response()
+}
+
+/// Apply the buyer's `component_{index}` variant selections to a bundle.
+///
+/// Unknown indices and variants that don't belong to the component are
+/// ignored, leaving the default selection in place.
+pub fn apply_bundle_selections(bundle: &mut ProductBundle, params: &[(String, String)]) {
+    for (key, variant_id) in params {
+        let Some(index) = key
+            .strip_prefix("component_")
+            .and_then(|index| index.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        if let Some(component) = bundle.components.get_mut(index) {
+            component.select_variant(variant_id);
+        }
+    }
+}
+
+/// Display the bundle builder fragment (for HTMX).
+///
+/// Lazy-loaded from the product page, and re-requested with the current
+/// `component_{index}` selections whenever a variant changes so the total
+/// updates. Returns an empty 200 response for products that aren't bundles.
+#[instrument(skip(state, currency, params))]
+pub async fn bundle(
+    State(state): State<AppState>,
+    Path(handle): Path<String>,
+    Query(params): Query<Vec<(String, String)>>,
+    currency: BuyerCurrency,
+) -> Response {
+    let mut bundle = match currency
+        .storefront(state.storefront())
+        .get_product_bundle(&handle)
+        .await
+    {
+        Ok(Some(bundle)) => bundle,
+        Ok(None) => return ().into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch bundle {handle}: {e}");
+            return ().into_response();
+        }
+    };
+
+    apply_bundle_selections(&mut bundle, &params);
+
+    let components = bundle
+        .components
+        .iter()
+        .enumerate()
+        .map(|(index, component)| BundleComponentView {
+            index,
+            quantity: component.quantity,
+            product: ProductView::localized(&component.product, &currency.formatter),
+            selected_variant_id: component.selected_variant.as_ref().map(|v| v.id.clone()),
+        })
+        .collect();
+
+    ProductBundleTemplate {
+        handle,
+        components,
+        total_price: bundle
+            .total_price()
+            .map(|price| currency.formatter.format(&price)),
+        available: bundle.available_for_sale(),
+    }
+    .into_response()
+}
+
+/// Subscribe to a back-in-stock notification for a variant (HTMX).
+///
+/// Only variants of this product that are currently sold out can be
+/// subscribed to. Always responds 200 with a status fragment so HTMX swaps
+/// the message into the form.
+#[instrument(skip(state, form), fields(variant_id = %form.variant_id))]
+pub async fn notify(
+    State(state): State<AppState>,
+    Path(handle): Path<String>,
+    Form(form): Form<NotifyForm>,
+) -> Response {
+    let Ok(email) = Email::parse(form.email.trim()) else {
+        return BackInStockStatusTemplate::error("Please enter a valid email address.")
+            .into_response();
+    };
+
+    let product = match state.storefront().get_product_by_handle(&handle).await {
+        Ok(product) => product,
+        Err(e) => {
+            tracing::error!("Failed to fetch product for back-in-stock {handle}: {e}");
+            return BackInStockStatusTemplate::error("Something went wrong. Please try again.")
+                .into_response();
+        }
+    };
+
+    let Some(variant) = product.variants.iter().find(|v| v.id == form.variant_id) else {
+        return BackInStockStatusTemplate::error("Please select a product option.").into_response();
+    };
+
+    if variant.available_for_sale {
+        return BackInStockStatusTemplate::error(
+            "Good news - this option is in stock and ready to order.",
+        )
+        .into_response();
+    }
+
+    match BackInStockRepository::new(state.pool())
+        .subscribe(&email, &variant.id, &handle)
+        .await
+    {
+        Ok(_) => BackInStockStatusTemplate {
+            success: true,
+            message: format!("We'll email {email} when it's back in stock."),
+        }
+        .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to save back-in-stock subscription for {handle}: {e}");
+            BackInStockStatusTemplate::error("Something went wrong. Please try again.")
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn variant(id: &str, title: &str, available_for_sale: bool) -> VariantView {
+        VariantView {
+            id: format!("gid://shopify/ProductVariant/{id}"),
+            title: title.to_string(),
+            price: "$24.00".to_string(),
+            compare_at_price: None,
+            image: None,
+            available_for_sale,
+            quantity_available: Some(if available_for_sale { 10 } else { 0 }),
+            shop_pay_installments: None,
+        }
+    }
+
+    fn product() -> ProductView {
+        ProductView {
+            handle: "tshirt".to_string(),
+            title: "T-Shirt".to_string(),
+            description: String::new(),
+            product_type: "Merch".to_string(),
+            price: "$24.00".to_string(),
+            compare_at_price: None,
+            featured_image: None,
+            images: Vec::new(),
+            variants: vec![
+                variant("111", "Small", true),
+                variant("222", "Large", false),
+            ],
+            ingredients: None,
+            directions: None,
+            warning: None,
+            promotes: Vec::new(),
+            benefits: None,
+            free_from: Vec::new(),
+            rating: None,
+            requires_selling_plan: false,
+            selling_plan_groups: Vec::new(),
+        }
+    }
+
+    fn show_page(selected_variant_id: Option<&str>) -> String {
+        ProductShowTemplate {
+            product: product(),
+            analytics: AnalyticsConfig::default(),
+            nonce: String::new(),
+            base_url: "https://example.com".to_string(),
+            breadcrumbs: Vec::new(),
+            store_url: "example.myshopify.com".to_string(),
+            selected_variant_id: selected_variant_id.map(String::from),
+        }
+        .render()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_variant_lookup() {
+        let product = product();
+        assert_eq!(product.variant(Some("222")).unwrap().title, "Large");
+        assert_eq!(
+            product
+                .variant(Some("gid://shopify/ProductVariant/222"))
+                .unwrap()
+                .title,
+            "Large"
+        );
+        // Unknown or missing IDs fall back to the first variant
+        assert_eq!(product.variant(Some("999")).unwrap().title, "Small");
+        assert_eq!(product.variant(None).unwrap().title, "Small");
+    }
+
+    #[test]
+    fn test_deep_link_to_sold_out_variant_shows_notify_form() {
+        let html = show_page(Some("222"));
+        assert!(html.contains("Notify Me"));
+        assert!(!html.contains(r#"id="add-to-cart-btn""#));
+        assert!(html.contains(r#"value="gid://shopify/ProductVariant/222""#));
+    }
+
+    #[test]
+    fn test_default_variant_shows_add_to_cart() {
+        let html = show_page(None);
+        assert!(html.contains(r#"id="add-to-cart-btn""#));
+        assert!(!html.contains("Notify Me"));
+    }
+
+    #[test]
+    fn test_variant_fragment_swaps_purchase_out_of_band() {
+        let html = ProductVariantTemplate {
+            product: product(),
+            selected_variant_id: Some("222".to_string()),
+            store_url: "example.myshopify.com".to_string(),
+        }
+        .render()
+        .unwrap();
+
+        assert!(html.contains(r#"hx-get="/products/tshirt/variant/111""#));
+        assert!(html.contains(r#"id="product-back-in-stock" hx-swap-oob="innerHTML""#));
+        assert!(html.contains("Notify Me"));
+        assert!(!html.contains(r#"id="add-to-cart-btn""#));
+    }
+
+    fn bundle() -> ProductBundle {
+        let money = serde_json::json!({ "amount": "5.00", "currency_code": "USD" });
+        let variant = |id: &str| {
+            serde_json::json!({
+                "id": id,
+                "title": id,
+                "available_for_sale": true,
+                "price": money,
+                "selected_options": []
+            })
+        };
+        let product = serde_json::json!({
+            "id": "gid://shopify/Product/1",
+            "handle": "component",
+            "title": "Component",
+            "description": "",
+            "description_html": "",
+            "available_for_sale": true,
+            "product_type": "",
+            "vendor": "",
+            "tags": [],
+            "price_range": { "min_variant_price": money, "max_variant_price": money },
+            "images": [],
+            "options": [],
+            "variants": [variant("small"), variant("large")],
+            "promotes": [],
+            "free_from": [],
+            "requires_selling_plan": false,
+            "selling_plan_groups": []
+        });
+        let component = serde_json::json!({
+            "quantity": 1,
+            "product": product,
+            "selected_variant": variant("small")
+        });
+        serde_json::from_value(serde_json::json!({
+            "product": product,
+            "variant_id": "gid://shopify/ProductVariant/0",
+            "components": [component, component]
+        }))
+        .unwrap()
+    }
+
+    fn selected(bundle: &ProductBundle) -> Vec<&str> {
+        bundle
+            .components
+            .iter()
+            .map(|c| c.selected_variant.as_ref().unwrap().id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_apply_bundle_selections_by_component_index() {
+        let mut bundle = bundle();
+        let params = vec![
+            ("handle".to_string(), "bundle".to_string()),
+            ("component_1".to_string(), "large".to_string()),
+        ];
+
+        apply_bundle_selections(&mut bundle, &params);
+
+        assert_eq!(selected(&bundle), vec!["small", "large"]);
+    }
+
+    #[test]
+    fn test_apply_bundle_selections_ignores_unknown_components_and_variants() {
+        let mut bundle = bundle();
+        let params = vec![
+            ("component_0".to_string(), "other".to_string()),
+            ("component_5".to_string(), "large".to_string()),
+            ("component_x".to_string(), "large".to_string()),
+        ];
+
+        apply_bundle_selections(&mut bundle, &params);
+
+        assert_eq!(selected(&bundle), vec!["small", "small"]);
+    }
+}
@@ -5,6 +5,7 @@ use askama_web::WebTemplate;
 use axum::{
     Router,
     extract::{Query, State},
+    http::header::{CACHE_CONTROL, RETRY_AFTER},
     response::IntoResponse,
     routing::get,
 };
@@ -70,6 +71,13 @@ pub struct SearchResultsTemplate {
     pub is_ready: bool,
 }
 
+/// Search autocomplete template (HTMX fragment).
+#[derive(Template, WebTemplate)]
+#[template(path = "partials/search_autocomplete.html")]
+pub struct SearchAutocompleteTemplate {
+    pub results: SearchResults,
+}
+
 /// Full search page template.
 #[derive(Template, WebTemplate)]
 #[template(path = "pages/search.html")]
@@ -113,6 +121,49 @@ pub async fn suggest(
     .into_response()
 }
 
+/// Number of products shown in the autocomplete dropdown.
+const AUTOCOMPLETE_LIMIT: usize = 8;
+
+/// Shortest query (in characters) that gets autocomplete suggestions.
+const AUTOCOMPLETE_MIN_CHARS: usize = 2;
+
+/// `Cache-Control` for autocomplete responses.
+///
+/// Suggestions only change when the index is rebuilt, so the browser can
+/// reuse them while the buyer edits and retypes a query.
+const AUTOCOMPLETE_CACHE_CONTROL: &str = "private, max-age=60";
+
+/// Search autocomplete endpoint (HTMX).
+///
+/// Returns the top product matches with a link to the full results page.
+/// Queries shorter than [`AUTOCOMPLETE_MIN_CHARS`] get an empty fragment.
+/// While the index is still building, returns an empty fragment with
+/// `Retry-After: 5`.
+#[instrument(skip(state))]
+pub async fn autocomplete(
+    State(state): State<AppState>,
+    Query(query): Query<SuggestQuery>,
+) -> impl IntoResponse {
+    if query.q.trim().chars().count() < AUTOCOMPLETE_MIN_CHARS {
+        return ([(CACHE_CONTROL, AUTOCOMPLETE_CACHE_CONTROL)], "").into_response();
+    }
+
+    if !state.search().is_ready() {
+        return ([(RETRY_AFTER, "5")], "").into_response();
+    }
+
+    let results = state
+        .search()
+        .autocomplete(&query.q, AUTOCOMPLETE_LIMIT)
+        .unwrap_or_default();
+
+    (
+        [(CACHE_CONTROL, AUTOCOMPLETE_CACHE_CONTROL)],
+        SearchAutocompleteTemplate { results },
+    )
+        .into_response()
+}
+
 /// Full search page.
 #[instrument(skip(state, nonce))]
 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
@@ -155,4 +206,5 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(search_page))
         .route("/suggest", get(suggest))
+        .route("/autocomplete", get(autocomplete))
 }
@@ -14,7 +14,7 @@ use serde::Deserialize;
 use tower_sessions::Session;
 
 use crate::models::session_keys;
-use crate::shopify::CustomerAccessToken;
+use crate::shopify::{CustomerAccessToken, pkce_challenge};
 use crate::state::AppState;
 
 /// Query parameters from Shopify OAuth callback.
@@ -45,16 +45,17 @@ fn generate_random_string(length: usize) -> String {
 
 /// Initiate Shopify Customer Account OAuth login.
 ///
-/// Generates state and nonce parameters, stores them in the session,
-/// and redirects to Shopify's authorization page.
+/// Generates state, nonce and PKCE verifier parameters, stores them in the
+/// session, and redirects to Shopify's authorization page.
 ///
 /// # Route
 ///
 /// `GET /auth/shopify/login`
 pub async fn login(State(state): State<AppState>, session: Session) -> Response {
-    // Generate CSRF state and OpenID nonce
+    // Generate CSRF state, OpenID nonce and PKCE code verifier
     let oauth_state = generate_random_string(32);
     let nonce = generate_random_string(32);
+    let code_verifier = generate_random_string(64);
 
     // Store in session for validation on callback
     if let Err(e) = session
@@ -73,13 +74,24 @@ pub async fn login(State(state): State<AppState>, session: Session) -> Response
         return Redirect::to("/auth/login?error=session").into_response();
     }
 
+    if let Err(e) = session
+        .insert(session_keys::SHOPIFY_OAUTH_CODE_VERIFIER, &code_verifier)
+        .await
+    {
+        tracing::error!("Failed to store PKCE code verifier in session: {}", e);
+        return Redirect::to("/auth/login?error=session").into_response();
+    }
+
     // Build the redirect URI
     let redirect_uri = format!("{}/auth/shopify/callback", state.config().base_url);
 
     // Generate and redirect to authorization URL
-    let auth_url = state
-        .customer()
-        .authorization_url(&redirect_uri, &oauth_state, &nonce);
+    let auth_url = state.customer().authorization_url(
+        &redirect_uri,
+        &oauth_state,
+        &nonce,
+        &pkce_challenge(&code_verifier),
+    );
 
     Redirect::to(&auth_url).into_response()
 }
@@ -135,11 +147,26 @@ pub async fn callback(
         .remove::<String>(session_keys::SHOPIFY_OAUTH_NONCE)
         .await;
 
+    // Take the PKCE verifier (one-time use)
+    let Some(code_verifier) = session
+        .remove::<String>(session_keys::SHOPIFY_OAUTH_CODE_VERIFIER)
+        .await
+        .ok()
+        .flatten()
+    else {
+        tracing::warn!("Shopify OAuth callback missing PKCE code verifier");
+        return Redirect::to("/auth/login?error=invalid_state").into_response();
+    };
+
     // Build redirect URI (must match the one used in authorization request)
     let redirect_uri = format!("{}/auth/shopify/callback", state.config().base_url);
 
     // Exchange code for tokens
-    let token = match state.customer().exchange_code(&code, &redirect_uri).await {
+    let token = match state
+        .customer()
+        .exchange_code(&code, &redirect_uri, &code_verifier)
+        .await
+    {
         Ok(token) => token,
         Err(e) => {
             tracing::error!("Failed to exchange Shopify OAuth code: {}", e);
@@ -0,0 +1,197 @@
+//! Shopify webhook handlers.
+//!
+//! Webhook requests are verified against `SHOPIFY_WEBHOOK_SECRET` using the
+//! `X-Shopify-Hmac-Sha256` header (base64-encoded HMAC-SHA256 of the raw body).
+//! Handlers acknowledge quickly and do any slow work in a background task,
+//! since Shopify retries webhooks that take longer than a few seconds.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+};
+use naked_pineapple_core::verify_webhook_signature;
+use secrecy::ExposeSecret;
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::services::{BackInStockService, LogNotifier};
+use crate::shopify::{COLLECTION_LISTINGS_TAG, PRODUCT_LISTINGS_TAG, collection_tag, product_tag};
+use crate::state::AppState;
+
+/// Header carrying the webhook signature.
+const HMAC_HEADER: &str = "x-shopify-hmac-sha256";
+
+/// Payload of the `inventory_levels/update` webhook.
+#[derive(Debug, Deserialize)]
+pub struct InventoryLevelPayload {
+    pub inventory_item_id: i64,
+    pub location_id: i64,
+    /// Available quantity at the location (null when untracked).
+    pub available: Option<i64>,
+}
+
+/// Payload fields shared by the product and collection webhooks.
+///
+/// `*/delete` payloads only carry the ID.
+#[derive(Debug, Deserialize)]
+pub struct ResourcePayload {
+    pub id: i64,
+    pub handle: Option<String>,
+}
+
+/// Check the request signature against the configured webhook secret.
+fn is_authentic(state: &AppState, headers: &HeaderMap, body: &[u8]) -> bool {
+    let Some(secret) = state.config().shopify.webhook_secret.as_ref() else {
+        tracing::warn!("Rejecting webhook: SHOPIFY_WEBHOOK_SECRET is not configured");
+        return false;
+    };
+    let Some(signature) = headers.get(HMAC_HEADER).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+
+    verify_webhook_signature(secret.expose_secret(), body, signature)
+}
+
+/// Handle `inventory_levels/update` webhooks.
+///
+/// POST /webhooks/inventory-levels/update
+///
+/// When stock increases, checks pending back-in-stock subscriptions and
+/// notifies subscribers of variants that are available again.
+#[instrument(skip(state, headers, body))]
+pub async fn inventory_levels_update(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    if !is_authentic(&state, &headers, &body) {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let payload: InventoryLevelPayload = match serde_json::from_slice(&body) {
+        Ok(payload) => payload,
+        Err(e) => {
+            tracing::warn!(error = %e, "Invalid inventory_levels/update payload");
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+
+    // Nothing can have come back in stock if the level isn't positive
+    if payload.available.is_some_and(|qty| qty <= 0) {
+        return StatusCode::OK;
+    }
+
+    tracing::debug!(
+        inventory_item_id = payload.inventory_item_id,
+        location_id = payload.location_id,
+        "Inventory level increased, checking back-in-stock subscriptions"
+    );
+
+    let service = BackInStockService::new(
+        state.pool().clone(),
+        state.storefront().clone(),
+        state.config().base_url.clone(),
+        LogNotifier,
+    );
+
+    tokio::spawn(async move {
+        match service.process_restocks().await {
+            Ok(0) => {}
+            Ok(sent) => tracing::info!(sent, "Sent back-in-stock notifications"),
+            Err(e) => tracing::error!(error = %e, "Failed to process back-in-stock notifications"),
+        }
+    });
+
+    StatusCode::OK
+}
+
+/// Handle `products/create`, `products/update` and `products/delete` webhooks.
+///
+/// POST /webhooks/products/{create,update,delete}
+///
+/// Evicts cached responses showing the product, plus product listings.
+#[instrument(skip(state, headers, body))]
+pub async fn product_changed(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    if !is_authentic(&state, &headers, &body) {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let payload: ResourcePayload = match serde_json::from_slice(&body) {
+        Ok(payload) => payload,
+        Err(e) => {
+            tracing::warn!(error = %e, "Invalid product webhook payload");
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+
+    let storefront = state.storefront();
+    storefront.invalidate_by_tag(&format!("gid://shopify/Product/{}", payload.id));
+    if let Some(handle) = &payload.handle {
+        storefront.invalidate_by_tag(&product_tag(handle));
+    }
+    storefront.invalidate_by_tag(PRODUCT_LISTINGS_TAG);
+
+    StatusCode::OK
+}
+
+/// Handle `collections/create`, `collections/update` and `collections/delete` webhooks.
+///
+/// POST /webhooks/collections/{create,update,delete}
+///
+/// Evicts cached responses showing the collection, plus collection listings.
+#[instrument(skip(state, headers, body))]
+pub async fn collection_changed(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    if !is_authentic(&state, &headers, &body) {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let payload: ResourcePayload = match serde_json::from_slice(&body) {
+        Ok(payload) => payload,
+        Err(e) => {
+            tracing::warn!(error = %e, "Invalid collection webhook payload");
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+
+    let storefront = state.storefront();
+    storefront.invalidate_by_tag(&format!("gid://shopify/Collection/{}", payload.id));
+    if let Some(handle) = &payload.handle {
+        storefront.invalidate_by_tag(&collection_tag(handle));
+    }
+    storefront.invalidate_by_tag(COLLECTION_LISTINGS_TAG);
+
+    StatusCode::OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_payload_update() {
+        let payload: ResourcePayload =
+            serde_json::from_str(r#"{"id":632910392,"handle":"ipod-nano","title":"IPod Nano"}"#)
+                .expect("valid payload");
+
+        assert_eq!(payload.id, 632_910_392);
+        assert_eq!(payload.handle.as_deref(), Some("ipod-nano"));
+    }
+
+    #[test]
+    fn test_resource_payload_delete_has_no_handle() {
+        let payload: ResourcePayload =
+            serde_json::from_str(r#"{"id":632910392}"#).expect("valid payload");
+
+        assert_eq!(payload.id, 632_910_392);
+        assert!(payload.handle.is_none());
+    }
+}
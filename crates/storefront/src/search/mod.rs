@@ -13,13 +13,14 @@ use std::sync::{Arc, RwLock};
 
 use std::ops::Bound;
 
-use tantivy::collector::TopDocs;
+use tantivy::collector::{Count, TopDocs};
 use tantivy::query::{
     BooleanQuery, FuzzyTermQuery, Occur, Query, RangeQuery, RegexQuery, TermQuery,
 };
 use tantivy::schema::{
     Field, IndexRecordOption, STORED, Schema, TextFieldIndexing, TextOptions, Value,
 };
+use tantivy::snippet::SnippetGenerator;
 use tantivy::{Index, IndexReader, ReloadPolicy, Term};
 use tracing::instrument;
 
@@ -67,6 +68,11 @@ pub struct SearchResult {
     pub price_cents: Option<u64>,
     pub available: bool,
     pub score: f32,
+    /// Excerpt of the description with matched terms wrapped in `<b>`.
+    ///
+    /// Already HTML-escaped. Only set on full search page results that
+    /// matched in the description.
+    pub snippet: Option<String>,
 }
 
 /// Schema field handles for the search index.
@@ -89,7 +95,6 @@ pub struct SearchFields {
 
 /// Inner index state (once built).
 struct ReadyIndex {
-    #[allow(dead_code)]
     index: Index,
     reader: IndexReader,
     fields: SearchFields,
@@ -316,6 +321,8 @@ impl SearchIndex {
             }
         };
 
+        let results = Self::add_snippets(ready, &searcher, &query_str, results);
+
         // Count totals for facets
         let (total_count, in_stock_count, out_of_stock_count, min_price, max_price) =
             Self::compute_facets(&searcher, &ready.fields, &query_str)?;
@@ -373,6 +380,51 @@ impl SearchIndex {
         Box::new(BooleanQuery::new(must_clauses))
     }
 
+    /// Highlight query terms in each result's description.
+    ///
+    /// Query terms go through the description field's tokenizer, so stemmed
+    /// matches ("serums" for "serum") are highlighted too. Results whose
+    /// description doesn't contain a query term get no snippet.
+    fn add_snippets(
+        ready: &ReadyIndex,
+        searcher: &tantivy::Searcher,
+        query_str: &str,
+        mut results: Vec<SearchResult>,
+    ) -> Vec<SearchResult> {
+        let field = ready.fields.description_text;
+        let Ok(mut tokenizer) = ready.index.tokenizer_for_field(field) else {
+            return results;
+        };
+
+        let mut terms: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        tokenizer.token_stream(query_str).process(&mut |token| {
+            let term = Term::from_field_text(field, &token.text);
+            terms.push((
+                Occur::Should,
+                Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
+            ));
+        });
+        if terms.is_empty() {
+            return results;
+        }
+
+        let generator = match SnippetGenerator::create(searcher, &BooleanQuery::new(terms), field) {
+            Ok(generator) => generator,
+            Err(e) => {
+                tracing::warn!("Failed to create snippet generator: {e}");
+                return results;
+            }
+        };
+
+        for result in &mut results {
+            let snippet = generator.snippet(&result.description);
+            if !snippet.is_empty() {
+                result.snippet = Some(snippet.to_html());
+            }
+        }
+        results
+    }
+
     /// Collect search results from top docs.
     fn collect_results(
         searcher: &tantivy::Searcher,
@@ -501,6 +553,51 @@ impl SearchIndex {
 
         let searcher = ready.reader.searcher();
 
+        let query = Self::suggestion_query(&ready.fields, &query_str);
+
+        // Search for more results than needed to allow grouping by type
+        let top_docs = searcher
+            .search(&query, &TopDocs::with_limit(limit * 4))
+            .map_err(|e| SearchError::Query(format!("Search failed: {e}")))?;
+
+        // Collect and group results
+        let mut products = Vec::new();
+        let mut collections = Vec::new();
+        let mut pages = Vec::new();
+        let mut articles = Vec::new();
+
+        for (score, doc_address) in top_docs {
+            let doc = searcher
+                .doc::<tantivy::TantivyDocument>(doc_address)
+                .map_err(|e| SearchError::Query(format!("Failed to retrieve doc: {e}")))?;
+
+            let result = Self::doc_to_result(&ready.fields, &doc, score)?;
+
+            match result.doc_type {
+                DocType::Product if products.len() < limit => products.push(result),
+                DocType::Collection if collections.len() < limit => collections.push(result),
+                DocType::Page if pages.len() < limit => pages.push(result),
+                DocType::Article if articles.len() < limit => articles.push(result),
+                _ => {}
+            }
+        }
+
+        Ok(SearchResults {
+            products,
+            collections,
+            pages,
+            articles,
+            query: query_str,
+            total_count: 0,
+            in_stock_count: 0,
+            out_of_stock_count: 0,
+            min_price_cents: 0,
+            max_price_cents: 0,
+        })
+    }
+
+    /// Build the prefix/fuzzy query used for as-you-type suggestions.
+    fn suggestion_query(fields: &SearchFields, query_str: &str) -> BooleanQuery {
         // Build a boolean query combining prefix and fuzzy matches
         let mut subqueries: Vec<(Occur, Box<dyn Query>)> = Vec::new();
 
@@ -521,19 +618,18 @@ impl SearchIndex {
                     .collect();
                 let prefix_pattern = format!("{escaped}.*");
                 if let Ok(regex_query) =
-                    RegexQuery::from_pattern(&prefix_pattern, ready.fields.title_text)
+                    RegexQuery::from_pattern(&prefix_pattern, fields.title_text)
                 {
                     subqueries.push((Occur::Should, Box::new(regex_query)));
                 }
                 // Also try prefix on tags
-                if let Ok(regex_query) =
-                    RegexQuery::from_pattern(&prefix_pattern, ready.fields.tags_text)
+                if let Ok(regex_query) = RegexQuery::from_pattern(&prefix_pattern, fields.tags_text)
                 {
                     subqueries.push((Occur::Should, Box::new(regex_query)));
                 }
             } else {
                 // Title exact match
-                let title_term = Term::from_field_text(ready.fields.title_text, term);
+                let title_term = Term::from_field_text(fields.title_text, term);
                 subqueries.push((
                     Occur::Should,
                     Box::new(TermQuery::new(title_term.clone(), IndexRecordOption::Basic)),
@@ -544,12 +640,12 @@ impl SearchIndex {
                 subqueries.push((Occur::Should, Box::new(fuzzy_title)));
 
                 // Description fuzzy match
-                let desc_term = Term::from_field_text(ready.fields.description_text, term);
+                let desc_term = Term::from_field_text(fields.description_text, term);
                 let fuzzy_desc = FuzzyTermQuery::new(desc_term, 1, true);
                 subqueries.push((Occur::Should, Box::new(fuzzy_desc)));
 
                 // Tags exact match
-                let tags_term = Term::from_field_text(ready.fields.tags_text, term);
+                let tags_term = Term::from_field_text(fields.tags_text, term);
                 subqueries.push((
                     Occur::Should,
                     Box::new(TermQuery::new(tags_term, IndexRecordOption::Basic)),
@@ -557,46 +653,67 @@ impl SearchIndex {
             }
         }
 
-        let query = BooleanQuery::new(subqueries);
+        BooleanQuery::new(subqueries)
+    }
 
-        // Search for more results than needed to allow grouping by type
-        let top_docs = searcher
-            .search(&query, &TopDocs::with_limit(limit * 4))
-            .map_err(|e| SearchError::Query(format!("Search failed: {e}")))?;
+    /// Search products for autocomplete, returning the top matches and the
+    /// total number of matching products.
+    ///
+    /// Uses the same prefix/fuzzy matching as [`Self::search`], restricted to
+    /// products. Runs entirely against the in-memory index, so it is cheap
+    /// enough to call on every keystroke.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index lock is poisoned or the search query fails.
+    #[instrument(skip(self))]
+    // Allow: The read guard must outlive `ready`; see `search`.
+    #[allow(clippy::significant_drop_tightening)]
+    pub fn autocomplete(
+        &self,
+        query_str: &str,
+        limit: usize,
+    ) -> Result<SearchResults, SearchError> {
+        let query_str = query_str.trim().to_lowercase();
+        if query_str.is_empty() {
+            return Ok(SearchResults::default());
+        }
 
-        // Collect and group results
-        let mut products = Vec::new();
-        let mut collections = Vec::new();
-        let mut pages = Vec::new();
-        let mut articles = Vec::new();
+        let guard = self
+            .inner
+            .read()
+            .map_err(|_| SearchError::Index("Lock poisoned".to_string()))?;
 
-        for (score, doc_address) in top_docs {
-            let doc = searcher
-                .doc::<tantivy::TantivyDocument>(doc_address)
-                .map_err(|e| SearchError::Query(format!("Failed to retrieve doc: {e}")))?;
+        let Some(ready) = guard.as_ref() else {
+            return Ok(SearchResults {
+                query: query_str,
+                ..Default::default()
+            });
+        };
 
-            let result = Self::doc_to_result(&ready.fields, &doc, score)?;
+        let searcher = ready.reader.searcher();
 
-            match result.doc_type {
-                DocType::Product if products.len() < limit => products.push(result),
-                DocType::Collection if collections.len() < limit => collections.push(result),
-                DocType::Page if pages.len() < limit => pages.push(result),
-                DocType::Article if articles.len() < limit => articles.push(result),
-                _ => {}
-            }
-        }
+        let product_term = Term::from_field_text(ready.fields.doc_type, "product");
+        let query = BooleanQuery::new(vec![
+            (
+                Occur::Must,
+                Box::new(TermQuery::new(product_term, IndexRecordOption::Basic)),
+            ),
+            (
+                Occur::Must,
+                Box::new(Self::suggestion_query(&ready.fields, &query_str)),
+            ),
+        ]);
+
+        let (top_docs, total_count) = searcher
+            .search(&query, &(TopDocs::with_limit(limit), Count))
+            .map_err(|e| SearchError::Query(format!("Search failed: {e}")))?;
 
         Ok(SearchResults {
-            products,
-            collections,
-            pages,
-            articles,
+            products: Self::collect_results(&searcher, &ready.fields, top_docs)?,
             query: query_str,
-            total_count: 0,
-            in_stock_count: 0,
-            out_of_stock_count: 0,
-            min_price_cents: 0,
-            max_price_cents: 0,
+            total_count,
+            ..Default::default()
         })
     }
 
@@ -639,6 +756,7 @@ impl SearchIndex {
             price_cents,
             available,
             score,
+            snippet: None,
         })
     }
 
@@ -0,0 +1,261 @@
+//! A/B testing for storefront templates.
+//!
+//! Experiments are configured in `content/experiments.toml`:
+//!
+//! ```toml
+//! [[experiments]]
+//! name = "add_to_cart_text"
+//! variants = ["control", "buy_now"]
+//! traffic_split = [0.5, 0.5]
+//! active = true
+//! ```
+//!
+//! Each session is assigned a variant by hashing the session ID with the
+//! experiment name, so the same session always lands in the same bucket.
+//! The `ab_test_middleware` caches assignments in the session and records
+//! new ones in `ab_test_assignments`; templates read them with the
+//! `ab_test` filter:
+//!
+//! ```html
+//! {% if "add_to_cart_text"|ab_test == "buy_now" %}Buy Now{% else %}Add to Cart{% endif %}
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// Allowed rounding error when checking that a traffic split sums to 1.0.
+const SPLIT_TOLERANCE: f32 = 0.001;
+
+/// A single experiment.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbTest {
+    /// Unique experiment name, used in templates.
+    pub name: String,
+    /// Variant names. The first is the control, shown while inactive.
+    pub variants: Vec<String>,
+    /// Share of traffic for each variant, in the same order (sums to 1.0).
+    pub traffic_split: Vec<f32>,
+    /// Whether sessions are currently being assigned to variants.
+    pub active: bool,
+}
+
+impl AbTest {
+    /// The control variant (first in the list).
+    #[must_use]
+    pub fn control(&self) -> &str {
+        self.variants.first().map_or("", String::as_str)
+    }
+
+    /// Deterministically pick a variant for a session.
+    ///
+    /// Inactive experiments always return the control variant.
+    #[must_use]
+    pub fn variant_for(&self, session_id: &str) -> &str {
+        if !self.active {
+            return self.control();
+        }
+
+        let bucket = bucket(&self.name, session_id);
+        let mut cumulative = 0.0;
+        for (variant, share) in self.variants.iter().zip(&self.traffic_split) {
+            cumulative += share;
+            if bucket < cumulative {
+                return variant;
+            }
+        }
+
+        // Rounding can leave the cumulative split just under 1.0
+        self.variants.last().map_or("", String::as_str)
+    }
+
+    /// Check the variants and traffic split are consistent.
+    fn validate(&self) -> Result<(), AbTestError> {
+        let invalid = |reason: &str| AbTestError::Invalid {
+            experiment: self.name.clone(),
+            reason: reason.to_string(),
+        };
+
+        if self.variants.is_empty() {
+            return Err(invalid("no variants"));
+        }
+        if self.variants.len() != self.traffic_split.len() {
+            return Err(invalid("traffic_split must have one entry per variant"));
+        }
+        if self.traffic_split.iter().any(|share| *share < 0.0) {
+            return Err(invalid("traffic_split entries must not be negative"));
+        }
+        let total: f32 = self.traffic_split.iter().sum();
+        if (total - 1.0).abs() > SPLIT_TOLERANCE {
+            return Err(invalid("traffic_split must sum to 1.0"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Map a session to a point in `[0, 1)` for an experiment.
+fn bucket(experiment: &str, session_id: &str) -> f32 {
+    let digest = Sha256::digest(format!("{experiment}:{session_id}").as_bytes());
+    let value = digest
+        .as_slice()
+        .first_chunk::<2>()
+        .map_or(0, |bytes| u16::from_be_bytes(*bytes));
+    f32::from(value) / 65_536.0
+}
+
+/// Top-level layout of `experiments.toml`.
+#[derive(Debug, Deserialize)]
+struct ExperimentsFile {
+    #[serde(default)]
+    experiments: Vec<AbTest>,
+}
+
+/// Configured experiments, keyed by name.
+#[derive(Debug, Clone, Default)]
+pub struct AbTestService {
+    experiments: Arc<HashMap<String, AbTest>>,
+}
+
+impl AbTestService {
+    /// Load experiments from a TOML file.
+    ///
+    /// A missing file means no experiments are configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or parsed, or an
+    /// experiment is misconfigured.
+    pub fn load(path: &Path) -> Result<Self, AbTestError> {
+        if !path.exists() {
+            tracing::info!("No experiments file at {:?}", path);
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| AbTestError::Io(e.to_string()))?;
+        let file: ExperimentsFile =
+            toml::from_str(&content).map_err(|e| AbTestError::Parse(e.to_string()))?;
+
+        let mut experiments = HashMap::new();
+        for experiment in file.experiments {
+            experiment.validate()?;
+            tracing::info!(
+                active = experiment.active,
+                "Loaded experiment: {}",
+                experiment.name
+            );
+            if let Some(duplicate) = experiments.insert(experiment.name.clone(), experiment) {
+                return Err(AbTestError::Invalid {
+                    experiment: duplicate.name,
+                    reason: "defined more than once".to_string(),
+                });
+            }
+        }
+
+        Ok(Self {
+            experiments: Arc::new(experiments),
+        })
+    }
+
+    /// Get an experiment by name.
+    #[must_use]
+    pub fn get(&self, experiment: &str) -> Option<&AbTest> {
+        self.experiments.get(experiment)
+    }
+
+    /// All configured experiments.
+    pub fn experiments(&self) -> impl Iterator<Item = &AbTest> {
+        self.experiments.values()
+    }
+
+    /// Deterministically assign a session to a variant of an experiment.
+    ///
+    /// Returns an empty string for unknown experiments.
+    #[must_use]
+    pub fn assign_variant(&self, experiment: &str, session_id: &str) -> &str {
+        self.get(experiment)
+            .map_or("", |test| test.variant_for(session_id))
+    }
+}
+
+/// Experiment configuration errors.
+#[derive(Debug, thiserror::Error)]
+pub enum AbTestError {
+    #[error("IO error: {0}")]
+    Io(String),
+    #[error("Parse error: {0}")]
+    Parse(String),
+    #[error("Invalid experiment {experiment}: {reason}")]
+    Invalid { experiment: String, reason: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn experiment(traffic_split: Vec<f32>, active: bool) -> AbTest {
+        AbTest {
+            name: "add_to_cart_text".to_string(),
+            variants: vec!["control".to_string(), "buy_now".to_string()],
+            traffic_split,
+            active,
+        }
+    }
+
+    #[test]
+    fn test_bucket_is_deterministic_and_in_range() {
+        let first = bucket("add_to_cart_text", "session-1");
+        assert!((0.0..1.0).contains(&first));
+        assert!((first - bucket("add_to_cart_text", "session-1")).abs() < f32::EPSILON);
+
+        let experiment = experiment(vec![0.5, 0.5], true);
+        assert_eq!(
+            experiment.variant_for("session-1"),
+            experiment.variant_for("session-1")
+        );
+    }
+
+    #[test]
+    fn test_traffic_split_is_respected() {
+        let all_control = experiment(vec![1.0, 0.0], true);
+        let all_variant = experiment(vec![0.0, 1.0], true);
+        let even = experiment(vec![0.5, 0.5], true);
+
+        let mut buy_now = 0;
+        for i in 0..1000 {
+            let session_id = format!("session-{i}");
+            assert_eq!(all_control.variant_for(&session_id), "control");
+            assert_eq!(all_variant.variant_for(&session_id), "buy_now");
+            if even.variant_for(&session_id) == "buy_now" {
+                buy_now += 1;
+            }
+        }
+        assert!((400..600).contains(&buy_now), "uneven split: {buy_now}");
+    }
+
+    #[test]
+    fn test_inactive_experiment_uses_control() {
+        let experiment = experiment(vec![0.0, 1.0], false);
+        assert_eq!(experiment.variant_for("session-1"), "control");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(experiment(vec![0.5, 0.5], true).validate().is_ok());
+        assert!(experiment(vec![0.3333, 0.6667], true).validate().is_ok());
+
+        for traffic_split in [vec![1.0], vec![0.5, 0.6], vec![1.5, -0.5]] {
+            assert!(matches!(
+                experiment(traffic_split, true).validate(),
+                Err(AbTestError::Invalid { .. })
+            ));
+        }
+
+        let mut no_variants = experiment(Vec::new(), true);
+        no_variants.variants.clear();
+        assert!(no_variants.validate().is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Back-in-stock notification service.
+//!
+//! When Shopify reports an inventory change, pending subscriptions are checked
+//! against live variant availability from the Storefront API. Subscriptions
+//! for variants that are available again are claimed (marked as notified)
+//! before their subscribers are notified through a [`RestockNotifier`].
+
+use std::future::Future;
+
+use sqlx::PgPool;
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::db::RepositoryError;
+use crate::db::back_in_stock::BackInStockRepository;
+use crate::models::BackInStockSubscription;
+use crate::shopify::{ShopifyError, StorefrontClient};
+
+/// Errors that can occur while processing back-in-stock notifications.
+#[derive(Debug, Error)]
+pub enum BackInStockError {
+    /// Database error.
+    #[error("repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    /// Shopify API error while checking availability.
+    #[error("shopify error: {0}")]
+    Shopify(#[from] ShopifyError),
+
+    /// The notification could not be delivered.
+    #[error("notification failed: {0}")]
+    Notify(String),
+}
+
+/// Delivers restock notifications to subscribers.
+///
+/// Implement this for a real email provider; [`LogNotifier`] is the default.
+pub trait RestockNotifier: Send + Sync {
+    /// Notify a subscriber that their variant is back in stock.
+    ///
+    /// `product_url` is an absolute link to the product page.
+    fn notify(
+        &self,
+        subscription: &BackInStockSubscription,
+        product_url: &str,
+    ) -> impl Future<Output = Result<(), BackInStockError>> + Send;
+}
+
+/// Notifier that logs notifications instead of sending email.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogNotifier;
+
+impl RestockNotifier for LogNotifier {
+    async fn notify(
+        &self,
+        subscription: &BackInStockSubscription,
+        product_url: &str,
+    ) -> Result<(), BackInStockError> {
+        tracing::info!(
+            email = %subscription.email,
+            variant_id = %subscription.variant_id,
+            product_url = %product_url,
+            "Back-in-stock notification"
+        );
+        Ok(())
+    }
+}
+
+/// Sends notifications for subscribed variants that have been restocked.
+pub struct BackInStockService<N = LogNotifier> {
+    pool: PgPool,
+    storefront: StorefrontClient,
+    base_url: String,
+    notifier: N,
+}
+
+impl<N: RestockNotifier> BackInStockService<N> {
+    /// Create a new back-in-stock service.
+    ///
+    /// `base_url` is the public storefront URL used to build product links.
+    #[must_use]
+    pub const fn new(
+        pool: PgPool,
+        storefront: StorefrontClient,
+        base_url: String,
+        notifier: N,
+    ) -> Self {
+        Self {
+            pool,
+            storefront,
+            base_url,
+            notifier,
+        }
+    }
+
+    /// Notify subscribers of every pending variant that is now available.
+    ///
+    /// Returns the number of notifications sent. Subscriptions are claimed
+    /// before sending, so overlapping runs don't notify anyone twice. A
+    /// failed notification is logged and released back to pending so it is
+    /// retried on the next run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database or Shopify API request fails.
+    #[instrument(skip(self))]
+    pub async fn process_restocks(&self) -> Result<usize, BackInStockError> {
+        let repo = BackInStockRepository::new(&self.pool);
+
+        let pending = repo.pending_variant_ids().await?;
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let available = self.storefront.get_available_variant_ids(&pending).await?;
+        if available.is_empty() {
+            return Ok(0);
+        }
+
+        let subscriptions = repo.claim_pending_for_variants(&available).await?;
+        let base_url = self.base_url.trim_end_matches('/');
+
+        let mut sent = 0;
+        for subscription in &subscriptions {
+            let product_url = format!("{base_url}/products/{}", subscription.product_handle);
+
+            if let Err(e) = self.notifier.notify(subscription, &product_url).await {
+                tracing::warn!(
+                    subscription_id = %subscription.id,
+                    error = %e,
+                    "Failed to send back-in-stock notification"
+                );
+                if let Err(e) = repo.release(subscription.id).await {
+                    tracing::error!(
+                        subscription_id = %subscription.id,
+                        error = %e,
+                        "Failed to release back-in-stock subscription for retry"
+                    );
+                }
+                continue;
+            }
+
+            sent += 1;
+        }
+
+        Ok(sent)
+    }
+}
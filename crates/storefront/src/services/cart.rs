@@ -0,0 +1,428 @@
+//! Persistent cart service.
+//!
+//! Each session's cart lives in `storefront.carts`, keyed by session ID, next
+//! to the Shopify cart it mirrors and a snapshot of that cart's lines. Shopify
+//! expires abandoned carts; when an operation finds the Shopify cart gone, a
+//! new one is created from the snapshot (with the operation applied) and the
+//! session is moved over to it.
+//!
+//! Operations on one session's cart run one at a time, so concurrent add
+//! requests (e.g. a double-clicked button on a new session) land on a single
+//! Shopify cart instead of each creating their own.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use sqlx::PgPool;
+use thiserror::Error;
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+use tracing::instrument;
+
+use crate::db::RepositoryError;
+use crate::db::carts::CartRepository;
+use crate::models::{StoredCart, StoredCartLine};
+use crate::shopify::types::{Cart, CartLineInput, CartLineUpdateInput};
+use crate::shopify::{ShopifyError, StorefrontClient};
+
+/// Errors that can occur during cart operations.
+#[derive(Debug, Error)]
+pub enum CartError {
+    /// Database error.
+    #[error("repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    /// Shopify API error.
+    #[error("shopify error: {0}")]
+    Shopify(#[from] ShopifyError),
+}
+
+/// Keeps carts in sync between the database and the Shopify Storefront API.
+///
+/// Cheap to clone; clones share the per-session locks.
+#[derive(Clone)]
+pub struct CartService {
+    pool: PgPool,
+    storefront: StorefrontClient,
+    locks: SessionLocks,
+}
+
+impl CartService {
+    /// Create a new cart service.
+    #[must_use]
+    pub fn new(pool: PgPool, storefront: StorefrontClient) -> Self {
+        Self {
+            pool,
+            storefront,
+            locks: SessionLocks::default(),
+        }
+    }
+
+    /// Get a cart service whose new carts are priced for the given country.
+    ///
+    /// Shares the database pool and session locks with `self`. Passing `None`
+    /// creates carts in the shop's base currency.
+    #[must_use]
+    pub fn with_country(&self, country: Option<&str>) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            storefront: self.storefront.with_country(country),
+            locks: self.locks.clone(),
+        }
+    }
+
+    /// Get a session's cart.
+    ///
+    /// Returns `None` if the session has no cart.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database or Shopify API request fails.
+    #[instrument(skip(self))]
+    pub async fn get(&self, session_id: &str) -> Result<Option<Cart>, CartError> {
+        let _guard = self.locks.lock(session_id).await;
+        let Some(stored) = self.stored(session_id).await? else {
+            return Ok(None);
+        };
+
+        match self.storefront.get_cart(&stored.shopify_cart_id).await {
+            Ok(cart) => Ok(Some(cart)),
+            Err(ShopifyError::NotFound(_)) => {
+                let lines = line_inputs(&stored.line_items);
+                self.replace_expired(session_id, &stored, lines)
+                    .await
+                    .map(Some)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Add lines to a session's cart, creating the cart if needed.
+    ///
+    /// The session must already be saved, since its cart row references it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database or Shopify API request fails.
+    #[instrument(skip(self, lines))]
+    pub async fn add(
+        &self,
+        session_id: &str,
+        lines: Vec<CartLineInput>,
+    ) -> Result<Cart, CartError> {
+        let _guard = self.locks.lock(session_id).await;
+        let Some(stored) = self.stored(session_id).await? else {
+            let cart = self.storefront.create_cart(Some(lines), None).await?;
+            self.save(session_id, &cart).await?;
+            return Ok(cart);
+        };
+
+        match self
+            .storefront
+            .add_to_cart(&stored.shopify_cart_id, lines.clone())
+            .await
+        {
+            Ok(cart) => {
+                self.save(session_id, &cart).await?;
+                Ok(cart)
+            }
+            Err(e) => {
+                let mut all_lines = line_inputs(&stored.line_items);
+                all_lines.extend(lines);
+                self.recover(session_id, &stored, all_lines, e).await
+            }
+        }
+    }
+
+    /// Set the quantity of a line in a session's cart.
+    ///
+    /// A quantity of zero removes the line. Returns `None` if the session has
+    /// no cart.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database or Shopify API request fails.
+    #[instrument(skip(self))]
+    pub async fn update(
+        &self,
+        session_id: &str,
+        line_id: &str,
+        quantity: i64,
+    ) -> Result<Option<Cart>, CartError> {
+        let _guard = self.locks.lock(session_id).await;
+        let Some(stored) = self.stored(session_id).await? else {
+            return Ok(None);
+        };
+
+        let update = CartLineUpdateInput {
+            id: line_id.to_string(),
+            quantity: Some(quantity),
+            merchandise_id: None,
+            attributes: None,
+            selling_plan_id: None,
+        };
+
+        let result = match self
+            .storefront
+            .update_cart(&stored.shopify_cart_id, vec![update])
+            .await
+        {
+            Ok(cart) => {
+                self.save(session_id, &cart).await?;
+                Ok(cart)
+            }
+            Err(e) => {
+                let lines = with_quantity(&stored.line_items, line_id, quantity);
+                self.recover(session_id, &stored, line_inputs(&lines), e)
+                    .await
+            }
+        };
+        result.map(Some)
+    }
+
+    /// Remove a line from a session's cart.
+    ///
+    /// Returns `None` if the session has no cart.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database or Shopify API request fails.
+    #[instrument(skip(self))]
+    pub async fn remove(&self, session_id: &str, line_id: &str) -> Result<Option<Cart>, CartError> {
+        let _guard = self.locks.lock(session_id).await;
+        let Some(stored) = self.stored(session_id).await? else {
+            return Ok(None);
+        };
+
+        let result = match self
+            .storefront
+            .remove_from_cart(&stored.shopify_cart_id, vec![line_id.to_string()])
+            .await
+        {
+            Ok(cart) => {
+                self.save(session_id, &cart).await?;
+                Ok(cart)
+            }
+            Err(e) => {
+                let lines = with_quantity(&stored.line_items, line_id, 0);
+                self.recover(session_id, &stored, line_inputs(&lines), e)
+                    .await
+            }
+        };
+        result.map(Some)
+    }
+
+    /// Load the stored cart for a session.
+    async fn stored(&self, session_id: &str) -> Result<Option<StoredCart>, CartError> {
+        Ok(CartRepository::new(&self.pool)
+            .get_by_session(session_id)
+            .await?)
+    }
+
+    /// Save a Shopify cart and its line snapshot for a session.
+    async fn save(&self, session_id: &str, cart: &Cart) -> Result<(), CartError> {
+        CartRepository::new(&self.pool)
+            .save(session_id, &cart.id, &snapshot(cart))
+            .await?;
+        Ok(())
+    }
+
+    /// Handle a failed change to a stored cart.
+    ///
+    /// If the Shopify cart has expired, replaces it with a new cart holding
+    /// `lines` (the lines the change would have left). Otherwise returns the
+    /// original error.
+    async fn recover(
+        &self,
+        session_id: &str,
+        stored: &StoredCart,
+        lines: Vec<CartLineInput>,
+        error: ShopifyError,
+    ) -> Result<Cart, CartError> {
+        let expired = matches!(
+            self.storefront.get_cart(&stored.shopify_cart_id).await,
+            Err(ShopifyError::NotFound(_))
+        );
+        if expired {
+            self.replace_expired(session_id, stored, lines).await
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Create a new Shopify cart with `lines` in place of an expired one.
+    async fn replace_expired(
+        &self,
+        session_id: &str,
+        stored: &StoredCart,
+        lines: Vec<CartLineInput>,
+    ) -> Result<Cart, CartError> {
+        tracing::info!(
+            expired_cart_id = %stored.shopify_cart_id,
+            lines = lines.len(),
+            "Shopify cart expired, migrating lines to a new cart"
+        );
+        let cart = self.storefront.create_cart(Some(lines), None).await?;
+        self.save(session_id, &cart).await?;
+        Ok(cart)
+    }
+}
+
+/// Snapshot of a Shopify cart's lines for storage.
+fn snapshot(cart: &Cart) -> Vec<StoredCartLine> {
+    cart.lines
+        .iter()
+        .map(|line| StoredCartLine {
+            line_id: line.id.clone(),
+            merchandise_id: line.merchandise.id.clone(),
+            quantity: line.quantity,
+        })
+        .collect()
+}
+
+/// Inputs that recreate stored lines on a new cart.
+fn line_inputs(lines: &[StoredCartLine]) -> Vec<CartLineInput> {
+    lines
+        .iter()
+        .map(|line| CartLineInput {
+            merchandise_id: line.merchandise_id.clone(),
+            quantity: line.quantity,
+            attributes: None,
+            selling_plan_id: None,
+        })
+        .collect()
+}
+
+/// Stored lines with one line's quantity changed, dropping it at zero.
+fn with_quantity(lines: &[StoredCartLine], line_id: &str, quantity: i64) -> Vec<StoredCartLine> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            if line.line_id != line_id {
+                Some(line.clone())
+            } else if quantity > 0 {
+                Some(StoredCartLine {
+                    quantity,
+                    ..line.clone()
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// =============================================================================
+// Per-Session Locks
+// =============================================================================
+
+/// One async lock per session with a cart operation in flight.
+#[derive(Clone, Default)]
+struct SessionLocks {
+    locks: Arc<Mutex<HashMap<String, Arc<AsyncMutex<()>>>>>,
+}
+
+impl SessionLocks {
+    /// Wait for exclusive access to a session's cart.
+    async fn lock(&self, session_id: &str) -> SessionLockGuard {
+        let lock = Arc::clone(
+            self.locks
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .entry(session_id.to_string())
+                .or_default(),
+        );
+
+        SessionLockGuard {
+            locks: Arc::clone(&self.locks),
+            session_id: session_id.to_string(),
+            guard: lock.lock_owned().await,
+        }
+    }
+
+    /// Number of sessions with a lock entry.
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.locks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+}
+
+/// Held while a session's cart is being changed.
+///
+/// Removes the session's lock entry on drop unless another request is
+/// waiting on it.
+struct SessionLockGuard {
+    locks: Arc<Mutex<HashMap<String, Arc<AsyncMutex<()>>>>>,
+    session_id: String,
+    guard: OwnedMutexGuard<()>,
+}
+
+impl Drop for SessionLockGuard {
+    fn drop(&mut self) {
+        let mut locks = self.locks.lock().unwrap_or_else(PoisonError::into_inner);
+        // One reference in the map and one in this guard means no waiters
+        if Arc::strong_count(OwnedMutexGuard::mutex(&self.guard)) <= 2 {
+            locks.remove(&self.session_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn line(line_id: &str, quantity: i64) -> StoredCartLine {
+        StoredCartLine {
+            line_id: line_id.to_string(),
+            merchandise_id: format!("gid://shopify/ProductVariant/{line_id}"),
+            quantity,
+        }
+    }
+
+    #[test]
+    fn test_with_quantity() {
+        let lines = vec![line("1", 1), line("2", 2)];
+
+        assert_eq!(
+            with_quantity(&lines, "2", 5),
+            vec![line("1", 1), line("2", 5)]
+        );
+        assert_eq!(with_quantity(&lines, "1", 0), vec![line("2", 2)]);
+        assert_eq!(with_quantity(&lines, "3", 4), lines);
+    }
+
+    #[test]
+    fn test_line_inputs() {
+        let inputs = line_inputs(&[line("1", 3)]);
+        assert_eq!(inputs.len(), 1);
+        let input = inputs.first().expect("one input");
+        assert_eq!(input.merchandise_id, "gid://shopify/ProductVariant/1");
+        assert_eq!(input.quantity, 3);
+    }
+
+    #[tokio::test]
+    async fn test_session_locks_serialize_and_clean_up() {
+        let locks = SessionLocks::default();
+
+        let first = locks.lock("a").await;
+        let waiter = tokio::spawn({
+            let locks = locks.clone();
+            async move {
+                let _guard = locks.lock("a").await;
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        // Other sessions aren't blocked
+        drop(locks.lock("b").await);
+
+        drop(first);
+        waiter.await.expect("waiter completes");
+        assert_eq!(locks.len(), 0);
+    }
+}
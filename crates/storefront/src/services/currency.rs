@@ -0,0 +1,372 @@
+//! Currency detection and localized price formatting.
+//!
+//! A buyer's display currency is resolved in priority order:
+//!
+//! 1. `?currency=CAD` query parameter (explicit choice, saved to the session)
+//! 2. Currency preference previously saved in the session
+//! 3. Cloudflare `CF-IPCountry` geolocation header
+//! 4. The shop's base currency
+//!
+//! Only currencies of countries the shop sells to (Shopify Markets) are
+//! accepted. The resolved country is passed to the Storefront API with
+//! `@inContext(country:)` so Shopify returns prices in that market's currency.
+
+use std::collections::HashMap;
+
+use naked_pineapple_core::{CurrencyCode, Price};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+use crate::shopify::types::{Money, ShopLocalization};
+
+/// A buyer's chosen display currency and the country it is priced for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CurrencyPreference {
+    /// ISO 3166-1 alpha-2 country code passed to `@inContext`.
+    pub country_code: String,
+    /// ISO 4217 currency code prices will be returned in.
+    pub currency_code: String,
+}
+
+/// Resolves a buyer's display currency from the shop's localization settings.
+pub struct CurrencyDetectionService<'a> {
+    localization: &'a ShopLocalization,
+}
+
+impl<'a> CurrencyDetectionService<'a> {
+    /// Create a new currency detection service.
+    #[must_use]
+    pub const fn new(localization: &'a ShopLocalization) -> Self {
+        Self { localization }
+    }
+
+    /// Resolve the display currency for a request.
+    ///
+    /// Returns `None` when the shop's base currency should be used.
+    #[must_use]
+    pub fn detect(
+        &self,
+        query_currency: Option<&str>,
+        session: Option<&CurrencyPreference>,
+        ip_country: Option<&str>,
+    ) -> Option<CurrencyPreference> {
+        if let Some(preference) = query_currency.and_then(|c| self.for_currency(c)) {
+            return Some(preference);
+        }
+
+        if let Some(preference) = session.filter(|p| self.is_available(p)) {
+            return Some(preference.clone());
+        }
+
+        ip_country.and_then(|c| self.for_country(c))
+    }
+
+    /// Get the preference for a currency code, if the shop sells in it.
+    ///
+    /// When several countries share a currency (e.g. EUR), the first
+    /// available country is used for pricing.
+    #[must_use]
+    pub fn for_currency(&self, currency_code: &str) -> Option<CurrencyPreference> {
+        self.localization
+            .countries
+            .iter()
+            .find(|c| c.currency_code.eq_ignore_ascii_case(currency_code.trim()))
+            .map(|c| CurrencyPreference {
+                country_code: c.country_code.clone(),
+                currency_code: c.currency_code.clone(),
+            })
+    }
+
+    /// Get the preference for a country code, if the shop sells there.
+    #[must_use]
+    pub fn for_country(&self, country_code: &str) -> Option<CurrencyPreference> {
+        self.localization
+            .countries
+            .iter()
+            .find(|c| c.country_code.eq_ignore_ascii_case(country_code.trim()))
+            .map(|c| CurrencyPreference {
+                country_code: c.country_code.clone(),
+                currency_code: c.currency_code.clone(),
+            })
+    }
+
+    /// Check whether a saved preference is still offered by the shop.
+    fn is_available(&self, preference: &CurrencyPreference) -> bool {
+        self.localization.countries.iter().any(|c| {
+            c.country_code == preference.country_code && c.currency_code == preference.currency_code
+        })
+    }
+
+    /// Distinct currency codes the shop sells in, sorted alphabetically.
+    #[must_use]
+    pub fn available_currencies(&self) -> Vec<String> {
+        let mut currencies: Vec<String> = self
+            .localization
+            .countries
+            .iter()
+            .map(|c| c.currency_code.clone())
+            .collect();
+        currencies.sort();
+        currencies.dedup();
+        currencies
+    }
+}
+
+// =============================================================================
+// Price Formatting
+// =============================================================================
+
+/// Formats prices using the shop's money format.
+///
+/// Amounts in the shop's base currency use Shopify's `money_format`
+/// (e.g. `${{amount}}`). Localized amounts use the market currency's symbol
+/// followed by its code (e.g. `$12.00 CAD`) so buyers can tell dollars apart.
+#[derive(Debug, Clone)]
+pub struct PriceFormatter {
+    money_format: String,
+    shop_currency: String,
+    symbols: HashMap<String, String>,
+}
+
+impl Default for PriceFormatter {
+    fn default() -> Self {
+        Self {
+            money_format: "${{amount}}".to_string(),
+            shop_currency: "USD".to_string(),
+            symbols: HashMap::new(),
+        }
+    }
+}
+
+impl PriceFormatter {
+    /// Create a formatter from the shop's localization settings.
+    #[must_use]
+    pub fn new(localization: &ShopLocalization) -> Self {
+        let symbols = localization
+            .countries
+            .iter()
+            .map(|c| (c.currency_code.clone(), c.currency_symbol.clone()))
+            .collect();
+
+        Self {
+            money_format: localization.money_format.clone(),
+            shop_currency: localization.currency_code.clone(),
+            symbols,
+        }
+    }
+
+    /// Format a Shopify Money value for display.
+    ///
+    /// Amounts that aren't decimal numbers are shown as-is with their code.
+    #[must_use]
+    pub fn format(&self, money: &Money) -> String {
+        money.amount.parse::<Decimal>().map_or_else(
+            |_| format!("{} {}", money.amount, money.currency_code),
+            |amount| self.format_amount(amount, &money.currency_code),
+        )
+    }
+
+    /// Format an amount in an ISO 4217 currency, such as a sum of Money
+    /// values.
+    #[must_use]
+    pub fn format_amount(&self, amount: Decimal, currency_code: &str) -> String {
+        if currency_code == self.shop_currency {
+            return apply_money_format(&self.money_format, amount, currency_code);
+        }
+
+        let symbol = self.symbols.get(currency_code).map_or("", String::as_str);
+        let number = format_number(amount, 2, ',', '.');
+        format!("{symbol}{number} {currency_code}")
+    }
+}
+
+/// Substitute an amount into a Shopify money format string.
+///
+/// Supports the `amount`, `amount_no_decimals`, `amount_with_comma_separator`
+/// and `amount_no_decimals_with_comma_separator` placeholders. A format
+/// without a placeholder falls back to [`format_price`].
+fn apply_money_format(format: &str, amount: Decimal, currency_code: &str) -> String {
+    let Some((prefix, rest)) = format.split_once("{{") else {
+        return format_price(amount, currency_code);
+    };
+    let Some((placeholder, suffix)) = rest.split_once("}}") else {
+        return format_price(amount, currency_code);
+    };
+
+    let (decimals, thousands, decimal_mark) = match placeholder.trim() {
+        "amount_no_decimals" => (0, ',', '.'),
+        "amount_with_comma_separator" => (2, '.', ','),
+        "amount_no_decimals_with_comma_separator" => (0, '.', ','),
+        _ => (2, ',', '.'),
+    };
+
+    let number = format_number(amount, decimals, thousands, decimal_mark);
+    format!("{prefix}{number}{suffix}")
+}
+
+/// Format an amount as a [`Price`] (`$1,234.50`).
+///
+/// Currencies without a core [`CurrencyCode`] are shown with their code
+/// (`1,250.00 JPY`).
+fn format_price(amount: Decimal, currency_code: &str) -> String {
+    let code = match currency_code {
+        "USD" => CurrencyCode::USD,
+        "EUR" => CurrencyCode::EUR,
+        "GBP" => CurrencyCode::GBP,
+        "CAD" => CurrencyCode::CAD,
+        "AUD" => CurrencyCode::AUD,
+        _ => return format!("{} {currency_code}", format_number(amount, 2, ',', '.')),
+    };
+    Price::new(amount, code).to_string()
+}
+
+/// Round half away from zero and format with the given decimal places and
+/// separators.
+fn format_number(amount: Decimal, decimals: u32, thousands: char, decimal_mark: char) -> String {
+    let mut rounded =
+        amount.round_dp_with_strategy(decimals, RoundingStrategy::MidpointAwayFromZero);
+    rounded.rescale(decimals);
+
+    let fixed = rounded.abs().to_string();
+    let (digits, frac_part) = fixed
+        .split_once('.')
+        .map_or((fixed.as_str(), None), |(i, f)| (i, Some(f)));
+
+    let mut result = String::new();
+    if rounded.is_sign_negative() && !rounded.is_zero() {
+        result.push('-');
+    }
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            result.push(thousands);
+        }
+        result.push(ch);
+    }
+    if let Some(frac) = frac_part {
+        result.push(decimal_mark);
+        result.push_str(frac);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shopify::types::LocalizationCountry;
+
+    fn localization() -> ShopLocalization {
+        let country = |country_code: &str, currency_code: &str, symbol: &str| LocalizationCountry {
+            country_code: country_code.to_string(),
+            name: country_code.to_string(),
+            currency_code: currency_code.to_string(),
+            currency_symbol: symbol.to_string(),
+        };
+        ShopLocalization {
+            money_format: "${{amount}}".to_string(),
+            currency_code: "USD".to_string(),
+            countries: vec![
+                country("US", "USD", "$"),
+                country("CA", "CAD", "$"),
+                country("GB", "GBP", "£"),
+            ],
+            language_code: "EN".to_string(),
+            languages: Vec::new(),
+        }
+    }
+
+    fn money(amount: &str, currency_code: &str) -> Money {
+        Money {
+            amount: amount.to_string(),
+            currency_code: currency_code.to_string(),
+        }
+    }
+
+    /// Parse a decimal literal for tests.
+    fn dec(amount: &str) -> Decimal {
+        amount.parse().expect("valid decimal")
+    }
+
+    #[test]
+    fn test_format_number_groups_and_rounds() {
+        assert_eq!(
+            format_number(dec("1234567.891"), 2, ',', '.'),
+            "1,234,567.89"
+        );
+        assert_eq!(format_number(dec("999"), 2, ',', '.'), "999.00");
+        assert_eq!(format_number(Decimal::ZERO, 2, ',', '.'), "0.00");
+        assert_eq!(format_number(dec("-1234.4"), 0, '.', ','), "-1.234");
+        assert_eq!(format_number(dec("123456"), 0, ',', '.'), "123,456");
+        // Halves round away from zero, which f64 formatting gets wrong
+        assert_eq!(format_number(dec("1.005"), 2, ',', '.'), "1.01");
+        assert_eq!(format_number(dec("-0.001"), 2, ',', '.'), "0.00");
+    }
+
+    #[test]
+    fn test_apply_money_format_placeholders() {
+        let amount = dec("1234.5");
+        assert_eq!(
+            apply_money_format("${{amount}}", amount, "USD"),
+            "$1,234.50"
+        );
+        assert_eq!(
+            apply_money_format("{{amount_no_decimals}} kr", dec("1234.4"), "SEK"),
+            "1,234 kr"
+        );
+        assert_eq!(
+            apply_money_format("€{{ amount_with_comma_separator }}", amount, "EUR"),
+            "€1.234,50"
+        );
+        assert_eq!(
+            apply_money_format(
+                "{{amount_no_decimals_with_comma_separator}}",
+                dec("1234567.2"),
+                "EUR"
+            ),
+            "1.234.567"
+        );
+        // Unknown placeholders use the default `amount` format
+        assert_eq!(
+            apply_money_format("{{amount_foo}} $", amount, "USD"),
+            "1,234.50 $"
+        );
+    }
+
+    #[test]
+    fn test_apply_money_format_without_placeholder_falls_back_to_price() {
+        let amount = dec("1234.5");
+        assert_eq!(apply_money_format("USD", amount, "USD"), "$1,234.50");
+        assert_eq!(apply_money_format("€{{amount", amount, "EUR"), "€1,234.50");
+        assert_eq!(apply_money_format("", dec("5"), "JPY"), "5.00 JPY");
+    }
+
+    #[test]
+    fn test_price_formatter_base_currency_uses_money_format() {
+        let formatter = PriceFormatter::new(&localization());
+        assert_eq!(formatter.format(&money("1234.5", "USD")), "$1,234.50");
+        assert_eq!(formatter.format(&money("0.0", "USD")), "$0.00");
+    }
+
+    #[test]
+    fn test_price_formatter_market_currency_shows_code() {
+        let formatter = PriceFormatter::new(&localization());
+        assert_eq!(formatter.format(&money("12", "CAD")), "$12.00 CAD");
+        assert_eq!(formatter.format(&money("8.5", "GBP")), "£8.50 GBP");
+        // Currencies the shop doesn't sell in have no symbol
+        assert_eq!(formatter.format(&money("3", "JPY")), "3.00 JPY");
+    }
+
+    #[test]
+    fn test_price_formatter_formats_sums() {
+        let formatter = PriceFormatter::new(&localization());
+        let total = dec("19.99") + dec("5.01");
+        assert_eq!(formatter.format_amount(total, "USD"), "$25.00");
+        assert_eq!(formatter.format_amount(dec("1500"), "CAD"), "$1,500.00 CAD");
+    }
+
+    #[test]
+    fn test_price_formatter_unparseable_amount() {
+        let formatter = PriceFormatter::default();
+        assert_eq!(formatter.format(&money("abc", "USD")), "abc USD");
+        assert_eq!(formatter.format(&money("19.99", "USD")), "$19.99");
+    }
+}
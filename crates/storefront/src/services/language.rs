@@ -0,0 +1,168 @@
+//! Buyer language detection.
+//!
+//! A buyer's content language is resolved in priority order:
+//!
+//! 1. `?language=fr` query parameter (explicit choice, saved to the session)
+//! 2. Language preference previously saved in the session
+//! 3. The browser's `Accept-Language` header
+//! 4. The shop's primary language
+//!
+//! Only languages the shop is published in are accepted. The resolved
+//! language is passed to the Storefront API with `@inContext(language:)` so
+//! Shopify returns translated product and collection content.
+
+use crate::shopify::types::ShopLocalization;
+
+/// Resolves a buyer's content language from the shop's localization settings.
+///
+/// Languages are Shopify `LanguageCode` values (e.g. `FR`, `PT_BR`).
+pub struct LanguageDetectionService<'a> {
+    localization: &'a ShopLocalization,
+}
+
+impl<'a> LanguageDetectionService<'a> {
+    /// Create a new language detection service.
+    #[must_use]
+    pub const fn new(localization: &'a ShopLocalization) -> Self {
+        Self { localization }
+    }
+
+    /// Resolve the content language for a request.
+    ///
+    /// Returns `None` when the shop's primary language should be used.
+    #[must_use]
+    pub fn detect(
+        &self,
+        query_language: Option<&str>,
+        session: Option<&str>,
+        accept_language: Option<&str>,
+    ) -> Option<String> {
+        let language = query_language
+            .and_then(|l| self.for_tag(l))
+            .or_else(|| session.and_then(|l| self.for_tag(l)))
+            .or_else(|| {
+                accept_language
+                    .map(parse_accept_language)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .find_map(|tag| self.for_tag(&tag))
+            })?;
+
+        (language != self.localization.language_code).then_some(language)
+    }
+
+    /// Get the shop language matching a BCP 47 tag or `LanguageCode`.
+    ///
+    /// Tries the full tag (`pt-BR` matches `PT_BR`), then its primary subtag
+    /// (`fr-CA` matches `FR`), then any regional variant of that subtag
+    /// (`pt` matches `PT_PT` if that's all the shop offers).
+    #[must_use]
+    pub fn for_tag(&self, tag: &str) -> Option<String> {
+        let code = tag.trim().replace('-', "_").to_ascii_uppercase();
+        if code.is_empty() {
+            return None;
+        }
+        let base = code.split('_').next().unwrap_or_default();
+
+        let languages = &self.localization.languages;
+        languages
+            .iter()
+            .find(|l| l.language_code == code)
+            .or_else(|| languages.iter().find(|l| l.language_code == base))
+            .or_else(|| {
+                languages
+                    .iter()
+                    .find(|l| l.language_code.split('_').next() == Some(base))
+            })
+            .map(|l| l.language_code.clone())
+    }
+}
+
+/// Language tags from an `Accept-Language` header, most preferred first.
+///
+/// Tags with `q=0` and the `*` wildcard are dropped.
+#[must_use]
+pub fn parse_accept_language(header: &str) -> Vec<String> {
+    let mut tags: Vec<(String, f32)> = header
+        .split(',')
+        .filter_map(|part| {
+            let mut params = part.split(';');
+            let tag = params.next()?.trim();
+            let quality = params
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+            (!tag.is_empty() && tag != "*" && quality > 0.0).then(|| (tag.to_string(), quality))
+        })
+        .collect();
+
+    // Stable, so equally weighted tags keep the browser's order
+    tags.sort_by(|a, b| b.1.total_cmp(&a.1));
+    tags.into_iter().map(|(tag, _)| tag).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shopify::types::LocalizationLanguage;
+
+    fn localization(primary: &str, languages: &[&str]) -> ShopLocalization {
+        ShopLocalization {
+            money_format: "${{amount}}".to_string(),
+            currency_code: "USD".to_string(),
+            countries: Vec::new(),
+            language_code: primary.to_string(),
+            languages: languages
+                .iter()
+                .map(|code| LocalizationLanguage {
+                    language_code: (*code).to_string(),
+                    name: (*code).to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_accept_language_orders_by_quality() {
+        assert_eq!(
+            parse_accept_language("fr-CA;q=0.8, en-US, de;q=0, *;q=0.1, es;q=0.8"),
+            vec!["en-US", "fr-CA", "es"]
+        );
+        assert!(parse_accept_language("").is_empty());
+    }
+
+    #[test]
+    fn test_for_tag_matches_region_and_base() {
+        let localization = localization("EN", &["EN", "FR", "PT_BR"]);
+        let service = LanguageDetectionService::new(&localization);
+
+        assert_eq!(service.for_tag("fr-CA").as_deref(), Some("FR"));
+        assert_eq!(service.for_tag("pt-BR").as_deref(), Some("PT_BR"));
+        assert_eq!(service.for_tag("pt").as_deref(), Some("PT_BR"));
+        assert_eq!(service.for_tag("de"), None);
+    }
+
+    #[test]
+    fn test_detect_priority() {
+        let localization = localization("EN", &["EN", "FR", "DE"]);
+        let service = LanguageDetectionService::new(&localization);
+
+        assert_eq!(
+            service
+                .detect(Some("de"), Some("FR"), Some("fr"))
+                .as_deref(),
+            Some("DE")
+        );
+        assert_eq!(
+            service
+                .detect(Some("xx"), Some("FR"), Some("de"))
+                .as_deref(),
+            Some("FR")
+        );
+        assert_eq!(
+            service.detect(None, None, Some("es, de;q=0.5")).as_deref(),
+            Some("DE")
+        );
+        // The primary language needs no @inContext
+        assert_eq!(service.detect(None, None, Some("en-GB")), None);
+    }
+}
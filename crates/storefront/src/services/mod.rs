@@ -2,14 +2,31 @@
 //!
 //! # Services
 //!
+//! - `ab_test` - A/B test experiments and variant assignment
 //! - `auth` - User authentication (password, `WebAuthn`, OAuth)
 //! - `email` - Email sending (verification, password reset)
-//! - `cart` - Cart operations (wrapper around Shopify cart)
+//! - `cart` - Persistent carts synced with the Shopify cart
 //! - `analytics` - Analytics event tracking
+//! - `back_in_stock` - Restock notifications for out-of-stock variants
+//! - `currency` - Buyer currency detection and localized price formatting
 //! - `klaviyo` - Klaviyo API for subscription management
+//! - `language` - Buyer language detection
+//! - `session_cleanup` - Periodic deletion of expired sessions
 
+pub mod ab_test;
 pub mod auth;
+pub mod back_in_stock;
+pub mod cart;
+pub mod currency;
 mod klaviyo;
+pub mod language;
+pub mod session_cleanup;
 
+pub use ab_test::{AbTest, AbTestError, AbTestService};
 pub use auth::{AuthError, AuthService};
+pub use back_in_stock::{BackInStockError, BackInStockService, LogNotifier, RestockNotifier};
+pub use cart::{CartError, CartService};
+pub use currency::{CurrencyDetectionService, CurrencyPreference, PriceFormatter};
 pub use klaviyo::{KlaviyoClient, KlaviyoError};
+pub use language::LanguageDetectionService;
+pub use session_cleanup::{SessionCleanupStats, SessionCleanupTask};
@@ -22,10 +22,19 @@
 //! // Generate login URL
 //! let state = generate_random_state();
 //! let nonce = generate_random_nonce();
-//! let auth_url = client.authorization_url("https://example.com/callback", &state, &nonce);
+//! let code_verifier = generate_random_verifier();
+//! let code_challenge = pkce_challenge(&code_verifier);
+//! let auth_url = client.authorization_url(
+//!     "https://example.com/callback",
+//!     &state,
+//!     &nonce,
+//!     &code_challenge,
+//! );
 //!
 //! // After OAuth callback, exchange code for token
-//! let token = client.exchange_code(&code, "https://example.com/callback").await?;
+//! let token = client
+//!     .exchange_code(&code, "https://example.com/callback", &code_verifier)
+//!     .await?;
 //!
 //! // Use token for API calls
 //! let customer = client.get_customer(&token.access_token).await?;
@@ -37,11 +46,14 @@ pub use types::*;
 
 use std::sync::Arc;
 
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
 use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
 
 use crate::config::ShopifyStorefrontConfig;
 use crate::shopify::ShopifyError;
+use crate::shopify::types::{Image, Money};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // GraphQL Types
@@ -79,6 +91,261 @@ impl<T> GraphQLResponse<T> {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// PKCE
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Derive the PKCE `S256` code challenge for a code verifier.
+///
+/// The challenge is the unpadded base64url encoding of the verifier's
+/// SHA-256 digest (RFC 7636 §4.2). The verifier itself must be 43-128
+/// characters from the unreserved URL character set.
+#[must_use]
+pub fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Order Detail
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Full details of one order, with fulfilled and returnable quantities per
+/// line item.
+const GET_ORDER_QUERY: &str = r"
+    query getOrder($id: ID!) {
+        order(id: $id) {
+            id
+            name
+            financialStatus
+            fulfillmentStatus
+            createdAt
+            lineItems(first: 100) {
+                nodes {
+                    id
+                    title
+                    variantTitle
+                    image {
+                        url
+                        alt_text: altText
+                        width
+                        height
+                    }
+                    quantity
+                    price {
+                        amount
+                        currency_code: currencyCode
+                    }
+                }
+            }
+            shippingAddress {
+                id
+                firstName
+                lastName
+                company
+                address1
+                address2
+                city
+                province
+                provinceCode: zoneCode
+                country
+                countryCode: territoryCode
+                zip
+                phone: phoneNumber
+            }
+            shippingLine {
+                title
+                originalPrice {
+                    amount
+                    currency_code: currencyCode
+                }
+            }
+            subtotal {
+                amount
+                currency_code: currencyCode
+            }
+            totalTax {
+                amount
+                currency_code: currencyCode
+            }
+            totalPrice {
+                amount
+                currency_code: currencyCode
+            }
+            discountApplications(first: 20) {
+                nodes {
+                    ... on DiscountCodeApplication {
+                        code
+                    }
+                }
+            }
+            fulfillments(first: 20) {
+                nodes {
+                    trackingInformation {
+                        company
+                        number
+                        url
+                    }
+                    fulfillmentLineItems(first: 100) {
+                        nodes {
+                            quantity
+                            lineItem {
+                                id
+                            }
+                        }
+                    }
+                }
+            }
+            returnInformation {
+                returnableLineItems(first: 100) {
+                    nodes {
+                        quantity
+                        lineItem {
+                            id
+                        }
+                    }
+                }
+            }
+        }
+    }
+";
+
+/// Order returned by [`GET_ORDER_QUERY`].
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderDetailNode {
+    id: String,
+    name: String,
+    financial_status: Option<String>,
+    fulfillment_status: String,
+    created_at: String,
+    line_items: NodeList<OrderLineItemNode>,
+    shipping_address: Option<Address>,
+    shipping_line: Option<OrderShippingLine>,
+    subtotal: Option<Money>,
+    total_tax: Option<Money>,
+    total_price: Money,
+    discount_applications: NodeList<DiscountApplicationNode>,
+    fulfillments: NodeList<FulfillmentNode>,
+    return_information: ReturnInformation,
+}
+
+#[derive(Deserialize)]
+struct NodeList<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderLineItemNode {
+    id: String,
+    title: String,
+    variant_title: Option<String>,
+    image: Option<Image>,
+    quantity: i64,
+    price: Option<Money>,
+}
+
+#[derive(Deserialize)]
+struct DiscountApplicationNode {
+    code: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FulfillmentNode {
+    tracking_information: Vec<OrderTracking>,
+    fulfillment_line_items: NodeList<LineItemQuantity>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReturnInformation {
+    returnable_line_items: NodeList<LineItemQuantity>,
+}
+
+/// A quantity of one order line item (fulfilled or returnable).
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LineItemQuantity {
+    quantity: Option<i64>,
+    line_item: LineItemRef,
+}
+
+#[derive(Deserialize)]
+struct LineItemRef {
+    id: String,
+}
+
+/// Total quantity of `line_item_id` across `items`.
+fn quantity_of<'a>(
+    items: impl IntoIterator<Item = &'a LineItemQuantity>,
+    line_item_id: &str,
+) -> i64 {
+    items
+        .into_iter()
+        .filter(|item| item.line_item.id == line_item_id)
+        .filter_map(|item| item.quantity)
+        .sum()
+}
+
+impl From<OrderDetailNode> for CustomerOrder {
+    fn from(order: OrderDetailNode) -> Self {
+        let fulfilled = || {
+            order
+                .fulfillments
+                .nodes
+                .iter()
+                .flat_map(|f| &f.fulfillment_line_items.nodes)
+        };
+        let returnable = &order.return_information.returnable_line_items.nodes;
+
+        let line_items = order
+            .line_items
+            .nodes
+            .into_iter()
+            .map(|item| CustomerOrderLineItem {
+                fulfilled_quantity: quantity_of(fulfilled(), &item.id),
+                returnable_quantity: quantity_of(returnable, &item.id),
+                id: item.id,
+                title: item.title,
+                variant_title: item.variant_title,
+                image: item.image,
+                quantity: item.quantity,
+                price: item.price,
+            })
+            .collect();
+
+        let tracking = order
+            .fulfillments
+            .nodes
+            .into_iter()
+            .flat_map(|f| f.tracking_information)
+            .filter(|t| t.number.is_some())
+            .collect();
+
+        Self {
+            id: order.id,
+            name: order.name,
+            financial_status: order.financial_status,
+            fulfillment_status: order.fulfillment_status,
+            created_at: order.created_at,
+            line_items,
+            shipping_address: order.shipping_address,
+            shipping_line: order.shipping_line,
+            subtotal: order.subtotal,
+            total_tax: order.total_tax,
+            total_price: order.total_price,
+            discount_codes: order
+                .discount_applications
+                .nodes
+                .into_iter()
+                .filter_map(|d| d.code)
+                .collect(),
+            tracking,
+        }
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Customer Account Client
 // ─────────────────────────────────────────────────────────────────────────────
@@ -142,12 +409,19 @@ impl CustomerClient {
     /// * `redirect_uri` - The callback URL to redirect to after authentication
     /// * `state` - A random string stored in the session to prevent CSRF attacks
     /// * `nonce` - A random string for `OpenID` Connect replay protection
+    /// * `code_challenge` - The PKCE challenge from [`pkce_challenge`]
     ///
     /// # Returns
     ///
     /// The full authorization URL to redirect the customer to.
     #[must_use]
-    pub fn authorization_url(&self, redirect_uri: &str, state: &str, nonce: &str) -> String {
+    pub fn authorization_url(
+        &self,
+        redirect_uri: &str,
+        state: &str,
+        nonce: &str,
+        code_challenge: &str,
+    ) -> String {
         format!(
             "https://shopify.com/{}/auth/oauth/authorize?\
             client_id={}&\
@@ -155,12 +429,15 @@ impl CustomerClient {
             redirect_uri={}&\
             scope=openid%20email%20customer-account-api:full&\
             state={}&\
-            nonce={}",
+            nonce={}&\
+            code_challenge={}&\
+            code_challenge_method=S256",
             self.inner.store_id,
             urlencoding::encode(&self.inner.client_id),
             urlencoding::encode(redirect_uri),
             urlencoding::encode(state),
-            urlencoding::encode(nonce)
+            urlencoding::encode(nonce),
+            urlencoding::encode(code_challenge)
         )
     }
 
@@ -192,6 +469,8 @@ impl CustomerClient {
     ///
     /// * `code` - The authorization code from the OAuth callback
     /// * `redirect_uri` - The same redirect URI used in the authorization request
+    /// * `code_verifier` - The PKCE verifier the authorization request's challenge
+    ///   was derived from
     ///
     /// # Errors
     ///
@@ -200,6 +479,7 @@ impl CustomerClient {
         &self,
         code: &str,
         redirect_uri: &str,
+        code_verifier: &str,
     ) -> Result<CustomerAccessToken, ShopifyError> {
         let url = format!(
             "https://shopify.com/{}/auth/oauth/token",
@@ -212,6 +492,7 @@ impl CustomerClient {
             ("client_secret", &self.inner.client_secret),
             ("code", code),
             ("redirect_uri", redirect_uri),
+            ("code_verifier", code_verifier),
         ];
 
         let response = self.inner.client.post(&url).form(&params).send().await?;
@@ -445,6 +726,74 @@ impl CustomerClient {
             .ok_or_else(|| ShopifyError::OAuth("No customer returned".to_string()))
     }
 
+    /// Update the current customer's name and phone number.
+    ///
+    /// Fields left as `None` are unchanged. `phone` should be in E.164 format.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ShopifyError::EmailVerificationRequired` if Shopify accepted the
+    /// change pending email verification, `ShopifyError::UserError` for
+    /// validation errors, or another error if the API request fails.
+    pub async fn update_customer_profile(
+        &self,
+        access_token: &str,
+        first_name: Option<String>,
+        last_name: Option<String>,
+        phone: Option<String>,
+    ) -> Result<(), ShopifyError> {
+        #[derive(Deserialize)]
+        struct Response {
+            #[serde(rename = "customerUpdate")]
+            customer_update: CustomerUpdateResult,
+        }
+
+        #[derive(Deserialize)]
+        struct CustomerUpdateResult {
+            #[serde(rename = "userErrors")]
+            user_errors: Vec<CustomerUserError>,
+        }
+
+        const QUERY: &str = r"
+            mutation customerUpdate($input: CustomerUpdateInput!) {
+                customerUpdate(input: $input) {
+                    customer {
+                        id
+                    }
+                    userErrors {
+                        field
+                        message
+                        code
+                    }
+                }
+            }
+        ";
+
+        let input = CustomerUpdateInput {
+            first_name,
+            last_name,
+            phone,
+            accepts_marketing: None,
+        };
+        let variables = serde_json::json!({ "input": input });
+        let response: Response = self.query(access_token, QUERY, Some(variables)).await?;
+        let user_errors = response.customer_update.user_errors;
+
+        if user_errors
+            .iter()
+            .any(|e| e.code.as_deref() == Some("EMAIL_VERIFICATION_REQUIRED"))
+        {
+            return Err(ShopifyError::EmailVerificationRequired);
+        }
+
+        if !user_errors.is_empty() {
+            let messages: Vec<_> = user_errors.iter().map(|e| e.message.as_str()).collect();
+            return Err(ShopifyError::UserError(messages.join(", ")));
+        }
+
+        Ok(())
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // Order Operations
     // ─────────────────────────────────────────────────────────────────────────
@@ -519,6 +868,36 @@ impl CustomerClient {
             .collect())
     }
 
+    /// Get full details of one of the customer's orders.
+    ///
+    /// Returns `None` if the order doesn't exist or belongs to another customer.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - The customer's access token
+    /// * `order_id` - The order GID
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    pub async fn get_order(
+        &self,
+        access_token: &str,
+        order_id: &str,
+    ) -> Result<Option<CustomerOrder>, ShopifyError> {
+        #[derive(Deserialize)]
+        struct Response {
+            order: Option<OrderDetailNode>,
+        }
+
+        let variables = serde_json::json!({ "id": order_id });
+        let response: Response = self
+            .query(access_token, GET_ORDER_QUERY, Some(variables))
+            .await?;
+
+        Ok(response.order.map(CustomerOrder::from))
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // Address Operations
     // ─────────────────────────────────────────────────────────────────────────
@@ -793,4 +1172,117 @@ impl CustomerClient {
 
         Ok(())
     }
+
+    /// Make an address the customer's default.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails or if there are validation errors.
+    pub async fn set_default_address(
+        &self,
+        access_token: &str,
+        address_id: &str,
+    ) -> Result<(), ShopifyError> {
+        #[derive(Deserialize)]
+        struct Response {
+            #[serde(rename = "customerAddressUpdate")]
+            address_update: DefaultAddressResult,
+        }
+
+        #[derive(Deserialize)]
+        struct DefaultAddressResult {
+            #[serde(rename = "userErrors")]
+            user_errors: Vec<CustomerUserError>,
+        }
+
+        const QUERY: &str = r"
+            mutation setDefaultAddress($addressId: ID!) {
+                customerAddressUpdate(addressId: $addressId, defaultAddress: true) {
+                    userErrors {
+                        field
+                        message
+                        code
+                    }
+                }
+            }
+        ";
+
+        let variables = serde_json::json!({ "addressId": address_id });
+        let response: Response = self.query(access_token, QUERY, Some(variables)).await?;
+
+        if !response.address_update.user_errors.is_empty() {
+            let messages: Vec<_> = response
+                .address_update
+                .user_errors
+                .iter()
+                .map(|e| e.message.as_str())
+                .collect();
+            return Err(ShopifyError::UserError(messages.join(", ")));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkce_challenge_rfc7636_vector() {
+        // Example from RFC 7636 Appendix B
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn test_order_detail_sums_quantities_per_line_item() {
+        let line_item = |id: &str, quantity: i64| serde_json::json!({ "quantity": quantity, "lineItem": { "id": id } });
+        let order: OrderDetailNode = serde_json::from_value(serde_json::json!({
+            "id": "gid://shopify/Order/1",
+            "name": "#1001",
+            "financialStatus": "PAID",
+            "fulfillmentStatus": "PARTIALLY_FULFILLED",
+            "createdAt": "2024-01-15T10:00:00Z",
+            "lineItems": { "nodes": [
+                { "id": "li-1", "title": "Pineapple", "quantity": 3 },
+                { "id": "li-2", "title": "Mango", "quantity": 1 }
+            ] },
+            "totalPrice": { "amount": "30.00", "currency_code": "USD" },
+            "discountApplications": { "nodes": [{ "code": "SAVE10" }, {}] },
+            "fulfillments": { "nodes": [
+                {
+                    "trackingInformation": [{ "number": "1Z999" }, {}],
+                    "fulfillmentLineItems": { "nodes": [line_item("li-1", 1)] }
+                },
+                {
+                    "trackingInformation": [],
+                    "fulfillmentLineItems": { "nodes": [line_item("li-1", 1)] }
+                }
+            ] },
+            "returnInformation": {
+                "returnableLineItems": { "nodes": [line_item("li-1", 2)] }
+            }
+        }))
+        .expect("valid order");
+
+        let order = CustomerOrder::from(order);
+
+        let quantities: Vec<_> = order
+            .line_items
+            .iter()
+            .map(|item| {
+                (
+                    item.id.as_str(),
+                    item.fulfilled_quantity,
+                    item.returnable_quantity,
+                )
+            })
+            .collect();
+        assert_eq!(quantities, [("li-1", 2, 2), ("li-2", 0, 0)]);
+        assert_eq!(order.discount_codes, ["SAVE10"]);
+        assert_eq!(order.tracking.len(), 1);
+    }
 }
@@ -3,7 +3,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::shopify::types::Money;
+use crate::shopify::types::{Image, Money};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // OAuth Types
@@ -183,6 +183,12 @@ pub struct Order {
 }
 
 impl Order {
+    /// The numeric part of the order ID, used in account URLs.
+    #[must_use]
+    pub fn numeric_id(&self) -> &str {
+        self.id.rsplit('/').next().unwrap_or(&self.id)
+    }
+
     /// Parse the `processed_at` timestamp.
     #[must_use]
     pub fn processed_at_datetime(&self) -> Option<DateTime<Utc>> {
@@ -192,6 +198,144 @@ impl Order {
     }
 }
 
+/// Full details of a single customer order.
+#[derive(Debug, Clone)]
+pub struct CustomerOrder {
+    /// The order ID.
+    pub id: String,
+    /// The order name (e.g., "#1001").
+    pub name: String,
+    /// The financial status.
+    pub financial_status: Option<String>,
+    /// The fulfillment status.
+    pub fulfillment_status: String,
+    /// When the order was created.
+    pub created_at: String,
+    /// Line items with fulfillment and return details.
+    pub line_items: Vec<CustomerOrderLineItem>,
+    /// Shipping address.
+    pub shipping_address: Option<Address>,
+    /// Shipping method.
+    pub shipping_line: Option<OrderShippingLine>,
+    /// Subtotal before shipping and taxes.
+    pub subtotal: Option<Money>,
+    /// Total tax.
+    pub total_tax: Option<Money>,
+    /// Total price.
+    pub total_price: Money,
+    /// Discount codes applied to the order.
+    pub discount_codes: Vec<String>,
+    /// Tracking numbers across all fulfillments.
+    pub tracking: Vec<OrderTracking>,
+}
+
+impl CustomerOrder {
+    /// Parse the `created_at` timestamp.
+    #[must_use]
+    pub fn created_at_datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.created_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+}
+
+/// A line item on a customer order.
+#[derive(Debug, Clone)]
+pub struct CustomerOrderLineItem {
+    /// The line item ID.
+    pub id: String,
+    /// Product title.
+    pub title: String,
+    /// Variant title.
+    pub variant_title: Option<String>,
+    /// Variant image.
+    pub image: Option<Image>,
+    /// Quantity ordered.
+    pub quantity: i64,
+    /// Unit price.
+    pub price: Option<Money>,
+    /// Quantity shipped so far.
+    pub fulfilled_quantity: i64,
+    /// Quantity that can still be returned.
+    pub returnable_quantity: i64,
+}
+
+/// The shipping method on an order.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderShippingLine {
+    /// Shipping method title.
+    pub title: String,
+    /// Shipping price.
+    #[serde(rename = "originalPrice")]
+    pub price: Money,
+}
+
+/// A shipment tracking number.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderTracking {
+    /// Carrier name.
+    pub company: Option<String>,
+    /// Tracking number.
+    pub number: Option<String>,
+    /// Tracking URL provided by the carrier integration.
+    pub url: Option<String>,
+}
+
+/// Carrier tracking URL templates, keyed by lowercase carrier name.
+///
+/// `{}` is replaced with the tracking number.
+const CARRIER_TRACKING_URLS: &[(&str, &str)] = &[
+    (
+        "usps",
+        "https://tools.usps.com/go/TrackConfirmAction?tLabels={}",
+    ),
+    ("ups", "https://www.ups.com/track?tracknum={}"),
+    ("fedex", "https://www.fedex.com/fedextrack/?trknbr={}"),
+    ("dhl", "https://www.dhl.com/en/express/tracking.html?AWB={}"),
+    (
+        "dhl express",
+        "https://www.dhl.com/en/express/tracking.html?AWB={}",
+    ),
+    (
+        "dhl ecommerce",
+        "https://webtrack.dhlecs.com/?trackingnumber={}",
+    ),
+    (
+        "canada post",
+        "https://www.canadapost-postescanada.ca/track-reperage/en#/search?searchFor={}",
+    ),
+    (
+        "royal mail",
+        "https://www.royalmail.com/track-your-item#/tracking-results/{}",
+    ),
+    (
+        "australia post",
+        "https://auspost.com.au/mypost/track/#/details/{}",
+    ),
+    ("ontrac", "https://www.ontrac.com/tracking/?number={}"),
+    ("lasership", "https://www.lasership.com/track/{}"),
+];
+
+impl OrderTracking {
+    /// URL to track this shipment.
+    ///
+    /// Prefers the URL Shopify provides, falling back to the carrier's
+    /// tracking page when the carrier is known.
+    #[must_use]
+    pub fn tracking_url(&self) -> Option<String> {
+        if let Some(url) = self.url.as_ref().filter(|u| !u.is_empty()) {
+            return Some(url.clone());
+        }
+
+        let number = self.number.as_deref()?;
+        let company = self.company.as_deref()?.trim().to_lowercase();
+        CARRIER_TRACKING_URLS
+            .iter()
+            .find(|(carrier, _)| *carrier == company)
+            .map(|(_, template)| template.replace("{}", &urlencoding::encode(number)))
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Input Types
 // ─────────────────────────────────────────────────────────────────────────────
@@ -258,6 +402,5 @@ pub(super) struct CustomerUserError {
     #[allow(dead_code)]
     pub field: Option<Vec<String>>,
     pub message: String,
-    #[allow(dead_code)]
     pub code: Option<String>,
 }
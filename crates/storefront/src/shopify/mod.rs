@@ -46,11 +46,14 @@ pub mod customer;
 mod storefront;
 pub mod types;
 
-pub use customer::{CustomerAccessToken, CustomerClient};
+pub use customer::{CustomerAccessToken, CustomerClient, pkce_challenge};
 pub use storefront::queries::get_collection_by_handle::{
     PriceRangeFilter, ProductCollectionSortKeys, ProductFilter,
 };
-pub use storefront::{StorefrontAccessToken, StorefrontClient, StorefrontCustomer};
+pub use storefront::{
+    COLLECTION_LISTINGS_TAG, PRODUCT_LISTINGS_TAG, StorefrontAccessToken, StorefrontClient,
+    StorefrontCustomer, collection_tag, product_tag,
+};
 pub use types::*;
 
 use thiserror::Error;
@@ -85,6 +88,11 @@ pub enum ShopifyError {
     /// OAuth error (token exchange, refresh, etc.).
     #[error("OAuth error: {0}")]
     OAuth(String),
+
+    /// The change was accepted but must be confirmed from a verification
+    /// email before it takes effect.
+    #[error("Email verification required")]
+    EmailVerificationRequired,
 }
 
 /// A GraphQL error returned by the Shopify API.
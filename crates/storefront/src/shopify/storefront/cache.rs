@@ -1,14 +1,39 @@
-//! Cache types for Storefront API responses.
+//! Cache for Storefront API responses.
+//!
+//! Every entry is tagged with the resources it contains so a webhook for one
+//! product or collection can evict every page, listing and bundle that shows
+//! it. Tags are resource GIDs (stable across handle changes) plus the
+//! handle-based tags from [`product_tag`] and [`collection_tag`].
 
-use crate::shopify::types::{Collection, CollectionConnection, Product, ProductConnection};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-/// Cache key for products and collections.
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-pub enum CacheKey {
-    Product(String),
-    Products { cursor: Option<String> },
-    Collection(String),
-    Collections { cursor: Option<String> },
+use moka::future::Cache;
+
+use crate::shopify::types::{
+    Collection, CollectionConnection, Metaobject, Product, ProductBundle, ProductConnection,
+    ShopLocalization,
+};
+
+/// Tag on every cached product listing page (evicted when products are created).
+pub const PRODUCT_LISTINGS_TAG: &str = "products";
+
+/// Tag on every cached collection listing page.
+pub const COLLECTION_LISTINGS_TAG: &str = "collections";
+
+/// Maximum number of cached responses.
+const MAX_CAPACITY: u64 = 1000;
+
+/// Tag for everything that shows the product with the given handle.
+#[must_use]
+pub fn product_tag(handle: &str) -> String {
+    format!("product:{handle}")
+}
+
+/// Tag for everything that shows the collection with the given handle.
+#[must_use]
+pub fn collection_tag(handle: &str) -> String {
+    format!("collection:{handle}")
 }
 
 /// Cached value types.
@@ -16,6 +41,140 @@ pub enum CacheKey {
 pub enum CacheValue {
     Product(Box<Product>),
     Products(ProductConnection),
+    Recommendations(Vec<Product>),
     Collection(Box<Collection>),
     Collections(CollectionConnection),
+    Localization(Box<ShopLocalization>),
+    /// `None` records that the product is not a bundle.
+    Bundle(Option<Box<ProductBundle>>),
+    Metaobjects(Vec<Metaobject>),
+}
+
+impl CacheValue {
+    /// Tags for the resources this value contains.
+    fn tags(&self) -> Vec<String> {
+        let mut tags = Vec::new();
+        match self {
+            Self::Product(product) => push_product_tags(&mut tags, product),
+            Self::Products(connection) => {
+                tags.push(PRODUCT_LISTINGS_TAG.to_string());
+                for product in &connection.products {
+                    push_product_tags(&mut tags, product);
+                }
+            }
+            Self::Recommendations(products) => {
+                for product in products {
+                    push_product_tags(&mut tags, product);
+                }
+            }
+            Self::Collection(collection) => push_collection_tags(&mut tags, collection),
+            Self::Collections(connection) => {
+                tags.push(COLLECTION_LISTINGS_TAG.to_string());
+                for collection in &connection.collections {
+                    push_collection_tags(&mut tags, collection);
+                }
+            }
+            Self::Localization(_) | Self::Bundle(None) => {}
+            Self::Metaobjects(metaobjects) => {
+                for product in metaobjects
+                    .iter()
+                    .flat_map(|m| &m.fields)
+                    .filter_map(|f| f.product.as_ref())
+                {
+                    tags.push(product.id.clone());
+                    tags.push(product_tag(&product.handle));
+                }
+            }
+            Self::Bundle(Some(bundle)) => {
+                push_product_tags(&mut tags, &bundle.product);
+                for component in &bundle.components {
+                    push_product_tags(&mut tags, &component.product);
+                }
+            }
+        }
+        tags
+    }
+}
+
+fn push_product_tags(tags: &mut Vec<String>, product: &Product) {
+    tags.push(product.id.clone());
+    tags.push(product_tag(&product.handle));
+}
+
+fn push_collection_tags(tags: &mut Vec<String>, collection: &Collection) {
+    tags.push(collection.id.clone());
+    tags.push(collection_tag(&collection.handle));
+    for product in &collection.products {
+        push_product_tags(tags, product);
+    }
+}
+
+/// A cached response and the tags it can be evicted by.
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    value: CacheValue,
+    tags: Arc<[String]>,
+    cached_at: Instant,
+}
+
+/// Tag-aware response cache shared by all [`super::StorefrontClient`] clones.
+#[derive(Clone)]
+pub struct ResponseCache {
+    entries: Cache<String, CacheEntry>,
+}
+
+impl ResponseCache {
+    /// Create a cache whose entries expire after `ttl`.
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Cache::builder()
+                .max_capacity(MAX_CAPACITY)
+                .time_to_live(ttl)
+                .support_invalidation_closures()
+                .build(),
+        }
+    }
+
+    /// Get a cached value.
+    pub async fn get(&self, key: &str) -> Option<CacheValue> {
+        let entry = self.entries.get(key).await?;
+        tracing::trace!(
+            key,
+            age_ms = u64::try_from(entry.cached_at.elapsed().as_millis()).unwrap_or(u64::MAX),
+            "Storefront cache hit"
+        );
+        Some(entry.value)
+    }
+
+    /// Cache a value, tagged with the resources it contains plus `tags`.
+    pub async fn insert_with_tags(&self, key: String, value: CacheValue, mut tags: Vec<String>) {
+        tags.extend(value.tags());
+        tags.sort_unstable();
+        tags.dedup();
+
+        let entry = CacheEntry {
+            value,
+            tags: tags.into(),
+            cached_at: Instant::now(),
+        };
+        self.entries.insert(key, entry).await;
+    }
+
+    /// Evict every entry tagged with `tag`.
+    pub fn invalidate_by_tag(&self, tag: &str) {
+        let tag = tag.to_string();
+        if let Err(e) = self
+            .entries
+            .invalidate_entries_if(move |_, entry| entry.tags.contains(&tag))
+        {
+            tracing::warn!(error = %e, "Failed to invalidate cached Storefront responses");
+        }
+    }
+
+    /// Evict every entry.
+    pub async fn invalidate_all(&self) {
+        self.entries.invalidate_all();
+        self.entries.run_pending_tasks().await;
+    }
 }
@@ -5,12 +5,12 @@ use tracing::warn;
 use crate::shopify::types::{
     Attribute, Cart, CartBuyerIdentity, CartCost, CartCustomer, CartDiscountCode, CartLine,
     CartLineCost, CartMerchandise, CartMerchandiseProduct, CartUserError, DiscountAllocation,
-    Image, Money, SelectedOption,
+    Image, Money, SelectedOption, ShippingRate,
 };
 
 use super::super::queries::{
-    add_to_cart, create_cart, get_cart, remove_from_cart, update_cart_discount_codes,
-    update_cart_lines, update_cart_note,
+    add_to_cart, create_cart, estimate_cart_shipping, get_cart, remove_cart_delivery_addresses,
+    remove_from_cart, update_cart_discount_codes, update_cart_lines, update_cart_note,
 };
 
 /// Convert a `CurrencyCode` enum to string.
@@ -1415,3 +1415,61 @@ pub fn convert_note_user_error(error: update_cart_note::CartUserErrorFields) ->
         message: error.message,
     }
 }
+
+pub fn convert_estimate_shipping_user_error(
+    error: estimate_cart_shipping::CartUserErrorFields,
+) -> CartUserError {
+    CartUserError {
+        code: error.code.map(|c| format!("{c:?}")),
+        field: error.field,
+        message: error.message,
+    }
+}
+
+pub fn convert_remove_delivery_address_user_error(
+    error: remove_cart_delivery_addresses::CartUserErrorFields,
+) -> CartUserError {
+    CartUserError {
+        code: error.code.map(|c| format!("{c:?}")),
+        field: error.field,
+        message: error.message,
+    }
+}
+
+// =============================================================================
+// Shipping Estimate Conversions
+// =============================================================================
+
+pub fn convert_shipping_rate(
+    option: estimate_cart_shipping::EstimateCartShippingCartDeliveryAddressesAddCartDeliveryGroupsEdgesNodeDeliveryOptions,
+) -> ShippingRate {
+    let estimated_delivery_days = option
+        .description
+        .as_deref()
+        .and_then(parse_delivery_days)
+        .or_else(|| option.title.as_deref().and_then(parse_delivery_days));
+
+    ShippingRate {
+        title: option.title.unwrap_or_else(|| option.handle.clone()),
+        handle: option.handle,
+        price: Money {
+            amount: option.estimated_cost.amount,
+            currency_code: currency_code_to_string(option.estimated_cost.currency_code),
+        },
+        estimated_delivery_days,
+    }
+}
+
+/// Extract the upper bound of a delivery estimate like "3-5 business days".
+///
+/// Shopify doesn't expose transit times on delivery options, so this relies
+/// on the rate's description or title mentioning a number of days.
+fn parse_delivery_days(text: &str) -> Option<u32> {
+    if !text.to_lowercase().contains("day") {
+        return None;
+    }
+
+    text.split(|c: char| !c.is_ascii_digit())
+        .filter_map(|part| part.parse::<u32>().ok())
+        .max()
+}
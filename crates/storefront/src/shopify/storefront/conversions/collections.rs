@@ -2,7 +2,7 @@
 
 use crate::shopify::types::{
     Collection, CollectionConnection, Image, Money, PageInfo, PriceRange, Product, ProductVariant,
-    Seo,
+    Seo, TagCount,
 };
 
 use super::super::queries::{get_collection_by_handle, get_collections};
@@ -40,9 +40,42 @@ pub fn convert_collection(
             .into_iter()
             .map(|e| convert_collection_product(e.node))
             .collect(),
+        products_page_info: Some(PageInfo {
+            has_next_page: collection.products.page_info.has_next_page,
+            has_previous_page: collection.products.page_info.has_previous_page,
+            start_cursor: collection.products.page_info.start_cursor,
+            end_cursor: collection.products.page_info.end_cursor,
+        }),
+        product_tags: convert_tag_filter(collection.products.filters),
     }
 }
 
+/// Filter ID Shopify uses for the product tag filter.
+const TAG_FILTER_ID: &str = "filter.p.tag";
+
+/// Extract the tag filter values (tag and product count) from a collection's
+/// product filters.
+///
+/// The tag is read from each value's `input` (`{"tag": "..."}`), falling
+/// back to its label.
+fn convert_tag_filter(
+    filters: Vec<get_collection_by_handle::GetCollectionByHandleCollectionProductsFilters>,
+) -> Vec<TagCount> {
+    filters
+        .into_iter()
+        .filter(|f| f.id == TAG_FILTER_ID)
+        .flat_map(|f| f.values)
+        .map(|v| TagCount {
+            tag: v
+                .input
+                .get("tag")
+                .and_then(serde_json::Value::as_str)
+                .map_or(v.label, String::from),
+            count: v.count,
+        })
+        .collect()
+}
+
 fn convert_image_collection(i: get_collection_by_handle::CollectionImageFields) -> Image {
     Image {
         id: i.id,
@@ -232,6 +265,8 @@ fn convert_collections_list_collection(
                 selling_plan_groups: Vec::new(),
             })
             .collect(),
+        products_page_info: None,
+        product_tags: Vec::new(),
     }
 }
 
@@ -0,0 +1,47 @@
+//! Metaobject type conversion functions.
+
+use crate::shopify::types::{Image, Metaobject, MetaobjectField, MetaobjectProduct};
+
+use super::super::queries::get_metaobjects;
+
+// =============================================================================
+// get_metaobjects conversions
+// =============================================================================
+
+pub fn convert_metaobjects(data: get_metaobjects::ResponseData) -> Vec<Metaobject> {
+    use get_metaobjects::GetMetaobjectsMetaobjectsNodesFieldsReference as Reference;
+
+    data.metaobjects
+        .nodes
+        .into_iter()
+        .map(|node| Metaobject {
+            id: node.id,
+            handle: node.handle,
+            type_name: node.type_,
+            fields: node
+                .fields
+                .into_iter()
+                .map(|field| MetaobjectField {
+                    key: field.key,
+                    type_name: field.type_,
+                    value: field.value,
+                    product: match field.reference {
+                        Some(Reference::Product(product)) => Some(MetaobjectProduct {
+                            id: product.id,
+                            handle: product.handle,
+                            title: product.title,
+                            featured_image: product.featured_image.map(|image| Image {
+                                id: None,
+                                url: image.url,
+                                alt_text: image.alt_text,
+                                width: None,
+                                height: None,
+                            }),
+                        }),
+                        _ => None,
+                    },
+                })
+                .collect(),
+        })
+        .collect()
+}
@@ -2,12 +2,20 @@
 
 pub mod cart;
 pub mod collections;
+pub mod metaobjects;
 pub mod products;
+pub mod shop;
 
 pub use cart::{
     CartData, convert_add_user_error, convert_cart, convert_discount_user_error,
-    convert_note_user_error, convert_remove_user_error, convert_update_user_error,
-    convert_user_error,
+    convert_estimate_shipping_user_error, convert_note_user_error,
+    convert_remove_delivery_address_user_error, convert_remove_user_error, convert_shipping_rate,
+    convert_update_user_error, convert_user_error,
 };
 pub use collections::{convert_collection, convert_collection_connection};
-pub use products::{convert_product, convert_product_connection, convert_product_recommendation};
+pub use metaobjects::convert_metaobjects;
+pub use products::{
+    convert_comparison_item, convert_product, convert_product_connection,
+    convert_product_recommendation,
+};
+pub use shop::convert_shop_localization;
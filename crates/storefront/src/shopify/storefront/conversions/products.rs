@@ -1,13 +1,15 @@
 //! Product type conversion functions.
 
 use crate::shopify::types::{
-    Image, InstallmentsCount, Money, PageInfo, PriceRange, Product, ProductConnection,
-    ProductOption, ProductRating, ProductVariant, SelectedOption, SellingPlan, SellingPlanGroup,
-    SellingPlanGroupOption, SellingPlanOption, SellingPlanPriceAdjustment,
+    ComparisonItem, Image, InstallmentsCount, Money, PageInfo, PriceRange, Product,
+    ProductConnection, ProductOption, ProductRating, ProductVariant, SelectedOption, SellingPlan,
+    SellingPlanGroup, SellingPlanGroupOption, SellingPlanOption, SellingPlanPriceAdjustment,
     SellingPlanPriceAdjustmentValue, Seo, ShopPayInstallmentsPricing,
 };
 
-use super::super::queries::{get_product_by_handle, get_product_recommendations, get_products};
+use super::super::queries::{
+    get_compare_variants, get_product_by_handle, get_product_recommendations, get_products,
+};
 
 /// JSON structure for the rating metafield value from Judge.me.
 #[derive(Debug, serde::Deserialize)]
@@ -716,3 +718,50 @@ fn convert_variant_rec(v: get_product_recommendations::ProductVariantFields) ->
             .map(convert_shop_pay_installments_rec),
     }
 }
+
+// =============================================================================
+// get_compare_variants conversions
+// =============================================================================
+
+pub fn convert_comparison_item(
+    v: get_compare_variants::GetCompareVariantsNodesOnProductVariant,
+) -> ComparisonItem {
+    let product = v.product;
+    let parse_list = |m: Option<get_compare_variants::MetafieldFields>| {
+        m.and_then(|m| serde_json::from_str::<Vec<String>>(&m.value).ok())
+            .unwrap_or_default()
+    };
+
+    ComparisonItem {
+        variant_id: v.id,
+        variant_title: v.title,
+        product_handle: product.handle,
+        product_title: product.title,
+        description: product.description,
+        image: v
+            .image
+            .or(product.featured_image)
+            .map(convert_image_compare),
+        price: Money {
+            amount: v.price.amount,
+            currency_code: currency_code_to_string(v.price.currency_code),
+        },
+        sku: v.sku.filter(|sku| !sku.is_empty()),
+        weight: v.weight,
+        weight_unit: format!("{:?}", v.weight_unit),
+        dimensions: v.dimensions.map(|m| m.value),
+        promotes: parse_list(product.promotes),
+        free_from: parse_list(product.free_from),
+        available_for_sale: v.available_for_sale,
+    }
+}
+
+fn convert_image_compare(i: get_compare_variants::ImageFields) -> Image {
+    Image {
+        id: i.id,
+        url: i.url,
+        alt_text: i.alt_text,
+        width: i.width,
+        height: i.height,
+    }
+}
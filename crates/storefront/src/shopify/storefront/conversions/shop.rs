@@ -0,0 +1,42 @@
+//! Shop type conversion functions.
+
+use crate::shopify::types::{LocalizationCountry, LocalizationLanguage, ShopLocalization};
+
+use super::super::queries::get_shop_localization;
+
+/// Convert a generated enum (e.g. `CountryCode`, `LanguageCode`) to string.
+fn enum_to_string<T: std::fmt::Debug>(value: T) -> String {
+    format!("{value:?}")
+}
+
+// =============================================================================
+// get_shop_localization conversions
+// =============================================================================
+
+pub fn convert_shop_localization(data: get_shop_localization::ResponseData) -> ShopLocalization {
+    ShopLocalization {
+        money_format: data.shop.money_format,
+        currency_code: enum_to_string(data.shop.payment_settings.currency_code),
+        countries: data
+            .localization
+            .available_countries
+            .into_iter()
+            .map(|country| LocalizationCountry {
+                country_code: enum_to_string(country.iso_code),
+                name: country.name,
+                currency_code: enum_to_string(country.currency.iso_code),
+                currency_symbol: country.currency.symbol,
+            })
+            .collect(),
+        language_code: enum_to_string(data.localization.language.iso_code),
+        languages: data
+            .localization
+            .available_languages
+            .into_iter()
+            .map(|language| LocalizationLanguage {
+                language_code: enum_to_string(language.iso_code),
+                name: language.endonym_name,
+            })
+            .collect(),
+    }
+}
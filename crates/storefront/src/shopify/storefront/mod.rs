@@ -1,7 +1,12 @@
 //! Shopify Storefront API client implementation.
 //!
 //! Uses `graphql_client` for type-safe queries with `reqwest` 0.13 for HTTP.
-//! Caches products and collections using `moka` (5-minute TTL).
+//! Caches products and collections using `moka` (2-minute TTL by default),
+//! with tag-based invalidation driven by Shopify webhooks.
+//!
+//! Use [`StorefrontClient::with_country`] to localize product and collection
+//! prices to a buyer's country, and [`StorefrontClient::with_language`] to
+//! translate their text, via the `@inContext` directive.
 
 mod cache;
 mod conversions;
@@ -25,37 +30,45 @@ pub type JSON = serde_json::Value;
 pub type UnsignedInt64 = String;
 
 use std::sync::Arc;
-use std::time::Duration;
 
+use futures::future::try_join_all;
 use graphql_client::{GraphQLQuery, Response};
-use moka::future::Cache;
 use secrecy::ExposeSecret;
 use tracing::{debug, instrument};
 
 use crate::config::ShopifyStorefrontConfig;
 use crate::shopify::ShopifyError;
 use crate::shopify::types::{
-    Cart, CartLineInput, CartLineUpdateInput, CartUserError, Collection, CollectionConnection,
-    Product, ProductConnection, ProductRecommendationIntent,
+    BundleComponent, Cart, CartLineInput, CartLineUpdateInput, CartUserError, Collection,
+    CollectionConnection, ComparisonItem, Metaobject, PageInfo, Product, ProductBundle,
+    ProductConnection, ProductRecommendationIntent, ShippingRate, ShopLocalization,
 };
 
-use cache::CacheValue;
+pub use cache::{COLLECTION_LISTINGS_TAG, PRODUCT_LISTINGS_TAG, collection_tag, product_tag};
+use cache::{CacheValue, ResponseCache};
 use conversions::{
     convert_add_user_error, convert_cart, convert_collection, convert_collection_connection,
-    convert_discount_user_error, convert_note_user_error, convert_product,
-    convert_product_connection, convert_product_recommendation, convert_remove_user_error,
+    convert_comparison_item, convert_discount_user_error, convert_estimate_shipping_user_error,
+    convert_metaobjects, convert_note_user_error, convert_product, convert_product_connection,
+    convert_product_recommendation, convert_remove_delivery_address_user_error,
+    convert_remove_user_error, convert_shipping_rate, convert_shop_localization,
     convert_update_user_error, convert_user_error,
 };
 use queries::{
     AddToCart, CreateCart, CustomerAccessTokenCreate, CustomerAccessTokenDelete,
     CustomerAccessTokenRenew, CustomerActivateByUrl, CustomerCreate, CustomerRecover,
-    CustomerResetByUrl, GetCart, GetCollectionByHandle, GetCollections, GetCustomerByToken,
-    GetProductByHandle, GetProductRecommendations, GetProducts, RemoveFromCart,
-    UpdateCartDiscountCodes, UpdateCartLines, UpdateCartNote, add_to_cart, create_cart,
-    customer_access_token_create, customer_access_token_delete, customer_access_token_renew,
-    customer_activate_by_url, customer_create, customer_recover, customer_reset_by_url, get_cart,
-    get_collection_by_handle, get_collections, get_customer_by_token, get_product_by_handle,
-    get_product_recommendations, get_products, remove_from_cart, update_cart_discount_codes,
+    CustomerResetByUrl, EstimateCartShipping, GetCart, GetCartDeliveryAddresses,
+    GetCollectionByHandle, GetCollections, GetCompareVariants, GetCustomerByToken, GetMetaobjects,
+    GetProductBundle, GetProductByHandle, GetProductRecommendations, GetProducts,
+    GetShopLocalization, GetShopName, GetVariantAvailability, RemoveCartDeliveryAddresses,
+    RemoveFromCart, UpdateCartDiscountCodes, UpdateCartLines, UpdateCartNote, add_to_cart,
+    create_cart, customer_access_token_create, customer_access_token_delete,
+    customer_access_token_renew, customer_activate_by_url, customer_create, customer_recover,
+    customer_reset_by_url, estimate_cart_shipping, get_cart, get_cart_delivery_addresses,
+    get_collection_by_handle, get_collections, get_compare_variants, get_customer_by_token,
+    get_metaobjects, get_product_bundle, get_product_by_handle, get_product_recommendations,
+    get_products, get_shop_localization, get_shop_name, get_variant_availability,
+    remove_cart_delivery_addresses, remove_from_cart, update_cart_discount_codes,
     update_cart_lines, update_cart_note,
 };
 
@@ -66,27 +79,32 @@ use queries::{
 /// Client for the Shopify Storefront API.
 ///
 /// Provides type-safe access to products, collections, and cart operations.
-/// Products and collections are cached for 5 minutes.
+/// Products and collections are cached for `cache_ttl` (see
+/// [`ShopifyStorefrontConfig`]); use [`Self::invalidate_by_tag`] to evict
+/// them early when Shopify data changes.
 #[derive(Clone)]
 pub struct StorefrontClient {
     inner: Arc<StorefrontClientInner>,
+    /// Buyer country for localized pricing (ISO 3166-1 alpha-2).
+    country: Option<String>,
+    /// Buyer language for translated content (Shopify `LanguageCode`).
+    language: Option<String>,
+    /// Skip the response cache (for signed-in customers).
+    bypass_cache: bool,
 }
 
 struct StorefrontClientInner {
     client: reqwest::Client,
     endpoint: String,
     access_token: String,
-    cache: Cache<String, CacheValue>,
+    cache: ResponseCache,
 }
 
 impl StorefrontClient {
     /// Create a new Storefront API client.
     #[must_use]
     pub fn new(config: &ShopifyStorefrontConfig) -> Self {
-        let cache = Cache::builder()
-            .max_capacity(1000)
-            .time_to_live(Duration::from_secs(300)) // 5 minutes
-            .build();
+        let cache = ResponseCache::new(config.cache_ttl);
 
         let endpoint = format!(
             "https://{}/api/{}/graphql.json",
@@ -100,9 +118,100 @@ impl StorefrontClient {
                 access_token: config.storefront_private_token.expose_secret().to_string(),
                 cache,
             }),
+            country: None,
+            language: None,
+            bypass_cache: false,
+        }
+    }
+
+    /// Get a client that localizes prices to the given country.
+    ///
+    /// Shares the HTTP client and cache with `self`; cached entries are keyed
+    /// per country. Passing `None` returns prices in the shop's base currency.
+    #[must_use]
+    pub fn with_country(&self, country: Option<&str>) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            country: country.map(str::to_ascii_uppercase),
+            language: self.language.clone(),
+            bypass_cache: self.bypass_cache,
+        }
+    }
+
+    /// Get a client that returns product and collection text in the given
+    /// language.
+    ///
+    /// Shares the HTTP client and cache with `self`; cached entries are keyed
+    /// per language. Passing `None` returns the shop's primary language.
+    #[must_use]
+    pub fn with_language(&self, language: Option<&str>) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            country: self.country.clone(),
+            language: language.map(str::to_ascii_uppercase),
+            bypass_cache: self.bypass_cache,
+        }
+    }
+
+    /// Get a client that neither reads from nor writes to the response cache.
+    ///
+    /// Used for signed-in customers so they always see live data.
+    #[must_use]
+    pub fn without_cache(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            country: self.country.clone(),
+            language: self.language.clone(),
+            bypass_cache: true,
+        }
+    }
+
+    /// Look up a cached response, unless this client bypasses the cache.
+    async fn cached(&self, key: &str) -> Option<CacheValue> {
+        if self.bypass_cache {
+            return None;
+        }
+        self.inner.cache.get(key).await
+    }
+
+    /// Cache a response, unless this client bypasses the cache.
+    async fn store(&self, key: String, value: CacheValue) {
+        self.store_with_tags(key, value, Vec::new()).await;
+    }
+
+    /// Cache a response with extra invalidation tags.
+    async fn store_with_tags(&self, key: String, value: CacheValue, tags: Vec<String>) {
+        if !self.bypass_cache {
+            self.inner.cache.insert_with_tags(key, value, tags).await;
+        }
+    }
+
+    /// Append the buyer country and language (if any) to a cache key.
+    fn localized_key(&self, key: String) -> String {
+        let key = match &self.country {
+            Some(country) => format!("{key}@{country}"),
+            None => key,
+        };
+        match &self.language {
+            Some(language) => format!("{key}~{language}"),
+            None => key,
         }
     }
 
+    /// Convert the buyer country to a query's generated `CountryCode` enum.
+    fn country_variable<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        self.country
+            .as_ref()
+            .and_then(|c| serde_json::from_value(serde_json::Value::String(c.clone())).ok())
+    }
+
+    /// Convert the buyer language to a query's generated `LanguageCode` enum.
+    fn language_variable<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        self.language
+            .as_ref()
+            .and_then(|l| serde_json::from_value(serde_json::Value::String(l.clone())).ok())
+    }
+
     /// Execute a GraphQL query.
     async fn execute<Q: GraphQLQuery>(
         &self,
@@ -243,10 +352,10 @@ impl StorefrontClient {
     /// Returns an error if the product is not found or the API request fails.
     #[instrument(skip(self), fields(handle = %handle))]
     pub async fn get_product_by_handle(&self, handle: &str) -> Result<Product, ShopifyError> {
-        let cache_key = format!("product:{handle}");
+        let cache_key = self.localized_key(format!("product:{handle}"));
 
         // Check cache
-        if let Some(CacheValue::Product(product)) = self.inner.cache.get(&cache_key).await {
+        if let Some(CacheValue::Product(product)) = self.cached(&cache_key).await {
             debug!("Cache hit for product");
             return Ok(*product);
         }
@@ -255,6 +364,8 @@ impl StorefrontClient {
             handle: handle.to_string(),
             image_count: Some(10),
             variant_count: Some(50),
+            country: self.country_variable(),
+            language: self.language_variable(),
         };
 
         let data = self.execute::<GetProductByHandle>(variables).await?;
@@ -266,9 +377,7 @@ impl StorefrontClient {
         let product = convert_product(product_data);
 
         // Cache the result
-        self.inner
-            .cache
-            .insert(cache_key, CacheValue::Product(Box::new(product.clone())))
+        self.store(cache_key, CacheValue::Product(Box::new(product.clone())))
             .await;
 
         Ok(product)
@@ -288,11 +397,15 @@ impl StorefrontClient {
         sort_key: Option<get_products::ProductSortKeys>,
         reverse: Option<bool>,
     ) -> Result<ProductConnection, ShopifyError> {
-        let cache_key = format!("products:{}:{:?}", after.as_deref().unwrap_or(""), query);
+        let cache_key = self.localized_key(format!(
+            "products:{}:{:?}",
+            after.as_deref().unwrap_or(""),
+            query
+        ));
 
         // Check cache (only for default queries without search)
         if query.is_none()
-            && let Some(CacheValue::Products(products)) = self.inner.cache.get(&cache_key).await
+            && let Some(CacheValue::Products(products)) = self.cached(&cache_key).await
         {
             debug!("Cache hit for products");
             return Ok(products);
@@ -304,6 +417,8 @@ impl StorefrontClient {
             query: query.clone(),
             sort_key,
             reverse,
+            country: self.country_variable(),
+            language: self.language_variable(),
         };
 
         let data = self.execute::<GetProducts>(variables).await?;
@@ -312,9 +427,7 @@ impl StorefrontClient {
 
         // Cache if not a search query
         if query.is_none() {
-            self.inner
-                .cache
-                .insert(cache_key, CacheValue::Products(connection.clone()))
+            self.store(cache_key, CacheValue::Products(connection.clone()))
                 .await;
         }
 
@@ -323,6 +436,9 @@ impl StorefrontClient {
 
     /// Get product recommendations.
     ///
+    /// Recommendations are based on Shopify's purchase history and are
+    /// cached per product and intent.
+    ///
     /// # Errors
     ///
     /// Returns an error if the API request fails.
@@ -332,6 +448,14 @@ impl StorefrontClient {
         product_id: &str,
         intent: Option<ProductRecommendationIntent>,
     ) -> Result<Vec<Product>, ShopifyError> {
+        let cache_key = self.localized_key(format!("recommendations:{product_id}:{intent:?}"));
+
+        // Check cache
+        if let Some(CacheValue::Recommendations(products)) = self.cached(&cache_key).await {
+            debug!("Cache hit for product recommendations");
+            return Ok(products);
+        }
+
         let variables = get_product_recommendations::Variables {
             product_id: product_id.to_string(),
             intent: intent.map(|i| match i {
@@ -342,6 +466,8 @@ impl StorefrontClient {
                     get_product_recommendations::ProductRecommendationIntent::COMPLEMENTARY
                 }
             }),
+            country: self.country_variable(),
+            language: self.language_variable(),
         };
 
         let data = self.execute::<GetProductRecommendations>(variables).await?;
@@ -351,13 +477,184 @@ impl StorefrontClient {
             .map(|recs| {
                 recs.into_iter()
                     .map(convert_product_recommendation)
-                    .collect()
+                    .collect::<Vec<_>>()
             })
             .unwrap_or_default();
 
+        // Cache the result
+        self.store(cache_key, CacheValue::Recommendations(products.clone()))
+            .await;
+
         Ok(products)
     }
 
+    /// Get the IDs of the given variants that are currently available for sale.
+    ///
+    /// Not cached - used to check live stock levels (e.g. for back-in-stock
+    /// notifications). IDs that don't resolve to a variant are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self, variant_ids), fields(count = variant_ids.len()))]
+    pub async fn get_available_variant_ids(
+        &self,
+        variant_ids: &[String],
+    ) -> Result<Vec<String>, ShopifyError> {
+        use get_variant_availability::GetVariantAvailabilityNodes;
+
+        // The `nodes` query accepts at most 250 IDs per request
+        const MAX_NODES: usize = 250;
+
+        let mut available = Vec::new();
+        for chunk in variant_ids.chunks(MAX_NODES) {
+            let variables = get_variant_availability::Variables {
+                ids: chunk.to_vec(),
+            };
+
+            let data = self.execute::<GetVariantAvailability>(variables).await?;
+
+            available.extend(
+                data.nodes
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|node| match node {
+                        GetVariantAvailabilityNodes::ProductVariant(v) if v.available_for_sale => {
+                            Some(v.id)
+                        }
+                        _ => None,
+                    }),
+            );
+        }
+
+        Ok(available)
+    }
+
+    /// Get variant details for the product comparison table.
+    ///
+    /// Not cached - the compare list is small and changes often. Results are
+    /// returned in the order of `variant_ids`; IDs that don't resolve to a
+    /// variant are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self, variant_ids), fields(count = variant_ids.len()))]
+    pub async fn get_compare_variants(
+        &self,
+        variant_ids: &[String],
+    ) -> Result<Vec<ComparisonItem>, ShopifyError> {
+        use get_compare_variants::GetCompareVariantsNodes;
+
+        if variant_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let variables = get_compare_variants::Variables {
+            ids: variant_ids.to_vec(),
+            country: self.country_variable(),
+            language: self.language_variable(),
+        };
+
+        let data = self.execute::<GetCompareVariants>(variables).await?;
+
+        Ok(data
+            .nodes
+            .into_iter()
+            .flatten()
+            .filter_map(|node| match node {
+                GetCompareVariantsNodes::ProductVariant(v) => Some(convert_comparison_item(v)),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Get a bundle product and its components by the bundle's handle.
+    ///
+    /// Returns `None` if the product isn't a bundle. Component products are
+    /// loaded concurrently through [`Self::get_product_by_handle`], and each
+    /// component's `selected_variant` starts as the variant configured in the
+    /// bundle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the product is not found or an API request fails.
+    #[instrument(skip(self), fields(handle = %handle))]
+    pub async fn get_product_bundle(
+        &self,
+        handle: &str,
+    ) -> Result<Option<ProductBundle>, ShopifyError> {
+        let cache_key = self.localized_key(format!("bundle:{handle}"));
+
+        if let Some(CacheValue::Bundle(bundle)) = self.cached(&cache_key).await {
+            debug!("Cache hit for product bundle");
+            return Ok(bundle.map(|b| *b));
+        }
+
+        let variables = get_product_bundle::Variables {
+            handle: handle.to_string(),
+            country: self.country_variable(),
+            language: self.language_variable(),
+        };
+
+        let data = self.execute::<GetProductBundle>(variables).await?;
+
+        let product_data = data
+            .product
+            .ok_or_else(|| ShopifyError::NotFound(format!("Product not found: {handle}")))?;
+
+        let bundle_variant = product_data
+            .variants
+            .nodes
+            .into_iter()
+            .next()
+            .filter(|v| v.requires_components && !v.components.nodes.is_empty());
+
+        let bundle = match bundle_variant {
+            Some(variant) => {
+                let components = variant
+                    .components
+                    .nodes
+                    .into_iter()
+                    .map(|component| async move {
+                        let component_product = self
+                            .get_product_by_handle(&component.product_variant.product.handle)
+                            .await?;
+                        let selected_variant = component_product
+                            .variants
+                            .iter()
+                            .find(|v| v.id == component.product_variant.id)
+                            .cloned();
+
+                        Ok::<_, ShopifyError>(BundleComponent {
+                            quantity: u32::try_from(component.quantity).unwrap_or(1),
+                            product: component_product,
+                            selected_variant,
+                        })
+                    });
+                let (product, components) =
+                    tokio::try_join!(self.get_product_by_handle(handle), try_join_all(components))?;
+
+                Some(ProductBundle {
+                    product,
+                    variant_id: variant.id,
+                    components,
+                })
+            }
+            None => None,
+        };
+
+        // Tag by handle too, so a product that becomes a bundle is picked up
+        self.store_with_tags(
+            cache_key,
+            CacheValue::Bundle(bundle.clone().map(Box::new)),
+            vec![product_tag(handle)],
+        )
+        .await;
+
+        Ok(bundle)
+    }
+
     // =========================================================================
     // Collection Methods
     // =========================================================================
@@ -402,21 +699,24 @@ impl StorefrontClient {
                             parts.push(format!("max:{max}"));
                         }
                     }
+                    if let Some(ref tag) = filter.tag {
+                        parts.push(format!("tag:{tag}"));
+                    }
                     parts.join(",")
                 })
                 .collect::<Vec<_>>()
                 .join(";")
         });
-        let cache_key = format!(
+        let cache_key = self.localized_key(format!(
             "collection:{handle}:{}:{}:{}:{}",
             after.as_deref().unwrap_or(""),
             sort_str,
             reverse_str,
             filter_str
-        );
+        ));
 
         // Check cache
-        if let Some(CacheValue::Collection(collection)) = self.inner.cache.get(&cache_key).await {
+        if let Some(CacheValue::Collection(collection)) = self.cached(&cache_key).await {
             debug!("Cache hit for collection");
             return Ok(*collection);
         }
@@ -428,6 +728,8 @@ impl StorefrontClient {
             sort_key,
             reverse,
             filters,
+            country: self.country_variable(),
+            language: self.language_variable(),
         };
 
         // Debug: Log the GraphQL variables being sent
@@ -451,17 +753,69 @@ impl StorefrontClient {
         let collection = convert_collection(collection_data);
 
         // Cache the result
-        self.inner
-            .cache
-            .insert(
-                cache_key,
-                CacheValue::Collection(Box::new(collection.clone())),
-            )
-            .await;
+        self.store(
+            cache_key,
+            CacheValue::Collection(Box::new(collection.clone())),
+        )
+        .await;
 
         Ok(collection)
     }
 
+    /// Get a page of a collection's products that have every one of `tags`.
+    ///
+    /// Each tag becomes a separate `{tag: ...}` product filter, which Shopify
+    /// combines with AND.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the collection is not found or the API request fails.
+    #[instrument(skip(self), fields(handle = %handle))]
+    pub async fn get_collection_products_by_tags(
+        &self,
+        handle: &str,
+        tags: &[String],
+        first: i64,
+        after: Option<String>,
+    ) -> Result<ProductConnection, ShopifyError> {
+        let filters = tags
+            .iter()
+            .map(|tag| get_collection_by_handle::ProductFilter {
+                available: None,
+                category: None,
+                price: None,
+                product_metafield: None,
+                product_type: None,
+                product_vendor: None,
+                tag: Some(tag.clone()),
+                taxonomy_metafield: None,
+                variant_metafield: None,
+                variant_option: None,
+            })
+            .collect::<Vec<_>>();
+
+        let collection = self
+            .get_collection_by_handle(
+                handle,
+                Some(first),
+                after,
+                None,
+                None,
+                (!filters.is_empty()).then_some(filters),
+            )
+            .await?;
+
+        Ok(ProductConnection {
+            products: collection.products,
+            page_info: collection.products_page_info.unwrap_or(PageInfo {
+                has_next_page: false,
+                has_previous_page: false,
+                start_cursor: None,
+                end_cursor: None,
+            }),
+        })
+    }
+
     /// Get a paginated list of collections.
     ///
     /// # Errors
@@ -478,8 +832,7 @@ impl StorefrontClient {
 
         // Check cache (only for default queries)
         if query.is_none()
-            && let Some(CacheValue::Collections(collections)) =
-                self.inner.cache.get(&cache_key).await
+            && let Some(CacheValue::Collections(collections)) = self.cached(&cache_key).await
         {
             debug!("Cache hit for collections");
             return Ok(collections);
@@ -499,21 +852,110 @@ impl StorefrontClient {
 
         // Cache if not a search query
         if query.is_none() {
-            self.inner
-                .cache
-                .insert(cache_key, CacheValue::Collections(connection.clone()))
+            self.store(cache_key, CacheValue::Collections(connection.clone()))
                 .await;
         }
 
         Ok(connection)
     }
 
+    // =========================================================================
+    // Shop Methods
+    // =========================================================================
+
+    /// Get the shop's money format and the countries it sells to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_shop_localization(&self) -> Result<ShopLocalization, ShopifyError> {
+        let cache_key = "shop:localization".to_string();
+
+        // Check cache
+        if let Some(CacheValue::Localization(localization)) = self.cached(&cache_key).await {
+            debug!("Cache hit for shop localization");
+            return Ok(*localization);
+        }
+
+        let data = self
+            .execute::<GetShopLocalization>(get_shop_localization::Variables {})
+            .await?;
+
+        let localization = convert_shop_localization(data);
+
+        // Cache the result
+        self.store(
+            cache_key,
+            CacheValue::Localization(Box::new(localization.clone())),
+        )
+        .await;
+
+        Ok(localization)
+    }
+
+    /// Check that the Storefront API is reachable (uncached).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn ping(&self) -> Result<(), ShopifyError> {
+        self.execute::<GetShopName>(get_shop_name::Variables {})
+            .await?;
+        Ok(())
+    }
+
+    // =========================================================================
+    // Metaobject Methods
+    // =========================================================================
+
+    /// Get metaobjects of one type (e.g., "testimonial").
+    ///
+    /// Only types whose definition has Storefront access enabled are returned;
+    /// other types yield an empty list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API request fails.
+    #[instrument(skip(self))]
+    pub async fn get_metaobjects(
+        &self,
+        type_name: &str,
+        first: i64,
+    ) -> Result<Vec<Metaobject>, ShopifyError> {
+        let cache_key = format!("metaobjects:{type_name}:{first}");
+
+        // Check cache
+        if let Some(CacheValue::Metaobjects(metaobjects)) = self.cached(&cache_key).await {
+            debug!("Cache hit for {type_name} metaobjects");
+            return Ok(metaobjects);
+        }
+
+        let variables = get_metaobjects::Variables {
+            type_: type_name.to_string(),
+            first,
+        };
+        let data = self.execute::<GetMetaobjects>(variables).await?;
+
+        let metaobjects = convert_metaobjects(data);
+
+        // Cache the result
+        self.store(cache_key, CacheValue::Metaobjects(metaobjects.clone()))
+            .await;
+
+        Ok(metaobjects)
+    }
+
     // =========================================================================
     // Cart Methods (not cached - mutable state)
     // =========================================================================
 
     /// Create a new cart.
     ///
+    /// The cart's buyer identity is set to this client's country (see
+    /// [`Self::with_country`]), so it is priced in that market's currency.
+    ///
     /// # Errors
     ///
     /// Returns an error if the cart creation fails or user errors are returned.
@@ -546,7 +988,16 @@ impl StorefrontClient {
                 note,
                 attributes: None,
                 discount_codes: None,
-                buyer_identity: None,
+                buyer_identity: self.country_variable().map(|country_code| {
+                    create_cart::CartBuyerIdentityInput {
+                        country_code: Some(country_code),
+                        company_location_id: None,
+                        customer_access_token: None,
+                        email: None,
+                        phone: None,
+                        preferences: None,
+                    }
+                }),
                 metafields: None,
                 delivery: None,
                 gift_card_codes: None,
@@ -757,13 +1208,17 @@ impl StorefrontClient {
         }]))
     }
 
-    /// Update discount codes on a cart.
+    /// Apply discount codes to a cart.
+    ///
+    /// Replaces the cart's discount codes with `discount_codes`; pass an empty
+    /// list to remove all codes. Unknown or expired codes are not user errors:
+    /// they are kept on the returned cart with `applicable: false`.
     ///
     /// # Errors
     ///
     /// Returns an error if the cart update fails or user errors are returned.
     #[instrument(skip(self, discount_codes), fields(cart_id = %cart_id))]
-    pub async fn update_discount_codes(
+    pub async fn apply_cart_discount(
         &self,
         cart_id: &str,
         discount_codes: Vec<String>,
@@ -837,28 +1292,166 @@ impl StorefrontClient {
         }]))
     }
 
+    /// Estimate shipping rates for a cart delivered to a partial address.
+    ///
+    /// Adds the address to the cart as a one-time delivery address (replacing
+    /// any from a previous estimate) and returns the delivery options Shopify
+    /// calculates for it. Only static rates are returned; carrier-calculated
+    /// rates are resolved at checkout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the country code is invalid, the API request fails,
+    /// or user errors are returned (e.g., unsupported address).
+    #[instrument(skip(self), fields(cart_id = %cart_id))]
+    pub async fn estimate_shipping(
+        &self,
+        cart_id: &str,
+        country_code: &str,
+        province_code: Option<&str>,
+        zip: Option<&str>,
+    ) -> Result<Vec<ShippingRate>, ShopifyError> {
+        let country_code: estimate_cart_shipping::CountryCode =
+            serde_json::from_value(serde_json::Value::String(country_code.to_uppercase()))?;
+
+        self.remove_estimate_addresses(cart_id).await?;
+
+        let variables = estimate_cart_shipping::Variables {
+            cart_id: cart_id.to_string(),
+            addresses: vec![estimate_cart_shipping::CartSelectableAddressInput {
+                address: estimate_cart_shipping::CartAddressInput {
+                    copy_from_customer_address_id: None,
+                    delivery_address: Some(estimate_cart_shipping::CartDeliveryAddressInput {
+                        address1: None,
+                        address2: None,
+                        city: None,
+                        company: None,
+                        country_code: Some(country_code),
+                        first_name: None,
+                        last_name: None,
+                        phone: None,
+                        province_code: province_code.map(str::to_string),
+                        zip: zip.map(str::to_string),
+                    }),
+                },
+                one_time_use: Some(true),
+                selected: Some(true),
+                validation_strategy: None,
+            }],
+        };
+
+        let data = self.execute::<EstimateCartShipping>(variables).await?;
+
+        let Some(result) = data.cart_delivery_addresses_add else {
+            return Err(ShopifyError::GraphQL(vec![super::GraphQLError {
+                message: "Failed to estimate shipping".to_string(),
+                locations: vec![],
+                path: vec![],
+            }]));
+        };
+
+        if !result.user_errors.is_empty() {
+            return Err(ShopifyError::UserError(
+                result
+                    .user_errors
+                    .into_iter()
+                    .map(|e| convert_estimate_shipping_user_error(e).message)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            ));
+        }
+
+        // Every line ships from the same place, so the first delivery group
+        // carries the rates for the whole cart
+        let rates = result
+            .cart
+            .and_then(|cart| cart.delivery_groups.edges.into_iter().next())
+            .map(|edge| {
+                edge.node
+                    .delivery_options
+                    .into_iter()
+                    .map(convert_shipping_rate)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(rates)
+    }
+
+    /// Remove one-time delivery addresses left on a cart by earlier estimates.
+    async fn remove_estimate_addresses(&self, cart_id: &str) -> Result<(), ShopifyError> {
+        let variables = get_cart_delivery_addresses::Variables {
+            cart_id: cart_id.to_string(),
+        };
+
+        let data = self.execute::<GetCartDeliveryAddresses>(variables).await?;
+
+        let address_ids: Vec<String> = data
+            .cart
+            .ok_or_else(|| ShopifyError::NotFound(format!("Cart not found: {cart_id}")))?
+            .delivery
+            .addresses
+            .into_iter()
+            .filter(|a| a.one_time_use)
+            .map(|a| a.id)
+            .collect();
+
+        if address_ids.is_empty() {
+            return Ok(());
+        }
+
+        let variables = remove_cart_delivery_addresses::Variables {
+            cart_id: cart_id.to_string(),
+            address_ids,
+        };
+
+        let data = self
+            .execute::<RemoveCartDeliveryAddresses>(variables)
+            .await?;
+
+        if let Some(result) = data.cart_delivery_addresses_remove
+            && !result.user_errors.is_empty()
+        {
+            return Err(ShopifyError::UserError(
+                result
+                    .user_errors
+                    .into_iter()
+                    .map(|e| convert_remove_delivery_address_user_error(e).message)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            ));
+        }
+
+        Ok(())
+    }
+
     // =========================================================================
     // Cache Management
     // =========================================================================
 
-    /// Invalidate a cached product.
-    pub async fn invalidate_product(&self, handle: &str) {
-        let cache_key = format!("product:{handle}");
-        self.inner.cache.invalidate(&cache_key).await;
+    /// Evict every cached response tagged with `tag`.
+    ///
+    /// Tags are resource GIDs (e.g., `gid://shopify/Product/123`), the
+    /// handle tags from [`product_tag`] and [`collection_tag`], and the
+    /// listing tags [`PRODUCT_LISTINGS_TAG`] and [`COLLECTION_LISTINGS_TAG`].
+    pub fn invalidate_by_tag(&self, tag: &str) {
+        debug!(tag, "Invalidating cached Storefront responses");
+        self.inner.cache.invalidate_by_tag(tag);
     }
 
-    /// Invalidate a cached collection.
-    pub async fn invalidate_collection(&self, handle: &str) {
-        self.inner
-            .cache
-            .invalidate(&format!("collection:{handle}:"))
-            .await;
+    /// Invalidate everything showing a product, in every country.
+    pub fn invalidate_product(&self, handle: &str) {
+        self.invalidate_by_tag(&product_tag(handle));
+    }
+
+    /// Invalidate everything showing a collection, in every country.
+    pub fn invalidate_collection(&self, handle: &str) {
+        self.invalidate_by_tag(&collection_tag(handle));
     }
 
     /// Invalidate all cached data.
     pub async fn invalidate_all(&self) {
-        self.inner.cache.invalidate_all();
-        self.inner.cache.run_pending_tasks().await;
+        self.inner.cache.invalidate_all().await;
     }
 
     // =========================================================================
@@ -45,6 +45,30 @@ pub struct GetProducts;
 )]
 pub struct GetProductRecommendations;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/storefront/schema.json",
+    query_path = "graphql/storefront/queries/products.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetVariantAvailability;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/storefront/schema.json",
+    query_path = "graphql/storefront/queries/products.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetCompareVariants;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/storefront/schema.json",
+    query_path = "graphql/storefront/queries/products.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetProductBundle;
+
 // Collection queries
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -120,6 +144,31 @@ pub struct UpdateCartDiscountCodes;
 )]
 pub struct UpdateCartNote;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/storefront/schema.json",
+    query_path = "graphql/storefront/queries/cart.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetCartDeliveryAddresses;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/storefront/schema.json",
+    query_path = "graphql/storefront/queries/cart.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct RemoveCartDeliveryAddresses;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/storefront/schema.json",
+    query_path = "graphql/storefront/queries/cart.graphql",
+    response_derives = "Debug, Clone",
+    skip_serializing_none
+)]
+pub struct EstimateCartShipping;
+
 // Customer mutations (Storefront API authentication)
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -184,3 +233,29 @@ pub struct CustomerAccessTokenDelete;
     response_derives = "Debug, Clone"
 )]
 pub struct GetCustomerByToken;
+
+// Shop queries
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/storefront/schema.json",
+    query_path = "graphql/storefront/queries/shop.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetShopLocalization;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/storefront/schema.json",
+    query_path = "graphql/storefront/queries/shop.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetShopName;
+
+// Metaobject queries
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/storefront/schema.json",
+    query_path = "graphql/storefront/queries/metaobjects.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetMetaobjects;
@@ -3,6 +3,7 @@
 //! These types provide a clean, ergonomic API separate from the raw
 //! `graphql_client` generated types.
 
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 // =============================================================================
@@ -287,6 +288,115 @@ pub struct Product {
     pub selling_plan_groups: Vec<SellingPlanGroup>,
 }
 
+/// A bundle product and the component variants it is made of.
+///
+/// Shopify models bundles as a parent variant whose `components` list the
+/// variants (and quantities) that make it up.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductBundle {
+    /// The bundle (parent) product.
+    pub product: Product,
+    /// The parent variant that carries the components.
+    pub variant_id: String,
+    /// Products included in the bundle.
+    pub components: Vec<BundleComponent>,
+}
+
+impl ProductBundle {
+    /// Total price of the selected component variants, times their quantities.
+    ///
+    /// Returns `None` if any component has no variant selected, or if the
+    /// prices aren't all decimal amounts in one currency.
+    #[must_use]
+    pub fn total_price(&self) -> Option<Money> {
+        let mut total = Decimal::ZERO;
+        let mut currency_code: Option<&str> = None;
+        for component in &self.components {
+            let price = &component.selected_variant.as_ref()?.price;
+            if *currency_code.get_or_insert(&price.currency_code) != price.currency_code {
+                return None;
+            }
+            let amount = price.amount.parse::<Decimal>().ok()?;
+            total += amount * Decimal::from(component.quantity);
+        }
+
+        Some(Money {
+            amount: total.to_string(),
+            currency_code: currency_code?.to_string(),
+        })
+    }
+
+    /// Whether every component has an in-stock variant selected.
+    #[must_use]
+    pub fn available_for_sale(&self) -> bool {
+        self.components.iter().all(|c| {
+            c.selected_variant
+                .as_ref()
+                .is_some_and(|v| v.available_for_sale)
+        })
+    }
+}
+
+/// One product within a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleComponent {
+    /// How many of this product the bundle includes.
+    pub quantity: u32,
+    /// The component product, with all its variants.
+    pub product: Product,
+    /// The buyer's current selection (defaults to the bundle's configured variant).
+    pub selected_variant: Option<ProductVariant>,
+}
+
+impl BundleComponent {
+    /// Select one of the component product's variants.
+    ///
+    /// Returns `false` (leaving the selection unchanged) if the variant
+    /// doesn't belong to this product.
+    pub fn select_variant(&mut self, variant_id: &str) -> bool {
+        match self.product.variants.iter().find(|v| v.id == variant_id) {
+            Some(variant) => {
+                self.selected_variant = Some(variant.clone());
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A product variant with the details shown in the comparison table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonItem {
+    /// Variant ID.
+    pub variant_id: String,
+    /// Variant title (e.g., "50ml").
+    pub variant_title: String,
+    /// Parent product handle.
+    pub product_handle: String,
+    /// Parent product title.
+    pub product_title: String,
+    /// Plain text product description.
+    pub description: String,
+    /// Variant image, falling back to the product's featured image.
+    pub image: Option<Image>,
+    /// Variant price.
+    pub price: Money,
+    /// Stock keeping unit.
+    pub sku: Option<String>,
+    /// Shipping weight.
+    pub weight: Option<f64>,
+    /// Weight unit (e.g., "GRAMS").
+    pub weight_unit: String,
+    /// Dimensions from the `custom.dimensions` variant metafield.
+    pub dimensions: Option<String>,
+    /// Skin concerns the product promotes (`custom.promotes` metafield).
+    pub promotes: Vec<String>,
+    /// Ingredients the product is free from (`custom.free_from` metafield).
+    pub free_from: Vec<String>,
+    /// Whether the variant is available for sale.
+    pub available_for_sale: bool,
+}
+
 // =============================================================================
 // Collection Types
 // =============================================================================
@@ -314,6 +424,20 @@ pub struct Collection {
     pub image: Option<Image>,
     /// Products in this collection.
     pub products: Vec<Product>,
+    /// Pagination info for `products` (only set when fetched by handle).
+    pub products_page_info: Option<PageInfo>,
+    /// Product tags available to filter by, with the number of matching
+    /// products (only set when fetched by handle).
+    pub product_tags: Vec<TagCount>,
+}
+
+/// A product tag with the number of products that have it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCount {
+    /// Tag value.
+    pub tag: String,
+    /// Number of products with the tag.
+    pub count: i64,
 }
 
 // =============================================================================
@@ -574,6 +698,117 @@ pub struct CartUserError {
     pub message: String,
 }
 
+/// Shipping rate available for a cart's delivery address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShippingRate {
+    /// Delivery option handle (identifies the rate when selecting it).
+    pub handle: String,
+    /// Display title (e.g., "Standard Shipping").
+    pub title: String,
+    /// Estimated shipping cost.
+    pub price: Money,
+    /// Estimated delivery time in days, if the rate describes one.
+    pub estimated_delivery_days: Option<u32>,
+}
+
+// =============================================================================
+// Localization Types
+// =============================================================================
+
+/// A country the shop sells to, with its local currency.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalizationCountry {
+    /// ISO 3166-1 alpha-2 country code (e.g., "CA").
+    pub country_code: String,
+    /// Country name (e.g., "Canada").
+    pub name: String,
+    /// ISO 4217 currency code (e.g., "CAD").
+    pub currency_code: String,
+    /// Currency symbol (e.g., "$").
+    pub currency_symbol: String,
+}
+
+/// A language the shop's content is published in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalizationLanguage {
+    /// Shopify `LanguageCode` (e.g., `FR`, `PT_BR`).
+    pub language_code: String,
+    /// Language name in that language (e.g., "Français").
+    pub name: String,
+}
+
+/// Shop currency and language settings and the markets it sells to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShopLocalization {
+    /// Shop money format (e.g., "${{amount}}").
+    pub money_format: String,
+    /// Shop's base currency code.
+    pub currency_code: String,
+    /// Countries the shop sells to.
+    pub countries: Vec<LocalizationCountry>,
+    /// Shop's primary language code.
+    pub language_code: String,
+    /// Languages the shop is published in, including the primary one.
+    pub languages: Vec<LocalizationLanguage>,
+}
+
+// =============================================================================
+// Metaobject Types
+// =============================================================================
+
+/// A metaobject entry (custom content such as a testimonial or FAQ).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Metaobject {
+    /// Metaobject GID.
+    pub id: String,
+    /// Unique handle within the type.
+    pub handle: String,
+    /// Type handle of the definition (e.g., "testimonial").
+    pub type_name: String,
+    /// Field values.
+    pub fields: Vec<MetaobjectField>,
+}
+
+impl Metaobject {
+    /// Get a field by key.
+    #[must_use]
+    pub fn field(&self, key: &str) -> Option<&MetaobjectField> {
+        self.fields.iter().find(|f| f.key == key)
+    }
+
+    /// Get the value of a field by key.
+    #[must_use]
+    pub fn value(&self, key: &str) -> Option<&str> {
+        self.field(key).and_then(|f| f.value.as_deref())
+    }
+}
+
+/// A field value on a metaobject.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaobjectField {
+    /// Field key.
+    pub key: String,
+    /// Metafield type (e.g., `single_line_text_field`).
+    pub type_name: String,
+    /// Serialized value (`None` if unset).
+    pub value: Option<String>,
+    /// Referenced product, for `product_reference` fields.
+    pub product: Option<MetaobjectProduct>,
+}
+
+/// A product referenced by a metaobject field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaobjectProduct {
+    /// Product GID.
+    pub id: String,
+    /// URL handle.
+    pub handle: String,
+    /// Product title.
+    pub title: String,
+    /// Featured image.
+    pub featured_image: Option<Image>,
+}
+
 // =============================================================================
 // Sort Keys
 // =============================================================================
@@ -647,3 +882,113 @@ pub enum ProductRecommendationIntent {
     /// Complementary products.
     Complementary,
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn variant(id: &str, amount: &str, currency_code: &str) -> ProductVariant {
+        ProductVariant {
+            id: id.to_string(),
+            title: id.to_string(),
+            available_for_sale: true,
+            quantity_available: None,
+            sku: None,
+            barcode: None,
+            price: Money {
+                amount: amount.to_string(),
+                currency_code: currency_code.to_string(),
+            },
+            compare_at_price: None,
+            selected_options: Vec::new(),
+            image: None,
+            shop_pay_installments: None,
+        }
+    }
+
+    fn component(quantity: u32, variants: &[ProductVariant]) -> BundleComponent {
+        let product = serde_json::from_value::<Product>(serde_json::json!({
+            "id": "gid://shopify/Product/1",
+            "handle": "component",
+            "title": "Component",
+            "description": "",
+            "description_html": "",
+            "available_for_sale": true,
+            "product_type": "",
+            "vendor": "",
+            "tags": [],
+            "price_range": {
+                "min_variant_price": { "amount": "0.0", "currency_code": "USD" },
+                "max_variant_price": { "amount": "0.0", "currency_code": "USD" }
+            },
+            "images": [],
+            "options": [],
+            "variants": variants,
+            "promotes": [],
+            "free_from": [],
+            "requires_selling_plan": false,
+            "selling_plan_groups": []
+        }))
+        .unwrap();
+        BundleComponent {
+            quantity,
+            selected_variant: product.variants.first().cloned(),
+            product,
+        }
+    }
+
+    fn bundle(components: Vec<BundleComponent>) -> ProductBundle {
+        let product = components.first().unwrap().product.clone();
+        ProductBundle {
+            product,
+            variant_id: "gid://shopify/ProductVariant/0".to_string(),
+            components,
+        }
+    }
+
+    #[test]
+    fn test_total_price_sums_quantities_exactly() {
+        let bundle = bundle(vec![
+            component(3, &[variant("a", "0.10", "USD")]),
+            component(2, &[variant("b", "19.99", "USD")]),
+        ]);
+        assert_eq!(
+            bundle.total_price(),
+            Some(Money {
+                amount: "40.28".to_string(),
+                currency_code: "USD".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_total_price_needs_every_selection_in_one_currency() {
+        let mut unselected = bundle(vec![component(1, &[variant("a", "5.00", "USD")])]);
+        unselected.components.first_mut().unwrap().selected_variant = None;
+        assert_eq!(unselected.total_price(), None);
+
+        let mixed = bundle(vec![
+            component(1, &[variant("a", "5.00", "USD")]),
+            component(1, &[variant("b", "5.00", "CAD")]),
+        ]);
+        assert_eq!(mixed.total_price(), None);
+    }
+
+    #[test]
+    fn test_select_variant_only_accepts_the_products_variants() {
+        let mut component = component(
+            1,
+            &[
+                variant("small", "5.00", "USD"),
+                variant("large", "8.00", "USD"),
+            ],
+        );
+
+        assert!(component.select_variant("large"));
+        assert_eq!(component.selected_variant.as_ref().unwrap().id, "large");
+
+        assert!(!component.select_variant("other"));
+        assert_eq!(component.selected_variant.as_ref().unwrap().id, "large");
+    }
+}
@@ -10,6 +10,7 @@ use webauthn_rs::prelude::*;
 use crate::config::StorefrontConfig;
 use crate::content::{ContentError, ContentStore};
 use crate::search::SearchIndex;
+use crate::services::{AbTestError, AbTestService, CartService, SessionCleanupStats};
 use crate::shopify::{CustomerClient, StorefrontClient};
 
 /// Error creating application state.
@@ -23,6 +24,8 @@ pub enum AppStateError {
     WebAuthn(#[from] WebauthnError),
     #[error("content error: {0}")]
     Content(#[from] ContentError),
+    #[error("experiments error: {0}")]
+    AbTest(#[from] AbTestError),
 }
 
 /// Application state shared across all handlers.
@@ -37,11 +40,15 @@ pub struct AppState {
 struct AppStateInner {
     config: StorefrontConfig,
     pool: PgPool,
+    read_pool: Option<PgPool>,
     storefront: StorefrontClient,
     customer: CustomerClient,
+    carts: CartService,
     webauthn: Webauthn,
     content: ContentStore,
+    ab_tests: AbTestService,
     search: SearchIndex,
+    session_cleanup: SessionCleanupStats,
 }
 
 impl AppState {
@@ -51,31 +58,40 @@ impl AppState {
     ///
     /// * `config` - Storefront configuration
     /// * `pool` - `PostgreSQL` connection pool
-    /// * `content_dir` - Path to content directory for markdown files
+    /// * `read_pool` - Read replica connection pool (reads use `pool` if `None`)
+    /// * `content_dir` - Path to content directory for markdown files and `experiments.toml`
     ///
     /// # Errors
     ///
-    /// Returns an error if the `WebAuthn` configuration is invalid or content fails to load.
+    /// Returns an error if the `WebAuthn` configuration is invalid or content or
+    /// experiments fail to load.
     pub fn new(
         config: StorefrontConfig,
         pool: PgPool,
+        read_pool: Option<PgPool>,
         content_dir: &Path,
     ) -> Result<Self, AppStateError> {
         let storefront = StorefrontClient::new(&config.shopify);
         let customer = CustomerClient::new(&config.shopify);
+        let carts = CartService::new(pool.clone(), storefront.clone());
         let webauthn = create_webauthn(&config)?;
         let content = ContentStore::load(content_dir)?;
+        let ab_tests = AbTestService::load(&content_dir.join("experiments.toml"))?;
         let search = SearchIndex::new();
 
         Ok(Self {
             inner: Arc::new(AppStateInner {
                 config,
                 pool,
+                read_pool,
                 storefront,
                 customer,
+                carts,
                 webauthn,
                 content,
+                ab_tests,
                 search,
+                session_cleanup: SessionCleanupStats::default(),
             }),
         })
     }
@@ -92,6 +108,14 @@ impl AppState {
         &self.inner.pool
     }
 
+    /// Get a reference to the read-only connection pool.
+    ///
+    /// Returns the read replica pool if configured, otherwise the primary pool.
+    #[must_use]
+    pub fn read_pool(&self) -> &PgPool {
+        self.inner.read_pool.as_ref().unwrap_or(&self.inner.pool)
+    }
+
     /// Get a reference to the Shopify Storefront API client.
     #[must_use]
     pub fn storefront(&self) -> &StorefrontClient {
@@ -104,6 +128,12 @@ impl AppState {
         &self.inner.customer
     }
 
+    /// Get a reference to the persistent cart service.
+    #[must_use]
+    pub fn carts(&self) -> &CartService {
+        &self.inner.carts
+    }
+
     /// Get a reference to the `WebAuthn` configuration.
     #[must_use]
     pub fn webauthn(&self) -> &Webauthn {
@@ -116,12 +146,24 @@ impl AppState {
         &self.inner.content
     }
 
+    /// Get a reference to the configured A/B test experiments.
+    #[must_use]
+    pub fn ab_tests(&self) -> &AbTestService {
+        &self.inner.ab_tests
+    }
+
     /// Get a reference to the search index.
     #[must_use]
     pub fn search(&self) -> &SearchIndex {
         &self.inner.search
     }
 
+    /// Get the result of the last expired session cleanup.
+    #[must_use]
+    pub fn session_cleanup(&self) -> &SessionCleanupStats {
+        &self.inner.session_cleanup
+    }
+
     /// Start building the search index asynchronously.
     ///
     /// This spawns a background task that fetches products/collections from Shopify
@@ -37,7 +37,10 @@ async function registerPasskey(name = 'Passkey') {
         // Start registration - get challenge from server
         const startResponse = await fetch('/api/auth/webauthn/register/start', {
             method: 'POST',
-            headers: { 'Content-Type': 'application/json' },
+            headers: {
+                'Content-Type': 'application/json',
+                'X-CSRF-Token': window.csrfToken,
+            },
             body: JSON.stringify({ name }),
         });
 
@@ -92,7 +95,10 @@ async function registerPasskey(name = 'Passkey') {
         // Finish registration - send credential to server
         const finishResponse = await fetch('/api/auth/webauthn/register/finish', {
             method: 'POST',
-            headers: { 'Content-Type': 'application/json' },
+            headers: {
+                'Content-Type': 'application/json',
+                'X-CSRF-Token': window.csrfToken,
+            },
             body: JSON.stringify({
                 credential: credentialResponse,
                 name,
@@ -147,7 +153,10 @@ async function loginWithPasskey(email) {
         // Start authentication - get challenge from server
         const startResponse = await fetch('/api/auth/webauthn/authenticate/start', {
             method: 'POST',
-            headers: { 'Content-Type': 'application/json' },
+            headers: {
+                'Content-Type': 'application/json',
+                'X-CSRF-Token': window.csrfToken,
+            },
             body: JSON.stringify({ email }),
         });
 
@@ -199,7 +208,10 @@ async function loginWithPasskey(email) {
         // Finish authentication - send credential to server
         const finishResponse = await fetch('/api/auth/webauthn/authenticate/finish', {
             method: 'POST',
-            headers: { 'Content-Type': 'application/json' },
+            headers: {
+                'Content-Type': 'application/json',
+                'X-CSRF-Token': window.csrfToken,
+            },
             body: JSON.stringify({ credential: credentialResponse }),
         });
 
@@ -0,0 +1,71 @@
+//! Development tasks for Naked Pineapple.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # Download the Admin and Storefront API GraphQL schemas
+//! cargo xtask update-schema
+//!
+//! # Download only the Admin API schema
+//! cargo xtask update-schema admin
+//! ```
+//!
+//! # Environment Variables
+//!
+//! - `SHOPIFY_STORE` - Store domain (e.g., `your-store.myshopify.com`)
+//! - `SHOPIFY_API_VERSION` - API version to download (default: `2026-01`)
+//! - `SHOPIFY_ADMIN_OAUTH_TOKEN` - Admin API token (from the admin OAuth flow)
+//! - `SHOPIFY_STOREFRONT_PRIVATE_TOKEN` - Storefront API private token
+
+// Terminal output is this tool's whole interface
+#![allow(clippy::print_stdout, clippy::print_stderr)]
+
+// Freshness checks are only used by the build scripts
+#[allow(dead_code)]
+mod schema;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use schema::{ADMIN, STOREFRONT, SchemaSource};
+
+/// API version used when `SHOPIFY_API_VERSION` is not set.
+const DEFAULT_API_VERSION: &str = "2026-01";
+
+const USAGE: &str = "Usage: cargo xtask update-schema [admin|storefront]";
+
+fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let sources: &[SchemaSource] = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["update-schema"] => &[ADMIN, STOREFRONT],
+        ["update-schema", "admin"] => &[ADMIN],
+        ["update-schema", "storefront"] => &[STOREFRONT],
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let version = std::env::var("SHOPIFY_API_VERSION")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| DEFAULT_API_VERSION.to_string());
+
+    for source in sources {
+        println!("Downloading {} schema ({version})...", source.name);
+        if let Err(e) = source.update(&version, &workspace_root().join(source.path)) {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+        println!("Schema saved to {}", source.path);
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// The workspace root (this crate lives in `crates/xtask`).
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
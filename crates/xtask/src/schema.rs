@@ -0,0 +1,162 @@
+//! Shopify GraphQL schema download.
+//!
+//! Shared by `cargo xtask update-schema` and the admin and storefront build
+//! scripts (which include this file with `#[path]`), so it only depends on
+//! `std` and `serde_json`. Requests go through `curl` to keep an HTTP client
+//! out of the build dependencies.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+
+/// Environment variable that opts a build into checking stale schemas.
+pub const FETCH_ENV: &str = "FETCH_SHOPIFY_SCHEMA";
+
+/// Schemas older than this are checked when [`FETCH_ENV`] is set.
+pub const MAX_AGE: Duration = Duration::from_hours(30 * 24);
+
+/// Standard introspection query, as used by `graphql_client`.
+const INTROSPECTION_QUERY: &str = "{ __schema { queryType { name } mutationType { name } \
+    subscriptionType { name } types { ...FullType } directives { name description locations \
+    args { ...InputValue } } } } fragment FullType on __Type { kind name description \
+    fields(includeDeprecated: true) { name description args { ...InputValue } type { ...TypeRef } \
+    isDeprecated deprecationReason } inputFields { ...InputValue } interfaces { ...TypeRef } \
+    enumValues(includeDeprecated: true) { name description isDeprecated deprecationReason } \
+    possibleTypes { ...TypeRef } } fragment InputValue on __InputValue { name description \
+    type { ...TypeRef } defaultValue } fragment TypeRef on __Type { kind name ofType { kind name \
+    ofType { kind name ofType { kind name ofType { kind name ofType { kind name ofType { kind name \
+    ofType { kind name } } } } } } } }";
+
+/// A Shopify API whose schema is checked in.
+pub struct SchemaSource {
+    /// Name used in messages.
+    pub name: &'static str,
+    /// Schema path, relative to the workspace root.
+    pub path: &'static str,
+    /// Header carrying the access token.
+    pub token_header: &'static str,
+    /// Environment variable holding the access token.
+    pub token_env: &'static str,
+    /// Endpoint path between the store domain and the API version.
+    api_path: &'static str,
+}
+
+/// The Admin API schema used by `crates/admin`.
+pub const ADMIN: SchemaSource = SchemaSource {
+    name: "Admin API",
+    path: "crates/admin/graphql/admin/schema.json",
+    token_header: "X-Shopify-Access-Token",
+    token_env: "SHOPIFY_ADMIN_OAUTH_TOKEN",
+    api_path: "admin/api",
+};
+
+/// The Storefront API schema used by `crates/storefront`.
+pub const STOREFRONT: SchemaSource = SchemaSource {
+    name: "Storefront API",
+    path: "crates/storefront/graphql/storefront/schema.json",
+    token_header: "Shopify-Storefront-Private-Token",
+    token_env: "SHOPIFY_STOREFRONT_PRIVATE_TOKEN",
+    api_path: "api",
+};
+
+impl SchemaSource {
+    /// GraphQL endpoint for `store` at API `version`.
+    #[must_use]
+    pub fn url(&self, store: &str, version: &str) -> String {
+        format!("https://{store}/{}/{version}/graphql.json", self.api_path)
+    }
+
+    /// Download the schema and write it to `path`.
+    ///
+    /// Reads `SHOPIFY_STORE` and the source's token from the environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming the missing variable, or describing why the
+    /// request failed.
+    pub fn update(&self, version: &str, path: &Path) -> Result<(), String> {
+        let store = required_env("SHOPIFY_STORE")?;
+        let token = required_env(self.token_env)?;
+
+        let schema = fetch(&self.url(&store, version), self.token_header, &token)
+            .map_err(|e| format!("failed to fetch the {} schema: {e}", self.name))?;
+        fs::write(path, schema).map_err(|e| format!("failed to write {}: {e}", path.display()))
+    }
+}
+
+/// Whether the file at `path` exists and was modified within `max_age`.
+#[must_use]
+pub fn is_fresh(path: &Path, max_age: Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < max_age)
+}
+
+/// A non-empty environment variable, or an error naming it.
+fn required_env(name: &str) -> Result<String, String> {
+    std::env::var(name)
+        .ok()
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| format!("{name} must be set to fetch the Shopify schema"))
+}
+
+/// Run the introspection query and return the pretty-printed `data` object.
+///
+/// The token header is passed to curl on stdin so it doesn't show up in the
+/// process list.
+fn fetch(url: &str, token_header: &str, token: &str) -> Result<String, String> {
+    let body = serde_json::json!({ "query": INTROSPECTION_QUERY }).to_string();
+
+    let mut child = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--request",
+            "POST",
+            url,
+        ])
+        .args(["--header", "Content-Type: application/json"])
+        .args(["--header", "@-"])
+        .args(["--data-binary", &body])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not run curl: {e}"))?;
+
+    // Dropping stdin closes it, so curl stops reading headers
+    let written = child
+        .stdin
+        .take()
+        .map(|mut stdin| writeln!(stdin, "{token_header}: {token}"));
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("could not run curl: {e}"))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    if let Some(Err(e)) = written {
+        return Err(format!("could not pass the token to curl: {e}"));
+    }
+
+    let mut response: serde_json::Value =
+        serde_json::from_slice(&output.stdout).map_err(|e| format!("invalid JSON: {e}"))?;
+    if let Some(errors) = response.get("errors") {
+        return Err(format!("GraphQL errors: {errors}"));
+    }
+    let data = response
+        .get_mut("data")
+        .map(serde_json::Value::take)
+        .filter(|data| data.get("__schema").is_some())
+        .ok_or("response has no schema")?;
+
+    let mut schema = serde_json::to_string_pretty(&data).map_err(|e| e.to_string())?;
+    schema.push('\n');
+    Ok(schema)
+}
@@ -23,7 +23,7 @@
  *
  * Output:
  *   - crates/storefront/static/images/derived/ (optimized images)
- *   - crates/storefront/src/image_manifest.rs (Rust manifest for hash lookups)
+ *   - crates/storefront/src/image_manifest.rs (Rust manifest for hash and BlurHash lookups)
  */
 
 import { readFile, writeFile, mkdir, copyFile, readdir, stat } from "node:fs/promises";
@@ -56,6 +56,10 @@ const QUALITY = {
   jpeg: 85,
 };
 
+// BlurHash components (x, y) and the width the image is shrunk to before encoding
+const BLURHASH_COMPONENTS = [4, 3];
+const BLURHASH_SAMPLE_WIDTH = 32;
+
 // Raster image extensions to process (resize + convert to multiple formats)
 const RASTER_EXTENSIONS = new Set([".jpg", ".jpeg", ".webp"]);
 
@@ -325,9 +329,101 @@ async function getAllImages(dir) {
   return images;
 }
 
+const BASE83_CHARS =
+  "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+
+function encodeBase83(value, length) {
+  let result = "";
+  for (let i = 1; i <= length; i++) {
+    result += BASE83_CHARS[Math.floor(value / 83 ** (length - i)) % 83];
+  }
+  return result;
+}
+
+function sRgbToLinear(value) {
+  const v = value / 255;
+  return v <= 0.04045 ? v / 12.92 : ((v + 0.055) / 1.055) ** 2.4;
+}
+
+function linearToSRgb(value) {
+  const v = Math.max(0, Math.min(1, value));
+  return v <= 0.0031308
+    ? Math.trunc(v * 12.92 * 255 + 0.5)
+    : Math.trunc((1.055 * v ** (1 / 2.4) - 0.055) * 255 + 0.5);
+}
+
+function signPow(value, exp) {
+  return Math.sign(value) * Math.abs(value) ** exp;
+}
+
+/**
+ * Encode RGBA pixels as a BlurHash string (https://blurha.sh)
+ */
+function encodeBlurhash(pixels, width, height, componentsX, componentsY) {
+  const factors = [];
+  for (let y = 0; y < componentsY; y++) {
+    for (let x = 0; x < componentsX; x++) {
+      const normalisation = x === 0 && y === 0 ? 1 : 2;
+      let r = 0;
+      let g = 0;
+      let b = 0;
+      for (let j = 0; j < height; j++) {
+        for (let i = 0; i < width; i++) {
+          const basis =
+            normalisation * Math.cos((Math.PI * x * i) / width) * Math.cos((Math.PI * y * j) / height);
+          const offset = 4 * (i + j * width);
+          r += basis * sRgbToLinear(pixels[offset]);
+          g += basis * sRgbToLinear(pixels[offset + 1]);
+          b += basis * sRgbToLinear(pixels[offset + 2]);
+        }
+      }
+      const scale = 1 / (width * height);
+      factors.push([r * scale, g * scale, b * scale]);
+    }
+  }
+
+  const [dc, ...ac] = factors;
+  let hash = encodeBase83(componentsX - 1 + (componentsY - 1) * 9, 1);
+
+  let maximumValue = 1;
+  if (ac.length > 0) {
+    const actualMax = Math.max(...ac.flat().map(Math.abs));
+    const quantisedMax = Math.max(0, Math.min(82, Math.floor(actualMax * 166 - 0.5)));
+    maximumValue = (quantisedMax + 1) / 166;
+    hash += encodeBase83(quantisedMax, 1);
+  } else {
+    hash += encodeBase83(0, 1);
+  }
+
+  const [dcR, dcG, dcB] = dc.map(linearToSRgb);
+  hash += encodeBase83((dcR << 16) + (dcG << 8) + dcB, 4);
+
+  for (const factor of ac) {
+    const [r, g, b] = factor.map((value) =>
+      Math.max(0, Math.min(18, Math.floor(signPow(value / maximumValue, 0.5) * 9 + 9.5)))
+    );
+    hash += encodeBase83(r * 19 * 19 + g * 19 + b, 2);
+  }
+
+  return hash;
+}
+
+/**
+ * Compute the BlurHash placeholder for a raster image
+ */
+async function getBlurhash(inputPath) {
+  const { data, info } = await sharp(inputPath)
+    .resize(BLURHASH_SAMPLE_WIDTH, null, { fit: "inside" })
+    .ensureAlpha()
+    .raw()
+    .toBuffer({ resolveWithObject: true });
+  const [componentsX, componentsY] = BLURHASH_COMPONENTS;
+  return encodeBlurhash(data, info.width, info.height, componentsX, componentsY);
+}
+
 /**
  * Process a single raster image into multiple sizes and formats with hashed filenames
- * Returns { files: string[], maxWidth: number }
+ * Returns { files: string[], maxWidth: number, blurhash: string }
  */
 async function processRasterImage(inputPath, outputDir, relativePath, hash) {
   const ext = extname(relativePath).toLowerCase();
@@ -341,7 +437,7 @@ async function processRasterImage(inputPath, outputDir, relativePath, hash) {
     metadata = await image.metadata();
   } catch (err) {
     console.log(`      ⚠️  Skipping (unsupported format): ${err.message}`);
-    return { files: [], maxWidth: 0 };
+    return { files: [], maxWidth: 0, blurhash: "" };
   }
   const originalWidth = metadata.width || 0;
 
@@ -388,7 +484,9 @@ async function processRasterImage(inputPath, outputDir, relativePath, hash) {
     files.push(jpegPath);
   }
 
-  return { files, maxWidth: maxGeneratedWidth };
+  const blurhash = await getBlurhash(inputPath);
+
+  return { files, maxWidth: maxGeneratedWidth, blurhash };
 }
 
 /**
@@ -421,6 +519,19 @@ async function loadExistingManifest() {
       manifest[path] = { hash, maxWidth: parseInt(maxWidth, 10) };
     }
 
+    // BlurHash entries: ("path", "blurhash"),
+    const blurhashStart = content.indexOf("IMAGE_BLURHASH");
+    if (blurhashStart !== -1) {
+      const blurhashRegex = /\(\s*"([^"]+)",\s*"([^"]+)",?\s*\)/g;
+      blurhashRegex.lastIndex = blurhashStart;
+      while ((match = blurhashRegex.exec(content)) !== null) {
+        const [, path, blurhash] = match;
+        if (manifest[path]) {
+          manifest[path].blurhash = blurhash;
+        }
+      }
+    }
+
     // Warn if manifest file exists but no entries were parsed
     const entryCount = Object.keys(manifest).length;
     if (entryCount === 0 && content.includes("HashMap::from")) {
@@ -442,7 +553,7 @@ async function loadExistingManifest() {
 }
 
 /**
- * Generate Rust manifest file with image hashes and max widths
+ * Generate Rust manifest file with image hashes, max widths, and BlurHashes
  *
  * Generates the manifest and runs rustfmt for proper formatting.
  */
@@ -452,6 +563,12 @@ async function generateRustManifest(manifest) {
     .map(([path, { hash, maxWidth }]) => `        ("${path}", ("${hash}", ${maxWidth})),`)
     .join("\n");
 
+  const blurhashEntries = Object.entries(manifest)
+    .filter(([, { blurhash }]) => blurhash)
+    .sort(([a], [b]) => a.localeCompare(b))
+    .map(([path, { blurhash }]) => `        ("${path}", "${blurhash}"),`)
+    .join("\n");
+
   // Note: imports must be in alphabetical order for rustfmt
   const rustCode = `//! Auto-generated image manifest for content-based hashing.
 //!
@@ -475,6 +592,15 @@ ${entries}
     ])
 });
 
+/// Maps image base paths to their \`BlurHash\` placeholders.
+///
+/// Only raster images have an entry.
+pub static IMAGE_BLURHASH: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+    HashMap::from([
+${blurhashEntries}
+    ])
+});
+
 /// Look up the content hash for an image path.
 ///
 /// Returns the hash if found, or an empty string if not found.
@@ -491,6 +617,34 @@ pub fn get_image_hash(base_path: &str) -> &'static str {
 pub fn get_image_max_width(base_path: &str) -> u32 {
     IMAGE_INFO.get(base_path).map_or(0, |(_, width)| *width)
 }
+
+/// Look up the \`BlurHash\` placeholder for an image path.
+///
+/// Returns the \`BlurHash\` string if found, or an empty string if not found.
+#[must_use]
+pub fn get_image_blurhash(base_path: &str) -> &'static str {
+    IMAGE_BLURHASH.get(base_path).map_or("", |blurhash| *blurhash)
+}
+
+/// Widths to list in a \`srcset\` for an image, out of the requested \`widths\`.
+///
+/// Keeps the requested widths that were generated (no wider than the image's
+/// max width) and adds the max width itself when it isn't one of them, so
+/// the largest available size is always offered. Returns every requested
+/// width for SVGs and unknown images.
+#[must_use]
+pub fn get_srcset_widths(base_path: &str, widths: &[u32]) -> Vec<u32> {
+    let max_width = get_image_max_width(base_path);
+    if max_width == 0 {
+        return widths.to_vec();
+    }
+
+    let mut available: Vec<u32> = widths.iter().copied().filter(|&w| w <= max_width).collect();
+    if !available.contains(&max_width) {
+        available.push(max_width);
+    }
+    available
+}
 `;
 
   await writeFile(MANIFEST_PATH, rustCode, "utf-8");
@@ -597,8 +751,12 @@ async function optimize() {
       // Hash matches - check if derived files actually exist
       const filesExist = await derivedFilesExist(imagePath, hash);
       if (filesExist) {
-        // Reuse existing manifest entry
+        // Reuse existing manifest entry, backfilling the BlurHash for raster
+        // images processed before placeholders were generated
         manifest[basePath] = existingEntry;
+        if (!existingEntry.blurhash && existingEntry.maxWidth > 0) {
+          existingEntry.blurhash = await getBlurhash(inputPath);
+        }
         cachedCount++;
         continue;
       }
@@ -621,12 +779,12 @@ async function optimize() {
       } else if (RASTER_EXTENSIONS.has(ext) || ext === PNG_EXTENSION) {
         // Process raster image with hash
         console.log(`   🖼️  Processing: ${imagePath} [${hash}]`);
-        const { files, maxWidth } = await processRasterImage(inputPath, DERIVED_DIR, imagePath, hash);
+        const { files, maxWidth, blurhash } = await processRasterImage(inputPath, DERIVED_DIR, imagePath, hash);
         if (files.length === 0) {
           skippedCount++;
           continue;
         }
-        manifest[basePath] = { hash, maxWidth };
+        manifest[basePath] = { hash, maxWidth, blurhash };
         console.log(`      Generated ${files.length} variants (max: ${maxWidth}px)`);
         totalVariants += files.length;
       } else {
@@ -699,12 +857,12 @@ async function optimizeSingle(imagePath) {
       console.log(`  Generated ${results.length} file(s)`);
     } else if (RASTER_EXTENSIONS.has(ext) || ext === PNG_EXTENSION) {
       console.log(`🖼️  Processing: ${imagePath} [${hash}]`);
-      const { files, maxWidth } = await processRasterImage(inputPath, DERIVED_DIR, imagePath, hash);
+      const { files, maxWidth, blurhash } = await processRasterImage(inputPath, DERIVED_DIR, imagePath, hash);
       if (files.length === 0) {
         console.error(`❌ Failed to process image`);
         process.exit(1);
       }
-      manifest[basePath] = { hash, maxWidth };
+      manifest[basePath] = { hash, maxWidth, blurhash };
       console.log(`   Generated ${files.length} variants (max: ${maxWidth}px)`);
     } else {
       console.error(`❌ Unknown image type: ${ext}`);